@@ -0,0 +1,230 @@
+//! Turning terminal output into styled text for the Terminal tab.
+//!
+//! Colours and bold come from SGR escape sequences (`ESC [ ... m`);
+//! cursor movement and other control sequences are dropped, and a
+//! carriage return starts its line over, as a progress bar expects.
+
+/// A run of text in one style.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    /// CSS colours, e.g. `#cd3131`.
+    pub fg: Option<String>,
+    pub bg: Option<String>,
+    pub bold: bool,
+    pub underline: bool,
+}
+
+impl Style {
+    /// Inline CSS for a span in this style.
+    pub fn css(&self) -> String {
+        let mut css = String::new();
+        if let Some(fg) = &self.fg {
+            css.push_str(&format!("color:{};", fg));
+        }
+        if let Some(bg) = &self.bg {
+            css.push_str(&format!("background-color:{};", bg));
+        }
+        if self.bold {
+            css.push_str("font-weight:bold;");
+        }
+        if self.underline {
+            css.push_str("text-decoration:underline;");
+        }
+        css
+    }
+}
+
+/// The 16 standard colours, normal then bright.
+const PALETTE: [&str; 16] = [
+    "#000000", "#cd3131", "#0dbc79", "#e5e510", "#2472c8", "#bc3fbc", "#11a8cd", "#e5e5e5",
+    "#666666", "#f14c4c", "#23d18b", "#f5f543", "#3b8eea", "#d670d6", "#29b8db", "#ffffff",
+];
+
+/// A colour from the 256-colour table.
+fn indexed(n: u16) -> String {
+    match n {
+        0..=15 => PALETTE[n as usize].to_string(),
+        16..=231 => {
+            let n = n - 16;
+            let level = |v: u16| if v == 0 { 0 } else { 55 + v * 40 };
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                level(n / 36),
+                level(n / 6 % 6),
+                level(n % 6)
+            )
+        }
+        _ => {
+            let grey = 8 + (n.min(255) - 232) * 10;
+            format!("#{:02x}{:02x}{:02x}", grey, grey, grey)
+        }
+    }
+}
+
+/// Apply the parameters of one SGR sequence to `style`.
+fn apply(style: &mut Style, params: &str) {
+    let codes: Vec<u16> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+    let mut i = 0;
+    while i < codes.len() {
+        match codes[i] {
+            0 => *style = Style::default(),
+            1 => style.bold = true,
+            4 => style.underline = true,
+            22 => style.bold = false,
+            24 => style.underline = false,
+            c @ 30..=37 => style.fg = Some(PALETTE[(c - 30) as usize].to_string()),
+            c @ 40..=47 => style.bg = Some(PALETTE[(c - 40) as usize].to_string()),
+            c @ 90..=97 => style.fg = Some(PALETTE[(c - 90 + 8) as usize].to_string()),
+            c @ 100..=107 => style.bg = Some(PALETTE[(c - 100 + 8) as usize].to_string()),
+            39 => style.fg = None,
+            49 => style.bg = None,
+            c @ (38 | 48) => {
+                let colour = match codes.get(i + 1) {
+                    Some(5) => {
+                        let colour = codes.get(i + 2).map(|&n| indexed(n));
+                        i += 2;
+                        colour
+                    }
+                    Some(2) => {
+                        let rgb = codes.get(i + 2..i + 5);
+                        i += 4;
+                        rgb.map(|c| format!("#{:02x}{:02x}{:02x}", c[0], c[1], c[2]))
+                    }
+                    _ => None,
+                };
+                if c == 38 {
+                    style.fg = colour;
+                } else {
+                    style.bg = colour;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+/// One line of output being built up, with a carriage return able to
+/// send writing back to its start.
+#[derive(Default)]
+struct Line {
+    cells: Vec<(char, Style)>,
+    cursor: usize,
+}
+
+impl Line {
+    fn put(&mut self, c: char, style: &Style) {
+        if self.cursor < self.cells.len() {
+            self.cells[self.cursor] = (c, style.clone());
+        } else {
+            self.cells.push((c, style.clone()));
+        }
+        self.cursor += 1;
+    }
+
+    fn into_spans(self, spans: &mut Vec<Span>) {
+        for (c, style) in self.cells {
+            match spans.last_mut() {
+                Some(span) if span.style == style => span.text.push(c),
+                _ => spans.push(Span {
+                    text: c.to_string(),
+                    style,
+                }),
+            }
+        }
+    }
+}
+
+/// `output` as styled spans, with lines ending in `\n`.
+pub fn render(output: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut style = Style::default();
+    let mut line = Line::default();
+    let mut chars = output.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                // CSI: parameters, then a final byte from @ to ~
+                Some('[') => {
+                    let mut params = String::new();
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            if c == 'm' {
+                                apply(&mut style, &params);
+                            }
+                            break;
+                        }
+                        params.push(c);
+                    }
+                }
+                // OSC (e.g. a window title): up to BEL or ESC \
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            '\n' => {
+                line.cursor = line.cells.len();
+                line.put('\n', &Style::default());
+                std::mem::take(&mut line).into_spans(&mut spans);
+            }
+            '\r' => line.cursor = 0,
+            '\x08' => line.cursor = line.cursor.saturating_sub(1),
+            '\t' => line.put('\t', &style),
+            c if c.is_control() => {}
+            c => line.put(c, &style),
+        }
+    }
+    line.into_spans(&mut spans);
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(spans: &[Span]) -> String {
+        spans.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_colours_and_reset() {
+        let spans = render("plain \x1b[1;31mred\x1b[0m done");
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[1].text, "red");
+        assert_eq!(spans[1].style.fg.as_deref(), Some("#cd3131"));
+        assert!(spans[1].style.bold);
+        assert_eq!(spans[2].style, Style::default());
+        assert_eq!(spans[1].style.css(), "color:#cd3131;font-weight:bold;");
+    }
+
+    #[test]
+    fn test_extended_colours() {
+        let spans = render("\x1b[38;5;196ma\x1b[48;2;1;2;3mb");
+        assert_eq!(spans[0].style.fg.as_deref(), Some("#ff0000"));
+        assert_eq!(spans[1].style.bg.as_deref(), Some("#010203"));
+        assert_eq!(indexed(244), "#808080");
+    }
+
+    #[test]
+    fn test_control_sequences_are_dropped() {
+        let output = "\x1b]0;title\x07\x1b[2J\x1b[Hready\r\n";
+        assert_eq!(text(&render(output)), "ready\n");
+    }
+
+    #[test]
+    fn test_carriage_return_overwrites_line() {
+        assert_eq!(text(&render("10%\r50%\r100%\nnext")), "100%\nnext");
+        assert_eq!(text(&render("abc\x08d")), "abd");
+    }
+}
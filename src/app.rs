@@ -1,9 +1,13 @@
 use crate::components::{
-    ConfigViewer, Explorer, Navbar, ServerConsole, ServerList, Sidebar, ToastContainer,
+    CommandPalette, ConfigViewer, Explorer, Navbar, PaletteCommand, RecentTools, ServerConsole,
+    ServerList, Sidebar, ToastContainer,
 };
-use crate::models::{CreateServerArgs, McpServer};
-use crate::state::{use_app_state, APP_STATE};
+use crate::dashboard::DashboardWidget;
+use crate::models::{CreateServerArgs, McpServer, NotificationLevel};
+use crate::recent_tools::RecentTool;
+use crate::state::{use_app_state, AppState, APP_STATE};
 use dioxus::prelude::*;
+use std::rc::Rc;
 
 pub fn App() -> Element {
     use_app_state();
@@ -11,14 +15,138 @@ pub fn App() -> Element {
     let mut show_explorer = use_signal(|| false);
     let mut show_console = use_signal(|| None::<McpServer>);
     let mut show_settings = use_signal(|| None::<Option<McpServer>>); // None=Closed, Some(None)=Add, Some(Some(s))=Edit
+    // Settings field a guided fix sent the user to, by server id
+    let mut settings_focus = use_signal(|| None::<(String, crate::fixes::SettingsField)>);
     let mut show_config = use_signal(|| false);
+    let mut show_update = use_signal(|| false);
+    let mut show_compare = use_signal(|| false);
+    let mut show_rotate_env = use_signal(|| false);
+    let mut show_duplicates = use_signal(|| false);
+    let mut bundle_path = use_signal(|| None::<std::path::PathBuf>);
+    // Report left behind by a panic in the previous run
+    let mut crash_report = use_signal(crate::crash::take_pending_crash);
     let mut active_tab = use_signal(|| "dashboard".to_string());
+    // Console tab to reopen when restoring a session
+    let mut console_tab = use_signal(|| None::<String>);
+    // Recent tool to open in the console, and a counter to remount it per launch
+    let mut launch_tool = use_signal(|| None::<RecentTool>);
+    let mut console_launches = use_signal(|| 0u32);
+    let mut show_palette = use_signal(|| false);
+    let mut show_pair = use_signal(|| false);
+    // Server grid narrowed to running servers from the dashboard widget
+    let mut running_only = use_signal(|| false);
+    let mut scroll_container = use_signal(|| None::<Rc<MountedData>>);
+
+    // Keep the saved session in step with the UI
+    use_effect(move || {
+        let tab = active_tab();
+        let console = show_console.read().as_ref().map(|s| s.id.clone());
+        AppState::update_session(move |s| {
+            s.active_tab = tab;
+            if console.is_none() {
+                s.console_tab = None;
+            }
+            s.console_server = console;
+        });
+    });
+
+    // Apply a session loaded at startup once the state is ready
+    use_effect(move || {
+        let Some(session) = APP_STATE.read().pending_restore.cloned() else {
+            return;
+        };
+        APP_STATE.write().pending_restore.set(None);
+
+        if !session.active_tab.is_empty() {
+            active_tab.set(session.active_tab.clone());
+        }
+        let servers = APP_STATE.read().servers.cloned();
+        if let Some(server) = session
+            .console_server
+            .as_ref()
+            .and_then(|id| servers.into_iter().find(|s| &s.id == id))
+        {
+            console_tab.set(session.console_tab.clone());
+            show_console.set(Some(server));
+        }
+        let y = session.dashboard_scroll;
+        spawn(async move {
+            // Give the list a moment to render before scrolling
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            if let Some(el) = scroll_container() {
+                let _ = el
+                    .scroll(
+                        dioxus::html::geometry::PixelsVector2D::new(0.0, y),
+                        ScrollBehavior::Instant,
+                    )
+                    .await;
+            }
+        });
+    });
+
+    // Apply accessibility preferences through the theme's root classes
+    use_effect(move || {
+        let appearance = *APP_STATE.read().appearance.read();
+        let _ = document::eval(&appearance.apply_js());
+    });
+
+    // Ctrl/Cmd+K opens the quick-launch palette from anywhere
+    use_hook(move || {
+        spawn(async move {
+            let mut eval = document::eval(
+                r#"document.addEventListener("keydown", (e) => {
+                       if ((e.ctrlKey || e.metaKey) && e.key.toLowerCase() === "k") {
+                           e.preventDefault();
+                           dioxus.send(true);
+                       }
+                   });"#,
+            );
+            while eval.recv::<bool>().await.is_ok() {
+                show_palette.set(true);
+            }
+        });
+    });
 
     let open_console = move |server: McpServer| {
+        console_tab.set(None);
+        launch_tool.set(None);
         show_console.set(Some(server));
     };
 
+    // Open a recently used tool, starting its server first if needed
+    let launch_recent_tool = move |recent: RecentTool| {
+        show_palette.set(false);
+        let server = APP_STATE
+            .read()
+            .servers
+            .read()
+            .iter()
+            .find(|s| s.id == recent.server_id)
+            .cloned();
+        let Some(server) = server else {
+            AppState::push_notification(
+                format!("{} is no longer installed", recent.server_name),
+                NotificationLevel::Warning,
+            );
+            return;
+        };
+        spawn(async move {
+            if let Err(e) = AppState::start_server_process(server.clone()).await {
+                AppState::push_notification(
+                    format!("Failed to start {}: {}", server.name, e),
+                    NotificationLevel::Error,
+                );
+                return;
+            }
+            console_tab.set(None);
+            launch_tool.set(Some(recent));
+            console_launches.with_mut(|n| *n += 1);
+            show_console.set(Some(server));
+        });
+    };
+
     let edit_server = move |server: McpServer| {
+        settings_focus.set(None);
         show_settings.set(Some(Some(server)));
     };
 
@@ -30,26 +158,27 @@ pub fn App() -> Element {
     };
 
     let save_server = move |args: CreateServerArgs| {
+        spawn(async move {
+            let name = args.name.clone();
+            if let Err(e) = crate::state::AppState::add_server(args).await {
+                crate::state::AppState::push_notification(
+                    format!("Could not save {}: {}", name, e),
+                    crate::models::NotificationLevel::Error,
+                );
+            }
+        });
+        show_settings.set(None);
+    };
+
+    let update_server = move |args: crate::models::UpdateServerArgs| {
         if let Some(Some(srv)) = show_settings() {
-            // Update
-            let id = srv.id.clone();
-            spawn(async move {
-                let update_args = crate::models::UpdateServerArgs {
-                    name: Some(args.name),
-                    server_type: Some(args.server_type),
-                    command: args.command, // Already Option
-                    args: args.args,       // Already Option
-                    env: args.env,         // Already Option
-                    url: args.url,
-                    description: args.description,
-                    is_active: None,
-                };
-                let _ = crate::state::AppState::update_server(id, update_args).await;
-            });
-        } else {
-            // Create
             spawn(async move {
-                let _ = crate::state::AppState::add_server(args).await;
+                if let Err(e) = crate::state::AppState::update_server(srv.id.clone(), args).await {
+                    crate::state::AppState::push_notification(
+                        format!("Could not save {}: {}", srv.name, e),
+                        crate::models::NotificationLevel::Error,
+                    );
+                }
             });
         }
         show_settings.set(None);
@@ -79,7 +208,8 @@ pub fn App() -> Element {
 
             Sidebar {
                 active_tab: active_tab(),
-                on_tab_change: move |tab| active_tab.set(tab)
+                on_tab_change: move |tab| active_tab.set(tab),
+                on_show_update: move |_| show_update.set(true)
             }
 
             main {
@@ -87,21 +217,77 @@ pub fn App() -> Element {
 
                 Navbar {
                     on_add_server: move |_| show_settings.set(Some(None)),
+                    on_pair_server: move |_| show_pair.set(true),
                     on_registry: move |_| show_explorer.set(true),
+                    on_compare: move |_| show_compare.set(true),
+                    on_command_palette: move |_| show_palette.set(true),
                     on_export: move |_| show_config.set(true),
+                    on_open_console: open_console,
+                    on_install_bundle: move |path| bundle_path.set(Some(path)),
+                    on_switch_workspace: move |_| {
+                        show_console.set(None);
+                        show_settings.set(None);
+                    },
                 }
 
                 div {
                     class: "flex-1 overflow-y-auto p-8 scroll-smooth z-0 custom-scrollbar",
+                    onmounted: move |evt| scroll_container.set(Some(evt.data())),
+                    onscroll: move |evt| {
+                        let top = evt.data().scroll_top();
+                        AppState::update_session(|s| s.dashboard_scroll = top);
+                    },
                     match active_tab().as_str() {
                         "research" => rsx! {
                             crate::components::Research {}
                         },
+                        "graph" => rsx! {
+                            crate::components::DependencyGraph { on_open_console: open_console }
+                        },
+                        "hub" => rsx! {
+                            crate::components::HubPanel {}
+                        },
+                        "projects" => rsx! {
+                            crate::components::ProjectProfiles {}
+                        },
+                        "whats_new" => rsx! {
+                            crate::components::WhatsNew {}
+                        },
+                        "settings_tab" => rsx! {
+                            crate::components::LanguageSettings {}
+                            crate::components::AccessibilitySettings {}
+                            crate::components::StorageSettings {}
+                            crate::components::SamplingSettings {}
+                            crate::components::RootsSettings {}
+                            crate::components::UsageExport {}
+                        },
                         _ => rsx! {
+                            crate::components::DashboardWidgets {
+                                on_select: move |widget| match widget {
+                                    DashboardWidget::TotalServers => running_only.set(false),
+                                    DashboardWidget::Running => running_only.set(true),
+                                    DashboardWidget::HubConnections
+                                    | DashboardWidget::CallsToday
+                                    | DashboardWidget::RecentErrors => active_tab.set("hub".to_string()),
+                                }
+                            }
+                            RecentTools { on_launch_tool: launch_recent_tool }
+                            if running_only() {
+                                div { class: "flex items-center gap-3 mb-3 text-xs text-zinc-500",
+                                    "Showing running servers"
+                                    button {
+                                        class: "text-indigo-400 hover:text-indigo-300",
+                                        onclick: move |_| running_only.set(false),
+                                        "Show all"
+                                    }
+                                }
+                            }
                             ServerList {
                                 on_open_console: open_console,
-                                on_edit_server: edit_server
+                                on_edit_server: edit_server,
+                                running_only: running_only()
                             }
+                            crate::components::ArchivedServers {}
                         }
                     }
                 }
@@ -117,20 +303,102 @@ pub fn App() -> Element {
 
             if let Some(opts) = show_settings() {
                 crate::components::Settings {
+                    focus: settings_focus()
+                        .filter(|(id, _)| opts.as_ref().is_some_and(|s| &s.id == id))
+                        .map(|(_, field)| field),
                     server: opts,
                     on_close: move |_| show_settings.set(None),
                     on_save: save_server,
+                    on_update: update_server,
                     on_delete: delete_server_handler
                 }
             }
 
             if let Some(srv) = show_console() {
                 ServerConsole {
+                    key: "{srv.id}-{console_launches}",
                     server: srv,
+                    initial_tab: console_tab(),
+                    launch_tool: launch_tool(),
                     on_close: move |_| show_console.set(None)
                 }
             }
 
+            if let Some(report) = crash_report() {
+                crate::components::CrashDialog {
+                    report,
+                    on_close: move |_| crash_report.set(None)
+                }
+            }
+
+            if show_palette() {
+                CommandPalette {
+                    on_launch_tool: launch_recent_tool,
+                    on_command: move |command| {
+                        show_palette.set(false);
+                        match command {
+                            PaletteCommand::AddServer => show_settings.set(Some(None)),
+                            PaletteCommand::PairServer => show_pair.set(true),
+                            PaletteCommand::OpenRegistry => show_explorer.set(true),
+                            PaletteCommand::CompareServers => show_compare.set(true),
+                            PaletteCommand::RotateEnv => show_rotate_env.set(true),
+                            PaletteCommand::FindDuplicates => show_duplicates.set(true),
+                            PaletteCommand::ExportConfig => show_config.set(true),
+                        }
+                    },
+                    on_close: move |_| show_palette.set(false)
+                }
+            }
+
+            if show_pair() {
+                crate::components::PairServer {
+                    on_close: move |_| show_pair.set(false)
+                }
+            }
+
+            if show_rotate_env() {
+                crate::components::EnvRotation {
+                    on_close: move |_| show_rotate_env.set(false)
+                }
+            }
+
+            if show_duplicates() {
+                crate::components::DuplicateServers {
+                    on_close: move |_| show_duplicates.set(false)
+                }
+            }
+
+            if show_compare() {
+                crate::components::ServerDiff {
+                    on_close: move |_| show_compare.set(false)
+                }
+            }
+
+            if let Some(path) = bundle_path() {
+                crate::components::BundleInstaller {
+                    path,
+                    on_close: move |_| bundle_path.set(None)
+                }
+            }
+
+            crate::components::SamplingApproval {}
+            crate::components::ElicitationForm {}
+
+            if show_settings().is_none() {
+                crate::components::FixItDialog {
+                    on_open_settings: move |(server, field): (McpServer, crate::fixes::SettingsField)| {
+                        settings_focus.set(Some((server.id.clone(), field)));
+                        show_settings.set(Some(Some(server)));
+                    }
+                }
+            }
+
+            if show_update() {
+                crate::components::UpdateDialog {
+                    on_close: move |_| show_update.set(false)
+                }
+            }
+
             if show_config() {
                 ConfigViewer {
                     servers: APP_STATE.read().servers.read().clone(),
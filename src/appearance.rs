@@ -0,0 +1,57 @@
+//! Accessibility preferences applied through the theme: a high-contrast
+//! palette and turning off animations. Each toggles a class on the
+//! document root, and `style.css` restyles the app from there.
+//!
+//! Reduced motion also follows the system `prefers-reduced-motion`
+//! setting where the webview exposes it, whatever is saved here.
+
+use serde::{Deserialize, Serialize};
+
+pub const HIGH_CONTRAST_CLASS: &str = "high-contrast";
+pub const REDUCE_MOTION_CLASS: &str = "reduce-motion";
+
+/// Detects the system reduced-motion preference; evaluates to `false`
+/// where the webview doesn't support the media query.
+pub const SYSTEM_REDUCED_MOTION_JS: &str = r#"return !!window.matchMedia && window.matchMedia("(prefers-reduced-motion: reduce)").matches;"#;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct AppearanceSettings {
+    pub high_contrast: bool,
+    pub reduce_motion: bool,
+}
+
+impl AppearanceSettings {
+    /// Script that applies the settings to the document root.
+    pub fn apply_js(&self) -> String {
+        format!(
+            "document.documentElement.classList.toggle('{}', {}); \
+             document.documentElement.classList.toggle('{}', {});",
+            HIGH_CONTRAST_CLASS, self.high_contrast, REDUCE_MOTION_CLASS, self.reduce_motion
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_js() {
+        let settings = AppearanceSettings {
+            high_contrast: true,
+            reduce_motion: false,
+        };
+        let js = settings.apply_js();
+        assert!(js.contains("toggle('high-contrast', true)"));
+        assert!(js.contains("toggle('reduce-motion', false)"));
+    }
+
+    #[test]
+    fn test_missing_fields_default_off() {
+        let settings: AppearanceSettings =
+            serde_json::from_str(r#"{"high_contrast":true}"#).unwrap();
+        assert!(settings.high_contrast);
+        assert!(!settings.reduce_motion);
+    }
+}
@@ -0,0 +1,268 @@
+//! Minimal ZIP reading and writing, enough for `.mcpb` / `.dxt` bundles.
+//! Supports stored and deflated entries; ZIP64 and encryption are not.
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+use std::io::{Read, Write};
+
+const LOCAL_HEADER_SIG: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIG: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIR_SIG: u32 = 0x0605_4b50;
+const END_OF_CENTRAL_DIR_LEN: usize = 22;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATE: u16 = 8;
+
+/// Most a bundle may unpack to, so a crafted archive can't fill memory.
+const MAX_UNPACKED_SIZE: u64 = 512 * 1024 * 1024;
+
+/// "Version made by": Unix host, spec 2.0. Lets readers honour file modes.
+const MADE_BY_UNIX: u16 = (3 << 8) | 20;
+/// DOS date for 1980-01-01, so archives are reproducible.
+const DOS_EPOCH_DATE: u16 = (1 << 5) | 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZipEntry {
+    /// Path inside the archive, always with `/` separators.
+    pub name: String,
+    pub data: Vec<u8>,
+    /// Unix permission bits, when the archive recorded them.
+    pub mode: Option<u32>,
+}
+
+fn u16_at(buf: &[u8], pos: usize) -> Result<u16, String> {
+    buf.get(pos..pos + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| "Truncated archive".to_string())
+}
+
+fn u32_at(buf: &[u8], pos: usize) -> Result<u32, String> {
+    buf.get(pos..pos + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| "Truncated archive".to_string())
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = Crc::new();
+    crc.update(data);
+    crc.sum()
+}
+
+/// All file entries of a ZIP archive. Directory entries are skipped.
+pub fn read(buf: &[u8]) -> Result<Vec<ZipEntry>, String> {
+    read_limited(buf, MAX_UNPACKED_SIZE)
+}
+
+/// [`read`], failing once the entries would unpack to more than `limit`
+/// bytes. Sizes in the headers are untrusted, so each entry is inflated
+/// no further than its declared size.
+fn read_limited(buf: &[u8], limit: u64) -> Result<Vec<ZipEntry>, String> {
+    if buf.len() < END_OF_CENTRAL_DIR_LEN {
+        return Err("Not a ZIP archive".to_string());
+    }
+    // The end record sits at the end, followed by an optional comment
+    let search_from = buf
+        .len()
+        .saturating_sub(END_OF_CENTRAL_DIR_LEN + u16::MAX as usize);
+    let eocd = (search_from..=buf.len() - END_OF_CENTRAL_DIR_LEN)
+        .rev()
+        .find(|&pos| u32_at(buf, pos) == Ok(END_OF_CENTRAL_DIR_SIG))
+        .ok_or("Not a ZIP archive")?;
+
+    let count = u16_at(buf, eocd + 10)? as usize;
+    let mut pos = u32_at(buf, eocd + 16)? as usize;
+    let mut entries = Vec::new();
+    let mut unpacked = 0u64;
+
+    for _ in 0..count {
+        if u32_at(buf, pos)? != CENTRAL_HEADER_SIG {
+            return Err("Corrupt central directory".to_string());
+        }
+        let made_by = u16_at(buf, pos + 4)?;
+        let flags = u16_at(buf, pos + 8)?;
+        let method = u16_at(buf, pos + 10)?;
+        let crc = u32_at(buf, pos + 16)?;
+        let compressed_size = u32_at(buf, pos + 20)? as usize;
+        let size = u32_at(buf, pos + 24)? as usize;
+        let name_len = u16_at(buf, pos + 28)? as usize;
+        let extra_len = u16_at(buf, pos + 30)? as usize;
+        let comment_len = u16_at(buf, pos + 32)? as usize;
+        let external_attrs = u32_at(buf, pos + 38)?;
+        let local_offset = u32_at(buf, pos + 42)? as usize;
+        let name_bytes = buf
+            .get(pos + 46..pos + 46 + name_len)
+            .ok_or("Truncated archive")?;
+        let name = String::from_utf8_lossy(name_bytes).replace('\\', "/");
+        pos += 46 + name_len + extra_len + comment_len;
+
+        if flags & 1 != 0 {
+            return Err(format!("{} is encrypted", name));
+        }
+        if compressed_size == u32::MAX as usize || size == u32::MAX as usize {
+            return Err("ZIP64 archives are not supported".to_string());
+        }
+        if name.ends_with('/') {
+            continue;
+        }
+        unpacked += size as u64;
+        if unpacked > limit {
+            return Err(format!(
+                "Archive unpacks to more than {} MB",
+                limit / (1024 * 1024)
+            ));
+        }
+
+        if u32_at(buf, local_offset)? != LOCAL_HEADER_SIG {
+            return Err(format!("Corrupt entry {}", name));
+        }
+        let data_start = local_offset
+            + 30
+            + u16_at(buf, local_offset + 26)? as usize
+            + u16_at(buf, local_offset + 28)? as usize;
+        let raw = buf
+            .get(data_start..data_start + compressed_size)
+            .ok_or("Truncated archive")?;
+
+        let data = match method {
+            METHOD_STORED => raw.to_vec(),
+            METHOD_DEFLATE => {
+                let mut out = Vec::new();
+                // One byte past the declared size is enough to catch a lie
+                DeflateDecoder::new(raw)
+                    .take(size as u64 + 1)
+                    .read_to_end(&mut out)
+                    .map_err(|e| format!("Failed to inflate {}: {}", name, e))?;
+                out
+            }
+            other => return Err(format!("{} uses unsupported compression ({})", name, other)),
+        };
+        if data.len() != size || crc32(&data) != crc {
+            return Err(format!("Checksum mismatch in {}", name));
+        }
+
+        let mode = (made_by >> 8 == 3 && external_attrs >> 16 != 0)
+            .then_some((external_attrs >> 16) & 0o7777);
+        entries.push(ZipEntry { name, data, mode });
+    }
+    Ok(entries)
+}
+
+/// Build a deflated ZIP archive from `entries`.
+pub fn write(entries: &[ZipEntry]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+
+    for entry in entries {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&entry.data).map_err(|e| e.to_string())?;
+        let compressed = encoder.finish().map_err(|e| e.to_string())?;
+        let crc = crc32(&entry.data);
+        let name = entry.name.as_bytes();
+        let offset = out.len() as u32;
+
+        out.extend_from_slice(&LOCAL_HEADER_SIG.to_le_bytes());
+        out.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        out.extend_from_slice(&0u16.to_le_bytes()); // flags
+        out.extend_from_slice(&METHOD_DEFLATE.to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes()); // time
+        out.extend_from_slice(&DOS_EPOCH_DATE.to_le_bytes());
+        out.extend_from_slice(&crc.to_le_bytes());
+        out.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+        out.extend_from_slice(&(entry.data.len() as u32).to_le_bytes());
+        out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes()); // extra length
+        out.extend_from_slice(name);
+        out.extend_from_slice(&compressed);
+
+        let mode = entry.mode.unwrap_or(0o644) | 0o100000; // regular file
+        central.extend_from_slice(&CENTRAL_HEADER_SIG.to_le_bytes());
+        central.extend_from_slice(&MADE_BY_UNIX.to_le_bytes());
+        central.extend_from_slice(&20u16.to_le_bytes());
+        central.extend_from_slice(&0u16.to_le_bytes());
+        central.extend_from_slice(&METHOD_DEFLATE.to_le_bytes());
+        central.extend_from_slice(&0u16.to_le_bytes());
+        central.extend_from_slice(&DOS_EPOCH_DATE.to_le_bytes());
+        central.extend_from_slice(&crc.to_le_bytes());
+        central.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+        central.extend_from_slice(&(entry.data.len() as u32).to_le_bytes());
+        central.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        central.extend_from_slice(&[0; 8]); // extra, comment, disk, internal attrs
+        central.extend_from_slice(&(mode << 16).to_le_bytes());
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name);
+    }
+
+    let central_offset = out.len() as u32;
+    out.extend_from_slice(&central);
+    out.extend_from_slice(&END_OF_CENTRAL_DIR_SIG.to_le_bytes());
+    out.extend_from_slice(&[0; 4]); // disk numbers
+    out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+    out.extend_from_slice(&central_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // comment length
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let entries = vec![
+            ZipEntry {
+                name: "manifest.json".to_string(),
+                data: br#"{"name": "demo"}"#.to_vec(),
+                mode: Some(0o644),
+            },
+            ZipEntry {
+                name: "server/run.sh".to_string(),
+                data: b"#!/bin/sh\necho hi\n".repeat(50),
+                mode: Some(0o755),
+            },
+        ];
+        let bytes = write(&entries).unwrap();
+        assert_eq!(read(&bytes).unwrap(), entries);
+    }
+
+    #[test]
+    fn test_rejects_garbage() {
+        assert!(read(b"definitely not a zip file").is_err());
+
+        let mut bytes = write(&[ZipEntry {
+            name: "a.txt".to_string(),
+            data: b"hello".to_vec(),
+            mode: None,
+        }])
+        .unwrap();
+        // Flip a byte of the compressed data
+        bytes[30 + "a.txt".len()] ^= 0xff;
+        assert!(read(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_rejects_oversized() {
+        let entries = [
+            ZipEntry {
+                name: "a.bin".to_string(),
+                data: vec![0; 4096],
+                mode: None,
+            },
+            ZipEntry {
+                name: "b.bin".to_string(),
+                data: vec![0; 4096],
+                mode: None,
+            },
+        ];
+        let mut bytes = write(&entries).unwrap();
+        assert!(read_limited(&bytes, 8192).is_ok());
+        assert!(read_limited(&bytes, 8191).is_err());
+
+        // A header understating what the data inflates to
+        let central = bytes.len() - 22 - 2 * (46 + "a.bin".len());
+        bytes[central + 24..central + 28].copy_from_slice(&16u32.to_le_bytes());
+        assert_eq!(read(&bytes).unwrap_err(), "Checksum mismatch in a.bin");
+    }
+}
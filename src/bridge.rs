@@ -0,0 +1,214 @@
+//! Stdio bridge to the local hub.
+//!
+//! Some MCP clients (notably Claude Desktop) can only launch stdio servers.
+//! Running `open-mcp-manager bridge` turns this binary into a tiny stdio MCP
+//! server that forwards every JSON-RPC message to the hub's SSE endpoint and
+//! writes everything the hub sends back to stdout.
+
+use futures_util::StreamExt;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::{watch, Mutex};
+
+/// Default SSE endpoint exposed by the hub.
+pub const DEFAULT_HUB_URL: &str = "http://localhost:3000/api/mcp/sse";
+
+/// Subcommand name used to launch the bridge.
+pub const BRIDGE_SUBCOMMAND: &str = "bridge";
+
+/// Resolve the `endpoint` event payload against the SSE URL.
+///
+/// Servers may announce either an absolute URL or a path relative to the
+/// SSE endpoint (e.g. `/messages?sessionId=...`).
+pub fn resolve_endpoint(sse_url: &str, endpoint: &str) -> Result<String, String> {
+    if endpoint.starts_with("http://") || endpoint.starts_with("https://") {
+        return Ok(endpoint.to_string());
+    }
+    let base = reqwest::Url::parse(sse_url).map_err(|e| e.to_string())?;
+    base.join(endpoint)
+        .map(|u| u.to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Parse the bridge arguments (everything after `bridge`).
+///
+/// Supports `--url <sse-url>`; anything else is rejected.
+pub fn parse_args(args: &[String]) -> Result<String, String> {
+    let mut url = DEFAULT_HUB_URL.to_string();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--url" => {
+                url = iter
+                    .next()
+                    .cloned()
+                    .ok_or("--url requires a value".to_string())?;
+            }
+            other => return Err(format!("Unknown bridge argument: {}", other)),
+        }
+    }
+    Ok(url)
+}
+
+/// Whether the hub answers on its SSE endpoint.
+pub async fn hub_reachable(sse_url: &str) -> bool {
+    let client = reqwest::Client::new();
+    match tokio::time::timeout(
+        std::time::Duration::from_secs(3),
+        client
+            .get(sse_url)
+            .header("Accept", "text/event-stream")
+            .send(),
+    )
+    .await
+    {
+        Ok(Ok(res)) => res.status().is_success(),
+        _ => false,
+    }
+}
+
+/// Run the bridge until stdin closes or the hub connection drops.
+///
+/// Diagnostics go to stderr so they never corrupt the stdio protocol stream.
+pub async fn run(sse_url: String) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    let res = client
+        .get(&sse_url)
+        .header("Accept", "text/event-stream")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect to hub at {}: {}", sse_url, e))?;
+
+    if !res.status().is_success() {
+        return Err(format!("Hub responded with status: {}", res.status()));
+    }
+
+    let (endpoint_tx, mut endpoint_rx) = watch::channel(None::<String>);
+    let stdout = Arc::new(Mutex::new(tokio::io::stdout()));
+
+    // Hub -> stdout
+    let stdout_clone = stdout.clone();
+    let sse_url_clone = sse_url.clone();
+    let reader = tokio::spawn(async move {
+        let mut stream = res.bytes_stream();
+        let mut buffer = String::new();
+        let mut event = String::new();
+
+        while let Some(item) = stream.next().await {
+            let bytes = match item {
+                Ok(b) => b,
+                Err(e) => {
+                    eprintln!("Hub stream error: {}", e);
+                    break;
+                }
+            };
+            buffer.push_str(&String::from_utf8_lossy(&bytes));
+
+            while let Some(pos) = buffer.find('\n') {
+                let line = buffer[..pos].trim_end_matches('\r').to_string();
+                buffer.drain(..=pos);
+
+                if let Some(name) = line.strip_prefix("event:") {
+                    event = name.trim().to_string();
+                } else if let Some(data) = line.strip_prefix("data:") {
+                    let data = data.trim_start();
+                    if event == "endpoint" {
+                        match resolve_endpoint(&sse_url_clone, data) {
+                            Ok(url) => {
+                                let _ = endpoint_tx.send(Some(url));
+                            }
+                            Err(e) => eprintln!("Invalid hub endpoint {}: {}", data, e),
+                        }
+                    } else {
+                        let mut out = stdout_clone.lock().await;
+                        if out
+                            .write_all(format!("{}\n", data).as_bytes())
+                            .await
+                            .is_err()
+                            || out.flush().await.is_err()
+                        {
+                            return;
+                        }
+                    }
+                } else if line.is_empty() {
+                    event.clear();
+                }
+            }
+        }
+    });
+
+    // Wait for the hub to tell us where to POST messages
+    let endpoint = loop {
+        if let Some(url) = endpoint_rx.borrow().clone() {
+            break url;
+        }
+        if endpoint_rx.changed().await.is_err() {
+            return Err("Hub closed the connection before sending an endpoint".to_string());
+        }
+    };
+
+    // stdin -> Hub
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let body: serde_json::Value = match serde_json::from_str(&line) {
+            Ok(v) => v,
+            Err(e) => {
+                eprintln!("Ignoring invalid JSON from client: {}", e);
+                continue;
+            }
+        };
+        match client.post(&endpoint).json(&body).send().await {
+            Ok(r) if !r.status().is_success() => {
+                eprintln!("Hub rejected message with status: {}", r.status());
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Failed to forward message to hub: {}", e);
+                break;
+            }
+        }
+        if reader.is_finished() {
+            break;
+        }
+    }
+
+    reader.abort();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_endpoint_absolute() {
+        let url = resolve_endpoint(DEFAULT_HUB_URL, "http://example.com/messages?id=1").unwrap();
+        assert_eq!(url, "http://example.com/messages?id=1");
+    }
+
+    #[test]
+    fn test_resolve_endpoint_relative() {
+        let url = resolve_endpoint(DEFAULT_HUB_URL, "/messages?sessionId=abc").unwrap();
+        assert_eq!(url, "http://localhost:3000/messages?sessionId=abc");
+    }
+
+    #[test]
+    fn test_parse_args_default() {
+        assert_eq!(parse_args(&[]).unwrap(), DEFAULT_HUB_URL);
+    }
+
+    #[test]
+    fn test_parse_args_custom_url() {
+        let args = vec!["--url".to_string(), "http://localhost:4000/sse".to_string()];
+        assert_eq!(parse_args(&args).unwrap(), "http://localhost:4000/sse");
+    }
+
+    #[test]
+    fn test_parse_args_rejects_unknown() {
+        assert!(parse_args(&["--nope".to_string()]).is_err());
+        assert!(parse_args(&["--url".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+//! The hub's tool catalog, every tool its servers expose with their
+//! schemas, exported as an OpenAPI document or Markdown so it can be
+//! published alongside other API docs.
+//!
+//! Each tool becomes a `POST /{server}/{tool}` operation taking the
+//! tool's arguments as its request body. The hub doesn't serve these
+//! paths; they only give doc tooling a familiar shape.
+
+use crate::models::Tool;
+use crate::tool_docs::{hints, parameters};
+use serde_json::{json, Map, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CatalogFormat {
+    OpenApi,
+    Markdown,
+}
+
+impl CatalogFormat {
+    pub const ALL: [CatalogFormat; 2] = [CatalogFormat::OpenApi, CatalogFormat::Markdown];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CatalogFormat::OpenApi => "OpenAPI JSON",
+            CatalogFormat::Markdown => "Markdown",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CatalogFormat::OpenApi => "json",
+            CatalogFormat::Markdown => "md",
+        }
+    }
+}
+
+/// One server's part of the catalog.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogServer {
+    pub name: String,
+    pub description: Option<String>,
+    pub tools: Vec<Tool>,
+}
+
+/// Path segment for a server or tool name.
+fn segment(name: &str) -> String {
+    crate::storage::dir_name(name)
+}
+
+/// The catalog as an OpenAPI 3.1 document, one tag per server.
+pub fn to_openapi(servers: &[CatalogServer], version: &str) -> Value {
+    let mut paths = Map::new();
+    let mut tags = Vec::new();
+    for server in servers {
+        let mut tag = json!({ "name": server.name });
+        if let Some(description) = &server.description {
+            tag["description"] = json!(description);
+        }
+        tags.push(tag);
+
+        for tool in &server.tools {
+            let response_schema = tool
+                .outputSchema
+                .clone()
+                .unwrap_or_else(|| json!({ "type": "object" }));
+            let mut operation = json!({
+                "operationId": format!("{}.{}", segment(&server.name), tool.name),
+                "tags": [server.name],
+                "summary": tool
+                    .annotations
+                    .as_ref()
+                    .and_then(|a| a.title.clone())
+                    .unwrap_or_else(|| tool.name.clone()),
+                "requestBody": {
+                    "required": true,
+                    "content": { "application/json": { "schema": tool.inputSchema } }
+                },
+                "responses": {
+                    "200": {
+                        "description": "Tool result",
+                        "content": { "application/json": { "schema": response_schema } }
+                    }
+                }
+            });
+            if let Some(description) = &tool.description {
+                operation["description"] = json!(description);
+            }
+            if let Some(annotations) = &tool.annotations {
+                operation["x-mcp-annotations"] = json!(annotations);
+            }
+            paths.insert(
+                format!("/{}/{}", segment(&server.name), segment(&tool.name)),
+                json!({ "post": operation }),
+            );
+        }
+    }
+
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "MCP hub tools",
+            "version": version,
+            "description": "Tools exposed by the servers behind this MCP hub."
+        },
+        "tags": tags,
+        "paths": paths
+    })
+}
+
+/// Escape `|` and newlines so text stays in one table cell.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// The catalog as Markdown: a section per server with a parameter table
+/// per tool.
+pub fn to_markdown(servers: &[CatalogServer]) -> String {
+    let mut out = String::from("# MCP hub tools\n");
+    for server in servers {
+        out.push_str(&format!("\n## {}\n\n", server.name));
+        if let Some(description) = &server.description {
+            out.push_str(&format!("{}\n\n", description));
+        }
+        if server.tools.is_empty() {
+            out.push_str("_No tools._\n");
+            continue;
+        }
+        for tool in &server.tools {
+            out.push_str(&format!("### `{}`\n\n", tool.name));
+            if let Some(description) = &tool.description {
+                out.push_str(&format!("{}\n\n", description.trim()));
+            }
+            if let Some(annotations) = &tool.annotations {
+                let labels: Vec<&str> = hints(annotations).iter().map(|(l, _)| *l).collect();
+                if !labels.is_empty() {
+                    out.push_str(&format!("_{}_\n\n", labels.join(" · ")));
+                }
+            }
+            let params = parameters(&tool.inputSchema);
+            if params.is_empty() {
+                out.push_str("Takes no parameters.\n\n");
+            } else {
+                out.push_str("| Parameter | Type | Required | Description |\n");
+                out.push_str("| --- | --- | --- | --- |\n");
+                for param in params {
+                    let mut description = param.description.clone().unwrap_or_default();
+                    if !param.choices.is_empty() {
+                        description.push_str(&format!(" One of: {}.", param.choices.join(", ")));
+                    }
+                    if let Some(default) = &param.default {
+                        description.push_str(&format!(" Default: `{}`.", default));
+                    }
+                    out.push_str(&format!(
+                        "| `{}` | {} | {} | {} |\n",
+                        param.name,
+                        cell(&param.type_label),
+                        if param.required { "yes" } else { "no" },
+                        cell(description.trim())
+                    ));
+                }
+                out.push('\n');
+            }
+            if let Some(schema) = &tool.outputSchema {
+                out.push_str(&format!(
+                    "Returns:\n\n```json\n{}\n```\n\n",
+                    serde_json::to_string_pretty(schema).unwrap_or_default()
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// The catalog in `format`, ready to write to a file.
+pub fn render(format: CatalogFormat, servers: &[CatalogServer], version: &str) -> String {
+    match format {
+        CatalogFormat::OpenApi => {
+            serde_json::to_string_pretty(&to_openapi(servers, version)).unwrap_or_default()
+        }
+        CatalogFormat::Markdown => to_markdown(servers),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ToolAnnotations;
+
+    fn catalog() -> Vec<CatalogServer> {
+        vec![CatalogServer {
+            name: "Git Hub".to_string(),
+            description: Some("Repositories and issues".to_string()),
+            tools: vec![Tool {
+                name: "search_issues".to_string(),
+                description: Some("Find issues | PRs".to_string()),
+                inputSchema: json!({
+                    "type": "object",
+                    "properties": { "q": { "type": "string", "description": "Query" } },
+                    "required": ["q"]
+                }),
+                outputSchema: None,
+                annotations: Some(ToolAnnotations {
+                    readOnlyHint: Some(true),
+                    ..Default::default()
+                }),
+            }],
+        }]
+    }
+
+    #[test]
+    fn test_openapi() {
+        let doc = to_openapi(&catalog(), "1.0.0");
+        assert_eq!(doc["openapi"], "3.1.0");
+        assert_eq!(doc["tags"][0]["name"], "Git Hub");
+        let op = &doc["paths"]["/Git-Hub/search_issues"]["post"];
+        assert_eq!(op["tags"][0], "Git Hub");
+        assert_eq!(op["description"], "Find issues | PRs");
+        assert_eq!(
+            op["requestBody"]["content"]["application/json"]["schema"]["required"][0],
+            "q"
+        );
+        assert_eq!(op["x-mcp-annotations"]["readOnlyHint"], true);
+    }
+
+    #[test]
+    fn test_markdown() {
+        let md = to_markdown(&catalog());
+        assert!(md.contains("## Git Hub\n\nRepositories and issues\n"));
+        assert!(md.contains("### `search_issues`\n\nFind issues | PRs\n\n_Read-only_\n"));
+        assert!(md.contains("| `q` | string | yes | Query |\n"));
+    }
+}
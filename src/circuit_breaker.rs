@@ -0,0 +1,210 @@
+//! Circuit breakers for hub routing.
+//!
+//! After several consecutive failed calls a server's circuit opens and
+//! further calls fail immediately for a cool-down period, instead of
+//! piling up more timeouts. Once the cool-down has passed one trial call
+//! is let through, and others are rejected while it runs: success closes
+//! the circuit, failure opens it again.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Consecutive failures that open a circuit.
+pub const FAILURE_THRESHOLD: u32 = 3;
+
+/// How long an open circuit rejects calls.
+pub const COOL_DOWN: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BreakerState {
+    Closed,
+    /// Calls are rejected for `remaining` more time.
+    Open {
+        remaining: Duration,
+    },
+    /// Cool-down is over; the next call is a trial.
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Breaker {
+    failures: u32,
+    opened_at: Option<Instant>,
+    /// When the trial call in flight started. It holds the circuit for
+    /// at most [`COOL_DOWN`], so an abandoned one doesn't block it for good.
+    trial_started: Option<Instant>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBreakers {
+    breakers: HashMap<String, Breaker>,
+}
+
+impl CircuitBreakers {
+    pub fn state(&self, server_id: &str, now: Instant) -> BreakerState {
+        match self.breakers.get(server_id).and_then(|b| b.opened_at) {
+            None => BreakerState::Closed,
+            Some(opened) => match COOL_DOWN.checked_sub(now.duration_since(opened)) {
+                Some(remaining) if !remaining.is_zero() => BreakerState::Open { remaining },
+                _ => BreakerState::HalfOpen,
+            },
+        }
+    }
+
+    /// Ok if a call may go through, or the error to fail it with.
+    pub fn check(&self, server_id: &str, server_name: &str, now: Instant) -> Result<(), String> {
+        match self.state(server_id, now) {
+            BreakerState::Open { remaining } => Err(format!(
+                "{} is unhealthy after {} failed calls; retrying in {}s",
+                server_name,
+                FAILURE_THRESHOLD,
+                remaining.as_secs().max(1)
+            )),
+            BreakerState::HalfOpen if self.trial_running(server_id, now) => Err(format!(
+                "{} is unhealthy after {} failed calls; a trial call is running",
+                server_name, FAILURE_THRESHOLD
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn trial_running(&self, server_id: &str, now: Instant) -> bool {
+        self.breakers
+            .get(server_id)
+            .and_then(|b| b.trial_started)
+            .is_some_and(|started| now.duration_since(started) < COOL_DOWN)
+    }
+
+    /// [`Self::check`] a call about to be made, claiming the trial if the
+    /// circuit is half open. Its outcome must be recorded, or the trial
+    /// ended with [`Self::end_trial`].
+    pub fn begin_call(
+        &mut self,
+        server_id: &str,
+        server_name: &str,
+        now: Instant,
+    ) -> Result<(), String> {
+        self.check(server_id, server_name, now)?;
+        if self.state(server_id, now) == BreakerState::HalfOpen {
+            if let Some(breaker) = self.breakers.get_mut(server_id) {
+                breaker.trial_started = Some(now);
+            }
+        }
+        Ok(())
+    }
+
+    /// Let another call be the trial, e.g. when this one was cancelled
+    /// and says nothing about the server's health.
+    pub fn end_trial(&mut self, server_id: &str) {
+        if let Some(breaker) = self.breakers.get_mut(server_id) {
+            breaker.trial_started = None;
+        }
+    }
+
+    pub fn record_success(&mut self, server_id: &str) {
+        self.breakers.remove(server_id);
+    }
+
+    /// Count a failure. Returns true if this call opened the circuit.
+    pub fn record_failure(&mut self, server_id: &str, now: Instant) -> bool {
+        let half_open = self.state(server_id, now) == BreakerState::HalfOpen;
+        let breaker = self.breakers.entry(server_id.to_string()).or_default();
+        breaker.failures += 1;
+        if half_open || (breaker.opened_at.is_none() && breaker.failures >= FAILURE_THRESHOLD) {
+            breaker.opened_at = Some(now);
+            breaker.trial_started = None;
+            return true;
+        }
+        false
+    }
+
+    /// Close the circuit by hand, e.g. after fixing or restarting the server.
+    pub fn reset(&mut self, server_id: &str) {
+        self.breakers.remove(server_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let mut breakers = CircuitBreakers::default();
+        let now = Instant::now();
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            assert!(!breakers.record_failure("s", now));
+        }
+        assert!(breakers.check("s", "github", now).is_ok());
+        assert!(breakers.record_failure("s", now));
+
+        let err = breakers.check("s", "github", now).unwrap_err();
+        assert!(err.contains("github is unhealthy"));
+        assert!(matches!(
+            breakers.state("s", now + Duration::from_secs(10)),
+            BreakerState::Open { remaining } if remaining == Duration::from_secs(20)
+        ));
+        assert!(breakers.check("other", "other", now).is_ok());
+    }
+
+    #[test]
+    fn test_success_resets_failures() {
+        let mut breakers = CircuitBreakers::default();
+        let now = Instant::now();
+        breakers.record_failure("s", now);
+        breakers.record_failure("s", now);
+        breakers.record_success("s");
+        breakers.record_failure("s", now);
+        assert_eq!(breakers.state("s", now), BreakerState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_trial() {
+        let mut breakers = CircuitBreakers::default();
+        let start = Instant::now();
+        for _ in 0..FAILURE_THRESHOLD {
+            breakers.record_failure("s", start);
+        }
+        let later = start + COOL_DOWN;
+        assert_eq!(breakers.state("s", later), BreakerState::HalfOpen);
+        assert!(breakers.check("s", "s", later).is_ok());
+
+        // One trial at a time
+        assert!(breakers.begin_call("s", "s", later).is_ok());
+        let err = breakers.begin_call("s", "s", later).unwrap_err();
+        assert!(err.contains("trial call is running"));
+        assert!(breakers.check("s", "s", later).is_err());
+
+        // A failed trial reopens straight away
+        assert!(breakers.record_failure("s", later));
+        assert!(breakers.check("s", "s", later).is_err());
+
+        // A successful trial closes
+        let after = later + COOL_DOWN;
+        assert!(breakers.begin_call("s", "s", after).is_ok());
+        breakers.record_success("s");
+        assert_eq!(breakers.state("s", after), BreakerState::Closed);
+        assert!(breakers.begin_call("s", "s", after).is_ok());
+        assert!(breakers.begin_call("s", "s", after).is_ok());
+    }
+
+    #[test]
+    fn test_trial_ended_or_abandoned() {
+        let mut breakers = CircuitBreakers::default();
+        let start = Instant::now();
+        for _ in 0..FAILURE_THRESHOLD {
+            breakers.record_failure("s", start);
+        }
+        let later = start + COOL_DOWN;
+        breakers.begin_call("s", "s", later).unwrap();
+
+        // A cancelled trial lets the next call try
+        breakers.end_trial("s");
+        assert!(breakers.begin_call("s", "s", later).is_ok());
+        assert_eq!(breakers.state("s", later), BreakerState::HalfOpen);
+
+        // One never finished stops holding the circuit after a cool-down
+        assert!(breakers.check("s", "s", later + COOL_DOWN / 2).is_err());
+        assert!(breakers.begin_call("s", "s", later + COOL_DOWN).is_ok());
+    }
+}
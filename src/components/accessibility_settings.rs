@@ -0,0 +1,63 @@
+use crate::appearance::{AppearanceSettings, SYSTEM_REDUCED_MOTION_JS};
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+/// High-contrast and reduced-motion toggles.
+pub fn AccessibilitySettings() -> Element {
+    let settings = *APP_STATE.read().appearance.read();
+    // Whether the system already asks for reduced motion
+    let system_reduced_motion = use_resource(|| async {
+        document::eval(SYSTEM_REDUCED_MOTION_JS)
+            .join::<bool>()
+            .await
+            .unwrap_or(false)
+    });
+    let system_reduced_motion = system_reduced_motion().unwrap_or(false);
+
+    rsx! {
+        section { class: "max-w-3xl mx-auto mb-6 p-5 rounded-2xl glass space-y-3",
+            h3 { class: "text-xs font-bold text-zinc-500 uppercase", "Accessibility" }
+            label { class: "flex items-start gap-3 text-sm",
+                input {
+                    r#type: "checkbox",
+                    class: "mt-1",
+                    checked: settings.high_contrast,
+                    onchange: move |evt| {
+                        AppState::save_appearance_settings(AppearanceSettings {
+                            high_contrast: evt.checked(),
+                            ..settings
+                        })
+                    }
+                }
+                div {
+                    div { class: "text-white", "High contrast" }
+                    p { class: "text-xs text-zinc-500", "Solid backgrounds, brighter text and stronger borders and focus outlines." }
+                }
+            }
+            label { class: "flex items-start gap-3 text-sm",
+                input {
+                    r#type: "checkbox",
+                    class: "mt-1",
+                    checked: settings.reduce_motion || system_reduced_motion,
+                    disabled: system_reduced_motion,
+                    onchange: move |evt| {
+                        AppState::save_appearance_settings(AppearanceSettings {
+                            reduce_motion: evt.checked(),
+                            ..settings
+                        })
+                    }
+                }
+                div {
+                    div { class: "text-white", "Reduce motion" }
+                    p { class: "text-xs text-zinc-500",
+                        if system_reduced_motion {
+                            "Animations are off because your system asks for reduced motion."
+                        } else {
+                            "Turn off fade, slide and scale animations."
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::models::NotificationLevel;
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+/// Collapsible list of archived servers under the dashboard grid, to
+/// restore them or delete them for good.
+pub fn ArchivedServers() -> Element {
+    let mut expanded = use_signal(|| false);
+    let archived: Vec<_> = APP_STATE
+        .read()
+        .servers
+        .read()
+        .iter()
+        .filter(|s| s.is_archived)
+        .cloned()
+        .collect();
+    if archived.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "mt-10",
+            button {
+                class: "flex items-center gap-2 text-xs font-bold uppercase tracking-wider text-zinc-500 hover:text-zinc-300 transition-colors",
+                "aria-expanded": "{expanded}",
+                onclick: move |_| expanded.toggle(),
+                span { if expanded() { "▾" } else { "▸" } }
+                "Archived ({archived.len()})"
+            }
+            if expanded() {
+                div { class: "mt-3 rounded-2xl glass border border-white-5 divide-y divide-white/5",
+                    for server in archived {
+                        div {
+                            key: "{server.id}",
+                            class: "flex items-center gap-4 px-4 py-3",
+                            div { class: "flex-1 min-w-0",
+                                p { class: "text-sm font-medium text-zinc-300 truncate", "{server.name}" }
+                                p { class: "text-xs text-zinc-600 truncate",
+                                    "{server.description.clone().unwrap_or_default()}"
+                                }
+                            }
+                            button {
+                                class: "px-3 py-1.5 rounded-lg text-xs font-semibold text-indigo-400 hover:text-white hover:bg-white-8 transition-colors",
+                                onclick: {
+                                    let server = server.clone();
+                                    move |_| {
+                                        let server = server.clone();
+                                        spawn(async move {
+                                            if let Err(e) = AppState::set_server_archived(server.id.clone(), false).await {
+                                                AppState::push_notification(
+                                                    format!("Could not restore {}: {}", server.name, e),
+                                                    NotificationLevel::Error,
+                                                );
+                                            }
+                                        });
+                                    }
+                                },
+                                "Restore"
+                            }
+                            button {
+                                class: "px-3 py-1.5 rounded-lg text-xs font-semibold text-zinc-500 hover:text-red-400 hover:bg-white-8 transition-colors",
+                                title: "Delete the server and its config",
+                                onclick: {
+                                    let server = server.clone();
+                                    move |_| {
+                                        let server = server.clone();
+                                        spawn(async move {
+                                            if let Err(e) = AppState::delete_server(server.id.clone()).await {
+                                                AppState::push_notification(
+                                                    format!("Could not delete {}: {}", server.name, e),
+                                                    NotificationLevel::Error,
+                                                );
+                                            }
+                                        });
+                                    }
+                                },
+                                "Delete"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::mcpb::{
+    default_value, input_from_value, validate_user_config, value_from_input, Bundle,
+};
+use crate::models::NotificationLevel;
+use crate::state::AppState;
+use dioxus::prelude::*;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct BundleInstallerProps {
+    /// `.mcpb` / `.dxt` file chosen by the user.
+    pub path: PathBuf,
+    pub on_close: EventHandler<()>,
+}
+
+/// Install wizard for a desktop extension bundle: shows the manifest and
+/// asks for its `user_config` values.
+pub fn BundleInstaller(props: BundleInstallerProps) -> Element {
+    let path = props.path.clone();
+    let bundle = use_hook(move || {
+        std::fs::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| crate::mcpb::read_bundle(&bytes))
+    });
+    let mut inputs = use_signal(|| match &bundle {
+        Ok(b) => b
+            .manifest
+            .user_config
+            .iter()
+            .map(|(k, f)| (k.clone(), input_from_value(f, &default_value(f))))
+            .collect::<BTreeMap<String, String>>(),
+        Err(_) => BTreeMap::new(),
+    });
+    let mut installing = use_signal(|| false);
+    let file_name = props
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let bundle: Bundle = match bundle {
+        Ok(b) => b,
+        Err(e) => {
+            return rsx! {
+                div { class: "fixed inset-0 z-50 flex items-center justify-center bg-black/60 p-4 backdrop-blur-md",
+                    div { class: "w-full max-w-md bg-zinc-950 text-zinc-300 rounded-2xl border border-zinc-800 shadow-2xl p-6 space-y-4 animate-scale-in",
+                        h2 { class: "font-bold text-xl text-white", "Can't open {file_name}" }
+                        p { class: "text-sm text-red-400", "{e}" }
+                        div { class: "flex justify-end",
+                            button {
+                                class: "px-5 py-2.5 text-zinc-400 hover:text-white transition-colors",
+                                onclick: move |_| props.on_close.call(()),
+                                "Close"
+                            }
+                        }
+                    }
+                }
+            };
+        }
+    };
+
+    let manifest = bundle.manifest.clone();
+    let values: BTreeMap<String, serde_json::Value> = inputs
+        .read()
+        .iter()
+        .filter_map(|(k, text)| {
+            manifest
+                .user_config
+                .get(k)
+                .map(|f| (k.clone(), value_from_input(f, text)))
+        })
+        .collect();
+    let errors = validate_user_config(&manifest, &values);
+    let title = manifest
+        .display_name
+        .clone()
+        .unwrap_or(manifest.name.clone());
+    let fields: Vec<_> = manifest.user_config.clone().into_iter().collect();
+
+    let install = move |_| {
+        let bundle = bundle.clone();
+        let values = values.clone();
+        installing.set(true);
+        spawn(async move {
+            match AppState::install_bundle(bundle, values).await {
+                Ok(()) => props.on_close.call(()),
+                Err(e) => AppState::push_notification(
+                    format!("Install failed: {}", e),
+                    NotificationLevel::Error,
+                ),
+            }
+            installing.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "fixed inset-0 z-50 flex items-center justify-center bg-black/60 p-4 backdrop-blur-md",
+            div { class: "w-full max-w-xl max-h-[85vh] bg-zinc-950 text-zinc-300 rounded-2xl flex flex-col overflow-hidden border border-zinc-800 shadow-2xl animate-scale-in",
+                div { class: "flex justify-between items-center p-5 bg-zinc-900 border-b border-zinc-800",
+                    div {
+                        h2 { class: "font-bold text-xl text-white", "📦 Install {title}" }
+                        p { class: "text-xs text-zinc-500 mt-1",
+                            "v{manifest.version} · {manifest.author.name} · {file_name}"
+                        }
+                    }
+                    button {
+                        class: "p-2 hover:bg-zinc-800 rounded-full transition-colors",
+                        onclick: move |_| props.on_close.call(()),
+                        "✕"
+                    }
+                }
+
+                div { class: "flex-1 overflow-y-auto custom-scrollbar p-6 space-y-5",
+                    if !manifest.description.is_empty() {
+                        p { class: "text-sm text-zinc-400", "{manifest.description}" }
+                    }
+                    if fields.is_empty() {
+                        p { class: "text-sm text-zinc-500", "This extension needs no configuration." }
+                    }
+                    for (key, field) in fields {
+                        div { key: "{key}", class: "space-y-1.5",
+                            label { class: "block text-xs font-bold text-zinc-400 uppercase tracking-wider",
+                                {field.title.clone().unwrap_or(key.clone())}
+                                if field.required {
+                                    span { class: "text-red-400 ml-1", "*" }
+                                }
+                            }
+                            if field.field_type == "boolean" {
+                                input {
+                                    r#type: "checkbox",
+                                    checked: inputs.read().get(&key).map(|v| v == "true").unwrap_or(false),
+                                    onchange: {
+                                        let key = key.clone();
+                                        move |evt: FormEvent| {
+                                            inputs.write().insert(key.clone(), evt.checked().to_string());
+                                        }
+                                    }
+                                }
+                            } else if field.multiple {
+                                textarea {
+                                    class: "w-full h-20 bg-black border border-zinc-800 rounded-lg px-3 py-2 text-sm font-mono text-white focus:outline-none focus:border-indigo-500",
+                                    placeholder: "One per line",
+                                    value: inputs.read().get(&key).cloned().unwrap_or_default(),
+                                    oninput: {
+                                        let key = key.clone();
+                                        move |evt: FormEvent| {
+                                            inputs.write().insert(key.clone(), evt.value());
+                                        }
+                                    }
+                                }
+                            } else {
+                                input {
+                                    class: "w-full bg-black border border-zinc-800 rounded-lg px-3 py-2 text-sm text-white focus:outline-none focus:border-indigo-500",
+                                    r#type: if field.sensitive { "password" } else if field.field_type == "number" { "number" } else { "text" },
+                                    value: inputs.read().get(&key).cloned().unwrap_or_default(),
+                                    oninput: {
+                                        let key = key.clone();
+                                        move |evt: FormEvent| {
+                                            inputs.write().insert(key.clone(), evt.value());
+                                        }
+                                    }
+                                }
+                            }
+                            if let Some(desc) = field.description.clone() {
+                                p { class: "text-[11px] text-zinc-500", "{desc}" }
+                            }
+                        }
+                    }
+                    if !errors.is_empty() {
+                        ul { class: "text-xs text-amber-400 space-y-1",
+                            for err in errors.iter() {
+                                li { "⚠ {err}" }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "p-5 bg-zinc-900 border-t border-zinc-800 flex justify-end gap-3",
+                    button {
+                        class: "px-5 py-2.5 text-zinc-400 hover:text-white transition-colors",
+                        onclick: move |_| props.on_close.call(()),
+                        "Cancel"
+                    }
+                    button {
+                        class: "px-6 py-2.5 bg-indigo-600 hover:bg-indigo-500 text-white rounded-xl text-sm font-bold transition-colors shadow-lg shadow-indigo-500/20 disabled:opacity-40",
+                        disabled: !errors.is_empty() || installing(),
+                        onclick: install,
+                        if installing() { "Installing..." } else { "Install" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use crate::models::McpServer;
+use crate::recent_tools::{matches, ranked, RecentTool};
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+/// App-level actions offered in the palette next to recent tools.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PaletteCommand {
+    AddServer,
+    PairServer,
+    OpenRegistry,
+    CompareServers,
+    RotateEnv,
+    FindDuplicates,
+    ExportConfig,
+}
+
+impl PaletteCommand {
+    const ALL: [PaletteCommand; 7] = [
+        PaletteCommand::AddServer,
+        PaletteCommand::PairServer,
+        PaletteCommand::OpenRegistry,
+        PaletteCommand::CompareServers,
+        PaletteCommand::RotateEnv,
+        PaletteCommand::FindDuplicates,
+        PaletteCommand::ExportConfig,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            PaletteCommand::AddServer => "Add server",
+            PaletteCommand::PairServer => "Pair remote server",
+            PaletteCommand::OpenRegistry => "Open registry",
+            PaletteCommand::CompareServers => "Compare servers",
+            PaletteCommand::RotateEnv => "Rotate env variable",
+            PaletteCommand::FindDuplicates => "Find duplicate servers",
+            PaletteCommand::ExportConfig => "Export config",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum Entry {
+    /// Start or stop a server found by its alias or name.
+    Server(McpServer, bool),
+    Tool(RecentTool),
+    Command(PaletteCommand),
+}
+
+/// Whether `needle` (lowercase) picks `server`: the start of its alias or
+/// any part of its name.
+fn server_matches(server: &McpServer, needle: &str) -> bool {
+    !needle.is_empty()
+        && (server.alias.as_deref().is_some_and(|a| a.starts_with(needle))
+            || server.name.to_lowercase().contains(needle))
+}
+
+#[derive(Props, Clone, PartialEq)]
+pub struct CommandPaletteProps {
+    /// Open a recent tool's modal with its last arguments.
+    pub on_launch_tool: EventHandler<RecentTool>,
+    pub on_command: EventHandler<PaletteCommand>,
+    pub on_close: EventHandler<()>,
+}
+
+/// Quick-launch palette (Ctrl/Cmd+K): recently used tools first, then
+/// app actions, filtered as you type. Typing a server's alias or name
+/// offers to start or stop it.
+pub fn CommandPalette(props: CommandPaletteProps) -> Element {
+    let mut query = use_signal(String::new);
+    let mut selected = use_signal(|| 0usize);
+
+    let needle = query().trim().to_lowercase();
+    let recent = ranked(&APP_STATE.read().recent_tools.read(), chrono::Utc::now());
+    let running = APP_STATE.read().processes;
+    let servers: Vec<Entry> = APP_STATE
+        .read()
+        .servers
+        .read()
+        .iter()
+        .filter(|s| !s.is_archived && server_matches(s, &needle))
+        .map(|s| Entry::Server(s.clone(), running.read().contains_key(&s.id)))
+        .collect();
+    let entries: Vec<Entry> = servers
+        .into_iter()
+        .chain(
+            recent
+                .into_iter()
+                .filter(|t| matches(t, &needle))
+                .map(Entry::Tool),
+        )
+        .chain(
+            PaletteCommand::ALL
+                .into_iter()
+                .filter(|c| c.label().to_lowercase().contains(&needle))
+                .map(Entry::Command),
+        )
+        .collect();
+    let current = selected().min(entries.len().saturating_sub(1));
+
+    let choose = move |entry: Entry| match entry {
+        Entry::Server(server, running) => {
+            spawn(async move {
+                if running {
+                    AppState::stop_server_process(&server.id).await;
+                } else {
+                    AppState::start_server_with_notification(server).await;
+                }
+            });
+            props.on_close.call(());
+        }
+        Entry::Tool(tool) => props.on_launch_tool.call(tool),
+        Entry::Command(command) => props.on_command.call(command),
+    };
+
+    let keyboard_entries = entries.clone();
+    let onkeydown = move |evt: KeyboardEvent| match evt.key() {
+        Key::Escape => props.on_close.call(()),
+        Key::ArrowDown => {
+            evt.prevent_default();
+            selected.set((current + 1).min(keyboard_entries.len().saturating_sub(1)));
+        }
+        Key::ArrowUp => {
+            evt.prevent_default();
+            selected.set(current.saturating_sub(1));
+        }
+        Key::Enter => {
+            if let Some(entry) = keyboard_entries.get(current) {
+                choose(entry.clone());
+            }
+        }
+        _ => {}
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-50 flex items-start justify-center bg-black/60 p-4 pt-[15vh] backdrop-blur-md",
+            onclick: move |_| props.on_close.call(()),
+            div {
+                class: "w-full max-w-xl bg-zinc-950 text-zinc-300 rounded-2xl flex flex-col overflow-hidden border border-zinc-800 shadow-2xl animate-scale-in",
+                onclick: move |evt| evt.stop_propagation(),
+                onkeydown,
+                input {
+                    class: "w-full bg-zinc-900 border-b border-zinc-800 px-5 py-4 text-sm text-white placeholder-zinc-500 focus:outline-none",
+                    placeholder: "Search servers, recent tools and actions...",
+                    onmounted: move |evt| async move {
+                        let _ = evt.data().set_focus(true).await;
+                    },
+                    value: "{query}",
+                    oninput: move |evt| {
+                        query.set(evt.value());
+                        selected.set(0);
+                    }
+                }
+                div { class: "max-h-[50vh] overflow-y-auto custom-scrollbar py-2",
+                    if entries.is_empty() {
+                        div { class: "px-5 py-6 text-center text-sm text-zinc-500", "Nothing matches." }
+                    }
+                    for (i, entry) in entries.into_iter().enumerate() {
+                        button {
+                            key: "{i}",
+                            class: if i == current { "w-full flex items-center justify-between gap-3 px-5 py-2 text-left text-sm bg-indigo-500/15 text-white" } else { "w-full flex items-center justify-between gap-3 px-5 py-2 text-left text-sm text-zinc-300 hover:bg-white/5" },
+                            onmouseenter: move |_| selected.set(i),
+                            onclick: {
+                                let entry = entry.clone();
+                                move |_| choose(entry.clone())
+                            },
+                            match entry {
+                                Entry::Server(server, running) => rsx! {
+                                    span { class: "flex items-center gap-2 min-w-0",
+                                        span { class: if running { "text-red-400" } else { "text-green-400" }, if running { "■" } else { "▶" } }
+                                        span { class: "truncate", if running { "Stop {server.name}" } else { "Start {server.name}" } }
+                                    }
+                                    if let Some(alias) = server.alias {
+                                        span { class: "text-[10px] font-mono text-zinc-500 shrink-0", "{alias}" }
+                                    }
+                                },
+                                Entry::Tool(tool) => rsx! {
+                                    span { class: "flex items-center gap-2 min-w-0",
+                                        span { class: "text-indigo-400", "▶" }
+                                        span { class: "font-mono truncate", "{tool.tool}" }
+                                        span { class: "text-xs text-zinc-500 truncate", "{tool.server_name}" }
+                                    }
+                                    span { class: "text-[10px] text-zinc-600 shrink-0", "{tool.uses}×" }
+                                },
+                                Entry::Command(command) => rsx! {
+                                    span { class: "flex items-center gap-2",
+                                        span { class: "text-zinc-500", "›" }
+                                        "{command.label()}"
+                                    }
+                                },
+                            }
+                        }
+                    }
+                }
+                div { class: "px-5 py-2 bg-zinc-900 border-t border-zinc-800 text-[10px] text-zinc-500",
+                    "↑↓ to move · Enter to open · Esc to close"
+                }
+            }
+        }
+    }
+}
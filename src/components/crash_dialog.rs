@@ -0,0 +1,55 @@
+use crate::state::AppState;
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct CrashDialogProps {
+    pub report: PathBuf,
+    pub on_close: EventHandler<()>,
+}
+
+/// Shown on start when the previous run ended in a panic.
+pub fn CrashDialog(props: CrashDialogProps) -> Element {
+    let report_open = props.report.clone();
+    let report_reveal = props.report.clone();
+
+    rsx! {
+        div { class: "fixed inset-0 z-50 flex items-center justify-center bg-black/60 p-4 backdrop-blur-md",
+            div { class: "w-full max-w-lg bg-zinc-950 text-zinc-300 rounded-2xl overflow-hidden border border-zinc-800 shadow-2xl animate-scale-in",
+                div { class: "p-6 space-y-3",
+                    h2 { class: "font-bold text-xl text-white", "⚠️ The app recovered from an error" }
+                    p { class: "text-sm text-zinc-400",
+                        "Open MCP Manager closed unexpectedly last time. A crash report was saved locally; nothing was uploaded."
+                    }
+                    code { class: "block p-3 rounded-lg bg-black text-[11px] font-mono text-zinc-400 break-all",
+                        "{props.report.display()}"
+                    }
+                }
+                div { class: "p-5 bg-zinc-900 border-t border-zinc-800 flex justify-end gap-3",
+                    button {
+                        class: "px-4 py-2.5 text-zinc-400 hover:text-white transition-colors mr-auto",
+                        onclick: move |_| AppState::reveal_in_file_manager(&report_reveal),
+                        "Show in folder"
+                    }
+                    button {
+                        class: "px-5 py-2.5 text-zinc-400 hover:text-white transition-colors",
+                        onclick: move |_| props.on_close.call(()),
+                        "Dismiss"
+                    }
+                    button {
+                        class: "px-6 py-2.5 bg-indigo-600 hover:bg-indigo-500 text-white rounded-xl text-sm font-bold transition-colors shadow-lg shadow-indigo-500/20",
+                        onclick: move |_| {
+                            if let Err(e) = open::that_detached(&report_open) {
+                                AppState::push_notification(
+                                    format!("Could not open report: {}", e),
+                                    crate::models::NotificationLevel::Error,
+                                );
+                            }
+                        },
+                        "Open report"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::dashboard::{call_counts, errors_on, start_of_day, DashboardWidget};
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct DashboardWidgetsProps {
+    /// A widget was clicked, to open the view behind its number.
+    pub on_select: EventHandler<DashboardWidget>,
+}
+
+/// Aggregate stats above the server grid. Which widgets show is chosen
+/// with "Customize" and saved per workspace.
+pub fn DashboardWidgets(props: DashboardWidgetsProps) -> Element {
+    let mut customizing = use_signal(|| false);
+    let settings = APP_STATE.read().dashboard.cloned();
+
+    let total = APP_STATE
+        .read()
+        .servers
+        .read()
+        .iter()
+        .filter(|s| !s.is_archived)
+        .count();
+    let running = APP_STATE.read().processes.read().len();
+    let connected = APP_STATE.read().running_handlers.read().len();
+    let errors = errors_on(
+        &APP_STATE.read().notification_history.read(),
+        chrono::Local::now().date_naive(),
+    );
+    let calls_today = use_resource(move || {
+        // Recount whenever a routed call is recorded
+        let _ = APP_STATE.read().request_metrics.read();
+        async move {
+            let db = APP_STATE.read().db.cloned()?;
+            let since = crate::weekly_report::db_time(start_of_day(chrono::Local::now()));
+            db.get_tool_calls_since(&since)
+                .ok()
+                .map(|calls| call_counts(&calls))
+        }
+    });
+    let (calls, failed_calls) = calls_today.cloned().flatten().unwrap_or_default();
+
+    let stat = |widget: DashboardWidget| -> (String, Option<String>) {
+        match widget {
+            DashboardWidget::TotalServers => (total.to_string(), None),
+            DashboardWidget::Running => (
+                running.to_string(),
+                Some(format!("{} stopped", total.saturating_sub(running))),
+            ),
+            DashboardWidget::HubConnections => (
+                connected.to_string(),
+                Some("routable by the hub".to_string()),
+            ),
+            DashboardWidget::CallsToday => (
+                calls.to_string(),
+                (failed_calls > 0).then(|| format!("{} failed", failed_calls)),
+            ),
+            DashboardWidget::RecentErrors => (errors.to_string(), None),
+        }
+    };
+
+    rsx! {
+        div { class: "mb-8",
+            div { class: "flex items-center justify-between mb-3",
+                h2 { class: "text-xs font-bold uppercase tracking-wider text-zinc-500", "Overview" }
+                button {
+                    class: "text-[10px] text-zinc-500 hover:text-white transition-colors",
+                    "aria-expanded": "{customizing}",
+                    onclick: move |_| customizing.toggle(),
+                    if customizing() { "Done" } else { "Customize" }
+                }
+            }
+            if customizing() {
+                div { class: "flex flex-wrap gap-4 mb-3 text-xs text-zinc-300",
+                    for widget in DashboardWidget::ALL {
+                        label { key: "{widget.label()}", class: "flex items-center gap-2",
+                            input {
+                                r#type: "checkbox",
+                                checked: settings.shows(widget),
+                                onchange: move |_| {
+                                    let mut settings = APP_STATE.read().dashboard.cloned();
+                                    settings.toggle(widget);
+                                    AppState::save_dashboard_settings(settings);
+                                }
+                            }
+                            "{widget.label()}"
+                        }
+                    }
+                }
+            }
+            div { class: "grid grid-cols-2 md:grid-cols-3 xl:grid-cols-5 gap-4",
+                for widget in settings.widgets.iter().copied() {
+                    {
+                        let (value, detail) = stat(widget);
+                        let alert = widget == DashboardWidget::RecentErrors && errors > 0;
+                        rsx! {
+                            button {
+                                key: "{widget.label()}",
+                                class: "p-4 rounded-2xl glass border border-white-5 hover:border-indigo-500/50 text-left transition-colors",
+                                onclick: move |_| props.on_select.call(widget),
+                                div { class: "flex items-center gap-2 text-xs text-zinc-500",
+                                    span { "aria-hidden": "true", "{widget.icon()}" }
+                                    "{widget.label()}"
+                                }
+                                div {
+                                    class: if alert { "mt-2 text-2xl font-bold text-red-400" } else { "mt-2 text-2xl font-bold text-white" },
+                                    "{value}"
+                                }
+                                if let Some(detail) = detail {
+                                    div { class: "text-[10px] text-zinc-500 mt-1", "{detail}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
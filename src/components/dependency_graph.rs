@@ -0,0 +1,143 @@
+use crate::editor_config::{known_config_paths, read_config};
+use crate::graph::{build_graph, force_layout, NodeKind};
+use crate::models::McpServer;
+use crate::state::APP_STATE;
+use dioxus::prelude::*;
+
+const WIDTH: f64 = 900.0;
+const HEIGHT: f64 = 560.0;
+const LAYOUT_ITERATIONS: usize = 300;
+
+fn node_style(kind: NodeKind) -> (&'static str, &'static str, f64) {
+    // (fill, stroke, radius)
+    match kind {
+        NodeKind::Editor => ("#1e1b4b", "#6366f1", 26.0),
+        NodeKind::Hub => ("#450a0a", "#ef4444", 24.0),
+        NodeKind::Server => ("#18181b", "#a1a1aa", 20.0),
+        NodeKind::Runtime => ("#422006", "#f59e0b", 16.0),
+    }
+}
+
+#[derive(Props, Clone, PartialEq)]
+pub struct DependencyGraphProps {
+    pub on_open_console: EventHandler<McpServer>,
+}
+
+/// Which editors use which servers (directly or through the hub), and
+/// the runtimes the servers need.
+pub fn DependencyGraph(props: DependencyGraphProps) -> Element {
+    let mut hovered = use_signal(|| None::<usize>);
+
+    let layout = use_memo(move || {
+        let servers = APP_STATE.read().servers.cloned();
+        // Re-read editor configs whenever the watcher sees them change
+        let _ = APP_STATE.read().config_drift.read().len();
+        let configs: Vec<(&str, serde_json::Value)> = known_config_paths()
+            .into_iter()
+            .filter(|(_, path)| path.exists())
+            .filter_map(|(editor, path)| read_config(&path).ok().map(|c| (editor, c)))
+            .collect();
+        let graph = build_graph(&servers, &configs);
+        let positions = force_layout(&graph, WIDTH, HEIGHT, LAYOUT_ITERATIONS);
+        (graph, positions)
+    });
+
+    let (graph, positions) = layout();
+    let highlighted: Vec<usize> = hovered()
+        .map(|h| {
+            let mut n = graph.neighbours(h);
+            n.push(h);
+            n
+        })
+        .unwrap_or_default();
+    let servers = APP_STATE.read().servers.cloned();
+    let running = APP_STATE.read().running_handlers;
+
+    rsx! {
+        div { class: "max-w-6xl mx-auto space-y-4",
+            div { class: "flex items-end justify-between",
+                div {
+                    h2 { class: "text-2xl font-bold text-white tracking-tight", "Dependency graph" }
+                    p { class: "text-sm text-zinc-500 mt-1",
+                        "Editors, the servers they use, and the runtimes those servers need. Hover to highlight, click a server to open it."
+                    }
+                }
+                div { class: "flex gap-4 text-xs text-zinc-400",
+                    span { span { class: "inline-block w-2.5 h-2.5 rounded-full bg-indigo-500 mr-1.5" } "Editor" }
+                    span { span { class: "inline-block w-2.5 h-2.5 rounded-full bg-red-500 mr-1.5" } "Hub" }
+                    span { span { class: "inline-block w-2.5 h-2.5 rounded-full bg-zinc-400 mr-1.5" } "Server" }
+                    span { span { class: "inline-block w-2.5 h-2.5 rounded-full bg-amber-500 mr-1.5" } "Runtime" }
+                }
+            }
+
+            if graph.nodes.is_empty() {
+                div { class: "p-8 rounded-2xl glass text-center text-zinc-500 text-sm", "No servers or editor configs found." }
+            } else {
+                div { class: "rounded-2xl glass overflow-hidden",
+                    svg {
+                        width: "100%",
+                        view_box: "0 0 {WIDTH} {HEIGHT}",
+                        for (i, &(a, b)) in graph.edges.iter().enumerate() {
+                            line {
+                                key: "e{i}",
+                                x1: "{positions[a].0}",
+                                y1: "{positions[a].1}",
+                                x2: "{positions[b].0}",
+                                y2: "{positions[b].1}",
+                                stroke: if highlighted.contains(&a) && highlighted.contains(&b) { "#e4e4e7" } else { "#3f3f46" },
+                                stroke_width: "1.5",
+                                opacity: if hovered().is_none() || (highlighted.contains(&a) && highlighted.contains(&b)) { "0.9" } else { "0.2" },
+                            }
+                        }
+                        for (i, node) in graph.nodes.iter().enumerate() {
+                            {
+                                let (fill, stroke, r) = node_style(node.kind);
+                                let (x, y) = positions[i];
+                                let dimmed = hovered().is_some() && !highlighted.contains(&i);
+                                let server = node
+                                    .id
+                                    .strip_prefix("server:")
+                                    .and_then(|id| servers.iter().find(|s| s.id == id))
+                                    .cloned();
+                                let is_running = server
+                                    .as_ref()
+                                    .map(|s| running.read().contains_key(&s.id))
+                                    .unwrap_or(false);
+                                rsx! {
+                                    g {
+                                        key: "{node.id}",
+                                        class: if server.is_some() { "cursor-pointer" } else { "" },
+                                        opacity: if dimmed { "0.3" } else { "1" },
+                                        onmouseenter: move |_| hovered.set(Some(i)),
+                                        onmouseleave: move |_| hovered.set(None),
+                                        onclick: move |_| {
+                                            if let Some(server) = server.clone() {
+                                                props.on_open_console.call(server);
+                                            }
+                                        },
+                                        circle {
+                                            cx: "{x}",
+                                            cy: "{y}",
+                                            r: "{r}",
+                                            fill: fill,
+                                            stroke: if is_running { "#22c55e" } else { stroke },
+                                            stroke_width: "2",
+                                        }
+                                        text {
+                                            x: "{x}",
+                                            y: "{y + r + 14.0}",
+                                            text_anchor: "middle",
+                                            fill: "#d4d4d8",
+                                            font_size: "12",
+                                            "{node.label}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::dedupe::{find_duplicates, MergePlan};
+use crate::models::{McpServer, NotificationLevel};
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct DuplicateServersProps {
+    pub on_close: EventHandler<()>,
+}
+
+/// Servers that launch the same command or URL, e.g. from importing a
+/// config twice, with a merge proposal for each set.
+pub fn DuplicateServers(props: DuplicateServersProps) -> Element {
+    let groups = find_duplicates(&APP_STATE.read().servers.read());
+
+    rsx! {
+        div { class: "fixed inset-0 z-50 flex items-center justify-center bg-black/60 p-4 backdrop-blur-md",
+            div {
+                class: "w-full max-w-3xl max-h-[85vh] bg-zinc-950 text-zinc-300 rounded-2xl flex flex-col overflow-hidden border border-zinc-800 shadow-2xl animate-scale-in",
+                "role": "dialog",
+                "aria-label": "Duplicate servers",
+                div { class: "flex justify-between items-center p-5 bg-zinc-900 border-b border-zinc-800",
+                    div {
+                        h2 { class: "font-bold text-xl text-white", "🧹 Duplicate servers" }
+                        p { class: "text-xs text-zinc-500 mt-1",
+                            "Servers with the same command and args, or the same URL. Merging keeps one and folds the others' env into it."
+                        }
+                    }
+                    button {
+                        class: "p-2 hover:bg-zinc-800 rounded-full transition-colors",
+                        onclick: move |_| props.on_close.call(()),
+                        "✕"
+                    }
+                }
+                div { class: "p-5 space-y-4 overflow-y-auto custom-scrollbar",
+                    if groups.is_empty() {
+                        p { class: "py-8 text-center text-sm text-zinc-500", "No duplicate servers found." }
+                    }
+                    for group in groups {
+                        DuplicateGroup {
+                            key: "{group[0].id}",
+                            group: group.clone(),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn DuplicateGroup(group: Vec<McpServer>) -> Element {
+    let mut keep_id = use_signal(|| group[0].id.clone());
+    let mut merging = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let Some(plan) = MergePlan::new(&group, &keep_id()) else {
+        return rsx! {};
+    };
+    let mut env_keys: Vec<String> = plan.env.keys().cloned().collect();
+    env_keys.sort();
+    let env_keys = env_keys.join(", ");
+    let conflicts = plan.conflicts.join(", ");
+    let launches = if plan.keep.is_remote() {
+        plan.keep.url.clone().unwrap_or_default()
+    } else {
+        let mut parts = vec![plan.keep.command.clone().unwrap_or_default()];
+        parts.extend(plan.keep.args.iter().flatten().cloned());
+        parts.join(" ")
+    };
+    let radio_name = format!("keep-{}", group[0].id);
+
+    let merge = {
+        let plan = plan.clone();
+        move |_| {
+            let plan = plan.clone();
+            merging.set(true);
+            error.set(None);
+            spawn(async move {
+                let summary = plan.summary();
+                match AppState::merge_duplicates(plan).await {
+                    Ok(_) => AppState::push_notification(summary, NotificationLevel::Success),
+                    Err(e) => error.set(Some(e)),
+                }
+                merging.set(false);
+            });
+        }
+    };
+
+    rsx! {
+        div { class: "p-4 rounded-xl border border-zinc-800 space-y-3",
+            div { class: "font-mono text-xs text-zinc-500 truncate", "{launches}" }
+            fieldset { class: "space-y-1",
+                legend { class: "text-xs font-bold text-zinc-500 uppercase mb-1", "Keep" }
+                for server in group.iter() {
+                    label { key: "{server.id}", class: "flex items-center gap-3 text-sm",
+                        input {
+                            r#type: "radio",
+                            name: "{radio_name}",
+                            checked: server.id == plan.keep.id,
+                            onchange: {
+                                let id = server.id.clone();
+                                move |_| keep_id.set(id.clone())
+                            }
+                        }
+                        span { class: "text-white", "{server.name}" }
+                        span { class: "text-xs text-zinc-600", "added {server.created_at}" }
+                        if server.is_archived {
+                            span { class: "text-[10px] text-zinc-500", "archived" }
+                        }
+                    }
+                }
+            }
+            if !env_keys.is_empty() {
+                p { class: "text-xs text-zinc-400",
+                    "Merged env: "
+                    span { class: "font-mono", "{env_keys}" }
+                }
+            }
+            if !plan.conflicts.is_empty() {
+                p { class: "text-xs text-amber-400",
+                    "⚠️ Different values for {conflicts}; the kept server's values are used."
+                }
+            }
+            if let Some(e) = error() {
+                p { class: "text-xs text-red-400", "role": "alert", "{e}" }
+            }
+            div { class: "flex justify-end",
+                button {
+                    class: "px-4 py-2 bg-indigo-600 hover:bg-indigo-500 text-white rounded-lg text-xs font-bold transition-colors disabled:opacity-50",
+                    disabled: merging(),
+                    onclick: merge,
+                    "Merge {plan.remove.len()} into {plan.keep.name}"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::elicitation::{FieldKind, PendingElicitation};
+use crate::models::NotificationLevel;
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+/// Shows the oldest form a server asked the user to fill in. Renders
+/// nothing while none are waiting.
+pub fn ElicitationForm() -> Element {
+    let pending = APP_STATE.read().elicitation_requests.read().first().cloned();
+    let Some(pending) = pending else {
+        return rsx! {};
+    };
+    let key = format!("{}-{}", pending.server_id, pending.request_id);
+    rsx! {
+        ElicitationDialog { key: "{key}", pending }
+    }
+}
+
+/// One request's form; keyed by request so answers start fresh.
+#[component]
+fn ElicitationDialog(pending: PendingElicitation) -> Element {
+    let mut answers = use_signal(|| {
+        pending
+            .fields
+            .iter()
+            .map(|f| (f.name.clone(), f.default.clone()))
+            .collect::<HashMap<_, _>>()
+    });
+    let mut error = use_signal(|| None::<String>);
+    let mut sending = use_signal(|| false);
+    let server_name = pending.server_name.clone();
+    let message = pending.message.clone();
+
+    let mut send = {
+        let pending = pending.clone();
+        move |values: Option<HashMap<String, String>>| {
+            if let Some(values) = &values {
+                if let Err(e) = crate::elicitation::content(&pending.fields, values) {
+                    error.set(Some(e));
+                    return;
+                }
+            }
+            let server_id = pending.server_id.clone();
+            let request_id = pending.request_id.clone();
+            sending.set(true);
+            spawn(async move {
+                if let Err(e) = AppState::answer_elicitation(server_id, request_id, values).await {
+                    AppState::push_notification(e, NotificationLevel::Error);
+                }
+                sending.set(false);
+            });
+        }
+    };
+    let mut decline = send.clone();
+
+    rsx! {
+        div { class: "fixed inset-0 z-50 flex items-center justify-center bg-black/60 p-4 backdrop-blur-md",
+            div { class: "w-full max-w-lg max-h-[85vh] bg-zinc-950 text-zinc-300 rounded-2xl flex flex-col overflow-hidden border border-zinc-800 shadow-2xl animate-scale-in",
+                div { class: "p-5 bg-zinc-900 border-b border-zinc-800",
+                    h2 { class: "font-bold text-xl text-white", "📝 {server_name} needs some input" }
+                    p { class: "text-sm text-zinc-400 mt-1 whitespace-pre-wrap", "{message}" }
+                }
+
+                div { class: "flex-1 overflow-y-auto custom-scrollbar p-6 space-y-4",
+                    for field in pending.fields.clone() {
+                        {
+                            let name = field.name.clone();
+                            let value = answers.read().get(&field.name).cloned().unwrap_or_default();
+                            let marker = if field.required { " *" } else { "" };
+                            rsx! {
+                                div { key: "{field.name}", class: "space-y-1",
+                                    label { class: "text-xs font-bold text-zinc-500 uppercase tracking-wider", "{field.title}{marker}" }
+                                    if let Some(description) = field.description.clone() {
+                                        p { class: "text-xs text-zinc-500", "{description}" }
+                                    }
+                                    match field.kind.clone() {
+                                        FieldKind::Boolean => rsx! {
+                                            input {
+                                                r#type: "checkbox",
+                                                checked: value == "true",
+                                                onchange: move |evt| {
+                                                    answers.write().insert(name.clone(), evt.checked().to_string());
+                                                }
+                                            }
+                                        },
+                                        FieldKind::Choice { values, labels } => rsx! {
+                                            select {
+                                                class: "w-full px-3 py-2 bg-zinc-900 border border-zinc-700 rounded-xl text-sm text-white focus:outline-none focus:border-indigo-500",
+                                                onchange: move |evt| {
+                                                    answers.write().insert(name.clone(), evt.value());
+                                                },
+                                                option { value: "", selected: value.is_empty(), "—" }
+                                                for (option_value, label) in values.into_iter().zip(labels) {
+                                                    option {
+                                                        key: "{option_value}",
+                                                        value: "{option_value}",
+                                                        selected: option_value == value,
+                                                        "{label}"
+                                                    }
+                                                }
+                                            }
+                                        },
+                                        kind => {
+                                            let input_type = if kind == FieldKind::Text { "text" } else { "number" };
+                                            rsx! {
+                                                input {
+                                                    class: "w-full px-3 py-2 bg-zinc-900 border border-zinc-700 rounded-xl text-sm text-white focus:outline-none focus:border-indigo-500",
+                                                    r#type: "{input_type}",
+                                                    value: "{value}",
+                                                    oninput: move |evt| {
+                                                        answers.write().insert(name.clone(), evt.value());
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if let Some(e) = error() {
+                        p { class: "text-sm text-red-400", "{e}" }
+                    }
+                }
+
+                div { class: "p-5 bg-zinc-900 border-t border-zinc-800 flex justify-end gap-3",
+                    button {
+                        class: "px-5 py-2.5 text-zinc-400 hover:text-white transition-colors disabled:opacity-40",
+                        disabled: sending(),
+                        onclick: move |_| decline(None),
+                        "Decline"
+                    }
+                    button {
+                        class: "px-6 py-2.5 bg-indigo-600 hover:bg-indigo-500 text-white rounded-xl text-sm font-bold transition-colors shadow-lg shadow-indigo-500/20 disabled:opacity-40",
+                        disabled: sending(),
+                        onclick: move |_| send(Some(answers.cloned())),
+                        if sending() { "Sending..." } else { "Submit" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::env_rotation::{env_keys, servers_with_key};
+use crate::models::{McpServer, NotificationLevel};
+use crate::server_diff::mask_value;
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct EnvRotationProps {
+    pub on_close: EventHandler<()>,
+}
+
+/// Rotate an env value, e.g. an API key, on every server that uses it:
+/// pick the key, review the affected servers, update them together and
+/// restart the running ones.
+pub fn EnvRotation(props: EnvRotationProps) -> Element {
+    let servers = APP_STATE.read().servers.cloned();
+    let keys = env_keys(&servers);
+    let mut key = use_signal(|| keys.first().map(|(k, _)| k.clone()).unwrap_or_default());
+    let mut value = use_signal(String::new);
+    let mut reveal = use_signal(|| false);
+    // Servers left out of the rotation, by id
+    let mut excluded = use_signal(HashSet::<String>::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut saving = use_signal(|| false);
+    // Servers updated by the rotation, once it is done
+    let mut updated = use_signal(|| None::<Vec<McpServer>>);
+
+    let running = APP_STATE.read().processes.cloned();
+    let affected: Vec<McpServer> = servers_with_key(&servers, &key())
+        .into_iter()
+        .cloned()
+        .collect();
+    let selected: Vec<String> = affected
+        .iter()
+        .filter(|s| !excluded.read().contains(&s.id))
+        .map(|s| s.id.clone())
+        .collect();
+    let selected_count = selected.len();
+    let can_update = !value().is_empty() && selected_count > 0 && !saving();
+
+    let rotate = move |_| {
+        let ids = selected.clone();
+        saving.set(true);
+        error.set(None);
+        spawn(async move {
+            match AppState::set_env_value(ids, key(), value()).await {
+                Ok(servers) => {
+                    AppState::push_notification(
+                        format!("Updated {} on {} server(s)", key(), servers.len()),
+                        NotificationLevel::Success,
+                    );
+                    updated.set(Some(servers));
+                }
+                Err(e) => error.set(Some(e)),
+            }
+            saving.set(false);
+        });
+    };
+
+    let to_restart: Vec<McpServer> = updated
+        .read()
+        .iter()
+        .flatten()
+        .filter(|s| running.contains_key(&s.id))
+        .cloned()
+        .collect();
+    let restart_count = to_restart.len();
+    let restart = move |_| {
+        let servers = to_restart.clone();
+        spawn(async move {
+            for server in servers {
+                AppState::stop_server_process(&server.id).await;
+                AppState::start_server_with_notification(server).await;
+            }
+        });
+        props.on_close.call(());
+    };
+
+    rsx! {
+        div { class: "fixed inset-0 z-50 flex items-center justify-center bg-black/60 p-4 backdrop-blur-md",
+            div {
+                class: "w-full max-w-2xl max-h-[85vh] bg-zinc-950 text-zinc-300 rounded-2xl flex flex-col overflow-hidden border border-zinc-800 shadow-2xl animate-scale-in",
+                "role": "dialog",
+                "aria-label": "Rotate an env variable",
+                div { class: "flex justify-between items-center p-5 bg-zinc-900 border-b border-zinc-800",
+                    div {
+                        h2 { class: "font-bold text-xl text-white", "🔑 Rotate env variable" }
+                        p { class: "text-xs text-zinc-500 mt-1",
+                            "Update a shared value, like an API key, on every server that uses it."
+                        }
+                    }
+                    button {
+                        class: "p-2 hover:bg-zinc-800 rounded-full transition-colors",
+                        onclick: move |_| props.on_close.call(()),
+                        "✕"
+                    }
+                }
+
+                if let Some(done) = updated() {
+                    div { class: "p-5 space-y-4",
+                        p { class: "text-sm text-white",
+                            "Updated {key} on {done.len()} server(s)."
+                        }
+                        if restart_count > 0 {
+                            p { class: "text-xs text-amber-400",
+                                "{restart_count} of them are running with the old value until restarted."
+                            }
+                        }
+                    }
+                    div { class: "p-5 bg-zinc-900 border-t border-zinc-800 flex justify-end gap-3",
+                        button {
+                            class: "px-5 py-2.5 text-zinc-400 hover:text-white transition-colors",
+                            onclick: move |_| props.on_close.call(()),
+                            if restart_count > 0 { "Later" } else { "Done" }
+                        }
+                        if restart_count > 0 {
+                            button {
+                                class: "px-6 py-2.5 bg-indigo-600 hover:bg-indigo-500 text-white rounded-xl text-sm font-bold transition-colors",
+                                onclick: restart,
+                                "Restart {restart_count} running server(s)"
+                            }
+                        }
+                    }
+                } else if keys.is_empty() {
+                    div { class: "p-8 text-center text-sm text-zinc-500", "No server sets any env variables." }
+                } else {
+                    div { class: "p-5 space-y-4 overflow-y-auto custom-scrollbar",
+                        div { class: "grid grid-cols-2 gap-3",
+                            div {
+                                label { r#for: "rotate-key", class: "block text-xs font-bold mb-1 text-zinc-500 uppercase", "Variable" }
+                                select {
+                                    id: "rotate-key",
+                                    class: "w-full bg-zinc-900 border border-zinc-800 rounded-lg px-3 py-2 text-sm font-mono text-white focus:outline-none focus:border-indigo-500",
+                                    onchange: move |evt| {
+                                        key.set(evt.value());
+                                        excluded.set(HashSet::new());
+                                    },
+                                    for (k, count) in keys.iter() {
+                                        option { key: "{k}", value: "{k}", selected: *k == key(), "{k} ({count})" }
+                                    }
+                                }
+                            }
+                            div {
+                                label { r#for: "rotate-value", class: "block text-xs font-bold mb-1 text-zinc-500 uppercase", "New value" }
+                                div { class: "flex gap-2",
+                                    input {
+                                        id: "rotate-value",
+                                        r#type: if reveal() { "text" } else { "password" },
+                                        class: "flex-1 min-w-0 px-3 py-2 bg-zinc-900 border border-zinc-800 rounded-lg font-mono text-sm text-white focus:outline-none focus:border-indigo-500",
+                                        value: "{value}",
+                                        oninput: move |evt| value.set(evt.value()),
+                                    }
+                                    button {
+                                        class: "px-2 text-xs text-zinc-500 hover:text-white",
+                                        onclick: move |_| reveal.toggle(),
+                                        if reveal() { "Hide" } else { "Show" }
+                                    }
+                                }
+                            }
+                        }
+
+                        div { class: "rounded-xl border border-zinc-800 divide-y divide-zinc-800",
+                            for server in affected.iter() {
+                                {
+                                    let id = server.id.clone();
+                                    let current = server
+                                        .env
+                                        .as_ref()
+                                        .and_then(|env| env.get(&key()))
+                                        .cloned()
+                                        .unwrap_or_default();
+                                    let shown = if reveal() { current.clone() } else { mask_value(&current) };
+                                    let included = !excluded.read().contains(&id);
+                                    rsx! {
+                                        label { key: "{id}", class: "flex items-center gap-3 px-3 py-2 text-sm",
+                                            input {
+                                                r#type: "checkbox",
+                                                checked: included,
+                                                onchange: move |evt| {
+                                                    let id = id.clone();
+                                                    excluded.with_mut(|ex| {
+                                                        if evt.checked() {
+                                                            ex.remove(&id);
+                                                        } else {
+                                                            ex.insert(id);
+                                                        }
+                                                    });
+                                                }
+                                            }
+                                            span { class: "flex-1 text-white", "{server.name}" }
+                                            if running.contains_key(&server.id) {
+                                                span { class: "text-[10px] text-green-400", "running" }
+                                            }
+                                            if server.is_archived {
+                                                span { class: "text-[10px] text-zinc-500", "archived" }
+                                            }
+                                            span { class: "font-mono text-xs text-zinc-500", "{shown}" }
+                                            if !value().is_empty() && current == value() {
+                                                span { class: "text-[10px] text-zinc-500", "already set" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        if let Some(e) = error() {
+                            p { class: "text-xs text-red-400", "role": "alert", "{e}" }
+                        }
+                    }
+                    div { class: "p-5 bg-zinc-900 border-t border-zinc-800 flex justify-end gap-3",
+                        button {
+                            class: "px-5 py-2.5 text-zinc-400 hover:text-white transition-colors",
+                            onclick: move |_| props.on_close.call(()),
+                            "Cancel"
+                        }
+                        button {
+                            class: "px-6 py-2.5 bg-indigo-600 hover:bg-indigo-500 text-white rounded-xl text-sm font-bold transition-colors disabled:opacity-50",
+                            disabled: !can_update,
+                            onclick: rotate,
+                            if saving() { "Updating…" } else { "Update {selected_count} server(s)" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -3,7 +3,7 @@ use crate::models::{
     prepare_install_args, CreateServerArgs, GitHubSearchResponse, RegistryInstallConfig,
     RegistryItem, RegistryServer, WizardAction,
 };
-use crate::state::APP_STATE;
+use crate::state::{AppState, APP_STATE};
 use dioxus::prelude::*;
 
 const GITHUB_SEARCH_API: &str = "https://api.github.com/search/repositories?q=topic:mcp-server&sort=stars&order=desc&per_page=100";
@@ -241,8 +241,10 @@ pub async fn fetch_all_registries(query: &str) -> Vec<RegistryItem> {
         }
     }
 
+    let all_items = crate::registry_validation::keep_valid(all_items, "registry search");
+
     // Cache all results
-    if let Ok(db) = Database::new() {
+    if let Some(db) = app_db() {
         let _ = db.cache_registry(&all_items, "all");
     }
 
@@ -299,8 +301,11 @@ async fn fetch_community_registry() -> Vec<RegistryItem> {
                 });
             }
 
+            // One malformed repo shouldn't break the Explorer or the cache
+            items = crate::registry_validation::keep_valid(items, "GitHub");
+
             // Cache community results
-            if let Ok(db) = Database::new() {
+            if let Some(db) = app_db() {
                 let _ = db.cache_registry(&items, "community");
             }
         }
@@ -312,8 +317,15 @@ async fn fetch_community_registry() -> Vec<RegistryItem> {
 async fn fetch_dynamic_registry() -> Vec<RegistryItem> {
     let mut items = get_official_registry();
 
-    // 1. Fetch Community results
-    let community_items = fetch_community_registry().await;
+    // 1. Fetch Community results (once per session)
+    let cached = APP_STATE.read().community_servers.cloned();
+    let community_items = if cached.is_empty() {
+        let fetched = fetch_community_registry().await;
+        APP_STATE.write().community_servers.set(fetched.clone());
+        fetched
+    } else {
+        cached
+    };
 
     // Merge logic: prefer official items if names collide?
     for item in community_items {
@@ -325,20 +337,24 @@ async fn fetch_dynamic_registry() -> Vec<RegistryItem> {
         }
     }
 
+    crate::state::AppState::record_registry_snapshot(&items);
     items
 }
 
 /// Fetch registry with explicit cache check (useful for forcing refresh)
 #[allow(dead_code)]
 pub async fn fetch_registry_with_cache(force_refresh: bool) -> Vec<RegistryItem> {
-    let db = Database::new().ok();
+    let db = app_db();
 
     // Check if we should use cache
     if !force_refresh {
         if let Some(ref db) = db {
+            let _ = db.bootstrap_registry();
             // Use cache if less than 24 hours old
             if let Ok(false) = db.is_cache_stale("github", 24) {
                 if let Ok(cached) = db.get_cached_registry(None) {
+                    // Cached before items were validated
+                    let cached = crate::registry_validation::keep_valid(cached, "the cache");
                     if !cached.is_empty() {
                         return cached;
                     }
@@ -351,6 +367,137 @@ pub async fn fetch_registry_with_cache(force_refresh: bool) -> Vec<RegistryItem>
     fetch_dynamic_registry().await
 }
 
+/// Card index reached from `index` by an arrow key in a grid of `columns`
+/// columns holding `len` cards. `None` for keys that don't move focus.
+fn grid_step(index: usize, key: &Key, columns: usize, len: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let columns = columns.max(1);
+    let last = len - 1;
+    let next = match key {
+        Key::ArrowRight => (index + 1).min(last),
+        Key::ArrowLeft => index.saturating_sub(1),
+        Key::ArrowDown => {
+            if index + columns <= last {
+                index + columns
+            } else {
+                index
+            }
+        }
+        Key::ArrowUp => index.checked_sub(columns).unwrap_or(index),
+        Key::Home => 0,
+        Key::End => last,
+        _ => return None,
+    };
+    Some(next)
+}
+
+/// Number of columns the registry grid currently renders with.
+async fn grid_columns() -> usize {
+    document::eval(
+        r#"const grid = document.getElementById("registry-grid");
+           return grid ? getComputedStyle(grid).gridTemplateColumns.split(" ").length : 1;"#,
+    )
+    .await
+    .ok()
+    .and_then(|v| v.as_u64())
+    .unwrap_or(1) as usize
+}
+
+/// Shell command equivalent to installing `item`, e.g. `npx -y pkg`.
+fn install_command(item: &RegistryItem) -> String {
+    let args = prepare_install_args(item, None);
+    std::iter::once(args.command.unwrap_or_default())
+        .chain(args.args.unwrap_or_default())
+        .map(|part| {
+            if part.is_empty() || part.contains(|c: char| c.is_whitespace() || c == '\'') {
+                format!("'{}'", part.replace('\'', r"'\''"))
+            } else {
+                part
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Raw README URL for a GitHub repository or folder link.
+fn readme_url(homepage: &str) -> Option<String> {
+    let path = homepage
+        .split("github.com/")
+        .nth(1)?
+        .trim_end_matches('/')
+        .trim_end_matches(".git");
+    let parts: Vec<&str> = path.split('/').collect();
+    match parts.as_slice() {
+        [owner, repo] => Some(format!(
+            "https://raw.githubusercontent.com/{}/{}/HEAD/README.md",
+            owner, repo
+        )),
+        [owner, repo, "tree", branch, rest @ ..] => Some(format!(
+            "https://raw.githubusercontent.com/{}/{}/{}/{}README.md",
+            owner,
+            repo,
+            branch,
+            rest.iter().map(|p| format!("{}/", p)).collect::<String>()
+        )),
+        [owner, repo, "blob", branch, rest @ ..]
+            if rest
+                .last()
+                .is_some_and(|f| f.to_lowercase().ends_with(".md")) =>
+        {
+            Some(format!(
+                "https://raw.githubusercontent.com/{}/{}/{}/{}",
+                owner,
+                repo,
+                branch,
+                rest.join("/")
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// npm package launched by `item`, if it runs through npx.
+fn npm_package(item: &RegistryItem) -> Option<String> {
+    let args = prepare_install_args(item, None);
+    if args.command.as_deref() != Some("npx") {
+        return None;
+    }
+    args.args?.into_iter().find(|a| !a.starts_with('-'))
+}
+
+/// README text for `item`, from its GitHub homepage or else from npm.
+async fn fetch_readme(item: &RegistryItem) -> Result<String, String> {
+    let client = reqwest::Client::new();
+    if let Some(url) = item.server.homepage.as_deref().and_then(readme_url) {
+        if let Ok(res) = client
+            .get(&url)
+            .header("User-Agent", "Open-MCP-Manager")
+            .send()
+            .await
+        {
+            if res.status().is_success() {
+                return res.text().await.map_err(|e| e.to_string());
+            }
+        }
+    }
+    if let Some(pkg) = npm_package(item) {
+        let url = format!("https://registry.npmjs.org/{}", pkg.replace('/', "%2F"));
+        let res = client
+            .get(&url)
+            .header("User-Agent", "Open-MCP-Manager")
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let body: serde_json::Value = res.json().await.map_err(|e| e.to_string())?;
+        if let Some(readme) = body["readme"].as_str().filter(|r| !r.trim().is_empty()) {
+            return Ok(readme.to_string());
+        }
+    }
+    Err("No README found for this server.".to_string())
+}
+
 pub fn detect_config_from_url(url: &str) -> Option<CreateServerArgs> {
     let url_lower = url.to_lowercase();
 
@@ -421,6 +568,102 @@ pub fn Explorer(props: ExplorerProps) -> Element {
     // Stores the collected inputs. Key = Env Var Name, Value = User Input
     let mut wizard_env_data = use_signal(std::collections::HashMap::<String, String>::new);
 
+    // README preview: the item and its text once loaded
+    let mut readme_item = use_signal(|| None::<RegistryItem>);
+    let mut readme = use_signal(|| None::<Result<String, String>>);
+    let mut copied_command = use_signal(|| None::<String>);
+
+    // Keyboard focus within the results grid
+    let mut focused = use_signal(|| None::<usize>);
+
+    let mut install_item = move |item: RegistryItem| {
+        if let Some(config) = &item.install_config {
+            if config.wizard.is_some() {
+                active_wizard_item.set(Some(item.clone()));
+                active_wizard_step.set(0);
+                wizard_env_data.write().clear();
+            } else {
+                let args = prepare_install_args(&item, None);
+                (props.on_install)(args);
+            }
+        }
+    };
+
+    let mut open_readme = move |item: RegistryItem| {
+        readme.set(None);
+        readme_item.set(Some(item.clone()));
+        spawn(async move { readme.set(Some(fetch_readme(&item).await)) });
+    };
+
+    let onkeydown = move |evt: KeyboardEvent| {
+        if active_wizard_item.read().is_some() {
+            return;
+        }
+        let key = evt.key();
+        if readme_item.read().is_some() {
+            if key == Key::Escape {
+                evt.prevent_default();
+                readme_item.set(None);
+                readme.set(None);
+            }
+            return;
+        }
+        let items = results.read().clone();
+        match key {
+            Key::Escape => (props.on_close)(()),
+            Key::Character(c) if c == "/" => {
+                evt.prevent_default();
+                let _ = document::eval(r#"document.getElementById("registry-search")?.focus();"#);
+            }
+            Key::Character(c) if c == "i" => {
+                let installed = |item: &RegistryItem| {
+                    APP_STATE
+                        .read()
+                        .servers
+                        .read()
+                        .iter()
+                        .any(|s| s.name == item.server.name)
+                };
+                if let Some(item) = focused()
+                    .and_then(|i| items.get(i))
+                    .filter(|i| !installed(i))
+                {
+                    install_item(item.clone());
+                }
+            }
+            Key::Enter => {
+                if let Some(item) = focused().and_then(|i| items.get(i)) {
+                    evt.prevent_default();
+                    open_readme(item.clone());
+                }
+            }
+            key => {
+                let Some(current) = focused() else {
+                    if grid_step(0, &key, 1, items.len()).is_some() {
+                        evt.prevent_default();
+                        focused.set(Some(0));
+                    }
+                    return;
+                };
+                if grid_step(current, &key, 1, items.len()).is_none() {
+                    return;
+                }
+                evt.prevent_default();
+                let len = items.len();
+                spawn(async move {
+                    let columns = grid_columns().await;
+                    if let Some(next) = grid_step(current, &key, columns, len) {
+                        focused.set(Some(next));
+                        let _ = document::eval(&format!(
+                            r#"document.getElementById("registry-card-{}")?.scrollIntoView({{ block: "nearest" }});"#,
+                            next
+                        ));
+                    }
+                });
+            }
+        }
+    };
+
     // Heuristic detection logic
     let install_from_url = move |_| {
         let u = url_input.read().clone();
@@ -467,6 +710,7 @@ pub fn Explorer(props: ExplorerProps) -> Element {
                     if let Some(step) = steps.get(step_idx) {
                         let item_name = item.server.name.clone();
                         let total_steps = steps.len();
+                        let locale = AppState::locale();
 
                         rsx! {
                             div {
@@ -492,8 +736,8 @@ pub fn Explorer(props: ExplorerProps) -> Element {
                                 // Wizard Content
                                 div {
                                     class: "flex-1 flex flex-col items-center justify-center max-w-2xl mx-auto w-full gap-6 text-center",
-                                    h3 { class: "text-xl font-bold", "{step.title}" }
-                                    p { class: "text-zinc-600 dark:text-zinc-400 mb-4", "{step.description}" }
+                                    h3 { class: "text-xl font-bold", "{step.title.get(&locale)}" }
+                                    p { class: "text-zinc-600 dark:text-zinc-400 mb-4", "{step.description.get(&locale)}" }
 
                                     {
                                         match &step.action {
@@ -502,7 +746,7 @@ pub fn Explorer(props: ExplorerProps) -> Element {
                                                     class: "px-6 py-3 bg-blue-600 text-white rounded-lg font-bold hover:bg-blue-700 flex items-center gap-2",
                                                     href: "{url}",
                                                     target: "_blank",
-                                                    "{label}"
+                                                    "{label.get(&locale)}"
                                                 }
                                             },
                                             WizardAction::Input { key, label, placeholder } => {
@@ -510,10 +754,10 @@ pub fn Explorer(props: ExplorerProps) -> Element {
                                                 rsx! {
                                                     div {
                                                         class: "w-full text-left",
-                                                        label { class: "block text-sm font-bold mb-2", "{label}" }
+                                                        label { class: "block text-sm font-bold mb-2", "{label.get(&locale)}" }
                                                         input {
                                                             class: "w-full px-4 py-3 rounded-lg border dark:bg-zinc-950 dark:border-zinc-700",
-                                                            placeholder: "{placeholder.clone().unwrap_or_default()}",
+                                                            placeholder: "{placeholder.as_ref().map(|p| p.get(&locale)).unwrap_or_default()}",
                                                             value: "{wizard_env_data.read().get(&key).cloned().unwrap_or_default()}",
                                                             oninput: move |evt| {
                                                                 wizard_env_data.write().insert(key.clone(), evt.value());
@@ -523,7 +767,7 @@ pub fn Explorer(props: ExplorerProps) -> Element {
                                                 }
                                             },
                                             WizardAction::Message { text } => rsx! {
-                                                div { class: "p-4 bg-zinc-100 dark:bg-zinc-800 rounded-lg", "{text}" }
+                                                div { class: "p-4 bg-zinc-100 dark:bg-zinc-800 rounded-lg", "{text.get(&locale)}" }
                                             }
                                         }
                                     }
@@ -581,6 +825,39 @@ pub fn Explorer(props: ExplorerProps) -> Element {
             rsx! {}
         }
     };
+    let readme_overlay = match readme_item() {
+        Some(item) => rsx! {
+            div { class: "absolute inset-0 z-40 bg-zinc-950 flex flex-col animate-fade-in",
+                div { class: "flex justify-between items-center p-6 border-b border-white-5 bg-zinc-900/50",
+                    div {
+                        h2 { class: "text-2xl font-bold text-white", "{item.server.name}" }
+                        p { class: "text-xs font-mono text-zinc-500 mt-1", "{install_command(&item)}" }
+                    }
+                    button {
+                        class: "p-2 hover:bg-zinc-800 rounded-full transition-colors",
+                        onclick: move |_| {
+                            readme_item.set(None);
+                            readme.set(None);
+                        },
+                        "✕"
+                    }
+                }
+                div { class: "flex-1 overflow-y-auto p-8 custom-scrollbar",
+                    match readme() {
+                        None => rsx! { div { class: "text-zinc-400", "Loading README..." } },
+                        Some(Err(e)) => rsx! { div { class: "text-zinc-500", "{e}" } },
+                        Some(Ok(text)) => rsx! {
+                            div {
+                                class: "markdown-body max-w-3xl mx-auto text-sm text-zinc-300 space-y-3",
+                                dangerous_inner_html: crate::output_format::markdown_to_html(&text)
+                            }
+                        },
+                    }
+                }
+            }
+        },
+        None => rsx! {},
+    };
     let items = results.read().clone();
 
     rsx! {
@@ -588,8 +865,14 @@ pub fn Explorer(props: ExplorerProps) -> Element {
             class: "fixed inset-0 z-50 bg-black/60 backdrop-blur-sm flex items-center justify-center p-4 animate-fade-in",
             onclick: move |_| (props.on_close)(()),
             div {
-                class: "glass-panel w-full max-w-5xl h-[80vh] rounded-2xl shadow-2xl flex flex-col overflow-hidden animate-scale-in border border-zinc-800",
+                id: "registry-panel",
+                class: "glass-panel w-full max-w-5xl h-[80vh] rounded-2xl shadow-2xl flex flex-col overflow-hidden animate-scale-in border border-zinc-800 outline-none",
+                tabindex: "-1",
                 onclick: move |evt| evt.stop_propagation(),
+                onmounted: move |evt| async move {
+                    let _ = evt.data().set_focus(true).await;
+                },
+                onkeydown,
 
                 // Header
                 div {
@@ -609,6 +892,7 @@ pub fn Explorer(props: ExplorerProps) -> Element {
                                  value: "{url_input}",
                                  oninput: move |evt| url_input.set(evt.value()),
                                  onkeydown: move |evt| {
+                                     evt.stop_propagation();
                                      if evt.key() == Key::Enter && !url_input.read().is_empty() {
                                          install_from_url(());
                                      }
@@ -622,11 +906,22 @@ pub fn Explorer(props: ExplorerProps) -> Element {
                              class: "relative",
                              input {
                                  class: "pl-10 pr-4 py-2 w-64 rounded-xl border border-white-10 bg-black-20 text-white focus:outline-none focus:ring-2 focus:ring-red-500/50 placeholder-zinc-600 transition-all",
-                                 placeholder: "Search registry...",
+                                 id: "registry-search",
+                                 placeholder: "Search registry... ( / )",
                                  value: "{query}",
                                  oninput: move |evt| {
                                      query.set(evt.value());
+                                     focused.set(None);
                                      search(());
+                                 },
+                                 onkeydown: move |evt| {
+                                     // Escape and arrows hand control back to the grid
+                                     if evt.key() == Key::Escape || evt.key() == Key::ArrowDown {
+                                         evt.prevent_default();
+                                         focused.set(Some(0));
+                                         let _ = document::eval(r#"document.getElementById("registry-search")?.blur(); document.getElementById("registry-panel")?.focus();"#);
+                                     }
+                                     evt.stop_propagation();
                                  }
                              }
                              div { class: "absolute left-3 top-2.5 text-zinc-500", "🔍" }
@@ -641,10 +936,13 @@ pub fn Explorer(props: ExplorerProps) -> Element {
                         div { class: "flex justify-center items-center h-full text-zinc-400", "Loading..." }
                     } else {
                         div {
+                            id: "registry-grid",
                             class: "grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-4",
-                            for item in items {
+                            for (index, item) in items.into_iter().enumerate() {
                                 div {
-                                    class: "group relative flex flex-col justify-between h-full bg-zinc-900/50 p-5 rounded-2xl border border-white-5 hover:border-red-500/30 hover:bg-zinc-900 transition-all duration-300",
+                                    id: "registry-card-{index}",
+                                    class: if focused() == Some(index) { "group relative flex flex-col justify-between h-full bg-zinc-900 p-5 rounded-2xl border border-red-500/60 ring-2 ring-red-500/30 transition-all duration-300" } else { "group relative flex flex-col justify-between h-full bg-zinc-900/50 p-5 rounded-2xl border border-white-5 hover:border-red-500/30 hover:bg-zinc-900 transition-all duration-300" },
+                                    onclick: move |_| focused.set(Some(index)),
                                     div {
                                         div { class: "flex justify-between items-start mb-3",
                                             h3 { class: "font-bold text-lg text-white group-hover:text-red-400 transition-colors", "{item.server.name}" }
@@ -666,6 +964,51 @@ pub fn Explorer(props: ExplorerProps) -> Element {
                                         }
                                     }
 
+                                    // Quick actions
+                                    div { class: "flex items-center gap-1 text-sm",
+                                        if let Some(url) = item.server.homepage.clone() {
+                                            button {
+                                                class: "px-2 py-1 rounded-lg text-zinc-500 hover:text-white hover:bg-white-5 transition-colors",
+                                                title: "Open homepage",
+                                                onclick: move |_| AppState::open_url(&url),
+                                                "🏠"
+                                            }
+                                        }
+                                        if let Some(url) = item.server.bugs.clone() {
+                                            button {
+                                                class: "px-2 py-1 rounded-lg text-zinc-500 hover:text-white hover:bg-white-5 transition-colors",
+                                                title: "Open issues",
+                                                onclick: move |_| AppState::open_url(&url),
+                                                "🐞"
+                                            }
+                                        }
+                                        button {
+                                            class: "px-2 py-1 rounded-lg text-zinc-500 hover:text-white hover:bg-white-5 transition-colors",
+                                            title: "Preview README",
+                                            onclick: {
+                                                let item = item.clone();
+                                                move |_| open_readme(item.clone())
+                                            },
+                                            "📖"
+                                        }
+                                        {
+                                            let command = install_command(&item);
+                                            let copied = copied_command.read().as_deref() == Some(item.server.name.as_str());
+                                            let name = item.server.name.clone();
+                                            rsx! {
+                                                button {
+                                                    class: "px-2 py-1 rounded-lg text-zinc-500 hover:text-white hover:bg-white-5 transition-colors",
+                                                    title: "Copy install command: {command}",
+                                                    onclick: move |_| {
+                                                        AppState::copy_to_clipboard(command.clone());
+                                                        copied_command.set(Some(name.clone()));
+                                                    },
+                                                    if copied { "✓" } else { "📋" }
+                                                }
+                                            }
+                                        }
+                                    }
+
 
                                     // Item Actions
                                     div {
@@ -681,6 +1024,7 @@ pub fn Explorer(props: ExplorerProps) -> Element {
 
                                         {
                                             let installed = APP_STATE.read().servers.read().iter().any(|s| s.name == item.server.name);
+                                            let item = item.clone();
                                             if installed {
                                                 rsx! {
                                                     button {
@@ -695,17 +1039,7 @@ pub fn Explorer(props: ExplorerProps) -> Element {
                                                         class: "relative z-10 px-4 py-2 bg-black dark:bg-white text-white dark:text-black rounded-lg font-bold hover:opacity-80",
                                                         onclick: move |evt| {
                                                             evt.stop_propagation();
-                                                            println!("Install clicked for {}", item.server.name);
-                                                            if let Some(config) = &item.install_config {
-                                                                if config.wizard.is_some() {
-                                                                    active_wizard_item.set(Some(item.clone()));
-                                                                    active_wizard_step.set(0);
-                                                                    wizard_env_data.write().clear();
-                                                                } else {
-                                                                    let args = prepare_install_args(&item, None);
-                                                                    (props.on_install)(args);
-                                                                }
-                                                            }
+                                                            install_item(item.clone());
                                                         },
                                                         "Install"
                                                     }
@@ -721,7 +1055,10 @@ pub fn Explorer(props: ExplorerProps) -> Element {
 
                 // Footer (Close)
                 div {
-                    class: "p-4 border-t border-zinc-200 dark:border-zinc-800 flex justify-end bg-white dark:bg-zinc-900",
+                    class: "p-4 border-t border-zinc-200 dark:border-zinc-800 flex justify-between items-center bg-white dark:bg-zinc-900",
+                    span { class: "text-xs text-zinc-500",
+                        "Arrows to move · Enter for details · i to install · / to search · Esc to close"
+                    }
                     button {
                         class: "px-6 py-2 bg-zinc-200 dark:bg-zinc-800 rounded-lg font-bold hover:bg-zinc-300 dark:hover:bg-zinc-700",
                         onclick: move |_| (props.on_close)(()),
@@ -729,6 +1066,8 @@ pub fn Explorer(props: ExplorerProps) -> Element {
                     }
                 }
 
+                {readme_overlay}
+
                 // Modal Overlay for Wizard
                 {wizard_overlay}
             }
@@ -737,11 +1076,12 @@ pub fn Explorer(props: ExplorerProps) -> Element {
 }
 
 pub fn get_official_registry() -> Vec<RegistryItem> {
-    if let Ok(db) = Database::new() {
-        db.get_cached_registry(Some("official")).unwrap_or_default()
-    } else {
-        Vec::new()
-    }
+    crate::db::official_registry().to_vec()
+}
+
+/// The app's shared database handle, if initialised.
+fn app_db() -> Option<Database> {
+    APP_STATE.read().db.cloned()
 }
 
 #[cfg(test)]
@@ -836,7 +1176,10 @@ mod tests {
         // Check it has wizard steps
         if let Some(config) = &gdrive.unwrap().install_config {
             assert!(config.wizard.is_some());
-            assert!(config.wizard.as_ref().unwrap().len() >= 3); // At least 3 steps
+            let steps = config.wizard.as_ref().unwrap();
+            assert!(steps.len() >= 3); // At least 3 steps
+            assert_eq!(steps[0].title.get("de-DE"), "Zugangsdaten besorgen");
+            assert_eq!(steps[0].title.get("ko"), "Get Credentials");
         }
     }
 
@@ -1039,6 +1382,53 @@ mod tests {
         assert_eq!(args.command, Some("python".to_string()));
     }
 
+    #[test]
+    fn test_grid_step() {
+        // 3 columns, 7 cards:  0 1 2 / 3 4 5 / 6
+        assert_eq!(grid_step(1, &Key::ArrowDown, 3, 7), Some(4));
+        assert_eq!(grid_step(4, &Key::ArrowDown, 3, 7), Some(4));
+        assert_eq!(grid_step(3, &Key::ArrowDown, 3, 7), Some(6));
+        assert_eq!(grid_step(4, &Key::ArrowUp, 3, 7), Some(1));
+        assert_eq!(grid_step(1, &Key::ArrowUp, 3, 7), Some(1));
+        assert_eq!(grid_step(6, &Key::ArrowRight, 3, 7), Some(6));
+        assert_eq!(grid_step(0, &Key::ArrowLeft, 3, 7), Some(0));
+        assert_eq!(grid_step(2, &Key::End, 3, 7), Some(6));
+        assert_eq!(grid_step(2, &Key::Enter, 3, 7), None);
+        assert_eq!(grid_step(0, &Key::ArrowDown, 3, 0), None);
+    }
+
+    #[test]
+    fn test_readme_url() {
+        assert_eq!(
+            readme_url("https://github.com/owner/repo").as_deref(),
+            Some("https://raw.githubusercontent.com/owner/repo/HEAD/README.md")
+        );
+        assert_eq!(
+            readme_url("https://github.com/modelcontextprotocol/servers/tree/main/src/fetch/")
+                .as_deref(),
+            Some("https://raw.githubusercontent.com/modelcontextprotocol/servers/main/src/fetch/README.md")
+        );
+        assert_eq!(
+            readme_url("https://github.com/o/r/blob/main/docs/guide.md").as_deref(),
+            Some("https://raw.githubusercontent.com/o/r/main/docs/guide.md")
+        );
+        assert_eq!(readme_url("https://www.npmjs.com/package/x"), None);
+    }
+
+    #[test]
+    fn test_install_command_and_npm_package() {
+        let item = get_official_registry()
+            .into_iter()
+            .find(|i| i.server.name == "Memory")
+            .unwrap();
+        let command = install_command(&item);
+        assert!(command.starts_with("npx "), "{}", command);
+        assert_eq!(
+            npm_package(&item).as_deref(),
+            Some("@modelcontextprotocol/server-memory")
+        );
+    }
+
     #[test]
     fn test_detect_unknown() {
         let url = "https://example.com/something";
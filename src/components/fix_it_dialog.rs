@@ -0,0 +1,138 @@
+use crate::fixes::{PendingFix, SettingsField};
+use crate::models::McpServer;
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct FixItDialogProps {
+    /// Open the server's Settings at the field that fixes the failure.
+    pub on_open_settings: EventHandler<(McpServer, SettingsField)>,
+}
+
+/// Walks through fixing a server that failed to start for a known reason.
+/// Renders nothing while no start has failed that way.
+pub fn FixItDialog(props: FixItDialogProps) -> Element {
+    let pending = APP_STATE.read().pending_fix.read().clone();
+    let Some(pending) = pending else {
+        return rsx! {};
+    };
+    let key = format!("{}-{:?}", pending.server.id, pending.failure);
+    rsx! {
+        FixDialog { key: "{key}", pending, on_open_settings: props.on_open_settings }
+    }
+}
+
+/// One failure's dialog; keyed by failure so a fix's output starts fresh.
+#[component]
+fn FixDialog(
+    pending: PendingFix,
+    on_open_settings: EventHandler<(McpServer, SettingsField)>,
+) -> Element {
+    let mut output = use_signal(|| None::<Result<Vec<String>, String>>);
+    let mut running = use_signal(|| false);
+    let mut retrying = use_signal(|| false);
+
+    let server_name = pending.server.name.clone();
+    let title = pending.failure.title();
+    let explanation = pending.failure.explanation();
+    let field = pending.failure.field();
+    let fix = pending.failure.command(std::env::consts::OS);
+
+    let open_settings = {
+        let server = pending.server.clone();
+        move |field: SettingsField| {
+            APP_STATE.write().pending_fix.set(None);
+            on_open_settings.call((server.clone(), field));
+        }
+    };
+
+    let run_fix = {
+        let command = fix.as_ref().map(|f| f.command.clone()).unwrap_or_default();
+        move |_| {
+            let command = command.clone();
+            running.set(true);
+            spawn(async move {
+                output.set(Some(AppState::run_fix_command(&command).await));
+                running.set(false);
+            });
+        }
+    };
+
+    let retry = move |_| {
+        retrying.set(true);
+        spawn(async move {
+            AppState::retry_pending_fix().await;
+        });
+    };
+
+    rsx! {
+        div { class: "fixed inset-0 z-50 flex items-center justify-center bg-black/60 p-4 backdrop-blur-md",
+            div { class: "bg-zinc-950 text-zinc-300 w-full max-w-lg rounded-2xl flex flex-col border border-zinc-800 shadow-2xl overflow-hidden animate-scale-in",
+                div { class: "p-5 bg-zinc-900 border-b border-zinc-800",
+                    h2 { class: "font-bold text-xl text-white", "🩺 {server_name}: {title}" }
+                    p { class: "text-sm text-zinc-400 mt-1", "{explanation}" }
+                }
+
+                div { class: "p-6 space-y-4",
+                    if let Some(field) = field {
+                        {
+                            let label = match &field {
+                                SettingsField::Env(key) => format!("Set {} in Settings", key),
+                                SettingsField::Command => "Change the command in Settings".to_string(),
+                            };
+                            let open_settings = open_settings.clone();
+                            rsx! {
+                                button {
+                                    class: "w-full px-4 py-2.5 bg-zinc-800 hover:bg-zinc-700 text-white rounded-xl text-sm font-bold transition-colors",
+                                    onclick: move |_| open_settings(field.clone()),
+                                    "{label}"
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(fix) = fix {
+                        div { class: "space-y-2",
+                            label { class: "text-xs font-bold text-zinc-500 uppercase tracking-wider", "{fix.label}" }
+                            pre { class: "p-3 bg-black/50 border border-zinc-800 rounded-xl font-mono text-xs text-zinc-300 whitespace-pre-wrap break-all", "{fix.command}" }
+                            button {
+                                class: "w-full px-4 py-2.5 bg-zinc-800 hover:bg-zinc-700 text-white rounded-xl text-sm font-bold transition-colors disabled:opacity-40",
+                                disabled: running(),
+                                onclick: run_fix,
+                                if running() { "Running..." } else { "Run this command" }
+                            }
+                        }
+                    }
+
+                    match output() {
+                        Some(Ok(lines)) => {
+                            let text = lines.join("\n");
+                            rsx! {
+                                pre { class: "p-3 max-h-48 overflow-y-auto custom-scrollbar bg-black/50 border border-zinc-800 rounded-xl font-mono text-xs text-zinc-300 whitespace-pre-wrap break-all", "{text}" }
+                                p { class: "text-sm text-emerald-400", "Done. Retry the start to see if it worked." }
+                            }
+                        }
+                        Some(Err(e)) => rsx! {
+                            pre { class: "p-3 bg-red-500/10 border border-red-500/20 rounded-xl font-mono text-xs text-red-400 whitespace-pre-wrap break-all", "{e}" }
+                        },
+                        None => rsx! {},
+                    }
+                }
+
+                div { class: "p-5 bg-zinc-900 border-t border-zinc-800 flex justify-end gap-3",
+                    button {
+                        class: "px-5 py-2.5 text-zinc-400 hover:text-white transition-colors",
+                        onclick: move |_| APP_STATE.write().pending_fix.set(None),
+                        "Dismiss"
+                    }
+                    button {
+                        class: "px-6 py-2.5 bg-indigo-600 hover:bg-indigo-500 text-white rounded-xl text-sm font-bold transition-colors shadow-lg shadow-indigo-500/20 disabled:opacity-40",
+                        disabled: running() || retrying(),
+                        onclick: retry,
+                        if retrying() { "Starting..." } else { "Retry start" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,745 @@
+use crate::catalog::CatalogFormat;
+use crate::models::{FailoverPair, RuleAction};
+use crate::result_cache::tool_key;
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+/// Name of a server by id, falling back to the id for removed servers.
+fn server_name(id: &str) -> String {
+    APP_STATE
+        .read()
+        .servers
+        .read()
+        .iter()
+        .find(|s| s.id == id)
+        .map(|s| s.name.clone())
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// How the hub routes tool calls: result caching for read-only tools,
+/// response size limits, concurrency limits, error budget alerts, the health endpoint, failover to
+/// backup servers and argument rewrite rules, plus an export of the tools it exposes.
+pub fn HubPanel() -> Element {
+    rsx! {
+        div { class: "max-w-4xl mx-auto space-y-6",
+            div {
+                h2 { class: "text-2xl font-bold text-white tracking-tight", "Hub" }
+                p { class: "text-sm text-zinc-500 mt-1",
+                    "How tool calls are routed to your servers."
+                }
+            }
+            ResultCachePanel {}
+            ResponseLimitPanel {}
+            ConcurrencyPanel {}
+            ErrorBudgetPanel {}
+            HealthEndpointPanel {}
+            FailoverPanel {}
+            ToolRulesPanel {}
+            AuditLogPanel {}
+            ToolCatalogPanel {}
+        }
+    }
+}
+
+#[component]
+fn ResultCachePanel() -> Element {
+    let settings = APP_STATE.read().result_cache_settings.cloned();
+    let cache = APP_STATE.read().result_cache;
+    let totals = cache.read().totals();
+    let stats = cache.read().stats().clone();
+    let tools = cache.read().read_only_tools();
+    let cached = cache.read().len();
+    let calls = totals.hits + totals.misses;
+    let hit_rate = if calls == 0 {
+        0
+    } else {
+        totals.hits * 100 / calls
+    };
+
+    let update = move |change: &dyn Fn(&mut crate::models::ResultCacheSettings)| {
+        let mut settings = APP_STATE.read().result_cache_settings.cloned();
+        change(&mut settings);
+        AppState::save_result_cache_settings(settings);
+    };
+
+    rsx! {
+        div { class: "p-6 rounded-2xl glass space-y-4",
+            div { class: "flex items-start justify-between gap-4",
+                div {
+                    h3 { class: "font-bold text-white", "Result cache" }
+                    p { class: "text-xs text-zinc-500 mt-1",
+                        "Answer repeated calls to read-only tools from memory for a short time."
+                    }
+                }
+                label { class: "flex items-center gap-2 text-xs text-zinc-300",
+                    input {
+                        r#type: "checkbox",
+                        checked: settings.enabled,
+                        onchange: move |evt| {
+                            let enabled = evt.checked();
+                            update(&|s| s.enabled = enabled);
+                        }
+                    }
+                    "Enabled"
+                }
+            }
+
+            div { class: "flex items-center gap-6 text-xs text-zinc-400",
+                label { class: "flex items-center gap-2",
+                    "Keep results for"
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        class: "w-20 bg-zinc-900 border border-zinc-800 rounded px-2 py-1 text-white focus:outline-none focus:border-indigo-500",
+                        value: "{settings.ttl_secs}",
+                        onchange: move |evt| {
+                            if let Ok(ttl) = evt.value().parse::<u64>() {
+                                update(&|s| s.ttl_secs = ttl.max(1));
+                            }
+                        }
+                    }
+                    "seconds"
+                }
+                span { "{totals.hits} hits · {totals.misses} misses · {hit_rate}% hit rate · {cached} cached" }
+                button {
+                    class: "ml-auto px-3 py-1 rounded bg-zinc-800 hover:bg-zinc-700 text-zinc-300",
+                    onclick: move |_| AppState::clear_result_cache(),
+                    "Clear"
+                }
+            }
+
+            if tools.is_empty() {
+                p { class: "text-xs text-zinc-500",
+                    "Read-only tools of running servers appear here once their tools are listed."
+                }
+            } else {
+                div { class: "rounded-xl border border-zinc-800 overflow-hidden text-xs",
+                    div { class: "grid grid-cols-[1fr_1fr_5rem_5rem_5rem] gap-3 px-3 py-2 bg-zinc-900 font-bold text-zinc-500 uppercase",
+                        span { "Server" }
+                        span { "Tool" }
+                        span { "Hits" }
+                        span { "Misses" }
+                        span { "Cache" }
+                    }
+                    for (server_id, tool) in tools {
+                        {
+                            let key = tool_key(&server_id, &tool);
+                            let tool_stats = stats.get(&key).copied().unwrap_or_default();
+                            let opted_out = settings.opted_out.contains(&key);
+                            rsx! {
+                                div {
+                                    key: "{key}",
+                                    class: "grid grid-cols-[1fr_1fr_5rem_5rem_5rem] gap-3 px-3 py-2 border-t border-zinc-800 text-zinc-300",
+                                    span { class: "truncate", "{server_name(&server_id)}" }
+                                    span { class: "font-mono truncate", "{tool}" }
+                                    span { "{tool_stats.hits}" }
+                                    span { "{tool_stats.misses}" }
+                                    input {
+                                        r#type: "checkbox",
+                                        title: "Cache this tool's results",
+                                        checked: !opted_out,
+                                        onchange: move |evt| {
+                                            let allow = evt.checked();
+                                            let key = key.clone();
+                                            update(&move |s| {
+                                                s.opted_out.retain(|k| *k != key);
+                                                if !allow {
+                                                    s.opted_out.push(key.clone());
+                                                }
+                                            });
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ResponseLimitPanel() -> Element {
+    let settings = APP_STATE.read().response_limits.cloned();
+
+    let update = move |change: &dyn Fn(&mut crate::models::ResponseLimitSettings)| {
+        let mut settings = APP_STATE.read().response_limits.cloned();
+        change(&mut settings);
+        AppState::save_response_limits(settings);
+    };
+
+    rsx! {
+        div { class: "p-6 rounded-2xl glass space-y-4",
+            div { class: "flex items-start justify-between gap-4",
+                div {
+                    h3 { class: "font-bold text-white", "Response size limit" }
+                    p { class: "text-xs text-zinc-500 mt-1",
+                        "Cut off tool results past this size so a huge output can't freeze the console. The full result is saved to a file you can open from the result."
+                    }
+                }
+                label { class: "flex items-center gap-2 text-xs text-zinc-300",
+                    input {
+                        r#type: "checkbox",
+                        checked: settings.enabled,
+                        onchange: move |evt| {
+                            let enabled = evt.checked();
+                            update(&|s| s.enabled = enabled);
+                        }
+                    }
+                    "Enabled"
+                }
+            }
+
+            label { class: "flex items-center gap-2 text-xs text-zinc-400",
+                "Keep up to"
+                input {
+                    r#type: "number",
+                    min: "1",
+                    class: "w-24 bg-zinc-900 border border-zinc-800 rounded px-2 py-1 text-white focus:outline-none focus:border-indigo-500",
+                    value: "{settings.max_kb}",
+                    onchange: move |evt| {
+                        if let Ok(kb) = evt.value().parse::<u64>() {
+                            update(&|s| s.max_kb = kb.max(1));
+                        }
+                    }
+                }
+                "KB of each result"
+            }
+        }
+    }
+}
+
+#[component]
+fn ConcurrencyPanel() -> Element {
+    let servers = APP_STATE.read().servers.cloned();
+    let limits = APP_STATE.read().concurrency_limits.cloned();
+    let queues = APP_STATE.read().request_queues;
+
+    rsx! {
+        div { class: "p-6 rounded-2xl glass space-y-4",
+            div {
+                h3 { class: "font-bold text-white", "Concurrency" }
+                p { class: "text-xs text-zinc-500 mt-1",
+                    "Cap how many tool calls a server handles at once. Extra calls wait their turn in order."
+                }
+            }
+            if servers.is_empty() {
+                p { class: "text-xs text-zinc-500", "No servers configured." }
+            } else {
+                div { class: "rounded-xl border border-zinc-800 overflow-hidden text-xs",
+                    div { class: "grid grid-cols-[1fr_6rem_4rem_4rem_4rem_4rem] gap-3 px-3 py-2 bg-zinc-900 font-bold text-zinc-500 uppercase",
+                        span { "Server" }
+                        span { "Max calls" }
+                        span { "Running" }
+                        span { "Waiting" }
+                        span { "Peak" }
+                        span { "Queued" }
+                    }
+                    for srv in servers {
+                        {
+                            let stats = queues
+                                .read()
+                                .get(&srv.id)
+                                .map(|q| q.stats())
+                                .unwrap_or_default();
+                            let limit = limits.get(&srv.id).map(|n| n.to_string()).unwrap_or_default();
+                            let id = srv.id.clone();
+                            rsx! {
+                                div {
+                                    key: "{srv.id}",
+                                    class: "grid grid-cols-[1fr_6rem_4rem_4rem_4rem_4rem] gap-3 px-3 py-2 border-t border-zinc-800 text-zinc-300 items-center",
+                                    span { class: "truncate", "{srv.name}" }
+                                    input {
+                                        r#type: "number",
+                                        min: "1",
+                                        placeholder: "No limit",
+                                        class: "w-full bg-zinc-900 border border-zinc-800 rounded px-2 py-1 text-white focus:outline-none focus:border-indigo-500",
+                                        value: "{limit}",
+                                        onchange: move |evt| {
+                                            AppState::set_concurrency_limit(&id, evt.value().trim().parse().ok());
+                                        }
+                                    }
+                                    span { "{stats.running}" }
+                                    span { class: if stats.waiting > 0 { "text-amber-400" } else { "" },
+                                        "{stats.waiting}"
+                                    }
+                                    span { "{stats.peak_waiting}" }
+                                    span { "{stats.queued_total}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ErrorBudgetPanel() -> Element {
+    let settings = APP_STATE.read().error_budget.cloned();
+    let window_mins = (settings.window_secs / 60).max(1);
+
+    let update = move |change: &dyn Fn(&mut crate::models::ErrorBudgetSettings)| {
+        let mut settings = APP_STATE.read().error_budget.cloned();
+        change(&mut settings);
+        AppState::save_error_budget_settings(settings);
+    };
+
+    rsx! {
+        div { class: "p-6 rounded-2xl glass space-y-4",
+            div { class: "flex items-start justify-between gap-4",
+                div {
+                    h3 { class: "font-bold text-white", "Error budget" }
+                    p { class: "text-xs text-zinc-500 mt-1",
+                        "Warn when too many of a server's tool calls fail, before a flaky server is noticed in the editor."
+                    }
+                }
+                label { class: "flex items-center gap-2 text-xs text-zinc-300",
+                    input {
+                        r#type: "checkbox",
+                        checked: settings.enabled,
+                        onchange: move |evt| {
+                            let enabled = evt.checked();
+                            update(&|s| s.enabled = enabled);
+                        }
+                    }
+                    "Enabled"
+                }
+            }
+
+            div { class: "flex flex-wrap items-center gap-2 text-xs text-zinc-400",
+                "Warn when"
+                input {
+                    r#type: "number",
+                    min: "1",
+                    max: "100",
+                    class: "w-16 bg-zinc-900 border border-zinc-800 rounded px-2 py-1 text-white focus:outline-none focus:border-indigo-500",
+                    value: "{settings.threshold_percent}",
+                    onchange: move |evt| {
+                        if let Ok(percent) = evt.value().parse::<u32>() {
+                            update(&|s| s.threshold_percent = percent.clamp(1, 100));
+                        }
+                    }
+                }
+                "% of calls fail within"
+                input {
+                    r#type: "number",
+                    min: "1",
+                    class: "w-16 bg-zinc-900 border border-zinc-800 rounded px-2 py-1 text-white focus:outline-none focus:border-indigo-500",
+                    value: "{window_mins}",
+                    onchange: move |evt| {
+                        if let Ok(mins) = evt.value().parse::<u64>() {
+                            update(&|s| s.window_secs = mins.max(1) * 60);
+                        }
+                    }
+                }
+                "minutes, once there have been at least"
+                input {
+                    r#type: "number",
+                    min: "1",
+                    class: "w-16 bg-zinc-900 border border-zinc-800 rounded px-2 py-1 text-white focus:outline-none focus:border-indigo-500",
+                    value: "{settings.min_requests}",
+                    onchange: move |evt| {
+                        if let Ok(min) = evt.value().parse::<u32>() {
+                            update(&|s| s.min_requests = min.max(1));
+                        }
+                    }
+                }
+                "calls."
+            }
+        }
+    }
+}
+
+#[component]
+fn HealthEndpointPanel() -> Element {
+    let settings = APP_STATE.read().health_endpoint.cloned();
+    let base = format!("http://127.0.0.1:{}", settings.port);
+
+    let update = move |change: &dyn Fn(&mut crate::models::HealthEndpointSettings)| {
+        let mut settings = APP_STATE.read().health_endpoint.cloned();
+        change(&mut settings);
+        AppState::save_health_endpoint(settings);
+    };
+
+    rsx! {
+        div { class: "p-6 rounded-2xl glass space-y-4",
+            div { class: "flex items-start justify-between gap-4",
+                div {
+                    h3 { class: "font-bold text-white", "Health endpoint" }
+                    p { class: "text-xs text-zinc-500 mt-1",
+                        "Let uptime monitors and scripts check the manager and each server's health as JSON, without the UI."
+                    }
+                }
+                label { class: "flex items-center gap-2 text-xs text-zinc-300",
+                    input {
+                        r#type: "checkbox",
+                        checked: settings.enabled,
+                        onchange: move |evt| {
+                            let enabled = evt.checked();
+                            update(&|s| s.enabled = enabled);
+                        }
+                    }
+                    "Enabled"
+                }
+            }
+
+            div { class: "flex flex-wrap items-center gap-2 text-xs text-zinc-400",
+                "Listen on 127.0.0.1 port"
+                input {
+                    r#type: "number",
+                    min: "1",
+                    max: "65535",
+                    class: "w-20 bg-zinc-900 border border-zinc-800 rounded px-2 py-1 text-white focus:outline-none focus:border-indigo-500",
+                    value: "{settings.port}",
+                    onchange: move |evt| {
+                        if let Ok(port) = evt.value().parse::<u16>() {
+                            update(&|s| s.port = port.max(1));
+                        }
+                    }
+                }
+            }
+            if settings.enabled {
+                div { class: "space-y-1 text-xs font-mono text-zinc-300",
+                    p { "{base}/healthz" span { class: "text-zinc-500 font-sans", " · 200 while the manager is up" } }
+                    p { "{base}/readyz" span { class: "text-zinc-500 font-sans", " · 503 while loading or a server is unhealthy" } }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn FailoverPanel() -> Element {
+    let pairs = APP_STATE.read().failover_pairs.cloned();
+    let servers = APP_STATE.read().servers.cloned();
+    let mut primary = use_signal(String::new);
+    let mut backup = use_signal(String::new);
+
+    let can_add = !primary().is_empty()
+        && !backup().is_empty()
+        && primary() != backup()
+        && !pairs
+            .iter()
+            .any(|p| p.primary == primary() && p.backup == backup());
+
+    let add = {
+        let pairs = pairs.clone();
+        move |_| {
+            let mut pairs = pairs.clone();
+            pairs.push(FailoverPair {
+                primary: primary(),
+                backup: backup(),
+            });
+            AppState::save_failover_pairs(pairs);
+            primary.set(String::new());
+            backup.set(String::new());
+        }
+    };
+
+    let select_class = "flex-1 bg-zinc-900 border border-zinc-800 rounded px-2 py-1 text-white focus:outline-none focus:border-indigo-500";
+
+    rsx! {
+        div { class: "p-6 rounded-2xl glass space-y-4",
+            div {
+                h3 { class: "font-bold text-white", "Failover" }
+                p { class: "text-xs text-zinc-500 mt-1",
+                    "While a primary server is unhealthy, its tool calls go to the backup instead. Both should expose the same tools."
+                }
+            }
+
+            if !pairs.is_empty() {
+                div { class: "rounded-xl border border-zinc-800 overflow-hidden text-xs",
+                    for (i, pair) in pairs.iter().enumerate() {
+                        div {
+                            key: "{pair.primary}-{pair.backup}",
+                            class: "flex items-center gap-3 px-3 py-2 border-t first:border-t-0 border-zinc-800 text-zinc-300",
+                            span { class: "truncate", "{server_name(&pair.primary)}" }
+                            span { class: "text-zinc-600", "→" }
+                            span { class: "truncate", "{server_name(&pair.backup)}" }
+                            button {
+                                class: "ml-auto px-2 py-1 rounded text-red-400 hover:bg-red-500/10",
+                                onclick: {
+                                    let pairs = pairs.clone();
+                                    move |_| {
+                                        let mut pairs = pairs.clone();
+                                        pairs.remove(i);
+                                        AppState::save_failover_pairs(pairs);
+                                    }
+                                },
+                                "Remove"
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "flex items-center gap-2 text-xs",
+                select {
+                    class: select_class,
+                    onchange: move |evt| primary.set(evt.value()),
+                    option { value: "", selected: primary().is_empty(), "Primary…" }
+                    for srv in servers.iter() {
+                        option { key: "{srv.id}", value: "{srv.id}", selected: primary() == srv.id, "{srv.name}" }
+                    }
+                }
+                span { class: "text-zinc-600", "→" }
+                select {
+                    class: select_class,
+                    onchange: move |evt| backup.set(evt.value()),
+                    option { value: "", selected: backup().is_empty(), "Backup…" }
+                    for srv in servers.iter().filter(|s| s.id != primary()) {
+                        option { key: "{srv.id}", value: "{srv.id}", selected: backup() == srv.id, "{srv.name}" }
+                    }
+                }
+                button {
+                    class: "px-3 py-1 rounded bg-indigo-600 hover:bg-indigo-500 text-white font-bold disabled:opacity-40",
+                    disabled: !can_add,
+                    onclick: add,
+                    "Add"
+                }
+            }
+        }
+    }
+}
+
+/// The rule described by the form: a kind, a key and up to two values
+/// whose meaning depends on the kind.
+fn form_action(kind: &str, key: &str, first: &str, second: &str) -> Result<RuleAction, String> {
+    let key = key.trim().to_string();
+    if key.is_empty() {
+        return Err("Enter an argument name".into());
+    }
+    let bound = |text: &str| -> Result<Option<f64>, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        text.parse()
+            .map(Some)
+            .map_err(|_| format!("{} is not a number", text))
+    };
+    match kind {
+        "rename" => {
+            let to = first.trim().to_string();
+            if to.is_empty() {
+                return Err("Enter the new name".into());
+            }
+            Ok(RuleAction::Rename { from: key, to })
+        }
+        "clamp" => {
+            let (min, max) = (bound(first)?, bound(second)?);
+            if min.is_none() && max.is_none() {
+                return Err("Enter a minimum or a maximum".into());
+            }
+            Ok(RuleAction::Clamp { key, min, max })
+        }
+        "root" => {
+            let root = first.trim().to_string();
+            if root.is_empty() {
+                return Err("Enter the root directory".into());
+            }
+            Ok(RuleAction::Root { key, root })
+        }
+        // Plain text is taken as a string, anything else as JSON
+        _ => Ok(RuleAction::Default {
+            key,
+            value: serde_json::from_str(first.trim())
+                .unwrap_or_else(|_| serde_json::Value::from(first)),
+        }),
+    }
+}
+
+#[component]
+fn ToolRulesPanel() -> Element {
+    let rules = APP_STATE.read().tool_rules.cloned();
+    let servers = APP_STATE.read().servers.cloned();
+    let mut server = use_signal(String::new);
+    let mut tool = use_signal(String::new);
+    let mut kind = use_signal(|| "default".to_string());
+    let mut key = use_signal(String::new);
+    let mut first = use_signal(String::new);
+    let mut second = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    let add = move |_| {
+        let tool_name = match tool().trim() {
+            "" => "*".to_string(),
+            name => name.to_string(),
+        };
+        let result = form_action(&kind(), &key(), &first(), &second())
+            .and_then(|action| AppState::add_tool_rule(&server(), &tool_name, action));
+        match result {
+            Ok(()) => {
+                key.set(String::new());
+                first.set(String::new());
+                second.set(String::new());
+                error.set(None);
+            }
+            Err(e) => error.set(Some(e)),
+        }
+    };
+
+    let (key_label, first_label) = match kind().as_str() {
+        "rename" => ("Argument", "New name"),
+        "clamp" => ("Argument", "Min"),
+        "root" => ("Path argument", "Root directory"),
+        _ => ("Argument", "Value"),
+    };
+    let input_class = "flex-1 min-w-0 bg-zinc-900 border border-zinc-800 rounded px-2 py-1 text-white focus:outline-none focus:border-indigo-500";
+
+    rsx! {
+        div { class: "p-6 rounded-2xl glass space-y-4",
+            div {
+                h3 { class: "font-bold text-white", "Argument rules" }
+                p { class: "text-xs text-zinc-500 mt-1",
+                    "Rewrite tool call arguments before they reach a server: fill in defaults, rename keys, clamp numbers or keep paths under a root. Rules apply in order."
+                }
+            }
+
+            if !rules.is_empty() {
+                div { class: "rounded-xl border border-zinc-800 overflow-hidden text-xs",
+                    for rule in rules {
+                        div {
+                            key: "{rule.id}",
+                            class: "flex items-center gap-3 px-3 py-2 border-t first:border-t-0 border-zinc-800 text-zinc-300",
+                            span { class: "truncate", "{server_name(&rule.server_id)}" }
+                            span { class: "font-mono text-zinc-500", "{rule.tool}" }
+                            span { class: "font-mono truncate", "{crate::tool_rules::describe(&rule.action)}" }
+                            button {
+                                class: "ml-auto px-2 py-1 rounded text-red-400 hover:bg-red-500/10",
+                                onclick: move |_| {
+                                    if let Err(e) = AppState::delete_tool_rule(rule.id) {
+                                        error.set(Some(e));
+                                    }
+                                },
+                                "Remove"
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "flex flex-wrap items-center gap-2 text-xs",
+                select {
+                    class: input_class,
+                    onchange: move |evt| server.set(evt.value()),
+                    option { value: "", selected: server().is_empty(), "Server…" }
+                    for srv in servers.iter() {
+                        option { key: "{srv.id}", value: "{srv.id}", selected: server() == srv.id, "{srv.name}" }
+                    }
+                }
+                input {
+                    class: input_class,
+                    placeholder: "Tool (all if empty)",
+                    value: "{tool}",
+                    oninput: move |evt| tool.set(evt.value()),
+                }
+                select {
+                    class: input_class,
+                    onchange: move |evt| kind.set(evt.value()),
+                    option { value: "default", selected: kind() == "default", "Default value" }
+                    option { value: "rename", selected: kind() == "rename", "Rename" }
+                    option { value: "clamp", selected: kind() == "clamp", "Clamp" }
+                    option { value: "root", selected: kind() == "root", "Keep under root" }
+                }
+            }
+            div { class: "flex items-center gap-2 text-xs",
+                input {
+                    class: input_class,
+                    placeholder: key_label,
+                    value: "{key}",
+                    oninput: move |evt| key.set(evt.value()),
+                }
+                input {
+                    class: input_class,
+                    placeholder: first_label,
+                    value: "{first}",
+                    oninput: move |evt| first.set(evt.value()),
+                }
+                if kind() == "clamp" {
+                    input {
+                        class: input_class,
+                        placeholder: "Max",
+                        value: "{second}",
+                        oninput: move |evt| second.set(evt.value()),
+                    }
+                }
+                button {
+                    class: "px-3 py-1 rounded bg-indigo-600 hover:bg-indigo-500 text-white font-bold disabled:opacity-40",
+                    disabled: server().is_empty(),
+                    onclick: add,
+                    "Add"
+                }
+            }
+            if let Some(e) = error() {
+                p { class: "text-xs text-red-400", "role": "alert", "{e}" }
+            }
+        }
+    }
+}
+
+#[component]
+fn AuditLogPanel() -> Element {
+    let entries = APP_STATE.read().audit_log.cloned();
+
+    rsx! {
+        div { class: "p-6 rounded-2xl glass space-y-4",
+            div {
+                h3 { class: "font-bold text-white", "Audit log" }
+                p { class: "text-xs text-zinc-500 mt-1",
+                    "Actions the manager took on its own, such as failovers."
+                }
+            }
+            if entries.is_empty() {
+                p { class: "text-xs text-zinc-500", "Nothing recorded yet." }
+            } else {
+                div { class: "space-y-1 text-xs font-mono max-h-64 overflow-y-auto",
+                    for entry in entries {
+                        div { key: "{entry.id}", class: "flex gap-3 text-zinc-400",
+                            span { class: "text-zinc-600 shrink-0", "{entry.created_at}" }
+                            span { class: "text-indigo-400 shrink-0", "{entry.event}" }
+                            span { class: "text-zinc-300 break-all", "{entry.detail}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ToolCatalogPanel() -> Element {
+    let mut exporting = use_signal(|| false);
+    let running = APP_STATE.read().running_handlers.read().len();
+
+    rsx! {
+        div { class: "p-6 rounded-2xl glass space-y-4",
+            div {
+                h3 { class: "font-bold text-white", "Tool catalog" }
+                p { class: "text-xs text-zinc-500 mt-1",
+                    "Export every tool of the {running} running servers with its schema, to publish what the hub exposes."
+                }
+            }
+            div { class: "flex gap-2",
+                for format in CatalogFormat::ALL {
+                    button {
+                        key: "{format.label()}",
+                        class: "px-3 py-1 rounded bg-zinc-800 hover:bg-zinc-700 text-xs text-zinc-300 disabled:opacity-50",
+                        disabled: exporting() || running == 0,
+                        onclick: move |_| {
+                            exporting.set(true);
+                            spawn(async move {
+                                AppState::export_tool_catalog(format).await;
+                                exporting.set(false);
+                            });
+                        },
+                        "Export {format.label()}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+pub use crate::json_schema::validate_against_schema;
+use dioxus::prelude::*;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    Key,
+    String,
+    Number,
+    Literal,
+    Punct,
+    Unmatched,
+    Text,
+}
+
+impl TokenKind {
+    fn class(&self) -> &'static str {
+        match self {
+            TokenKind::Key => "text-indigo-300",
+            TokenKind::String => "text-emerald-300",
+            TokenKind::Number => "text-amber-300",
+            TokenKind::Literal => "text-fuchsia-300",
+            TokenKind::Punct => "text-zinc-500",
+            TokenKind::Unmatched => "text-red-400 bg-red-500/20",
+            TokenKind::Text => "text-zinc-300",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+}
+
+/// Split JSON source into highlightable tokens.
+///
+/// Never fails: anything unrecognised becomes `Text` so partially typed
+/// input still renders. Brackets without a partner are marked `Unmatched`.
+pub fn tokenize(src: &str) -> Vec<Token> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '"' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            let text: String = chars[start..i].iter().collect();
+            // A string followed by ':' is an object key
+            let mut j = i;
+            while j < chars.len() && chars[j].is_whitespace() {
+                j += 1;
+            }
+            let kind = if j < chars.len() && chars[j] == ':' {
+                TokenKind::Key
+            } else {
+                TokenKind::String
+            };
+            tokens.push(Token { kind, text });
+        } else if c == '-' || c.is_ascii_digit() {
+            let start = i;
+            i += 1;
+            while i < chars.len()
+                && (chars[i].is_ascii_digit() || matches!(chars[i], '.' | 'e' | 'E' | '+' | '-'))
+            {
+                i += 1;
+            }
+            tokens.push(Token {
+                kind: TokenKind::Number,
+                text: chars[start..i].iter().collect(),
+            });
+        } else if c.is_ascii_alphabetic() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_alphabetic() {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let kind = if matches!(word.as_str(), "true" | "false" | "null") {
+                TokenKind::Literal
+            } else {
+                TokenKind::Text
+            };
+            tokens.push(Token { kind, text: word });
+        } else if matches!(c, '{' | '}' | '[' | ']' | ':' | ',') {
+            tokens.push(Token {
+                kind: TokenKind::Punct,
+                text: c.to_string(),
+            });
+            i += 1;
+        } else {
+            // Merge runs of whitespace / other characters
+            let start = i;
+            while i < chars.len()
+                && !matches!(chars[i], '"' | '{' | '}' | '[' | ']' | ':' | ',' | '-')
+                && !chars[i].is_ascii_alphanumeric()
+            {
+                i += 1;
+            }
+            if i == start {
+                i += 1;
+            }
+            tokens.push(Token {
+                kind: TokenKind::Text,
+                text: chars[start..i].iter().collect(),
+            });
+        }
+    }
+
+    // Bracket matching
+    let mut stack: Vec<usize> = Vec::new();
+    for idx in 0..tokens.len() {
+        if tokens[idx].kind != TokenKind::Punct {
+            continue;
+        }
+        match tokens[idx].text.as_str() {
+            "{" | "[" => stack.push(idx),
+            close @ ("}" | "]") => {
+                let open = if close == "}" { "{" } else { "[" };
+                match stack.last() {
+                    Some(&o) if tokens[o].text == open => {
+                        stack.pop();
+                    }
+                    _ => tokens[idx].kind = TokenKind::Unmatched,
+                }
+            }
+            _ => {}
+        }
+    }
+    for idx in stack {
+        tokens[idx].kind = TokenKind::Unmatched;
+    }
+
+    tokens
+}
+
+/// Parse the editor contents and collect every problem to show below it.
+pub fn check(src: &str, schema: Option<&Value>) -> Vec<String> {
+    match serde_json::from_str::<Value>(src) {
+        Ok(value) => schema
+            .map(|s| validate_against_schema(&value, s))
+            .unwrap_or_default(),
+        Err(e) => vec![format!("Invalid JSON: {}", e)],
+    }
+}
+
+#[derive(Props, Clone, PartialEq)]
+pub struct JsonEditorProps {
+    pub value: String,
+    pub on_change: EventHandler<String>,
+    #[props(default)]
+    pub schema: Option<Value>,
+    #[props(default = "h-40".to_string())]
+    pub height: String,
+}
+
+/// Lightweight JSON editor: a transparent textarea layered over a
+/// highlighted `pre`, with bracket matching and schema validation messages.
+pub fn JsonEditor(props: JsonEditorProps) -> Element {
+    let tokens = tokenize(&props.value);
+    let problems = check(&props.value, props.schema.as_ref());
+    let border = if problems.is_empty() {
+        "border-zinc-700 focus-within:border-indigo-500"
+    } else {
+        "border-amber-700/60"
+    };
+    let shared = "p-3 font-mono text-sm leading-5 whitespace-pre-wrap break-words";
+
+    rsx! {
+        div { class: "w-full",
+            div { class: "relative w-full {props.height} bg-black/50 border rounded overflow-hidden {border}",
+                pre {
+                    class: "absolute inset-0 m-0 overflow-auto pointer-events-none {shared}",
+                    "aria-hidden": "true",
+                    for (i, token) in tokens.iter().enumerate() {
+                        span { key: "{i}", class: token.kind.class(), "{token.text}" }
+                    }
+                    // Keep a trailing newline visible
+                    " "
+                }
+                textarea {
+                    class: "absolute inset-0 w-full h-full bg-transparent text-transparent caret-white resize-none focus:outline-none {shared}",
+                    spellcheck: false,
+                    value: "{props.value}",
+                    oninput: move |evt| props.on_change.call(evt.value())
+                }
+            }
+            if !problems.is_empty() {
+                ul { class: "mt-2 space-y-0.5",
+                    for problem in problems.iter() {
+                        li { class: "text-xs font-mono text-amber-400", "⚠ {problem}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(src: &str) -> Vec<(TokenKind, String)> {
+        tokenize(src)
+            .into_iter()
+            .filter(|t| !t.text.trim().is_empty())
+            .map(|t| (t.kind, t.text))
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_keys_and_values() {
+        let k = kinds(r#"{"a": "b", "n": -1.5, "t": true}"#);
+        assert_eq!(k[1], (TokenKind::Key, "\"a\"".to_string()));
+        assert_eq!(k[3], (TokenKind::String, "\"b\"".to_string()));
+        assert_eq!(k[7], (TokenKind::Number, "-1.5".to_string()));
+        assert_eq!(k[11], (TokenKind::Literal, "true".to_string()));
+    }
+
+    #[test]
+    fn test_tokenize_round_trips_source() {
+        let src = "{\n  \"x\": [1, 2, {\"y\": null}]\n}";
+        let joined: String = tokenize(src).into_iter().map(|t| t.text).collect();
+        assert_eq!(joined, src);
+    }
+
+    #[test]
+    fn test_unmatched_brackets_marked() {
+        let tokens = tokenize(r#"{"a": [1, 2}"#);
+        let unmatched: Vec<_> = tokens
+            .iter()
+            .filter(|t| t.kind == TokenKind::Unmatched)
+            .map(|t| t.text.as_str())
+            .collect();
+        // `[` is never closed and `}` closes it with the wrong kind; `{` is left open
+        assert_eq!(unmatched, vec!["{", "[", "}"]);
+        assert!(tokenize(r#"{"a": [1]}"#)
+            .iter()
+            .all(|t| t.kind != TokenKind::Unmatched));
+    }
+
+    #[test]
+    fn test_escaped_quotes_in_string() {
+        let k = kinds(r#"{"a": "say \"hi\""}"#);
+        assert_eq!(k[3], (TokenKind::String, r#""say \"hi\"""#.to_string()));
+    }
+
+    #[test]
+    fn test_check_reports_parse_errors() {
+        assert!(check("{", None)[0].starts_with("Invalid JSON"));
+        assert!(check("{}", None).is_empty());
+    }
+}
@@ -0,0 +1,34 @@
+use crate::locale::{system_locale, LANGUAGES};
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+/// Language for registry-provided text such as install wizards.
+pub fn LanguageSettings() -> Element {
+    let language = APP_STATE.read().language.cloned();
+    let system = system_locale();
+
+    rsx! {
+        section { class: "max-w-3xl mx-auto mb-6 p-5 rounded-2xl glass space-y-3",
+            h3 { class: "text-xs font-bold text-zinc-500 uppercase", "Language" }
+            select {
+                class: "w-full px-3 py-2 bg-zinc-900 border border-zinc-700 rounded-xl text-sm text-white focus:outline-none focus:border-indigo-500",
+                onchange: move |evt| {
+                    let value = evt.value();
+                    AppState::set_language(Some(value).filter(|v| !v.is_empty()));
+                },
+                option { value: "", selected: language.is_none(), "System ({system})" }
+                for (locale, name) in LANGUAGES {
+                    option {
+                        key: "{locale}",
+                        value: "{locale}",
+                        selected: language.as_deref() == Some(locale),
+                        "{name}"
+                    }
+                }
+            }
+            p { class: "text-xs text-zinc-500",
+                "Install wizards from the registry are shown in this language where a translation exists, and in English otherwise."
+            }
+        }
+    }
+}
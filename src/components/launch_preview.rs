@@ -0,0 +1,128 @@
+use crate::docker::launch_command;
+use crate::launch::{base_env, masked_value, plan};
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct LaunchPreviewProps {
+    pub command: String,
+    pub args: Vec<String>,
+    pub env: HashMap<String, String>,
+    /// The server's working directory, if it has its own.
+    #[props(default)]
+    pub cwd: Option<String>,
+    /// Start from the clean-environment allowlist instead of inheriting.
+    #[props(default)]
+    pub clean_env: bool,
+    /// Names the server's container when it runs in Docker.
+    #[props(default)]
+    pub server_id: String,
+    /// Image to run the server in, if any.
+    #[props(default)]
+    pub docker_image: Option<String>,
+    #[props(default)]
+    pub docker_mounts: Vec<String>,
+}
+
+/// Dry run of a stdio server launch: resolved command line, working
+/// directory and merged environment, with secrets masked.
+pub fn LaunchPreview(props: LaunchPreviewProps) -> Element {
+    let mut show_inherited = use_signal(|| false);
+
+    let inherited_env = base_env(props.clean_env, std::env::vars());
+    let cwd = props.cwd.as_deref().map(std::path::Path::new);
+    let plan = match launch_command(
+        &props.server_id,
+        props.docker_image.as_deref(),
+        &props.docker_mounts,
+        &props.env,
+        Some(&props.command),
+        &props.args,
+    )
+    .and_then(|(program, args)| plan(&program, &args, &props.env, cwd, inherited_env))
+    {
+        Ok(plan) => plan,
+        Err(e) => {
+            return rsx! {
+                p { class: "text-sm text-amber-400", "⚠️ {e}" }
+            }
+        }
+    };
+    let command_line = plan.command_line();
+    let cwd = plan
+        .cwd
+        .as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "(unknown)".to_string());
+    let inherited = plan.env.len() - plan.configured_keys.len();
+    let rows: Vec<(String, String, bool)> = plan
+        .env
+        .iter()
+        .map(|(k, v)| {
+            (
+                k.clone(),
+                masked_value(k, v),
+                plan.configured_keys.contains(k),
+            )
+        })
+        .filter(|(_, _, configured)| *configured || show_inherited())
+        .collect();
+
+    rsx! {
+        div { class: "space-y-4 text-sm",
+            p { class: "text-xs text-zinc-500",
+                "What starting this server would run, from the form as it is now. Nothing is started."
+            }
+
+            div {
+                label { class: "block text-xs font-bold mb-1 text-zinc-500 uppercase", "Command line" }
+                pre { class: "px-3 py-2 bg-zinc-900 border border-zinc-800 rounded-lg font-mono text-xs text-zinc-200 whitespace-pre-wrap break-all select-text",
+                    "{command_line}"
+                }
+                match &plan.resolved_program {
+                    Some(path) => rsx! {
+                        p { class: "mt-1 text-xs text-zinc-500 font-mono break-all", "→ {path.display()}" }
+                    },
+                    None => rsx! {
+                        p { class: "mt-1 text-xs text-amber-400",
+                            "⚠️ \"{plan.program}\" was not found on PATH; the launch would fail."
+                        }
+                    },
+                }
+            }
+
+            div {
+                label { class: "block text-xs font-bold mb-1 text-zinc-500 uppercase", "Working directory" }
+                p { class: "font-mono text-xs text-zinc-300 break-all", "{cwd}" }
+            }
+
+            div {
+                div { class: "flex items-center justify-between mb-1",
+                    label { class: "text-xs font-bold text-zinc-500 uppercase", "Environment" }
+                    label { class: "flex items-center gap-2 text-xs text-zinc-500",
+                        input {
+                            r#type: "checkbox",
+                            checked: show_inherited(),
+                            onchange: move |evt| show_inherited.set(evt.checked())
+                        }
+                        "Show {inherited} inherited"
+                    }
+                }
+                if rows.is_empty() {
+                    p { class: "text-xs text-zinc-600 italic", "No variables set by this server." }
+                } else {
+                    div { class: "rounded-lg border border-zinc-800 max-h-64 overflow-y-auto font-mono text-xs",
+                        for (key, value, configured) in rows {
+                            div {
+                                key: "{key}",
+                                class: "flex gap-3 px-3 py-1 border-t first:border-t-0 border-zinc-800",
+                                span { class: if configured { "shrink-0 text-indigo-300" } else { "shrink-0 text-zinc-500" }, "{key}" }
+                                span { class: "text-zinc-300 break-all", "{value}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
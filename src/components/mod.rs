@@ -1,24 +1,100 @@
+mod accessibility_settings;
+mod archived_servers;
+mod bundle_installer;
+mod command_palette;
 mod config_viewer;
+mod crash_dialog;
+mod dashboard_widgets;
+mod dependency_graph;
+mod duplicate_servers;
+mod elicitation_form;
+mod fix_it_dialog;
+mod env_rotation;
 mod explorer;
+mod hub_panel;
+pub(crate) mod json_editor;
+mod language_settings;
+mod launch_preview;
 mod navbar;
+mod notification_center;
+mod pair_server;
+mod project_profiles;
+mod prompt_card;
+mod raw_request;
+mod recent_tools;
+mod registry_entry;
 mod research;
+mod resource_template_card;
+mod roots_settings;
+mod sampling_approval;
+mod sampling_settings;
 mod server_card;
 mod server_console;
+mod server_diff;
+mod server_history;
 mod server_list;
 mod settings;
 mod sidebar;
+mod storage_settings;
+mod terminal_view;
 mod theme_toggle;
 mod three_preview;
 pub mod toast;
+mod tool_docs;
+mod tool_output;
+mod tool_presets;
+mod tool_snippets;
+mod update_dialog;
+mod usage_export;
+mod whats_new;
+mod workspace_switcher;
 
+pub use accessibility_settings::AccessibilitySettings;
+pub use archived_servers::ArchivedServers;
+pub use bundle_installer::BundleInstaller;
+pub use command_palette::{CommandPalette, PaletteCommand};
 pub use config_viewer::ConfigViewer;
+pub use crash_dialog::CrashDialog;
+pub use dashboard_widgets::DashboardWidgets;
+pub use dependency_graph::DependencyGraph;
+pub use duplicate_servers::DuplicateServers;
+pub use elicitation_form::ElicitationForm;
+pub use fix_it_dialog::FixItDialog;
+pub use env_rotation::EnvRotation;
 pub use explorer::Explorer;
+pub use hub_panel::HubPanel;
+pub use json_editor::JsonEditor;
+pub use language_settings::LanguageSettings;
+pub use launch_preview::LaunchPreview;
 pub use navbar::Navbar;
+pub use notification_center::NotificationCenter;
+pub use pair_server::PairServer;
+pub use project_profiles::ProjectProfiles;
+pub use prompt_card::PromptCard;
+pub use raw_request::RawRequest;
+pub use recent_tools::RecentTools;
+pub use registry_entry::RegistryEntry;
 pub use research::Research;
+pub use resource_template_card::ResourceTemplateCard;
+pub use roots_settings::RootsSettings;
+pub use sampling_approval::SamplingApproval;
+pub use sampling_settings::SamplingSettings;
 pub use server_card::ServerCard;
 pub use server_console::ServerConsole;
+pub use server_diff::ServerDiff;
+pub use server_history::ServerHistory;
 pub use server_list::ServerList;
 pub use settings::Settings;
 pub use sidebar::Sidebar;
+pub use storage_settings::StorageSettings;
+pub use terminal_view::TerminalView;
 pub use theme_toggle::ThemeToggle;
 pub use toast::ToastContainer;
+pub use tool_docs::ToolDocs;
+pub use tool_output::{SchemaView, ToolAudio, ToolImage, ToolOutput, ToolOutputDiff};
+pub use tool_presets::ToolPresets;
+pub use tool_snippets::ToolSnippets;
+pub use update_dialog::UpdateDialog;
+pub use usage_export::UsageExport;
+pub use whats_new::WhatsNew;
+pub use workspace_switcher::WorkspaceSwitcher;
@@ -1,11 +1,20 @@
-use crate::components::ThemeToggle;
+use crate::components::{NotificationCenter, ThemeToggle, WorkspaceSwitcher};
+use crate::models::{McpServer, NotificationLevel};
+use crate::state::AppState;
 use dioxus::prelude::*;
 
 #[derive(Clone, PartialEq, Props)]
 pub struct NavbarProps {
     on_export: EventHandler<()>,
     on_add_server: EventHandler<()>,
+    on_pair_server: EventHandler<()>,
     on_registry: EventHandler<()>,
+    on_compare: EventHandler<()>,
+    on_command_palette: EventHandler<()>,
+    on_open_console: EventHandler<McpServer>,
+    on_switch_workspace: EventHandler<()>,
+    /// A `.mcpb` / `.dxt` bundle was picked for install.
+    on_install_bundle: EventHandler<std::path::PathBuf>,
 }
 
 pub fn Navbar(props: NavbarProps) -> Element {
@@ -15,17 +24,26 @@ pub fn Navbar(props: NavbarProps) -> Element {
 
             // Left side (Breadcrumbs or Page Title - Optional, can be empty for now or show 'Dashboard')
             div {
-                class: "flex items-center gap-2",
+                class: "flex items-center gap-4",
                 h1 {
                     class: "text-2xl font-bold text-white tracking-tight",
                     "Dashboard"
                 }
+                WorkspaceSwitcher { on_switch: move |_| props.on_switch_workspace.call(()) }
             }
 
             // Actions
             div {
                 class: "flex items-center gap-4",
 
+                // Quick-launch palette
+                button {
+                    class: "flex items-center gap-2 px-3 py-2.5 rounded-xl text-sm font-semibold text-zinc-400 hover:text-white hover:bg-white-8 transition-all border border-transparent hover:border-white-5",
+                    title: "Recent tools and actions (Ctrl+K)",
+                    onclick: move |_| props.on_command_palette.call(()),
+                    "⌘K"
+                }
+
                 // Registry
                 button {
                     class: "flex items-center gap-2 px-4 py-2.5 rounded-xl text-sm font-semibold text-zinc-400 hover:text-white hover:bg-white-8 transition-all border border-transparent hover:border-white-5",
@@ -36,6 +54,55 @@ pub fn Navbar(props: NavbarProps) -> Element {
                     "Registry"
                 }
 
+                // Install a desktop extension bundle
+                label {
+                    class: "flex items-center gap-2 px-4 py-2.5 rounded-xl text-sm font-semibold text-zinc-400 hover:text-white hover:bg-white-8 transition-all border border-transparent hover:border-white-5 cursor-pointer",
+                    title: "Install a .mcpb / .dxt extension bundle",
+                    "📦 Bundle"
+                    input {
+                        r#type: "file",
+                        accept: ".mcpb,.dxt",
+                        class: "hidden",
+                        onchange: move |evt| {
+                            if let Some(file) = evt.files().first() {
+                                props.on_install_bundle.call(file.path());
+                            }
+                        }
+                    }
+                }
+
+                // Import the servers a project folder's editor configs define
+                button {
+                    class: "flex items-center gap-2 px-4 py-2.5 rounded-xl text-sm font-semibold text-zinc-400 hover:text-white hover:bg-white-8 transition-all border border-transparent hover:border-white-5",
+                    title: "Import servers from a project folder (.mcp.json, .vscode/mcp.json, ...)",
+                    onclick: move |_| {
+                        spawn(async move {
+                            match AppState::import_project().await {
+                                Ok(summary) if summary.is_empty() => {}
+                                Ok(summary) => AppState::push_notification(summary, NotificationLevel::Success),
+                                Err(e) => AppState::push_notification(e, NotificationLevel::Error),
+                            }
+                        });
+                    },
+                    "📁 Project"
+                }
+
+                // Pair a remote server from a code or QR
+                button {
+                    class: "flex items-center gap-2 px-4 py-2.5 rounded-xl text-sm font-semibold text-zinc-400 hover:text-white hover:bg-white-8 transition-all border border-transparent hover:border-white-5",
+                    title: "Pair a remote server from a pairing code or QR",
+                    onclick: move |_| props.on_pair_server.call(()),
+                    "🔗 Pair"
+                }
+
+                // Compare two servers' configs
+                button {
+                    class: "flex items-center gap-2 px-4 py-2.5 rounded-xl text-sm font-semibold text-zinc-400 hover:text-white hover:bg-white-8 transition-all border border-transparent hover:border-white-5",
+                    title: "Compare two server configs",
+                    onclick: move |_| props.on_compare.call(()),
+                    "⇄ Compare"
+                }
+
                 // Export Config
                 button {
                     class: "flex items-center gap-2 px-4 py-2.5 rounded-xl text-sm font-semibold text-zinc-400 hover:text-white hover:bg-white-8 transition-all border border-transparent hover:border-white-5",
@@ -58,6 +125,8 @@ pub fn Navbar(props: NavbarProps) -> Element {
 
                 div { class: "w-px h-8 bg-white-10 mx-2" }
 
+                NotificationCenter { on_open_console: move |server| props.on_open_console.call(server) }
+
                 ThemeToggle {}
             }
         }
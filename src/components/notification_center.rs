@@ -0,0 +1,364 @@
+use crate::models::{
+    McpServer, Notification, NotificationEvent, NotificationLevel, NotificationRules,
+};
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct NotificationCenterProps {
+    pub on_open_console: EventHandler<McpServer>,
+}
+
+/// History entries matching `level` (all when `None`), newest first.
+fn filter_history(
+    history: &[Notification],
+    level: Option<&NotificationLevel>,
+) -> Vec<Notification> {
+    history
+        .iter()
+        .rev()
+        .filter(|n| level.map(|l| &n.level == l).unwrap_or(true))
+        .cloned()
+        .collect()
+}
+
+const FILTERS: [(&str, Option<NotificationLevel>); 5] = [
+    ("All", None),
+    ("Info", Some(NotificationLevel::Info)),
+    ("Success", Some(NotificationLevel::Success)),
+    ("Warning", Some(NotificationLevel::Warning)),
+    ("Error", Some(NotificationLevel::Error)),
+];
+
+const LEVELS: [NotificationLevel; 4] = [
+    NotificationLevel::Info,
+    NotificationLevel::Success,
+    NotificationLevel::Warning,
+    NotificationLevel::Error,
+];
+
+/// Add `item` to `list` when `on`, remove it otherwise.
+fn set_membership<T: PartialEq>(list: &mut Vec<T>, item: T, on: bool) {
+    list.retain(|x| x != &item);
+    if on {
+        list.push(item);
+    }
+}
+
+pub fn NotificationCenter(props: NotificationCenterProps) -> Element {
+    let mut is_open = use_signal(|| false);
+    let mut show_rules = use_signal(|| false);
+    let mut filter = use_signal(|| None::<NotificationLevel>);
+    let dnd = APP_STATE.read().notification_rules.read().do_not_disturb;
+
+    let history = APP_STATE.read().notification_history.cloned();
+    let unread = history.iter().filter(|n| !n.read).count();
+    let visible = filter_history(&history, filter().as_ref());
+
+    let open_notification = move |note: Notification| {
+        AppState::mark_notification_read(note.id);
+        let server = note.server_id.as_ref().and_then(|id| {
+            APP_STATE
+                .read()
+                .servers
+                .read()
+                .iter()
+                .find(|s| &s.id == id)
+                .cloned()
+        });
+        if let Some(server) = server {
+            is_open.set(false);
+            props.on_open_console.call(server);
+        }
+    };
+
+    rsx! {
+        div { class: "relative",
+            button {
+                class: "relative p-2.5 rounded-xl text-zinc-400 hover:text-white hover:bg-white-8 transition-all",
+                title: "Notifications",
+                onclick: move |_| is_open.set(!is_open()),
+                if dnd { "🔕" } else { "🔔" }
+                if unread > 0 {
+                    span { class: "absolute -top-0.5 -right-0.5 min-w-[18px] h-[18px] px-1 rounded-full bg-red-500 text-white text-[10px] font-bold flex items-center justify-center",
+                        "{unread}"
+                    }
+                }
+            }
+
+            if is_open() {
+                div { class: "absolute right-0 mt-2 w-96 bg-zinc-950 border border-zinc-800 rounded-xl shadow-2xl z-50 overflow-hidden animate-scale-in",
+                    // Header
+                    div { class: "flex items-center justify-between px-4 py-3 bg-zinc-900 border-b border-zinc-800",
+                        h3 { class: "text-sm font-bold text-white", "Notifications" }
+                        div { class: "flex gap-3 text-xs",
+                            button {
+                                class: if show_rules() { "text-white" } else { "text-zinc-500 hover:text-zinc-300" },
+                                title: "Notification rules",
+                                onclick: move |_| show_rules.set(!show_rules()),
+                                "⚙ Rules"
+                            }
+                            button {
+                                class: "text-indigo-400 hover:text-indigo-300 disabled:opacity-40",
+                                disabled: unread == 0,
+                                onclick: move |_| AppState::mark_all_notifications_read(),
+                                "Mark all read"
+                            }
+                            button {
+                                class: "text-zinc-500 hover:text-zinc-300",
+                                onclick: move |_| AppState::clear_notification_history(),
+                                "Clear"
+                            }
+                        }
+                    }
+
+                    if show_rules() {
+                        RulesPanel {}
+                    } else {
+                        // Level filters
+                        div { class: "flex gap-1 px-3 py-2 border-b border-zinc-800",
+                            for (label, level) in FILTERS {
+                                button {
+                                    key: "{label}",
+                                    class: if filter() == level { "px-2.5 py-1 rounded-lg text-xs font-semibold bg-zinc-800 text-white" } else { "px-2.5 py-1 rounded-lg text-xs font-semibold text-zinc-500 hover:text-zinc-300" },
+                                    onclick: move |_| filter.set(level.clone()),
+                                    "{label}"
+                                }
+                            }
+                        }
+
+                        // History
+                        div { class: "max-h-96 overflow-y-auto custom-scrollbar",
+                            if visible.is_empty() {
+                                p { class: "px-4 py-8 text-center text-sm text-zinc-500", "No notifications" }
+                            }
+                            for note in visible {
+                                NotificationRow {
+                                    key: "{note.id}",
+                                    notification: note.clone(),
+                                    on_click: open_notification
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Editor for [`NotificationRules`]; every change is saved immediately.
+#[component]
+fn RulesPanel() -> Element {
+    let rules = APP_STATE.read().notification_rules.cloned();
+    let update = move |change: Box<dyn FnOnce(&mut NotificationRules)>| {
+        let mut rules = APP_STATE.read().notification_rules.cloned();
+        change(&mut rules);
+        AppState::save_notification_rules(rules);
+    };
+    let (quiet_start, quiet_end) = rules.quiet_hours.unwrap_or((22, 7));
+    let chip_on = "px-2.5 py-1 rounded-lg text-xs font-semibold bg-indigo-500/20 text-indigo-300";
+    let chip_off = "px-2.5 py-1 rounded-lg text-xs font-semibold bg-zinc-900 text-zinc-500 hover:text-zinc-300";
+
+    rsx! {
+        div { class: "p-4 space-y-4 text-sm",
+            label { class: "flex items-center justify-between",
+                span { class: "font-semibold text-zinc-300", "Do not disturb" }
+                input {
+                    r#type: "checkbox",
+                    checked: rules.do_not_disturb,
+                    onchange: move |evt| {
+                        let on = evt.checked();
+                        update(Box::new(move |r| r.do_not_disturb = on))
+                    }
+                }
+            }
+
+            div {
+                p { class: "text-xs font-bold uppercase text-zinc-500 mb-2", "Mute toasts by level" }
+                div { class: "flex flex-wrap gap-1",
+                    for level in LEVELS {
+                        {
+                            let muted = rules.muted_levels.contains(&level);
+                            rsx! {
+                                button {
+                                    key: "{level:?}",
+                                    class: if muted { chip_on } else { chip_off },
+                                    onclick: move |_| {
+                                        let level = level.clone();
+                                        update(Box::new(move |r| set_membership(&mut r.muted_levels, level, !muted)))
+                                    },
+                                    if muted { "🔇 {level:?}" } else { "{level:?}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div {
+                p { class: "text-xs font-bold uppercase text-zinc-500 mb-2", "Events" }
+                div { class: "space-y-1.5",
+                    for event in NotificationEvent::ALL {
+                        {
+                            let muted = rules.muted_events.contains(&event);
+                            let native = rules.native_events.contains(&event);
+                            rsx! {
+                                div { key: "{event:?}", class: "flex items-center justify-between",
+                                    span { class: "text-zinc-300", "{event.label()}" }
+                                    div { class: "flex gap-1",
+                                        button {
+                                            class: if muted { chip_off } else { chip_on },
+                                            onclick: move |_| update(Box::new(move |r| set_membership(&mut r.muted_events, event, !muted))),
+                                            "Toast"
+                                        }
+                                        button {
+                                            class: if native { chip_on } else { chip_off },
+                                            onclick: move |_| update(Box::new(move |r| set_membership(&mut r.native_events, event, !native))),
+                                            "Native"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div {
+                label { class: "flex items-center justify-between mb-2",
+                    span { class: "font-semibold text-zinc-300", "Quiet hours" }
+                    input {
+                        r#type: "checkbox",
+                        checked: rules.quiet_hours.is_some(),
+                        onchange: move |evt| {
+                            let on = evt.checked();
+                            update(Box::new(move |r| {
+                                r.quiet_hours = if on { Some((quiet_start, quiet_end)) } else { None }
+                            }))
+                        }
+                    }
+                }
+                if rules.quiet_hours.is_some() {
+                    div { class: "flex items-center gap-2 text-xs text-zinc-400",
+                        "From"
+                        input {
+                            class: "w-16 px-2 py-1 bg-zinc-900 border border-zinc-700 rounded-lg",
+                            r#type: "number",
+                            min: "0",
+                            max: "23",
+                            value: "{quiet_start}",
+                            onchange: move |evt| {
+                                if let Ok(h) = evt.value().parse::<u8>() {
+                                    let h = h.min(23);
+                                    update(Box::new(move |r| r.quiet_hours = Some((h, quiet_end))))
+                                }
+                            }
+                        }
+                        "to"
+                        input {
+                            class: "w-16 px-2 py-1 bg-zinc-900 border border-zinc-700 rounded-lg",
+                            r#type: "number",
+                            min: "0",
+                            max: "23",
+                            value: "{quiet_end}",
+                            onchange: move |evt| {
+                                if let Ok(h) = evt.value().parse::<u8>() {
+                                    let h = h.min(23);
+                                    update(Box::new(move |r| r.quiet_hours = Some((quiet_start, h))))
+                                }
+                            }
+                        }
+                        ":00"
+                    }
+                }
+            }
+
+            p { class: "text-[10px] text-zinc-600", "Muted notifications are still kept in the history." }
+        }
+    }
+}
+
+#[component]
+fn NotificationRow(notification: Notification, on_click: EventHandler<Notification>) -> Element {
+    let icon = match notification.level {
+        NotificationLevel::Info => "ℹ️",
+        NotificationLevel::Success => "✅",
+        NotificationLevel::Warning => "⚠️",
+        NotificationLevel::Error => "❌",
+    };
+    let row_class = if notification.read {
+        "bg-transparent"
+    } else {
+        "bg-indigo-500/5"
+    };
+    let linked = notification.server_id.is_some();
+    let note = notification.clone();
+
+    rsx! {
+        div {
+            class: "flex gap-3 px-4 py-3 border-b border-zinc-900 hover:bg-zinc-900 cursor-pointer {row_class}",
+            onclick: move |_| on_click.call(note.clone()),
+            span { class: "text-base", "{icon}" }
+            div { class: "flex-1 min-w-0",
+                p { class: "text-sm text-zinc-200 break-words", "{notification.message}" }
+                div { class: "flex gap-2 mt-1 text-[10px] text-zinc-500",
+                    span { "{notification.created_at}" }
+                    if linked {
+                        span { class: "text-indigo-400", "Open console →" }
+                    }
+                }
+            }
+            if !notification.read {
+                span { class: "mt-1.5 w-2 h-2 rounded-full bg-indigo-400 shrink-0" }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(id: u32, level: NotificationLevel) -> Notification {
+        Notification {
+            id,
+            message: String::new(),
+            level,
+            duration: 5,
+            server_id: None,
+            created_at: String::new(),
+            read: false,
+            event: NotificationEvent::General,
+        }
+    }
+
+    #[test]
+    fn test_set_membership() {
+        let mut list = vec![NotificationLevel::Info];
+        set_membership(&mut list, NotificationLevel::Error, true);
+        set_membership(&mut list, NotificationLevel::Error, true);
+        assert_eq!(list.len(), 2);
+        set_membership(&mut list, NotificationLevel::Info, false);
+        assert_eq!(list, vec![NotificationLevel::Error]);
+    }
+
+    #[test]
+    fn test_filter_history_newest_first() {
+        let history = vec![
+            note(1, NotificationLevel::Info),
+            note(2, NotificationLevel::Error),
+            note(3, NotificationLevel::Info),
+        ];
+        let all: Vec<u32> = filter_history(&history, None)
+            .iter()
+            .map(|n| n.id)
+            .collect();
+        assert_eq!(all, vec![3, 2, 1]);
+        let info: Vec<u32> = filter_history(&history, Some(&NotificationLevel::Info))
+            .iter()
+            .map(|n| n.id)
+            .collect();
+        assert_eq!(info, vec![3, 1]);
+    }
+}
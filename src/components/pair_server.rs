@@ -0,0 +1,217 @@
+use crate::pairing::{parse, Pairing};
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+/// Scans the camera for a QR code with the webview's `BarcodeDetector`,
+/// showing the feed in `#pair-qr-preview`. Sends `{code}` or `{error}`.
+const SCAN_QR_JS: &str = r#"
+    (async () => {
+        if (!("BarcodeDetector" in window) || !navigator.mediaDevices) {
+            dioxus.send({ error: "QR scanning isn't supported here. Type or paste the code shown under the QR instead." });
+            return;
+        }
+        let stream;
+        try {
+            stream = await navigator.mediaDevices.getUserMedia({ video: { facingMode: "environment" } });
+        } catch (e) {
+            dioxus.send({ error: "Could not open the camera: " + e.message });
+            return;
+        }
+        const video = document.createElement("video");
+        video.className = "w-full rounded-lg";
+        video.muted = true;
+        video.srcObject = stream;
+        document.getElementById("pair-qr-preview")?.replaceChildren(video);
+        await video.play();
+        const detector = new BarcodeDetector({ formats: ["qr_code"] });
+        const stop = () => { stream.getTracks().forEach((t) => t.stop()); video.remove(); };
+        const deadline = Date.now() + 30000;
+        while (Date.now() < deadline && video.isConnected) {
+            const codes = await detector.detect(video).catch(() => []);
+            if (codes.length > 0) {
+                stop();
+                dioxus.send({ code: codes[0].rawValue });
+                return;
+            }
+            await new Promise((r) => setTimeout(r, 250));
+        }
+        stop();
+        dioxus.send({ error: "No QR code found" });
+    })();
+"#;
+
+#[derive(serde::Deserialize)]
+struct ScanResult {
+    code: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Props, Clone, PartialEq)]
+pub struct PairServerProps {
+    pub on_close: EventHandler<()>,
+}
+
+/// Add a remote SSE server from a pairing code or QR code shown by its
+/// host. Works from the keyboard: the code field has focus, Enter pairs
+/// and Escape closes.
+pub fn PairServer(props: PairServerProps) -> Element {
+    let mut code = use_signal(String::new);
+    let mut name = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut scanning = use_signal(|| false);
+
+    let input = code();
+    let pairing: Option<Result<Pairing, String>> =
+        (!input.trim().is_empty()).then(|| parse(&input));
+    let can_pair = matches!(pairing, Some(Ok(_)));
+
+    let pair = move || {
+        let mut pairing = match parse(&code()) {
+            Ok(pairing) => pairing,
+            Err(e) => {
+                error.set(Some(e));
+                return;
+            }
+        };
+        if !name().trim().is_empty() {
+            pairing.name = Some(name());
+        }
+        let display_name = pairing.display_name();
+        spawn(async move {
+            match AppState::add_server(pairing.to_server_args()).await {
+                Ok(()) => {
+                    AppState::push_notification(
+                        format!("Paired with {}", display_name),
+                        crate::models::NotificationLevel::Success,
+                    );
+                    props.on_close.call(());
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    };
+
+    let scan = move |_| {
+        scanning.set(true);
+        error.set(None);
+        spawn(async move {
+            let mut eval = document::eval(SCAN_QR_JS);
+            match eval.recv::<ScanResult>().await {
+                Ok(ScanResult {
+                    code: Some(text), ..
+                }) => code.set(text),
+                Ok(ScanResult { error: e, .. }) => {
+                    error.set(Some(e.unwrap_or_else(|| "No QR code found".to_string())))
+                }
+                Err(e) => error.set(Some(format!("{:?}", e))),
+            }
+            scanning.set(false);
+        });
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-50 flex items-center justify-center bg-black/60 p-4 backdrop-blur-md",
+            onkeydown: move |evt| {
+                if evt.key() == Key::Escape {
+                    props.on_close.call(());
+                }
+            },
+            div {
+                class: "w-full max-w-lg bg-zinc-950 text-zinc-300 rounded-2xl overflow-hidden border border-zinc-800 shadow-2xl animate-scale-in",
+                "role": "dialog",
+                "aria-label": "Pair a remote server",
+                div { class: "p-6 space-y-4",
+                    div {
+                        h2 { class: "font-bold text-xl text-white", "🔗 Pair a remote server" }
+                        p { class: "text-sm text-zinc-500 mt-1",
+                            "Enter the pairing code shown by the remote host, or scan its QR code. The server URL and access token are filled in for you."
+                        }
+                    }
+
+                    div {
+                        label { r#for: "pair-code", class: "block text-xs font-bold mb-1 text-zinc-500 uppercase", "Pairing code" }
+                        input {
+                            id: "pair-code",
+                            class: "w-full px-3 py-2 bg-zinc-900 border border-zinc-800 rounded-lg font-mono text-xs text-white focus:outline-none focus:border-indigo-500",
+                            placeholder: "mcp-pair:… or https://host/sse?token=…",
+                            value: "{input}",
+                            onmounted: move |evt| async move {
+                                let _ = evt.data().set_focus(true).await;
+                            },
+                            oninput: move |evt| {
+                                code.set(evt.value());
+                                error.set(None);
+                            },
+                            onkeydown: move |evt| {
+                                if evt.key() == Key::Enter {
+                                    pair();
+                                }
+                            }
+                        }
+                    }
+
+                    div {
+                        label { r#for: "pair-name", class: "block text-xs font-bold mb-1 text-zinc-500 uppercase", "Name (optional)" }
+                        input {
+                            id: "pair-name",
+                            class: "w-full px-3 py-2 bg-zinc-900 border border-zinc-800 rounded-lg text-sm text-white focus:outline-none focus:border-indigo-500",
+                            placeholder: "From the code, or the host name",
+                            value: "{name}",
+                            oninput: move |evt| name.set(evt.value()),
+                            onkeydown: move |evt| {
+                                if evt.key() == Key::Enter {
+                                    pair();
+                                }
+                            }
+                        }
+                    }
+
+                    div { id: "pair-qr-preview" }
+
+                    match pairing {
+                        Some(Ok(p)) => rsx! {
+                            div { class: "text-xs space-y-1", "aria-live": "polite",
+                                p { class: "text-zinc-400", "Server: ", span { class: "font-mono text-zinc-200 break-all", "{p.url}" } }
+                                p { class: "text-zinc-400",
+                                    if p.token.is_some() { "Access token included" } else { "No access token" }
+                                }
+                                if p.is_insecure() {
+                                    p { class: "text-amber-400",
+                                        "⚠️ The token will be sent unencrypted over http:// to another machine."
+                                    }
+                                }
+                            }
+                        },
+                        Some(Err(e)) => rsx! {
+                            p { class: "text-xs text-zinc-500", "aria-live": "polite", "{e}" }
+                        },
+                        None => rsx! {},
+                    }
+                    if let Some(e) = error() {
+                        p { class: "text-xs text-red-400", "role": "alert", "{e}" }
+                    }
+                }
+                div { class: "p-5 bg-zinc-900 border-t border-zinc-800 flex justify-end gap-3",
+                    button {
+                        class: "px-4 py-2.5 text-zinc-400 hover:text-white transition-colors mr-auto disabled:opacity-50",
+                        disabled: scanning(),
+                        onclick: scan,
+                        if scanning() { "📷 Scanning…" } else { "📷 Scan QR" }
+                    }
+                    button {
+                        class: "px-5 py-2.5 text-zinc-400 hover:text-white transition-colors",
+                        onclick: move |_| props.on_close.call(()),
+                        "Cancel"
+                    }
+                    button {
+                        class: "px-6 py-2.5 bg-indigo-600 hover:bg-indigo-500 text-white rounded-xl text-sm font-bold transition-colors shadow-lg shadow-indigo-500/20 disabled:opacity-50",
+                        disabled: !can_pair,
+                        onclick: move |_| pair(),
+                        "Pair"
+                    }
+                }
+            }
+        }
+    }
+}
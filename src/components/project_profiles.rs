@@ -0,0 +1,175 @@
+use crate::models::NotificationLevel;
+use crate::project_import::{ProjectProfile, PROFILE_CONFIG_FILES};
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+/// Project folders bound to a set of servers, with the state of each
+/// project's config file and a button to regenerate it.
+pub fn ProjectProfiles() -> Element {
+    let profiles = APP_STATE.read().project_profiles.cloned();
+    // Bumped after writing a config file, which no signal tracks
+    let mut rescan = use_signal(|| 0u32);
+
+    let add = move |_| {
+        spawn(async move {
+            match AppState::import_project().await {
+                Ok(summary) if summary.is_empty() => {}
+                Ok(summary) => AppState::push_notification(summary, NotificationLevel::Success),
+                Err(e) => AppState::push_notification(e, NotificationLevel::Error),
+            }
+        });
+    };
+
+    rsx! {
+        div { class: "max-w-4xl mx-auto space-y-6",
+            div { class: "flex items-start justify-between gap-4",
+                div {
+                    h2 { class: "text-2xl font-bold text-white tracking-tight", "Projects" }
+                    p { class: "text-sm text-zinc-500 mt-1",
+                        "Each project keeps its own MCP config, generated from the servers tagged with the project."
+                    }
+                }
+                button {
+                    class: "px-4 py-2 bg-indigo-600 hover:bg-indigo-500 text-white rounded-xl text-sm font-bold transition-colors",
+                    onclick: add,
+                    "📁 Add project"
+                }
+            }
+            if profiles.is_empty() {
+                p { class: "text-sm text-zinc-500",
+                    "No projects yet. Add a project folder to import the servers its editors use."
+                }
+            }
+            for (key, profile) in profiles.into_iter().map(|p| (p.path.display().to_string(), p)) {
+                ProfileCard {
+                    key: "{key}",
+                    profile,
+                    generation: rescan(),
+                    on_written: move |_| rescan.with_mut(|n| *n += 1),
+                }
+            }
+        }
+    }
+}
+
+/// `generation` changes after a config file is written, so the card
+/// re-reads it.
+#[component]
+fn ProfileCard(profile: ProjectProfile, generation: u32, on_written: EventHandler<()>) -> Element {
+    let _ = generation;
+    let servers = APP_STATE.read().servers.cloned();
+    let tags = APP_STATE.read().server_tags.cloned();
+    let tag = profile.tag();
+    let name = profile.name();
+    let path = profile.path.display().to_string();
+    let config_file = profile.config_file.clone();
+    let members = profile.servers(&servers, &tags).len();
+    let plan = AppState::project_profile_plan(&profile);
+    let out_of_date = matches!(&plan, Ok(p) if !p.is_empty());
+    let (status_class, status) = match &plan {
+        Ok(p) if p.is_empty() => ("text-green-400", "✓ In sync".to_string()),
+        Ok(p) => (
+            "text-amber-400",
+            format!("{} to add or update, {} to remove", p.add.len(), p.remove.len()),
+        ),
+        Err(e) => ("text-red-400", format!("Can't read {}: {}", config_file, e)),
+    };
+
+    let generate = {
+        let profile = profile.clone();
+        move |_| match AppState::generate_project_config(&profile) {
+            Ok(path) => {
+                AppState::push_notification(
+                    format!("Wrote {}", path.display()),
+                    NotificationLevel::Success,
+                );
+                on_written.call(());
+            }
+            Err(e) => AppState::push_notification(e, NotificationLevel::Error),
+        }
+    };
+    let reimport = {
+        let dir = profile.path.clone();
+        move |_| match AppState::import_project_dir(&dir) {
+            Ok(summary) => AppState::push_notification(summary, NotificationLevel::Success),
+            Err(e) => AppState::push_notification(e, NotificationLevel::Error),
+        }
+    };
+    let remove = {
+        let dir = profile.path.clone();
+        move |_| AppState::remove_project_profile(&dir)
+    };
+    let set_config_file = {
+        let dir = profile.path.clone();
+        move |evt: FormEvent| AppState::set_profile_config_file(&dir, &evt.value())
+    };
+
+    rsx! {
+        div { class: "p-6 rounded-2xl glass space-y-4",
+            div { class: "flex items-start justify-between gap-4",
+                div { class: "min-w-0",
+                    h3 { class: "font-bold text-white", "{name}" }
+                    p { class: "text-xs text-zinc-500 font-mono truncate", title: "{path}", "{path}" }
+                    p { class: "text-xs text-zinc-500 mt-1", "{members} server(s) tagged {tag}" }
+                }
+                div { class: "flex items-center gap-2 shrink-0",
+                    button {
+                        class: "px-3 py-1.5 rounded-lg text-xs text-zinc-400 hover:text-white hover:bg-white-8 transition-colors",
+                        title: "Import entries added to the project's config files",
+                        onclick: reimport,
+                        "Re-import"
+                    }
+                    button {
+                        class: "px-3 py-1.5 rounded-lg text-xs text-zinc-400 hover:text-red-400 hover:bg-white-8 transition-colors",
+                        title: "Forget this project; its servers stay",
+                        onclick: remove,
+                        "Remove"
+                    }
+                }
+            }
+
+            div { class: "flex items-center gap-3 text-xs",
+                select {
+                    class: "bg-zinc-900 border border-zinc-800 rounded px-2 py-1 text-white font-mono focus:outline-none focus:border-indigo-500",
+                    onchange: set_config_file,
+                    for file in PROFILE_CONFIG_FILES {
+                        option { key: "{file}", value: "{file}", selected: file == config_file, "{file}" }
+                    }
+                }
+                span { class: "{status_class}", "{status}" }
+                button {
+                    class: "ml-auto px-3 py-1.5 bg-indigo-600 hover:bg-indigo-500 text-white rounded-lg font-bold transition-colors disabled:opacity-40",
+                    disabled: !out_of_date,
+                    onclick: generate,
+                    "Generate {config_file}"
+                }
+            }
+
+            div { class: "flex flex-wrap gap-2",
+                for server in servers.iter().filter(|s| !s.is_archived) {
+                    {
+                        let checked = tags.get(&server.id).is_some_and(|t| t.contains(&tag));
+                        let id = server.id.clone();
+                        let tag = tag.clone();
+                        rsx! {
+                            label {
+                                key: "{server.id}",
+                                class: "flex items-center gap-1.5 px-2 py-1 rounded-md bg-white-5 text-xs text-zinc-300 cursor-pointer",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: checked,
+                                    onchange: move |evt| {
+                                        if let Err(e) = AppState::set_server_tagged(&id, &tag, evt.checked()) {
+                                            AppState::push_notification(e, NotificationLevel::Error);
+                                        }
+                                    }
+                                }
+                                "{server.name}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
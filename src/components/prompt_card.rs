@@ -0,0 +1,118 @@
+use crate::components::{ToolAudio, ToolImage};
+use crate::models::{GetPromptResult, Prompt};
+use crate::state::AppState;
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct PromptCardProps {
+    pub server_id: String,
+    pub prompt: Prompt,
+}
+
+/// A prompt from `prompts/list`, with a form for its arguments and the
+/// messages the server returns for them.
+pub fn PromptCard(props: PromptCardProps) -> Element {
+    let mut values = use_signal(HashMap::<String, String>::new);
+    let mut result = use_signal(|| None::<GetPromptResult>);
+    let mut error = use_signal(|| None::<String>);
+    let mut busy = use_signal(|| false);
+
+    let prompt = props.prompt.clone();
+    let args = prompt.arguments.clone().unwrap_or_default();
+    let missing = args.iter().any(|arg| {
+        arg.required.unwrap_or(false)
+            && values
+                .read()
+                .get(&arg.name)
+                .is_none_or(|v| v.trim().is_empty())
+    });
+
+    let server_id = props.server_id.clone();
+    let name = prompt.name.clone();
+    let get_prompt = move |_| {
+        let server_id = server_id.clone();
+        let name = name.clone();
+        let arguments: HashMap<String, String> = values
+            .read()
+            .iter()
+            .filter(|(_, v)| !v.is_empty())
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        error.set(None);
+        busy.set(true);
+        spawn(async move {
+            match AppState::get_prompt(server_id, name, arguments).await {
+                Ok(res) => result.set(Some(res)),
+                Err(e) => {
+                    result.set(None);
+                    error.set(Some(format!("Failed to get prompt: {}", e)));
+                }
+            }
+            busy.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "p-4 border border-zinc-800 rounded-xl bg-zinc-900/50",
+            h3 { class: "font-bold text-white mb-1", "{prompt.name}" }
+            p { class: "text-sm text-zinc-400", "{prompt.description.clone().unwrap_or_default()}" }
+            if !args.is_empty() {
+                div { class: "mt-2 space-y-2",
+                    span { class: "text-xs font-bold text-zinc-500 uppercase", "Arguments" }
+                    for arg in args {
+                        label { key: "{arg.name}", class: "block",
+                            span { class: "text-xs text-zinc-400 font-mono",
+                                "{arg.name} "
+                                if arg.required.unwrap_or(false) {
+                                    "(required)"
+                                }
+                            }
+                            input {
+                                class: "mt-1 w-full px-3 py-1.5 bg-zinc-950 border border-zinc-700 rounded text-sm text-white font-mono focus:outline-none focus:border-indigo-500",
+                                placeholder: "{arg.description.clone().unwrap_or_default()}",
+                                value: "{values.read().get(&arg.name).cloned().unwrap_or_default()}",
+                                oninput: {
+                                    let key = arg.name.clone();
+                                    move |evt: FormEvent| {
+                                        values.write().insert(key.clone(), evt.value());
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            button {
+                class: "mt-3 px-3 py-1 bg-zinc-800 hover:bg-zinc-700 text-zinc-300 rounded text-xs font-bold disabled:opacity-50",
+                disabled: busy() || missing,
+                onclick: get_prompt,
+                if busy() { "Getting..." } else { "Get Prompt" }
+            }
+            if let Some(e) = error() {
+                p { class: "mt-2 text-xs text-red-400", "role": "alert", "{e}" }
+            }
+            if let Some(res) = result() {
+                div { class: "mt-3 space-y-2",
+                    if let Some(description) = res.description {
+                        p { class: "text-xs text-zinc-500", "{description}" }
+                    }
+                    for (i, message) in res.messages.into_iter().enumerate() {
+                        div { key: "{i}", class: "p-3 rounded border border-zinc-800 bg-black/50",
+                            span { class: "text-xs font-bold text-zinc-500 uppercase", "{message.role}" }
+                            if message.content.is_audio() {
+                                ToolAudio { content: message.content, file_stem: format!("{}-{}", prompt.name, i + 1) }
+                            } else if message.content.is_image() {
+                                ToolImage { content: message.content, file_stem: format!("{}-{}", prompt.name, i + 1) }
+                            } else if let Some(text) = message.content.text {
+                                div { class: "mt-1 text-sm text-zinc-300 whitespace-pre-wrap", "{text}" }
+                            } else {
+                                div { class: "mt-1 text-xs text-zinc-500 italic", "[{message.content.content_type} content]" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::components::JsonEditor;
+use crate::process::RawExchange;
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct RawRequestProps {
+    pub server_id: String,
+}
+
+/// Send a request with any method and params, for experimenting with
+/// server extensions the manager knows nothing about. Shows the messages
+/// exchanged in full, envelope and all.
+pub fn RawRequest(props: RawRequestProps) -> Element {
+    let mut method = use_signal(String::new);
+    let mut params = use_signal(|| "{}".to_string());
+    let mut exchange = use_signal(|| None::<Result<RawExchange, String>>);
+    let mut sending = use_signal(|| false);
+
+    let recording = APP_STATE
+        .read()
+        .recordings
+        .read()
+        .contains_key(&props.server_id);
+    let can_send = !method().trim().is_empty() && !sending();
+
+    let server_id = props.server_id.clone();
+    let send = move |_| {
+        let name = method().trim().to_string();
+        let parsed = match params().trim() {
+            "" => Ok(None),
+            text => serde_json::from_str(text)
+                .map(Some)
+                .map_err(|e| format!("Params aren't valid JSON: {}", e)),
+        };
+        let params = match parsed {
+            Ok(params) => params,
+            Err(e) => {
+                exchange.set(Some(Err(e)));
+                return;
+            }
+        };
+        let server_id = server_id.clone();
+        sending.set(true);
+        spawn(async move {
+            let result = AppState::send_raw_request(server_id, name, params).await;
+            exchange.set(Some(result));
+            sending.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "p-4 space-y-3",
+            p { class: "text-xs text-zinc-500",
+                if recording {
+                    "Sent as-is. The request and reply are added to the log and to the session recording."
+                } else {
+                    "Sent as-is. The request and reply are added to the log; start recording to keep them in a session file too."
+                }
+            }
+            div {
+                label { class: "block text-xs font-bold text-zinc-400 mb-2 uppercase", "Method" }
+                input {
+                    class: "w-full px-3 py-2 bg-black/50 border border-zinc-700 rounded font-mono text-sm focus:outline-none focus:border-indigo-500",
+                    placeholder: "e.g. myserver/debugState",
+                    value: "{method}",
+                    oninput: move |evt| method.set(evt.value())
+                }
+            }
+            div {
+                label { class: "block text-xs font-bold text-zinc-400 mb-2 uppercase", "Params (JSON)" }
+                JsonEditor {
+                    value: params(),
+                    on_change: move |v| params.set(v)
+                }
+            }
+            div { class: "flex justify-end",
+                button {
+                    class: "px-4 py-2 bg-indigo-600 hover:bg-indigo-500 disabled:opacity-50 text-white rounded text-sm font-bold transition-colors",
+                    disabled: !can_send,
+                    onclick: send,
+                    if sending() { "Sending..." } else { "Send Request" }
+                }
+            }
+            match exchange() {
+                Some(Ok(exchange)) => {
+                    let request = serde_json::to_string_pretty(&exchange.request).unwrap_or_default();
+                    let elapsed = format!("{}ms", exchange.elapsed.as_millis());
+                    rsx! {
+                        div { class: "space-y-3",
+                            div {
+                                label { class: "block text-xs font-bold text-zinc-400 mb-2 uppercase", "Request" }
+                                pre { class: "p-3 bg-black/50 border border-zinc-800 rounded font-mono text-xs text-zinc-300 whitespace-pre-wrap break-all", "{request}" }
+                            }
+                            div {
+                                div { class: "flex justify-between mb-2",
+                                    label { class: "text-xs font-bold text-zinc-400 uppercase", "Response" }
+                                    span { class: "text-xs text-zinc-500", "{elapsed}" }
+                                }
+                                match exchange.response {
+                                    Ok(message) => {
+                                        let text = serde_json::to_string_pretty(&message).unwrap_or_default();
+                                        let color = if message.get("error").is_some() { "text-red-400" } else { "text-zinc-300" };
+                                        rsx! {
+                                            pre { class: "p-3 bg-black/50 border border-zinc-800 rounded font-mono text-xs whitespace-pre-wrap break-all {color}", "{text}" }
+                                        }
+                                    }
+                                    Err(e) => rsx! {
+                                        pre { class: "p-3 bg-red-500/10 border border-red-500/20 rounded font-mono text-xs text-red-400 whitespace-pre-wrap break-all", "{e}" }
+                                    },
+                                }
+                            }
+                        }
+                    }
+                }
+                Some(Err(e)) => rsx! {
+                    pre { class: "p-3 bg-red-500/10 border border-red-500/20 rounded font-mono text-xs text-red-400 whitespace-pre-wrap break-all", "{e}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
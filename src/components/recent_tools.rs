@@ -0,0 +1,44 @@
+use crate::recent_tools::{ranked, RecentTool};
+use crate::state::APP_STATE;
+use dioxus::prelude::*;
+
+/// How many recent tools the dashboard strip shows.
+const DASHBOARD_RECENT_TOOLS: usize = 6;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct RecentToolsProps {
+    pub on_launch_tool: EventHandler<RecentTool>,
+}
+
+/// Dashboard strip of the most used tools, one click to reopen each.
+pub fn RecentTools(props: RecentToolsProps) -> Element {
+    let recent = ranked(&APP_STATE.read().recent_tools.read(), chrono::Utc::now());
+    if recent.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "mb-8",
+            div { class: "flex items-center justify-between mb-3",
+                h2 { class: "text-xs font-bold uppercase tracking-wider text-zinc-500", "Recent tools" }
+                span { class: "text-[10px] text-zinc-600", "Ctrl+K for all" }
+            }
+            div { class: "flex flex-wrap gap-2",
+                for tool in recent.into_iter().take(DASHBOARD_RECENT_TOOLS) {
+                    button {
+                        key: "{tool.server_id}/{tool.tool}",
+                        class: "flex items-center gap-2 px-3 py-2 rounded-xl glass border border-white-5 hover:border-indigo-500/50 text-sm transition-colors",
+                        title: "Open with last arguments: {tool.args}",
+                        onclick: {
+                            let tool = tool.clone();
+                            move |_| props.on_launch_tool.call(tool.clone())
+                        },
+                        span { class: "text-indigo-400", "▶" }
+                        span { class: "font-mono text-white", "{tool.tool}" }
+                        span { class: "text-xs text-zinc-500", "{tool.server_name}" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::components::JsonEditor;
+use crate::models::CreateServerArgs;
+use crate::registry_entry::{contribution_url, entry, registry_schema, to_json};
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct RegistryEntryProps {
+    /// The server form as it is now.
+    pub args: CreateServerArgs,
+}
+
+/// The server as a `registry.json` entry, for its author to polish and
+/// submit to the project's registry.
+pub fn RegistryEntry(props: RegistryEntryProps) -> Element {
+    let initial = entry(&props.args);
+    let mut text = use_signal({
+        let initial = initial.clone();
+        move || initial.map(|item| to_json(&item)).unwrap_or_default()
+    });
+
+    if let Err(e) = initial {
+        return rsx! {
+            p { class: "text-sm text-amber-400", "⚠️ {e}" }
+        };
+    }
+
+    let schema = registry_schema();
+    let mut problems = crate::components::json_editor::check(&text(), Some(&schema));
+    if problems.is_empty() {
+        if let Ok(value) = serde_json::from_str(&text()) {
+            problems = crate::registry_entry::check(&value);
+        }
+    }
+    // Shape errors are shown by the editor itself
+    let content_problems: Vec<String> = problems
+        .iter()
+        .filter(|p| !p.starts_with('$') && !p.starts_with("Invalid JSON"))
+        .cloned()
+        .collect();
+    let ready = problems.is_empty();
+
+    rsx! {
+        div { class: "space-y-4 text-sm",
+            p { class: "text-xs text-zinc-500",
+                "How this server would appear in the registry. Environment values are replaced by placeholders; reword the wizard steps so users know where to get each value."
+            }
+            JsonEditor {
+                value: text(),
+                schema: Some(schema),
+                height: "h-80".to_string(),
+                on_change: move |v| text.set(v)
+            }
+            if !content_problems.is_empty() {
+                ul { class: "space-y-1 text-xs text-amber-400",
+                    for problem in content_problems {
+                        li { key: "{problem}", "⚠️ {problem}" }
+                    }
+                }
+            }
+            div { class: "flex justify-end gap-2",
+                button {
+                    class: "px-4 py-2 bg-zinc-800 hover:bg-zinc-700 text-zinc-300 rounded-lg text-xs font-bold transition-colors",
+                    onclick: move |_| AppState::copy_to_clipboard(text()),
+                    "Copy"
+                }
+                button {
+                    class: "px-4 py-2 bg-indigo-600 hover:bg-indigo-500 disabled:opacity-50 text-white rounded-lg text-xs font-bold transition-colors",
+                    disabled: !ready,
+                    title: "Copies the entry and opens registry.json on GitHub; paste it into the list and propose the change",
+                    onclick: move |_| {
+                        AppState::copy_to_clipboard(text());
+                        AppState::open_url(&contribution_url());
+                    },
+                    "Open pull request"
+                }
+            }
+        }
+    }
+}
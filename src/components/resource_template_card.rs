@@ -0,0 +1,67 @@
+use crate::models::ResourceTemplate;
+use crate::uri_template;
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct ResourceTemplateCardProps {
+    pub template: ResourceTemplate,
+    /// Called with the expanded URI to read.
+    pub on_read: EventHandler<String>,
+}
+
+/// A template from `resources/templates/list`, with a field for each of
+/// its variables and a preview of the URI they make.
+pub fn ResourceTemplateCard(props: ResourceTemplateCardProps) -> Element {
+    let mut values = use_signal(HashMap::<String, String>::new);
+
+    let template = props.template.clone();
+    let variables = uri_template::variables(&template.uriTemplate);
+    let uri = uri_template::expand(&template.uriTemplate, &values.read());
+    let missing = variables
+        .iter()
+        .any(|name| values.read().get(name).is_none_or(|v| v.trim().is_empty()));
+
+    rsx! {
+        div { class: "p-4 border border-zinc-800 rounded-xl bg-zinc-900/50",
+            h3 { class: "font-bold text-white mb-1", "{template.name}" }
+            div { class: "flex items-center gap-2 text-xs text-zinc-500 mb-2 font-mono",
+                span { class: "px-1.5 py-0.5 bg-zinc-800 rounded", "{template.mimeType.clone().unwrap_or(\"unknown\".into())}" }
+                "{template.uriTemplate}"
+            }
+            p { class: "text-sm text-zinc-400", "{template.description.clone().unwrap_or_default()}" }
+            if !variables.is_empty() {
+                div { class: "mt-2 space-y-2",
+                    for name in variables {
+                        label { key: "{name}", class: "block",
+                            span { class: "text-xs text-zinc-400 font-mono", "{name}" }
+                            input {
+                                class: "mt-1 w-full px-3 py-1.5 bg-zinc-950 border border-zinc-700 rounded text-sm text-white font-mono focus:outline-none focus:border-indigo-500",
+                                value: "{values.read().get(&name).cloned().unwrap_or_default()}",
+                                oninput: {
+                                    let key = name.clone();
+                                    move |evt: FormEvent| {
+                                        values.write().insert(key.clone(), evt.value());
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            div { class: "mt-3 flex items-center gap-2",
+                button {
+                    class: "px-3 py-1 bg-zinc-800 hover:bg-zinc-700 text-zinc-300 rounded text-xs font-bold disabled:opacity-50",
+                    title: if missing { "Fill in every variable to read this resource" } else { "" },
+                    disabled: missing,
+                    onclick: {
+                        let uri = uri.clone();
+                        move |_| props.on_read.call(uri.clone())
+                    },
+                    "Read Resource"
+                }
+                span { class: "text-xs font-mono text-zinc-500 truncate", "{uri}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+/// The directories each server is offered through `roots/list`, e.g. the
+/// folders a filesystem server may read.
+pub fn RootsSettings() -> Element {
+    let servers = APP_STATE.read().servers.cloned();
+    let roots = APP_STATE.read().server_roots.cloned();
+
+    rsx! {
+        section { class: "max-w-3xl mx-auto mb-6 p-5 rounded-2xl glass space-y-3",
+            h3 { class: "text-xs font-bold text-zinc-500 uppercase", "Roots" }
+            p { class: "text-xs text-zinc-500",
+                "Folders a server may work in. Servers that ask for roots get these, and running servers are told when they change."
+            }
+            div { class: "space-y-2",
+                for server in servers.into_iter().filter(|s| !s.is_archived) {
+                    {
+                        let dirs = roots.get(&server.id).cloned().unwrap_or_default();
+                        let id = server.id.clone();
+                        rsx! {
+                            div { key: "{server.id}", class: "p-3 rounded-xl bg-white-5 space-y-2",
+                                div { class: "flex items-center justify-between gap-3",
+                                    span { class: "text-sm text-white font-medium", "{server.name}" }
+                                    button {
+                                        class: "px-3 py-1 rounded-lg text-xs text-zinc-400 hover:text-white hover:bg-white-8 transition-colors",
+                                        onclick: move |_| {
+                                            let id = id.clone();
+                                            spawn(async move { AppState::add_server_root(id).await });
+                                        },
+                                        "📁 Add folder"
+                                    }
+                                }
+                                if dirs.is_empty() {
+                                    p { class: "text-xs text-zinc-600", "No roots" }
+                                }
+                                for dir in dirs {
+                                    {
+                                        let label = dir.display().to_string();
+                                        let id = server.id.clone();
+                                        rsx! {
+                                            div { key: "{label}", class: "flex items-center justify-between gap-3 text-xs",
+                                                span { class: "text-zinc-300 font-mono truncate", title: "{label}", "{label}" }
+                                                button {
+                                                    class: "text-zinc-500 hover:text-red-400 transition-colors",
+                                                    title: "Stop offering this folder",
+                                                    onclick: move |_| AppState::remove_server_root(&id, &dir),
+                                                    "✕"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::models::NotificationLevel;
+use crate::sampling::content_text;
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+/// Asks the user to approve the oldest sampling request a server sent
+/// before it goes to the LLM. Renders nothing while none are waiting.
+pub fn SamplingApproval() -> Element {
+    let mut sending = use_signal(|| false);
+    let pending = APP_STATE.read().sampling_requests.read().first().cloned();
+    let Some(pending) = pending else {
+        return rsx! {};
+    };
+    let more = APP_STATE.read().sampling_requests.read().len() - 1;
+    let settings = APP_STATE.read().sampling.cloned();
+    let request = pending.request.clone();
+    let hints = request.model_hints().join(", ");
+    let endpoint = settings.endpoint().to_string();
+    let server_name = pending.server_name.clone();
+
+    let approve = {
+        let pending = pending.clone();
+        move |_| {
+            let server_id = pending.server_id.clone();
+            let request_id = pending.request_id.clone();
+            sending.set(true);
+            spawn(async move {
+                if let Err(e) = AppState::approve_sampling(server_id, request_id).await {
+                    AppState::push_notification(e, NotificationLevel::Error);
+                }
+                sending.set(false);
+            });
+        }
+    };
+    let reject = move |_| {
+        let server_id = pending.server_id.clone();
+        let request_id = pending.request_id.clone();
+        spawn(async move {
+            if let Err(e) = AppState::reject_sampling(server_id, request_id).await {
+                AppState::push_notification(e, NotificationLevel::Error);
+            }
+        });
+    };
+
+    rsx! {
+        div { class: "fixed inset-0 z-50 flex items-center justify-center bg-black/60 p-4 backdrop-blur-md",
+            div { class: "w-full max-w-xl max-h-[85vh] bg-zinc-950 text-zinc-300 rounded-2xl flex flex-col overflow-hidden border border-zinc-800 shadow-2xl animate-scale-in",
+                div { class: "p-5 bg-zinc-900 border-b border-zinc-800",
+                    h2 { class: "font-bold text-xl text-white", "🧠 {server_name} wants to sample the LLM" }
+                    p { class: "text-xs text-zinc-500 mt-1",
+                        "Sent to {settings.model} at {endpoint} · up to {request.max_tokens} tokens"
+                        if more > 0 { " · {more} more waiting" }
+                    }
+                }
+
+                div { class: "flex-1 overflow-y-auto custom-scrollbar p-6 space-y-4",
+                    if let Some(system) = request.system_prompt.clone() {
+                        div { class: "space-y-1",
+                            div { class: "text-xs font-bold text-zinc-500 uppercase tracking-wider", "System prompt" }
+                            p { class: "text-sm text-zinc-400 whitespace-pre-wrap", "{system}" }
+                        }
+                    }
+                    for (i, message) in request.messages.iter().enumerate() {
+                        div { key: "{i}", class: "space-y-1",
+                            div { class: "text-xs font-bold text-zinc-500 uppercase tracking-wider", "{message.role}" }
+                            p { class: "text-sm text-zinc-200 whitespace-pre-wrap rounded-lg bg-zinc-900 border border-zinc-800 p-3",
+                                {content_text(&message.content)}
+                            }
+                        }
+                    }
+                    if !hints.is_empty() {
+                        p { class: "text-xs text-zinc-500", "Server prefers: {hints}" }
+                    }
+                }
+
+                div { class: "p-5 bg-zinc-900 border-t border-zinc-800 flex justify-end gap-3",
+                    button {
+                        class: "px-5 py-2.5 text-zinc-400 hover:text-white transition-colors disabled:opacity-40",
+                        disabled: sending(),
+                        onclick: reject,
+                        "Reject"
+                    }
+                    button {
+                        class: "px-6 py-2.5 bg-indigo-600 hover:bg-indigo-500 text-white rounded-xl text-sm font-bold transition-colors shadow-lg shadow-indigo-500/20 disabled:opacity-40",
+                        disabled: sending(),
+                        onclick: approve,
+                        if sending() { "Sending..." } else { "Approve" }
+                    }
+                }
+            }
+        }
+    }
+}
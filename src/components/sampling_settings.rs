@@ -0,0 +1,84 @@
+use crate::models::SamplingProvider;
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+/// The LLM API that answers servers' sampling requests once approved.
+pub fn SamplingSettings() -> Element {
+    let settings = APP_STATE.read().sampling.cloned();
+    let default_endpoint = settings.provider.default_endpoint();
+
+    let update = move |change: &dyn Fn(&mut crate::models::SamplingSettings)| {
+        let mut settings = APP_STATE.read().sampling.cloned();
+        change(&mut settings);
+        AppState::save_sampling(settings);
+    };
+
+    rsx! {
+        section { class: "max-w-3xl mx-auto mb-6 p-5 rounded-2xl glass space-y-3",
+            div { class: "flex items-center justify-between gap-4",
+                h3 { class: "text-xs font-bold text-zinc-500 uppercase", "Sampling" }
+                label { class: "flex items-center gap-2 text-xs text-zinc-300",
+                    input {
+                        r#type: "checkbox",
+                        checked: settings.enabled,
+                        onchange: move |evt| {
+                            let enabled = evt.checked();
+                            update(&|s| s.enabled = enabled);
+                        }
+                    }
+                    "Enabled"
+                }
+            }
+            p { class: "text-xs text-zinc-500",
+                "Servers can ask the manager to run a prompt through an LLM. Each request is shown for your approval before it is sent; while sampling is off they are refused."
+            }
+            div { class: "grid grid-cols-2 gap-3",
+                select {
+                    class: "px-3 py-2 bg-zinc-900 border border-zinc-700 rounded-xl text-sm text-white focus:outline-none focus:border-indigo-500",
+                    onchange: move |evt| {
+                        let value = evt.value();
+                        if let Some(provider) = SamplingProvider::ALL.into_iter().find(|p| p.label() == value) {
+                            update(&|s| s.provider = provider);
+                        }
+                    },
+                    for (provider, label) in SamplingProvider::ALL.map(|p| (p, p.label())) {
+                        option {
+                            key: "{label}",
+                            value: "{label}",
+                            selected: settings.provider == provider,
+                            "{label}"
+                        }
+                    }
+                }
+                input {
+                    class: "px-3 py-2 bg-zinc-900 border border-zinc-700 rounded-xl text-sm text-white focus:outline-none focus:border-indigo-500",
+                    placeholder: "Model, e.g. gpt-4o-mini",
+                    value: "{settings.model}",
+                    onchange: move |evt| {
+                        let model = evt.value();
+                        update(&|s| s.model = model.clone());
+                    }
+                }
+                input {
+                    class: "col-span-2 px-3 py-2 bg-zinc-900 border border-zinc-700 rounded-xl text-sm text-white font-mono focus:outline-none focus:border-indigo-500",
+                    placeholder: "{default_endpoint}",
+                    value: "{settings.endpoint}",
+                    onchange: move |evt| {
+                        let endpoint = evt.value();
+                        update(&|s| s.endpoint = endpoint.clone());
+                    }
+                }
+                input {
+                    class: "col-span-2 px-3 py-2 bg-zinc-900 border border-zinc-700 rounded-xl text-sm text-white font-mono focus:outline-none focus:border-indigo-500",
+                    r#type: "password",
+                    placeholder: "API key",
+                    value: "{settings.api_key}",
+                    onchange: move |evt| {
+                        let key = evt.value();
+                        update(&|s| s.api_key = key.clone());
+                    }
+                }
+            }
+        }
+    }
+}
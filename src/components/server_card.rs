@@ -1,4 +1,6 @@
+use crate::circuit_breaker::BreakerState;
 use crate::models::McpServer;
+use crate::probe::ProbeLevel;
 use crate::state::APP_STATE;
 use dioxus::prelude::*;
 
@@ -26,7 +28,7 @@ pub fn ServerCard(props: ServerCardProps) -> Element {
             if running {
                 crate::state::AppState::stop_server_process(&srv.id).await;
             } else {
-                let _ = crate::state::AppState::start_server_process(srv).await;
+                crate::state::AppState::start_server_with_notification(srv).await;
             }
         });
     };
@@ -37,15 +39,109 @@ pub fn ServerCard(props: ServerCardProps) -> Element {
         spawn(async move {
             // Stop then start
             crate::state::AppState::stop_server_process(&srv.id).await;
-            let _ = crate::state::AppState::start_server_process(srv).await;
+            crate::state::AppState::start_server_with_notification(srv).await;
         });
     };
 
+    let server_for_replay = props.server.clone();
+    let replay_session = move |_| {
+        let srv = server_for_replay.clone();
+        spawn(async move {
+            if let Err(e) = crate::state::AppState::start_replay(srv).await {
+                crate::state::AppState::push_notification(
+                    format!("Could not replay the recording: {}", e),
+                    crate::models::NotificationLevel::Error,
+                );
+            }
+        });
+    };
+
+    let server_for_archive = props.server.clone();
+    let archive_server = move |_| {
+        let srv = server_for_archive.clone();
+        spawn(async move {
+            match crate::state::AppState::set_server_archived(srv.id.clone(), true).await {
+                Ok(()) => crate::state::AppState::push_notification(
+                    format!(
+                        "Archived {}. Restore it from the Archived section.",
+                        srv.name
+                    ),
+                    crate::models::NotificationLevel::Info,
+                ),
+                Err(e) => crate::state::AppState::push_notification(
+                    format!("Could not archive {}: {}", srv.name, e),
+                    crate::models::NotificationLevel::Error,
+                ),
+            }
+        });
+    };
+
+    let pinned = props.server.is_pinned;
+    let server_for_pin = props.server.clone();
+    let toggle_pinned = move |_| {
+        if let Err(e) = crate::state::AppState::set_server_pinned(&server_for_pin.id, !pinned) {
+            crate::state::AppState::push_notification(
+                format!("Could not update {}: {}", server_for_pin.name, e),
+                crate::models::NotificationLevel::Error,
+            );
+        }
+    };
+
     let running = is_running();
+    let restart_required = running && crate::state::AppState::needs_restart(&props.server);
     let desc = props.server.description.clone().unwrap_or_default();
+    let tags = APP_STATE
+        .read()
+        .server_tags
+        .read()
+        .get(&props.server.id)
+        .cloned()
+        .unwrap_or_default();
+    let lint_warnings = crate::lint::lint_server(&props.server);
+    let expiry = crate::expiry::remaining(&props.server, chrono::Utc::now());
+    let breaker = APP_STATE
+        .read()
+        .circuit_breakers
+        .read()
+        .state(&props.server.id, std::time::Instant::now());
+    let server_for_breaker = props.server.id.clone();
+    // Liveness pings of a running server; None until the first one
+    let probe = if running {
+        APP_STATE.read().probes.read().get(&props.server.id).cloned()
+    } else {
+        None
+    };
+    let status_dot = match probe.as_ref().map(|p| p.level()) {
+        _ if !running => "bg-zinc-600",
+        Some(ProbeLevel::Unhealthy) => "bg-red-500 shadow-[0_0_8px_rgba(239,68,68,0.6)]",
+        Some(ProbeLevel::Degraded) => "bg-amber-400 shadow-[0_0_8px_rgba(251,191,36,0.6)]",
+        _ => "bg-green-400 shadow-[0_0_8px_rgba(74,222,128,0.6)] animate-pulse",
+    };
+    let usage = APP_STATE
+        .read()
+        .resource_usage
+        .read()
+        .get(&props.server.id)
+        .map(|u| u.summary());
+    let status_title = match &probe {
+        Some(probe) => probe.summary(),
+        None if running => "Running".to_string(),
+        None => "Stopped".to_string(),
+    };
+    let error_spike = {
+        let state = APP_STATE.read();
+        let metrics = state.request_metrics.read();
+        metrics.is_over_budget(&props.server.id).then(|| {
+            metrics.error_rate(
+                &props.server.id,
+                std::time::Instant::now(),
+                state.error_budget.read().window(),
+            )
+        })
+    };
 
     // Icons
-    let type_icon = if props.server.server_type == "sse" {
+    let type_icon = if props.server.is_remote() {
         // Globe icon
         rsx! {
             svg { class: "w-6 h-6", fill: "none", view_box: "0 0 24 24", stroke: "currentColor", stroke_width: "1.5",
@@ -61,14 +157,14 @@ pub fn ServerCard(props: ServerCardProps) -> Element {
         }
     };
 
-    let type_label = if props.server.server_type == "sse" {
-        "Remote SSE"
-    } else {
-        "Local STDIO"
+    let type_label = match props.server.server_type.as_str() {
+        "sse" => "Remote SSE",
+        "ws" => "Remote WebSocket",
+        _ => "Local STDIO",
     };
 
     // Runtime config display
-    let runtime_config = if props.server.server_type == "sse" {
+    let runtime_config = if props.server.is_remote() {
         props
             .server
             .url
@@ -129,30 +225,51 @@ pub fn ServerCard(props: ServerCardProps) -> Element {
                             div {
                                 class: "flex items-center gap-2",
                                 span {
-                                    class: format!(
-                                        "h-2 w-2 rounded-full {}",
-                                        if running { "bg-green-400 shadow-[0_0_8px_rgba(74,222,128,0.6)] animate-pulse" } else { "bg-zinc-600" }
-                                    ),
+                                    class: "h-2 w-2 rounded-full {status_dot}",
+                                    title: "{status_title}",
                                 }
                                 span {
                                     class: "text-xs font-medium text-zinc-400 uppercase tracking-wider",
                                     "{type_label}"
                                 }
+                                if let Some(left) = expiry {
+                                    span {
+                                        class: "px-1.5 py-0.5 rounded bg-amber-500/10 text-[10px] font-bold text-amber-400",
+                                        title: "Temporary: archived when it expires",
+                                        "⏳ {left}"
+                                    }
+                                }
                             }
                         }
                     }
 
-                    // Power Button
-                    button {
-                        class: format!(
-                            "flex h-10 w-10 items-center justify-center rounded-xl transition-all active:scale-95 duration-200 {}",
-                            if running { "bg-red-500/10 text-red-400 hover:bg-red-500/20 ring-1 ring-red-500/20" }
-                            else { "bg-green-500/10 text-green-400 hover:bg-green-500/20 ring-1 ring-green-500/20" }
-                        ),
-                        onclick: toggle_server.clone(),
-                        title: if running { "Stop Server" } else { "Start Server" },
-                        svg { class: "w-5 h-5", fill: "none", view_box: "0 0 24 24", stroke: "currentColor", stroke_width: "2",
-                            path { stroke_linecap: "round", stroke_linejoin: "round", d: "M5.636 5.636a9 9 0 1012.728 0M12 3v9" }
+                    div {
+                        class: "flex items-center gap-2",
+                        // Favorite toggle
+                        button {
+                            class: format!(
+                                "flex h-10 w-10 items-center justify-center rounded-xl text-lg transition-all active:scale-95 duration-200 {}",
+                                if pinned { "text-amber-400 hover:bg-amber-500/10" }
+                                else { "text-zinc-600 hover:text-zinc-300 hover:bg-white/5" }
+                            ),
+                            onclick: toggle_pinned,
+                            title: if pinned { "Unpin from the top" } else { "Pin to the top" },
+                            "aria-pressed": "{pinned}",
+                            if pinned { "★" } else { "☆" }
+                        }
+
+                        // Power Button
+                        button {
+                            class: format!(
+                                "flex h-10 w-10 items-center justify-center rounded-xl transition-all active:scale-95 duration-200 {}",
+                                if running { "bg-red-500/10 text-red-400 hover:bg-red-500/20 ring-1 ring-red-500/20" }
+                                else { "bg-green-500/10 text-green-400 hover:bg-green-500/20 ring-1 ring-green-500/20" }
+                            ),
+                            onclick: toggle_server.clone(),
+                            title: if running { "Stop Server" } else { "Start Server" },
+                            svg { class: "w-5 h-5", fill: "none", view_box: "0 0 24 24", stroke: "currentColor", stroke_width: "2",
+                                path { stroke_linecap: "round", stroke_linejoin: "round", d: "M5.636 5.636a9 9 0 1012.728 0M12 3v9" }
+                            }
                         }
                     }
                 }
@@ -165,6 +282,18 @@ pub fn ServerCard(props: ServerCardProps) -> Element {
                     } else {
                          p { class: "text-sm text-zinc-400 leading-relaxed line-clamp-2", "{desc}" }
                     }
+                    if !tags.is_empty() {
+                        div {
+                            class: "mt-2 flex flex-wrap gap-1.5",
+                            for tag in tags {
+                                span {
+                                    key: "{tag}",
+                                    class: "px-2 py-0.5 rounded-md bg-white-5 text-[11px] font-mono text-zinc-400 ring-1 ring-white-10",
+                                    "{tag}"
+                                }
+                            }
+                        }
+                    }
                 }
 
                 // Details Area
@@ -185,6 +314,35 @@ pub fn ServerCard(props: ServerCardProps) -> Element {
                         }
                     }
 
+                    // Running with an outdated config
+                    if restart_required {
+                        div {
+                            class: "flex items-center justify-between gap-3 rounded-xl bg-indigo-500/5 border border-indigo-500/20 p-3",
+                            p {
+                                class: "text-[11px] text-indigo-300 leading-snug",
+                                "⟳ Restart required: the config changed since this server started."
+                            }
+                            button {
+                                class: "shrink-0 px-2 py-1 rounded-lg text-[11px] font-bold bg-indigo-600 hover:bg-indigo-500 text-white transition-colors",
+                                onclick: restart_server.clone(),
+                                "Apply & restart"
+                            }
+                        }
+                    }
+
+                    // Lint Warnings
+                    if !lint_warnings.is_empty() {
+                        div {
+                            class: "rounded-xl bg-amber-500/5 border border-amber-500/20 p-3 space-y-1",
+                            for warning in lint_warnings.iter() {
+                                p {
+                                    class: "text-[11px] text-amber-400 leading-snug",
+                                    "⚠️ {warning.message}"
+                                }
+                            }
+                        }
+                    }
+
                     // Env Vars
                     if !env_preview.is_empty() {
                         div {
@@ -214,11 +372,58 @@ pub fn ServerCard(props: ServerCardProps) -> Element {
                 div {
                     class: "text-[10px] font-bold uppercase tracking-wider text-zinc-600",
                      if running { span { class: "text-green-500/80", "• Active" } } else { span { "• Idle" } }
+                     match breaker {
+                         BreakerState::Open { remaining } => rsx! {
+                             span {
+                                 class: "ml-2 text-red-400",
+                                 title: "Recent calls kept failing; hub calls fail fast until the cool-down ends",
+                                 "• Unhealthy · retry in {remaining.as_secs().max(1)}s"
+                             }
+                             button {
+                                 class: "ml-2 text-zinc-400 hover:text-white underline",
+                                 onclick: move |_| crate::state::AppState::reset_circuit_breaker(&server_for_breaker),
+                                 "Reset"
+                             }
+                         },
+                         BreakerState::HalfOpen => rsx! {
+                             span {
+                                 class: "ml-2 text-amber-400",
+                                 title: "The next hub call decides whether the server is healthy again",
+                                 "• Recovering"
+                             }
+                         },
+                         BreakerState::Closed => rsx! {},
+                     }
+                     if let Some(usage) = usage {
+                         span {
+                             class: "ml-2 normal-case",
+                             title: "CPU and memory of the server and the processes it started",
+                             "• {usage}"
+                         }
+                     }
+                     if let Some(rate) = error_spike {
+                         span {
+                             class: "ml-2 text-amber-400",
+                             title: "Over the error budget set in the Hub tab",
+                             "• {rate.percent()}% errors ({rate.failed}/{rate.total})"
+                         }
+                     }
                 }
 
                 div {
                     class: "flex items-center gap-2",
 
+                    if props.server.server_type == "stdio" && !running {
+                        button {
+                            class: "p-2 rounded-lg text-zinc-400 hover:text-white hover:bg-white-8 transition-colors",
+                            onclick: replay_session,
+                            title: "Replay a recorded session",
+                            svg { class: "w-4 h-4", fill: "none", view_box: "0 0 24 24", stroke: "currentColor", stroke_width: "2",
+                                path { stroke_linecap: "round", stroke_linejoin: "round", d: "M4 4v5h.582M20 12a8 8 0 11-2.343-5.657M10 9l5 3-5 3V9z" }
+                            }
+                        }
+                    }
+
                     if props.server.server_type == "stdio" {
                         button {
                             class: "p-2 rounded-lg text-zinc-400 hover:text-white hover:bg-white-8 transition-colors",
@@ -239,6 +444,15 @@ pub fn ServerCard(props: ServerCardProps) -> Element {
                         }
                     }
 
+                    button {
+                        class: "p-2 rounded-lg text-zinc-400 hover:text-white hover:bg-white-8 transition-colors",
+                        onclick: archive_server,
+                        title: "Archive (hide, keeping its config)",
+                        svg { class: "w-4 h-4", fill: "none", view_box: "0 0 24 24", stroke: "currentColor", stroke_width: "2",
+                            path { stroke_linecap: "round", stroke_linejoin: "round", d: "M5 8h14M5 8a2 2 0 110-4h14a2 2 0 110 4M5 8v10a2 2 0 002 2h10a2 2 0 002-2V8m-9 4h4" }
+                        }
+                    }
+
                     button {
                         class: "p-2 rounded-lg text-zinc-400 hover:text-white hover:bg-white-8 transition-colors",
                         onclick: move |_| (props.on_edit_click)(()),
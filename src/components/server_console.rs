@@ -1,4 +1,13 @@
-use crate::models::{McpServer, Prompt, Resource, Tool};
+use crate::components::{
+    JsonEditor, PromptCard, RawRequest, ResourceTemplateCard, SchemaView, TerminalView, ToolAudio,
+    ToolDocs, ToolImage, ToolOutput, ToolOutputDiff, ToolPresets, ToolSnippets,
+};
+use crate::log_time::TimeDisplay;
+use crate::models::{
+    Content, LoggingLevel, McpServer, Prompt, Resource, ResourceTemplate, Tool, Truncation,
+};
+use crate::process::ListKind;
+use crate::recent_tools::RecentTool;
 use crate::state::AppState;
 use crate::state::APP_STATE;
 use dioxus::prelude::*;
@@ -7,6 +16,20 @@ use dioxus::prelude::*;
 pub struct ServerConsoleProps {
     server: McpServer,
     on_close: EventHandler<()>,
+    /// Tab to open with, as saved in the session (e.g. "tools").
+    #[props(default)]
+    initial_tab: Option<String>,
+    /// Recently used tool to open straight away, pre-filled with its last arguments.
+    #[props(default)]
+    launch_tool: Option<RecentTool>,
+}
+
+/// A tool result kept aside so later runs can be compared with it.
+#[derive(Clone, PartialEq)]
+struct PinnedRun {
+    text: String,
+    is_error: bool,
+    args: String,
 }
 
 #[derive(Clone, PartialEq)]
@@ -15,18 +38,61 @@ enum Tab {
     Tools,
     Resources,
     Prompts,
+    /// The server's terminal, in PTY mode.
+    Terminal,
+    /// Requests with any method, typed by hand.
+    Raw,
+}
+
+impl Tab {
+    fn name(&self) -> &'static str {
+        match self {
+            Tab::Logs => "logs",
+            Tab::Tools => "tools",
+            Tab::Resources => "resources",
+            Tab::Prompts => "prompts",
+            Tab::Terminal => "terminal",
+            Tab::Raw => "raw",
+        }
+    }
+
+    fn from_name(name: &str) -> Tab {
+        match name {
+            "tools" => Tab::Tools,
+            "resources" => Tab::Resources,
+            "prompts" => Tab::Prompts,
+            "terminal" => Tab::Terminal,
+            // Its name in earlier versions
+            "raw" | "advanced" => Tab::Raw,
+            _ => Tab::Logs,
+        }
+    }
 }
 
 pub fn ServerConsole(props: ServerConsoleProps) -> Element {
-    let mut active_tab = use_signal(|| Tab::Logs);
+    let initial_tab = match props.launch_tool {
+        Some(_) => Some(Tab::Tools),
+        None => props.initial_tab.as_deref().map(Tab::from_name),
+    };
+    let mut active_tab = use_signal(|| initial_tab.clone().unwrap_or(Tab::Logs));
     let mut active_tool = use_signal(|| None::<Tool>);
+    // Tool whose generated docs are expanded in the Tools tab
+    let mut docs_tool = use_signal(|| None::<String>);
     let mut tool_args = use_signal(|| "{}".to_string());
     let mut tool_output = use_signal(|| None::<String>);
     let mut tool_error = use_signal(|| false);
+    let mut tool_media = use_signal(Vec::<Content>::new);
+    // Set when the result was cut down to the response size limit
+    let mut tool_truncation = use_signal(|| None::<Truncation>);
+    let mut pinned = use_signal(|| None::<PinnedRun>);
+    // Arguments the shown result was produced with
+    let mut output_args = use_signal(String::new);
     let mut active_resource_content = use_signal(|| None::<(String, String)>); // (uri, content)
+    let mut watch_error = use_signal(|| None::<String>);
 
     let mut tools_list = use_signal(Vec::<Tool>::new);
     let mut resources_list = use_signal(Vec::<Resource>::new);
+    let mut templates_list = use_signal(Vec::<ResourceTemplate>::new);
     let mut prompts_list = use_signal(Vec::<Prompt>::new);
     let mut error_msg = use_signal(|| None::<String>);
     let mut is_loading = use_signal(|| false);
@@ -40,12 +106,57 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
         map.get(&srv_id).cloned()
     });
 
+    let mut time_display = use_signal(|| TimeDisplay::Absolute);
+    // Re-render every second so relative times stay current
+    let mut now = use_signal(chrono::Utc::now);
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            if time_display() == TimeDisplay::Relative {
+                now.set(chrono::Utc::now());
+            }
+        }
+    });
+
+    // The level asked of the server; while one is set, the log shows only
+    // the server's log messages
+    let mut log_level = use_signal(|| None::<LoggingLevel>);
+    let supports_logging = AppState::supports_logging(&props.server.id);
+
     let log_text = if let Some(sig) = log_signal() {
-        sig.read().clone()
+        match log_level() {
+            Some(min) => crate::log_time::render(
+                &crate::log_time::server_messages(&sig.read(), min),
+                time_display(),
+                now(),
+            ),
+            None => crate::log_time::render(&sig.read(), time_display(), now()),
+        }
     } else {
         "Process not running or no logs yet.".to_string()
     };
 
+    // Where the log stood when the last tool call started, and the call's
+    // request id if the server reported one
+    let mut call_log_start = use_signal(|| None::<(usize, Option<u64>)>);
+    let call_logs = match (call_log_start(), log_signal()) {
+        (Some((start, request_id)), Some(sig)) => {
+            let logs =
+                crate::log_time::request_logs(sig.read().get(start..).unwrap_or(""), request_id);
+            crate::log_time::render(&logs, time_display(), now())
+        }
+        _ => String::new(),
+    };
+    let call_log_lines = call_logs.lines().count();
+
+    let recording = APP_STATE
+        .read()
+        .recordings
+        .read()
+        .contains_key(&props.server.id);
+
+    let has_terminal = AppState::terminal(&props.server.id).is_some();
+
     let status_text = if log_signal().is_some() {
         "Connected"
     } else {
@@ -70,16 +181,70 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
     let fetch_resources = move |_| {
         let id_val = srv_id_resources.clone();
         spawn(async move {
-            match AppState::get_resources(id_val).await {
+            match AppState::get_resources(id_val.clone()).await {
                 Ok(r) => {
                     resources_list.set(r);
                     error_msg.set(None);
                 }
                 Err(e) => error_msg.set(Some(e)),
             }
+            // Not every server has templates; those without reply with an error
+            templates_list.set(
+                AppState::get_resource_templates(id_val)
+                    .await
+                    .unwrap_or_default(),
+            );
+        });
+    };
+
+    let srv_id_resource = props.server.id.clone();
+    let load_resource = move |uri: String| {
+        let id_val = srv_id_resource.clone();
+        is_loading.set(true);
+        spawn(async move {
+            match AppState::read_resource(id_val, uri.clone()).await {
+                Ok(res) => {
+                    let text = match res.contents.first() {
+                        Some(content) => match (&content.text, &content.blob) {
+                            (Some(text), _) => text.clone(),
+                            (None, Some(blob)) => format!(
+                                "[Base64 Blob: {}...]",
+                                blob.chars().take(50).collect::<String>()
+                            ),
+                            (None, None) => "Empty content".into(),
+                        },
+                        None => "No content returned".into(),
+                    };
+                    active_resource_content.set(Some((uri, text)));
+                }
+                Err(e) => {
+                    error_msg.set(Some(format!("Failed to read resource: {}", e)));
+                }
+            }
+            is_loading.set(false);
         });
     };
 
+    // Re-read the open resource whenever the server reports it changed
+    let watched_resources = APP_STATE.read().watched_resources;
+    let srv_id_watch = props.server.id.clone();
+    let open_resource_updates = use_memo(move || {
+        let uri = active_resource_content.read().as_ref()?.0.clone();
+        watched_resources
+            .read()
+            .get(&srv_id_watch)?
+            .get(&uri)
+            .copied()
+    });
+    let reload_resource = load_resource.clone();
+    use_effect(move || {
+        if open_resource_updates().is_some_and(|n| n > 0) {
+            if let Some((uri, _)) = active_resource_content.peek().clone() {
+                reload_resource(uri);
+            }
+        }
+    });
+
     let srv_id_prompts = props.server.id.clone();
     let fetch_prompts = move |_| {
         let id_val = srv_id_prompts.clone();
@@ -94,9 +259,90 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
         });
     };
 
-    let srv_id_exec = props.server.id.clone();
+    // Reload the open list whenever the server says it changed
+    let list_changes = APP_STATE.read().list_changes;
+    let srv_id_changes = props.server.id.clone();
+    let open_list_changes = use_memo(move || {
+        let kind = match *active_tab.read() {
+            Tab::Tools => ListKind::Tools,
+            Tab::Resources => ListKind::Resources,
+            Tab::Prompts => ListKind::Prompts,
+            _ => return None,
+        };
+        let count = list_changes
+            .read()
+            .get(&(srv_id_changes.clone(), kind))
+            .copied()
+            .unwrap_or(0);
+        Some((kind, count))
+    });
+    let (reload_tools, reload_resources, reload_prompts) = (
+        fetch_tools.clone(),
+        fetch_resources.clone(),
+        fetch_prompts.clone(),
+    );
+    let mut seen_list_change = use_signal(|| None::<(ListKind, u64)>);
+    use_effect(move || {
+        let change = open_list_changes();
+        let previous = *seen_list_change.peek();
+        seen_list_change.set(change);
+        // Switching tabs changes the memo too; only a higher count for the
+        // same list means the server changed it
+        let Some(((kind, count), (seen_kind, seen))) = change.zip(previous) else {
+            return;
+        };
+        if kind == seen_kind && count > seen {
+            match kind {
+                ListKind::Tools => reload_tools(()),
+                ListKind::Resources => reload_resources(()),
+                ListKind::Prompts => reload_prompts(()),
+            }
+        }
+    });
+
+    // A restored tab needs its list loaded, as if it had been clicked
+    let (load_tools, load_resources, load_prompts) = (
+        fetch_tools.clone(),
+        fetch_resources.clone(),
+        fetch_prompts.clone(),
+    );
+    let launch = props.launch_tool.clone();
+    let srv_id_launch = props.server.id.clone();
+    use_hook(move || match (initial_tab, launch) {
+        (_, Some(recent)) => {
+            spawn(async move {
+                match AppState::get_tools(srv_id_launch).await {
+                    Ok(tools) => {
+                        match tools.iter().find(|t| t.name == recent.tool) {
+                            Some(tool) => {
+                                tool_args.set(recent.args.clone());
+                                active_tool.set(Some(tool.clone()));
+                            }
+                            None => error_msg.set(Some(format!(
+                                "Tool \"{}\" is no longer offered by this server",
+                                recent.tool
+                            ))),
+                        }
+                        tools_list.set(tools);
+                    }
+                    Err(e) => error_msg.set(Some(e)),
+                }
+            });
+        }
+        (Some(Tab::Tools), None) => load_tools(()),
+        (Some(Tab::Resources), None) => load_resources(()),
+        (Some(Tab::Prompts), None) => load_prompts(()),
+        _ => {}
+    });
+
+    use_effect(move || {
+        let name = active_tab.read().name().to_string();
+        AppState::update_session(|s| s.console_tab = Some(name));
+    });
+
+    let server_exec = props.server.clone();
     let execute_tool = move |_| {
-        let id_val = srv_id_exec.clone();
+        let server = server_exec.clone();
         let t_name = active_tool()
             .as_ref()
             .map(|t| t.name.clone())
@@ -106,6 +352,10 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
         is_loading.set(true);
         tool_output.set(None);
         tool_error.set(false);
+        tool_media.set(Vec::new());
+        tool_truncation.set(None);
+        call_log_start.set(None);
+        let log_start = log_signal().map(|sig| sig.read().len()).unwrap_or(0);
 
         spawn(async move {
             let args_json: serde_json::Value = match serde_json::from_str(&t_args_str) {
@@ -118,12 +368,17 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
                 }
             };
 
-            match AppState::execute_tool(id_val, t_name, args_json).await {
+            AppState::record_recent_tool(&server, &t_name, &t_args_str);
+            output_args.set(t_args_str);
+            match AppState::execute_tool(server.id, t_name, args_json).await {
                 Ok(res) => {
                     // Combine all content parts
                     let mut output = String::new();
+                    let mut media = Vec::new();
                     for content in res.content {
-                        if let Some(text) = content.text {
+                        if content.is_image() || content.is_audio() {
+                            media.push(content);
+                        } else if let Some(text) = content.text {
                             output.push_str(&text);
                             output.push('\n');
                         } else if let Some(data) = content.data {
@@ -134,13 +389,18 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
                         }
                     }
                     tool_output.set(Some(output));
+                    tool_media.set(media);
+                    tool_truncation.set(res.truncated);
                     if let Some(is_err) = res.isError {
                         tool_error.set(is_err);
                     }
+                    call_log_start.set(Some((log_start, res.request_id)));
                 }
                 Err(e) => {
                     tool_output.set(Some(e));
                     tool_error.set(true);
+                    // No request id; show any stderr tagged during the call
+                    call_log_start.set(Some((log_start, None)));
                 }
             }
             is_loading.set(false);
@@ -168,9 +428,29 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
         });
     };
 
+    // Only servers run from local files can be packaged
+    let bundleable = crate::mcpb::bundle_source(&props.server).is_ok();
+
     let current_tab = active_tab.read().clone();
     let current_tool = active_tool.read().clone();
     let current_resource = active_resource_content.read().clone();
+    let watched = watched_resources
+        .read()
+        .get(&props.server.id)
+        .cloned()
+        .unwrap_or_default();
+    let modal_width = if pinned.read().is_some() {
+        "max-w-5xl"
+    } else {
+        "max-w-2xl"
+    };
+
+    let usage = APP_STATE
+        .read()
+        .resource_usage
+        .read()
+        .get(&props.server.id)
+        .map(|u| u.summary());
 
     let active_class = "px-4 py-2 text-sm font-medium transition-colors text-white border-b-2 border-indigo-500 bg-zinc-800/50";
     let inactive_class =
@@ -190,6 +470,13 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
                         }
                     }
                     div { class: "flex items-center gap-2",
+                        if let Some(usage) = usage {
+                            span {
+                                class: "text-zinc-400 text-xs font-mono mr-2",
+                                title: "CPU and memory of the server and the processes it started",
+                                "{usage}"
+                            }
+                        }
                         if let Some(res) = ping_result() {
                              match res {
                                  Ok(ms) => rsx! { span { class: "text-green-400 text-xs font-bold mr-2 animate-pulse", "🟢 {ms}ms" } },
@@ -201,6 +488,51 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
                             onclick: test_connection,
                             if ping_result().is_none() { "Test Connection" } else { "Retest" }
                         }
+                        if !props.server.is_remote() {
+                            button {
+                                class: "px-3 py-1 bg-zinc-800 hover:bg-zinc-700 text-zinc-300 rounded text-xs font-bold mr-2 border border-zinc-700 transition-colors",
+                                title: "Open working directory",
+                                onclick: {
+                                    let server = props.server.clone();
+                                    move |_| match crate::platform::server_working_dir(&server) {
+                                        Ok(dir) => AppState::reveal_in_file_manager(&dir),
+                                        Err(e) => AppState::push_notification(
+                                            e.to_string(),
+                                            crate::models::NotificationLevel::Error,
+                                        ),
+                                    }
+                                },
+                                "📂 Folder"
+                            }
+                            if log_signal().is_some() {
+                                button {
+                                    class: if recording { "px-3 py-1 bg-red-900/40 hover:bg-red-800/60 text-red-200 rounded text-xs font-bold mr-2 border border-red-900/50 transition-colors" } else { "px-3 py-1 bg-zinc-800 hover:bg-zinc-700 text-zinc-300 rounded text-xs font-bold mr-2 border border-zinc-700 transition-colors" },
+                                    title: "Capture everything sent to and from the server, for replay without it",
+                                    onclick: {
+                                        let id = props.server.id.clone();
+                                        move |_| {
+                                            if recording {
+                                                AppState::stop_recording(&id);
+                                            } else if let Err(e) = AppState::start_recording(&id) {
+                                                error_msg.set(Some(e));
+                                            }
+                                        }
+                                    },
+                                    if recording { "⏹ Stop recording" } else { "⏺ Record" }
+                                }
+                            }
+                        }
+                        if bundleable {
+                            button {
+                                class: "px-3 py-1 bg-zinc-800 hover:bg-zinc-700 text-zinc-300 rounded text-xs font-bold mr-2 border border-zinc-700 transition-colors",
+                                title: "Package as a .mcpb bundle for Claude Desktop",
+                                onclick: {
+                                    let server = props.server.clone();
+                                    move |_| AppState::export_bundle(&server)
+                                },
+                                "📦 Export .mcpb"
+                            }
+                        }
                         button {
                             class: "px-3 py-1 bg-blue-900/40 hover:bg-blue-800/60 text-blue-200 rounded text-xs font-bold mr-2 border border-blue-900/50 transition-colors flex items-center gap-1",
                             onclick: update_package,
@@ -245,6 +577,18 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
                         },
                         "Prompts"
                     }
+                    if has_terminal {
+                        button {
+                            class: if current_tab == Tab::Terminal { active_class } else { inactive_class },
+                            onclick: move |_| active_tab.set(Tab::Terminal),
+                            "Terminal"
+                        }
+                    }
+                    button {
+                        class: if current_tab == Tab::Raw { active_class } else { inactive_class },
+                        onclick: move |_| active_tab.set(Tab::Raw),
+                        "Raw"
+                    }
                 }
 
                 // Error Banner
@@ -258,27 +602,104 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
                 // Content Area
                 div { class: "flex-1 overflow-auto bg-zinc-950",
                     if current_tab == Tab::Logs {
-                        div { class: "p-4 font-mono text-xs whitespace-pre-wrap text-zinc-400", "{log_text}" }
+                        div { class: "sticky top-0 flex justify-end gap-2 px-4 py-2 bg-zinc-950/90",
+                            if supports_logging {
+                                select {
+                                    class: "px-2 py-1 rounded text-[10px] font-bold text-zinc-400 bg-zinc-900 border border-zinc-800",
+                                    "aria-label": "Server log level",
+                                    title: "Ask the server for log messages of this level and above, and show only those",
+                                    value: log_level().map(|l| l.as_str()).unwrap_or(""),
+                                    onchange: {
+                                        let server_id = props.server.id.clone();
+                                        move |evt: FormEvent| {
+                                            let level = LoggingLevel::from_name(&evt.value());
+                                            log_level.set(level);
+                                            let server_id = server_id.clone();
+                                            spawn(async move {
+                                                // Back to everything the server is willing to send
+                                                let asked = level.unwrap_or(LoggingLevel::Debug);
+                                                match AppState::set_log_level(server_id, asked).await {
+                                                    Ok(()) => error_msg.set(None),
+                                                    Err(e) => error_msg.set(Some(e)),
+                                                }
+                                            });
+                                        }
+                                    },
+                                    option { value: "", "All output" }
+                                    for level in LoggingLevel::ALL {
+                                        option { value: "{level.as_str()}", "Server logs: {level.as_str()}+" }
+                                    }
+                                }
+                            }
+                            button {
+                                class: "px-2 py-1 rounded text-[10px] font-bold text-zinc-400 hover:text-white bg-zinc-900 border border-zinc-800",
+                                title: "Switch between clock times and time since each line",
+                                onclick: move |_| {
+                                    now.set(chrono::Utc::now());
+                                    time_display.set(time_display().toggled());
+                                },
+                                "🕒 {time_display().label()}"
+                            }
+                        }
+                        div { class: "px-4 pb-4 font-mono text-xs whitespace-pre-wrap text-zinc-400", "{log_text}" }
                     } else if current_tab == Tab::Tools {
                          div { class: "p-4 grid gap-4",
                             for tool in tools_list() {
                                 div { class: "p-4 border border-zinc-800 rounded-xl bg-zinc-900/50",
                                     div { class: "flex justify-between items-start mb-2",
                                         h3 { class: "font-bold text-white", "{tool.name}" }
-                                        button {
-                                            class: "px-3 py-1 bg-indigo-600 hover:bg-indigo-500 text-white rounded text-xs font-bold",
-                                            onclick: move |_| {
-                                                tool_error.set(false);
-                                                tool_output.set(None);
-                                                tool_args.set("{}".to_string());
-                                                active_tool.set(Some(tool.clone()));
-                                            },
-                                            "Call"
+                                        div { class: "flex gap-2",
+                                            button {
+                                                class: "px-3 py-1 bg-zinc-800 hover:bg-zinc-700 text-zinc-300 rounded text-xs font-bold",
+                                                onclick: {
+                                                    let name = tool.name.clone();
+                                                    move |_| {
+                                                        let open = docs_tool.read().as_ref() == Some(&name);
+                                                        docs_tool.set(if open { None } else { Some(name.clone()) });
+                                                    }
+                                                },
+                                                "📖 Docs"
+                                            }
+                                            button {
+                                                class: "px-3 py-1 bg-indigo-600 hover:bg-indigo-500 text-white rounded text-xs font-bold",
+                                                onclick: {
+                                                    let tool = tool.clone();
+                                                    move |_| {
+                                                        tool_error.set(false);
+                                                        tool_output.set(None);
+                                                        tool_media.set(Vec::new());
+                                                        tool_truncation.set(None);
+                                                        pinned.set(None);
+                                                        tool_args.set("{}".to_string());
+                                                        active_tool.set(Some(tool.clone()));
+                                                    }
+                                                },
+                                                "Call"
+                                            }
                                         }
                                     }
-                                    p { class: "text-sm text-zinc-400 mb-3", "{tool.description.clone().unwrap_or_default()}" }
-                                    div { class: "bg-black/50 p-2 rounded border border-zinc-800 font-mono text-xs text-zinc-500 overflow-x-auto",
-                                        "{serde_json::to_string_pretty(&tool.inputSchema).unwrap_or_default()}"
+                                    if docs_tool.read().as_ref() == Some(&tool.name) {
+                                        ToolDocs {
+                                            server_id: srv_id_read.clone(),
+                                            tool: tool.clone(),
+                                            on_try: {
+                                                let tool = tool.clone();
+                                                move |args: String| {
+                                                    tool_error.set(false);
+                                                    tool_output.set(None);
+                                                    tool_media.set(Vec::new());
+                                                    tool_truncation.set(None);
+                                                    pinned.set(None);
+                                                    tool_args.set(args);
+                                                    active_tool.set(Some(tool.clone()));
+                                                }
+                                            }
+                                        }
+                                    } else {
+                                        p { class: "text-sm text-zinc-400 mb-3", "{tool.description.clone().unwrap_or_default()}" }
+                                        div { class: "bg-black/50 p-2 rounded border border-zinc-800 overflow-x-auto",
+                                            SchemaView { schema: tool.inputSchema.clone() }
+                                        }
                                     }
                                 }
                             }
@@ -296,77 +717,52 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
                                         "{res.uri}"
                                     }
                                     p { class: "text-sm text-zinc-400", "{res.description.clone().unwrap_or_default()}" }
-                                    button {
-                                        class: "mt-3 px-3 py-1 bg-zinc-800 hover:bg-zinc-700 text-zinc-300 rounded text-xs font-bold",
-                                        onclick: {
-                                            let uri = res.uri.clone();
-                                            let id_val = srv_id_read.clone();
-                                            move |_| {
-                                                let uri_clone = uri.clone();
-                                                let id_val_clone = id_val.clone();
-                                                is_loading.set(true);
-                                                spawn(async move {
-                                                    match AppState::read_resource(id_val_clone, uri_clone.clone()).await {
-                                                        Ok(res) => {
-                                                            if let Some(content) = res.contents.first() {
-                                                                if let Some(text) = &content.text {
-                                                                    active_resource_content.set(Some((uri_clone, text.clone())));
-                                                                } else if let Some(blob) = &content.blob {
-                                                                    active_resource_content.set(Some((
-                                                                        uri_clone,
-                                                                        format!("[Base64 Blob: {}...]", blob.chars().take(50).collect::<String>()),
-                                                                    )));
-                                                                } else {
-                                                                    active_resource_content.set(Some((uri_clone, "Empty content".into())));
-                                                                }
-                                                            } else {
-                                                                active_resource_content.set(Some((uri_clone, "No content returned".into())));
-                                                            }
-                                                        }
-                                                        Err(e) => {
-                                                            error_msg.set(Some(format!("Failed to read resource: {}", e)));
-                                                        }
-                                                    }
-                                                    is_loading.set(false);
-                                                });
-                                            }
-                                        },
-                                        "Read Resource"
+                                    div { class: "mt-3 flex items-center gap-2",
+                                        button {
+                                            class: "px-3 py-1 bg-zinc-800 hover:bg-zinc-700 text-zinc-300 rounded text-xs font-bold",
+                                            onclick: {
+                                                let uri = res.uri.clone();
+                                                let load_resource = load_resource.clone();
+                                                move |_| load_resource(uri.clone())
+                                            },
+                                            "Read Resource"
+                                        }
+                                        if watched.contains_key(&res.uri) {
+                                            span { class: "text-xs text-emerald-400", "● Watching" }
+                                        }
                                     }
                                 }
                             }
-                            if resources_list().is_empty() {
+                            if !templates_list().is_empty() {
+                                span { class: "text-xs font-bold text-zinc-500 uppercase", "Templates" }
+                            }
+                            for template in templates_list() {
+                                ResourceTemplateCard {
+                                    key: "{template.uriTemplate}",
+                                    template,
+                                    on_read: {
+                                        let load_resource = load_resource.clone();
+                                        move |uri: String| load_resource(uri)
+                                    },
+                                }
+                            }
+                            if resources_list().is_empty() && templates_list().is_empty() {
                                 div { class: "text-center text-zinc-500 py-10", "No resources found or not fetched." }
                             }
                         }
                     } else if current_tab == Tab::Prompts {
                         div { class: "p-4 grid gap-4",
                              for prompt in prompts_list() {
-                                div { class: "p-4 border border-zinc-800 rounded-xl bg-zinc-900/50",
-                                    h3 { class: "font-bold text-white mb-1", "{prompt.name}" }
-                                    p { class: "text-sm text-zinc-400", "{prompt.description.clone().unwrap_or_default()}" }
-                                    if let Some(args) = &prompt.arguments {
-                                        div { class: "mt-2",
-                                            span { class: "text-xs font-bold text-zinc-500 uppercase", "Arguments" }
-                                            ul { class: "list-disc list-inside text-xs text-zinc-400 font-mono",
-                                                for arg in args {
-                                                    li {
-                                                        "{arg.name} "
-                                                        if arg.required.unwrap_or(false) {
-                                                            "(required)"
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                    button { class: "mt-3 px-3 py-1 bg-zinc-800 hover:bg-zinc-700 text-zinc-300 rounded text-xs font-bold", "Get Prompt" }
-                                }
+                                PromptCard { key: "{prompt.name}", server_id: srv_id_read.clone(), prompt }
                             }
                             if prompts_list().is_empty() {
                                 div { class: "text-center text-zinc-500 py-10", "No prompts found or not fetched." }
                             }
                         }
+                    } else if current_tab == Tab::Terminal {
+                        TerminalView { server_id: props.server.id.clone() }
+                    } else if current_tab == Tab::Raw {
+                        RawRequest { server_id: props.server.id.clone() }
                     }
                 }
 
@@ -381,27 +777,125 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
                 // Tool Execution Modal Overlay
                 if let Some(tool) = current_tool {
                     div { class: "absolute inset-0 z-50 bg-black/80 flex items-center justify-center p-8 backdrop-blur-sm",
-                        div { class: "w-full max-w-2xl bg-zinc-900 border border-zinc-700 rounded-xl shadow-2xl flex flex-col max-h-full animate-scale-in",
+                        div { class: "w-full {modal_width} bg-zinc-900 border border-zinc-700 rounded-xl shadow-2xl flex flex-col max-h-full animate-scale-in",
                             div { class: "p-4 border-b border-zinc-800 flex justify-between items-center",
                                 h3 { class: "font-bold text-white", "Execute: {tool.name}" }
                                 button { class: "text-zinc-500 hover:text-white", onclick: move |_| active_tool.set(None), "✕" }
                             }
                             div { class: "p-4 flex-1 overflow-auto",
                                 label { class: "block text-xs font-bold text-zinc-400 mb-2 uppercase", "Arguments (JSON)" }
-                                textarea {
-                                    class: "w-full h-40 bg-black/50 border border-zinc-700 rounded p-3 font-mono text-sm text-zinc-300 focus:border-indigo-500 focus:outline-none resize-none",
-                                    value: "{tool_args}",
-                                    oninput: move |evt| tool_args.set(evt.value())
+                                ToolPresets {
+                                    key: "{tool.name}",
+                                    server_id: props.server.id.clone(),
+                                    tool_name: tool.name.clone(),
+                                    args: tool_args(),
+                                    on_apply: move |args| tool_args.set(args)
+                                }
+                                JsonEditor {
+                                    value: tool_args(),
+                                    schema: Some(tool.inputSchema.clone()),
+                                    on_change: move |v| tool_args.set(v)
                                 }
+                                ToolSnippets { tool_name: tool.name.clone(), args: tool_args() }
 
-                                if let Some(res) = tool_output() {
+                                if let Some(pin) = pinned() {
+                                    div { class: "mt-4 grid grid-cols-2 gap-4",
+                                        div { class: "min-w-0",
+                                            div { class: "flex items-center justify-between mb-2",
+                                                label { class: "text-xs font-bold text-amber-400 uppercase", "📌 Pinned" }
+                                                button {
+                                                    class: "text-[11px] text-zinc-500 hover:text-white",
+                                                    onclick: move |_| pinned.set(None),
+                                                    "Unpin"
+                                                }
+                                            }
+                                            p { class: "mb-2 text-[11px] font-mono text-zinc-500 truncate", title: "{pin.args}", "{pin.args}" }
+                                            ToolOutput { text: pin.text.clone(), is_error: pin.is_error }
+                                        }
+                                        div { class: "min-w-0",
+                                            label { class: "block text-xs font-bold text-zinc-400 mb-2 uppercase", "Latest" }
+                                            if let Some(res) = tool_output() {
+                                                p { class: "mb-2 text-[11px] font-mono text-zinc-500 truncate", title: "{output_args}", "{output_args}" }
+                                                ToolOutput { text: res, is_error: tool_error() }
+                                            } else {
+                                                div { class: "p-3 rounded border border-dashed border-zinc-700 text-xs text-zinc-500",
+                                                    if is_loading() { "Running..." } else { "Run the tool again to compare." }
+                                                }
+                                            }
+                                        }
+                                    }
+                                    if let Some(res) = tool_output() {
+                                        div { class: "mt-4",
+                                            ToolOutputDiff { old: pin.text.clone(), new: res }
+                                        }
+                                    }
+                                } else if let Some(res) = tool_output() {
                                     div { class: "mt-4",
-                                        label { class: "block text-xs font-bold text-zinc-400 mb-2 uppercase",
-                                            if tool_error() { "Error" } else { "Result" }
+                                        div { class: "flex items-center justify-between mb-2",
+                                            label { class: "text-xs font-bold text-zinc-400 uppercase",
+                                                if tool_error() { "Error" } else { "Result" }
+                                            }
+                                            button {
+                                                class: "text-[11px] text-zinc-500 hover:text-white",
+                                                title: "Keep this result to compare with the next run",
+                                                onclick: {
+                                                    let text = res.clone();
+                                                    move |_| {
+                                                        pinned.set(Some(PinnedRun {
+                                                            text: text.clone(),
+                                                            is_error: tool_error(),
+                                                            args: output_args(),
+                                                        }));
+                                                        tool_output.set(None);
+                                                    }
+                                                },
+                                                "📌 Pin"
+                                            }
+                                        }
+                                        if let Some(cut) = tool_truncation() {
+                                            div { class: "mb-2 px-3 py-2 rounded border border-amber-900 bg-amber-950/30 text-xs text-amber-300 flex items-center justify-between gap-3",
+                                                "role": "status",
+                                                span {
+                                                    "Output cut off at the response size limit. The server sent {crate::storage::format_size(cut.original_bytes as u64)}."
+                                                }
+                                                if let Some(path) = cut.full_output {
+                                                    button {
+                                                        class: "shrink-0 px-2 py-1 rounded bg-zinc-800 hover:bg-zinc-700 text-zinc-200",
+                                                        title: "{path.display()}",
+                                                        onclick: {
+                                                            let path = path.clone();
+                                                            move |_| {
+                                                                if let Err(e) = open::that_detached(&path) {
+                                                                    error_msg.set(Some(format!("Couldn't open the full output: {}", e)));
+                                                                }
+                                                            }
+                                                        },
+                                                        "View full output in file"
+                                                    }
+                                                } else {
+                                                    span { class: "shrink-0 text-zinc-500", "The full output couldn't be saved." }
+                                                }
+                                            }
+                                        }
+                                        if !res.is_empty() || tool_media.read().is_empty() {
+                                            ToolOutput { text: res, is_error: tool_error() }
+                                        }
+                                        div { class: "mt-2 space-y-2",
+                                            for (i, content) in tool_media().into_iter().enumerate() {
+                                                if content.is_audio() {
+                                                    ToolAudio { key: "{i}", content, file_stem: format!("{}-{}", tool.name, i + 1) }
+                                                } else {
+                                                    ToolImage { key: "{i}", content, file_stem: format!("{}-{}", tool.name, i + 1) }
+                                                }
+                                            }
                                         }
-                                        div { class: "p-3 rounded border font-mono text-sm whitespace-pre-wrap overflow-x-auto",
-                                            class: if tool_error() { "bg-red-950/30 border-red-900 text-red-300" } else { "bg-green-950/30 border-green-900 text-green-300" },
-                                            "{res}"
+                                        if call_log_lines > 0 {
+                                            details { class: "mt-3 rounded border border-zinc-800 bg-zinc-950",
+                                                summary { class: "px-3 py-2 text-xs font-bold text-zinc-400 cursor-pointer",
+                                                    "Server logs during this call ({call_log_lines})"
+                                                }
+                                                div { class: "px-3 pb-3 font-mono text-xs whitespace-pre-wrap text-zinc-400", "{call_logs}" }
+                                            }
                                         }
                                     }
                                 }
@@ -412,6 +906,24 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
                                     onclick: move |_| active_tool.set(None),
                                     "Close"
                                 }
+                                if is_loading() {
+                                    button {
+                                        class: "px-4 py-2 bg-red-900/60 hover:bg-red-800 text-red-100 rounded text-sm font-bold",
+                                        title: "Stop waiting for this call and tell the server to abandon it",
+                                        onclick: {
+                                            let server_id = props.server.id.clone();
+                                            move |_| {
+                                                let server_id = server_id.clone();
+                                                spawn(async move {
+                                                    if let Err(e) = AppState::cancel_request(server_id).await {
+                                                        error_msg.set(Some(e));
+                                                    }
+                                                });
+                                            }
+                                        },
+                                        "Cancel"
+                                    }
+                                }
                                 button {
                                     class: "px-4 py-2 bg-indigo-600 hover:bg-indigo-500 text-white rounded text-sm font-bold disabled:opacity-50 disabled:cursor-not-allowed",
                                     disabled: is_loading(),
@@ -431,8 +943,38 @@ pub fn ServerConsole(props: ServerConsoleProps) -> Element {
                                 div {
                                     h3 { class: "font-bold text-white", "Resource Content" }
                                     span { class: "text-xs font-mono text-zinc-500", "{uri}" }
+                                    if let Some(e) = watch_error() {
+                                        p { class: "text-xs text-red-400", "role": "alert", "{e}" }
+                                    }
+                                }
+                                div { class: "flex items-center gap-3",
+                                    if let Some(updates) = watched.get(&uri).copied() {
+                                        span { class: "text-xs text-emerald-400", "● Live · {updates} updates" }
+                                    }
+                                    button {
+                                        class: "px-3 py-1 bg-zinc-800 hover:bg-zinc-700 text-zinc-300 rounded text-xs font-bold",
+                                        title: "Refresh when the server reports this resource changed",
+                                        onclick: {
+                                            let uri = uri.clone();
+                                            let id_val = props.server.id.clone();
+                                            let watching = watched.contains_key(&uri);
+                                            move |_| {
+                                                let uri = uri.clone();
+                                                let id_val = id_val.clone();
+                                                watch_error.set(None);
+                                                spawn(async move {
+                                                    if watching {
+                                                        AppState::unwatch_resource(id_val, uri).await;
+                                                    } else if let Err(e) = AppState::watch_resource(id_val, uri).await {
+                                                        watch_error.set(Some(e));
+                                                    }
+                                                });
+                                            }
+                                        },
+                                        if watched.contains_key(&uri) { "Stop watching" } else { "Watch" }
+                                    }
+                                    button { class: "text-zinc-500 hover:text-white", onclick: move |_| active_resource_content.set(None), "✕" }
                                 }
-                                button { class: "text-zinc-500 hover:text-white", onclick: move |_| active_resource_content.set(None), "✕" }
                             }
                             div { class: "p-0 flex-1 overflow-auto bg-black/30",
                                 pre { class: "p-4 font-mono text-sm text-zinc-300 whitespace-pre-wrap", "{content}" }
@@ -0,0 +1,101 @@
+use crate::models::McpServer;
+use crate::server_diff::diff_servers;
+use crate::state::APP_STATE;
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct ServerDiffProps {
+    pub on_close: EventHandler<()>,
+}
+
+#[component]
+fn ServerPicker(
+    servers: Vec<McpServer>,
+    selected: Option<String>,
+    on_pick: EventHandler<String>,
+) -> Element {
+    rsx! {
+        select {
+            class: "w-full bg-zinc-900 border border-zinc-800 rounded-lg px-3 py-2 text-sm text-white focus:outline-none focus:border-indigo-500",
+            onchange: move |evt| on_pick.call(evt.value()),
+            for s in servers {
+                option { key: "{s.id}", value: "{s.id}", selected: selected.as_deref() == Some(s.id.as_str()), "{s.name}" }
+            }
+        }
+    }
+}
+
+/// Compare two servers' configs side by side, e.g. "staging" vs "prod"
+/// variants of the same server.
+pub fn ServerDiff(props: ServerDiffProps) -> Element {
+    let servers = APP_STATE.read().servers.cloned();
+    let mut left = use_signal(|| servers.first().map(|s| s.id.clone()));
+    let mut right = use_signal(|| servers.get(1).map(|s| s.id.clone()));
+    let mut only_changes = use_signal(|| false);
+    let mut reveal = use_signal(|| false);
+
+    let find = |id: Option<String>| id.and_then(|id| servers.iter().find(|s| s.id == id).cloned());
+    let pair = find(left()).zip(find(right()));
+    let rows = pair
+        .as_ref()
+        .map(|(a, b)| diff_servers(a, b, reveal()))
+        .unwrap_or_default();
+    let changed = rows.iter().filter(|r| r.changed).count();
+
+    rsx! {
+        div { class: "fixed inset-0 z-50 flex items-center justify-center bg-black/60 p-4 backdrop-blur-md",
+            div { class: "w-full max-w-4xl max-h-[85vh] bg-zinc-950 text-zinc-300 rounded-2xl flex flex-col overflow-hidden border border-zinc-800 shadow-2xl animate-scale-in",
+                div { class: "flex justify-between items-center p-5 bg-zinc-900 border-b border-zinc-800",
+                    div {
+                        h2 { class: "font-bold text-xl text-white", "⇄ Compare servers" }
+                        if pair.is_some() {
+                            p { class: "text-xs text-zinc-500 mt-1", "{changed} of {rows.len()} fields differ" }
+                        }
+                    }
+                    button {
+                        class: "p-2 hover:bg-zinc-800 rounded-full transition-colors",
+                        onclick: move |_| props.on_close.call(()),
+                        "✕"
+                    }
+                }
+
+                if servers.len() < 2 {
+                    div { class: "p-8 text-center text-sm text-zinc-500", "Add at least two servers to compare them." }
+                } else {
+                    div { class: "p-5 space-y-4 overflow-y-auto custom-scrollbar",
+                        div { class: "grid grid-cols-[8rem_1fr_1fr] gap-3 items-center",
+                            div {}
+                            ServerPicker { servers: servers.clone(), selected: left(), on_pick: move |id| left.set(Some(id)) }
+                            ServerPicker { servers: servers.clone(), selected: right(), on_pick: move |id| right.set(Some(id)) }
+                        }
+                        div { class: "flex gap-4 text-xs text-zinc-400",
+                            label { class: "flex items-center gap-2",
+                                input { r#type: "checkbox", checked: only_changes(), onchange: move |evt| only_changes.set(evt.checked()) }
+                                "Only differences"
+                            }
+                            label { class: "flex items-center gap-2",
+                                input { r#type: "checkbox", checked: reveal(), onchange: move |evt| reveal.set(evt.checked()) }
+                                "Reveal env values"
+                            }
+                        }
+                        div { class: "rounded-xl border border-zinc-800 overflow-hidden",
+                            for row in rows.into_iter().filter(|r| r.changed || !only_changes()) {
+                                div {
+                                    key: "{row.field}",
+                                    class: if row.changed { "grid grid-cols-[8rem_1fr_1fr] gap-3 px-3 py-2 border-b border-zinc-800 bg-amber-500/5 text-sm" } else { "grid grid-cols-[8rem_1fr_1fr] gap-3 px-3 py-2 border-b border-zinc-800 text-sm" },
+                                    span { class: if row.changed { "font-mono text-xs text-amber-400" } else { "font-mono text-xs text-zinc-500" }, "{row.field}" }
+                                    for value in [row.left, row.right] {
+                                        match value {
+                                            Some(v) => rsx! { span { class: "font-mono text-xs text-zinc-200 break-all", "{v}" } },
+                                            None => rsx! { span { class: "text-xs italic text-zinc-600", "not set" } },
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
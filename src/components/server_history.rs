@@ -0,0 +1,101 @@
+use crate::models::{McpServer, ServerRevision};
+use crate::server_diff::diff_servers;
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct ServerHistoryProps {
+    pub server_id: String,
+    /// Called with the server after a revision was restored.
+    pub on_restore: EventHandler<McpServer>,
+}
+
+/// Earlier configs of a server, each shown as a diff against the current
+/// one, with one-click restore.
+pub fn ServerHistory(props: ServerHistoryProps) -> Element {
+    let id = props.server_id.clone();
+    let mut revisions = use_signal(|| AppState::get_server_revisions(&id));
+    let mut restoring = use_signal(|| None::<i64>);
+
+    let current = APP_STATE
+        .read()
+        .servers
+        .read()
+        .iter()
+        .find(|s| s.id == props.server_id)
+        .cloned();
+
+    let restore = move |revision: ServerRevision| {
+        let server_id = revision.server_id.clone();
+        restoring.set(Some(revision.id));
+        spawn(async move {
+            match AppState::restore_server_revision(revision.id).await {
+                Ok(server) => {
+                    revisions.set(AppState::get_server_revisions(&server_id));
+                    props.on_restore.call(server);
+                }
+                Err(e) => AppState::push_notification(
+                    format!("Could not restore revision: {}", e),
+                    crate::models::NotificationLevel::Error,
+                ),
+            }
+            restoring.set(None);
+        });
+    };
+
+    let (Some(current), Ok(list)) = (current, revisions()) else {
+        let message = match revisions() {
+            Err(e) => e,
+            Ok(_) => "Server not found.".to_string(),
+        };
+        return rsx! { p { class: "text-sm text-red-400", "{message}" } };
+    };
+
+    rsx! {
+        if list.is_empty() {
+            div { class: "py-10 text-center text-sm text-zinc-500",
+                "No earlier versions yet. Each saved change to this server is kept here."
+            }
+        } else {
+            div { class: "space-y-3",
+                for revision in list {
+                    {
+                        let changes: Vec<_> = diff_servers(&revision.config, &current, false)
+                            .into_iter()
+                            .filter(|d| d.changed)
+                            .collect();
+                        let busy = restoring() == Some(revision.id);
+                        let unchanged = changes.is_empty();
+                        rsx! {
+                            div { key: "{revision.id}", class: "p-4 bg-zinc-900 rounded-xl border border-zinc-800 space-y-2",
+                                div { class: "flex items-center justify-between",
+                                    div {
+                                        span { class: "text-sm font-bold text-white", "{revision.created_at}" }
+                                        span { class: "ml-2 text-xs text-zinc-500", "{changes.len()} field(s) differ from now" }
+                                    }
+                                    button {
+                                        class: "px-3 py-1.5 text-xs font-bold rounded-lg bg-indigo-500/10 text-indigo-300 hover:bg-indigo-500/20 disabled:opacity-50 transition-colors",
+                                        disabled: restoring().is_some() || unchanged,
+                                        onclick: {
+                                            let revision = revision.clone();
+                                            let mut restore = restore;
+                                            move |_| restore(revision.clone())
+                                        },
+                                        if busy { "Restoring..." } else { "↺ Restore" }
+                                    }
+                                }
+                                for change in changes {
+                                    div { key: "{change.field}", class: "grid grid-cols-[7rem_1fr_1fr] gap-2 text-xs font-mono",
+                                        span { class: "text-zinc-500", "{change.field}" }
+                                        span { class: "text-red-300 line-through break-all", "{change.right.clone().unwrap_or_default()}" }
+                                        span { class: "text-green-300 break-all", "{change.left.clone().unwrap_or_default()}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
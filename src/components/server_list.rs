@@ -1,21 +1,27 @@
 use crate::components::ServerCard;
-use crate::models::McpServer;
-use crate::state::APP_STATE;
+use crate::models::{McpServer, NotificationLevel};
+use crate::state::{AppState, APP_STATE};
 use dioxus::prelude::*;
 
 #[derive(PartialEq, Clone, Props)]
 pub struct ServerListProps {
     on_open_console: EventHandler<McpServer>,
     on_edit_server: EventHandler<McpServer>,
+    /// Show only servers that are running.
+    #[props(default)]
+    running_only: bool,
 }
 
 pub fn ServerList(props: ServerListProps) -> Element {
     let servers = APP_STATE.read().servers;
+    let processes = APP_STATE.read().processes;
+    // Card being dragged to a new place
+    let mut dragging = use_signal(|| None::<String>);
 
     rsx! {
         div {
             class: "grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 gap-6",
-            if servers.read().is_empty() {
+            if servers.read().iter().all(|s| s.is_archived) {
                  div {
                      class: "col-span-full flex flex-col items-center justify-center py-20 text-center text-zinc-500",
                      div { class: "text-4xl mb-4 opacity-20", "📭" }
@@ -24,14 +30,43 @@ pub fn ServerList(props: ServerListProps) -> Element {
                  }
             } else {
                 {
-                    let servers_vec = servers.read().clone();
+                    let servers_vec: Vec<McpServer> = servers
+                        .read()
+                        .iter()
+                        .filter(|s| !s.is_archived)
+                        .filter(|s| !props.running_only || processes.read().contains_key(&s.id))
+                        .cloned()
+                        .collect();
                     rsx! {
                         for (i, server) in servers_vec.iter().enumerate() {
                             div {
-                                class: "animate-fade-in-up",
+                                key: "{server.id}",
+                                class: if dragging().as_ref() == Some(&server.id) { "animate-fade-in-up opacity-40" } else { "animate-fade-in-up" },
                                 style: format!("animation-delay: {}ms", i * 50),
+                                draggable: "true",
+                                ondragstart: {
+                                    let id = server.id.clone();
+                                    move |_| dragging.set(Some(id.clone()))
+                                },
+                                ondragend: move |_| dragging.set(None),
+                                // Lets the card be dropped on
+                                ondragover: move |evt| evt.prevent_default(),
+                                ondrop: {
+                                    let target = server.id.clone();
+                                    move |evt: DragEvent| {
+                                        evt.prevent_default();
+                                        let Some(moved) = dragging.take() else {
+                                            return;
+                                        };
+                                        if let Err(e) = AppState::move_server(&moved, &target) {
+                                            AppState::push_notification(
+                                                format!("Could not save the server order: {}", e),
+                                                NotificationLevel::Error,
+                                            );
+                                        }
+                                    }
+                                },
                                 ServerCard {
-                                    key: "{server.id}",
                                     server: server.clone(),
                                     on_console_click: {
                                         let s = server.clone();
@@ -1,18 +1,74 @@
-use crate::models::{CreateServerArgs, McpServer};
+use crate::components::json_editor::{validate_against_schema, JsonEditor};
+use crate::components::{LaunchPreview, RegistryEntry, ServerHistory};
+use crate::models::{CreateServerArgs, McpServer, UpdateServerArgs};
+use crate::state::AppState;
 use dioxus::prelude::*;
+use serde_json::{json, Value};
+
+/// JSON Schema for [`CreateServerArgs`], used by the "Edit as JSON" mode.
+fn server_args_schema() -> Value {
+    let optional_string = json!({"type": ["string", "null"]});
+    json!({
+        "type": "object",
+        "additionalProperties": false,
+        "required": ["name", "type"],
+        "properties": {
+            "name": {"type": "string"},
+            "type": {"enum": ["stdio", "sse", "ws"]},
+            "command": optional_string,
+            "args": {"type": ["array", "null"], "items": {"type": "string"}},
+            "url": optional_string,
+            "env": {"type": ["object", "null"]},
+            "description": optional_string
+        }
+    })
+}
+
+/// Parse and validate a raw JSON server definition.
+fn parse_server_json(src: &str) -> Result<CreateServerArgs, String> {
+    let value: Value = serde_json::from_str(src).map_err(|e| format!("Invalid JSON: {}", e))?;
+    if let Some(first) = validate_against_schema(&value, &server_args_schema())
+        .into_iter()
+        .next()
+    {
+        return Err(first);
+    }
+    let args: CreateServerArgs = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    if args.name.trim().is_empty() {
+        return Err("$.name: must not be empty".to_string());
+    }
+    Ok(args)
+}
 
 #[derive(Props, Clone, PartialEq)]
 pub struct SettingsProps {
     pub server: Option<McpServer>,
     pub on_close: EventHandler<()>,
+    /// A new server to create.
     pub on_save: EventHandler<CreateServerArgs>,
+    /// Changes to the server being edited.
+    pub on_update: EventHandler<UpdateServerArgs>,
     pub on_delete: EventHandler<String>,
+    /// Field to jump to, e.g. from a guided fix.
+    #[props(default)]
+    pub focus: Option<crate::fixes::SettingsField>,
 }
 
 #[derive(Clone, Copy, PartialEq)]
 enum ServerType {
     Stdio,
     Sse,
+    Ws,
+}
+
+impl ServerType {
+    fn from_str(server_type: &str) -> Self {
+        match server_type {
+            "sse" => ServerType::Sse,
+            "ws" => ServerType::Ws,
+            _ => ServerType::Stdio,
+        }
+    }
 }
 
 pub fn Settings(props: SettingsProps) -> Element {
@@ -23,13 +79,7 @@ pub fn Settings(props: SettingsProps) -> Element {
         props
             .server
             .as_ref()
-            .map(|s| {
-                if s.server_type == "sse" {
-                    ServerType::Sse
-                } else {
-                    ServerType::Stdio
-                }
-            })
+            .map(|s| ServerType::from_str(&s.server_type))
             .unwrap_or(ServerType::Stdio)
     });
 
@@ -83,8 +133,91 @@ pub fn Settings(props: SettingsProps) -> Element {
             .and_then(|s| s.env.clone())
             .unwrap_or_default()
     });
-    let mut env_key_input = use_signal(String::new);
-    let mut env_value_input = use_signal(String::new);
+    // A guided fix for a missing variable starts with it in the inputs
+    let focus_env = match &props.focus {
+        Some(crate::fixes::SettingsField::Env(key)) => Some(key.clone()),
+        _ => None,
+    };
+    let mut env_key_input = use_signal({
+        let key = focus_env.clone();
+        move || key.unwrap_or_default()
+    });
+    let mut env_value_input = use_signal({
+        let value = focus_env
+            .as_ref()
+            .and_then(|key| env_map.peek().get(key).cloned());
+        move || value.unwrap_or_default()
+    });
+    let focus_command = props.focus == Some(crate::fixes::SettingsField::Command);
+    let focus_env = focus_env.is_some();
+
+    // Raw JSON ("advanced") mode
+    let mut json_mode = use_signal(|| false);
+    let mut json_text = use_signal(String::new);
+
+    // Revision list instead of the form
+    let mut history_mode = use_signal(|| false);
+
+    // Launch options live outside the server record, so only existing
+    // servers have them
+    let mut launch_options = use_signal(|| {
+        props
+            .server
+            .as_ref()
+            .map(|s| AppState::launch_options(&s.id))
+            .unwrap_or_default()
+    });
+
+    // Directory a stdio server starts in; blank for the manager's own
+    let current_cwd = props.server.as_ref().and_then(|s| s.cwd.clone());
+    let mut cwd = use_signal({
+        let current_cwd = current_cwd.clone();
+        move || current_cwd.unwrap_or_default()
+    });
+
+    // Image a stdio server runs in, blank to run on the host, and its
+    // mounts one per line
+    let current_docker_image = props.server.as_ref().and_then(|s| s.docker_image.clone());
+    let current_docker_mounts = props
+        .server
+        .as_ref()
+        .and_then(|s| s.docker_mounts.clone())
+        .unwrap_or_default();
+    let mut docker_image = use_signal({
+        let current_docker_image = current_docker_image.clone();
+        move || current_docker_image.unwrap_or_default()
+    });
+    let mut docker_mounts = use_signal({
+        let current_docker_mounts = current_docker_mounts.clone();
+        move || current_docker_mounts.join("\n")
+    });
+
+    // Short name for the CLI and command palette; blank for none
+    let current_alias = props.server.as_ref().and_then(|s| s.alias.clone());
+    let mut alias = use_signal({
+        let current_alias = current_alias.clone();
+        move || current_alias.unwrap_or_default()
+    });
+    let max_alias_len = crate::models::MAX_ALIAS_LEN;
+
+    // Seconds a request waits for its reply; blank for the default
+    let current_timeout = props.server.as_ref().and_then(|s| s.request_timeout_secs);
+    let mut request_timeout =
+        use_signal(move || current_timeout.map(|s| s.to_string()).unwrap_or_default());
+    let default_timeout = crate::process::DEFAULT_REQUEST_TIMEOUT.as_secs();
+
+    // Lifetime picked in the form: None leaves it as it is, Some(None)
+    // makes the server permanent
+    let mut lifetime = use_signal(|| None::<Option<i64>>);
+    let current_expiry = props
+        .server
+        .as_ref()
+        .and_then(|s| crate::expiry::remaining(s, chrono::Utc::now()));
+
+    // Dry-run launch preview instead of the form
+    let mut preview_mode = use_signal(|| false);
+    // Shown as an entry for the project's registry.json
+    let mut registry_mode = use_signal(|| false);
 
     // Add argument
     let add_arg = move |_| {
@@ -106,11 +239,13 @@ pub fn Settings(props: SettingsProps) -> Element {
         }
     };
 
-    let onsubmit = move |_| {
+    // Snapshot of the form as CreateServerArgs
+    let form_args = move || {
         let st = server_type();
         let type_str = match st {
             ServerType::Stdio => "stdio".to_string(),
             ServerType::Sse => "sse".to_string(),
+            ServerType::Ws => "ws".to_string(),
         };
 
         let final_args = {
@@ -152,7 +287,7 @@ pub fn Settings(props: SettingsProps) -> Element {
             Some(desc_val)
         };
 
-        (props.on_save)(CreateServerArgs {
+        CreateServerArgs {
             name: name(),
             server_type: type_str,
             command: final_command,
@@ -160,6 +295,114 @@ pub fn Settings(props: SettingsProps) -> Element {
             env: final_env,
             url: final_url,
             description: final_desc,
+        }
+    };
+
+    // Load a parsed definition back into the form fields
+    let mut apply_args = move |args: CreateServerArgs| {
+        server_type.set(ServerType::from_str(&args.server_type));
+        name.set(args.name);
+        description.set(args.description.unwrap_or_default());
+        command.set(args.command.unwrap_or_default());
+        url.set(args.url.unwrap_or_default());
+        args_list.set(args.args.unwrap_or_default());
+        env_map.set(args.env.unwrap_or_default());
+    };
+
+    let json_result = if json_mode() {
+        Some(parse_server_json(&json_text()))
+    } else {
+        None
+    };
+
+    let toggle_json = move |_| {
+        if json_mode() {
+            // Only leave JSON mode with a valid definition
+            if let Ok(args) = parse_server_json(&json_text()) {
+                apply_args(args);
+                json_mode.set(false);
+            }
+        } else {
+            json_text.set(serde_json::to_string_pretty(&form_args()).unwrap_or_default());
+            json_mode.set(true);
+        }
+    };
+
+    let preview_id = props.server.as_ref().map(|s| s.id.clone()).unwrap_or_default();
+    let server_id = props.server.as_ref().map(|s| s.id.clone());
+    let onsubmit = move |_| {
+        let args = if json_mode() {
+            match parse_server_json(&json_text()) {
+                Ok(args) => args,
+                Err(_) => return,
+            }
+        } else {
+            form_args()
+        };
+        let Some(id) = &server_id else {
+            match lifetime() {
+                // New temporary servers get their expiry as they are created
+                Some(Some(hours)) => {
+                    spawn(async move {
+                        if let Err(e) = AppState::add_temporary_server(args, hours).await {
+                            AppState::push_notification(e, crate::models::NotificationLevel::Error);
+                        }
+                    });
+                    (props.on_close)(());
+                }
+                _ => (props.on_save)(args),
+            }
+            return;
+        };
+
+        // Checked before anything is saved, so nothing is half-applied
+        let secs = match request_timeout().trim() {
+            "" => None,
+            secs => match secs.parse::<u32>().ok().filter(|s| *s > 0) {
+                Some(secs) => Some(secs),
+                None => {
+                    AppState::push_notification(
+                        "Could not save: enter the request timeout as a whole number of seconds".to_string(),
+                        crate::models::NotificationLevel::Error,
+                    );
+                    return;
+                }
+            },
+        };
+        let new_cwd = Some(cwd().trim().to_string()).filter(|c| !c.is_empty());
+        let new_alias = Some(alias().trim().to_string()).filter(|a| !a.is_empty());
+        let new_image = Some(docker_image().trim().to_string()).filter(|i| !i.is_empty());
+        let new_mounts: Vec<String> = docker_mounts()
+            .lines()
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .map(String::from)
+            .collect();
+        let docker_changed = new_image != current_docker_image || new_mounts != current_docker_mounts;
+
+        AppState::set_launch_options(id, launch_options());
+        if let Some(hours) = lifetime() {
+            if let Err(e) = AppState::set_server_expiry(id, hours) {
+                AppState::push_notification(
+                    format!("Could not change the server's lifetime: {}", e),
+                    crate::models::NotificationLevel::Error,
+                );
+            }
+        }
+        (props.on_update)(UpdateServerArgs {
+            name: Some(args.name),
+            server_type: Some(args.server_type),
+            command: args.command,
+            args: args.args,
+            url: args.url,
+            env: args.env,
+            description: args.description,
+            is_active: None,
+            request_timeout_secs: (secs != current_timeout).then_some(secs),
+            alias: (new_alias != current_alias).then_some(new_alias),
+            cwd: (new_cwd != current_cwd).then_some(new_cwd),
+            docker_image: docker_changed.then_some(new_image),
+            docker_mounts: docker_changed.then_some(new_mounts),
         });
     };
 
@@ -172,6 +415,12 @@ pub fn Settings(props: SettingsProps) -> Element {
     let current_type = server_type();
     let current_args = args_list();
     let current_env: Vec<(String, String)> = env_map().into_iter().collect();
+    let json_invalid = matches!(json_result, Some(Err(_)));
+    let lint_warnings = match &json_result {
+        Some(Ok(args)) => crate::lint::lint_args(args),
+        Some(Err(_)) => Vec::new(),
+        None => crate::lint::lint_args(&form_args()),
+    };
 
     rsx! {
         div {
@@ -186,10 +435,42 @@ pub fn Settings(props: SettingsProps) -> Element {
                         h2 { class: "font-bold text-xl text-white", "{title}" }
                         p { class: "text-xs text-zinc-500 mt-1", "Configure your MCP server instance" }
                     }
-                    button {
-                        class: "p-2 hover:bg-zinc-800 rounded-full transition-colors",
-                        onclick: move |_| (props.on_close)(()),
-                        "✕"
+                    div { class: "flex items-center gap-2",
+                        if is_edit {
+                            button {
+                                class: if history_mode() { "px-3 py-1.5 text-xs font-bold rounded-lg bg-indigo-500/20 text-indigo-300 transition-colors" } else { "px-3 py-1.5 text-xs font-bold rounded-lg text-zinc-500 hover:text-zinc-300 hover:bg-zinc-800 transition-colors" },
+                                onclick: move |_| history_mode.set(!history_mode()),
+                                "🕘 History"
+                            }
+                        }
+                        if current_type == ServerType::Stdio {
+                            button {
+                                class: if preview_mode() { "px-3 py-1.5 text-xs font-bold rounded-lg bg-indigo-500/20 text-indigo-300 transition-colors" } else { "px-3 py-1.5 text-xs font-bold rounded-lg text-zinc-500 hover:text-zinc-300 hover:bg-zinc-800 transition-colors" },
+                                disabled: json_mode() || history_mode() || registry_mode(),
+                                title: "Show what would be executed without starting the server",
+                                onclick: move |_| preview_mode.set(!preview_mode()),
+                                "▶ Preview launch"
+                            }
+                            button {
+                                class: if registry_mode() { "px-3 py-1.5 text-xs font-bold rounded-lg bg-indigo-500/20 text-indigo-300 transition-colors" } else { "px-3 py-1.5 text-xs font-bold rounded-lg text-zinc-500 hover:text-zinc-300 hover:bg-zinc-800 transition-colors" },
+                                disabled: json_mode() || history_mode() || preview_mode(),
+                                title: "Turn this server into an entry for the app's registry",
+                                onclick: move |_| registry_mode.set(!registry_mode()),
+                                "📤 Registry entry"
+                            }
+                        }
+                        button {
+                            class: if json_mode() { "px-3 py-1.5 text-xs font-bold rounded-lg bg-indigo-500/20 text-indigo-300 transition-colors" } else { "px-3 py-1.5 text-xs font-bold rounded-lg text-zinc-500 hover:text-zinc-300 hover:bg-zinc-800 transition-colors" },
+                            disabled: json_invalid || history_mode() || preview_mode() || registry_mode(),
+                            title: if json_invalid { "Fix the JSON errors to return to the form" } else { "" },
+                            onclick: toggle_json,
+                            "{{ }} Edit as JSON"
+                        }
+                        button {
+                            class: "p-2 hover:bg-zinc-800 rounded-full transition-colors",
+                            onclick: move |_| (props.on_close)(()),
+                            "✕"
+                        }
                     }
                 }
 
@@ -197,163 +478,459 @@ pub fn Settings(props: SettingsProps) -> Element {
                 div {
                     class: "p-6 space-y-5 overflow-y-auto max-h-[65vh]",
 
-                    // Server Type Toggle
-                    div {
-                        class: "flex gap-2 p-1 bg-zinc-900 rounded-xl",
-                        button {
-                            class: if current_type == ServerType::Stdio { "flex-1 flex items-center justify-center gap-2 py-2.5 text-sm font-bold rounded-lg bg-zinc-800 text-indigo-400 shadow-lg transition-all" } else { "flex-1 flex items-center justify-center gap-2 py-2.5 text-sm font-bold rounded-lg text-zinc-500 hover:text-zinc-300 transition-all" },
-                            onclick: move |_| server_type.set(ServerType::Stdio),
-                            "⌨ stdio (Local)"
+                    if history_mode() {
+                        if let Some(server) = props.server.as_ref() {
+                            ServerHistory {
+                                server_id: server.id.clone(),
+                                on_restore: move |restored: McpServer| {
+                                    apply_args(CreateServerArgs {
+                                        name: restored.name,
+                                        server_type: restored.server_type,
+                                        command: restored.command,
+                                        args: restored.args,
+                                        url: restored.url,
+                                        env: restored.env,
+                                        description: restored.description,
+                                    });
+                                    json_mode.set(false);
+                                    history_mode.set(false);
+                                }
+                            }
                         }
-                        button {
-                            class: if current_type == ServerType::Sse { "flex-1 flex items-center justify-center gap-2 py-2.5 text-sm font-bold rounded-lg bg-zinc-800 text-indigo-400 shadow-lg transition-all" } else { "flex-1 flex items-center justify-center gap-2 py-2.5 text-sm font-bold rounded-lg text-zinc-500 hover:text-zinc-300 transition-all" },
-                            onclick: move |_| server_type.set(ServerType::Sse),
-                            "🌐 sse (Remote)"
+                    } else if preview_mode() {
+                        LaunchPreview {
+                            command: command(),
+                            args: args_list(),
+                            env: env_map(),
+                            cwd: Some(cwd()).filter(|c| !c.trim().is_empty()),
+                            clean_env: launch_options().clean_env,
+                            server_id: preview_id.clone(),
+                            docker_image: Some(docker_image()).filter(|i| !i.trim().is_empty()),
+                            docker_mounts: docker_mounts().lines().map(str::trim).filter(|m| !m.is_empty()).map(String::from).collect::<Vec<_>>(),
                         }
-                    }
-
-                    // Name
-                    div {
-                        label { class: "block text-sm font-bold mb-2 text-zinc-400", "Name" }
-                        input {
-                            class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors",
-                            placeholder: "e.g. github-mcp",
-                            value: "{name}",
-                            oninput: move |evt| name.set(evt.value())
+                    } else if registry_mode() {
+                        RegistryEntry { args: form_args() }
+                    } else if json_mode() {
+                        p { class: "text-xs text-zinc-500",
+                            "Paste or edit the full server definition. Fields: name, type, command, args, url, env, description."
                         }
-                    }
-
-                    // Description
-                    div {
-                        label { class: "block text-sm font-bold mb-2 text-zinc-400", "Description" }
-                        textarea {
-                            class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors resize-none h-20",
-                            placeholder: "What does this server do?",
-                            value: "{description}",
-                            oninput: move |evt| description.set(evt.value())
+                        JsonEditor {
+                            value: json_text(),
+                            schema: Some(server_args_schema()),
+                            height: "h-80".to_string(),
+                            on_change: move |v| json_text.set(v)
+                        }
+                    } else {
+                        // Server Type Toggle
+                        div {
+                            class: "flex gap-2 p-1 bg-zinc-900 rounded-xl",
+                            button {
+                                class: if current_type == ServerType::Stdio { "flex-1 flex items-center justify-center gap-2 py-2.5 text-sm font-bold rounded-lg bg-zinc-800 text-indigo-400 shadow-lg transition-all" } else { "flex-1 flex items-center justify-center gap-2 py-2.5 text-sm font-bold rounded-lg text-zinc-500 hover:text-zinc-300 transition-all" },
+                                onclick: move |_| server_type.set(ServerType::Stdio),
+                                "⌨ stdio (Local)"
+                            }
+                            button {
+                                class: if current_type == ServerType::Sse { "flex-1 flex items-center justify-center gap-2 py-2.5 text-sm font-bold rounded-lg bg-zinc-800 text-indigo-400 shadow-lg transition-all" } else { "flex-1 flex items-center justify-center gap-2 py-2.5 text-sm font-bold rounded-lg text-zinc-500 hover:text-zinc-300 transition-all" },
+                                onclick: move |_| server_type.set(ServerType::Sse),
+                                "🌐 sse (Remote)"
+                            }
+                            button {
+                                class: if current_type == ServerType::Ws { "flex-1 flex items-center justify-center gap-2 py-2.5 text-sm font-bold rounded-lg bg-zinc-800 text-indigo-400 shadow-lg transition-all" } else { "flex-1 flex items-center justify-center gap-2 py-2.5 text-sm font-bold rounded-lg text-zinc-500 hover:text-zinc-300 transition-all" },
+                                onclick: move |_| server_type.set(ServerType::Ws),
+                                "🔌 ws (Remote)"
+                            }
                         }
-                    }
 
-                    // Conditional: Stdio or SSE fields
-                    if current_type == ServerType::Stdio {
-                        // Command
+                        // Name
                         div {
-                            label { class: "block text-sm font-bold mb-2 text-zinc-400", "Command" }
+                            label { class: "block text-sm font-bold mb-2 text-zinc-400", "Name" }
                             input {
-                                class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono",
-                                placeholder: "e.g. npx, node, python, uvx",
-                                value: "{command}",
-                                oninput: move |evt| command.set(evt.value())
+                                class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors",
+                                placeholder: "e.g. github-mcp",
+                                value: "{name}",
+                                oninput: move |evt| name.set(evt.value())
                             }
                         }
 
-                        // Arguments
+                        // Description
                         div {
-                            label { class: "block text-sm font-bold mb-2 text-zinc-400", "Arguments" }
-                            div { class: "flex gap-2",
+                            label { class: "block text-sm font-bold mb-2 text-zinc-400", "Description" }
+                            textarea {
+                                class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors resize-none h-20",
+                                placeholder: "What does this server do?",
+                                value: "{description}",
+                                oninput: move |evt| description.set(evt.value())
+                            }
+                        }
+
+                        // Conditional: Stdio or SSE fields
+                        if current_type == ServerType::Stdio {
+                            // Command
+                            div {
+                                label { class: "block text-sm font-bold mb-2 text-zinc-400", "Command" }
                                 input {
-                                    class: "flex-1 px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors",
-                                    placeholder: "Add argument...",
-                                    value: "{arg_input}",
-                                    oninput: move |evt| arg_input.set(evt.value()),
-                                    onkeypress: move |evt| {
-                                        if evt.key() == Key::Enter {
-                                            let val = arg_input().trim().to_string();
-                                            if !val.is_empty() {
-                                                args_list.write().push(val);
-                                                arg_input.set(String::new());
+                                    class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono",
+                                    placeholder: "e.g. npx, node, python, uvx",
+                                    onmounted: move |evt| async move {
+                                        if focus_command {
+                                            let _ = evt.data().set_focus(true).await;
+                                        }
+                                    },
+                                    value: "{command}",
+                                    oninput: move |evt| command.set(evt.value())
+                                }
+                            }
+
+                            // Arguments
+                            div {
+                                label { class: "block text-sm font-bold mb-2 text-zinc-400", "Arguments" }
+                                div { class: "flex gap-2",
+                                    input {
+                                        class: "flex-1 px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors",
+                                        placeholder: "Add argument...",
+                                        value: "{arg_input}",
+                                        oninput: move |evt| arg_input.set(evt.value()),
+                                        onkeypress: move |evt| {
+                                            if evt.key() == Key::Enter {
+                                                let val = arg_input().trim().to_string();
+                                                if !val.is_empty() {
+                                                    args_list.write().push(val);
+                                                    arg_input.set(String::new());
+                                                }
                                             }
                                         }
                                     }
+                                    button {
+                                        class: "px-4 py-2.5 bg-zinc-800 hover:bg-zinc-700 text-zinc-400 rounded-xl transition-colors",
+                                        onclick: add_arg,
+                                        "+"
+                                    }
+                                }
+                                div { class: "flex flex-wrap gap-2 mt-3",
+                                    for (i, arg) in current_args.iter().enumerate() {
+                                        span {
+                                            key: "{i}",
+                                            class: "inline-flex items-center gap-2 px-3 py-1.5 bg-indigo-500/10 text-indigo-400 rounded-lg text-xs font-semibold",
+                                            "{arg}"
+                                            button {
+                                                class: "hover:text-white transition-colors",
+                                                onclick: {
+                                                    let idx = i;
+                                                    move |_| {
+                                                        args_list.write().remove(idx);
+                                                    }
+                                                },
+                                                "×"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        } else if current_type == ServerType::Ws {
+                            // URL for WebSocket
+                            div {
+                                label { class: "block text-sm font-bold mb-2 text-zinc-400", "WebSocket URL" }
+                                input {
+                                    class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono",
+                                    placeholder: "wss://example.com/mcp",
+                                    value: "{url}",
+                                    oninput: move |evt| url.set(evt.value())
+                                }
+                                p { class: "mt-2 text-xs text-zinc-500", "Dropped connections are retried automatically." }
+                            }
+                        } else {
+                            // URL for SSE
+                            div {
+                                label { class: "block text-sm font-bold mb-2 text-zinc-400", "SSE Endpoint URL" }
+                                input {
+                                    class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono",
+                                    placeholder: "https://example.com/mcp",
+                                    value: "{url}",
+                                    oninput: move |evt| url.set(evt.value())
+                                }
+                                p { class: "mt-2 text-xs text-zinc-500", "The server must support SSE transport." }
+                            }
+                        }
+
+                        // Environment Variables
+                        div {
+                            label { class: "block text-sm font-bold mb-2 text-zinc-400", "Environment Variables" }
+                            div { class: "flex gap-2",
+                                input {
+                                    class: "w-1/3 px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono text-xs",
+                                    placeholder: "KEY",
+                                    value: "{env_key_input}",
+                                    oninput: move |evt| env_key_input.set(evt.value())
+                                }
+                                input {
+                                    class: "flex-1 px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono text-xs",
+                                    placeholder: "VALUE",
+                                    onmounted: move |evt| async move {
+                                        if focus_env {
+                                            let _ = evt.data().set_focus(true).await;
+                                        }
+                                    },
+                                    value: "{env_value_input}",
+                                    oninput: move |evt| env_value_input.set(evt.value())
                                 }
                                 button {
                                     class: "px-4 py-2.5 bg-zinc-800 hover:bg-zinc-700 text-zinc-400 rounded-xl transition-colors",
-                                    onclick: add_arg,
+                                    onclick: add_env,
                                     "+"
                                 }
                             }
-                            div { class: "flex flex-wrap gap-2 mt-3",
-                                for (i, arg) in current_args.iter().enumerate() {
-                                    span {
-                                        key: "{i}",
-                                        class: "inline-flex items-center gap-2 px-3 py-1.5 bg-indigo-500/10 text-indigo-400 rounded-lg text-xs font-semibold",
-                                        "{arg}"
+                            div { class: "grid gap-2 mt-3",
+                                for (key, value) in current_env.iter() {
+                                    div {
+                                        key: "{key}",
+                                        class: "flex items-center justify-between p-3 bg-zinc-900 rounded-xl border border-zinc-800",
+                                        div { class: "flex gap-4",
+                                            div {
+                                                span { class: "text-[10px] font-bold uppercase text-zinc-500 block", "KEY" }
+                                                span { class: "font-mono text-sm font-bold text-indigo-400", "{key}" }
+                                            }
+                                            div {
+                                                span { class: "text-[10px] font-bold uppercase text-zinc-500 block", "VALUE" }
+                                                span { class: "font-mono text-sm text-zinc-300 truncate max-w-[200px]", "{value}" }
+                                            }
+                                        }
                                         button {
-                                            class: "hover:text-white transition-colors",
+                                            class: "p-2 text-zinc-500 hover:text-red-400 hover:bg-red-500/10 rounded-lg transition-colors",
                                             onclick: {
-                                                let idx = i;
+                                                let k = key.clone();
                                                 move |_| {
-                                                    args_list.write().remove(idx);
+                                                    env_map.write().remove(&k);
                                                 }
                                             },
-                                            "×"
+                                            "🗑"
                                         }
                                     }
                                 }
                             }
                         }
-                    } else {
-                        // URL for SSE
-                        div {
-                            label { class: "block text-sm font-bold mb-2 text-zinc-400", "SSE Endpoint URL" }
-                            input {
-                                class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono",
-                                placeholder: "https://example.com/mcp",
-                                value: "{url}",
-                                oninput: move |evt| url.set(evt.value())
+
+                        // Clean environment
+                        if current_type == ServerType::Stdio && is_edit {
+                            label { class: "flex items-start gap-3 p-3 bg-zinc-900 rounded-xl border border-zinc-800 cursor-pointer",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "mt-0.5",
+                                    checked: launch_options().clean_env,
+                                    onchange: move |evt| launch_options.write().clean_env = evt.checked()
+                                }
+                                div {
+                                    span { class: "block text-sm font-bold text-zinc-300", "Clean environment" }
+                                    span { class: "block text-xs text-zinc-500 mt-0.5",
+                                        "Start with only PATH, HOME and a few system variables plus the ones above, so unrelated secrets from the manager's environment don't reach this server. Applies on next start."
+                                    }
+                                }
                             }
-                            p { class: "mt-2 text-xs text-zinc-500", "The server must support SSE transport." }
                         }
-                    }
 
-                    // Environment Variables
-                    div {
-                        label { class: "block text-sm font-bold mb-2 text-zinc-400", "Environment Variables" }
-                        div { class: "flex gap-2",
-                            input {
-                                class: "w-1/3 px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono text-xs",
-                                placeholder: "KEY",
-                                value: "{env_key_input}",
-                                oninput: move |evt| env_key_input.set(evt.value())
-                            }
-                            input {
-                                class: "flex-1 px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono text-xs",
-                                placeholder: "VALUE",
-                                value: "{env_value_input}",
-                                oninput: move |evt| env_value_input.set(evt.value())
+                        // Output encoding
+                        if current_type == ServerType::Stdio && is_edit {
+                            div {
+                                label { class: "block text-sm font-bold mb-2 text-zinc-400", "Output encoding" }
+                                select {
+                                    class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors",
+                                    onchange: move |evt| {
+                                        let label = evt.value();
+                                        launch_options.write().encoding = Some(label).filter(|l| !l.is_empty());
+                                    },
+                                    option { value: "", selected: launch_options().encoding.is_none(), "UTF-8 (default)" }
+                                    for (label, name) in crate::output_reader::COMMON_ENCODINGS.iter() {
+                                        option {
+                                            key: "{label}",
+                                            value: "{label}",
+                                            selected: launch_options().encoding.as_deref() == Some(*label),
+                                            "{name}"
+                                        }
+                                    }
+                                }
+                                p { class: "mt-2 text-xs text-zinc-500",
+                                    "Pick the code page this server prints its logs in if they show up garbled. Applies on next start."
+                                }
                             }
-                            button {
-                                class: "px-4 py-2.5 bg-zinc-800 hover:bg-zinc-700 text-zinc-400 rounded-xl transition-colors",
-                                onclick: add_env,
-                                "+"
+                        }
+
+                        // Message framing
+                        if current_type == ServerType::Stdio && is_edit {
+                            label { class: "flex items-start gap-3 p-3 bg-zinc-900 rounded-xl border border-zinc-800 cursor-pointer",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "mt-0.5",
+                                    checked: launch_options().content_length_framing,
+                                    onchange: move |evt| launch_options.write().content_length_framing = evt.checked()
+                                }
+                                div {
+                                    span { class: "block text-sm font-bold text-zinc-300", "Content-Length framing" }
+                                    span { class: "block text-xs text-zinc-500 mt-0.5",
+                                        "Send messages with LSP-style Content-Length headers instead of one per line. Servers that answer this way are detected automatically; only needed for ones that never write first."
+                                    }
+                                }
                             }
                         }
-                        div { class: "grid gap-2 mt-3",
-                            for (key, value) in current_env.iter() {
+
+                        // Terminal mode
+                        if current_type == ServerType::Stdio && is_edit {
+                            label { class: "flex items-start gap-3 p-3 bg-zinc-900 rounded-xl border border-zinc-800 cursor-pointer",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "mt-0.5",
+                                    checked: launch_options().pty,
+                                    onchange: move |evt| launch_options.write().pty = evt.checked()
+                                }
                                 div {
-                                    key: "{key}",
-                                    class: "flex items-center justify-between p-3 bg-zinc-900 rounded-xl border border-zinc-800",
-                                    div { class: "flex gap-4",
-                                        div {
-                                            span { class: "text-[10px] font-bold uppercase text-zinc-500 block", "KEY" }
-                                            span { class: "font-mono text-sm font-bold text-indigo-400", "{key}" }
-                                        }
-                                        div {
-                                            span { class: "text-[10px] font-bold uppercase text-zinc-500 block", "VALUE" }
-                                            span { class: "font-mono text-sm text-zinc-300 truncate max-w-[200px]", "{value}" }
-                                        }
+                                    span { class: "block text-sm font-bold text-zinc-300", "Terminal mode (experimental)" }
+                                    span { class: "block text-xs text-zinc-500 mt-0.5",
+                                        "Run the server in a pseudo-terminal, for servers that check for one and hang on plain pipes. Its console gets a Terminal tab for answering prompts. macOS and Linux only. Applies on next start."
                                     }
-                                    button {
-                                        class: "p-2 text-zinc-500 hover:text-red-400 hover:bg-red-500/10 rounded-lg transition-colors",
-                                        onclick: {
-                                            let k = key.clone();
-                                            move |_| {
-                                                env_map.write().remove(&k);
-                                            }
-                                        },
-                                        "🗑"
+                                }
+                            }
+                        }
+
+                        // Start/stop hooks
+                        if is_edit {
+                            div { class: "space-y-3",
+                                label { class: "block text-sm font-bold text-zinc-400", "Hooks" }
+                                input {
+                                    class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono text-sm",
+                                    placeholder: "Before start, e.g. docker start pg-dev",
+                                    value: "{launch_options().pre_start.unwrap_or_default()}",
+                                    oninput: move |evt| {
+                                        launch_options.write().pre_start = Some(evt.value()).filter(|c| !c.trim().is_empty());
+                                    }
+                                }
+                                input {
+                                    class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono text-sm",
+                                    placeholder: "After stop, e.g. docker stop pg-dev",
+                                    value: "{launch_options().post_stop.unwrap_or_default()}",
+                                    oninput: move |evt| {
+                                        launch_options.write().post_stop = Some(evt.value()).filter(|c| !c.trim().is_empty());
                                     }
                                 }
+                                p { class: "text-xs text-zinc-500",
+                                    "Shell commands run with this server's environment, for services it depends on. Their output goes to the server's logs. If the start hook fails or runs past 60s, the server isn't started."
+                                }
+                            }
+                        }
+
+                        // Working directory
+                        if current_type == ServerType::Stdio && is_edit {
+                            div {
+                                label { class: "block text-sm font-bold mb-2 text-zinc-400", "Working directory" }
+                                input {
+                                    class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono text-sm",
+                                    placeholder: "e.g. /home/me/src/weather-server",
+                                    value: "{cwd}",
+                                    oninput: move |evt| cwd.set(evt.value())
+                                }
+                                p { class: "mt-2 text-xs text-zinc-500",
+                                    "Where the server is started, for servers run from a cloned repository. Relative paths in the command and arguments start here. Leave blank to use the manager's own."
+                                }
+                            }
+                        }
+
+                        // Docker
+                        if current_type == ServerType::Stdio && is_edit {
+                            div { class: "space-y-3",
+                                label { class: "block text-sm font-bold mb-2 text-zinc-400", "Docker image" }
+                                input {
+                                    class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono text-sm",
+                                    placeholder: "e.g. mcp/fetch",
+                                    value: "{docker_image}",
+                                    oninput: move |evt| docker_image.set(evt.value())
+                                }
+                                textarea {
+                                    class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors resize-none h-20 font-mono text-sm",
+                                    placeholder: "Mounts, one per line, e.g. /home/me/notes:/notes:ro",
+                                    value: "{docker_mounts}",
+                                    oninput: move |evt| docker_mounts.set(evt.value())
+                                }
+                                p { class: "text-xs text-zinc-500",
+                                    "Runs the server with docker run -i, isolated from the host's toolchain. The command and arguments above, if any, run inside the container instead of the image's own; the environment variables are passed in. Leave the image blank to run on the host."
+                                }
+                            }
+                        }
+
+                        // Alias
+                        if is_edit {
+                            div {
+                                label { class: "block text-sm font-bold mb-2 text-zinc-400", "Alias" }
+                                input {
+                                    class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors font-mono text-sm",
+                                    placeholder: "e.g. fs",
+                                    maxlength: "{max_alias_len}",
+                                    value: "{alias}",
+                                    oninput: move |evt| alias.set(evt.value())
+                                }
+                                p { class: "mt-2 text-xs text-zinc-500",
+                                    "A short name for the command palette and the command line, e.g. open-mcp-manager start fs. Lowercase letters, digits, - and _."
+                                }
+                            }
+                        }
+
+                        // Request timeout
+                        if is_edit {
+                            div {
+                                label { class: "block text-sm font-bold mb-2 text-zinc-400", "Request timeout (seconds)" }
+                                input {
+                                    r#type: "number",
+                                    min: "1",
+                                    class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors",
+                                    placeholder: "{default_timeout}",
+                                    value: "{request_timeout}",
+                                    oninput: move |evt| request_timeout.set(evt.value())
+                                }
+                                p { class: "mt-2 text-xs text-zinc-500",
+                                    "How long a request waits for the server's reply before failing. Leave blank for the default."
+                                }
+                            }
+                        }
+
+                        // Lifetime
+                        div {
+                            label { class: "block text-sm font-bold mb-2 text-zinc-400", "Lifetime" }
+                            select {
+                                class: "w-full px-4 py-2.5 bg-zinc-900 border border-zinc-700 rounded-xl focus:outline-none focus:border-indigo-500 transition-colors",
+                                onchange: move |evt| {
+                                    lifetime.set(match evt.value().as_str() {
+                                        "" => None,
+                                        "permanent" => Some(None),
+                                        hours => hours.parse().ok().map(Some),
+                                    });
+                                },
+                                option { value: "", selected: lifetime().is_none(),
+                                    if let Some(left) = current_expiry.as_ref() { "Temporary ({left})" } else { "Permanent" }
+                                }
+                                if current_expiry.is_some() {
+                                    option { value: "permanent", selected: lifetime() == Some(None), "Make permanent" }
+                                }
+                                for (label, hours) in crate::expiry::EXPIRY_CHOICES {
+                                    option {
+                                        key: "{hours}",
+                                        value: "{hours}",
+                                        selected: lifetime() == Some(Some(hours)),
+                                        "Temporary: archive after {label}"
+                                    }
+                                }
+                            }
+                            p { class: "mt-2 text-xs text-zinc-500",
+                                "Temporary servers are stopped and archived when they expire, and left out of exported configs."
+                            }
+                        }
+                    }
+                }
+
+                // Lint Warnings
+                if !lint_warnings.is_empty() && !history_mode() {
+                    div {
+                        class: "px-6 py-3 bg-amber-500/5 border-t border-amber-500/20 space-y-1",
+                        for warning in lint_warnings.iter() {
+                            p {
+                                key: "{warning.code}{warning.message}",
+                                class: "text-xs text-amber-400",
+                                "⚠️ {warning.message}"
                             }
                         }
                     }
@@ -379,7 +956,8 @@ pub fn Settings(props: SettingsProps) -> Element {
                         "Cancel"
                     }
                     button {
-                        class: "px-6 py-2.5 bg-indigo-600 hover:bg-indigo-500 text-white rounded-xl text-sm font-bold transition-colors shadow-lg shadow-indigo-500/20",
+                        class: "px-6 py-2.5 bg-indigo-600 hover:bg-indigo-500 disabled:opacity-50 text-white rounded-xl text-sm font-bold transition-colors shadow-lg shadow-indigo-500/20",
+                        disabled: json_invalid || history_mode(),
                         onclick: onsubmit,
                         if is_edit { "Save Changes" } else { "Create Server" }
                     }
@@ -388,3 +966,39 @@ pub fn Settings(props: SettingsProps) -> Element {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_server_json_round_trip() {
+        let args = CreateServerArgs {
+            name: "memory".to_string(),
+            server_type: "stdio".to_string(),
+            command: Some("npx".to_string()),
+            args: Some(vec!["-y".to_string(), "pkg".to_string()]),
+            ..Default::default()
+        };
+        let src = serde_json::to_string_pretty(&args).unwrap();
+        let parsed = parse_server_json(&src).unwrap();
+        assert_eq!(parsed.name, "memory");
+        assert_eq!(parsed.args.unwrap(), vec!["-y", "pkg"]);
+    }
+
+    #[test]
+    fn test_parse_server_json_rejects_invalid_definitions() {
+        assert!(parse_server_json("{")
+            .unwrap_err()
+            .starts_with("Invalid JSON"));
+        assert!(parse_server_json(r#"{"name": "x", "type": "http"}"#)
+            .unwrap_err()
+            .starts_with("$.type"));
+        assert!(
+            parse_server_json(r#"{"name": "x", "type": "stdio", "cmd": "npx"}"#)
+                .unwrap_err()
+                .contains("unknown property")
+        );
+        assert!(parse_server_json(r#"{"name": " ", "type": "stdio"}"#).is_err());
+    }
+}
@@ -1,7 +1,20 @@
+use crate::state::{AppState, APP_STATE};
+use crate::updater::CURRENT_VERSION;
 use dioxus::prelude::*;
 
 #[component]
-pub fn Sidebar(active_tab: String, on_tab_change: EventHandler<String>) -> Element {
+pub fn Sidebar(
+    active_tab: String,
+    on_tab_change: EventHandler<String>,
+    on_show_update: EventHandler<()>,
+) -> Element {
+    let update = APP_STATE.read().available_update.cloned();
+    let auto_check = *APP_STATE.read().update_checks_enabled.read();
+    let restore_session = *APP_STATE.read().restore_session_enabled.read();
+    let stop_grace = *APP_STATE.read().stop_grace_secs.read();
+    let report_open = *APP_STATE.read().weekly_report_open.read();
+    let mut checking = use_signal(|| false);
+
     rsx! {
         aside {
             class: "w-72 flex flex-col glass border-r-0 border-r border-white-5 relative z-10",
@@ -38,6 +51,30 @@ pub fn Sidebar(active_tab: String, on_tab_change: EventHandler<String>) -> Eleme
                     active: active_tab == "research",
                     on_click: move |_| on_tab_change.call("research".to_string())
                 }
+                SidebarLink {
+                    label: "Graph",
+                    icon: "share",
+                    active: active_tab == "graph",
+                    on_click: move |_| on_tab_change.call("graph".to_string())
+                }
+                SidebarLink {
+                    label: "Hub",
+                    icon: "bolt",
+                    active: active_tab == "hub",
+                    on_click: move |_| on_tab_change.call("hub".to_string())
+                }
+                SidebarLink {
+                    label: "Projects",
+                    icon: "folder",
+                    active: active_tab == "projects",
+                    on_click: move |_| on_tab_change.call("projects".to_string())
+                }
+                SidebarLink {
+                    label: "What's New",
+                    icon: "sparkles",
+                    active: active_tab == "whats_new",
+                    on_click: move |_| on_tab_change.call("whats_new".to_string())
+                }
                 SidebarLink {
                     label: "Settings",
                     icon: "cog",
@@ -63,8 +100,93 @@ pub fn Sidebar(active_tab: String, on_tab_change: EventHandler<String>) -> Eleme
                     div {
                         class: "flex flex-col",
                         span { class: "text-xs font-semibold text-zinc-300", "System Online" }
-                        span { class: "text-[10px] text-zinc-500 font-mono", "v0.1.0 Alpha" }
+                        span { class: "text-[10px] text-zinc-500 font-mono", "v{CURRENT_VERSION}" }
+                    }
+                }
+                if let Some(release) = update {
+                    button {
+                        class: "mt-3 w-full flex items-center gap-2 px-3 py-2 rounded-xl text-xs font-bold bg-indigo-500/10 text-indigo-300 hover:bg-indigo-500/20 transition-colors",
+                        onclick: move |_| on_show_update.call(()),
+                        "⬆ Update to {release.version()}"
+                    }
+                } else {
+                    button {
+                        class: "mt-3 w-full flex items-center gap-2 px-3 py-2 rounded-xl text-xs font-semibold text-zinc-500 hover:text-zinc-300 hover:bg-white-5 transition-colors disabled:opacity-50",
+                        disabled: checking(),
+                        onclick: move |_| {
+                            checking.set(true);
+                            spawn(async move {
+                                AppState::check_for_updates(true).await;
+                                checking.set(false);
+                            });
+                        },
+                        if checking() { "🔄 Checking..." } else { "🔄 Check for updates" }
+                    }
+                }
+                label { class: "mt-2 flex items-center gap-2 px-3 text-[10px] text-zinc-500",
+                    input {
+                        r#type: "checkbox",
+                        checked: auto_check,
+                        onchange: move |evt| AppState::set_update_checks_enabled(evt.checked())
+                    }
+                    "Check for updates automatically"
+                }
+                label { class: "mt-1 flex items-center gap-2 px-3 text-[10px] text-zinc-500",
+                    input {
+                        r#type: "checkbox",
+                        checked: restore_session,
+                        onchange: move |evt| AppState::set_restore_session_enabled(evt.checked())
+                    }
+                    "Restore session on launch"
+                }
+                label {
+                    class: "mt-1 flex items-center gap-2 px-3 text-[10px] text-zinc-500",
+                    title: "How long a stopping server gets to exit, first once its input closes and then once asked to terminate, before it is killed",
+                    "Stop grace period"
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        class: "w-12 bg-zinc-900 border border-zinc-800 rounded px-1 text-zinc-300 focus:outline-none focus:border-indigo-500",
+                        value: "{stop_grace}",
+                        onchange: move |evt| {
+                            if let Ok(secs) = evt.value().parse::<u64>() {
+                                AppState::set_stop_grace_secs(secs);
+                            }
+                        }
+                    }
+                    "s"
+                }
+                label {
+                    class: "mt-1 flex items-center gap-2 px-3 text-[10px] text-zinc-500",
+                    title: "A summary of uptime, tool usage, errors and registry news is saved every Monday",
+                    input {
+                        r#type: "checkbox",
+                        checked: report_open,
+                        onchange: move |evt| AppState::set_weekly_report_open(evt.checked())
                     }
+                    "Open weekly report on Mondays"
+                }
+                button {
+                    class: "mt-3 w-full flex items-center gap-2 px-3 py-2 rounded-xl text-xs font-semibold text-zinc-500 hover:text-zinc-300 hover:bg-white-5 transition-colors",
+                    onclick: move |_| match AppState::generate_weekly_report() {
+                        Ok(path) => AppState::open_url(&path.to_string_lossy()),
+                        Err(e) => AppState::push_notification(
+                            format!("Could not write the weekly report: {}", e),
+                            crate::models::NotificationLevel::Error,
+                        ),
+                    },
+                    "📊 Weekly report"
+                }
+                button {
+                    class: "mt-3 w-full flex items-center gap-2 px-3 py-2 rounded-xl text-xs font-semibold text-zinc-500 hover:text-zinc-300 hover:bg-white-5 transition-colors",
+                    onclick: move |_| match crate::platform::app_data_dir() {
+                        Ok(dir) => crate::state::AppState::reveal_in_file_manager(&dir),
+                        Err(e) => crate::state::AppState::push_notification(
+                            e.to_string(),
+                            crate::models::NotificationLevel::Error,
+                        ),
+                    },
+                    "📁 Open data folder"
                 }
             }
         }
@@ -97,6 +219,26 @@ fn SidebarLink(label: String, icon: String, active: bool, on_click: EventHandler
                 path { stroke_linecap: "round", stroke_linejoin: "round", d: "M4 17l6-6-6-6m8 14h8" }
              }
         },
+        "share" => rsx! {
+            svg { class: "w-5 h-5", fill: "none", view_box: "0 0 24 24", stroke: "currentColor", stroke_width: "2",
+                path { stroke_linecap: "round", stroke_linejoin: "round", d: "M8.684 13.342C8.886 12.938 9 12.482 9 12c0-.482-.114-.938-.316-1.342m0 2.684a3 3 0 110-2.684m0 2.684l6.632 3.316m-6.632-6l6.632-3.316m0 0a3 3 0 105.367-2.684 3 3 0 00-5.367 2.684zm0 9.316a3 3 0 105.368 2.684 3 3 0 00-5.368-2.684z" }
+            }
+        },
+        "sparkles" => rsx! {
+            svg { class: "w-5 h-5", fill: "none", view_box: "0 0 24 24", stroke: "currentColor", stroke_width: "2",
+                path { stroke_linecap: "round", stroke_linejoin: "round", d: "M5 3v4M3 5h4M6 17v4m-2-2h4m5-16l2.286 6.857L21 12l-5.714 2.143L13 21l-2.286-6.857L5 12l5.714-2.143L13 3z" }
+            }
+        },
+        "folder" => rsx! {
+            svg { class: "w-5 h-5", fill: "none", view_box: "0 0 24 24", stroke: "currentColor", stroke_width: "2",
+                path { stroke_linecap: "round", stroke_linejoin: "round", d: "M3 7a2 2 0 012-2h4l2 2h8a2 2 0 012 2v8a2 2 0 01-2 2H5a2 2 0 01-2-2V7z" }
+            }
+        },
+        "bolt" => rsx! {
+            svg { class: "w-5 h-5", fill: "none", view_box: "0 0 24 24", stroke: "currentColor", stroke_width: "2",
+                path { stroke_linecap: "round", stroke_linejoin: "round", d: "M13 10V3L4 14h7v7l9-11h-7z" }
+            }
+        },
         _ => rsx! { div {} },
     };
 
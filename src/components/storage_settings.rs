@@ -0,0 +1,244 @@
+use crate::models::{McpServer, NotificationLevel};
+use crate::state::{AppState, APP_STATE};
+use crate::storage::{format_size, Locations, UsageEntry};
+use dioxus::prelude::*;
+
+/// Where installs, extensions and caches live, how much space each takes
+/// and which directories are left over from deleted servers.
+pub fn StorageSettings() -> Element {
+    let mut root = use_signal(|| crate::storage::root().ok());
+    let mut error = use_signal(|| None::<String>);
+    let mut busy = use_signal(|| false);
+    // Bumped to rescan the disk after a change
+    let mut scan = use_signal(|| 0u32);
+
+    let usage = use_resource(move || async move {
+        scan();
+        let root = root()?;
+        let usage = crate::storage::usage_in(&root);
+        let orphans = AppState::orphaned_dirs();
+        Some((usage, orphans))
+    });
+
+    let per_server = use_resource(move || async move {
+        scan();
+        let servers = APP_STATE.read().servers.cloned();
+        let locations = Locations::current().ok()?;
+        let mut usage: Vec<(McpServer, Vec<UsageEntry>)> = servers
+            .into_iter()
+            .map(|server| {
+                let usage = crate::storage::server_usage(&server, &locations);
+                (server, usage)
+            })
+            .filter(|(_, usage)| !usage.is_empty())
+            .collect();
+        usage.sort_by_key(|(_, usage)| {
+            std::cmp::Reverse(usage.iter().map(|e| e.bytes).sum::<u64>())
+        });
+        Some(usage)
+    });
+
+    let mut change_root = move |reset: bool| {
+        error.set(None);
+        spawn(async move {
+            match AppState::choose_storage_root(reset).await {
+                Ok(path) => {
+                    root.set(Some(path));
+                    scan.with_mut(|n| *n += 1);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    };
+
+    let clean_up = move |_| {
+        busy.set(true);
+        spawn(async move {
+            let freed = AppState::remove_orphaned_dirs();
+            AppState::push_notification(
+                format!("Freed {}", format_size(freed)),
+                NotificationLevel::Success,
+            );
+            scan.with_mut(|n| *n += 1);
+            busy.set(false);
+        });
+    };
+
+    let root_label = root()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "Unavailable".to_string());
+
+    rsx! {
+        div { class: "max-w-3xl mx-auto space-y-6",
+            div {
+                h2 { class: "text-2xl font-bold text-white tracking-tight", "Storage" }
+                p { class: "text-sm text-zinc-500 mt-1",
+                    "Source installs, extracted bundles and caches are kept under one root."
+                }
+            }
+
+            section { class: "p-5 rounded-2xl glass space-y-3",
+                h3 { class: "text-xs font-bold text-zinc-500 uppercase", "Install location" }
+                div { class: "flex items-center gap-3",
+                    span { class: "flex-1 font-mono text-sm text-white truncate", title: "{root_label}", "{root_label}" }
+                    button {
+                        class: "px-3 py-1.5 bg-zinc-800 hover:bg-zinc-700 text-white rounded-lg text-xs font-bold transition-colors",
+                        onclick: move |_| change_root(false),
+                        "Change…"
+                    }
+                    button {
+                        class: "px-3 py-1.5 text-zinc-400 hover:text-white text-xs transition-colors",
+                        onclick: move |_| change_root(true),
+                        "Reset"
+                    }
+                }
+                p { class: "text-xs text-zinc-500", "Applies to new installs; existing ones stay where they are." }
+                if let Some(e) = error() {
+                    p { class: "text-xs text-red-400", "role": "alert", "{e}" }
+                }
+            }
+
+            match usage.read().as_ref() {
+                Some(Some((usage, orphans))) => {
+                    let total: u64 = usage.iter().map(|(_, size)| size).sum();
+                    rsx! {
+                        section { class: "p-5 rounded-2xl glass space-y-3",
+                            h3 { class: "text-xs font-bold text-zinc-500 uppercase", "Disk usage" }
+                            for (kind, size) in usage.iter() {
+                                div { key: "{kind.dir_name()}", class: "flex justify-between text-sm",
+                                    span { "{kind.label()}" }
+                                    span { class: "font-mono text-zinc-400", "{format_size(*size)}" }
+                                }
+                            }
+                            div { class: "flex justify-between text-sm font-bold text-white border-t border-zinc-800 pt-2",
+                                span { "Total" }
+                                span { class: "font-mono", "{format_size(total)}" }
+                            }
+                        }
+                        section { class: "p-5 rounded-2xl glass space-y-3",
+                            div { class: "flex items-center justify-between",
+                                h3 { class: "text-xs font-bold text-zinc-500 uppercase", "Orphaned directories" }
+                                if matches!(orphans, Ok(dirs) if !dirs.is_empty()) {
+                                    button {
+                                        class: "px-3 py-1.5 bg-red-600/80 hover:bg-red-500 text-white rounded-lg text-xs font-bold transition-colors disabled:opacity-50",
+                                        disabled: busy(),
+                                        onclick: clean_up,
+                                        "Clean up"
+                                    }
+                                }
+                            }
+                            match orphans {
+                                Ok(dirs) if dirs.is_empty() => rsx! {
+                                    p { class: "text-sm text-zinc-500", "Every directory belongs to a server." }
+                                },
+                                Ok(dirs) => rsx! {
+                                    for dir in dirs.iter() {
+                                        div { key: "{dir.display()}", class: "flex justify-between gap-3 text-xs",
+                                            span { class: "font-mono text-zinc-400 truncate", "{dir.display()}" }
+                                            span { class: "font-mono text-zinc-500", "{format_size(crate::storage::dir_size(dir))}" }
+                                        }
+                                    }
+                                },
+                                Err(e) => rsx! {
+                                    p { class: "text-xs text-red-400", "Could not check for orphans: {e}" }
+                                },
+                            }
+                        }
+                    }
+                }
+                Some(None) => rsx! {
+                    p { class: "text-sm text-zinc-500", "The storage location is unavailable." }
+                },
+                None => rsx! {
+                    p { class: "text-sm text-zinc-500", "Measuring…" }
+                },
+            }
+
+            section { class: "p-5 rounded-2xl glass space-y-3",
+                h3 { class: "text-xs font-bold text-zinc-500 uppercase", "Per server" }
+                match per_server.read().as_ref() {
+                    Some(Some(usage)) if usage.is_empty() => rsx! {
+                        p { class: "text-sm text-zinc-500", "No server has files on disk yet." }
+                    },
+                    Some(Some(usage)) => rsx! {
+                        for (server, entries) in usage.iter() {
+                            ServerDiskUsage {
+                                key: "{server.id}",
+                                name: server.name.clone(),
+                                entries: entries.clone(),
+                                on_cleaned: move |_| scan.with_mut(|n| *n += 1),
+                            }
+                        }
+                    },
+                    Some(None) => rsx! {
+                        p { class: "text-sm text-zinc-500", "The storage location is unavailable." }
+                    },
+                    None => rsx! {
+                        p { class: "text-sm text-zinc-500", "Measuring…" }
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// One server's footprint, with a button to drop its caches.
+#[component]
+fn ServerDiskUsage(
+    name: String,
+    entries: Vec<UsageEntry>,
+    on_cleaned: EventHandler<()>,
+) -> Element {
+    let total: u64 = entries.iter().map(|e| e.bytes).sum();
+    let cached: u64 = entries
+        .iter()
+        .filter(|e| e.kind.is_cache())
+        .map(|e| e.bytes)
+        .sum();
+
+    let clean = {
+        let entries = entries.clone();
+        let name = name.clone();
+        move |evt: MouseEvent| {
+            // Keep the click from toggling the surrounding <details>
+            evt.prevent_default();
+            match crate::storage::clean_caches(&entries) {
+                Ok(freed) => AppState::push_notification(
+                    format!("Freed {} of caches for {}", format_size(freed), name),
+                    NotificationLevel::Success,
+                ),
+                Err(e) => AppState::push_notification(
+                    format!("Could not clean caches for {}: {}", name, e),
+                    NotificationLevel::Error,
+                ),
+            }
+            on_cleaned.call(());
+        }
+    };
+
+    rsx! {
+        details { class: "rounded-xl border border-zinc-800",
+            summary { class: "flex items-center gap-3 px-3 py-2 text-sm cursor-pointer",
+                span { class: "flex-1 text-white", "{name}" }
+                span { class: "font-mono text-zinc-400", "{format_size(total)}" }
+                if cached > 0 {
+                    button {
+                        class: "px-2 py-1 bg-zinc-800 hover:bg-zinc-700 text-white rounded text-[10px] font-bold transition-colors",
+                        title: "Deleted caches are downloaded again on next launch",
+                        onclick: clean,
+                        "Clean caches ({format_size(cached)})"
+                    }
+                }
+            }
+            div { class: "px-3 pb-2 space-y-1",
+                for entry in entries.iter() {
+                    div { key: "{entry.path.display()}", class: "flex justify-between gap-3 text-xs",
+                        span { class: "w-20 shrink-0 text-zinc-500", "{entry.kind.label()}" }
+                        span { class: "flex-1 font-mono text-zinc-400 truncate", title: "{entry.path.display()}", "{entry.path.display()}" }
+                        span { class: "font-mono text-zinc-500", "{format_size(entry.bytes)}" }
+                    }
+                }
+            }
+        }
+    }
+}
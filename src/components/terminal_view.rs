@@ -0,0 +1,119 @@
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct TerminalViewProps {
+    pub server_id: String,
+}
+
+/// What a key press sends to the terminal. Enter sends a newline, as the
+/// terminal is raw and won't translate a carriage return.
+fn key_bytes(key: &Key, ctrl: bool) -> Option<Vec<u8>> {
+    let bytes: &[u8] = match key {
+        Key::Character(c) if ctrl => {
+            // Ctrl+C is 0x03, Ctrl+D 0x04 and so on
+            let c = c.chars().next()?.to_ascii_uppercase();
+            return ('@'..='_').contains(&c).then(|| vec![c as u8 & 0x1f]);
+        }
+        Key::Character(c) => return Some(c.as_bytes().to_vec()),
+        Key::Enter => b"\n",
+        Key::Backspace => b"\x7f",
+        Key::Tab => b"\t",
+        Key::Escape => b"\x1b",
+        Key::ArrowUp => b"\x1b[A",
+        Key::ArrowDown => b"\x1b[B",
+        Key::ArrowRight => b"\x1b[C",
+        Key::ArrowLeft => b"\x1b[D",
+        Key::Home => b"\x1b[H",
+        Key::End => b"\x1b[F",
+        Key::Delete => b"\x1b[3~",
+        _ => return None,
+    };
+    Some(bytes.to_vec())
+}
+
+/// The terminal a server runs in when started in PTY mode: its output in
+/// colour, and keys typed here sent to it, e.g. to answer a one-time
+/// prompt.
+pub fn TerminalView(props: TerminalViewProps) -> Element {
+    let mut output = use_signal(Vec::<u8>::new);
+    let mut send_error = use_signal(|| None::<String>);
+
+    let server_id = props.server_id.clone();
+    use_future(move || {
+        let server_id = server_id.clone();
+        async move {
+            // Follows the terminal across restarts of the server
+            loop {
+                if let Some(terminal) = AppState::terminal(&server_id) {
+                    let mut rx = terminal.output();
+                    drop(terminal);
+                    loop {
+                        output.set(rx.borrow_and_update().clone());
+                        if rx.changed().await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            }
+        }
+    });
+
+    let server_id = props.server_id.clone();
+    let onkeydown = move |evt: KeyboardEvent| {
+        let Some(bytes) = key_bytes(&evt.key(), evt.modifiers().ctrl()) else {
+            return;
+        };
+        evt.prevent_default();
+        let Some(terminal) = AppState::terminal(&server_id) else {
+            send_error.set(Some("Server is not running".into()));
+            return;
+        };
+        spawn(async move {
+            send_error.set(terminal.send_keys(bytes).await.err());
+        });
+    };
+
+    let spans = crate::ansi::render(&String::from_utf8_lossy(&output.read()));
+
+    rsx! {
+        div { class: "flex flex-col h-full",
+            div { class: "px-4 py-2 flex items-center justify-between text-xs text-zinc-500 border-b border-zinc-800",
+                span { "Click the terminal and type to send keys. The server's terminal does not echo what you type." }
+                if let Some(e) = send_error() {
+                    span { class: "text-red-400", "role": "alert", "{e}" }
+                }
+            }
+            div {
+                class: "flex-1 overflow-auto bg-black p-4 focus:outline-none focus:ring-1 focus:ring-indigo-500",
+                tabindex: "0",
+                "aria-label": "Server terminal",
+                onkeydown,
+                pre { class: "font-mono text-sm text-zinc-300 whitespace-pre-wrap",
+                    for (i, run) in spans.into_iter().enumerate() {
+                        span { key: "{i}", style: "{run.style.css()}", "{run.text}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_bytes() {
+        assert_eq!(
+            key_bytes(&Key::Character("y".into()), false),
+            Some(b"y".to_vec())
+        );
+        assert_eq!(key_bytes(&Key::Character("c".into()), true), Some(vec![3]));
+        assert_eq!(key_bytes(&Key::Character("1".into()), true), None);
+        assert_eq!(key_bytes(&Key::Enter, false), Some(b"\n".to_vec()));
+        assert_eq!(key_bytes(&Key::ArrowUp, false), Some(b"\x1b[A".to_vec()));
+        assert_eq!(key_bytes(&Key::Shift, false), None);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::models::Tool;
+use crate::state::APP_STATE;
+use crate::tool_docs::{format_example, hints, parameters};
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct ToolDocsProps {
+    pub server_id: String,
+    pub tool: Tool,
+    /// Opens the tool with an example's arguments filled in.
+    pub on_try: EventHandler<String>,
+}
+
+/// Reference page for a tool, generated from its schema, annotations and
+/// the arguments of past successful calls.
+pub fn ToolDocs(props: ToolDocsProps) -> Element {
+    let params = parameters(&props.tool.inputSchema);
+    let hints = props
+        .tool
+        .annotations
+        .as_ref()
+        .map(hints)
+        .unwrap_or_default();
+    let output_schema = props
+        .tool
+        .outputSchema
+        .as_ref()
+        .map(|s| serde_json::to_string_pretty(s).unwrap_or_default());
+
+    let server_id = props.server_id.clone();
+    let tool_name = props.tool.name.clone();
+    let examples = use_resource(move || {
+        let server_id = server_id.clone();
+        let tool_name = tool_name.clone();
+        async move {
+            let db = APP_STATE.read().db.cloned()?;
+            db.get_tool_examples(&server_id, &tool_name, 3).ok()
+        }
+    });
+    let examples = examples.cloned().flatten().unwrap_or_default();
+    let on_try = props.on_try;
+
+    rsx! {
+        div { class: "space-y-4 text-sm",
+            if let Some(description) = &props.tool.description {
+                p { class: "text-zinc-300 whitespace-pre-wrap", "{description}" }
+            }
+            if !hints.is_empty() {
+                div { class: "flex flex-wrap gap-2",
+                    for (label, explanation) in hints {
+                        span {
+                            key: "{label}",
+                            class: "px-2 py-0.5 rounded bg-zinc-800 border border-zinc-700 text-[11px] text-zinc-300",
+                            title: "{explanation}",
+                            "{label}"
+                        }
+                    }
+                }
+            }
+            div {
+                h4 { class: "text-xs font-bold text-zinc-500 uppercase mb-2", "Parameters" }
+                if params.is_empty() {
+                    p { class: "text-xs text-zinc-500", "Takes no parameters." }
+                } else {
+                    table { class: "w-full text-xs",
+                        thead {
+                            tr { class: "text-left text-zinc-500",
+                                th { class: "pb-1 pr-3 font-semibold", "Name" }
+                                th { class: "pb-1 pr-3 font-semibold", "Type" }
+                                th { class: "pb-1 font-semibold", "Description" }
+                            }
+                        }
+                        tbody {
+                            for param in params {
+                                tr { key: "{param.name}", class: "border-t border-zinc-800 align-top",
+                                    td { class: "py-1.5 pr-3 font-mono text-white whitespace-nowrap",
+                                        "{param.name}"
+                                        if param.required {
+                                            span { class: "ml-1 text-red-400", title: "Required", "*" }
+                                        }
+                                    }
+                                    td { class: "py-1.5 pr-3 font-mono text-indigo-300 whitespace-nowrap", "{param.type_label}" }
+                                    td { class: "py-1.5 text-zinc-400",
+                                        "{param.description.clone().unwrap_or_default()}"
+                                        if !param.choices.is_empty() {
+                                            div { class: "text-zinc-500", "One of: {param.choices.join(\", \")}" }
+                                        }
+                                        if let Some(default) = &param.default {
+                                            div { class: "text-zinc-500", "Default: {default}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(schema) = output_schema {
+                div {
+                    h4 { class: "text-xs font-bold text-zinc-500 uppercase mb-2", "Output" }
+                    pre { class: "bg-black/50 p-2 rounded border border-zinc-800 font-mono text-xs text-zinc-500 overflow-x-auto",
+                        "{schema}"
+                    }
+                }
+            }
+            if !examples.is_empty() {
+                div {
+                    h4 { class: "text-xs font-bold text-zinc-500 uppercase mb-2", "Examples" }
+                    div { class: "space-y-2",
+                        for args in examples {
+                            div { key: "{args}", class: "relative",
+                                pre { class: "p-2 pr-16 rounded border border-zinc-800 bg-zinc-900 font-mono text-xs text-zinc-300 overflow-x-auto",
+                                    "{format_example(&args)}"
+                                }
+                                button {
+                                    class: "absolute top-2 right-2 px-2 py-0.5 rounded bg-indigo-600 hover:bg-indigo-500 text-[11px] font-bold text-white",
+                                    onclick: {
+                                        let args = args.clone();
+                                        move |_| on_try.call(format_example(&args))
+                                    },
+                                    "Try"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,445 @@
+use crate::models::Content;
+use crate::output_diff::{change_counts, diff_outputs, DiffLine};
+use crate::output_format::{data_url, markdown_to_html, parse_csv, strip_ansi, OutputFormat};
+use crate::state::AppState;
+use crate::storage::format_size;
+use dioxus::prelude::*;
+use serde_json::Value;
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct ToolOutputProps {
+    pub text: String,
+    #[props(default)]
+    pub is_error: bool,
+}
+
+/// Text rendered at once. Longer output is rendered as plain text, a
+/// chunk at a time, so the webview stays responsive.
+const RENDER_CHUNK: usize = 64 * 1024;
+
+/// Tool result text, shown with an auto-detected renderer that can be
+/// switched per result.
+pub fn ToolOutput(props: ToolOutputProps) -> Element {
+    if props.text.len() > RENDER_CHUNK {
+        return rsx! {
+            StreamedText { text: props.text, is_error: props.is_error }
+        };
+    }
+
+    // Choice made for a particular text; a new result falls back to detection
+    let mut chosen = use_signal(|| None::<(String, OutputFormat)>);
+    let format = chosen
+        .read()
+        .as_ref()
+        .filter(|(text, _)| *text == props.text)
+        .map(|(_, f)| *f)
+        .unwrap_or_else(|| OutputFormat::detect(&props.text));
+
+    let frame = if props.is_error {
+        "bg-red-950/30 border-red-900 text-red-300"
+    } else {
+        "bg-green-950/30 border-green-900 text-green-300"
+    };
+
+    rsx! {
+        div { class: "flex gap-1 mb-2",
+            for f in OutputFormat::ALL.into_iter().filter(|f| f.applies_to(&props.text)) {
+                button {
+                    key: "{f.label()}",
+                    class: if f == format { "px-2 py-0.5 rounded text-[11px] font-semibold bg-zinc-700 text-white" } else { "px-2 py-0.5 rounded text-[11px] font-semibold text-zinc-500 hover:text-zinc-300" },
+                    onclick: {
+                        let text = props.text.clone();
+                        move |_| chosen.set(Some((text.clone(), f)))
+                    },
+                    "{f.label()}"
+                }
+            }
+        }
+        div { class: "p-3 rounded border text-sm overflow-x-auto {frame}",
+            match format {
+                OutputFormat::Json => match serde_json::from_str::<Value>(props.text.trim()) {
+                    Ok(value) => rsx! {
+                        div { class: "font-mono text-xs", JsonNode { name: None, value, depth: 0 } }
+                    },
+                    Err(_) => rsx! { div { class: "font-mono whitespace-pre-wrap", "{props.text}" } },
+                },
+                OutputFormat::Markdown => rsx! {
+                    div {
+                        class: "markdown-body text-zinc-200 space-y-2",
+                        dangerous_inner_html: markdown_to_html(&props.text)
+                    }
+                },
+                OutputFormat::Csv => match parse_csv(&props.text) {
+                    Some(rows) => rsx! { CsvTable { rows } },
+                    None => rsx! { div { class: "font-mono whitespace-pre-wrap", "{props.text}" } },
+                },
+                OutputFormat::Plain => rsx! {
+                    div { class: "font-mono whitespace-pre-wrap", "{strip_ansi(&props.text)}" }
+                },
+                OutputFormat::Raw => rsx! {
+                    div { class: "font-mono whitespace-pre-wrap", "{props.text}" }
+                },
+            }
+        }
+    }
+}
+
+/// Long text added to the page a chunk at a time.
+#[component]
+fn StreamedText(text: String, is_error: bool) -> Element {
+    // (length of the text being rendered, bytes of it shown so far); the
+    // length tells a new result apart, which starts over
+    let mut shown = use_signal(|| (0usize, 0usize));
+    let total = use_hook(|| Rc::new(Cell::new(0usize)));
+    total.set(text.len());
+    let target = total.clone();
+    use_future(move || {
+        let target = target.clone();
+        async move {
+            loop {
+                let len = target.get();
+                let (for_len, count) = shown();
+                if for_len != len {
+                    shown.set((len, RENDER_CHUNK));
+                } else if count < len {
+                    shown.set((len, count + RENDER_CHUNK));
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+            }
+        }
+    });
+
+    let (for_len, count) = shown();
+    let count = if for_len == text.len() {
+        count
+    } else {
+        RENDER_CHUNK
+    };
+    // Each chunk is its own node, so a step only adds to the page
+    let mut chunks = Vec::new();
+    let mut end = 0;
+    while end < count.min(text.len()) {
+        let mut next = (end + RENDER_CHUNK).min(text.len());
+        while !text.is_char_boundary(next) {
+            next -= 1;
+        }
+        chunks.push(&text[end..next]);
+        end = next;
+    }
+    let frame = if is_error {
+        "bg-red-950/30 border-red-900 text-red-300"
+    } else {
+        "bg-green-950/30 border-green-900 text-green-300"
+    };
+
+    rsx! {
+        if end < text.len() {
+            p { class: "mb-2 text-[11px] text-zinc-500", "aria-live": "polite",
+                "Rendering {format_size(end as u64)} of {format_size(text.len() as u64)}…"
+            }
+        }
+        div { class: "p-3 rounded border text-sm overflow-x-auto {frame}",
+            div { class: "font-mono whitespace-pre-wrap",
+                for (i, chunk) in chunks.into_iter().enumerate() {
+                    span { key: "{i}", "{chunk}" }
+                }
+            }
+        }
+    }
+}
+
+/// A tool's input schema as a collapsible tree of typed parameters.
+#[component]
+pub fn SchemaView(schema: Value) -> Element {
+    rsx! {
+        div { class: "font-mono text-xs",
+            JsonNode { name: None, value: schema, depth: 0, schema: true }
+        }
+    }
+}
+
+/// Colour of a schema type label.
+fn type_class(type_label: &str) -> &'static str {
+    match type_label.split([' ', '|']).next().unwrap_or_default() {
+        "string" => "text-green-300",
+        "number" | "integer" => "text-amber-300",
+        "boolean" => "text-purple-300",
+        "object" => "text-indigo-300",
+        "array" => "text-cyan-300",
+        "enum" => "text-pink-300",
+        _ => "text-zinc-400",
+    }
+}
+
+/// Collapsible JSON tree. Objects and arrays below the second level
+/// start collapsed. With `schema` set the value is read as a JSON
+/// schema: each node shows its type, whether it is `required` by its
+/// parent and its enum values, and the rest of its keywords as JSON.
+#[component]
+fn JsonNode(
+    name: Option<String>,
+    value: Value,
+    depth: usize,
+    #[props(default)] schema: bool,
+    #[props(default)] required: bool,
+) -> Element {
+    let label = name.map(|n| format!("{}: ", n)).unwrap_or_default();
+    if schema && value.is_object() {
+        let node = crate::tool_docs::schema_node(&value);
+        let has_body =
+            !node.children.is_empty() || !node.extra.is_empty() || !node.choices.is_empty();
+        return rsx! {
+            details { open: depth < 2, class: "ml-3",
+                summary { class: if has_body { "cursor-pointer text-zinc-400" } else { "list-none text-zinc-400" },
+                    span { class: "text-indigo-300", "{label}" }
+                    span { class: type_class(&node.type_label), "{node.type_label}" }
+                    if required {
+                        span { class: "ml-2 px-1 rounded bg-red-900/40 text-red-300 text-[10px] font-bold", "required" }
+                    }
+                    if let Some(description) = node.description {
+                        span { class: "ml-2 text-zinc-500 font-sans", "{description}" }
+                    }
+                }
+                if !node.choices.is_empty() {
+                    div { class: "ml-3 flex flex-wrap gap-1 my-1",
+                        for choice in node.choices {
+                            span { key: "{choice}", class: "px-1.5 rounded bg-pink-900/30 text-pink-300", "{choice}" }
+                        }
+                    }
+                }
+                for (k, v) in node.extra {
+                    JsonNode { key: "{k}", name: Some(k.clone()), value: v, depth: depth + 1 }
+                }
+                for (k, v, required) in node.children {
+                    JsonNode { key: "{k}", name: Some(k.clone()), value: v, depth: depth + 1, schema: true, required }
+                }
+            }
+        };
+    }
+    match value {
+        Value::Object(map) => rsx! {
+            details { open: depth < 2, class: "ml-3",
+                summary { class: "cursor-pointer text-zinc-400",
+                    span { class: "text-indigo-300", "{label}" }
+                    "{{…}} {map.len()} keys"
+                }
+                for (k, v) in map {
+                    JsonNode { key: "{k}", name: Some(k.clone()), value: v, depth: depth + 1 }
+                }
+            }
+        },
+        Value::Array(items) => rsx! {
+            details { open: depth < 2, class: "ml-3",
+                summary { class: "cursor-pointer text-zinc-400",
+                    span { class: "text-indigo-300", "{label}" }
+                    "[…] {items.len()} items"
+                }
+                for (i, v) in items.into_iter().enumerate() {
+                    JsonNode { key: "{i}", name: Some(i.to_string()), value: v, depth: depth + 1 }
+                }
+            }
+        },
+        Value::String(s) => rsx! {
+            div { class: "ml-3",
+                span { class: "text-indigo-300", "{label}" }
+                span { class: "text-green-300", "\"{s}\"" }
+            }
+        },
+        other => rsx! {
+            div { class: "ml-3",
+                span { class: "text-indigo-300", "{label}" }
+                span { class: "text-amber-300", "{other}" }
+            }
+        },
+    }
+}
+
+#[component]
+fn CsvTable(rows: Vec<Vec<String>>) -> Element {
+    let mut rows = rows.into_iter();
+    let header = rows.next().unwrap_or_default();
+    rsx! {
+        table { class: "w-full text-xs text-left text-zinc-300 border-collapse",
+            thead {
+                tr {
+                    for (i, cell) in header.into_iter().enumerate() {
+                        th { key: "{i}", class: "px-2 py-1 border-b border-zinc-700 font-bold text-white", "{cell}" }
+                    }
+                }
+            }
+            tbody {
+                for (r, row) in rows.enumerate() {
+                    tr { key: "{r}", class: "odd:bg-white/5",
+                        for (i, cell) in row.into_iter().enumerate() {
+                            td { key: "{i}", class: "px-2 py-1 border-b border-zinc-800", "{cell}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Line diff from a pinned result to the latest one.
+#[component]
+pub fn ToolOutputDiff(old: String, new: String) -> Element {
+    let lines = diff_outputs(&old, &new);
+    let (removed, added) = change_counts(&lines);
+
+    rsx! {
+        div { class: "rounded border border-zinc-800 bg-zinc-950",
+            div { class: "px-3 py-1.5 border-b border-zinc-800 text-[11px] text-zinc-400",
+                if removed + added == 0 {
+                    "No differences"
+                } else {
+                    span { class: "text-red-400", "−{removed}" }
+                    " "
+                    span { class: "text-green-400", "+{added}" }
+                    " lines"
+                }
+            }
+            div { class: "p-2 font-mono text-xs overflow-x-auto max-h-[40vh] overflow-y-auto",
+                for (i, line) in lines.into_iter().enumerate() {
+                    match line {
+                        DiffLine::Same(text) => rsx! {
+                            div { key: "{i}", class: "whitespace-pre text-zinc-500", "  {text}" }
+                        },
+                        DiffLine::Removed(text) => rsx! {
+                            div { key: "{i}", class: "whitespace-pre bg-red-500/10 text-red-300", "- {text}" }
+                        },
+                        DiffLine::Added(text) => rsx! {
+                            div { key: "{i}", class: "whitespace-pre bg-green-500/10 text-green-300", "+ {text}" }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+const ZOOM_STEPS: [u32; 6] = [25, 50, 100, 150, 200, 400];
+/// Index of 100% in `ZOOM_STEPS`, where zooming from "fit" starts.
+const ACTUAL_SIZE_STEP: usize = 2;
+
+/// An image content block, with zoom and save-as.
+#[component]
+pub fn ToolImage(content: Content, file_stem: String) -> Element {
+    // None = fit to the panel width
+    let mut zoom = use_signal(|| None::<u32>);
+    let mime = content
+        .mimeType
+        .clone()
+        .unwrap_or_else(|| "image/png".to_string());
+    let src = data_url(&mime, content.data.as_deref().unwrap_or(""));
+    let step = zoom().and_then(|z| ZOOM_STEPS.iter().position(|&s| s == z));
+
+    rsx! {
+        div { class: "rounded border border-zinc-800 bg-zinc-900/50",
+            div { class: "flex items-center gap-1 px-2 py-1 border-b border-zinc-800 text-[11px] text-zinc-400",
+                span { class: "font-mono mr-auto", "{mime}" }
+                button {
+                    class: "px-2 py-0.5 rounded hover:bg-zinc-800 disabled:opacity-30",
+                    disabled: step == Some(0),
+                    onclick: move |_| {
+                        let i = step.unwrap_or(ACTUAL_SIZE_STEP);
+                        zoom.set(Some(ZOOM_STEPS[i.saturating_sub(1)]));
+                    },
+                    "−"
+                }
+                button {
+                    class: "px-2 py-0.5 rounded hover:bg-zinc-800 font-mono",
+                    onclick: move |_| zoom.set(None),
+                    if let Some(z) = zoom() { "{z}%" } else { "Fit" }
+                }
+                button {
+                    class: "px-2 py-0.5 rounded hover:bg-zinc-800 disabled:opacity-30",
+                    disabled: step == Some(ZOOM_STEPS.len() - 1),
+                    onclick: move |_| {
+                        let i = step.unwrap_or(ACTUAL_SIZE_STEP);
+                        zoom.set(Some(ZOOM_STEPS[(i + 1).min(ZOOM_STEPS.len() - 1)]));
+                    },
+                    "+"
+                }
+                button {
+                    class: "ml-2 px-2 py-0.5 rounded hover:bg-zinc-800 hover:text-white",
+                    onclick: move |_| {
+                        let content = content.clone();
+                        let file_stem = file_stem.clone();
+                        spawn(async move { AppState::save_content_as(content, &file_stem).await });
+                    },
+                    "💾 Save as"
+                }
+            }
+            div { class: "overflow-auto max-h-[60vh] p-2",
+                img {
+                    src: "{src}",
+                    class: if zoom().is_none() { "max-w-full cursor-zoom-in" } else { "max-w-none cursor-zoom-out" },
+                    style: zoom().map(|z| format!("width: {}%", z)).unwrap_or_default(),
+                    onclick: move |_| zoom.set(if zoom().is_none() { Some(100) } else { None }),
+                }
+            }
+        }
+    }
+}
+
+/// Script that turns the base64 payload it receives into a blob URL and
+/// points the audio element at it, releasing any previous URL.
+const AUDIO_BLOB_JS: &str = r#"
+    const [id, mime, b64] = await dioxus.recv();
+    const el = document.getElementById(id);
+    if (!el) return;
+    const bytes = Uint8Array.from(atob(b64), c => c.charCodeAt(0));
+    if (el.dataset.blobUrl) URL.revokeObjectURL(el.dataset.blobUrl);
+    el.dataset.blobUrl = URL.createObjectURL(new Blob([bytes], { type: mime }));
+    el.src = el.dataset.blobUrl;
+"#;
+
+/// An audio content block: a player fed from a blob URL, plus save-as.
+#[component]
+pub fn ToolAudio(content: Content, file_stem: String) -> Element {
+    let element_id = use_hook(|| format!("tool-audio-{}", uuid::Uuid::new_v4()));
+    let mime = content
+        .mimeType
+        .clone()
+        .unwrap_or_else(|| "audio/wav".to_string());
+    let data: String = content
+        .data
+        .as_deref()
+        .unwrap_or("")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+
+    rsx! {
+        div { class: "rounded border border-zinc-800 bg-zinc-900/50",
+            div { class: "flex items-center gap-1 px-2 py-1 border-b border-zinc-800 text-[11px] text-zinc-400",
+                span { class: "font-mono mr-auto", "🔊 {mime}" }
+                button {
+                    class: "px-2 py-0.5 rounded hover:bg-zinc-800 hover:text-white",
+                    onclick: move |_| {
+                        let content = content.clone();
+                        let file_stem = file_stem.clone();
+                        spawn(async move { AppState::save_content_as(content, &file_stem).await });
+                    },
+                    "💾 Save as"
+                }
+            }
+            div { class: "p-2",
+                audio {
+                    id: "{element_id}",
+                    class: "w-full",
+                    controls: true,
+                    onmounted: {
+                        let element_id = element_id.clone();
+                        move |_| {
+                            let eval = document::eval(AUDIO_BLOB_JS);
+                            let _ = eval.send((element_id.clone(), mime.clone(), data.clone()));
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
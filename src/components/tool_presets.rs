@@ -0,0 +1,146 @@
+use crate::models::ToolPreset;
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct ToolPresetsProps {
+    pub server_id: String,
+    pub tool_name: String,
+    /// Arguments as typed in the editor, saved as a new preset.
+    pub args: String,
+    /// A preset was picked; receives its arguments.
+    pub on_apply: EventHandler<String>,
+}
+
+/// Dropdown of named argument presets for a tool, with save and delete.
+pub fn ToolPresets(props: ToolPresetsProps) -> Element {
+    let (server_id, tool_name) = (props.server_id.clone(), props.tool_name.clone());
+    let load = move || AppState::get_tool_presets(&server_id, &tool_name).unwrap_or_default();
+    let mut presets = use_signal(load.clone());
+    let mut selected = use_signal(|| None::<String>);
+    let mut new_name = use_signal(|| None::<String>);
+    let mut error = use_signal(|| None::<String>);
+
+    let reload = load.clone();
+    let save = {
+        let props = props.clone();
+        move || {
+            let name = new_name().unwrap_or_default().trim().to_string();
+            if name.is_empty() {
+                error.set(Some("Give the preset a name".to_string()));
+                return;
+            }
+            if let Err(e) = serde_json::from_str::<serde_json::Value>(&props.args) {
+                error.set(Some(format!("Arguments are not valid JSON: {}", e)));
+                return;
+            }
+            let preset = ToolPreset {
+                server_id: props.server_id.clone(),
+                tool: props.tool_name.clone(),
+                name: name.clone(),
+                args: props.args.clone(),
+            };
+            match AppState::save_tool_preset(preset) {
+                Ok(()) => {
+                    presets.set(reload());
+                    selected.set(Some(name));
+                    new_name.set(None);
+                    error.set(None);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        }
+    };
+
+    let reload = load.clone();
+    let delete = {
+        let props = props.clone();
+        move |_| {
+            let Some(name) = selected() else {
+                return;
+            };
+            match AppState::delete_tool_preset(&props.server_id, &props.tool_name, &name) {
+                Ok(()) => {
+                    presets.set(reload());
+                    selected.set(None);
+                    error.set(None);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "mb-3",
+            div { class: "flex items-center gap-2 text-xs",
+                select {
+                    class: "flex-1 bg-zinc-950 border border-zinc-700 rounded px-2 py-1 text-zinc-300 focus:outline-none focus:border-indigo-500",
+                    disabled: presets.read().is_empty(),
+                    onchange: move |evt| {
+                        let name = evt.value();
+                        if let Some(preset) = presets.read().iter().find(|p| p.name == name) {
+                            props.on_apply.call(preset.args.clone());
+                        }
+                        selected.set(Some(name).filter(|n| !n.is_empty()));
+                    },
+                    option { value: "", selected: selected().is_none(),
+                        if presets.read().is_empty() { "No presets saved" } else { "Presets…" }
+                    }
+                    for preset in presets() {
+                        option {
+                            key: "{preset.name}",
+                            value: "{preset.name}",
+                            selected: selected().as_deref() == Some(preset.name.as_str()),
+                            "{preset.name}"
+                        }
+                    }
+                }
+                if selected().is_some() {
+                    button {
+                        class: "px-2 py-1 rounded text-red-400 hover:bg-red-500/10",
+                        title: "Delete this preset",
+                        onclick: delete,
+                        "Delete"
+                    }
+                }
+                button {
+                    class: "px-2 py-1 rounded bg-zinc-800 hover:bg-zinc-700 text-zinc-300",
+                    onclick: move |_| {
+                        error.set(None);
+                        new_name.set(if new_name().is_some() { None } else { selected().or(Some(String::new())) });
+                    },
+                    "Save as preset"
+                }
+            }
+            if let Some(name) = new_name() {
+                div { class: "flex items-center gap-2 mt-2 text-xs",
+                    input {
+                        class: "flex-1 bg-zinc-950 border border-zinc-700 rounded px-2 py-1 text-white focus:outline-none focus:border-indigo-500",
+                        placeholder: "Preset name, e.g. prod database",
+                        value: "{name}",
+                        oninput: move |evt| new_name.set(Some(evt.value())),
+                        onkeydown: {
+                            let mut save = save.clone();
+                            move |evt: KeyboardEvent| {
+                                if evt.key() == Key::Enter {
+                                    save();
+                                }
+                            }
+                        }
+                    }
+                    button {
+                        class: "px-2 py-1 rounded bg-indigo-600 hover:bg-indigo-500 text-white font-bold",
+                        onclick: {
+                            let mut save = save.clone();
+                            move |_| save()
+                        },
+                        "Save"
+                    }
+                }
+            }
+            if let Some(e) = error() {
+                p { class: "mt-1 text-xs text-red-400", "{e}" }
+            }
+        }
+    }
+}
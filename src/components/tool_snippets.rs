@@ -0,0 +1,63 @@
+use crate::bridge::DEFAULT_HUB_URL;
+use crate::snippets::{generate, SnippetKind};
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct ToolSnippetsProps {
+    pub tool_name: String,
+    /// Arguments as typed in the editor (may not be valid JSON yet).
+    pub args: String,
+}
+
+/// "Copy as" buttons for turning the current tool call into code.
+pub fn ToolSnippets(props: ToolSnippetsProps) -> Element {
+    let mut kind = use_signal(|| None::<SnippetKind>);
+    let mut copied = use_signal(|| false);
+
+    let snippet = kind().map(|k| {
+        serde_json::from_str::<serde_json::Value>(&props.args)
+            .map(|args| generate(k, &props.tool_name, &args, DEFAULT_HUB_URL))
+            .map_err(|e| format!("Arguments are not valid JSON: {}", e))
+    });
+
+    rsx! {
+        div { class: "mt-3",
+            div { class: "flex items-center gap-1 text-[11px]",
+                span { class: "font-bold text-zinc-400 uppercase mr-1", "Copy as" }
+                for k in SnippetKind::ALL {
+                    button {
+                        key: "{k.label()}",
+                        class: if kind() == Some(k) { "px-2 py-0.5 rounded font-semibold bg-zinc-700 text-white" } else { "px-2 py-0.5 rounded font-semibold text-zinc-500 hover:text-zinc-300" },
+                        onclick: move |_| {
+                            copied.set(false);
+                            kind.set(if kind() == Some(k) { None } else { Some(k) });
+                        },
+                        "{k.label()}"
+                    }
+                }
+            }
+            match snippet {
+                Some(Ok(code)) => rsx! {
+                    div { class: "relative mt-2",
+                        pre { class: "p-3 pr-20 rounded border border-zinc-800 bg-zinc-900 text-xs text-zinc-300 font-mono overflow-x-auto",
+                            "{code}"
+                        }
+                        button {
+                            class: "absolute top-2 right-2 px-2 py-1 rounded bg-zinc-800 hover:bg-zinc-700 text-[11px] text-white",
+                            onclick: move |_| {
+                                AppState::copy_to_clipboard(code.clone());
+                                copied.set(true);
+                            },
+                            if copied() { "✓ Copied" } else { "📋 Copy" }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "mt-2 text-xs text-red-400", "{e}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
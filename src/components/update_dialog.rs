@@ -0,0 +1,96 @@
+use crate::state::{AppState, APP_STATE};
+use crate::updater::{pick_asset, CURRENT_VERSION};
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct UpdateDialogProps {
+    pub on_close: EventHandler<()>,
+}
+
+/// Changelog and install prompt for a newer release of the manager.
+pub fn UpdateDialog(props: UpdateDialogProps) -> Element {
+    let mut installing = use_signal(|| false);
+    let Some(release) = APP_STATE.read().available_update.cloned() else {
+        return rsx! {};
+    };
+
+    let title = release
+        .name
+        .clone()
+        .unwrap_or_else(|| release.tag_name.clone());
+    let changelog = release
+        .body
+        .clone()
+        .unwrap_or_else(|| "No changelog provided.".to_string());
+    let installable = pick_asset(
+        &release.assets,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    )
+    .is_some();
+    let release_url = release.html_url.clone();
+
+    let install = move |_| {
+        let release = release.clone();
+        installing.set(true);
+        spawn(async move {
+            if let Err(e) = AppState::install_update(release).await {
+                AppState::push_notification(
+                    format!("Update failed: {}", e),
+                    crate::models::NotificationLevel::Error,
+                );
+            }
+            installing.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "fixed inset-0 z-50 flex items-center justify-center bg-black/60 p-4 backdrop-blur-md",
+            div { class: "w-full max-w-xl bg-zinc-950 text-zinc-300 rounded-2xl flex flex-col overflow-hidden border border-zinc-800 shadow-2xl animate-scale-in",
+                div { class: "flex justify-between items-center p-5 bg-zinc-900 border-b border-zinc-800",
+                    div {
+                        h2 { class: "font-bold text-xl text-white", "Update available" }
+                        p { class: "text-xs text-zinc-500 mt-1", "{title} · you have {CURRENT_VERSION}" }
+                    }
+                    button {
+                        class: "p-2 hover:bg-zinc-800 rounded-full transition-colors",
+                        onclick: move |_| props.on_close.call(()),
+                        "✕"
+                    }
+                }
+
+                div { class: "p-6 max-h-[50vh] overflow-y-auto custom-scrollbar",
+                    h3 { class: "text-xs font-bold uppercase text-zinc-500 mb-2", "What's new" }
+                    pre { class: "whitespace-pre-wrap break-words font-sans text-sm text-zinc-300 leading-relaxed",
+                        "{changelog}"
+                    }
+                }
+
+                div { class: "p-5 bg-zinc-900 border-t border-zinc-800 flex justify-end gap-3",
+                    button {
+                        class: "px-4 py-2.5 text-zinc-400 hover:text-white transition-colors mr-auto",
+                        onclick: move |_| {
+                            if let Err(e) = open::that_detached(&release_url) {
+                                tracing::warn!("Failed to open release page: {}", e);
+                            }
+                        },
+                        "View release"
+                    }
+                    button {
+                        class: "px-5 py-2.5 text-zinc-400 hover:text-white transition-colors",
+                        onclick: move |_| props.on_close.call(()),
+                        "Later"
+                    }
+                    if installable {
+                        button {
+                            class: "px-6 py-2.5 bg-indigo-600 hover:bg-indigo-500 disabled:opacity-50 text-white rounded-xl text-sm font-bold transition-colors shadow-lg shadow-indigo-500/20",
+                            disabled: installing(),
+                            onclick: install,
+                            if installing() { "Downloading..." } else { "Download & install" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::state::AppState;
+use crate::usage_export::{DateRange, UsageTable, EXPORT_SUBCOMMAND};
+use chrono::NaiveDate;
+use dioxus::prelude::*;
+
+/// Exports the tool call log and server run times for a range of days as
+/// CSV.
+pub fn UsageExport() -> Element {
+    let mut range = use_signal(|| DateRange::last_days(chrono::Utc::now().date_naive(), 7));
+    let mut error = use_signal(|| None::<String>);
+    let mut busy = use_signal(|| false);
+
+    let parse = |value: String| NaiveDate::parse_from_str(&value, "%Y-%m-%d").ok();
+    let mut export = move |table: UsageTable| {
+        error.set(None);
+        busy.set(true);
+        spawn(async move {
+            if let Err(e) = AppState::export_usage(table, range()).await {
+                error.set(Some(e));
+            }
+            busy.set(false);
+        });
+    };
+    let valid = range().from <= range().to;
+
+    rsx! {
+        div { class: "max-w-3xl mx-auto space-y-6",
+            div {
+                h2 { class: "text-2xl font-bold text-white tracking-tight", "Usage data" }
+                p { class: "text-sm text-zinc-500 mt-1",
+                    "Export routed tool calls and server run times as CSV to analyze them in your own tools. The last four weeks are kept."
+                }
+            }
+
+            section { class: "p-5 rounded-2xl glass space-y-3",
+                div { class: "flex items-center gap-3 text-sm",
+                    label { class: "flex items-center gap-2 text-zinc-400",
+                        "From"
+                        input {
+                            r#type: "date",
+                            class: "px-2 py-1 bg-zinc-900 border border-zinc-700 rounded-lg text-white focus:outline-none focus:border-indigo-500",
+                            value: "{range().from}",
+                            onchange: move |evt| {
+                                if let Some(day) = parse(evt.value()) {
+                                    range.write().from = day;
+                                }
+                            }
+                        }
+                    }
+                    label { class: "flex items-center gap-2 text-zinc-400",
+                        "To"
+                        input {
+                            r#type: "date",
+                            class: "px-2 py-1 bg-zinc-900 border border-zinc-700 rounded-lg text-white focus:outline-none focus:border-indigo-500",
+                            value: "{range().to}",
+                            onchange: move |evt| {
+                                if let Some(day) = parse(evt.value()) {
+                                    range.write().to = day;
+                                }
+                            }
+                        }
+                    }
+                    span { class: "text-xs text-zinc-500", "UTC" }
+                }
+                div { class: "flex gap-2",
+                    for table in UsageTable::ALL {
+                        button {
+                            key: "{table.name()}",
+                            class: "px-3 py-1.5 bg-zinc-800 hover:bg-zinc-700 text-white rounded-lg text-xs font-bold transition-colors disabled:opacity-50",
+                            disabled: busy() || !valid,
+                            onclick: move |_| export(table),
+                            "{table.label()} CSV"
+                        }
+                    }
+                }
+                if !valid {
+                    p { class: "text-xs text-red-400", "role": "alert", "The start date is after the end date." }
+                }
+                if let Some(e) = error() {
+                    p { class: "text-xs text-red-400", "role": "alert", "{e}" }
+                }
+                p { class: "text-xs text-zinc-500",
+                    "From a terminal: "
+                    code { class: "font-mono text-zinc-400",
+                        "open-mcp-manager {EXPORT_SUBCOMMAND} --table server_runs --from 2026-01-01 --output runs.csv"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::registry_digest::RegistryDigest;
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+/// `2026-01-12T09:00:00+00:00` -> `2026-01-12`.
+fn short_date(rfc3339: &str) -> &str {
+    rfc3339.split('T').next().unwrap_or(rfc3339)
+}
+
+/// Weekly registry digest: servers that appeared, disappeared or had a
+/// major version bump between the last two snapshots.
+pub fn WhatsNew() -> Element {
+    let digest = APP_STATE.read().registry_digest.cloned();
+    let notify = *APP_STATE.read().digest_notifications_enabled.read();
+
+    rsx! {
+        div { class: "max-w-4xl mx-auto space-y-6",
+            div { class: "flex items-end justify-between",
+                div {
+                    h2 { class: "text-2xl font-bold text-white tracking-tight", "What's new this week" }
+                    if let Some(d) = digest.as_ref() {
+                        p { class: "text-sm text-zinc-500 mt-1",
+                            "Registry changes from {short_date(&d.since)} to {short_date(&d.until)}"
+                        }
+                    }
+                }
+                label { class: "flex items-center gap-2 text-xs text-zinc-400",
+                    input {
+                        r#type: "checkbox",
+                        checked: notify,
+                        onchange: move |evt| AppState::set_digest_notifications_enabled(evt.checked())
+                    }
+                    "Notify me on Mondays"
+                }
+            }
+
+            match digest {
+                None => rsx! {
+                    div { class: "p-8 rounded-2xl glass text-center text-zinc-500 text-sm",
+                        "A snapshot of the registry is taken weekly when you browse it. "
+                        "The digest appears once there are two to compare."
+                    }
+                },
+                Some(d) if d.is_empty() => rsx! {
+                    div { class: "p-8 rounded-2xl glass text-center text-zinc-500 text-sm",
+                        "No registry changes since the last snapshot."
+                    }
+                },
+                Some(d) => rsx! { DigestSections { digest: d } },
+            }
+        }
+    }
+}
+
+#[component]
+fn DigestSections(digest: RegistryDigest) -> Element {
+    rsx! {
+        div { class: "grid gap-4 md:grid-cols-3",
+            section { class: "p-5 rounded-2xl glass space-y-3",
+                h3 { class: "text-sm font-bold text-green-400", "✨ New ({digest.added.len()})" }
+                ul { class: "space-y-1.5 text-sm",
+                    for e in digest.added.iter() {
+                        li { key: "{e.name}", class: "flex justify-between gap-2",
+                            span { class: "text-zinc-200 truncate", "{e.name}" }
+                            span { class: "text-[10px] uppercase text-zinc-600", "{e.source}" }
+                        }
+                    }
+                }
+            }
+            section { class: "p-5 rounded-2xl glass space-y-3",
+                h3 { class: "text-sm font-bold text-amber-400", "⬆️ Major updates ({digest.bumped.len()})" }
+                ul { class: "space-y-1.5 text-sm",
+                    for b in digest.bumped.iter() {
+                        li { key: "{b.name}", class: "flex justify-between gap-2",
+                            span { class: "text-zinc-200 truncate", "{b.name}" }
+                            span { class: "font-mono text-xs text-zinc-500", "{b.from} → {b.to}" }
+                        }
+                    }
+                }
+            }
+            section { class: "p-5 rounded-2xl glass space-y-3",
+                h3 { class: "text-sm font-bold text-red-400", "🗑️ Removed ({digest.removed.len()})" }
+                ul { class: "space-y-1.5 text-sm",
+                    for e in digest.removed.iter() {
+                        li { key: "{e.name}", class: "text-zinc-500 line-through truncate", "{e.name}" }
+                    }
+                }
+            }
+        }
+    }
+}
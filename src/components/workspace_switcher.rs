@@ -0,0 +1,96 @@
+use crate::models::NotificationLevel;
+use crate::state::{AppState, APP_STATE};
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct WorkspaceSwitcherProps {
+    /// Called before switching, so open dialogs can be closed.
+    pub on_switch: EventHandler<()>,
+}
+
+/// Header dropdown for choosing or creating a workspace.
+pub fn WorkspaceSwitcher(props: WorkspaceSwitcherProps) -> Element {
+    let mut is_open = use_signal(|| false);
+    let mut new_name = use_signal(String::new);
+
+    let current = APP_STATE.read().workspace.cloned();
+    let workspaces = APP_STATE.read().workspaces.cloned();
+
+    let mut switch_to = move |name: String| {
+        is_open.set(false);
+        props.on_switch.call(());
+        spawn(async move {
+            if let Err(e) = AppState::switch_workspace(name).await {
+                AppState::push_notification(
+                    format!("Could not switch workspace: {}", e),
+                    NotificationLevel::Error,
+                );
+            }
+        });
+    };
+
+    let mut create = move || {
+        let name = new_name();
+        if name.trim().is_empty() {
+            return;
+        }
+        new_name.set(String::new());
+        switch_to(name);
+    };
+
+    rsx! {
+        div { class: "relative",
+            button {
+                class: "flex items-center gap-2 px-3 py-1.5 rounded-lg text-xs font-semibold text-zinc-400 bg-white-5 hover:text-white hover:bg-white-8 transition-all border border-white-5",
+                title: "Switch workspace",
+                onclick: move |_| is_open.set(!is_open()),
+                "🗂️ {current}"
+                span { class: "text-zinc-600", "▾" }
+            }
+
+            if is_open() {
+                div { class: "absolute left-0 mt-2 w-64 bg-zinc-950 border border-zinc-800 rounded-xl shadow-2xl z-50 overflow-hidden animate-scale-in",
+                    div { class: "px-4 py-3 bg-zinc-900 border-b border-zinc-800",
+                        h3 { class: "text-sm font-bold text-white", "Workspaces" }
+                        p { class: "text-[11px] text-zinc-500", "Each workspace has its own servers and secrets." }
+                    }
+                    div { class: "max-h-64 overflow-y-auto custom-scrollbar py-1",
+                        for name in workspaces {
+                            button {
+                                key: "{name}",
+                                class: if name == current { "w-full flex items-center justify-between px-4 py-2 text-sm text-white bg-zinc-900" } else { "w-full flex items-center justify-between px-4 py-2 text-sm text-zinc-400 hover:text-white hover:bg-zinc-900" },
+                                onclick: {
+                                    let name = name.clone();
+                                    move |_| switch_to(name.clone())
+                                },
+                                "{name}"
+                                if name == current {
+                                    span { class: "text-indigo-400", "✓" }
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex gap-2 p-3 border-t border-zinc-800",
+                        input {
+                            class: "flex-1 min-w-0 bg-black border border-zinc-800 rounded-lg px-3 py-1.5 text-sm text-white focus:outline-none focus:border-indigo-500",
+                            placeholder: "New workspace",
+                            value: "{new_name}",
+                            oninput: move |evt| new_name.set(evt.value()),
+                            onkeydown: move |evt| {
+                                if evt.key() == Key::Enter {
+                                    create();
+                                }
+                            }
+                        }
+                        button {
+                            class: "px-3 py-1.5 bg-indigo-600 hover:bg-indigo-500 text-white rounded-lg text-xs font-bold transition-colors disabled:opacity-40",
+                            disabled: new_name().trim().is_empty(),
+                            onclick: move |_| create(),
+                            "Create"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,286 @@
+//! Editor config files generated from the manager's servers, as the
+//! config viewer previews, copies and downloads them.
+//!
+//! Hub Mode points the editor at the hub alone; Direct Mode lists every
+//! active server. Output is deterministic (keys sorted) so it can be
+//! compared byte for byte.
+
+use crate::bridge::DEFAULT_HUB_URL;
+use crate::editor_config::{direct_entry, hub_bridge_entry, HUB_ENTRY_NAME};
+use crate::models::McpServer;
+use serde_json::{json, Map, Value};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ConfigMode {
+    Hub,
+    Direct,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TargetEditor {
+    Claude,
+    Cursor,
+    Windsurf,
+    OpenCode,
+    Antigravity,
+}
+
+impl TargetEditor {
+    pub const ALL: [TargetEditor; 5] = [
+        TargetEditor::Claude,
+        TargetEditor::Cursor,
+        TargetEditor::Windsurf,
+        TargetEditor::OpenCode,
+        TargetEditor::Antigravity,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TargetEditor::Claude => "Claude",
+            TargetEditor::Cursor => "Cursor",
+            TargetEditor::Windsurf => "Windsurf",
+            TargetEditor::OpenCode => "OpenCode",
+            TargetEditor::Antigravity => "Antigravity",
+        }
+    }
+
+    pub fn macos_path(&self) -> &'static str {
+        match self {
+            TargetEditor::Claude => {
+                "~/Library/Application Support/Claude/claude_desktop_config.json"
+            }
+            TargetEditor::Cursor => "~/.cursor/mcp.json",
+            TargetEditor::Windsurf => "~/.codeium/windsurf/mcp_config.json",
+            TargetEditor::OpenCode => "opencode.jsonc (Project Root)",
+            TargetEditor::Antigravity => "~/.gemini/antigravity/mcp_config.json",
+        }
+    }
+
+    pub fn windows_path(&self) -> &'static str {
+        match self {
+            TargetEditor::Claude => "%APPDATA%\\Claude\\claude_desktop_config.json",
+            TargetEditor::Cursor => {
+                "%APPDATA%\\Cursor\\mcp.json or %USERPROFILE%\\.cursor\\mcp.json"
+            }
+            TargetEditor::Windsurf => "%USERPROFILE%\\.codeium\\windsurf\\mcp_config.json",
+            TargetEditor::OpenCode => "opencode.jsonc (Project Root)",
+            TargetEditor::Antigravity => "%USERPROFILE%\\.gemini\\antigravity\\mcp_config.json",
+        }
+    }
+
+    /// Whether the editor can connect to a URL (SSE) server directly.
+    /// Stdio-only clients are pointed at the bridge command instead.
+    pub fn supports_remote(&self) -> bool {
+        !matches!(self, TargetEditor::Claude)
+    }
+
+    pub fn download_filename(&self) -> &'static str {
+        match self {
+            TargetEditor::Claude => "claude_desktop_config.json",
+            TargetEditor::Cursor => "mcp.json",
+            TargetEditor::Windsurf => "mcp_config.json",
+            TargetEditor::OpenCode => "opencode.jsonc",
+            TargetEditor::Antigravity => "mcp_config.json",
+        }
+    }
+}
+
+/// Hub Mode entry for an editor: a URL for SSE-capable clients, or a
+/// command launching the stdio bridge for stdio-only clients.
+pub fn hub_entry(editor: TargetEditor, command: &str) -> Value {
+    if editor.supports_remote() {
+        json!({ "url": DEFAULT_HUB_URL })
+    } else {
+        hub_bridge_entry(command)
+    }
+}
+
+pub fn hub_config(editor: TargetEditor, command: &str) -> Value {
+    let mut servers = Map::new();
+    servers.insert(HUB_ENTRY_NAME.to_string(), hub_entry(editor, command));
+    json!({ "mcpServers": servers })
+}
+
+/// Direct Mode entries, one per active server. Temporary servers are
+/// experiments, not part of the setup, and archived ones are left out
+/// unless asked for.
+pub fn direct_servers(servers: &[McpServer], include_archived: bool) -> Map<String, Value> {
+    servers
+        .iter()
+        .filter(|s| s.is_active && s.expires_at.is_none())
+        .filter(|s| include_archived || !s.is_archived)
+        .map(|server| (server.name.clone(), direct_entry(server)))
+        .collect()
+}
+
+/// The config file for `editor` in `mode`. `command` launches the bridge
+/// for editors that can't reach the hub by URL.
+pub fn generate(
+    editor: TargetEditor,
+    mode: ConfigMode,
+    servers: &[McpServer],
+    include_archived: bool,
+    command: &str,
+) -> Value {
+    match mode {
+        ConfigMode::Hub => hub_config(editor, command),
+        ConfigMode::Direct => json!({ "mcpServers": direct_servers(servers, include_archived) }),
+    }
+}
+
+/// [`generate`], pretty-printed as the file is written.
+pub fn render(
+    editor: TargetEditor,
+    mode: ConfigMode,
+    servers: &[McpServer],
+    include_archived: bool,
+    command: &str,
+) -> String {
+    serde_json::to_string_pretty(&generate(editor, mode, servers, include_archived, command))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BRIDGE: &str = "/usr/bin/open-mcp-manager";
+
+    fn direct(servers: &[McpServer], include_archived: bool) -> String {
+        render(
+            TargetEditor::Cursor,
+            ConfigMode::Direct,
+            servers,
+            include_archived,
+            BRIDGE,
+        )
+    }
+
+    #[test]
+    fn test_hub_config_uses_bridge_for_stdio_only_editors() {
+        assert_eq!(
+            render(TargetEditor::Claude, ConfigMode::Hub, &[], false, BRIDGE),
+            r#"{
+  "mcpServers": {
+    "mcp-manager-hub": {
+      "args": [
+        "bridge"
+      ],
+      "command": "/usr/bin/open-mcp-manager"
+    }
+  }
+}"#
+        );
+    }
+
+    #[test]
+    fn test_hub_config_uses_url_for_remote_editors() {
+        let expected = r#"{
+  "mcpServers": {
+    "mcp-manager-hub": {
+      "url": "http://localhost:3000/api/mcp/sse"
+    }
+  }
+}"#;
+        for editor in TargetEditor::ALL
+            .into_iter()
+            .filter(|e| e.supports_remote())
+        {
+            // The hub entry doesn't depend on the servers
+            let config = render(
+                editor,
+                ConfigMode::Hub,
+                &[McpServer::test_stdio("a", "npx")],
+                false,
+                "unused",
+            );
+            assert_eq!(config, expected, "{}", editor.name());
+        }
+    }
+
+    #[test]
+    fn test_direct_config_includes_env_and_remote_servers() {
+        let github = McpServer::test_stdio("github", "npx")
+            .with_args(&["-y", "@mcp/github"])
+            .with_env(&[
+                ("GITHUB_TOKEN", "ghp_x"),
+                ("API_BASE", "https://api.github.com"),
+            ]);
+        let empty_env = McpServer::test_stdio("files", "npx").with_env(&[]);
+        let mut remote = McpServer::test_stdio("search", "npx");
+        remote.server_type = "sse".to_string();
+        remote.command = None;
+        remote.args = None;
+        remote.url = Some("https://search.example.com/sse".to_string());
+
+        assert_eq!(
+            direct(&[github, empty_env, remote], false),
+            r#"{
+  "mcpServers": {
+    "files": {
+      "command": "npx"
+    },
+    "github": {
+      "args": [
+        "-y",
+        "@mcp/github"
+      ],
+      "command": "npx",
+      "env": {
+        "API_BASE": "https://api.github.com",
+        "GITHUB_TOKEN": "ghp_x"
+      }
+    },
+    "search": {
+      "url": "https://search.example.com/sse"
+    }
+  }
+}"#
+        );
+    }
+
+    #[test]
+    fn test_direct_config_lists_active_servers_only() {
+        let mut inactive = McpServer::test_stdio("inactive", "npx");
+        inactive.is_active = false;
+        let mut temporary = McpServer::test_stdio("temporary", "npx");
+        temporary.expires_at = Some("2024-01-02 00:00:00".to_string());
+        let mut archived = McpServer::test_stdio("archived", "npx");
+        archived.is_archived = true;
+        let servers = [
+            McpServer::test_stdio("kept", "npx").with_args(&["-y", "@mcp/kept"]),
+            inactive,
+            temporary,
+            archived,
+        ];
+
+        assert_eq!(
+            direct(&servers, false),
+            r#"{
+  "mcpServers": {
+    "kept": {
+      "args": [
+        "-y",
+        "@mcp/kept"
+      ],
+      "command": "npx"
+    }
+  }
+}"#
+        );
+        let with_archived = direct_servers(&servers, true);
+        assert_eq!(
+            with_archived.keys().collect::<Vec<_>>(),
+            ["archived", "kept"]
+        );
+    }
+
+    #[test]
+    fn test_direct_config_without_servers() {
+        let expected = "{\n  \"mcpServers\": {}\n}";
+        assert_eq!(direct(&[], false), expected);
+        let mut inactive = McpServer::test_stdio("inactive", "npx");
+        inactive.is_active = false;
+        assert_eq!(direct(&[inactive], true), expected);
+    }
+}
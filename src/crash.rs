@@ -0,0 +1,146 @@
+//! Local crash capture: a panic hook that writes a report file, plus a
+//! small in-memory log buffer so reports include what happened just before.
+
+use crate::models::AppResult;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Log lines kept in memory for crash reports.
+const RECENT_LOG_LINES: usize = 200;
+
+/// Marker file naming the newest crash report not yet shown to the user.
+const PENDING_MARKER: &str = "pending";
+
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Log writer that forwards to stderr and remembers recent lines.
+struct LogTee;
+
+impl Write for LogTee {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Ok(mut logs) = RECENT_LOGS.lock() {
+            for line in String::from_utf8_lossy(buf).lines() {
+                if logs.len() == RECENT_LOG_LINES {
+                    logs.pop_front();
+                }
+                logs.push_back(line.to_string());
+            }
+        }
+        std::io::stderr().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        std::io::stderr().flush()
+    }
+}
+
+/// Set up `tracing` output to stderr, teed into the crash log buffer.
+pub fn init_logging() {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::INFO)
+        .with_ansi(false)
+        .with_writer(|| LogTee)
+        .try_init();
+}
+
+pub fn crash_dir() -> AppResult<PathBuf> {
+    Ok(crate::platform::app_data_dir()?.join("crashes"))
+}
+
+pub fn format_report(timestamp: &str, panic: &str, backtrace: &str, logs: &[String]) -> String {
+    format!(
+        "Open MCP Manager {} crash report\nTime: {}\nPlatform: {} {}\n\n== Panic ==\n{}\n\n== Backtrace ==\n{}\n\n== Recent logs ==\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        timestamp,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        panic,
+        backtrace,
+        logs.join("\n")
+    )
+}
+
+/// Write a report into `dir` and mark it as pending for the next start.
+/// Each report gets its own file, even when panics come at the same time.
+fn write_report(dir: &Path, timestamp: &str, report: &str) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let path = dir.join(format!("crash-{}-{}.log", timestamp, &suffix[..8]));
+    std::fs::write(&path, report)?;
+    std::fs::write(dir.join(PENDING_MARKER), path.to_string_lossy().as_bytes())?;
+    Ok(path)
+}
+
+/// The pending report in `dir`, if any. Clears the marker so it is only
+/// reported once.
+fn take_pending(dir: &Path) -> Option<PathBuf> {
+    let marker = dir.join(PENDING_MARKER);
+    let path = PathBuf::from(std::fs::read_to_string(&marker).ok()?.trim());
+    let _ = std::fs::remove_file(&marker);
+    path.exists().then_some(path)
+}
+
+/// Crash report from a previous run that the user has not seen yet.
+pub fn take_pending_crash() -> Option<PathBuf> {
+    take_pending(&crash_dir().ok()?)
+}
+
+/// Write a crash report for every panic, then run the default hook.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S%.3f").to_string();
+        let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+        // try_lock: the panic may have happened while the buffer was held
+        let logs: Vec<String> = RECENT_LOGS
+            .try_lock()
+            .map(|l| l.iter().cloned().collect())
+            .unwrap_or_default();
+        let report = format_report(&timestamp, &info.to_string(), &backtrace, &logs);
+
+        match crash_dir().map(|dir| write_report(&dir, &timestamp, &report)) {
+            Ok(Ok(path)) => eprintln!("Crash report written to {}", path.display()),
+            Ok(Err(e)) => eprintln!("Failed to write crash report: {}", e),
+            Err(e) => eprintln!("Failed to write crash report: {}", e),
+        }
+        previous(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_report_sections() {
+        let report = format_report(
+            "20260101-120000",
+            "panicked at src/main.rs:1:1: boom",
+            "0: main",
+            &["INFO starting app".to_string()],
+        );
+        assert!(report.contains("Time: 20260101-120000"));
+        assert!(report.contains("== Panic ==\npanicked at src/main.rs:1:1: boom"));
+        assert!(report.contains("== Recent logs ==\nINFO starting app"));
+    }
+
+    #[test]
+    fn test_pending_report_is_taken_once() {
+        let dir = std::env::temp_dir().join(format!("omm-crash-{}", uuid::Uuid::new_v4()));
+        assert!(take_pending(&dir).is_none());
+
+        let path = write_report(&dir, "20260101-120000", "report").unwrap();
+        assert_eq!(take_pending(&dir), Some(path.clone()));
+        assert!(take_pending(&dir).is_none());
+        assert!(path.exists());
+
+        // A second panic in the same instant doesn't overwrite the first
+        let other = write_report(&dir, "20260101-120000", "other").unwrap();
+        assert_ne!(other, path);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "report");
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
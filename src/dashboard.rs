@@ -0,0 +1,153 @@
+//! Home dashboard widgets: aggregate stats shown above the server grid,
+//! and which of them the user has chosen to see.
+
+use crate::models::{Notification, NotificationLevel, ToolCallRecord};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DashboardWidget {
+    TotalServers,
+    Running,
+    HubConnections,
+    CallsToday,
+    RecentErrors,
+}
+
+impl DashboardWidget {
+    /// Every widget, in display order.
+    pub const ALL: [DashboardWidget; 5] = [
+        DashboardWidget::TotalServers,
+        DashboardWidget::Running,
+        DashboardWidget::HubConnections,
+        DashboardWidget::CallsToday,
+        DashboardWidget::RecentErrors,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DashboardWidget::TotalServers => "Servers",
+            DashboardWidget::Running => "Running",
+            DashboardWidget::HubConnections => "Hub connections",
+            DashboardWidget::CallsToday => "Tool calls today",
+            DashboardWidget::RecentErrors => "Errors today",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            DashboardWidget::TotalServers => "🗂️",
+            DashboardWidget::Running => "🟢",
+            DashboardWidget::HubConnections => "🔌",
+            DashboardWidget::CallsToday => "🛠️",
+            DashboardWidget::RecentErrors => "⚠️",
+        }
+    }
+}
+
+/// Widgets shown on the dashboard, in display order.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DashboardSettings {
+    pub widgets: Vec<DashboardWidget>,
+}
+
+impl Default for DashboardSettings {
+    fn default() -> Self {
+        Self {
+            widgets: DashboardWidget::ALL.to_vec(),
+        }
+    }
+}
+
+impl DashboardSettings {
+    pub fn shows(&self, widget: DashboardWidget) -> bool {
+        self.widgets.contains(&widget)
+    }
+
+    /// Show or hide `widget`, keeping the shown ones in display order.
+    pub fn toggle(&mut self, widget: DashboardWidget) {
+        let shown = !self.shows(widget);
+        self.widgets = DashboardWidget::ALL
+            .into_iter()
+            .filter(|w| if *w == widget { shown } else { self.shows(*w) })
+            .collect();
+    }
+}
+
+/// Local midnight starting the day of `now`, for querying today's calls.
+pub fn start_of_day(now: DateTime<Local>) -> DateTime<Utc> {
+    now.date_naive()
+        .and_hms_opt(0, 0, 0)
+        .and_then(|midnight| midnight.and_local_timezone(Local).earliest())
+        .map_or_else(|| now.with_timezone(&Utc), |t| t.with_timezone(&Utc))
+}
+
+/// Calls made and how many of them failed.
+pub fn call_counts(calls: &[ToolCallRecord]) -> (usize, usize) {
+    (calls.len(), calls.iter().filter(|c| !c.ok).count())
+}
+
+/// Error notifications raised on `day`. Notification times are local.
+pub fn errors_on(history: &[Notification], day: NaiveDate) -> usize {
+    let day = day.format("%Y-%m-%d").to_string();
+    history
+        .iter()
+        .filter(|n| n.level == NotificationLevel::Error && n.created_at.starts_with(&day))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_keeps_display_order() {
+        let mut settings = DashboardSettings::default();
+        settings.toggle(DashboardWidget::Running);
+        settings.toggle(DashboardWidget::TotalServers);
+        assert!(!settings.shows(DashboardWidget::Running));
+        settings.toggle(DashboardWidget::Running);
+        assert_eq!(
+            settings.widgets[..2],
+            [DashboardWidget::Running, DashboardWidget::HubConnections]
+        );
+
+        let json = serde_json::to_string(&settings).unwrap();
+        assert!(json.contains("\"hub_connections\""));
+        assert_eq!(
+            serde_json::from_str::<DashboardSettings>(&json).unwrap(),
+            settings
+        );
+    }
+
+    #[test]
+    fn test_counts() {
+        let call = |ok| ToolCallRecord {
+            server_id: "a".to_string(),
+            tool: "t".to_string(),
+            ok,
+            created_at: String::new(),
+            args: None,
+        };
+        assert_eq!(call_counts(&[call(true), call(false), call(true)]), (3, 1));
+
+        let notification = |level, created_at: &str| Notification {
+            id: 0,
+            message: String::new(),
+            level,
+            duration: 0,
+            server_id: None,
+            created_at: created_at.to_string(),
+            read: false,
+            event: Default::default(),
+        };
+        let history = [
+            notification(NotificationLevel::Error, "2026-03-02 09:00:00"),
+            notification(NotificationLevel::Error, "2026-03-01 23:59:59"),
+            notification(NotificationLevel::Warning, "2026-03-02 10:00:00"),
+        ];
+        let day = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        assert_eq!(errors_on(&history, day), 1);
+    }
+}
@@ -1,36 +1,114 @@
+use crate::dedupe::MergePlan;
 use crate::models::{
-    AppError, AppResult, CreateServerArgs, McpServer, RegistryInstallConfig, RegistryItem,
-    RegistryServer, ResearchNote, UpdateServerArgs,
+    AppError, AppResult, AuditEntry, CreateServerArgs, McpServer, RegistryInstallConfig,
+    RegistryItem, RegistryServer, ResearchNote, RuleAction, ServerRevision, ServerRun,
+    ToolCallRecord, ToolPreset, ToolRule, UpdateServerArgs,
 };
+use crate::registry_digest::RegistrySnapshot;
 use rusqlite::{params, Connection};
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
 use uuid::Uuid;
 
+/// The registry embedded in the binary and what was wrong with it, parsed
+/// once on first use.
+fn embedded_registry() -> &'static (Vec<RegistryItem>, Vec<String>) {
+    static OFFICIAL: OnceLock<(Vec<RegistryItem>, Vec<String>)> = OnceLock::new();
+    OFFICIAL.get_or_init(|| {
+        let parsed = crate::registry_validation::parse_registry(include_str!("../registry.json"));
+        for problem in &parsed.1 {
+            tracing::error!("Embedded registry.json: {}", problem);
+        }
+        parsed
+    })
+}
+
+/// The valid entries of the registry embedded in the binary.
+pub fn official_registry() -> &'static [RegistryItem] {
+    &embedded_registry().0
+}
+
+/// Problems with the embedded registry; entries with one are left out.
+pub fn official_registry_problems() -> &'static [String] {
+    &embedded_registry().1
+}
+
+/// Revisions kept per server; older ones are pruned on update.
+pub const SERVER_REVISIONS_KEPT: usize = 50;
+
+fn server_from_row(row: &rusqlite::Row) -> rusqlite::Result<McpServer> {
+    let args_str: Option<String> = row.get(4).ok();
+    let env_str: Option<String> = row.get(6).ok();
+    let mounts_str: Option<String> = row.get(19).ok();
+    Ok(McpServer {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        server_type: row.get(2)?,
+        command: row.get(3)?,
+        args: args_str.and_then(|s| serde_json::from_str(&s).ok()),
+        url: row.get(5)?,
+        env: env_str.and_then(|s| serde_json::from_str(&s).ok()),
+        description: row.get(7)?,
+        is_active: row.get(8)?,
+        created_at: row.get(9)?,
+        updated_at: row.get(10)?,
+        is_archived: row.get(11)?,
+        expires_at: row.get(12)?,
+        is_pinned: row.get(13)?,
+        position: row.get(14)?,
+        request_timeout_secs: row.get(15)?,
+        alias: row.get(16)?,
+        cwd: row.get(17)?,
+        docker_image: row.get(18)?,
+        docker_mounts: mounts_str.and_then(|s| serde_json::from_str(&s).ok()),
+    })
+}
+
+fn tool_call_from_row(row: &rusqlite::Row) -> rusqlite::Result<ToolCallRecord> {
+    Ok(ToolCallRecord {
+        server_id: row.get(0)?,
+        tool: row.get(1)?,
+        ok: row.get(2)?,
+        created_at: row.get(3)?,
+        args: row.get(4)?,
+    })
+}
+
+fn server_run_from_row(row: &rusqlite::Row) -> rusqlite::Result<ServerRun> {
+    Ok(ServerRun {
+        server_id: row.get(0)?,
+        started_at: row.get(1)?,
+        stopped_at: row.get(2)?,
+    })
+}
+
 #[derive(Clone)]
 pub struct Database {
     conn: Arc<Mutex<Connection>>,
 }
 
 impl Database {
+    /// Open the database of the active workspace.
     pub fn new() -> AppResult<Self> {
-        let db_path = get_db_path()?;
+        Self::open_workspace(&crate::workspace::active())
+    }
+
+    pub fn open_workspace(workspace: &str) -> AppResult<Self> {
+        let db_path = crate::workspace::db_path(workspace)?;
         let conn = Connection::open(db_path)?;
         init_db_schema(&conn)?;
-        let db = Self {
+        Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
-        };
-        db.bootstrap_registry()?;
-        Ok(db)
+        })
     }
 
-    fn bootstrap_registry(&self) -> AppResult<()> {
+    /// Seed the registry cache with the embedded registry if it has no
+    /// official entries yet. Only needed before reading the cache.
+    pub fn bootstrap_registry(&self) -> AppResult<()> {
         let items = self.get_cached_registry(Some("official"))?;
         if items.is_empty() {
-            println!("Bootstrapping registry from JSON...");
-            let registry_json = include_str!("../registry.json");
-            let official_items: Vec<RegistryItem> = serde_json::from_str(registry_json)?;
-            self.cache_registry(&official_items, "official")?;
+            tracing::debug!("Bootstrapping registry cache from embedded JSON");
+            self.cache_registry(official_registry(), "official")?;
         }
         Ok(())
     }
@@ -50,26 +128,11 @@ impl Database {
             .conn
             .lock()
             .map_err(|e| AppError::Database(e.to_string()))?;
-        let mut stmt = conn.prepare("SELECT * FROM mcp_servers ORDER BY created_at DESC")?;
-
-        let server_iter = stmt.query_map([], |row| {
-            let args_str: Option<String> = row.get(4).ok();
-            let env_str: Option<String> = row.get(6).ok();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM mcp_servers ORDER BY is_pinned DESC, position, created_at DESC",
+        )?;
 
-            Ok(McpServer {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                server_type: row.get(2)?,
-                command: row.get(3)?,
-                args: args_str.and_then(|s| serde_json::from_str(&s).ok()),
-                url: row.get(5)?,
-                env: env_str.and_then(|s| serde_json::from_str(&s).ok()),
-                description: row.get(7)?,
-                is_active: row.get(8)?,
-                created_at: row.get(9)?,
-                updated_at: row.get(10)?,
-            })
-        })?;
+        let server_iter = stmt.query_map([], server_from_row)?;
 
         let mut servers = Vec::new();
         for server in server_iter {
@@ -86,24 +149,7 @@ impl Database {
             .map_err(|e| AppError::Database(e.to_string()))?;
         let mut stmt = conn.prepare("SELECT * FROM mcp_servers WHERE id = ?1")?;
 
-        let server = stmt.query_row(params![id], |row| {
-            let args_str: Option<String> = row.get(4).ok();
-            let env_str: Option<String> = row.get(6).ok();
-
-            Ok(McpServer {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                server_type: row.get(2)?,
-                command: row.get(3)?,
-                args: args_str.and_then(|s| serde_json::from_str(&s).ok()),
-                url: row.get(5)?,
-                env: env_str.and_then(|s| serde_json::from_str(&s).ok()),
-                description: row.get(7)?,
-                is_active: row.get(8)?,
-                created_at: row.get(9)?,
-                updated_at: row.get(10)?,
-            })
-        })?;
+        let server = stmt.query_row(params![id], server_from_row)?;
 
         Ok(server)
     }
@@ -119,7 +165,10 @@ impl Database {
         let env_json = serde_json::to_string(&args.env.unwrap_or_default())?;
 
         conn.execute(
-            "INSERT INTO mcp_servers (id, name, type, command, args, url, env, description) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            // New servers go first, ahead of any custom order
+            "INSERT INTO mcp_servers (id, name, type, command, args, url, env, description, position)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8,
+                     (SELECT COALESCE(MIN(position), 0) - 1 FROM mcp_servers))",
             params![
                 id,
                 args.name,
@@ -134,81 +183,120 @@ impl Database {
 
         // Fetch back to return full object
         let mut stmt = conn.prepare("SELECT * FROM mcp_servers WHERE id = ?1")?;
-        let server = stmt.query_row(params![id], |row| {
-            let args_str: Option<String> = row.get(4).ok();
-            let env_str: Option<String> = row.get(6).ok();
-
-            Ok(McpServer {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                server_type: row.get(2)?,
-                command: row.get(3)?,
-                args: args_str.and_then(|s| serde_json::from_str(&s).ok()),
-                url: row.get(5)?,
-                env: env_str.and_then(|s| serde_json::from_str(&s).ok()),
-                description: row.get(7)?,
-                is_active: row.get(8)?,
-                created_at: row.get(9)?,
-                updated_at: row.get(10)?,
-            })
-        })?;
+        let server = stmt.query_row(params![id], server_from_row)?;
 
         Ok(server)
     }
 
+    /// Apply every field of `args` that is set, all or none.
     pub fn update_server(&self, id: String, args: UpdateServerArgs) -> AppResult<McpServer> {
-        let conn = self
+        let mut conn = self
             .conn
             .lock()
             .map_err(|e| AppError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+        let before = tx.query_row(
+            "SELECT * FROM mcp_servers WHERE id = ?1",
+            params![id],
+            server_from_row,
+        )?;
 
         if let Some(val) = args.name {
-            self.execute_update(&conn, "name", val, &id)?;
+            self.execute_update(&tx, "name", val, &id)?;
         }
         if let Some(val) = args.server_type {
-            self.execute_update(&conn, "type", val, &id)?;
+            self.execute_update(&tx, "type", val, &id)?;
         }
         if let Some(val) = args.command {
-            self.execute_update(&conn, "command", val, &id)?;
+            self.execute_update(&tx, "command", val, &id)?;
         }
         if let Some(val) = args.args {
-            self.execute_update(&conn, "args", serde_json::to_string(&val)?, &id)?;
+            self.execute_update(&tx, "args", serde_json::to_string(&val)?, &id)?;
         }
         if let Some(val) = args.url {
-            self.execute_update(&conn, "url", val, &id)?;
+            self.execute_update(&tx, "url", val, &id)?;
         }
         if let Some(val) = args.env {
-            self.execute_update(&conn, "env", serde_json::to_string(&val)?, &id)?;
+            self.execute_update(&tx, "env", serde_json::to_string(&val)?, &id)?;
         }
         if let Some(val) = args.description {
-            self.execute_update(&conn, "description", val, &id)?;
+            self.execute_update(&tx, "description", val, &id)?;
         }
         if let Some(val) = args.is_active {
-            self.execute_update(&conn, "is_active", val, &id)?;
+            self.execute_update(&tx, "is_active", val, &id)?;
+        }
+        if let Some(val) = args.request_timeout_secs {
+            self.execute_update(&tx, "request_timeout_secs", val, &id)?;
+        }
+        if let Some(val) = args.alias {
+            self.execute_update(&tx, "alias", val, &id)?;
+        }
+        if let Some(val) = args.cwd {
+            self.execute_update(&tx, "cwd", val, &id)?;
+        }
+        if let Some(image) = args.docker_image {
+            let mounts = match args.docker_mounts {
+                Some(mounts) if image.is_some() && !mounts.is_empty() => {
+                    Some(serde_json::to_string(&mounts)?)
+                }
+                _ => None,
+            };
+            self.execute_update(&tx, "docker_image", image, &id)?;
+            self.execute_update(&tx, "docker_mounts", mounts, &id)?;
         }
 
-        // Fetch updated
-        let mut stmt = conn.prepare("SELECT * FROM mcp_servers WHERE id = ?1")?;
-        let server = stmt.query_row(params![id], |row| {
-            let args_str: Option<String> = row.get(4).ok();
-            let env_str: Option<String> = row.get(6).ok();
-            Ok(McpServer {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                server_type: row.get(2)?,
-                command: row.get(3)?,
-                args: args_str.and_then(|s| serde_json::from_str(&s).ok()),
-                url: row.get(5)?,
-                env: env_str.and_then(|s| serde_json::from_str(&s).ok()),
-                description: row.get(7)?,
-                is_active: row.get(8)?,
-                created_at: row.get(9)?,
-                updated_at: row.get(10)?,
-            })
-        })?;
+        let server = tx.query_row(
+            "SELECT * FROM mcp_servers WHERE id = ?1",
+            params![id],
+            server_from_row,
+        )?;
+
+        record_revision(&tx, &before, &server)?;
+        tx.commit()?;
         Ok(server)
     }
 
+    /// Set env `key` to `value` on each of `ids` that already sets it, all
+    /// or none. Returns the updated servers.
+    pub fn set_env_value(
+        &self,
+        ids: &[String],
+        key: &str,
+        value: &str,
+    ) -> AppResult<Vec<McpServer>> {
+        let mut conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+        let mut updated = Vec::new();
+        for id in ids {
+            let before = tx.query_row(
+                "SELECT * FROM mcp_servers WHERE id = ?1",
+                params![id],
+                server_from_row,
+            )?;
+            let mut env = before.env.clone().unwrap_or_default();
+            match env.get_mut(key) {
+                Some(current) if current != value => *current = value.to_string(),
+                _ => continue,
+            }
+            tx.execute(
+                "UPDATE mcp_servers SET env = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+                params![serde_json::to_string(&env)?, id],
+            )?;
+            let server = tx.query_row(
+                "SELECT * FROM mcp_servers WHERE id = ?1",
+                params![id],
+                server_from_row,
+            )?;
+            record_revision(&tx, &before, &server)?;
+            updated.push(server);
+        }
+        tx.commit()?;
+        Ok(updated)
+    }
+
     fn execute_update<T: rusqlite::ToSql>(
         &self,
         conn: &Connection,
@@ -224,15 +312,247 @@ impl Database {
         Ok(())
     }
 
+    /// Archive or restore a server. Not a config change, so no revision.
+    pub fn set_server_archived(&self, id: &str, archived: bool) -> AppResult<McpServer> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "UPDATE mcp_servers SET is_archived = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+            params![archived, id],
+        )?;
+        let server = conn.query_row(
+            "SELECT * FROM mcp_servers WHERE id = ?1",
+            params![id],
+            server_from_row,
+        )?;
+        Ok(server)
+    }
+
+    /// Pin a server to the top of the dashboard, or unpin it. Not a
+    /// config change, so no revision.
+    pub fn set_server_pinned(&self, id: &str, pinned: bool) -> AppResult<McpServer> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "UPDATE mcp_servers SET is_pinned = ?1 WHERE id = ?2",
+            params![pinned, id],
+        )?;
+        let server = conn.query_row(
+            "SELECT * FROM mcp_servers WHERE id = ?1",
+            params![id],
+            server_from_row,
+        )?;
+        Ok(server)
+    }
+
+    /// Save the dashboard order: each server's position is its index in
+    /// `ids`.
+    pub fn reorder_servers(&self, ids: &[String]) -> AppResult<()> {
+        let mut conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+        for (position, id) in ids.iter().enumerate() {
+            tx.execute(
+                "UPDATE mcp_servers SET position = ?1 WHERE id = ?2",
+                params![position as i64, id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Make a server temporary until `expires_at`, or permanent with
+    /// `None`. Not a config change, so no revision.
+    pub fn set_server_expiry(&self, id: &str, expires_at: Option<&str>) -> AppResult<McpServer> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "UPDATE mcp_servers SET expires_at = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+            params![expires_at, id],
+        )?;
+        let server = conn.query_row(
+            "SELECT * FROM mcp_servers WHERE id = ?1",
+            params![id],
+            server_from_row,
+        )?;
+        Ok(server)
+    }
+
+    /// Merge duplicate servers into `plan.keep`, all or nothing: write the
+    /// merged env and description, move presets and usage history over,
+    /// delete the duplicates and log the merge to the audit log.
+    pub fn merge_servers(&self, plan: &MergePlan) -> AppResult<McpServer> {
+        let mut conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let tx = conn.transaction()?;
+        let keep_id = &plan.keep.id;
+        let before = tx.query_row(
+            "SELECT * FROM mcp_servers WHERE id = ?1",
+            params![keep_id],
+            server_from_row,
+        )?;
+        tx.execute(
+            "UPDATE mcp_servers SET env = ?1, description = ?2, updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?3",
+            params![serde_json::to_string(&plan.env)?, plan.description, keep_id],
+        )?;
+
+        for duplicate in &plan.remove {
+            let id = &duplicate.id;
+            // Presets the kept server already has under the same name win
+            tx.execute(
+                "UPDATE OR IGNORE tool_presets SET server_id = ?1 WHERE server_id = ?2",
+                params![keep_id, id],
+            )?;
+            tx.execute(
+                "UPDATE tool_calls SET server_id = ?1 WHERE server_id = ?2",
+                params![keep_id, id],
+            )?;
+            tx.execute(
+                "UPDATE server_runs SET server_id = ?1 WHERE server_id = ?2",
+                params![keep_id, id],
+            )?;
+            tx.execute("DELETE FROM tool_presets WHERE server_id = ?1", params![id])?;
+            tx.execute("DELETE FROM tool_rules WHERE server_id = ?1", params![id])?;
+            tx.execute(
+                "UPDATE OR IGNORE server_tags SET server_id = ?1 WHERE server_id = ?2",
+                params![keep_id, id],
+            )?;
+            tx.execute("DELETE FROM server_tags WHERE server_id = ?1", params![id])?;
+            tx.execute(
+                "DELETE FROM server_revisions WHERE server_id = ?1",
+                params![id],
+            )?;
+            let deleted = tx.execute("DELETE FROM mcp_servers WHERE id = ?1", params![id])?;
+            if deleted == 0 {
+                return Err(AppError::Database(format!(
+                    "Server {} no longer exists",
+                    duplicate.name
+                )));
+            }
+        }
+
+        let server = tx.query_row(
+            "SELECT * FROM mcp_servers WHERE id = ?1",
+            params![keep_id],
+            server_from_row,
+        )?;
+        record_revision(&tx, &before, &server)?;
+        tx.execute(
+            "INSERT INTO audit_log (event, server_id, detail) VALUES ('merge', ?1, ?2)",
+            params![keep_id, plan.summary()],
+        )?;
+        tx.commit()?;
+        Ok(server)
+    }
+
     pub fn delete_server(&self, id: String) -> AppResult<()> {
         let conn = self
             .conn
             .lock()
             .map_err(|e| AppError::Database(e.to_string()))?;
         conn.execute("DELETE FROM mcp_servers WHERE id = ?1", params![id])?;
+        conn.execute(
+            "DELETE FROM server_revisions WHERE server_id = ?1",
+            params![id],
+        )?;
+        conn.execute("DELETE FROM tool_presets WHERE server_id = ?1", params![id])?;
+        conn.execute("DELETE FROM tool_rules WHERE server_id = ?1", params![id])?;
+        conn.execute("DELETE FROM server_tags WHERE server_id = ?1", params![id])?;
         Ok(())
     }
 
+    /// Previous configs of a server, newest first.
+    pub fn get_server_revisions(&self, server_id: &str) -> AppResult<Vec<ServerRevision>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, server_id, config, created_at FROM server_revisions
+             WHERE server_id = ?1 ORDER BY id DESC",
+        )?;
+        let rows = stmt.query_map(params![server_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        let mut revisions = Vec::new();
+        for row in rows {
+            let (id, server_id, config, created_at) = row?;
+            revisions.push(ServerRevision {
+                id,
+                server_id,
+                config: serde_json::from_str(&config)?,
+                created_at,
+            });
+        }
+        Ok(revisions)
+    }
+
+    /// Put a server back to the config stored in `revision_id`. The config
+    /// being replaced becomes a revision itself, so a restore can be undone.
+    pub fn restore_server_revision(&self, revision_id: i64) -> AppResult<McpServer> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let (server_id, config): (String, String) = conn.query_row(
+            "SELECT server_id, config FROM server_revisions WHERE id = ?1",
+            params![revision_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let config: McpServer = serde_json::from_str(&config)?;
+        let before = conn.query_row(
+            "SELECT * FROM mcp_servers WHERE id = ?1",
+            params![server_id],
+            server_from_row,
+        )?;
+
+        let args_json = config
+            .args
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let env_json = config.env.as_ref().map(serde_json::to_string).transpose()?;
+        conn.execute(
+            "UPDATE mcp_servers SET name = ?1, type = ?2, command = ?3, args = ?4, url = ?5,
+                env = ?6, description = ?7, updated_at = CURRENT_TIMESTAMP WHERE id = ?8",
+            params![
+                config.name,
+                config.server_type,
+                config.command,
+                args_json,
+                config.url,
+                env_json,
+                config.description,
+                server_id
+            ],
+        )?;
+
+        let server = conn.query_row(
+            "SELECT * FROM mcp_servers WHERE id = ?1",
+            params![server_id],
+            server_from_row,
+        )?;
+        record_revision(&conn, &before, &server)?;
+        Ok(server)
+    }
+
     // === Registry Cache Methods ===
 
     /// Cache registry items for offline use
@@ -453,22 +773,501 @@ impl Database {
         )?;
         Ok(())
     }
-}
 
-fn get_db_path() -> AppResult<PathBuf> {
-    let mut path = dirs::data_local_dir().ok_or(AppError::Io("Could not find data dir".into()))?;
-    path.push("open-mcp-manager");
-    std::fs::create_dir_all(&path)?;
-    path.push("servers.db");
-    Ok(path)
+    // === Tool Preset Methods ===
+
+    /// Saved argument presets of one tool, by name.
+    pub fn get_tool_presets(&self, server_id: &str, tool: &str) -> AppResult<Vec<ToolPreset>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(
+            "SELECT server_id, tool, name, args FROM tool_presets
+             WHERE server_id = ?1 AND tool = ?2 ORDER BY name COLLATE NOCASE",
+        )?;
+        let rows = stmt.query_map(params![server_id, tool], |row| {
+            Ok(ToolPreset {
+                server_id: row.get(0)?,
+                tool: row.get(1)?,
+                name: row.get(2)?,
+                args: row.get(3)?,
+            })
+        })?;
+
+        let mut presets = Vec::new();
+        for preset in rows {
+            presets.push(preset?);
+        }
+        Ok(presets)
+    }
+
+    /// Look up one preset by name, e.g. for a scheduled run.
+    pub fn get_tool_preset(
+        &self,
+        server_id: &str,
+        tool: &str,
+        name: &str,
+    ) -> AppResult<Option<ToolPreset>> {
+        Ok(self
+            .get_tool_presets(server_id, tool)?
+            .into_iter()
+            .find(|p| p.name == name))
+    }
+
+    /// Save a preset, replacing one with the same name.
+    pub fn save_tool_preset(&self, preset: &ToolPreset) -> AppResult<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO tool_presets (server_id, tool, name, args) VALUES (?1, ?2, ?3, ?4)",
+            params![preset.server_id, preset.tool, preset.name, preset.args],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_tool_preset(&self, server_id: &str, tool: &str, name: &str) -> AppResult<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM tool_presets WHERE server_id = ?1 AND tool = ?2 AND name = ?3",
+            params![server_id, tool, name],
+        )?;
+        Ok(())
+    }
+
+    // === Tool Rule Methods ===
+
+    /// Every server's argument rules, in the order they were added.
+    pub fn get_tool_rules(&self) -> AppResult<Vec<ToolRule>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut stmt =
+            conn.prepare("SELECT id, server_id, tool, action FROM tool_rules ORDER BY id")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        let mut rules = Vec::new();
+        for row in rows {
+            let (id, server_id, tool, action) = row?;
+            // Skip rules this version doesn't understand
+            if let Ok(action) = serde_json::from_str(&action) {
+                rules.push(ToolRule {
+                    id,
+                    server_id,
+                    tool,
+                    action,
+                });
+            }
+        }
+        Ok(rules)
+    }
+
+    /// Add a rule after the existing ones, returning its id.
+    pub fn add_tool_rule(
+        &self,
+        server_id: &str,
+        tool: &str,
+        action: &RuleAction,
+    ) -> AppResult<i64> {
+        let action = serde_json::to_string(action)?;
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT INTO tool_rules (server_id, tool, action) VALUES (?1, ?2, ?3)",
+            params![server_id, tool, action],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn delete_tool_rule(&self, id: i64) -> AppResult<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute("DELETE FROM tool_rules WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    // === Server Tag Methods ===
+
+    /// Each tagged server's tags, in the order they were added.
+    pub fn get_server_tags(&self) -> AppResult<HashMap<String, Vec<String>>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare("SELECT server_id, tag FROM server_tags ORDER BY rowid")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+        for row in rows {
+            let (server_id, tag) = row?;
+            tags.entry(server_id).or_default().push(tag);
+        }
+        Ok(tags)
+    }
+
+    /// Tag a server; a tag it already has is left as is.
+    pub fn add_server_tag(&self, server_id: &str, tag: &str) -> AppResult<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT OR IGNORE INTO server_tags (server_id, tag) VALUES (?1, ?2)",
+            params![server_id, tag],
+        )?;
+        Ok(())
+    }
+
+    pub fn remove_server_tag(&self, server_id: &str, tag: &str) -> AppResult<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM server_tags WHERE server_id = ?1 AND tag = ?2",
+            params![server_id, tag],
+        )?;
+        Ok(())
+    }
+
+    // === App Settings Methods ===
+
+    pub fn append_audit(
+        &self,
+        event: &str,
+        server_id: Option<&str>,
+        detail: &str,
+    ) -> AppResult<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT INTO audit_log (event, server_id, detail) VALUES (?1, ?2, ?3)",
+            params![event, server_id, detail],
+        )?;
+        Ok(())
+    }
+
+    /// The `limit` most recent audit entries, newest first.
+    pub fn get_audit_log(&self, limit: usize) -> AppResult<Vec<AuditEntry>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(
+            "SELECT id, event, server_id, detail, created_at FROM audit_log
+             ORDER BY id DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit as i64], |row| {
+            Ok(AuditEntry {
+                id: row.get(0)?,
+                event: row.get(1)?,
+                server_id: row.get(2)?,
+                detail: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    // === Usage History Methods ===
+
+    /// Log a routed tool call; `args` is the call's arguments as JSON text.
+    pub fn append_tool_call(
+        &self,
+        server_id: &str,
+        tool: &str,
+        args: &str,
+        ok: bool,
+    ) -> AppResult<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT INTO tool_calls (server_id, tool, ok, args) VALUES (?1, ?2, ?3, ?4)",
+            params![server_id, tool, ok, args],
+        )?;
+        Ok(())
+    }
+
+    /// Distinct arguments `tool` on `server_id` succeeded with, most
+    /// recently used first.
+    pub fn get_tool_examples(
+        &self,
+        server_id: &str,
+        tool: &str,
+        limit: usize,
+    ) -> AppResult<Vec<String>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(
+            "SELECT args FROM tool_calls
+             WHERE server_id = ?1 AND tool = ?2 AND ok = 1 AND args IS NOT NULL
+             GROUP BY args ORDER BY MAX(id) DESC LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![server_id, tool, limit as i64], |row| row.get(0))?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Tool calls made at or after `since` (a `CURRENT_TIMESTAMP`-style
+    /// UTC time), oldest first.
+    pub fn get_tool_calls_since(&self, since: &str) -> AppResult<Vec<ToolCallRecord>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(
+            "SELECT server_id, tool, ok, created_at, args FROM tool_calls
+             WHERE created_at >= ?1 ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![since], tool_call_from_row)?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Tool calls made from `from` up to but excluding `to`, oldest first.
+    pub fn get_tool_calls_between(&self, from: &str, to: &str) -> AppResult<Vec<ToolCallRecord>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(
+            "SELECT server_id, tool, ok, created_at, args FROM tool_calls
+             WHERE created_at >= ?1 AND created_at < ?2 ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![from, to], tool_call_from_row)?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    pub fn start_server_run(&self, server_id: &str) -> AppResult<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT INTO server_runs (server_id) VALUES (?1)",
+            params![server_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn end_server_run(&self, server_id: &str) -> AppResult<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "UPDATE server_runs SET stopped_at = CURRENT_TIMESTAMP
+             WHERE server_id = ?1 AND stopped_at IS NULL",
+            params![server_id],
+        )?;
+        Ok(())
+    }
+
+    /// Close runs left open by the app quitting or crashing. When those
+    /// servers stopped is unknown, so the runs count as having no length.
+    pub fn close_open_server_runs(&self) -> AppResult<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "UPDATE server_runs SET stopped_at = started_at WHERE stopped_at IS NULL",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Runs still going or that ended at or after `since`.
+    pub fn get_server_runs_since(&self, since: &str) -> AppResult<Vec<ServerRun>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(
+            "SELECT server_id, started_at, stopped_at FROM server_runs
+             WHERE stopped_at IS NULL OR stopped_at >= ?1 ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![since], server_run_from_row)?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Runs that overlap `from` up to but excluding `to`.
+    pub fn get_server_runs_between(&self, from: &str, to: &str) -> AppResult<Vec<ServerRun>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(
+            "SELECT server_id, started_at, stopped_at FROM server_runs
+             WHERE started_at < ?2 AND (stopped_at IS NULL OR stopped_at >= ?1)
+             ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![from, to], server_run_from_row)?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Forget tool calls and runs that ended before `before`.
+    pub fn prune_usage_history(&self, before: &str) -> AppResult<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM tool_calls WHERE created_at < ?1",
+            params![before],
+        )?;
+        conn.execute(
+            "DELETE FROM server_runs WHERE stopped_at < ?1",
+            params![before],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_setting(&self, key: &str) -> AppResult<Option<String>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare("SELECT value FROM app_settings WHERE key = ?1")?;
+        let mut rows = stmt.query(params![key])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(row.get(0)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn set_setting(&self, key: &str, value: &str) -> AppResult<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    // === Registry Snapshot Methods ===
+
+    /// Store a snapshot, keeping only the newest `keep`.
+    pub fn save_registry_snapshot(
+        &self,
+        snapshot: &RegistrySnapshot,
+        keep: usize,
+    ) -> AppResult<()> {
+        let entries = serde_json::to_string(&snapshot.entries)
+            .map_err(|e| AppError::Serialization(e.to_string()))?;
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "INSERT INTO registry_snapshots (taken_at, entries) VALUES (?1, ?2)",
+            params![snapshot.taken_at, entries],
+        )?;
+        conn.execute(
+            "DELETE FROM registry_snapshots WHERE id NOT IN
+                (SELECT id FROM registry_snapshots ORDER BY id DESC LIMIT ?1)",
+            params![keep as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Most recent snapshots, newest first.
+    pub fn get_registry_snapshots(&self, limit: usize) -> AppResult<Vec<RegistrySnapshot>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut stmt = conn.prepare(
+            "SELECT taken_at, entries FROM registry_snapshots ORDER BY id DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(params![limit as i64], |row| {
+            let taken_at: String = row.get(0)?;
+            let entries: String = row.get(1)?;
+            Ok((taken_at, entries))
+        })?;
+
+        let mut snapshots = Vec::new();
+        for row in rows {
+            let (taken_at, entries) = row?;
+            let entries = serde_json::from_str(&entries)
+                .map_err(|e| AppError::Serialization(e.to_string()))?;
+            snapshots.push(RegistrySnapshot { taken_at, entries });
+        }
+        Ok(snapshots)
+    }
 }
 
-fn init_db_schema(conn: &Connection) -> AppResult<()> {
+/// Store `before` as a revision of the server if `after` changed its
+/// config, pruning to the newest `SERVER_REVISIONS_KEPT`.
+fn record_revision(conn: &Connection, before: &McpServer, after: &McpServer) -> AppResult<()> {
+    if before.same_config(after) {
+        return Ok(());
+    }
     conn.execute(
-        "CREATE TABLE IF NOT EXISTS mcp_servers (
+        "INSERT INTO server_revisions (server_id, config) VALUES (?1, ?2)",
+        params![before.id, serde_json::to_string(before)?],
+    )?;
+    conn.execute(
+        "DELETE FROM server_revisions WHERE server_id = ?1 AND id NOT IN
+            (SELECT id FROM server_revisions WHERE server_id = ?1 ORDER BY id DESC LIMIT ?2)",
+        params![before.id, SERVER_REVISIONS_KEPT as i64],
+    )?;
+    Ok(())
+}
+
+/// Add a column to a table created by an older version.
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> AppResult<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(Result::ok)
+        .any(|name| name == column);
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Column definitions of `mcp_servers`, shared by the initial create and
+/// the rebuild in [`allow_ws_servers`].
+const MCP_SERVERS_COLUMNS: &str = "
             id TEXT PRIMARY KEY,
             name TEXT NOT NULL UNIQUE,
-            type TEXT NOT NULL CHECK (type IN ('stdio', 'sse')),
+            type TEXT NOT NULL CHECK (type IN ('stdio', 'sse', 'ws')),
             command TEXT,
             args TEXT,
             url TEXT,
@@ -476,8 +1275,66 @@ fn init_db_schema(conn: &Connection) -> AppResult<()> {
             description TEXT,
             is_active BOOLEAN DEFAULT 1,
             created_at TEXT DEFAULT CURRENT_TIMESTAMP,
-            updated_at TEXT DEFAULT CURRENT_TIMESTAMP
-        )",
+            updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+            is_archived BOOLEAN DEFAULT 0,
+            expires_at TEXT,
+            is_pinned BOOLEAN DEFAULT 0,
+            position INTEGER DEFAULT 0,
+            request_timeout_secs INTEGER,
+            alias TEXT,
+            cwd TEXT,
+            docker_image TEXT,
+            docker_mounts TEXT
+        ";
+
+/// Rebuild an `mcp_servers` table created before WebSocket servers existed.
+///
+/// SQLite can't alter a CHECK constraint, so the rows are copied into a new
+/// table with the current definition, which then replaces the old one.
+fn allow_ws_servers(conn: &Connection) -> AppResult<()> {
+    let sql: String = conn.query_row(
+        "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'mcp_servers'",
+        [],
+        |row| row.get(0),
+    )?;
+    if sql.contains("'ws'") {
+        return Ok(());
+    }
+    let columns = "id, name, type, command, args, url, env, description, is_active, \
+        created_at, updated_at, is_archived, expires_at, is_pinned, position, \
+        request_timeout_secs, alias, cwd, docker_image, docker_mounts";
+    conn.execute_batch(&format!(
+        "BEGIN;
+        CREATE TABLE mcp_servers_new ({});
+        INSERT INTO mcp_servers_new ({}) SELECT {} FROM mcp_servers;
+        DROP TABLE mcp_servers;
+        ALTER TABLE mcp_servers_new RENAME TO mcp_servers;
+        COMMIT;",
+        MCP_SERVERS_COLUMNS, columns, columns
+    ))?;
+    Ok(())
+}
+
+fn init_db_schema(conn: &Connection) -> AppResult<()> {
+    conn.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS mcp_servers ({})",
+            MCP_SERVERS_COLUMNS
+        ),
+        [],
+    )?;
+    add_column_if_missing(conn, "mcp_servers", "is_archived", "BOOLEAN DEFAULT 0")?;
+    add_column_if_missing(conn, "mcp_servers", "expires_at", "TEXT")?;
+    add_column_if_missing(conn, "mcp_servers", "is_pinned", "BOOLEAN DEFAULT 0")?;
+    add_column_if_missing(conn, "mcp_servers", "position", "INTEGER DEFAULT 0")?;
+    add_column_if_missing(conn, "mcp_servers", "request_timeout_secs", "INTEGER")?;
+    add_column_if_missing(conn, "mcp_servers", "alias", "TEXT")?;
+    add_column_if_missing(conn, "mcp_servers", "cwd", "TEXT")?;
+    add_column_if_missing(conn, "mcp_servers", "docker_image", "TEXT")?;
+    add_column_if_missing(conn, "mcp_servers", "docker_mounts", "TEXT")?;
+    allow_ws_servers(conn)?;
+    conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_mcp_servers_alias ON mcp_servers(alias)",
         [],
     )?;
 
@@ -507,23 +1364,121 @@ fn init_db_schema(conn: &Connection) -> AppResult<()> {
 
     // Metadata table to track cache freshness
     conn.execute(
-        "CREATE TABLE IF NOT EXISTS cache_metadata (
-            key TEXT PRIMARY KEY,
-            value TEXT NOT NULL,
-            updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+        "CREATE TABLE IF NOT EXISTS cache_metadata (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
+    // Research notes table for the 'Research Project'
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS research_notes (
+            id TEXT PRIMARY KEY,
+            title TEXT NOT NULL,
+            content TEXT,
+            tags TEXT,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+            updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
+    // Weekly registry snapshots for the "what's new" digest
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS registry_snapshots (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            taken_at TEXT NOT NULL,
+            entries TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Previous server configs, written on every update that changes one
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS server_revisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            server_id TEXT NOT NULL,
+            config TEXT NOT NULL,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
+    // Named argument presets per server and tool
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS tool_presets (
+            server_id TEXT NOT NULL,
+            tool TEXT NOT NULL,
+            name TEXT NOT NULL,
+            args TEXT NOT NULL,
+            PRIMARY KEY (server_id, tool, name)
+        )",
+        [],
+    )?;
+
+    // Argument rewrites the hub applies to tool calls, as JSON actions
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS tool_rules (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            server_id TEXT NOT NULL,
+            tool TEXT NOT NULL,
+            action TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Labels on servers, e.g. the project they were imported from
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS server_tags (
+            server_id TEXT NOT NULL,
+            tag TEXT NOT NULL,
+            PRIMARY KEY (server_id, tag)
+        )",
+        [],
+    )?;
+
+    // Append-only log of automatic actions, e.g. hub failovers
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS audit_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            event TEXT NOT NULL,
+            server_id TEXT,
+            detail TEXT NOT NULL,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+
+    // Routed tool calls and server run times, for the weekly report
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS tool_calls (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            server_id TEXT NOT NULL,
+            tool TEXT NOT NULL,
+            ok INTEGER NOT NULL,
+            created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+            args TEXT
+        )",
+        [],
+    )?;
+    add_column_if_missing(conn, "tool_calls", "args", "TEXT")?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS server_runs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            server_id TEXT NOT NULL,
+            started_at TEXT DEFAULT CURRENT_TIMESTAMP,
+            stopped_at TEXT
         )",
         [],
     )?;
 
-    // Research notes table for the 'Research Project'
+    // Key/value app preferences (JSON values)
     conn.execute(
-        "CREATE TABLE IF NOT EXISTS research_notes (
-            id TEXT PRIMARY KEY,
-            title TEXT NOT NULL,
-            content TEXT,
-            tags TEXT,
-            created_at TEXT DEFAULT CURRENT_TIMESTAMP,
-            updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+        "CREATE TABLE IF NOT EXISTS app_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
         )",
         [],
     )?;
@@ -534,7 +1489,6 @@ fn init_db_schema(conn: &Connection) -> AppResult<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
 
     #[test]
     fn test_create_and_get_server() {
@@ -583,11 +1537,12 @@ mod tests {
             env: None,
             description: None,
             is_active: Some(false),
+            ..Default::default()
         };
 
         let updated = db.update_server(server.id.clone(), update_args).unwrap();
         assert_eq!(updated.name, "updated-name");
-        assert_eq!(updated.is_active, false);
+        assert!(!updated.is_active);
 
         let servers = db.get_servers().unwrap();
         assert_eq!(servers[0].name, "updated-name");
@@ -616,6 +1571,77 @@ mod tests {
         assert_eq!(servers_after.len(), 0);
     }
 
+    #[test]
+    fn test_create_and_update_ws_server() {
+        let db = Database::new_in_memory().unwrap();
+        let args = CreateServerArgs {
+            name: "ws-server".to_string(),
+            server_type: "ws".to_string(),
+            command: None,
+            args: None,
+            url: Some("ws://localhost:9000".to_string()),
+            env: None,
+            description: None,
+        };
+        let server = db.create_server(args).unwrap();
+        assert_eq!(server.server_type, "ws");
+
+        let update_args = UpdateServerArgs {
+            url: Some("wss://example.com/mcp".to_string()),
+            ..Default::default()
+        };
+        let updated = db.update_server(server.id, update_args).unwrap();
+        assert_eq!(updated.server_type, "ws");
+        assert_eq!(updated.url, Some("wss://example.com/mcp".to_string()));
+    }
+
+    #[test]
+    fn test_old_schema_is_rebuilt_to_allow_ws() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE mcp_servers (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE,
+                type TEXT NOT NULL CHECK (type IN ('stdio', 'sse')),
+                command TEXT,
+                args TEXT,
+                url TEXT,
+                env TEXT,
+                description TEXT,
+                is_active BOOLEAN DEFAULT 1,
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO mcp_servers (id, name, type, command) VALUES ('a', 'old', 'stdio', 'cmd')",
+            [],
+        )
+        .unwrap();
+
+        init_db_schema(&conn).unwrap();
+        let db = Database {
+            conn: Arc::new(Mutex::new(conn)),
+        };
+
+        let servers = db.get_servers().unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].command, Some("cmd".to_string()));
+
+        let args = CreateServerArgs {
+            name: "ws-server".to_string(),
+            server_type: "ws".to_string(),
+            command: None,
+            args: None,
+            url: Some("ws://localhost:9000".to_string()),
+            env: None,
+            description: None,
+        };
+        assert!(db.create_server(args).is_ok());
+    }
+
     // === Additional Database Tests ===
 
     #[test]
@@ -680,6 +1706,7 @@ mod tests {
             env: None,
             description: None,
             is_active: None,
+            ..Default::default()
         };
 
         let updated = db.update_server(server.id, update_args).unwrap();
@@ -709,6 +1736,7 @@ mod tests {
             env: None,
             description: None,
             is_active: None,
+            ..Default::default()
         };
 
         let updated = db.update_server(server.id, update_args).unwrap();
@@ -747,6 +1775,7 @@ mod tests {
             )])),
             description: None,
             is_active: None,
+            ..Default::default()
         };
 
         let updated = db.update_server(server.id, update_args).unwrap();
@@ -817,6 +1846,397 @@ mod tests {
         assert!(server.is_active);
     }
 
+    #[test]
+    fn test_set_env_value() {
+        let db = Database::new_in_memory().unwrap();
+        let create = |name: &str, env: &[(&str, &str)]| {
+            db.create_server(CreateServerArgs {
+                name: name.to_string(),
+                server_type: "stdio".to_string(),
+                command: Some("cmd".to_string()),
+                args: None,
+                url: None,
+                env: Some(
+                    env.iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect(),
+                ),
+                description: None,
+            })
+            .unwrap()
+        };
+        let a = create("a", &[("API_KEY", "old"), ("REGION", "eu")]);
+        let b = create("b", &[("API_KEY", "old")]);
+        let c = create("c", &[("OTHER", "x")]);
+
+        let ids = [a.id.clone(), b.id.clone(), c.id.clone()];
+        let updated = db.set_env_value(&ids, "API_KEY", "new").unwrap();
+        assert_eq!(updated.len(), 2);
+        let a = db.get_server(a.id).unwrap();
+        let env = a.env.as_ref().unwrap();
+        assert_eq!(env["API_KEY"], "new");
+        assert_eq!(env["REGION"], "eu");
+        // Servers without the key are left alone
+        assert!(!db
+            .get_server(c.id)
+            .unwrap()
+            .env
+            .unwrap()
+            .contains_key("API_KEY"));
+        // The old value is kept as a revision
+        assert_eq!(db.get_server_revisions(&a.id).unwrap().len(), 1);
+
+        // A missing server rolls the whole rotation back
+        let ids = [b.id.clone(), "missing".to_string()];
+        assert!(db.set_env_value(&ids, "API_KEY", "newer").is_err());
+        assert_eq!(db.get_server(b.id).unwrap().env.unwrap()["API_KEY"], "new");
+    }
+
+    #[test]
+    fn test_merge_servers() {
+        let db = Database::new_in_memory().unwrap();
+        let create = |name: &str, env: &[(&str, &str)]| {
+            db.create_server(CreateServerArgs {
+                name: name.to_string(),
+                server_type: "stdio".to_string(),
+                command: Some("npx".to_string()),
+                args: Some(vec!["pkg".to_string()]),
+                url: None,
+                env: Some(
+                    env.iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect(),
+                ),
+                description: None,
+            })
+            .unwrap()
+        };
+        let keep = create("pkg", &[("KEY", "a")]);
+        let duplicate = create("pkg (2)", &[("REGION", "eu")]);
+        db.save_tool_preset(&ToolPreset {
+            server_id: duplicate.id.clone(),
+            tool: "query".to_string(),
+            name: "mine".to_string(),
+            args: "{}".to_string(),
+        })
+        .unwrap();
+        db.append_tool_call(&duplicate.id, "query", "{}", true)
+            .unwrap();
+
+        let group = [keep.clone(), duplicate.clone()];
+        let plan = MergePlan::new(&group, &keep.id).unwrap();
+        let merged = db.merge_servers(&plan).unwrap();
+        assert_eq!(merged.env.as_ref().unwrap()["REGION"], "eu");
+
+        let servers = db.get_servers().unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(db.get_tool_presets(&keep.id, "query").unwrap().len(), 1);
+        let calls = db.get_tool_calls_since("2000-01-01 00:00:00").unwrap();
+        assert_eq!(calls[0].server_id, keep.id);
+        let audit = db.get_audit_log(10).unwrap();
+        assert_eq!(audit[0].event, "merge");
+        assert_eq!(audit[0].detail, "Merged pkg (2) into pkg");
+
+        // Merging a duplicate that is already gone changes nothing
+        assert!(db.merge_servers(&plan).is_err());
+        assert_eq!(db.get_audit_log(10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_archive_server() {
+        let db = Database::new_in_memory().unwrap();
+        let server = db
+            .create_server(CreateServerArgs {
+                name: "archive-test".to_string(),
+                server_type: "stdio".to_string(),
+                command: Some("cmd".to_string()),
+                args: None,
+                url: None,
+                env: None,
+                description: None,
+            })
+            .unwrap();
+        assert!(!server.is_archived);
+
+        let archived = db.set_server_archived(&server.id, true).unwrap();
+        assert!(archived.is_archived);
+        assert!(archived.same_config(&server));
+        assert!(db.get_servers().unwrap()[0].is_archived);
+        // Archiving isn't a config change
+        assert!(db.get_server_revisions(&server.id).unwrap().is_empty());
+
+        assert!(
+            !db.set_server_archived(&server.id, false)
+                .unwrap()
+                .is_archived
+        );
+    }
+
+    #[test]
+    fn test_archived_column_added_to_old_schema() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE mcp_servers (
+                id TEXT PRIMARY KEY, name TEXT NOT NULL UNIQUE, type TEXT NOT NULL,
+                command TEXT, args TEXT, url TEXT, env TEXT, description TEXT,
+                is_active BOOLEAN DEFAULT 1,
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO mcp_servers (id, name, type) VALUES ('1', 'old', 'stdio')",
+            [],
+        )
+        .unwrap();
+        init_db_schema(&conn).unwrap();
+        // Idempotent on the next start
+        init_db_schema(&conn).unwrap();
+        let server = conn
+            .query_row("SELECT * FROM mcp_servers", [], server_from_row)
+            .unwrap();
+        assert!(!server.is_archived);
+        assert!(server.expires_at.is_none());
+        assert!(!server.is_pinned);
+        assert_eq!(server.position, 0);
+        assert!(server.cwd.is_none());
+    }
+
+    #[test]
+    fn test_pinned_and_ordered_servers() {
+        let db = Database::new_in_memory().unwrap();
+        let create = |name: &str| {
+            db.create_server(CreateServerArgs {
+                name: name.to_string(),
+                server_type: "stdio".to_string(),
+                command: Some("cmd".to_string()),
+                args: None,
+                url: None,
+                env: None,
+                description: None,
+            })
+            .unwrap()
+        };
+        let a = create("a");
+        let b = create("b");
+        let c = create("c");
+        let names = |db: &Database| -> Vec<String> {
+            db.get_servers()
+                .unwrap()
+                .into_iter()
+                .map(|s| s.name)
+                .collect()
+        };
+        // Newest first until reordered
+        assert_eq!(names(&db), ["c", "b", "a"]);
+
+        db.reorder_servers(&[a.id.clone(), c.id.clone(), b.id.clone()])
+            .unwrap();
+        assert_eq!(names(&db), ["a", "c", "b"]);
+
+        let pinned = db.set_server_pinned(&b.id, true).unwrap();
+        assert!(pinned.is_pinned);
+        assert!(pinned.same_config(&b));
+        assert_eq!(names(&db), ["b", "a", "c"]);
+
+        // A new server goes ahead of the custom order, after favorites
+        create("d");
+        assert_eq!(names(&db), ["b", "d", "a", "c"]);
+        assert!(db.get_server_revisions(&b.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_server_expiry() {
+        let db = Database::new_in_memory().unwrap();
+        let server = db
+            .create_server(CreateServerArgs {
+                name: "scratch".to_string(),
+                server_type: "stdio".to_string(),
+                command: Some("cmd".to_string()),
+                args: None,
+                url: None,
+                env: None,
+                description: None,
+            })
+            .unwrap();
+        assert!(server.expires_at.is_none());
+
+        let temporary = db
+            .set_server_expiry(&server.id, Some("2026-03-02 12:00:00"))
+            .unwrap();
+        assert_eq!(temporary.expires_at.as_deref(), Some("2026-03-02 12:00:00"));
+        assert!(temporary.same_config(&server));
+        assert!(db.get_server_revisions(&server.id).unwrap().is_empty());
+        assert!(db
+            .set_server_expiry(&server.id, None)
+            .unwrap()
+            .expires_at
+            .is_none());
+    }
+
+    #[test]
+    fn test_update_server_request_timeout() {
+        let db = Database::new_in_memory().unwrap();
+        let server = db
+            .create_server(CreateServerArgs {
+                name: "slow".to_string(),
+                server_type: "stdio".to_string(),
+                command: Some("cmd".to_string()),
+                args: None,
+                url: None,
+                env: None,
+                description: None,
+            })
+            .unwrap();
+        assert_eq!(server.request_timeout_secs, None);
+
+        let timeout = |secs| UpdateServerArgs {
+            request_timeout_secs: Some(secs),
+            ..Default::default()
+        };
+        let updated = db
+            .update_server(server.id.clone(), timeout(Some(600)))
+            .unwrap();
+        assert_eq!(updated.request_timeout_secs, Some(600));
+        assert_eq!(db.get_servers().unwrap()[0].request_timeout_secs, Some(600));
+        // Left alone by updates that don't mention it
+        let renamed = UpdateServerArgs {
+            name: Some("slower".to_string()),
+            ..Default::default()
+        };
+        let renamed = db.update_server(server.id.clone(), renamed).unwrap();
+        assert_eq!(renamed.request_timeout_secs, Some(600));
+        assert_eq!(
+            db.update_server(server.id, timeout(None))
+                .unwrap()
+                .request_timeout_secs,
+            None
+        );
+    }
+
+    #[test]
+    fn test_update_server_alias() {
+        let db = Database::new_in_memory().unwrap();
+        let create = |name: &str| {
+            db.create_server(CreateServerArgs {
+                name: name.to_string(),
+                server_type: "stdio".to_string(),
+                command: Some("cmd".to_string()),
+                args: None,
+                url: None,
+                env: None,
+                description: None,
+            })
+            .unwrap()
+        };
+        let files = create("filesystem");
+        let github = create("github");
+        let alias = |alias: Option<&str>| UpdateServerArgs {
+            alias: Some(alias.map(str::to_string)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            db.update_server(files.id.clone(), alias(Some("fs")))
+                .unwrap()
+                .alias
+                .as_deref(),
+            Some("fs")
+        );
+        // Unique, though any number of servers can have none
+        assert!(db
+            .update_server(github.id.clone(), alias(Some("fs")))
+            .is_err());
+        // All or nothing: the name isn't changed either
+        let clash = UpdateServerArgs {
+            name: Some("gh".to_string()),
+            ..alias(Some("fs"))
+        };
+        assert!(db.update_server(github.id.clone(), clash).is_err());
+        let servers = db.get_servers().unwrap();
+        assert!(servers.iter().any(|s| s.name == "github"));
+        assert!(db
+            .update_server(files.id, alias(None))
+            .unwrap()
+            .alias
+            .is_none());
+        assert!(db.update_server(github.id, alias(Some("fs"))).is_ok());
+    }
+
+    #[test]
+    fn test_update_server_cwd() {
+        let db = Database::new_in_memory().unwrap();
+        let server = db
+            .create_server(CreateServerArgs {
+                name: "weather".to_string(),
+                server_type: "stdio".to_string(),
+                command: Some("node".to_string()),
+                args: Some(vec!["index.js".to_string()]),
+                url: None,
+                env: None,
+                description: None,
+            })
+            .unwrap();
+        assert!(server.cwd.is_none());
+
+        let cwd = |cwd: Option<&str>| UpdateServerArgs {
+            cwd: Some(cwd.map(str::to_string)),
+            ..Default::default()
+        };
+        let moved = db
+            .update_server(server.id.clone(), cwd(Some("/home/me/weather")))
+            .unwrap();
+        assert_eq!(moved.cwd.as_deref(), Some("/home/me/weather"));
+        assert_eq!(
+            db.get_servers().unwrap()[0].cwd.as_deref(),
+            Some("/home/me/weather")
+        );
+        assert!(db
+            .update_server(server.id, cwd(None))
+            .unwrap()
+            .cwd
+            .is_none());
+    }
+
+    #[test]
+    fn test_update_server_docker() {
+        let db = Database::new_in_memory().unwrap();
+        let server = db
+            .create_server(CreateServerArgs {
+                name: "fetch".to_string(),
+                server_type: "stdio".to_string(),
+                command: None,
+                args: None,
+                url: None,
+                env: None,
+                description: None,
+            })
+            .unwrap();
+        assert!(server.docker_image.is_none());
+
+        let mounts = vec!["/home/me/data:/data:ro".to_string()];
+        let docker = |image: Option<&str>, mounts: &[String]| UpdateServerArgs {
+            docker_image: Some(image.map(str::to_string)),
+            docker_mounts: Some(mounts.to_vec()),
+            ..Default::default()
+        };
+        let boxed = db
+            .update_server(server.id.clone(), docker(Some("mcp/fetch"), &mounts))
+            .unwrap();
+        assert_eq!(boxed.docker_image.as_deref(), Some("mcp/fetch"));
+        assert_eq!(boxed.docker_mounts, Some(mounts.clone()));
+        assert_eq!(
+            db.get_servers().unwrap()[0].docker_mounts,
+            Some(mounts.clone())
+        );
+
+        // Mounts go with the image
+        let host = db.update_server(server.id, docker(None, &mounts)).unwrap();
+        assert!(host.docker_image.is_none() && host.docker_mounts.is_none());
+    }
+
     #[test]
     fn test_server_has_timestamps() {
         let db = Database::new_in_memory().unwrap();
@@ -913,6 +2333,7 @@ mod tests {
             env: None,
             description: Some("New description".to_string()),
             is_active: None,
+            ..Default::default()
         };
 
         let updated = db.update_server(server.id, update_args).unwrap();
@@ -1243,4 +2664,309 @@ mod tests {
         assert_eq!(cached.len(), 1);
         assert_eq!(cached[0].server.name, "No Config Server");
     }
+
+    #[test]
+    fn test_app_settings_round_trip() {
+        let db = Database::new_in_memory().unwrap();
+        assert_eq!(db.get_setting("theme").unwrap(), None);
+        db.set_setting("theme", "dark").unwrap();
+        db.set_setting("theme", "light").unwrap();
+        assert_eq!(db.get_setting("theme").unwrap().as_deref(), Some("light"));
+    }
+
+    #[test]
+    fn test_registry_snapshots_keep_newest() {
+        use crate::registry_digest::SnapshotEntry;
+        let db = Database::new_in_memory().unwrap();
+        for week in 1..=4 {
+            let snapshot = RegistrySnapshot {
+                taken_at: format!("2026-01-0{}T00:00:00+00:00", week),
+                entries: vec![SnapshotEntry {
+                    name: format!("server-{}", week),
+                    version: None,
+                    source: "official".to_string(),
+                }],
+            };
+            db.save_registry_snapshot(&snapshot, 3).unwrap();
+        }
+        let snapshots = db.get_registry_snapshots(10).unwrap();
+        assert_eq!(snapshots.len(), 3);
+        assert_eq!(snapshots[0].entries[0].name, "server-4");
+        assert_eq!(snapshots[2].entries[0].name, "server-2");
+    }
+
+    #[test]
+    fn test_server_revisions_and_restore() {
+        let db = Database::new_in_memory().unwrap();
+        let server = db
+            .create_server(CreateServerArgs {
+                name: "fetch".to_string(),
+                server_type: "stdio".to_string(),
+                command: Some("uvx".to_string()),
+                args: Some(vec!["mcp-server-fetch".to_string()]),
+                ..Default::default()
+            })
+            .unwrap();
+
+        // Toggling the active flag or saving unchanged values is not a revision
+        db.update_server(
+            server.id.clone(),
+            UpdateServerArgs {
+                command: Some("uvx".to_string()),
+                is_active: Some(false),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(db.get_server_revisions(&server.id).unwrap().is_empty());
+
+        db.update_server(
+            server.id.clone(),
+            UpdateServerArgs {
+                command: Some("python".to_string()),
+                description: Some("changed".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let revisions = db.get_server_revisions(&server.id).unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].config.command.as_deref(), Some("uvx"));
+
+        let restored = db.restore_server_revision(revisions[0].id).unwrap();
+        assert_eq!(restored.command.as_deref(), Some("uvx"));
+        assert_eq!(restored.description, None);
+        assert!(!restored.is_active);
+
+        // The restore itself can be undone
+        let revisions = db.get_server_revisions(&server.id).unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].config.command.as_deref(), Some("python"));
+
+        db.delete_server(server.id.clone()).unwrap();
+        assert!(db.get_server_revisions(&server.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tool_rules_round_trip() {
+        let db = Database::new_in_memory().unwrap();
+        let server = db
+            .create_server(CreateServerArgs {
+                name: "filesystem".to_string(),
+                server_type: "stdio".to_string(),
+                command: Some("npx".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        let rename = RuleAction::Rename {
+            from: "file".to_string(),
+            to: "path".to_string(),
+        };
+        let clamp = RuleAction::Clamp {
+            key: "depth".to_string(),
+            min: None,
+            max: Some(3.0),
+        };
+        let first = db.add_tool_rule(&server.id, "*", &rename).unwrap();
+        db.add_tool_rule(&server.id, "tree", &clamp).unwrap();
+
+        let rules = db.get_tool_rules().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].id, first);
+        assert_eq!(rules[0].action, rename);
+        assert_eq!(rules[1].tool, "tree");
+        assert_eq!(rules[1].action, clamp);
+
+        db.delete_tool_rule(first).unwrap();
+        assert_eq!(db.get_tool_rules().unwrap().len(), 1);
+        db.delete_server(server.id).unwrap();
+        assert!(db.get_tool_rules().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_server_tags() {
+        let db = Database::new_in_memory().unwrap();
+        let server = db
+            .create_server(CreateServerArgs {
+                name: "filesystem".to_string(),
+                server_type: "stdio".to_string(),
+                command: Some("npx".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        db.add_server_tag(&server.id, "project:api").unwrap();
+        db.add_server_tag(&server.id, "project:web").unwrap();
+        db.add_server_tag(&server.id, "project:api").unwrap();
+        assert_eq!(
+            db.get_server_tags().unwrap()[&server.id],
+            ["project:api", "project:web"]
+        );
+        db.remove_server_tag(&server.id, "project:api").unwrap();
+        assert_eq!(db.get_server_tags().unwrap()[&server.id], ["project:web"]);
+
+        db.delete_server(server.id).unwrap();
+        assert!(db.get_server_tags().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tool_presets_round_trip() {
+        let db = Database::new_in_memory().unwrap();
+        let server = db
+            .create_server(CreateServerArgs {
+                name: "postgres".to_string(),
+                server_type: "stdio".to_string(),
+                command: Some("npx".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        let preset = |name: &str, args: &str| ToolPreset {
+            server_id: server.id.clone(),
+            tool: "query".to_string(),
+            name: name.to_string(),
+            args: args.to_string(),
+        };
+
+        db.save_tool_preset(&preset("staging database", r#"{"db":"staging"}"#))
+            .unwrap();
+        db.save_tool_preset(&preset("prod database", r#"{"db":"old"}"#))
+            .unwrap();
+        // Same name replaces
+        db.save_tool_preset(&preset("prod database", r#"{"db":"prod"}"#))
+            .unwrap();
+
+        let presets = db.get_tool_presets(&server.id, "query").unwrap();
+        let names: Vec<&str> = presets.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["prod database", "staging database"]);
+        assert_eq!(presets[0].args, r#"{"db":"prod"}"#);
+        assert!(db.get_tool_presets(&server.id, "other").unwrap().is_empty());
+        assert_eq!(
+            db.get_tool_preset(&server.id, "query", "staging database")
+                .unwrap()
+                .map(|p| p.args),
+            Some(r#"{"db":"staging"}"#.to_string())
+        );
+
+        db.delete_tool_preset(&server.id, "query", "staging database")
+            .unwrap();
+        assert_eq!(db.get_tool_presets(&server.id, "query").unwrap().len(), 1);
+
+        db.delete_server(server.id.clone()).unwrap();
+        assert!(db.get_tool_presets(&server.id, "query").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_audit_log_newest_first() {
+        let db = Database::new_in_memory().unwrap();
+        db.append_audit("failover", Some("a"), "search-a -> search-b")
+            .unwrap();
+        db.append_audit("merge", None, "merged duplicates").unwrap();
+
+        let entries = db.get_audit_log(10).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].event, "merge");
+        assert_eq!(entries[0].server_id, None);
+        assert_eq!(entries[1].server_id.as_deref(), Some("a"));
+        assert_eq!(db.get_audit_log(1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_tool_examples() {
+        let db = Database::new_in_memory().unwrap();
+        db.append_tool_call("a", "query", r#"{"q":"old"}"#, true)
+            .unwrap();
+        db.append_tool_call("a", "query", r#"{"q":"bad"}"#, false)
+            .unwrap();
+        db.append_tool_call("a", "query", r#"{"q":"new"}"#, true)
+            .unwrap();
+        db.append_tool_call("a", "query", r#"{"q":"old"}"#, true)
+            .unwrap();
+        db.append_tool_call("b", "query", r#"{"q":"other"}"#, true)
+            .unwrap();
+
+        let examples = db.get_tool_examples("a", "query", 5).unwrap();
+        assert_eq!(examples, [r#"{"q":"old"}"#, r#"{"q":"new"}"#]);
+        assert_eq!(db.get_tool_examples("a", "query", 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_usage_history() {
+        let db = Database::new_in_memory().unwrap();
+        db.append_tool_call("a", "query", "{}", true).unwrap();
+        db.append_tool_call("a", "query", "{}", false).unwrap();
+        db.start_server_run("a").unwrap();
+        db.start_server_run("b").unwrap();
+        db.end_server_run("a").unwrap();
+
+        let calls = db.get_tool_calls_since("2000-01-01 00:00:00").unwrap();
+        assert_eq!(calls.len(), 2);
+        assert!(calls[0].ok && !calls[1].ok);
+        assert!(db
+            .get_tool_calls_since("9999-01-01 00:00:00")
+            .unwrap()
+            .is_empty());
+
+        // Only b's run is still open
+        let runs = db.get_server_runs_since("9999-01-01 00:00:00").unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].server_id, "b");
+        db.close_open_server_runs().unwrap();
+        let runs = db.get_server_runs_since("2000-01-01 00:00:00").unwrap();
+        assert!(runs.iter().all(|r| r.stopped_at.is_some()));
+
+        db.prune_usage_history("9999-01-01 00:00:00").unwrap();
+        assert!(db
+            .get_tool_calls_since("2000-01-01 00:00:00")
+            .unwrap()
+            .is_empty());
+        assert!(db
+            .get_server_runs_since("2000-01-01 00:00:00")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_usage_between() {
+        let db = Database::new_in_memory().unwrap();
+        db.append_tool_call("a", "query", r#"{"q":1}"#, true)
+            .unwrap();
+        db.start_server_run("a").unwrap();
+
+        let calls = db
+            .get_tool_calls_between("2000-01-01 00:00:00", "9999-01-01 00:00:00")
+            .unwrap();
+        assert_eq!(calls[0].args.as_deref(), Some(r#"{"q":1}"#));
+        assert!(db
+            .get_tool_calls_between("2000-01-01 00:00:00", "2000-01-02 00:00:00")
+            .unwrap()
+            .is_empty());
+
+        // The open run overlaps any range after it started
+        assert_eq!(
+            db.get_server_runs_between("2000-01-01 00:00:00", "9999-01-01 00:00:00")
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(db
+            .get_server_runs_between("2000-01-01 00:00:00", "2000-01-02 00:00:00")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_official_registry_parsed_once() {
+        let first = official_registry();
+        assert!(!first.is_empty());
+        assert!(std::ptr::eq(first, official_registry()));
+    }
+
+    #[test]
+    fn test_bootstrap_registry_is_idempotent() {
+        let db = Database::new_in_memory().unwrap();
+        assert!(db.get_cached_registry(Some("official")).unwrap().is_empty());
+        db.bootstrap_registry().unwrap();
+        db.bootstrap_registry().unwrap();
+        let cached = db.get_cached_registry(Some("official")).unwrap();
+        assert_eq!(cached.len(), official_registry().len());
+    }
 }
@@ -0,0 +1,161 @@
+//! Finding servers defined more than once, typically by importing the
+//! same config twice, and planning how to merge each set into one.
+//!
+//! Servers are duplicates when they launch the same thing: the same
+//! command and args for stdio, the same URL for SSE. Names, env and
+//! descriptions may differ.
+
+use crate::models::McpServer;
+use std::collections::{BTreeMap, HashMap};
+
+/// What a server launches, ignoring surrounding whitespace.
+fn identity(server: &McpServer) -> Option<String> {
+    if server.is_remote() {
+        let url = server.url.as_deref()?.trim().trim_end_matches('/');
+        return (!url.is_empty()).then(|| format!("{}\n{}", server.server_type, url));
+    }
+    let command = server.command.as_deref()?.trim();
+    if command.is_empty() {
+        return None;
+    }
+    let args: Vec<&str> = server.args.iter().flatten().map(|a| a.trim()).collect();
+    Some(format!("stdio\n{}\n{}", command, args.join("\n")))
+}
+
+/// Sets of two or more duplicate servers, oldest first within each set.
+pub fn find_duplicates(servers: &[McpServer]) -> Vec<Vec<McpServer>> {
+    let mut groups = BTreeMap::<String, Vec<McpServer>>::new();
+    for server in servers {
+        if let Some(identity) = identity(server) {
+            groups.entry(identity).or_default().push(server.clone());
+        }
+    }
+    let mut groups: Vec<Vec<McpServer>> = groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|mut group| {
+            group.sort_by(|a, b| (&a.created_at, &a.name).cmp(&(&b.created_at, &b.name)));
+            group
+        })
+        .collect();
+    groups.sort_by(|a, b| a[0].name.cmp(&b[0].name));
+    groups
+}
+
+/// How a set of duplicates becomes one server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergePlan {
+    pub keep: McpServer,
+    pub remove: Vec<McpServer>,
+    /// Union of every env; the kept server's values win.
+    pub env: HashMap<String, String>,
+    /// The kept description, or the first one set by a duplicate.
+    pub description: Option<String>,
+    /// Env keys the duplicates set to a different value than the result.
+    pub conflicts: Vec<String>,
+}
+
+impl MergePlan {
+    /// Merge `group` into the server `keep_id`, or into the oldest one.
+    pub fn new(group: &[McpServer], keep_id: &str) -> Option<Self> {
+        let keep = group
+            .iter()
+            .find(|s| s.id == keep_id)
+            .or_else(|| group.first())?
+            .clone();
+        let remove: Vec<McpServer> = group.iter().filter(|s| s.id != keep.id).cloned().collect();
+
+        let mut env = keep.env.clone().unwrap_or_default();
+        let mut conflicts = Vec::new();
+        for (key, value) in remove.iter().filter_map(|s| s.env.as_ref()).flatten() {
+            match env.get(key) {
+                None => {
+                    env.insert(key.clone(), value.clone());
+                }
+                Some(current) if current != value && !conflicts.contains(key) => {
+                    conflicts.push(key.clone());
+                }
+                Some(_) => {}
+            }
+        }
+        conflicts.sort();
+
+        let description = std::iter::once(&keep)
+            .chain(&remove)
+            .filter_map(|s| s.description.clone())
+            .find(|d| !d.trim().is_empty());
+
+        Some(Self {
+            keep,
+            remove,
+            env,
+            description,
+            conflicts,
+        })
+    }
+
+    /// Audit log line describing the merge.
+    pub fn summary(&self) -> String {
+        let names: Vec<&str> = self.remove.iter().map(|s| s.name.as_str()).collect();
+        format!("Merged {} into {}", names.join(", "), self.keep.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_duplicates() {
+        let mut sse_a = McpServer::test_stdio("5", "");
+        sse_a.server_type = "sse".to_string();
+        sse_a.command = None;
+        sse_a.url = Some("https://example.com/sse/".to_string());
+        let mut sse_b = sse_a.clone();
+        sse_b.id = "6".to_string();
+        sse_b.url = Some("https://example.com/sse".to_string());
+
+        let servers = [
+            McpServer::test_stdio("3", "npx").with_args(&["-y", "pkg"]),
+            McpServer::test_stdio("1", "npx").with_args(&["-y", "pkg "]),
+            McpServer::test_stdio("2", "npx").with_args(&["-y", "other"]),
+            McpServer::test_stdio("4", ""),
+            sse_a,
+            sse_b,
+        ];
+        let groups = find_duplicates(&servers);
+        let ids: Vec<Vec<&str>> = groups
+            .iter()
+            .map(|g| g.iter().map(|s| s.id.as_str()).collect())
+            .collect();
+        assert_eq!(ids, [vec!["1", "3"], vec!["5", "6"]]);
+    }
+
+    #[test]
+    fn test_merge_plan() {
+        let mut newer = McpServer::test_stdio("2", "npx")
+            .named("server-2")
+            .with_args(&["pkg"])
+            .with_env(&[("KEY", "b"), ("REGION", "eu")]);
+        newer.description = Some("Imported".to_string());
+        let older = McpServer::test_stdio("1", "npx")
+            .named("server-1")
+            .with_args(&["pkg"])
+            .with_env(&[("KEY", "a")]);
+        let group = [older, newer];
+
+        let plan = MergePlan::new(&group, "1").unwrap();
+        assert_eq!(plan.keep.id, "1");
+        assert_eq!(plan.remove.len(), 1);
+        assert_eq!(plan.env["KEY"], "a");
+        assert_eq!(plan.env["REGION"], "eu");
+        assert_eq!(plan.conflicts, ["KEY"]);
+        assert_eq!(plan.description.as_deref(), Some("Imported"));
+        assert_eq!(plan.summary(), "Merged server-2 into server-1");
+
+        let plan = MergePlan::new(&group, "2").unwrap();
+        assert_eq!(plan.env["KEY"], "b");
+        // Unknown ids fall back to the oldest server
+        assert_eq!(MergePlan::new(&group, "x").unwrap().keep.id, "1");
+    }
+}
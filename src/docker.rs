@@ -0,0 +1,219 @@
+//! Running stdio servers in Docker containers, to keep untrusted servers
+//! and their toolchains off the host.
+//!
+//! A server with an image is started as `docker run -i` with its stdio
+//! attached, so it speaks MCP exactly like a local process. Its command
+//! and arguments, if any, replace the image's default command. Values of
+//! its environment reach the container through the `docker` process's
+//! own environment, named with `-e KEY`, so they never appear on a
+//! command line.
+
+use crate::models::McpServer;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Name of the container a server runs in, so a stop can remove it even
+/// when the `docker` client had to be killed.
+pub fn container_name(server_id: &str) -> String {
+    format!("open-mcp-manager-{}", server_id)
+}
+
+/// `image` trimmed, if it can be passed to `docker run`.
+pub fn check_image(image: &str) -> Result<String, String> {
+    let image = image.trim();
+    if image.is_empty() {
+        return Err("No image specified".to_string());
+    }
+    // Would be read as an option
+    if image.starts_with('-') || image.chars().any(char::is_whitespace) {
+        return Err(format!("{} is not an image name", image));
+    }
+    Ok(image.to_string())
+}
+
+/// `mount` trimmed, if it is `HOST:CONTAINER` with an optional `:ro` or
+/// `:rw`: a full path on the host and an absolute path in the container.
+pub fn check_mount(mount: &str) -> Result<String, String> {
+    let mount = mount.trim();
+    let paths = mount
+        .strip_suffix(":ro")
+        .or_else(|| mount.strip_suffix(":rw"))
+        .unwrap_or(mount);
+    // Windows hosts have a colon of their own, e.g. C:\data:/data
+    let (host, container) = paths
+        .rfind(":/")
+        .map(|at| (&paths[..at], &paths[at + 1..]))
+        .ok_or_else(|| format!("{} is not HOST:CONTAINER", mount))?;
+    if !Path::new(host).is_absolute() {
+        return Err(format!("{} is not a full path", host));
+    }
+    if container.len() < 2 {
+        return Err(format!(
+            "{} can't be mounted over the container's root",
+            host
+        ));
+    }
+    Ok(mount.to_string())
+}
+
+/// Arguments for `docker` that run `image` for server `server_id`, with
+/// `mounts`, the variables `env_keys` passed through, and `command` and
+/// `args` as the container's command.
+pub fn run_args(
+    server_id: &str,
+    image: &str,
+    mounts: &[String],
+    env_keys: &[&String],
+    command: Option<&str>,
+    args: &[String],
+) -> Vec<String> {
+    // --init passes signals on and reaps zombies, as PID 1 must
+    let mut run: Vec<String> = ["run", "-i", "--rm", "--init", "--name"]
+        .map(String::from)
+        .to_vec();
+    run.push(container_name(server_id));
+    let mut keys = env_keys.to_vec();
+    keys.sort();
+    for key in keys {
+        run.push("-e".to_string());
+        run.push(key.clone());
+    }
+    for mount in mounts {
+        run.push("-v".to_string());
+        run.push(mount.clone());
+    }
+    run.push(image.to_string());
+    run.extend(command.map(str::to_string));
+    run.extend(args.iter().cloned());
+    run
+}
+
+/// Program and arguments that start a stdio server: `docker` with
+/// [`run_args`] when it has an `image`, otherwise its own `command`. The
+/// image and mounts are checked here, whatever stored them.
+pub fn launch_command(
+    server_id: &str,
+    image: Option<&str>,
+    mounts: &[String],
+    env: &HashMap<String, String>,
+    command: Option<&str>,
+    args: &[String],
+) -> Result<(String, Vec<String>), String> {
+    let Some(image) = image else {
+        let command = command.ok_or("No command specified")?;
+        return Ok((command.to_string(), args.to_vec()));
+    };
+    let image = check_image(image)?;
+    let mounts = mounts
+        .iter()
+        .map(|m| check_mount(m))
+        .collect::<Result<Vec<_>, _>>()?;
+    let keys: Vec<&String> = env.keys().collect();
+    let command = command.filter(|c| !c.trim().is_empty());
+    let args = run_args(server_id, &image, &mounts, &keys, command, args);
+    Ok(("docker".to_string(), args))
+}
+
+/// [`launch_command`] for a saved server.
+pub fn server_command(server: &McpServer) -> Result<(String, Vec<String>), String> {
+    launch_command(
+        &server.id,
+        server.docker_image.as_deref(),
+        server.docker_mounts.as_deref().unwrap_or_default(),
+        server.env.as_ref().unwrap_or(&HashMap::new()),
+        server.command.as_deref(),
+        server.args.as_deref().unwrap_or_default(),
+    )
+}
+
+/// Force-remove the container named `name`, if there is one. A failure
+/// is only logged; most often there was nothing to remove.
+pub async fn remove(name: &str) {
+    let mut cmd = tokio::process::Command::new("docker");
+    cmd.args(["rm", "-f", name])
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    #[cfg(windows)]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    if let Err(e) = cmd.status().await {
+        tracing::debug!("Couldn't remove container {}: {}", name, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_args() {
+        let key = "API_KEY".to_string();
+        let other = "DEBUG".to_string();
+        let args = run_args(
+            "abc",
+            "mcp/fetch:latest",
+            &["/home/me/data:/data:ro".to_string()],
+            &[&key, &other],
+            None,
+            &["--verbose".to_string()],
+        );
+        assert_eq!(
+            args.join(" "),
+            "run -i --rm --init --name open-mcp-manager-abc -e API_KEY -e DEBUG -v /home/me/data:/data:ro mcp/fetch:latest --verbose"
+        );
+
+        let args = run_args("abc", "node:20", &[], &[], Some("npx"), &["-y".into()]);
+        assert_eq!(args[args.len() - 3..], ["node:20", "npx", "-y"]);
+    }
+
+    #[test]
+    fn test_launch_command() {
+        let env = HashMap::from([("API_KEY".to_string(), "secret".to_string())]);
+        let args = ["-y".to_string()];
+        assert_eq!(
+            launch_command("abc", None, &[], &env, Some("npx"), &args).unwrap(),
+            ("npx".to_string(), args.to_vec())
+        );
+        assert_eq!(
+            launch_command("abc", None, &[], &env, None, &[]).unwrap_err(),
+            "No command specified"
+        );
+
+        // The image's own command when the server has none
+        let (program, run) =
+            launch_command("abc", Some("mcp/fetch"), &[], &env, Some(" "), &[]).unwrap();
+        assert_eq!(program, "docker");
+        assert_eq!(run[run.len() - 3..], ["-e", "API_KEY", "mcp/fetch"]);
+
+        let mounts = ["data:/data".to_string()];
+        assert!(launch_command("abc", Some("mcp/fetch"), &mounts, &env, None, &[]).is_err());
+        assert!(launch_command("abc", Some("-it"), &[], &env, None, &[]).is_err());
+    }
+
+    #[test]
+    fn test_check_image() {
+        assert_eq!(check_image(" mcp/fetch ").unwrap(), "mcp/fetch");
+        assert!(check_image("").is_err());
+        assert!(check_image("--privileged").is_err());
+        assert!(check_image("mcp/fetch --privileged").is_err());
+    }
+
+    #[test]
+    fn test_check_mount() {
+        assert_eq!(
+            check_mount(" /home/me/data:/data:ro ").unwrap(),
+            "/home/me/data:/data:ro"
+        );
+        assert!(check_mount("/home/me/data:/data").is_ok());
+        assert_eq!(
+            check_mount("data:/data").unwrap_err(),
+            "data is not a full path"
+        );
+        assert!(check_mount("/home/me/data").is_err());
+        assert!(check_mount("/home/me:/").is_err());
+        assert!(check_mount("--privileged").is_err());
+    }
+}
@@ -0,0 +1,479 @@
+//! Reading and writing editor MCP configuration files.
+
+use crate::bridge::BRIDGE_SUBCOMMAND;
+use crate::models::{AppError, AppResult, CreateServerArgs, McpServer};
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+
+/// Key used for the manager's own entry in an editor's `mcpServers` map.
+pub const HUB_ENTRY_NAME: &str = "mcp-manager-hub";
+
+/// Path of the running manager binary, used as the bridge command.
+pub fn bridge_command() -> String {
+    std::env::current_exe()
+        .ok()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| "open-mcp-manager".to_string())
+}
+
+/// Entry launching the stdio bridge, for clients that only speak stdio.
+pub fn hub_bridge_entry(command: &str) -> Value {
+    json!({
+        "command": command,
+        "args": [BRIDGE_SUBCOMMAND]
+    })
+}
+
+/// Location of Claude Desktop's `claude_desktop_config.json` on this platform.
+pub fn claude_desktop_config_path() -> AppResult<PathBuf> {
+    let mut path = dirs::config_dir().ok_or(AppError::Io("Could not find config dir".into()))?;
+    path.push("Claude");
+    path.push("claude_desktop_config.json");
+    Ok(path)
+}
+
+/// User-level editor config files watched for external edits.
+pub fn known_config_paths() -> Vec<(&'static str, PathBuf)> {
+    let mut paths = Vec::new();
+    if let Ok(claude) = claude_desktop_config_path() {
+        paths.push(("Claude", claude));
+    }
+    if let Some(home) = dirs::home_dir() {
+        paths.push(("Cursor", home.join(".cursor").join("mcp.json")));
+        paths.push((
+            "Windsurf",
+            home.join(".codeium")
+                .join("windsurf")
+                .join("mcp_config.json"),
+        ));
+        paths.push((
+            "Antigravity",
+            home.join(".gemini")
+                .join("antigravity")
+                .join("mcp_config.json"),
+        ));
+    }
+    paths
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DriftKind {
+    /// Present in the editor config but unknown to the manager.
+    NotInManager,
+    /// Same name as a managed server, but a different command/args/url.
+    Changed,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriftEntry {
+    pub name: String,
+    pub kind: DriftKind,
+    pub entry: Value,
+}
+
+/// External changes found in one editor's config file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigDrift {
+    pub editor: &'static str,
+    pub path: PathBuf,
+    pub entries: Vec<DriftEntry>,
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|x| x.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Compare an editor config's `mcpServers` with the managed servers.
+/// The manager's own hub entry is ignored.
+pub fn detect_drift(config: &Value, servers: &[McpServer]) -> Vec<DriftEntry> {
+    let Some(entries) = config.get("mcpServers").and_then(|m| m.as_object()) else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .filter(|(name, _)| name.as_str() != HUB_ENTRY_NAME)
+        .filter_map(|(name, entry)| {
+            let kind = match servers.iter().find(|s| &s.name == name) {
+                None => DriftKind::NotInManager,
+                Some(server) => {
+                    // Compared with what Direct Mode would write for it
+                    let expected = direct_entry(server);
+                    let same = ["command", "url"]
+                        .iter()
+                        .all(|key| entry.get(key) == expected.get(key))
+                        && string_list(entry.get("args")) == string_list(expected.get("args"));
+                    if same {
+                        return None;
+                    }
+                    DriftKind::Changed
+                }
+            };
+            Some(DriftEntry {
+                name: name.clone(),
+                kind,
+                entry: entry.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Convert an editor `mcpServers` entry into a server definition.
+pub fn entry_to_args(name: &str, entry: &Value) -> CreateServerArgs {
+    let url = entry
+        .get("url")
+        .and_then(|u| u.as_str())
+        .map(str::to_string);
+    let args = string_list(entry.get("args"));
+    let env: std::collections::HashMap<String, String> = entry
+        .get("env")
+        .and_then(|e| e.as_object())
+        .map(|e| {
+            e.iter()
+                .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    CreateServerArgs {
+        name: name.to_string(),
+        server_type: url
+            .as_deref()
+            .map_or("stdio", crate::models::remote_server_type)
+            .to_string(),
+        command: entry
+            .get("command")
+            .and_then(|c| c.as_str())
+            .map(str::to_string),
+        args: if args.is_empty() { None } else { Some(args) },
+        url,
+        env: if env.is_empty() { None } else { Some(env) },
+        description: None,
+    }
+}
+
+/// Insert (or replace) a single server entry, leaving every other key intact.
+pub fn merge_server_entry(mut config: Value, name: &str, entry: Value) -> Value {
+    if !config.is_object() {
+        config = json!({});
+    }
+    let root = config.as_object_mut().expect("config is an object");
+    let servers = root.entry("mcpServers").or_insert_with(|| json!({}));
+    if !servers.is_object() {
+        *servers = json!({});
+    }
+    servers
+        .as_object_mut()
+        .expect("mcpServers is an object")
+        .insert(name.to_string(), entry);
+    config
+}
+
+/// Direct Mode entry launching or connecting to `server` itself.
+pub fn direct_entry(server: &McpServer) -> Value {
+    let mut entry = Map::new();
+    if server.is_remote() {
+        if let Some(url) = &server.url {
+            entry.insert("url".to_string(), json!(url));
+        }
+        if let Some(args) = &server.args {
+            entry.insert("args".to_string(), json!(args));
+        }
+    } else if let Ok((command, args)) = crate::docker::server_command(server) {
+        // Docker servers are launched through `docker run`, as the manager does
+        entry.insert("command".to_string(), json!(command));
+        if !args.is_empty() || server.args.is_some() {
+            entry.insert("args".to_string(), json!(args));
+        }
+    }
+    if let Some(env) = &server.env {
+        if !env.is_empty() {
+            entry.insert("env".to_string(), json!(env));
+        }
+    }
+    Value::Object(entry)
+}
+
+/// Edits that move an editor config between Hub Mode (one entry for the
+/// hub) and Direct Mode (one entry per managed server).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MigrationPlan {
+    /// `mcpServers` entries to delete.
+    pub remove: Vec<String>,
+    /// Entries to add, or to replace when they differ.
+    pub add: Vec<(String, Value)>,
+}
+
+impl MigrationPlan {
+    pub fn is_empty(&self) -> bool {
+        self.remove.is_empty() && self.add.is_empty()
+    }
+}
+
+/// The edits that bring `config` into Hub Mode (`to_hub`) or Direct Mode.
+///
+/// Going to the hub drops the entries of servers the hub now serves
+/// (`direct`) and adds `hub_entry`; going direct does the reverse.
+/// Entries the manager doesn't know are left alone either way.
+pub fn plan_migration(
+    config: &Value,
+    to_hub: bool,
+    hub_entry: &Value,
+    direct: &Map<String, Value>,
+) -> MigrationPlan {
+    let existing = config.get("mcpServers").and_then(|m| m.as_object());
+    let current = |name: &str| existing.and_then(|m| m.get(name));
+
+    let mut plan = MigrationPlan::default();
+    if to_hub {
+        plan.remove = direct
+            .keys()
+            .filter(|name| name.as_str() != HUB_ENTRY_NAME && current(name).is_some())
+            .cloned()
+            .collect();
+        if current(HUB_ENTRY_NAME) != Some(hub_entry) {
+            plan.add
+                .push((HUB_ENTRY_NAME.to_string(), hub_entry.clone()));
+        }
+    } else {
+        if current(HUB_ENTRY_NAME).is_some() {
+            plan.remove.push(HUB_ENTRY_NAME.to_string());
+        }
+        plan.add = direct
+            .iter()
+            .filter(|(name, entry)| current(name) != Some(*entry))
+            .map(|(name, entry)| (name.clone(), entry.clone()))
+            .collect();
+    }
+    plan
+}
+
+/// `config` with `plan` applied, every other key left intact.
+pub fn apply_migration(mut config: Value, plan: &MigrationPlan) -> Value {
+    if let Some(servers) = config.get_mut("mcpServers").and_then(|m| m.as_object_mut()) {
+        for name in &plan.remove {
+            servers.remove(name);
+        }
+    }
+    for (name, entry) in &plan.add {
+        config = merge_server_entry(config, name, entry.clone());
+    }
+    config
+}
+
+/// Apply `plan` to the config at `path`, keeping the previous file as
+/// `<file>.bak`. Returns the backup's path.
+pub fn write_migration(path: &Path, plan: &MigrationPlan) -> AppResult<PathBuf> {
+    let existing = read_config(path)?;
+    let backup = path.with_extension("json.bak");
+    if path.exists() {
+        std::fs::copy(path, &backup)?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let migrated = apply_migration(existing, plan);
+    std::fs::write(path, serde_json::to_string_pretty(&migrated)?)?;
+    Ok(backup)
+}
+
+/// Read a config file, treating a missing or empty file as `{}`.
+pub fn read_config(path: &Path) -> AppResult<Value> {
+    match std::fs::read_to_string(path) {
+        Ok(content) if content.trim().is_empty() => Ok(json!({})),
+        Ok(content) => Ok(serde_json::from_str(&content)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(json!({})),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Write `entry` under `name` into the config at `path`.
+///
+/// The previous file (if any) is kept alongside as `<file>.bak`.
+pub fn write_server_entry(path: &Path, name: &str, entry: Value) -> AppResult<()> {
+    let existing = read_config(path)?;
+    if path.exists() {
+        std::fs::copy(path, path.with_extension("json.bak"))?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let merged = merge_server_entry(existing, name, entry);
+    std::fs::write(path, serde_json::to_string_pretty(&merged)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn managed(name: &str, command: &str, args: &[&str]) -> McpServer {
+        McpServer {
+            id: name.to_string(),
+            name: name.to_string(),
+            server_type: "stdio".to_string(),
+            command: Some(command.to_string()),
+            args: Some(args.iter().map(|a| a.to_string()).collect()),
+            is_active: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_detect_drift() {
+        let config = json!({
+            "mcpServers": {
+                "mcp-manager-hub": { "command": "x", "args": ["bridge"] },
+                "memory": { "command": "npx", "args": ["-y", "memory"] },
+                "git": { "command": "uvx", "args": ["mcp-server-git", "--repo", "."] },
+                "brave": { "command": "npx", "args": ["-y", "brave"] }
+            }
+        });
+        let servers = vec![
+            managed("memory", "npx", &["-y", "memory"]),
+            managed("git", "uvx", &["mcp-server-git"]),
+        ];
+        let drift = detect_drift(&config, &servers);
+        let found: Vec<(&str, DriftKind)> =
+            drift.iter().map(|d| (d.name.as_str(), d.kind)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("brave", DriftKind::NotInManager),
+                ("git", DriftKind::Changed)
+            ]
+        );
+        assert!(detect_drift(&json!({}), &servers).is_empty());
+    }
+
+    #[test]
+    fn test_direct_entry_runs_docker() {
+        let mut server = managed("fetch", "", &[]);
+        server.docker_image = Some("mcp/fetch".to_string());
+        server.docker_mounts = Some(vec!["/srv/data:/data:ro".to_string()]);
+        let entry = direct_entry(&server);
+        assert_eq!(entry["command"], "docker");
+        assert_eq!(
+            string_list(entry.get("args")),
+            [
+                "run",
+                "-i",
+                "--rm",
+                "--init",
+                "--name",
+                "open-mcp-manager-fetch",
+                "-v",
+                "/srv/data:/data:ro",
+                "mcp/fetch"
+            ]
+        );
+        let config = json!({ "mcpServers": { "fetch": entry } });
+        assert!(detect_drift(&config, &[server]).is_empty());
+    }
+
+    #[test]
+    fn test_entry_to_args() {
+        let args = entry_to_args(
+            "brave",
+            &json!({ "command": "npx", "args": ["-y", "brave"], "env": { "BRAVE_API_KEY": "k" } }),
+        );
+        assert_eq!(args.server_type, "stdio");
+        assert_eq!(args.command.as_deref(), Some("npx"));
+        assert_eq!(args.args.unwrap(), vec!["-y", "brave"]);
+        assert_eq!(args.env.unwrap()["BRAVE_API_KEY"], "k");
+
+        let remote = entry_to_args("remote", &json!({ "url": "http://localhost:8080/sse" }));
+        assert_eq!(remote.server_type, "sse");
+        assert!(remote.command.is_none());
+    }
+
+    #[test]
+    fn test_merge_into_empty_config() {
+        let merged = merge_server_entry(json!({}), "hub", json!({"command": "x"}));
+        assert_eq!(merged["mcpServers"]["hub"]["command"], "x");
+    }
+
+    #[test]
+    fn test_merge_preserves_other_servers_and_keys() {
+        let existing = json!({
+            "globalShortcut": "Ctrl+Space",
+            "mcpServers": { "other": { "command": "npx" } }
+        });
+        let merged = merge_server_entry(existing, "hub", json!({"command": "x"}));
+        assert_eq!(merged["globalShortcut"], "Ctrl+Space");
+        assert_eq!(merged["mcpServers"]["other"]["command"], "npx");
+        assert_eq!(merged["mcpServers"]["hub"]["command"], "x");
+    }
+
+    #[test]
+    fn test_merge_replaces_existing_entry() {
+        let existing = json!({ "mcpServers": { "hub": { "url": "old" } } });
+        let merged = merge_server_entry(existing, "hub", json!({"command": "new"}));
+        assert_eq!(merged["mcpServers"]["hub"], json!({"command": "new"}));
+    }
+
+    #[test]
+    fn test_migration_round_trip() {
+        let hub = hub_bridge_entry("/bin/omm");
+        let mut direct = Map::new();
+        direct.insert(
+            "memory".to_string(),
+            direct_entry(&managed("memory", "npx", &["-y", "memory"])),
+        );
+        direct.insert(
+            "git".to_string(),
+            direct_entry(&managed("git", "uvx", &["mcp-server-git"])),
+        );
+        let config = json!({
+            "theme": "dark",
+            "mcpServers": {
+                "memory": { "command": "npx", "args": ["-y", "memory"] },
+                "other": { "command": "node", "args": ["x.js"] }
+            }
+        });
+
+        let to_hub = plan_migration(&config, true, &hub, &direct);
+        assert_eq!(to_hub.remove, vec!["memory"]);
+        assert_eq!(to_hub.add, vec![(HUB_ENTRY_NAME.to_string(), hub.clone())]);
+        let hub_config = apply_migration(config, &to_hub);
+        assert_eq!(hub_config["theme"], "dark");
+        let names: Vec<&String> = hub_config["mcpServers"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect();
+        assert_eq!(names, vec!["mcp-manager-hub", "other"]);
+        assert!(plan_migration(&hub_config, true, &hub, &direct).is_empty());
+
+        let to_direct = plan_migration(&hub_config, false, &hub, &direct);
+        assert_eq!(to_direct.remove, vec![HUB_ENTRY_NAME]);
+        assert_eq!(to_direct.add.len(), 2);
+        let direct_config = apply_migration(hub_config, &to_direct);
+        assert_eq!(direct_config["mcpServers"]["git"]["command"], "uvx");
+        assert_eq!(direct_config["mcpServers"]["other"]["command"], "node");
+        assert!(plan_migration(&direct_config, false, &hub, &direct).is_empty());
+    }
+
+    #[test]
+    fn test_write_server_entry_creates_file_and_backup() {
+        let dir = std::env::temp_dir().join(format!("omm-editor-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("claude_desktop_config.json");
+
+        write_server_entry(&path, "hub", json!({"command": "a"})).unwrap();
+        write_server_entry(&path, "hub", json!({"command": "b"})).unwrap();
+
+        let written = read_config(&path).unwrap();
+        assert_eq!(written["mcpServers"]["hub"]["command"], "b");
+        let backup = read_config(&path.with_extension("json.bak")).unwrap();
+        assert_eq!(backup["mcpServers"]["hub"]["command"], "a");
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,207 @@
+//! Answering `elicitation/create` requests, where a server asks the user
+//! for structured input mid-session. The requested schema is a flat
+//! object of primitive fields, which the manager shows as a form.
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// Params of an `elicitation/create` request.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ElicitRequest {
+    pub message: String,
+    pub requested_schema: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldKind {
+    Text,
+    Number,
+    Integer,
+    Boolean,
+    /// One of these values, shown with the matching label.
+    Choice {
+        values: Vec<String>,
+        labels: Vec<String>,
+    },
+}
+
+/// One input of the form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub kind: FieldKind,
+    pub required: bool,
+    /// The schema's default, as the form shows it.
+    pub default: String,
+}
+
+/// An elicitation request waiting for the user to fill it in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingElicitation {
+    pub server_id: String,
+    pub server_name: String,
+    /// The server's id for the request, to reply with.
+    pub request_id: Value,
+    pub message: String,
+    pub fields: Vec<Field>,
+}
+
+/// The form fields for `schema`, by name.
+pub fn fields(schema: &Value) -> Result<Vec<Field>, String> {
+    let properties = schema["properties"]
+        .as_object()
+        .ok_or("The requested schema has no properties")?;
+    let required: Vec<&str> = schema["required"]
+        .as_array()
+        .map(|r| r.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    properties
+        .iter()
+        .map(|(name, prop)| {
+            let kind = match (prop["type"].as_str(), prop["enum"].as_array()) {
+                (Some("string"), Some(values)) => {
+                    let values: Vec<String> = values
+                        .iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect();
+                    let labels = prop["enumNames"]
+                        .as_array()
+                        .map(|names| {
+                            names
+                                .iter()
+                                .filter_map(|n| n.as_str().map(str::to_string))
+                                .collect::<Vec<_>>()
+                        })
+                        .filter(|names| names.len() == values.len())
+                        .unwrap_or_else(|| values.clone());
+                    FieldKind::Choice { values, labels }
+                }
+                (Some("string"), None) => FieldKind::Text,
+                (Some("number"), _) => FieldKind::Number,
+                (Some("integer"), _) => FieldKind::Integer,
+                (Some("boolean"), _) => FieldKind::Boolean,
+                (other, _) => {
+                    return Err(format!(
+                        "Field {} has unsupported type {}",
+                        name,
+                        other.unwrap_or("(none)")
+                    ))
+                }
+            };
+            let default = match &prop["default"] {
+                Value::Null => String::new(),
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            Ok(Field {
+                name: name.clone(),
+                title: prop["title"].as_str().unwrap_or(name).to_string(),
+                description: prop["description"].as_str().map(str::to_string),
+                kind,
+                required: required.contains(&name.as_str()),
+                default,
+            })
+        })
+        .collect()
+}
+
+/// The form's `answers`, keyed by field name, as the content to return.
+/// Blank optional fields are left out.
+pub fn content(fields: &[Field], answers: &HashMap<String, String>) -> Result<Value, String> {
+    let mut content = Map::new();
+    for field in fields {
+        let answer = answers.get(&field.name).map(|a| a.trim()).unwrap_or("");
+        if answer.is_empty() && field.kind != FieldKind::Boolean {
+            if field.required {
+                return Err(format!("{} is required", field.title));
+            }
+            continue;
+        }
+        let value = match &field.kind {
+            FieldKind::Text => json!(answer),
+            FieldKind::Choice { values, .. } if values.iter().any(|v| v == answer) => json!(answer),
+            FieldKind::Choice { .. } => {
+                return Err(format!("{} has no option {}", field.title, answer))
+            }
+            FieldKind::Number => answer
+                .parse::<f64>()
+                .map(|n| json!(n))
+                .map_err(|_| format!("{} must be a number", field.title))?,
+            FieldKind::Integer => answer
+                .parse::<i64>()
+                .map(|n| json!(n))
+                .map_err(|_| format!("{} must be a whole number", field.title))?,
+            FieldKind::Boolean => json!(answer == "true"),
+        };
+        content.insert(field.name.clone(), value);
+    }
+    Ok(Value::Object(content))
+}
+
+/// The reply to an elicitation: the user's answers, or `None` when they
+/// declined.
+pub fn result(content: Option<Value>) -> Value {
+    match content {
+        Some(content) => json!({ "action": "accept", "content": content }),
+        None => json!({ "action": "decline" }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_and_content() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "title": "Your name" },
+                "size": { "type": "string", "enum": ["s", "l"], "enumNames": ["Small", "Large"] },
+                "count": { "type": "integer", "default": 2 },
+                "notify": { "type": "boolean" }
+            },
+            "required": ["name"]
+        });
+        let form = fields(&schema).unwrap();
+        assert_eq!(form.len(), 4);
+        let name = form.iter().find(|f| f.name == "name").unwrap();
+        assert_eq!(name.title, "Your name");
+        assert!(name.required);
+        let size = form.iter().find(|f| f.name == "size").unwrap();
+        assert_eq!(
+            size.kind,
+            FieldKind::Choice {
+                values: vec!["s".into(), "l".into()],
+                labels: vec!["Small".into(), "Large".into()],
+            }
+        );
+        let count = form.iter().find(|f| f.name == "count").unwrap();
+        assert_eq!(count.default, "2");
+
+        let mut answers = HashMap::new();
+        assert_eq!(
+            content(&form, &answers).unwrap_err(),
+            "Your name is required"
+        );
+        answers.insert("name".to_string(), " Ada ".to_string());
+        answers.insert("count".to_string(), "x".to_string());
+        assert_eq!(
+            content(&form, &answers).unwrap_err(),
+            "count must be a whole number"
+        );
+        answers.insert("count".to_string(), "3".to_string());
+        answers.insert("size".to_string(), "l".to_string());
+        assert_eq!(
+            content(&form, &answers).unwrap(),
+            json!({ "name": "Ada", "size": "l", "count": 3, "notify": false })
+        );
+
+        assert!(fields(&json!({ "properties": { "a": { "type": "array" } } })).is_err());
+        assert_eq!(result(None), json!({ "action": "decline" }));
+    }
+}
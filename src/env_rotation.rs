@@ -0,0 +1,68 @@
+//! Rotating a secret shared by several servers: find every server whose
+//! env sets a key, so a new value can be written to all of them at once.
+
+use crate::models::McpServer;
+use std::collections::BTreeMap;
+
+/// Env keys set by any server, with how many servers set each, by name.
+pub fn env_keys(servers: &[McpServer]) -> Vec<(String, usize)> {
+    let mut keys = BTreeMap::<String, usize>::new();
+    for env in servers.iter().filter_map(|s| s.env.as_ref()) {
+        for key in env.keys() {
+            *keys.entry(key.clone()).or_default() += 1;
+        }
+    }
+    keys.into_iter().collect()
+}
+
+/// Servers whose env sets `key`.
+pub fn servers_with_key<'a>(servers: &'a [McpServer], key: &str) -> Vec<&'a McpServer> {
+    servers
+        .iter()
+        .filter(|s| s.env.as_ref().is_some_and(|env| env.contains_key(key)))
+        .collect()
+}
+
+/// The env key to rotate, trimmed, or why it can't be used.
+pub fn validate_key(key: &str) -> Result<&str, String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("Pick an env variable".to_string());
+    }
+    if key.contains('=') || key.contains('\0') {
+        return Err(format!("Invalid env variable name: {}", key));
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_servers_by_key() {
+        let servers = [
+            McpServer::test_stdio("search", "npx")
+                .with_env(&[("BRAVE_API_KEY", "old"), ("REGION", "eu")]),
+            McpServer::test_stdio("news", "npx").with_env(&[("BRAVE_API_KEY", "old")]),
+            McpServer::test_stdio("fs", "npx"),
+        ];
+        assert_eq!(
+            env_keys(&servers),
+            vec![("BRAVE_API_KEY".to_string(), 2), ("REGION".to_string(), 1)]
+        );
+        let ids: Vec<_> = servers_with_key(&servers, "BRAVE_API_KEY")
+            .iter()
+            .map(|s| s.id.as_str())
+            .collect();
+        assert_eq!(ids, ["search", "news"]);
+        assert!(servers_with_key(&servers, "brave_api_key").is_empty());
+    }
+
+    #[test]
+    fn test_validate_key() {
+        assert_eq!(validate_key(" TOKEN "), Ok("TOKEN"));
+        assert!(validate_key("  ").is_err());
+        assert!(validate_key("A=B").is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! Temporary servers for quick experiments. A server with an expiry is
+//! stopped and archived once it passes, and is left out of exported
+//! configs in the meantime.
+//!
+//! Expiry times are stored in UTC in the database's timestamp format,
+//! like `created_at`.
+
+use crate::models::McpServer;
+use crate::weekly_report::db_time;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+
+/// Lifetimes offered when marking a server temporary.
+pub const EXPIRY_CHOICES: [(&str, i64); 3] = [("1 hour", 1), ("24 hours", 24), ("7 days", 24 * 7)];
+
+/// Expiry `hours` from `now`, as stored on the server.
+pub fn expires_at(now: DateTime<Utc>, hours: i64) -> String {
+    db_time(now + Duration::hours(hours))
+}
+
+fn parse(text: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+/// Whether `server` is temporary and its time is up. An unreadable
+/// expiry counts as not expired rather than archiving the server.
+pub fn is_expired(server: &McpServer, now: DateTime<Utc>) -> bool {
+    server
+        .expires_at
+        .as_deref()
+        .and_then(parse)
+        .is_some_and(|at| at <= now)
+}
+
+/// Time left before `server` expires, e.g. "3h left", or `None` for
+/// permanent servers.
+pub fn remaining(server: &McpServer, now: DateTime<Utc>) -> Option<String> {
+    let at = parse(server.expires_at.as_deref()?)?;
+    let mins = (at - now).num_minutes();
+    Some(match mins {
+        ..=0 => "expired".to_string(),
+        1..=59 => format!("{}m left", mins),
+        60..=2879 => format!("{}h left", mins / 60),
+        _ => format!("{}d left", mins / (60 * 24)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn temporary(expires_at: Option<String>) -> McpServer {
+        McpServer {
+            id: "1".to_string(),
+            name: "scratch".to_string(),
+            server_type: "stdio".to_string(),
+            command: Some("npx".to_string()),
+            is_active: true,
+            expires_at,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_expiry() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let server = temporary(Some(expires_at(now, 24)));
+        assert_eq!(server.expires_at.as_deref(), Some("2026-03-02 12:00:00"));
+        assert!(!is_expired(&server, now));
+        assert_eq!(remaining(&server, now).as_deref(), Some("24h left"));
+        assert_eq!(
+            remaining(&server, now + Duration::minutes(24 * 60 - 5)).as_deref(),
+            Some("5m left")
+        );
+        assert!(is_expired(&server, now + Duration::hours(24)));
+        assert_eq!(
+            remaining(&server, now + Duration::hours(25)).as_deref(),
+            Some("expired")
+        );
+
+        let permanent = temporary(None);
+        assert!(!is_expired(&permanent, now));
+        assert!(remaining(&permanent, now).is_none());
+        assert!(!is_expired(&temporary(Some("soon".to_string())), now));
+    }
+}
@@ -0,0 +1,393 @@
+//! Recognising common reasons a server fails to start, and how to fix
+//! them: the setting to change, a command that fixes it, or both.
+//!
+//! Diagnosis reads the start error and what the server printed. It only
+//! names a failure when the output clearly says so; anything else is left
+//! to the log.
+
+use crate::models::McpServer;
+use std::time::Duration;
+
+/// How long a fix command (e.g. installing Node.js) may run.
+pub const FIX_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Node.js asked for when the server doesn't say which version it needs.
+const DEFAULT_NODE_MAJOR: &str = "20";
+
+/// Programs that run on Node.js, so a version complaint is about Node.
+const NODE_PROGRAMS: [&str; 5] = ["node", "npx", "npm", "pnpm", "bunx"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Failure {
+    /// The server needs an environment variable it wasn't given.
+    MissingEnv { key: String },
+    /// The installed Node.js is older than the server needs.
+    NodeTooOld { required: Option<String> },
+    /// Something else is already listening on the server's port.
+    PortInUse { port: Option<u16> },
+    /// The command isn't installed or isn't on PATH.
+    ProgramNotFound { program: String },
+}
+
+/// A field of the server's Settings form.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingsField {
+    /// The environment variable `key`, ready to be given a value.
+    Env(String),
+    Command,
+}
+
+/// A shell command that fixes the failure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixCommand {
+    pub label: String,
+    pub command: String,
+}
+
+/// A failed start waiting for the user to fix it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingFix {
+    pub server: McpServer,
+    pub failure: Failure,
+}
+
+impl Failure {
+    pub fn title(&self) -> String {
+        match self {
+            Failure::MissingEnv { key } => format!("{} is not set", key),
+            Failure::NodeTooOld { .. } => "Node.js is too old".to_string(),
+            Failure::PortInUse { port: Some(port) } => format!("Port {} is in use", port),
+            Failure::PortInUse { port: None } => "The port is in use".to_string(),
+            Failure::ProgramNotFound { program } => format!("{} was not found", program),
+        }
+    }
+
+    pub fn explanation(&self) -> String {
+        match self {
+            Failure::MissingEnv { key } => format!(
+                "The server needs the environment variable {}, usually an API key from the service it connects to. Add it to the server's environment variables.",
+                key
+            ),
+            Failure::NodeTooOld { required: Some(version) } => format!(
+                "The server needs Node.js {} or newer. Install a current version, then start the server again.",
+                version
+            ),
+            Failure::NodeTooOld { required: None } => {
+                "The server needs a newer Node.js. Install a current version, then start the server again.".to_string()
+            }
+            Failure::PortInUse { .. } => {
+                "Another program, or another copy of this server, is already listening on its port. Stop it, or give the server a different port.".to_string()
+            }
+            Failure::ProgramNotFound { program } => format!(
+                "{} isn't installed or isn't on PATH. Install it, or point the server at the full path of the program.",
+                program
+            ),
+        }
+    }
+
+    /// The Settings field that fixes the failure, if one does.
+    pub fn field(&self) -> Option<SettingsField> {
+        match self {
+            Failure::MissingEnv { key } => Some(SettingsField::Env(key.clone())),
+            Failure::NodeTooOld { .. } => None,
+            Failure::PortInUse { .. } => Some(SettingsField::Env("PORT".to_string())),
+            Failure::ProgramNotFound { .. } => Some(SettingsField::Command),
+        }
+    }
+
+    /// A command that fixes the failure on `os` (as in
+    /// [`std::env::consts::OS`]), if there is a safe one.
+    pub fn command(&self, os: &str) -> Option<FixCommand> {
+        match self {
+            Failure::NodeTooOld { required } => node_install(os, required.as_deref()),
+            Failure::ProgramNotFound { program } if NODE_PROGRAMS.contains(&program.as_str()) => {
+                node_install(os, None)
+            }
+            Failure::ProgramNotFound { program } if program == "uvx" || program == "uv" => {
+                Some(FixCommand {
+                    label: "Install uv".to_string(),
+                    command: match os {
+                        "windows" => "powershell -ExecutionPolicy ByPass -c \"irm https://astral.sh/uv/install.ps1 | iex\"".to_string(),
+                        _ => "curl -LsSf https://astral.sh/uv/install.sh | sh".to_string(),
+                    },
+                })
+            }
+            Failure::PortInUse { port: Some(port) } => Some(FixCommand {
+                label: format!("Show what is using port {}", port),
+                command: match os {
+                    "windows" => format!("netstat -ano | findstr :{}", port),
+                    _ => format!("lsof -nP -iTCP:{} -sTCP:LISTEN", port),
+                },
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Installing Node.js the way each platform recommends. Linux
+/// distributions differ too much to pick one.
+fn node_install(os: &str, required: Option<&str>) -> Option<FixCommand> {
+    let major = required
+        .and_then(|v| v.split('.').next())
+        .unwrap_or(DEFAULT_NODE_MAJOR);
+    let command = match os {
+        "macos" => format!(
+            "brew install node@{0} && brew link --overwrite node@{0}",
+            major
+        ),
+        "windows" => "winget install --exact --id OpenJS.NodeJS.LTS".to_string(),
+        _ => return None,
+    };
+    Some(FixCommand {
+        label: "Install Node.js".to_string(),
+        command,
+    })
+}
+
+/// Why `server` failed to start, judging by `error` and its `log`.
+pub fn diagnose(server: &McpServer, error: &str, log: &str) -> Option<Failure> {
+    // `docker` for a server in a container, whose toolchain isn't the host's
+    let program = crate::docker::server_command(server)
+        .map(|(program, _)| program)
+        .unwrap_or_default();
+    let program = program.trim();
+    if !server.is_remote() && !program.is_empty() && is_not_found(error) {
+        return Some(Failure::ProgramNotFound {
+            program: program.to_string(),
+        });
+    }
+    let lines = || error.lines().chain(log.lines());
+
+    if let Some(line) = lines().find(|l| is_port_in_use(l)) {
+        return Some(Failure::PortInUse {
+            port: port_in(line),
+        });
+    }
+
+    let program_name = program
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(program)
+        .trim_end_matches(".cmd")
+        .trim_end_matches(".exe");
+    if NODE_PROGRAMS.contains(&program_name) && lines().any(is_node_version_error) {
+        // npm names the version on a later line than the warning
+        let required = lines()
+            .filter(|l| is_node_version_error(l))
+            .find_map(required_node);
+        return Some(Failure::NodeTooOld { required });
+    }
+
+    let env = server.env.clone().unwrap_or_default();
+    lines()
+        .filter(|l| is_missing_env_message(l))
+        .flat_map(env_names)
+        .find(|key| env.get(key).is_none_or(|v| v.trim().is_empty()))
+        .map(|key| Failure::MissingEnv { key })
+}
+
+fn is_not_found(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("no such file or directory")
+        || error.contains("cannot find the file")
+        || error.contains("program not found")
+}
+
+fn is_port_in_use(line: &str) -> bool {
+    let line = line.to_lowercase();
+    line.contains("eaddrinuse")
+        || line.contains("address already in use")
+        || line.contains("port is already allocated")
+        || line.contains("only one usage of each socket address")
+}
+
+/// The port in e.g. `listen EADDRINUSE: address already in use :::3000`
+/// or `port 8080 is already in use`.
+fn port_in(line: &str) -> Option<u16> {
+    let lower = line.to_lowercase();
+    let starts = lower
+        .match_indices(':')
+        .map(|(i, m)| i + m.len())
+        .chain(lower.match_indices("port ").map(|(i, m)| i + m.len()));
+    starts
+        .filter_map(|start| {
+            let digits: String = lower[start..]
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            digits.parse::<u16>().ok()
+        })
+        .filter(|port| *port > 0)
+        .last()
+}
+
+fn is_node_version_error(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    let asks_for_newer = (lower.contains("requires node") || lower.contains("node.js version"))
+        && (lower.contains(">=") || lower.contains("or higher") || lower.contains("or newer"));
+    lower.contains("ebadengine")
+        || lower.contains("unsupported engine")
+        || asks_for_newer
+        || (lower.contains("engine \"node\"") && lower.contains("incompatible"))
+}
+
+/// The minimum version in e.g. `required: { node: '>=20.0.0' }` or
+/// `requires Node.js 18 or higher`.
+fn required_node(line: &str) -> Option<String> {
+    // Search and slice the same string: lowercasing can change byte offsets
+    let lower = line.to_lowercase();
+    let after = match lower.find(">=") {
+        Some(i) => &lower[i + 2..],
+        None => &lower[lower.find("node")?..],
+    };
+    let start = after.find(|c: char| c.is_ascii_digit())?;
+    let version: String = after[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    Some(version.trim_end_matches('.').to_string())
+}
+
+fn is_missing_env_message(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    [
+        "missing",
+        "not set",
+        "is required",
+        "are required",
+        "must be set",
+        "must be provided",
+        "not defined",
+        "not found in environment",
+        "undefined",
+    ]
+    .iter()
+    .any(|phrase| lower.contains(phrase))
+}
+
+/// Words in `line` that look like environment variable names, e.g.
+/// `BRAVE_API_KEY`.
+fn env_names(line: &str) -> Vec<String> {
+    line.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .filter(|word| {
+            word.contains('_')
+                && word.len() >= 4
+                && word.starts_with(|c: char| c.is_ascii_uppercase())
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_env() {
+        let log = "[stderr] Error: BRAVE_API_KEY environment variable is required\n";
+        let failure = diagnose(
+            &McpServer::test_stdio("1", "npx"),
+            "failed the MCP handshake",
+            log,
+        )
+        .unwrap();
+        assert_eq!(
+            failure,
+            Failure::MissingEnv {
+                key: "BRAVE_API_KEY".to_string()
+            }
+        );
+        assert_eq!(
+            failure.field(),
+            Some(SettingsField::Env("BRAVE_API_KEY".to_string()))
+        );
+        assert_eq!(failure.command("macos"), None);
+
+        // Already set, so the message is about something else
+        let configured = McpServer::test_stdio("1", "npx").with_env(&[("BRAVE_API_KEY", "key")]);
+        assert_eq!(diagnose(&configured, "failed", log), None);
+        assert_eq!(
+            diagnose(
+                &McpServer::test_stdio("1", "npx"),
+                "failed",
+                "[stderr] Listening on stdio"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_node_too_old() {
+        let log = "npm WARN EBADENGINE Unsupported engine {\nnpm WARN EBADENGINE   required: { node: '>=20.0.0' },";
+        let failure = diagnose(&McpServer::test_stdio("1", "npx"), "failed", log).unwrap();
+        assert_eq!(
+            failure,
+            Failure::NodeTooOld {
+                required: Some("20.0.0".to_string())
+            }
+        );
+        let install = failure.command("macos").unwrap();
+        assert_eq!(
+            install.command,
+            "brew install node@20 && brew link --overwrite node@20"
+        );
+        assert!(failure.command("linux").is_none());
+        assert_eq!(
+            required_node("This server requires Node.js 18 or higher").as_deref(),
+            Some("18")
+        );
+        // Lowercasing `İ` adds a byte each, which used to push the slice
+        // past the end of the original line
+        assert_eq!(required_node("İİİİİİİİ node 18").as_deref(), Some("18"));
+        // Python servers aren't asked about Node
+        assert_eq!(
+            diagnose(&McpServer::test_stdio("1", "uvx"), "failed", log),
+            None
+        );
+    }
+
+    #[test]
+    fn test_port_in_use() {
+        let log = "Error: listen EADDRINUSE: address already in use :::3000";
+        let failure = diagnose(&McpServer::test_stdio("1", "node"), "failed", log).unwrap();
+        assert_eq!(failure, Failure::PortInUse { port: Some(3000) });
+        assert_eq!(
+            failure.command("linux").unwrap().command,
+            "lsof -nP -iTCP:3000 -sTCP:LISTEN"
+        );
+        assert_eq!(
+            failure.field(),
+            Some(SettingsField::Env("PORT".to_string()))
+        );
+        assert_eq!(port_in("OSError: [Errno 98] Address already in use"), None);
+    }
+
+    #[test]
+    fn test_program_not_found() {
+        let failure = diagnose(
+            &McpServer::test_stdio("1", "uvx"),
+            "No such file or directory (os error 2)",
+            "",
+        )
+        .unwrap();
+        assert_eq!(
+            failure,
+            Failure::ProgramNotFound {
+                program: "uvx".to_string()
+            }
+        );
+        assert_eq!(failure.field(), Some(SettingsField::Command));
+        assert_eq!(failure.command("linux").unwrap().label, "Install uv");
+        assert!(diagnose(
+            &McpServer::test_stdio("1", "my-tool"),
+            "No such file or directory (os error 2)",
+            ""
+        )
+        .unwrap()
+        .command("macos")
+        .is_none());
+    }
+}
@@ -0,0 +1,49 @@
+//! JSON-RPC message framing over stdio.
+//!
+//! MCP servers normally send one JSON message per line, but a few use
+//! LSP-style framing: `Content-Length: N` headers, a blank line, then
+//! exactly N bytes of JSON. Both are read; writes switch to headers once
+//! a server is seen using them (or is configured to).
+
+/// The length from a `Content-Length: N` header line, if it is one.
+/// Header names are case-insensitive.
+pub fn content_length(line: &[u8]) -> Option<usize> {
+    let line = std::str::from_utf8(line).ok()?;
+    let (name, value) = line.split_once(':')?;
+    if !name.trim().eq_ignore_ascii_case("content-length") {
+        return None;
+    }
+    value.trim().parse().ok()
+}
+
+/// `json` ready to write to a server's stdin.
+pub fn frame(json: &str, content_length: bool) -> String {
+    if content_length {
+        format!("Content-Length: {}\r\n\r\n{}", json.len(), json)
+    } else {
+        format!("{}\n", json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_length_header() {
+        assert_eq!(content_length(b"Content-Length: 42"), Some(42));
+        assert_eq!(content_length(b"content-length:7"), Some(7));
+        assert_eq!(content_length(b"Content-Type: application/json"), None);
+        assert_eq!(content_length(br#"{"jsonrpc":"2.0","id":1}"#), None);
+        assert_eq!(content_length(b"Content-Length: lots"), None);
+    }
+
+    #[test]
+    fn test_frame() {
+        assert_eq!(frame("{}", false), "{}\n");
+        assert_eq!(
+            frame(r#"{"a":"é"}"#, true),
+            "Content-Length: 10\r\n\r\n{\"a\":\"é\"}"
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Relationship graph between editors, servers and the runtimes servers
+//! depend on, with a small force-directed layout for drawing it.
+
+use crate::editor_config::HUB_ENTRY_NAME;
+use crate::models::McpServer;
+use serde_json::Value;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeKind {
+    Editor,
+    /// The manager's own hub entry, which serves every running server.
+    Hub,
+    Server,
+    Runtime,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub id: String,
+    pub label: String,
+    pub kind: NodeKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graph {
+    pub nodes: Vec<GraphNode>,
+    /// Pairs of indices into `nodes`.
+    pub edges: Vec<(usize, usize)>,
+}
+
+/// Runtime a server's command needs installed, if any.
+pub fn runtime_for(server: &McpServer) -> Option<&'static str> {
+    if server.is_remote() {
+        return None;
+    }
+    let command = server.command.as_deref()?;
+    let stem = Path::new(command)
+        .file_stem()?
+        .to_string_lossy()
+        .to_lowercase();
+    match stem.as_str() {
+        "npx" | "node" | "npm" | "pnpm" => Some("Node.js"),
+        "uvx" | "uv" | "python" | "python3" | "pip" | "pipx" => Some("Python"),
+        "docker" | "podman" => Some("Docker"),
+        "bun" | "bunx" => Some("Bun"),
+        "deno" => Some("Deno"),
+        _ => None,
+    }
+}
+
+impl Graph {
+    fn add_node(&mut self, id: String, label: String, kind: NodeKind) -> usize {
+        if let Some(i) = self.nodes.iter().position(|n| n.id == id) {
+            return i;
+        }
+        self.nodes.push(GraphNode { id, label, kind });
+        self.nodes.len() - 1
+    }
+
+    /// Indices of nodes sharing an edge with `node`.
+    pub fn neighbours(&self, node: usize) -> Vec<usize> {
+        self.edges
+            .iter()
+            .filter_map(|&(a, b)| match (a == node, b == node) {
+                (true, _) => Some(b),
+                (_, true) => Some(a),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Build the graph from the manager's servers and each editor's parsed
+/// config file. Editors reach servers directly by name or via the hub.
+pub fn build_graph(servers: &[McpServer], editor_configs: &[(&str, Value)]) -> Graph {
+    let mut graph = Graph {
+        nodes: Vec::new(),
+        edges: Vec::new(),
+    };
+
+    let server_nodes: Vec<usize> = servers
+        .iter()
+        .map(|s| graph.add_node(format!("server:{}", s.id), s.name.clone(), NodeKind::Server))
+        .collect();
+
+    for (server, &node) in servers.iter().zip(&server_nodes) {
+        if let Some(runtime) = runtime_for(server) {
+            let rt = graph.add_node(
+                format!("runtime:{}", runtime),
+                runtime.to_string(),
+                NodeKind::Runtime,
+            );
+            graph.edges.push((node, rt));
+        }
+    }
+
+    for (editor, config) in editor_configs {
+        let Some(entries) = config.get("mcpServers").and_then(Value::as_object) else {
+            continue;
+        };
+        let editor_node = graph.add_node(
+            format!("editor:{}", editor),
+            editor.to_string(),
+            NodeKind::Editor,
+        );
+        for name in entries.keys() {
+            if name == HUB_ENTRY_NAME {
+                let hub =
+                    graph.add_node("hub".to_string(), "Manager hub".to_string(), NodeKind::Hub);
+                graph.edges.push((editor_node, hub));
+                if graph.neighbours(hub).len() == 1 {
+                    graph.edges.extend(server_nodes.iter().map(|&s| (hub, s)));
+                }
+            } else if let Some(i) = servers.iter().position(|s| &s.name == name) {
+                graph.edges.push((editor_node, server_nodes[i]));
+            }
+        }
+    }
+    graph
+}
+
+/// Force-directed positions within `width` x `height`: edges pull nodes
+/// together, all nodes push apart. Starts from a circle so the result is
+/// deterministic.
+pub fn force_layout(graph: &Graph, width: f64, height: f64, iterations: usize) -> Vec<(f64, f64)> {
+    let n = graph.nodes.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let (cx, cy) = (width / 2.0, height / 2.0);
+    let radius = width.min(height) / 3.0;
+    let mut pos: Vec<(f64, f64)> = (0..n)
+        .map(|i| {
+            let angle = i as f64 / n as f64 * std::f64::consts::TAU;
+            (cx + radius * angle.cos(), cy + radius * angle.sin())
+        })
+        .collect();
+
+    // Ideal edge length for the available area
+    let k = (width * height / n as f64).sqrt() * 0.6;
+    let margin = 40.0;
+    let mut temperature = width / 10.0;
+
+    for _ in 0..iterations {
+        let mut disp = vec![(0.0, 0.0); n];
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let (dx, dy) = (pos[i].0 - pos[j].0, pos[i].1 - pos[j].1);
+                let dist = (dx * dx + dy * dy).sqrt().max(0.01);
+                let force = k * k / dist;
+                let (fx, fy) = (dx / dist * force, dy / dist * force);
+                disp[i].0 += fx;
+                disp[i].1 += fy;
+                disp[j].0 -= fx;
+                disp[j].1 -= fy;
+            }
+        }
+        for &(a, b) in &graph.edges {
+            let (dx, dy) = (pos[a].0 - pos[b].0, pos[a].1 - pos[b].1);
+            let dist = (dx * dx + dy * dy).sqrt().max(0.01);
+            let force = dist * dist / k;
+            let (fx, fy) = (dx / dist * force, dy / dist * force);
+            disp[a].0 -= fx;
+            disp[a].1 -= fy;
+            disp[b].0 += fx;
+            disp[b].1 += fy;
+        }
+        for i in 0..n {
+            // Gentle pull to the centre keeps disconnected parts on screen
+            disp[i].0 += (cx - pos[i].0) * 0.05;
+            disp[i].1 += (cy - pos[i].1) * 0.05;
+            let len = (disp[i].0 * disp[i].0 + disp[i].1 * disp[i].1)
+                .sqrt()
+                .max(0.01);
+            let step = len.min(temperature);
+            pos[i].0 = (pos[i].0 + disp[i].0 / len * step).clamp(margin, width - margin);
+            pos[i].1 = (pos[i].1 + disp[i].1 / len * step).clamp(margin, height - margin);
+        }
+        temperature *= 0.95;
+    }
+    pos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn label_edges(graph: &Graph) -> Vec<(String, String)> {
+        let mut edges: Vec<_> = graph
+            .edges
+            .iter()
+            .map(|&(a, b)| (graph.nodes[a].label.clone(), graph.nodes[b].label.clone()))
+            .collect();
+        edges.sort();
+        edges
+    }
+
+    #[test]
+    fn test_build_graph() {
+        let servers = vec![
+            McpServer::test_stdio("1", "npx").named("github"),
+            McpServer::test_stdio("2", "uvx").named("fetch"),
+            McpServer::test_stdio("3", "/opt/bin/local-mcp").named("local"),
+        ];
+        let configs = vec![
+            (
+                "Cursor",
+                json!({ "mcpServers": { "github": {}, "unknown": {} } }),
+            ),
+            ("Claude", json!({ "mcpServers": { HUB_ENTRY_NAME: {} } })),
+            ("Windsurf", json!({ "mcpServers": { HUB_ENTRY_NAME: {} } })),
+            ("Broken", json!("not a config")),
+        ];
+        let graph = build_graph(&servers, &configs);
+
+        assert_eq!(
+            label_edges(&graph),
+            vec![
+                ("Claude".to_string(), "Manager hub".to_string()),
+                ("Cursor".to_string(), "github".to_string()),
+                ("Manager hub".to_string(), "fetch".to_string()),
+                ("Manager hub".to_string(), "github".to_string()),
+                ("Manager hub".to_string(), "local".to_string()),
+                ("Windsurf".to_string(), "Manager hub".to_string()),
+                ("fetch".to_string(), "Python".to_string()),
+                ("github".to_string(), "Node.js".to_string()),
+            ]
+        );
+        assert!(!graph.nodes.iter().any(|n| n.label == "Broken"));
+    }
+
+    #[test]
+    fn test_force_layout_stays_in_bounds_and_separates_nodes() {
+        let servers: Vec<McpServer> = (0..6)
+            .map(|i| McpServer::test_stdio(&i.to_string(), "npx").named(&format!("s{}", i)))
+            .collect();
+        let graph = build_graph(&servers, &[]);
+        let pos = force_layout(&graph, 800.0, 500.0, 200);
+        assert_eq!(pos.len(), graph.nodes.len());
+        for &(x, y) in &pos {
+            assert!((40.0..=760.0).contains(&x) && (40.0..=460.0).contains(&y));
+        }
+        for i in 0..pos.len() {
+            for j in (i + 1)..pos.len() {
+                let d = ((pos[i].0 - pos[j].0).powi(2) + (pos[i].1 - pos[j].1).powi(2)).sqrt();
+                assert!(d > 20.0, "nodes {} and {} overlap", i, j);
+            }
+        }
+    }
+}
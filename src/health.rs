@@ -0,0 +1,226 @@
+//! `/healthz` and `/readyz` for external uptime monitors.
+//!
+//! A tiny HTTP/1.1 listener on localhost answers each request with a
+//! JSON report of the manager and every server, built fresh per request
+//! so it never goes stale. Liveness (`/healthz`) answers 200 whenever the
+//! manager is up; readiness (`/readyz`) answers 503 until the database is
+//! loaded and while any running server's circuit breaker is open.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Largest request head read before answering.
+const MAX_REQUEST: usize = 8 * 1024;
+
+/// How long a client gets to send its request.
+const READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ServerStatus {
+    Stopped,
+    Running,
+    /// Running, but calls are paused after repeated failures.
+    Unhealthy,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ServerHealth {
+    pub id: String,
+    pub name: String,
+    pub status: ServerStatus,
+    /// Share of hub calls that failed within the error budget window.
+    pub error_rate_percent: u32,
+    pub calls: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthReport {
+    /// Whether the manager has finished loading its database.
+    pub ready: bool,
+    pub servers: Vec<ServerHealth>,
+}
+
+impl HealthReport {
+    fn is_degraded(&self) -> bool {
+        self.servers
+            .iter()
+            .any(|s| s.status == ServerStatus::Unhealthy)
+    }
+
+    fn body(&self, status: &str) -> Value {
+        json!({
+            "status": status,
+            "version": env!("CARGO_PKG_VERSION"),
+            "ready": self.ready,
+            "servers": self.servers,
+        })
+    }
+}
+
+/// The status code and JSON body for `method` and `path`.
+pub fn respond(method: &str, path: &str, report: &HealthReport) -> (u16, Value) {
+    if method != "GET" && method != "HEAD" {
+        return (405, json!({ "error": "Method not allowed" }));
+    }
+    // Monitors often add a cache-busting query
+    let path = path.split('?').next().unwrap_or(path);
+    match path {
+        "/healthz" => {
+            let status = if report.is_degraded() {
+                "degraded"
+            } else {
+                "ok"
+            };
+            (200, report.body(status))
+        }
+        "/readyz" if report.ready && !report.is_degraded() => (200, report.body("ready")),
+        "/readyz" => (503, report.body("not ready")),
+        _ => (404, json!({ "error": "Not found" })),
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        503 => "Service Unavailable",
+        _ => "Bad Request",
+    }
+}
+
+/// A complete HTTP/1.1 response; HEAD gets the headers only.
+fn http_response(method: &str, status: u16, body: &Value) -> String {
+    let body = body.to_string();
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        status,
+        reason(status),
+        body.len()
+    );
+    if method == "HEAD" {
+        head
+    } else {
+        head + &body
+    }
+}
+
+/// Pause after a failed `accept`, e.g. when out of file descriptors, so
+/// the loop doesn't spin.
+const ACCEPT_BACKOFF: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Answer requests on `listener` until the task is dropped, calling
+/// `report` for each one. Each connection is handled in its own task, so a
+/// slow client doesn't hold up the rest.
+pub async fn serve(listener: TcpListener, report: impl Fn() -> HealthReport) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                tracing::debug!("Health endpoint accept failed: {}", e);
+                tokio::time::sleep(ACCEPT_BACKOFF).await;
+                continue;
+            }
+        };
+        // Built here, where app state can be read, then handed to the task
+        tokio::spawn(answer(stream, report()));
+    }
+}
+
+/// Read one request from `stream` and answer it from `report`.
+async fn answer(mut stream: TcpStream, report: HealthReport) {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    let read_head = async {
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST {
+            match stream.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => request.extend_from_slice(&buf[..n]),
+            }
+        }
+    };
+    // A client that never finishes its request is dropped
+    if tokio::time::timeout(READ_TIMEOUT, read_head).await.is_err() {
+        return;
+    }
+    let line = String::from_utf8_lossy(&request);
+    let mut parts = line.lines().next().unwrap_or("").split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let (status, body) = respond(method, path, &report);
+    let _ = stream
+        .write_all(http_response(method, status, &body).as_bytes())
+        .await;
+    let _ = stream.shutdown().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(status: ServerStatus) -> HealthReport {
+        HealthReport {
+            ready: true,
+            servers: vec![ServerHealth {
+                id: "1".into(),
+                name: "files".into(),
+                status,
+                error_rate_percent: 0,
+                calls: 0,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_respond() {
+        let (status, body) = respond("GET", "/healthz", &report(ServerStatus::Running));
+        assert_eq!(status, 200);
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["servers"][0]["status"], "running");
+        assert_eq!(
+            respond("GET", "/readyz?t=1", &report(ServerStatus::Stopped)).0,
+            200
+        );
+
+        let unhealthy = report(ServerStatus::Unhealthy);
+        assert_eq!(
+            respond("GET", "/healthz", &unhealthy).1["status"],
+            "degraded"
+        );
+        assert_eq!(respond("GET", "/readyz", &unhealthy).0, 503);
+        let loading = HealthReport {
+            ready: false,
+            servers: Vec::new(),
+        };
+        assert_eq!(respond("GET", "/readyz", &loading).0, 503);
+
+        assert_eq!(respond("GET", "/", &loading).0, 404);
+        assert_eq!(respond("POST", "/healthz", &loading).0, 405);
+    }
+
+    #[tokio::test]
+    async fn test_serve() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, || report(ServerStatus::Running)));
+
+        // A client that sends nothing doesn't keep the next one waiting
+        let _idle = TcpStream::connect(addr).await.unwrap();
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /healthz HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        tokio::time::timeout(READ_TIMEOUT / 2, stream.read_to_string(&mut response))
+            .await
+            .expect("answered while the idle client is still connected")
+            .unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        let body = response.split("\r\n\r\n").nth(1).unwrap();
+        let body: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["servers"][0]["name"], "files");
+    }
+}
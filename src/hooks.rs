@@ -0,0 +1,103 @@
+//! Commands run around a server's lifetime: a pre-start hook that brings
+//! up something the server needs (a local database, a mounted volume) and
+//! a post-stop hook that tears it down again.
+//!
+//! Hooks run through the platform shell with the server's env. A hook
+//! that leaves a service running must detach it from its output (e.g.
+//! `db-server > /dev/null 2>&1 &`), or it is waited on until it times out.
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+/// How long a hook may run before it is killed and counts as failed.
+pub const HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// What a hook printed, and why it failed if it did.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HookRun {
+    /// Stdout then stderr, one entry per line.
+    pub output: Vec<String>,
+    pub error: Option<String>,
+}
+
+fn shell(command: &str) -> Command {
+    #[cfg(windows)]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        cmd
+    }
+    #[cfg(not(windows))]
+    {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+fn lines(bytes: &[u8]) -> impl Iterator<Item = String> + '_ {
+    bytes
+        .split(|b| *b == b'\n')
+        .map(|line| String::from_utf8_lossy(line).trim_end().to_string())
+        .filter(|line| !line.is_empty())
+}
+
+/// Run `command` with `env` added to the environment, killing it after
+/// `timeout`. Output printed before a timeout is lost.
+pub async fn run(command: &str, env: &HashMap<String, String>, timeout: Duration) -> HookRun {
+    let mut cmd = shell(command);
+    cmd.envs(env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            return HookRun {
+                output: Vec::new(),
+                error: Some(e.to_string()),
+            }
+        }
+    };
+
+    match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Err(_) => HookRun {
+            output: Vec::new(),
+            error: Some(format!("timed out after {}s", timeout.as_secs())),
+        },
+        Ok(Err(e)) => HookRun {
+            output: Vec::new(),
+            error: Some(e.to_string()),
+        },
+        Ok(Ok(out)) => HookRun {
+            output: lines(&out.stdout).chain(lines(&out.stderr)).collect(),
+            error: (!out.status.success()).then(|| format!("exited with {}", out.status)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_hook() {
+        let env = HashMap::from([("DB_PORT".to_string(), "5433".to_string())]);
+        let ok = run("echo up on $DB_PORT; echo warming >&2", &env, HOOK_TIMEOUT).await;
+        assert_eq!(ok.output, vec!["up on 5433", "warming"]);
+        assert_eq!(ok.error, None);
+
+        let failed = run("echo no volume; exit 3", &env, HOOK_TIMEOUT).await;
+        assert_eq!(failed.output, vec!["no volume"]);
+        assert!(failed.error.unwrap().contains('3'));
+
+        let slow = run("sleep 5", &env, Duration::from_secs(1)).await;
+        assert_eq!(slow.error.as_deref(), Some("timed out after 1s"));
+    }
+}
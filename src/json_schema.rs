@@ -0,0 +1,126 @@
+//! Validating JSON against the subset of JSON Schema the app relies on:
+//! tool input schemas, server definitions and registry entries.
+
+use serde_json::Value;
+
+fn type_matches(value: &Value, ty: &str) -> bool {
+    match ty {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+/// Validate `value` against the subset of JSON Schema used by MCP tool
+/// input schemas (`type`, `enum`, `required`, `properties`,
+/// `additionalProperties: false`, `items`). Returns human-readable messages.
+pub fn validate_against_schema(value: &Value, schema: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    validate_at(value, schema, "$", &mut errors);
+    errors
+}
+
+fn validate_at(value: &Value, schema: &Value, path: &str, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+
+    if let Some(ty) = schema.get("type") {
+        let allowed: Vec<&str> = match ty {
+            Value::String(s) => vec![s.as_str()],
+            Value::Array(a) => a.iter().filter_map(|t| t.as_str()).collect(),
+            _ => vec![],
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|t| type_matches(value, t)) {
+            errors.push(format!("{}: expected {}", path, allowed.join(" or ")));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            let opts: Vec<String> = options.iter().map(|o| o.to_string()).collect();
+            errors.push(format!("{}: must be one of {}", path, opts.join(", ")));
+        }
+    }
+
+    if let Some(obj) = value.as_object() {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for key in required.iter().filter_map(|k| k.as_str()) {
+                if !obj.contains_key(key) {
+                    errors.push(format!("{}: missing required property '{}'", path, key));
+                }
+            }
+        }
+        let props = schema.get("properties").and_then(|p| p.as_object());
+        for (key, child) in obj {
+            let child_path = format!("{}.{}", path, key);
+            match props.and_then(|p| p.get(key)) {
+                Some(child_schema) => validate_at(child, child_schema, &child_path, errors),
+                None => {
+                    if schema.get("additionalProperties") == Some(&Value::Bool(false)) {
+                        errors.push(format!("{}: unknown property", child_path));
+                    }
+                }
+            }
+        }
+    }
+
+    if let (Some(items), Some(item_schema)) = (value.as_array(), schema.get("items")) {
+        for (idx, item) in items.iter().enumerate() {
+            validate_at(item, item_schema, &format!("{}[{}]", path, idx), errors);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validate_required_and_types() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "query": {"type": "string"},
+                "limit": {"type": "integer"}
+            },
+            "required": ["query"]
+        });
+        assert!(validate_against_schema(&json!({"query": "x", "limit": 3}), &schema).is_empty());
+
+        let errors = validate_against_schema(&json!({"limit": "3"}), &schema);
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("missing required property 'query'")));
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("$.limit: expected integer")));
+    }
+
+    #[test]
+    fn test_validate_enum_items_and_additional_properties() {
+        let schema = json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "mode": {"enum": ["fast", "slow"]},
+                "tags": {"type": "array", "items": {"type": "string"}}
+            }
+        });
+        let errors = validate_against_schema(
+            &json!({"mode": "medium", "tags": ["a", 1], "x": 1}),
+            &schema,
+        );
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().any(|e| e.starts_with("$.mode")));
+        assert!(errors.iter().any(|e| e.starts_with("$.tags[1]")));
+        assert!(errors.iter().any(|e| e == "$.x: unknown property"));
+    }
+}
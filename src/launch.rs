@@ -0,0 +1,264 @@
+//! What starting a stdio server actually runs: the program as found on
+//! PATH, its arguments, working directory and full environment. Used by
+//! the "Preview launch" panel to debug env issues without spawning.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchPlan {
+    pub program: String,
+    /// Where `program` was found, or None if it can't be found.
+    pub resolved_program: Option<PathBuf>,
+    pub args: Vec<String>,
+    /// The server's own working directory, or else the manager's.
+    pub cwd: Option<PathBuf>,
+    /// The complete environment the process starts with.
+    pub env: BTreeMap<String, String>,
+    /// Keys set by the server config rather than inherited.
+    pub configured_keys: Vec<String>,
+}
+
+/// Inherited variables a clean environment keeps: enough to find
+/// programs, a home and temp directory, and for Windows to work at all.
+pub const CLEAN_ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LANG",
+    "TMPDIR",
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "SYSTEMROOT",
+    "TEMP",
+    "TMP",
+    "PATHEXT",
+    "COMSPEC",
+];
+
+/// The environment a child inherits: all of `inherited`, or only the
+/// allowlisted variables when `clean` is set.
+pub fn base_env(
+    clean: bool,
+    inherited: impl IntoIterator<Item = (String, String)>,
+) -> Vec<(String, String)> {
+    inherited
+        .into_iter()
+        .filter(|(key, _)| {
+            // Windows variable names are case-insensitive
+            !clean || CLEAN_ENV_ALLOWLIST.contains(&key.to_uppercase().as_str())
+        })
+        .collect()
+}
+
+/// Plan a launch the way [`crate::process::McpProcess::start`] spawns:
+/// the inherited `base_env` (see [`base_env`]) with the server's own
+/// variables on top, in `cwd` if it has one.
+pub fn plan(
+    command: &str,
+    args: &[String],
+    env: &HashMap<String, String>,
+    cwd: Option<&Path>,
+    base_env: impl IntoIterator<Item = (String, String)>,
+) -> Result<LaunchPlan, String> {
+    let program = command.trim();
+    if program.is_empty() {
+        return Err("No command specified".to_string());
+    }
+    let mut merged: BTreeMap<String, String> = base_env.into_iter().collect();
+    merged.extend(env.iter().map(|(k, v)| (k.clone(), v.clone())));
+    let path_var = merged.get("PATH").cloned();
+    let mut configured_keys: Vec<String> = env.keys().cloned().collect();
+    configured_keys.sort();
+
+    let cwd = cwd
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::current_dir().ok());
+    // A relative path to the program starts from the working directory
+    let located = match &cwd {
+        Some(dir) if Path::new(program).components().count() > 1 => {
+            dir.join(program).display().to_string()
+        }
+        _ => program.to_string(),
+    };
+
+    let resolved_program = match crate::process::resolve_command(
+        program,
+        path_var.as_deref(),
+        crate::process::known_fallback_dirs(),
+    ) {
+        Some(resolved) => {
+            if let Some(path) = resolved.path {
+                merged.insert("PATH".to_string(), path.to_string_lossy().into_owned());
+            }
+            Some(resolved.program)
+        }
+        None => resolve_program(&located, path_var.as_deref()),
+    };
+
+    Ok(LaunchPlan {
+        program: program.to_string(),
+        resolved_program,
+        args: args.to_vec(),
+        cwd,
+        env: merged,
+        configured_keys,
+    })
+}
+
+/// Find `program` the way the OS will: as a path if it has a directory
+/// part, otherwise in each `PATH` entry (trying `PATHEXT` on Windows).
+pub fn resolve_program(program: &str, path_var: Option<&str>) -> Option<PathBuf> {
+    let extensions: Vec<String> = if cfg!(windows) {
+        std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+            .split(';')
+            .map(str::to_string)
+            .chain(std::iter::once(String::new()))
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+    let candidates = |base: &Path| -> Option<PathBuf> {
+        extensions
+            .iter()
+            .map(|ext| PathBuf::from(format!("{}{}", base.display(), ext)))
+            .find(|p| is_executable(p))
+    };
+
+    let as_path = Path::new(program);
+    if as_path.components().count() > 1 {
+        return candidates(as_path);
+    }
+    std::env::split_paths(path_var?).find_map(|dir| candidates(&dir.join(program)))
+}
+
+/// A file the OS would run. On Unix that takes an exec bit; a file
+/// without one is skipped by the search, as `execvp` does.
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+/// `value` hidden if `key` looks like it holds a secret.
+pub fn masked_value(key: &str, value: &str) -> String {
+    if crate::mcpb::is_secret_key(key) && !value.is_empty() {
+        "••••••••".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+impl LaunchPlan {
+    /// The command line as it could be pasted into a shell.
+    pub fn command_line(&self) -> String {
+        std::iter::once(&self.program)
+            .chain(&self.args)
+            .map(|part| shell_quote(part))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn shell_quote(part: &str) -> String {
+    let plain = !part.is_empty()
+        && part
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@+,".contains(c));
+    if plain {
+        part.to_string()
+    } else {
+        format!("'{}'", part.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_merges_env_over_inherited() {
+        let env = HashMap::from([("API_KEY".to_string(), "abc".to_string())]);
+        let base = vec![
+            ("API_KEY".to_string(), "old".to_string()),
+            ("LANG".to_string(), "C".to_string()),
+        ];
+        let plan = plan(
+            "npx",
+            &["-y".to_string(), "my server".to_string()],
+            &env,
+            None,
+            base,
+        )
+        .unwrap();
+
+        assert_eq!(plan.env["API_KEY"], "abc");
+        assert_eq!(plan.env["LANG"], "C");
+        assert_eq!(plan.configured_keys, vec!["API_KEY"]);
+        assert_eq!(plan.command_line(), "npx -y 'my server'");
+        assert!(super::plan("  ", &[], &env, None, vec![]).is_err());
+    }
+
+    #[test]
+    fn test_resolve_program_searches_path() {
+        let dir = std::env::temp_dir().join(format!("omm-launch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("tool"), "").unwrap();
+        let path_var = std::env::join_paths([dir.clone()]).unwrap();
+        let path_var = path_var.to_str().unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            // Not executable yet, so not what the OS would run
+            assert_eq!(resolve_program("tool", Some(path_var)), None);
+            let executable = std::fs::Permissions::from_mode(0o755);
+            std::fs::set_permissions(dir.join("tool"), executable).unwrap();
+        }
+        assert_eq!(
+            resolve_program("tool", Some(path_var)),
+            Some(dir.join("tool"))
+        );
+        assert_eq!(resolve_program("missing", Some(path_var)), None);
+        assert_eq!(resolve_program("tool", None), None);
+        let full = dir.join("tool");
+        assert_eq!(
+            resolve_program(full.to_str().unwrap(), None),
+            Some(full.clone())
+        );
+
+        // Relative to the server's working directory
+        let plan = plan("./tool", &[], &HashMap::new(), Some(&dir), vec![]).unwrap();
+        assert_eq!(plan.cwd, Some(dir.clone()));
+        assert_eq!(plan.resolved_program, Some(dir.join("./tool")));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_clean_base_env_keeps_allowlist_only() {
+        let inherited = vec![
+            ("PATH".to_string(), "/usr/bin".to_string()),
+            ("Path".to_string(), "C:\\Windows".to_string()),
+            ("AWS_SECRET_ACCESS_KEY".to_string(), "leak".to_string()),
+        ];
+        assert_eq!(base_env(false, inherited.clone()).len(), 3);
+        let clean = base_env(true, inherited);
+        let keys: Vec<&str> = clean.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, vec!["PATH", "Path"]);
+    }
+
+    #[test]
+    fn test_masked_value_hides_secrets() {
+        assert_eq!(masked_value("GITHUB_TOKEN", "ghp_123"), "••••••••");
+        assert_eq!(masked_value("LANG", "C"), "C");
+    }
+}
@@ -6,10 +6,68 @@
 #![allow(non_snake_case)]
 
 // Core modules
+pub mod ansi;
+pub mod appearance;
+pub mod archive;
+pub mod bridge;
+pub mod catalog;
+pub mod circuit_breaker;
+pub mod config_gen;
+pub mod crash;
+pub mod dashboard;
 pub mod db;
+pub mod dedupe;
+pub mod docker;
+pub mod editor_config;
+pub mod elicitation;
+pub mod env_rotation;
+pub mod expiry;
+pub mod fixes;
+pub mod framing;
+pub mod graph;
+pub mod health;
+pub mod hooks;
+pub mod json_schema;
+pub mod launch;
+pub mod lint;
+pub mod locale;
+pub mod log_time;
+pub mod mcpb;
 pub mod models;
+pub mod output_diff;
+pub mod output_format;
+pub mod output_reader;
+pub mod pairing;
+pub mod platform;
+pub mod privileged;
+pub mod probe;
 pub mod process;
+pub mod project_import;
+pub mod pty;
+pub mod recent_tools;
+pub mod registry_digest;
+pub mod registry_entry;
+pub mod registry_validation;
+pub mod request_metrics;
+pub mod request_queue;
+pub mod resources;
+pub mod response_limit;
+pub mod result_cache;
+pub mod sampling;
+pub mod server_diff;
+pub mod session_recording;
+pub mod snippets;
+pub mod start;
 pub mod state;
+pub mod storage;
+pub mod tool_docs;
+pub mod tool_rules;
+pub mod updater;
+pub mod uri_template;
+pub mod usage_export;
+pub mod verify;
+pub mod weekly_report;
+pub mod workspace;
 
 // UI components (keep private to the crate)
 pub mod app;
@@ -18,4 +76,4 @@ pub(crate) mod components;
 // Re-exports for convenience
 pub use db::Database;
 pub use models::{AppError, AppResult, CreateServerArgs, McpServer, UpdateServerArgs};
-pub use process::{McpProcess, ProcessLog};
+pub use process::{LogEntry, McpProcess, ProcessLog};
@@ -0,0 +1,240 @@
+//! Health lint over server configurations.
+//!
+//! Flags common startup mistakes before the user hits them at runtime.
+
+use crate::models::{CreateServerArgs, McpServer};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintWarning {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl LintWarning {
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Env var name suffixes that almost always need a real value.
+const REQUIRED_KEY_SUFFIXES: [&str; 5] = ["_KEY", "_TOKEN", "_SECRET", "PASSWORD", "_PAT"];
+
+pub fn lint_server(server: &McpServer) -> Vec<LintWarning> {
+    lint(
+        &server.server_type,
+        server.command.as_deref(),
+        server.args.as_deref().unwrap_or_default(),
+        server.url.as_deref(),
+        server.env.as_ref(),
+    )
+}
+
+pub fn lint_args(args: &CreateServerArgs) -> Vec<LintWarning> {
+    lint(
+        &args.server_type,
+        args.command.as_deref(),
+        args.args.as_deref().unwrap_or_default(),
+        args.url.as_deref(),
+        args.env.as_ref(),
+    )
+}
+
+/// Program name without directory or Windows extension (`C:\\x\\npx.cmd` -> `npx`).
+fn program_name(command: &str) -> String {
+    let file = command
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(command)
+        .to_lowercase();
+    for ext in [".cmd", ".exe", ".bat"] {
+        if let Some(stripped) = file.strip_suffix(ext) {
+            return stripped.to_string();
+        }
+    }
+    file
+}
+
+fn lint(
+    server_type: &str,
+    command: Option<&str>,
+    args: &[String],
+    url: Option<&str>,
+    env: Option<&HashMap<String, String>>,
+) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    if server_type == "sse" {
+        if url.map(|u| u.trim().is_empty()).unwrap_or(true) {
+            warnings.push(LintWarning::new(
+                "sse-missing-url",
+                "SSE server has no URL configured.",
+            ));
+        }
+    } else if server_type == "ws" {
+        match url.map(str::trim).filter(|u| !u.is_empty()) {
+            None => warnings.push(LintWarning::new(
+                "ws-missing-url",
+                "WebSocket server has no URL configured.",
+            )),
+            Some(u) if crate::models::remote_server_type(u) != "ws" => {
+                warnings.push(LintWarning::new(
+                    "ws-url-scheme",
+                    "WebSocket URLs start with ws:// or wss://.",
+                ))
+            }
+            Some(_) => {}
+        }
+    } else {
+        if url.map(|u| !u.trim().is_empty()).unwrap_or(false) {
+            warnings.push(LintWarning::new(
+                "stdio-with-url",
+                "URL is set on a stdio server and will be ignored. Switch the type to SSE to use it.",
+            ));
+        }
+
+        match command.map(program_name) {
+            None => warnings.push(LintWarning::new(
+                "missing-command",
+                "No command specified for stdio server.",
+            )),
+            Some(program)
+                if program == "npx" && !args.iter().any(|a| a == "-y" || a == "--yes") =>
+            {
+                warnings.push(LintWarning::new(
+                    "npx-without-yes",
+                    "npx without -y prompts for install confirmation and hangs when run headless.",
+                ));
+            }
+            Some(program) if program == "uvx" => {
+                if let Some(pkg) = args.iter().find(|a| !a.starts_with('-')) {
+                    if pkg.starts_with('@') || pkg.contains('/') {
+                        warnings.push(LintWarning::new(
+                            "uvx-npm-package",
+                            format!(
+                                "'{}' looks like an npm package; uvx installs from PyPI. Use npx instead.",
+                                pkg
+                            ),
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(env) = env {
+        let mut empty: Vec<&String> = env
+            .iter()
+            .filter(|(k, v)| {
+                v.trim().is_empty()
+                    && REQUIRED_KEY_SUFFIXES
+                        .iter()
+                        .any(|s| k.to_uppercase().ends_with(s))
+            })
+            .map(|(k, _)| k)
+            .collect();
+        empty.sort();
+        for key in empty {
+            warnings.push(LintWarning::new(
+                "empty-required-env",
+                format!(
+                    "{} is empty; the server will likely fail to authenticate.",
+                    key
+                ),
+            ));
+        }
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stdio(command: &str, args: &[&str]) -> CreateServerArgs {
+        CreateServerArgs {
+            name: "test".to_string(),
+            server_type: "stdio".to_string(),
+            command: Some(command.to_string()),
+            args: Some(args.iter().map(|a| a.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    fn codes(warnings: &[LintWarning]) -> Vec<&'static str> {
+        warnings.iter().map(|w| w.code).collect()
+    }
+
+    #[test]
+    fn test_clean_config_has_no_warnings() {
+        assert!(lint_args(&stdio(
+            "npx",
+            &["-y", "@modelcontextprotocol/server-memory"]
+        ))
+        .is_empty());
+    }
+
+    #[test]
+    fn test_npx_without_yes() {
+        let w = lint_args(&stdio("npx", &["@modelcontextprotocol/server-memory"]));
+        assert_eq!(codes(&w), vec!["npx-without-yes"]);
+
+        let w = lint_args(&stdio("C:\\nodejs\\npx.cmd", &["pkg"]));
+        assert_eq!(codes(&w), vec!["npx-without-yes"]);
+    }
+
+    #[test]
+    fn test_uvx_with_npm_package() {
+        let w = lint_args(&stdio("uvx", &["@modelcontextprotocol/server-git"]));
+        assert_eq!(codes(&w), vec!["uvx-npm-package"]);
+        assert!(lint_args(&stdio("uvx", &["mcp-server-git"])).is_empty());
+    }
+
+    #[test]
+    fn test_url_on_stdio_server() {
+        let mut args = stdio("node", &["index.js"]);
+        args.url = Some("http://localhost:8080/sse".to_string());
+        assert_eq!(codes(&lint_args(&args)), vec!["stdio-with-url"]);
+    }
+
+    #[test]
+    fn test_empty_required_env() {
+        let mut args = stdio("node", &["index.js"]);
+        args.env = Some(HashMap::from([
+            ("BRAVE_API_KEY".to_string(), "".to_string()),
+            ("LOG_LEVEL".to_string(), "".to_string()),
+            ("GITHUB_TOKEN".to_string(), "ghp_x".to_string()),
+        ]));
+        let w = lint_args(&args);
+        assert_eq!(codes(&w), vec!["empty-required-env"]);
+        assert!(w[0].message.contains("BRAVE_API_KEY"));
+    }
+
+    #[test]
+    fn test_sse_missing_url() {
+        let args = CreateServerArgs {
+            name: "remote".to_string(),
+            server_type: "sse".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(codes(&lint_args(&args)), vec!["sse-missing-url"]);
+    }
+
+    #[test]
+    fn test_ws_url_scheme() {
+        let mut args = CreateServerArgs {
+            name: "remote".to_string(),
+            server_type: "ws".to_string(),
+            url: Some("https://example.com/mcp".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(codes(&lint_args(&args)), vec!["ws-url-scheme"]);
+        args.url = Some("wss://example.com/mcp".to_string());
+        assert!(lint_args(&args).is_empty());
+    }
+}
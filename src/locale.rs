@@ -0,0 +1,119 @@
+//! The app language, used to pick translations of registry-provided text
+//! such as install wizard steps. The app's own UI is English.
+//!
+//! Locales are BCP 47-style tags like "de" or "pt-BR". Without a saved
+//! choice the system locale is used, read from the usual environment
+//! variables.
+
+use std::collections::BTreeMap;
+
+/// Language used when nothing better matches.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Languages offered in settings. Registries may carry others; those are
+/// still used when the system locale asks for them.
+pub const LANGUAGES: [(&str, &str); 8] = [
+    ("en", "English"),
+    ("de", "Deutsch"),
+    ("es", "Español"),
+    ("fr", "Français"),
+    ("it", "Italiano"),
+    ("ja", "日本語"),
+    ("pt-BR", "Português (Brasil)"),
+    ("zh-CN", "简体中文"),
+];
+
+/// `de_DE.UTF-8` or `de-de` as `de-DE`. Empty and POSIX "C" locales give
+/// `None`.
+pub fn normalize(raw: &str) -> Option<String> {
+    let tag = raw.split(['.', '@']).next()?.trim();
+    if tag.is_empty() || tag == "C" || tag == "POSIX" {
+        return None;
+    }
+    let mut parts = tag.split(['_', '-']);
+    let language = parts.next()?.to_lowercase();
+    Some(match parts.next() {
+        Some(region) if !region.is_empty() => format!("{}-{}", language, region.to_uppercase()),
+        _ => language,
+    })
+}
+
+/// The locale asked for by `LC_ALL`, `LC_MESSAGES` or `LANG`, in that
+/// order, as looked up by `var`.
+pub fn system_locale_from(var: impl Fn(&str) -> Option<String>) -> String {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .find_map(|key| var(key).as_deref().and_then(normalize))
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+pub fn system_locale() -> String {
+    system_locale_from(|key| std::env::var(key).ok())
+}
+
+/// Locales to try for `locale`, most specific first: "pt-BR", then "pt",
+/// then English.
+fn fallbacks(locale: &str) -> Vec<String> {
+    let mut tags: Vec<String> = normalize(locale).into_iter().collect();
+    if let Some((language, _)) = tags.first().and_then(|tag| tag.split_once('-')) {
+        let language = language.to_string();
+        tags.push(language);
+    }
+    tags.push(DEFAULT_LOCALE.to_string());
+    tags
+}
+
+/// The translation in `texts` best matching `locale`. Falls back to
+/// English, then to any translation, so a registry entry is never blank.
+pub fn pick<'a>(texts: &'a BTreeMap<String, String>, locale: &str) -> &'a str {
+    fallbacks(locale)
+        .iter()
+        .find_map(|tag| {
+            texts
+                .iter()
+                .find(|(key, _)| normalize(key).as_deref() == Some(tag))
+        })
+        .or_else(|| texts.iter().next())
+        .map(|(_, text)| text.as_str())
+        .unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_locale() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(system_locale_from(env(&[("LANG", "de_DE.UTF-8")])), "de-DE");
+        assert_eq!(
+            system_locale_from(env(&[("LC_ALL", "C"), ("LANG", "fr_CA")])),
+            "fr-CA"
+        );
+        assert_eq!(system_locale_from(env(&[])), "en");
+    }
+
+    #[test]
+    fn test_pick_falls_back() {
+        let texts: BTreeMap<String, String> = [
+            ("en", "Client ID"),
+            ("pt", "ID do cliente"),
+            ("de", "Client-ID"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(pick(&texts, "de"), "Client-ID");
+        assert_eq!(pick(&texts, "pt-BR"), "ID do cliente");
+        assert_eq!(pick(&texts, "ja"), "Client ID");
+
+        let only_german = BTreeMap::from([("de".to_string(), "Schritt".to_string())]);
+        assert_eq!(pick(&only_german, "en"), "Schritt");
+    }
+}
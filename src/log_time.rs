@@ -0,0 +1,209 @@
+//! Timestamps on console log lines. Each line is stored prefixed with the
+//! UTC time it was received; the console reformats the prefix for display,
+//! either as a local clock time or relative to now ("3s ago").
+//!
+//! Stderr printed while a tool call was in flight is tagged with the
+//! call's request id (`[stderr #12]`), so the console can show the logs
+//! emitted during a call.
+//!
+//! Log messages a server sends over MCP are tagged with their level
+//! (`[log warning]`), so they can be told apart from raw output.
+
+use crate::models::LoggingLevel;
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeDisplay {
+    Absolute,
+    Relative,
+}
+
+impl TimeDisplay {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeDisplay::Absolute => "Clock time",
+            TimeDisplay::Relative => "Relative",
+        }
+    }
+
+    pub fn toggled(&self) -> Self {
+        match self {
+            TimeDisplay::Absolute => TimeDisplay::Relative,
+            TimeDisplay::Relative => TimeDisplay::Absolute,
+        }
+    }
+}
+
+/// `line` prefixed with `at`, as stored in the log buffer.
+pub fn stamp(at: DateTime<Utc>, line: &str) -> String {
+    format!(
+        "{} {}",
+        at.to_rfc3339_opts(SecondsFormat::Millis, true),
+        line
+    )
+}
+
+/// Split a stored line into its timestamp and text. Lines written before
+/// timestamps were added, or by replays, have none.
+pub fn split(line: &str) -> (Option<DateTime<Utc>>, &str) {
+    let Some((prefix, rest)) = line.split_once(' ') else {
+        return (None, line);
+    };
+    match DateTime::parse_from_rfc3339(prefix) {
+        Ok(at) => (Some(at.with_timezone(&Utc)), rest),
+        Err(_) => (None, line),
+    }
+}
+
+/// Stream tag for stderr printed during the tool call `request_id`.
+pub fn request_tag(request_id: u64) -> String {
+    format!("[stderr #{}]", request_id)
+}
+
+/// A server log message as stored, e.g. `[log warning] db: slow query`.
+pub fn message_line(level: LoggingLevel, logger: Option<&str>, text: &str) -> String {
+    match logger {
+        Some(logger) => format!("[log {}] {}: {}", level.as_str(), logger, text),
+        None => format!("[log {}] {}", level.as_str(), text),
+    }
+}
+
+/// The level of a stored line, if it is a server log message.
+fn message_level(line: &str) -> Option<LoggingLevel> {
+    let (_, rest) = split(line);
+    let (tag, _) = rest.strip_prefix("[log ")?.split_once(']')?;
+    LoggingLevel::from_name(tag)
+}
+
+/// Stored server log messages of `min` and above, leaving out raw output.
+pub fn server_messages(text: &str, min: LoggingLevel) -> String {
+    text.lines()
+        .filter(|line| message_level(line).is_some_and(|level| level >= min))
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// Stored lines tagged with `request_id`, or with any request when it is
+/// `None`.
+pub fn request_logs(text: &str, request_id: Option<u64>) -> String {
+    let tag = request_id.map(request_tag);
+    text.lines()
+        .filter(|line| {
+            let (_, rest) = split(line);
+            match &tag {
+                Some(tag) => rest.starts_with(tag.as_str()),
+                None => rest.starts_with("[stderr #"),
+            }
+        })
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// How long before `now` `at` was, e.g. "3s ago".
+pub fn relative(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - at).num_seconds().max(0);
+    match secs {
+        0 => "just now".to_string(),
+        1..=59 => format!("{}s ago", secs),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+/// The stored log `text` with each timestamp shown as `display`.
+pub fn render(text: &str, display: TimeDisplay, now: DateTime<Utc>) -> String {
+    let mut out = String::with_capacity(text.len());
+    for line in text.lines() {
+        match split(line) {
+            (Some(at), rest) => {
+                let shown = match display {
+                    TimeDisplay::Absolute => {
+                        at.with_timezone(&Local).format("%H:%M:%S%.3f").to_string()
+                    }
+                    TimeDisplay::Relative => relative(at, now),
+                };
+                out.push_str(&shown);
+                out.push(' ');
+                out.push_str(rest);
+            }
+            (None, line) => out.push_str(line),
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_stamp_round_trip() {
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 9, 30, 5).unwrap();
+        let line = stamp(at, "[stderr] ready");
+        assert_eq!(line, "2026-03-01T09:30:05.000Z [stderr] ready");
+        assert_eq!(split(&line), (Some(at), "[stderr] ready"));
+        assert_eq!(split("[replay] old.jsonl"), (None, "[replay] old.jsonl"));
+    }
+
+    #[test]
+    fn test_request_logs() {
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 9, 30, 5).unwrap();
+        let text = [
+            stamp(at, "[stderr] starting"),
+            stamp(at, &format!("{} Traceback", request_tag(1))),
+            stamp(at, &format!("{} other call", request_tag(12))),
+            stamp(at, "[stdout] done"),
+        ]
+        .join("\n");
+        assert_eq!(
+            request_logs(&text, Some(1)),
+            "2026-03-01T09:30:05.000Z [stderr #1] Traceback\n"
+        );
+        assert_eq!(request_logs(&text, None).lines().count(), 2);
+        assert!(request_logs(&text, Some(3)).is_empty());
+    }
+
+    #[test]
+    fn test_server_messages() {
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 9, 30, 5).unwrap();
+        let text = [
+            stamp(at, "[stderr] starting"),
+            stamp(at, &message_line(LoggingLevel::Debug, None, "cache warm")),
+            stamp(
+                at,
+                &message_line(LoggingLevel::Warning, Some("db"), "slow query"),
+            ),
+            stamp(at, "[stdout] [log nonsense] raw"),
+        ]
+        .join("\n");
+        assert_eq!(
+            server_messages(&text, LoggingLevel::Info),
+            "2026-03-01T09:30:05.000Z [log warning] db: slow query\n"
+        );
+        assert_eq!(
+            server_messages(&text, LoggingLevel::Debug).lines().count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_relative_render() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let text = format!(
+            "{}\n{}\n[replay] file\n",
+            stamp(now - chrono::Duration::seconds(3), "[stdout] a"),
+            stamp(now - chrono::Duration::minutes(90), "[stderr] b"),
+        );
+        assert_eq!(
+            render(&text, TimeDisplay::Relative, now),
+            "3s ago [stdout] a\n1h ago [stderr] b\n[replay] file\n"
+        );
+        assert_eq!(
+            relative(now + chrono::Duration::seconds(1), now),
+            "just now"
+        );
+    }
+}
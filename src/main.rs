@@ -5,10 +5,97 @@ use dioxus_logger::tracing;
 
 // Use the library crate
 use open_mcp_manager::app::App;
+use open_mcp_manager::{bridge, crash, start, usage_export, verify};
 
 fn main() {
-    // Initialize logging
-    dioxus_logger::init(tracing::Level::INFO).expect("failed to init logger");
+    let cli_args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Headless stdio bridge mode: `open-mcp-manager bridge [--url <sse-url>]`
+    if cli_args.first().map(String::as_str) == Some(bridge::BRIDGE_SUBCOMMAND) {
+        let code = match bridge::parse_args(&cli_args[1..]) {
+            Ok(url) => {
+                let runtime = tokio::runtime::Runtime::new().expect("failed to start runtime");
+                match runtime.block_on(bridge::run(url)) {
+                    Ok(()) => 0,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        1
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                2
+            }
+        };
+        std::process::exit(code);
+    }
+
+    // Headless usage export: `open-mcp-manager export-usage [--table ...] [--from ...]`
+    if cli_args.first().map(String::as_str) == Some(usage_export::EXPORT_SUBCOMMAND) {
+        let today = chrono::Utc::now().date_naive();
+        let code = match usage_export::parse_args(&cli_args[1..], today) {
+            Ok(args) => match usage_export::run(args) {
+                Ok(()) => 0,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    1
+                }
+            },
+            Err(e) => {
+                eprintln!("{}", e);
+                2
+            }
+        };
+        std::process::exit(code);
+    }
+
+    // Headless config check for CI: `open-mcp-manager verify [NAME...] [--config FILE]`
+    if cli_args.first().map(String::as_str) == Some(verify::VERIFY_SUBCOMMAND) {
+        let code = match verify::parse_args(&cli_args[1..]) {
+            Ok(args) => {
+                let runtime = tokio::runtime::Runtime::new().expect("failed to start runtime");
+                match runtime.block_on(verify::run(args)) {
+                    Ok(true) => 0,
+                    Ok(false) => 1,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        1
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                2
+            }
+        };
+        std::process::exit(code);
+    }
+
+    // Headless server run: `open-mcp-manager start NAME`, by name or alias
+    if cli_args.first().map(String::as_str) == Some(start::START_SUBCOMMAND) {
+        let code = match start::parse_args(&cli_args[1..]) {
+            Ok(name) => {
+                let runtime = tokio::runtime::Runtime::new().expect("failed to start runtime");
+                match runtime.block_on(start::run(&name)) {
+                    Ok(code) => code,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        1
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                2
+            }
+        };
+        std::process::exit(code);
+    }
+
+    // Initialize logging and local crash reports
+    crash::init_logging();
+    crash::install_panic_hook();
     tracing::info!("starting app");
 
     // Launch the Dioxus Desktop app
@@ -0,0 +1,795 @@
+//! Desktop extension bundles (`.mcpb`, formerly `.dxt`): a ZIP holding a
+//! server and a `manifest.json` describing how to launch it.
+
+use crate::archive::ZipEntry;
+use crate::models::{CreateServerArgs, McpServer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Author {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct McpConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    /// Per-OS replacements keyed by `darwin`, `win32` or `linux`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub platform_overrides: HashMap<String, Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ManifestServer {
+    /// `node`, `python`, `uv` or `binary`.
+    #[serde(rename = "type")]
+    pub server_type: String,
+    pub entry_point: String,
+    pub mcp_config: McpConfig,
+}
+
+/// One setting the user fills in at install time.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct UserConfigField {
+    /// `string`, `number`, `boolean`, `directory` or `file`.
+    #[serde(rename = "type")]
+    pub field_type: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub sensitive: bool,
+    #[serde(default)]
+    pub multiple: bool,
+    #[serde(default)]
+    pub default: Option<Value>,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Manifest {
+    #[serde(default, alias = "dxt_version")]
+    pub manifest_version: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    pub version: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: Author,
+    pub server: ManifestServer,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub user_config: BTreeMap<String, UserConfigField>,
+}
+
+/// A bundle read into memory, ready to be installed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bundle {
+    pub manifest: Manifest,
+    pub entries: Vec<ZipEntry>,
+}
+
+pub fn read_bundle(bytes: &[u8]) -> Result<Bundle, String> {
+    let entries = crate::archive::read(bytes)?;
+    let manifest = entries
+        .iter()
+        .find(|e| e.name == MANIFEST_FILE)
+        .ok_or("Bundle has no manifest.json")?;
+    let manifest: Manifest = serde_json::from_slice(&manifest.data)
+        .map_err(|e| format!("Invalid manifest.json: {}", e))?;
+    if manifest.name.trim().is_empty() || manifest.server.mcp_config.command.is_empty() {
+        return Err("manifest.json must set name and server.mcp_config.command".to_string());
+    }
+    if crate::storage::dir_name(&manifest.name).is_empty() {
+        return Err(format!("Invalid bundle name: {}", manifest.name));
+    }
+    Ok(Bundle { manifest, entries })
+}
+
+/// Value used for a field the user left empty.
+pub fn default_value(field: &UserConfigField) -> Value {
+    field
+        .default
+        .clone()
+        .unwrap_or(match field.field_type.as_str() {
+            "boolean" => Value::Bool(false),
+            _ if field.multiple => Value::Array(Vec::new()),
+            _ => Value::String(String::new()),
+        })
+}
+
+/// Typed value for text entered in the install wizard. Multi-value
+/// fields take one entry per line.
+pub fn value_from_input(field: &UserConfigField, input: &str) -> Value {
+    if field.multiple {
+        return Value::Array(
+            input
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(|l| Value::String(l.to_string()))
+                .collect(),
+        );
+    }
+    match field.field_type.as_str() {
+        "boolean" => Value::Bool(input == "true"),
+        "number" => input
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(|n| serde_json::Number::from_f64(n).map(Value::Number))
+            .unwrap_or_else(|| Value::String(input.to_string())),
+        _ => Value::String(input.to_string()),
+    }
+}
+
+/// Text shown in the wizard for a field's default.
+pub fn input_from_value(field: &UserConfigField, value: &Value) -> String {
+    match value {
+        Value::Array(items) if field.multiple => items
+            .iter()
+            .map(value_to_string)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        other => value_to_string(other),
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        Value::Array(a) => a.is_empty(),
+        _ => false,
+    }
+}
+
+/// Problems with the values entered for `user_config`, one per field.
+pub fn validate_user_config(manifest: &Manifest, values: &BTreeMap<String, Value>) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (key, field) in &manifest.user_config {
+        let label = field.title.as_deref().unwrap_or(key);
+        let value = values.get(key).cloned().unwrap_or(Value::Null);
+        if is_empty(&value) {
+            if field.required && field.default.is_none() {
+                errors.push(format!("{} is required", label));
+            }
+            continue;
+        }
+        if field.field_type == "number" {
+            let number = match &value {
+                Value::Number(n) => n.as_f64(),
+                Value::String(s) => s.trim().parse().ok(),
+                _ => None,
+            };
+            match number {
+                None => errors.push(format!("{} must be a number", label)),
+                Some(n) if field.min.is_some_and(|min| n < min) => {
+                    errors.push(format!("{} must be at least {}", label, field.min.unwrap()))
+                }
+                Some(n) if field.max.is_some_and(|max| n > max) => {
+                    errors.push(format!("{} must be at most {}", label, field.max.unwrap()))
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    errors
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Expand `${__dirname}`, `${HOME}`, `${user_config.key}` and friends.
+/// A string that is exactly one multi-value reference expands to several.
+fn substitute(template: &str, install_dir: &Path, values: &BTreeMap<String, Value>) -> Vec<String> {
+    if let Some(key) = template
+        .strip_prefix("${user_config.")
+        .and_then(|rest| rest.strip_suffix('}'))
+    {
+        if let Some(Value::Array(items)) = values.get(key) {
+            return items.iter().map(value_to_string).collect();
+        }
+    }
+
+    let home = dirs::home_dir().unwrap_or_default();
+    let mut vars: Vec<(String, String)> = vec![
+        (
+            "__dirname".into(),
+            install_dir.to_string_lossy().to_string(),
+        ),
+        ("HOME".into(), home.to_string_lossy().to_string()),
+        (
+            "DESKTOP".into(),
+            dirs::desktop_dir()
+                .unwrap_or_else(|| home.join("Desktop"))
+                .to_string_lossy()
+                .to_string(),
+        ),
+        (
+            "DOCUMENTS".into(),
+            dirs::document_dir()
+                .unwrap_or_else(|| home.join("Documents"))
+                .to_string_lossy()
+                .to_string(),
+        ),
+        (
+            "DOWNLOADS".into(),
+            dirs::download_dir()
+                .unwrap_or_else(|| home.join("Downloads"))
+                .to_string_lossy()
+                .to_string(),
+        ),
+        (
+            "pathSeparator".into(),
+            std::path::MAIN_SEPARATOR.to_string(),
+        ),
+        ("/".into(), std::path::MAIN_SEPARATOR.to_string()),
+    ];
+    for (key, value) in values {
+        let joined = match value {
+            Value::Array(items) => items
+                .iter()
+                .map(value_to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+            other => value_to_string(other),
+        };
+        vars.push((format!("user_config.{}", key), joined));
+    }
+
+    let mut out = template.to_string();
+    for (name, value) in vars {
+        out = out.replace(&format!("${{{}}}", name), &value);
+    }
+    vec![out]
+}
+
+/// `mcp_config` with this platform's overrides applied.
+fn effective_config(manifest: &Manifest) -> McpConfig {
+    let mut config = manifest.server.mcp_config.clone();
+    let platform = match std::env::consts::OS {
+        "macos" => "darwin",
+        "windows" => "win32",
+        other => other,
+    };
+    if let Some(Value::Object(over)) = config.platform_overrides.get(platform).cloned() {
+        if let Some(Value::String(command)) = over.get("command") {
+            config.command = command.clone();
+        }
+        if let Some(args) = over
+            .get("args")
+            .and_then(|a| serde_json::from_value(a.clone()).ok())
+        {
+            config.args = args;
+        }
+        if let Some(env) = over
+            .get("env")
+            .and_then(|e| serde_json::from_value::<HashMap<String, String>>(e.clone()).ok())
+        {
+            config.env.extend(env);
+        }
+    }
+    config
+}
+
+/// Server configuration for a bundle extracted into `install_dir`.
+pub fn server_args(
+    manifest: &Manifest,
+    install_dir: &Path,
+    values: &BTreeMap<String, Value>,
+) -> CreateServerArgs {
+    // Fill in defaults for anything left empty
+    let mut resolved = values.clone();
+    for (key, field) in &manifest.user_config {
+        if resolved.get(key).map(is_empty).unwrap_or(true) {
+            resolved.insert(key.clone(), default_value(field));
+        }
+    }
+
+    let config = effective_config(manifest);
+    let command = substitute(&config.command, install_dir, &resolved).join(" ");
+    let args: Vec<String> = config
+        .args
+        .iter()
+        .flat_map(|a| substitute(a, install_dir, &resolved))
+        .collect();
+    let env: HashMap<String, String> = config
+        .env
+        .iter()
+        .map(|(k, v)| (k.clone(), substitute(v, install_dir, &resolved).join(",")))
+        .filter(|(_, v)| !v.is_empty())
+        .collect();
+
+    CreateServerArgs {
+        name: manifest
+            .display_name
+            .clone()
+            .unwrap_or(manifest.name.clone()),
+        server_type: "stdio".to_string(),
+        command: Some(command),
+        args: Some(args),
+        url: None,
+        env: if env.is_empty() { None } else { Some(env) },
+        description: Some(manifest.description.clone()).filter(|d| !d.is_empty()),
+    }
+}
+
+/// Relative path for an archive entry, refusing anything that would
+/// escape the install directory.
+fn safe_relative_path(name: &str) -> Result<PathBuf, String> {
+    let path = Path::new(name);
+    if path
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        Ok(path.to_path_buf())
+    } else {
+        Err(format!("Refusing unsafe path in bundle: {}", name))
+    }
+}
+
+/// Managed directory a bundle is extracted into, named after the bundle.
+pub fn install_dir(manifest: &Manifest) -> crate::models::AppResult<PathBuf> {
+    let name = crate::storage::dir_name(&manifest.name);
+    if name.is_empty() {
+        return Err(crate::models::AppError::Io(format!(
+            "Invalid bundle name: {}",
+            manifest.name
+        )));
+    }
+    let extensions = crate::storage::dir(crate::storage::StorageKind::Extensions)?;
+    Ok(extensions.join(name))
+}
+
+/// Whether `dir` is a directory strictly inside `parent`, and so safe to
+/// delete when replacing an install.
+fn is_strict_child(dir: &Path, parent: &Path) -> bool {
+    dir.strip_prefix(parent).is_ok_and(|rest| {
+        rest.components().next().is_some()
+            && rest.components().all(|c| matches!(c, Component::Normal(_)))
+    })
+}
+
+/// Unpack a bundle into `dir`, replacing any previous install there. Only
+/// a directory inside the extensions dir is ever replaced.
+pub fn extract(bundle: &Bundle, dir: &Path) -> Result<(), String> {
+    let files = bundle
+        .entries
+        .iter()
+        .map(|e| safe_relative_path(&e.name).map(|p| (dir.join(p), e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if dir.exists() {
+        let extensions = crate::storage::dir(crate::storage::StorageKind::Extensions)
+            .map_err(|e| e.to_string())?;
+        if !is_strict_child(dir, &extensions) {
+            return Err(format!(
+                "Refusing to replace {}: not inside {}",
+                dir.display(),
+                extensions.display()
+            ));
+        }
+        std::fs::remove_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    for (path, entry) in files {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(&path, &entry.data).map_err(|e| e.to_string())?;
+        #[cfg(unix)]
+        if let Some(mode) = entry.mode {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode & 0o777))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+// === Packaging ===
+
+/// Folders never packed into a bundle.
+const SKIP_DIRS: &[&str] = &[".git", "__pycache__", ".venv", "venv", ".idea", ".vscode"];
+
+/// Files marking a project root when walking up from a script entry point.
+const PROJECT_MARKERS: &[&str] = &["package.json", "pyproject.toml", "requirements.txt"];
+
+/// Manifest `server.type` for a launch command.
+fn runtime_for(command: &str) -> &'static str {
+    let stem = Path::new(command)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match stem.as_str() {
+        "node" => "node",
+        "python" | "python3" | "py" => "python",
+        "uv" => "uv",
+        _ => "binary",
+    }
+}
+
+/// Env keys whose values must not be shared.
+pub fn is_secret_key(key: &str) -> bool {
+    let key = key.to_uppercase();
+    ["KEY", "TOKEN", "SECRET", "PASSWORD", "CREDENTIAL"]
+        .iter()
+        .any(|s| key.contains(s))
+}
+
+/// Nearest ancestor of `entry` that looks like a project root, else its folder.
+fn project_root(entry: &Path) -> PathBuf {
+    let parent = entry.parent().unwrap_or(Path::new("."));
+    parent
+        .ancestors()
+        .find(|dir| PROJECT_MARKERS.iter().any(|m| dir.join(m).is_file()))
+        .unwrap_or(parent)
+        .to_path_buf()
+}
+
+/// Folder to package and the entry point inside it, for a server run
+/// from local files. Package-runner servers (npx, uvx) have none.
+pub fn bundle_source(server: &McpServer) -> Result<(PathBuf, PathBuf), String> {
+    let command = server.command.as_deref().ok_or("Server has no command")?;
+    if runtime_for(command) == "binary" && Path::new(command).is_file() {
+        let entry = PathBuf::from(command);
+        let root = entry.parent().unwrap_or(Path::new(".")).to_path_buf();
+        return Ok((root, entry));
+    }
+    let entry = server
+        .args
+        .iter()
+        .flatten()
+        .map(PathBuf::from)
+        .find(|p| p.is_absolute() && p.is_file())
+        .ok_or("Only servers launched from local files can be bundled")?;
+    Ok((project_root(&entry), entry))
+}
+
+/// `${__dirname}`-relative form of `path` if it lies inside `root`.
+fn bundled_path(path: &str, root: &Path) -> Option<String> {
+    let rel = Path::new(path).strip_prefix(root).ok()?;
+    let rel: Vec<String> = rel
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    Some(format!("${{__dirname}}/server/{}", rel.join("/")))
+}
+
+/// Manifest for packaging `server` from `root`. Env values become
+/// `user_config` fields; secrets get no default so they are never shared.
+pub fn manifest_for_server(server: &McpServer, root: &Path, entry: &Path) -> Manifest {
+    let command = server.command.clone().unwrap_or_default();
+    let server_type = runtime_for(&command);
+    let rewrite = |arg: &String| bundled_path(arg, root).unwrap_or_else(|| arg.clone());
+
+    let mut env = HashMap::new();
+    let mut user_config = BTreeMap::new();
+    for (key, value) in server.env.iter().flatten() {
+        let config_key = key.to_lowercase();
+        env.insert(key.clone(), format!("${{user_config.{}}}", config_key));
+        let sensitive = is_secret_key(key);
+        user_config.insert(
+            config_key,
+            UserConfigField {
+                field_type: "string".to_string(),
+                title: Some(key.clone()),
+                required: sensitive,
+                sensitive,
+                default: (!sensitive).then(|| Value::String(value.clone())),
+                ..Default::default()
+            },
+        );
+    }
+
+    let entry_point = bundled_path(&entry.to_string_lossy(), root)
+        .map(|p| p.trim_start_matches("${__dirname}/").to_string())
+        .unwrap_or_default();
+    let name: String = server
+        .name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+
+    Manifest {
+        manifest_version: "0.2".to_string(),
+        name: name.trim_matches('-').to_string(),
+        display_name: Some(server.name.clone()),
+        version: "1.0.0".to_string(),
+        description: server.description.clone().unwrap_or_default(),
+        author: Author {
+            name: std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .unwrap_or_else(|_| "Unknown".to_string()),
+            ..Default::default()
+        },
+        server: ManifestServer {
+            server_type: server_type.to_string(),
+            entry_point,
+            mcp_config: McpConfig {
+                command: if server_type == "binary" {
+                    rewrite(&command)
+                } else {
+                    command.clone()
+                },
+                args: server.args.iter().flatten().map(rewrite).collect(),
+                env,
+                platform_overrides: HashMap::new(),
+            },
+        },
+        user_config,
+    }
+}
+
+/// Every file under `dir` as a bundle entry below `server/`. Skips VCS and
+/// editor folders and `.env` files.
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<ZipEntry>) -> std::io::Result<()> {
+    let mut children: Vec<_> = std::fs::read_dir(dir)?.flatten().collect();
+    children.sort_by_key(|e| e.file_name());
+    for child in children {
+        let path = child.path();
+        let file_name = child.file_name().to_string_lossy().to_string();
+        let file_type = child.file_type()?;
+        if file_type.is_dir() {
+            if !SKIP_DIRS.contains(&file_name.as_str()) {
+                collect_files(root, &path, out)?;
+            }
+        } else if file_type.is_file() && !file_name.starts_with(".env") {
+            let rel: Vec<String> = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect();
+            #[cfg(unix)]
+            let mode = {
+                use std::os::unix::fs::PermissionsExt;
+                Some(child.metadata()?.permissions().mode() & 0o777)
+            };
+            #[cfg(not(unix))]
+            let mode = None;
+            out.push(ZipEntry {
+                name: format!("server/{}", rel.join("/")),
+                data: std::fs::read(&path)?,
+                mode,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Package a locally run server as `.mcpb` bytes.
+pub fn build_bundle(server: &McpServer) -> Result<(Manifest, Vec<u8>), String> {
+    let (root, entry) = bundle_source(server)?;
+    let manifest = manifest_for_server(server, &root, &entry);
+    let mut entries = vec![ZipEntry {
+        name: MANIFEST_FILE.to_string(),
+        data: serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?,
+        mode: None,
+    }];
+    collect_files(&root, &root, &mut entries).map_err(|e| e.to_string())?;
+    let bytes = crate::archive::write(&entries)?;
+    Ok((manifest, bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn manifest() -> Manifest {
+        serde_json::from_value(json!({
+            "dxt_version": "0.1",
+            "name": "files",
+            "display_name": "File Browser",
+            "version": "1.0.0",
+            "description": "Browse files",
+            "author": { "name": "Someone" },
+            "server": {
+                "type": "node",
+                "entry_point": "server/index.js",
+                "mcp_config": {
+                    "command": "node",
+                    "args": ["${__dirname}/server/index.js", "${user_config.dirs}"],
+                    "env": { "API_KEY": "${user_config.api_key}", "LIMIT": "${user_config.limit}" }
+                }
+            },
+            "user_config": {
+                "api_key": { "type": "string", "title": "API key", "sensitive": true, "required": true },
+                "dirs": { "type": "directory", "multiple": true, "default": ["/tmp"] },
+                "limit": { "type": "number", "min": 1, "max": 10, "default": 5 }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_manifest_accepts_dxt_version() {
+        let m = manifest();
+        assert_eq!(m.manifest_version, "0.1");
+        assert!(m.user_config["api_key"].sensitive);
+    }
+
+    #[test]
+    fn test_validate_user_config() {
+        let m = manifest();
+        let mut values = BTreeMap::new();
+        assert_eq!(
+            validate_user_config(&m, &values),
+            vec!["API key is required"]
+        );
+
+        values.insert("api_key".to_string(), json!("secret"));
+        values.insert("limit".to_string(), json!("50"));
+        assert_eq!(
+            validate_user_config(&m, &values),
+            vec!["limit must be at most 10"]
+        );
+
+        values.insert("limit".to_string(), json!("3"));
+        assert!(validate_user_config(&m, &values).is_empty());
+    }
+
+    #[test]
+    fn test_wizard_input_conversion() {
+        let m = manifest();
+        let dirs = &m.user_config["dirs"];
+        assert_eq!(value_from_input(dirs, "/a\n\n /b "), json!(["/a", "/b"]));
+        assert_eq!(input_from_value(dirs, &json!(["/a", "/b"])), "/a\n/b");
+        assert_eq!(value_from_input(&m.user_config["limit"], "7"), json!(7.0));
+    }
+
+    #[test]
+    fn test_server_args_substitution() {
+        let m = manifest();
+        let dir = Path::new("/data/extensions/files");
+        let mut values = BTreeMap::new();
+        values.insert("api_key".to_string(), json!("secret"));
+        values.insert("dirs".to_string(), json!(["/a", "/b"]));
+
+        let args = server_args(&m, dir, &values);
+        assert_eq!(args.name, "File Browser");
+        assert_eq!(args.command.as_deref(), Some("node"));
+        let expected_entry = format!("{}/server/index.js", dir.to_string_lossy());
+        assert_eq!(
+            args.args.unwrap(),
+            vec![expected_entry.as_str(), "/a", "/b"]
+        );
+        let env = args.env.unwrap();
+        assert_eq!(env["API_KEY"], "secret");
+        assert_eq!(env["LIMIT"], "5");
+    }
+
+    #[test]
+    fn test_read_bundle_and_reject_traversal() {
+        let manifest_bytes = serde_json::to_vec(&manifest()).unwrap();
+        let entry = |name: &str, data: &[u8]| ZipEntry {
+            name: name.to_string(),
+            data: data.to_vec(),
+            mode: None,
+        };
+        let bytes = crate::archive::write(&[
+            entry(MANIFEST_FILE, &manifest_bytes),
+            entry("server/index.js", b"console.log('hi')"),
+        ])
+        .unwrap();
+        let bundle = read_bundle(&bytes).unwrap();
+        assert_eq!(bundle.manifest.name, "files");
+
+        let dir = std::env::temp_dir().join(format!("omm-mcpb-{}", uuid::Uuid::new_v4()));
+        extract(&bundle, &dir).unwrap();
+        assert!(dir.join("server/index.js").is_file());
+
+        let evil = Bundle {
+            manifest: bundle.manifest.clone(),
+            entries: vec![entry("../escape.txt", b"x")],
+        };
+        assert!(extract(&evil, &dir).is_err());
+        assert!(dir.join("server/index.js").is_file());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_reject_empty_dir_name() {
+        let mut bad = manifest();
+        bad.name = "..".to_string();
+        let bytes = crate::archive::write(&[ZipEntry {
+            name: MANIFEST_FILE.to_string(),
+            data: serde_json::to_vec(&bad).unwrap(),
+            mode: None,
+        }])
+        .unwrap();
+        assert!(read_bundle(&bytes).is_err());
+        assert!(install_dir(&bad).is_err());
+    }
+
+    #[test]
+    fn test_is_strict_child() {
+        let parent = Path::new("/data/extensions");
+        assert!(is_strict_child(Path::new("/data/extensions/files"), parent));
+        assert!(!is_strict_child(parent, parent));
+        assert!(!is_strict_child(Path::new("/data/extensions/.."), parent));
+        assert!(!is_strict_child(Path::new("/data/other"), parent));
+    }
+
+    #[test]
+    fn test_build_bundle_from_local_server() {
+        let root = std::env::temp_dir().join(format!("omm-pack-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("dist")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join("package.json"), "{}").unwrap();
+        std::fs::write(root.join("dist/index.js"), "console.log('hi')").unwrap();
+        std::fs::write(root.join(".env"), "API_KEY=secret").unwrap();
+        std::fs::write(root.join(".git/config"), "").unwrap();
+
+        let entry = root.join("dist/index.js");
+        let server = McpServer {
+            id: "1".to_string(),
+            name: "My Server".to_string(),
+            server_type: "stdio".to_string(),
+            command: Some("node".to_string()),
+            args: Some(vec![
+                entry.to_string_lossy().to_string(),
+                "--verbose".to_string(),
+            ]),
+            env: Some(HashMap::from([
+                ("API_KEY".to_string(), "secret".to_string()),
+                ("REGION".to_string(), "eu".to_string()),
+            ])),
+            is_active: true,
+            ..Default::default()
+        };
+
+        let (manifest, bytes) = build_bundle(&server).unwrap();
+        assert_eq!(manifest.name, "my-server");
+        assert_eq!(manifest.server.server_type, "node");
+        assert_eq!(manifest.server.entry_point, "server/dist/index.js");
+        assert_eq!(
+            manifest.server.mcp_config.args,
+            vec!["${__dirname}/server/dist/index.js", "--verbose"]
+        );
+        assert_eq!(
+            manifest.server.mcp_config.env["API_KEY"],
+            "${user_config.api_key}"
+        );
+        assert!(manifest.user_config["api_key"].sensitive);
+        assert!(manifest.user_config["api_key"].default.is_none());
+        assert_eq!(manifest.user_config["region"].default, Some(json!("eu")));
+
+        let bundle = read_bundle(&bytes).unwrap();
+        let names: Vec<&str> = bundle.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![MANIFEST_FILE, "server/dist/index.js", "server/package.json"]
+        );
+        assert!(!String::from_utf8_lossy(&bytes).contains("API_KEY=secret"));
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
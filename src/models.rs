@@ -19,12 +19,324 @@ pub enum NotificationLevel {
     Error,
 }
 
+/// UI and process state saved between launches for session restore.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct SessionState {
+    /// Ids of servers that were running.
+    pub running_servers: Vec<String>,
+    pub active_tab: String,
+    /// Server whose console was open, and which console tab.
+    pub console_server: Option<String>,
+    pub console_tab: Option<String>,
+    pub dashboard_scroll: f64,
+}
+
+/// What triggered a notification, so rules can target specific events.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum NotificationEvent {
+    #[default]
+    General,
+    ServerFailure,
+    PackageUpdate,
+    ConfigChange,
+}
+
+impl NotificationEvent {
+    pub const ALL: [NotificationEvent; 4] = [
+        NotificationEvent::General,
+        NotificationEvent::ServerFailure,
+        NotificationEvent::PackageUpdate,
+        NotificationEvent::ConfigChange,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotificationEvent::General => "General",
+            NotificationEvent::ServerFailure => "Server failures",
+            NotificationEvent::PackageUpdate => "Package updates",
+            NotificationEvent::ConfigChange => "Editor config changes",
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Notification {
     pub id: u32,
     pub message: String,
     pub level: NotificationLevel,
     pub duration: u32, // in seconds
+    /// Server the notification relates to, for click-through to its console.
+    #[serde(default)]
+    pub server_id: Option<String>,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub read: bool,
+    #[serde(default)]
+    pub event: NotificationEvent,
+}
+
+/// User rules deciding how a notification is delivered.
+/// History always records every notification.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct NotificationRules {
+    pub do_not_disturb: bool,
+    pub muted_levels: Vec<NotificationLevel>,
+    pub muted_events: Vec<NotificationEvent>,
+    /// Events that also raise a native OS notification.
+    pub native_events: Vec<NotificationEvent>,
+    /// Local hours `(start, end)` during which toasts are suppressed; may wrap midnight.
+    pub quiet_hours: Option<(u8, u8)>,
+}
+
+impl Default for NotificationRules {
+    fn default() -> Self {
+        Self {
+            do_not_disturb: false,
+            muted_levels: Vec::new(),
+            muted_events: Vec::new(),
+            native_events: vec![NotificationEvent::ServerFailure],
+            quiet_hours: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NotificationDelivery {
+    pub toast: bool,
+    pub native: bool,
+}
+
+impl NotificationRules {
+    pub fn in_quiet_hours(&self, hour: u8) -> bool {
+        match self.quiet_hours {
+            Some((start, end)) if start <= end => hour >= start && hour < end,
+            Some((start, end)) => hour >= start || hour < end,
+            None => false,
+        }
+    }
+
+    pub fn delivery(
+        &self,
+        level: &NotificationLevel,
+        event: NotificationEvent,
+        hour: u8,
+    ) -> NotificationDelivery {
+        let muted = self.do_not_disturb
+            || self.in_quiet_hours(hour)
+            || self.muted_levels.contains(level)
+            || self.muted_events.contains(&event);
+        NotificationDelivery {
+            toast: !muted,
+            native: !muted && self.native_events.contains(&event),
+        }
+    }
+}
+
+/// Hub caching of read-only tool results.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ResultCacheSettings {
+    pub enabled: bool,
+    pub ttl_secs: u64,
+    /// `server_id/tool` keys never cached even when read-only.
+    pub opted_out: Vec<String>,
+}
+
+impl Default for ResultCacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_secs: 30,
+            opted_out: Vec::new(),
+        }
+    }
+}
+
+/// When a server's share of failed hub calls counts as a spike.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ErrorBudgetSettings {
+    pub enabled: bool,
+    /// Failed calls, as a percentage of all calls, that raise an alert.
+    pub threshold_percent: u32,
+    pub window_secs: u64,
+    /// Calls needed within the window before the rate means anything.
+    pub min_requests: u32,
+}
+
+impl ErrorBudgetSettings {
+    pub fn window(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.window_secs)
+    }
+}
+
+impl Default for ErrorBudgetSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold_percent: 25,
+            window_secs: 300,
+            min_requests: 5,
+        }
+    }
+}
+
+/// The local `/healthz` and `/readyz` listener for uptime monitors.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct HealthEndpointSettings {
+    pub enabled: bool,
+    /// Port on 127.0.0.1; the default stays clear of the hub's 3000.
+    pub port: u16,
+}
+
+impl Default for HealthEndpointSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 3001,
+        }
+    }
+}
+
+/// How much of a tool result the hub keeps in memory and shows.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ResponseLimitSettings {
+    pub enabled: bool,
+    /// Largest result kept whole, counting text and base64 data.
+    pub max_kb: u64,
+}
+
+impl ResponseLimitSettings {
+    pub fn max_bytes(&self) -> usize {
+        (self.max_kb as usize).saturating_mul(1024)
+    }
+}
+
+impl Default for ResponseLimitSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_kb: 1024,
+        }
+    }
+}
+
+/// API that fulfils servers' `sampling/createMessage` requests.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SamplingProvider {
+    /// Chat completions, also spoken by most local and hosted gateways.
+    #[default]
+    OpenAi,
+    Anthropic,
+}
+
+impl SamplingProvider {
+    pub const ALL: [SamplingProvider; 2] = [SamplingProvider::OpenAi, SamplingProvider::Anthropic];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SamplingProvider::OpenAi => "OpenAI-compatible",
+            SamplingProvider::Anthropic => "Anthropic",
+        }
+    }
+
+    pub fn default_endpoint(self) -> &'static str {
+        match self {
+            SamplingProvider::OpenAi => "https://api.openai.com/v1/chat/completions",
+            SamplingProvider::Anthropic => "https://api.anthropic.com/v1/messages",
+        }
+    }
+}
+
+/// Where servers' sampling requests are sent once approved.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct SamplingSettings {
+    pub enabled: bool,
+    pub provider: SamplingProvider,
+    /// Empty means the provider's public API.
+    pub endpoint: String,
+    pub api_key: String,
+    /// Model every request is sent to; servers' model hints are only shown.
+    pub model: String,
+}
+
+impl SamplingSettings {
+    pub fn endpoint(&self) -> &str {
+        if self.endpoint.trim().is_empty() {
+            self.provider.default_endpoint()
+        } else {
+            self.endpoint.trim()
+        }
+    }
+}
+
+/// How a server's process is started, beyond its command and env.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct LaunchOptions {
+    /// Start from an empty environment plus a small allowlist instead of
+    /// inheriting the manager's.
+    pub clean_env: bool,
+    /// Label of the encoding the server prints its output in, e.g.
+    /// "windows-1252". None means UTF-8.
+    pub encoding: Option<String>,
+    /// Frame messages with `Content-Length` headers from the start, for
+    /// servers that expect them but never write first.
+    pub content_length_framing: bool,
+    /// Shell command run before the server starts. The start is aborted
+    /// if it fails.
+    pub pre_start: Option<String>,
+    /// Shell command run after the server stops.
+    pub post_stop: Option<String>,
+    /// Run the server in a pseudo-terminal, for servers that misbehave
+    /// when stdin isn't a terminal. Experimental; macOS and Linux only.
+    pub pty: bool,
+}
+
+/// A backup server that takes a primary's tool calls while the primary's
+/// circuit is open. Both are expected to expose the same tool names.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FailoverPair {
+    pub primary: String,
+    pub backup: String,
+}
+
+/// Something the app did on its own that is worth looking back on.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub id: i64,
+    /// Short kind, e.g. "failover".
+    pub event: String,
+    pub server_id: Option<String>,
+    pub detail: String,
+    pub created_at: String,
+}
+
+/// One tool call routed through the hub, as kept for reports.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ToolCallRecord {
+    pub server_id: String,
+    pub tool: String,
+    pub ok: bool,
+    pub created_at: String,
+    /// The call's arguments as JSON, when they were recorded.
+    #[serde(default)]
+    pub args: Option<String>,
+}
+
+/// A stretch of time a server was running. `stopped_at` is None while it
+/// still is.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ServerRun {
+    pub server_id: String,
+    pub started_at: String,
+    pub stopped_at: Option<String>,
 }
 
 impl From<rusqlite::Error> for AppError {
@@ -47,7 +359,7 @@ impl From<std::io::Error> for AppError {
 
 pub type AppResult<T> = Result<T, AppError>;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)] // Added PartialEq for Dioxus props
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)] // Added PartialEq for Dioxus props
 pub struct McpServer {
     pub id: String,
     pub name: String,
@@ -61,9 +373,220 @@ pub struct McpServer {
     pub is_active: bool,
     pub created_at: String,
     pub updated_at: String,
+    /// Kept with its config but hidden from the dashboard, not startable
+    /// and left out of exported configs.
+    #[serde(default)]
+    pub is_archived: bool,
+    /// When a temporary server is archived (UTC, database timestamp
+    /// format); `None` for permanent servers.
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    /// Favorites are listed before all other servers.
+    #[serde(default)]
+    pub is_pinned: bool,
+    /// Place on the dashboard, lowest first, within pinned and unpinned
+    /// servers.
+    #[serde(default)]
+    pub position: i64,
+    /// Seconds a request to the server waits for its reply; `None` for
+    /// the default.
+    #[serde(default)]
+    pub request_timeout_secs: Option<u32>,
+    /// Short name for the CLI and command palette, e.g. `fs`.
+    #[serde(default)]
+    pub alias: Option<String>,
+    /// Directory a stdio server is started in; `None` for the manager's
+    /// own.
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Image a stdio server runs in under Docker; `None` to run it on
+    /// the host.
+    #[serde(default)]
+    pub docker_image: Option<String>,
+    /// Volumes for its container, as `HOST:CONTAINER[:ro]`.
+    #[serde(default)]
+    pub docker_mounts: Option<Vec<String>>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+/// Longest alias a server can have.
+pub const MAX_ALIAS_LEN: usize = 32;
+
+/// Transports that connect to a URL instead of launching a command.
+pub const REMOTE_SERVER_TYPES: [&str; 2] = ["sse", "ws"];
+
+/// Transport for a remote server at `url`: WebSocket for `ws://` and
+/// `wss://` URLs, SSE otherwise.
+pub fn remote_server_type(url: &str) -> &'static str {
+    let url = url.trim().to_ascii_lowercase();
+    if url.starts_with("ws://") || url.starts_with("wss://") {
+        "ws"
+    } else {
+        "sse"
+    }
+}
+
+impl McpServer {
+    /// Whether the server is reached by URL rather than launched.
+    pub fn is_remote(&self) -> bool {
+        REMOTE_SERVER_TYPES.contains(&self.server_type.as_str())
+    }
+
+    /// Whether `name` is the server's name or alias.
+    pub fn is_called(&self, name: &str) -> bool {
+        self.name == name || self.alias.as_deref() == Some(name)
+    }
+
+    /// Same name, transport, command, args, url, env and description.
+    /// Ignores the id, active and archived flags and timestamps.
+    pub fn same_config(&self, other: &McpServer) -> bool {
+        self.name == other.name
+            && self.server_type == other.server_type
+            && self.command == other.command
+            && self.args == other.args
+            && self.url == other.url
+            && self.env == other.env
+            && self.description == other.description
+    }
+
+    /// Same transport, command, args, url, env, working directory and
+    /// container: everything a running process was started with.
+    /// Renaming a server doesn't need a restart.
+    pub fn same_launch_config(&self, other: &McpServer) -> bool {
+        self.server_type == other.server_type
+            && self.command == other.command
+            && self.args == other.args
+            && self.url == other.url
+            && self.env == other.env
+            && self.cwd == other.cwd
+            && self.docker_image == other.docker_image
+            && self.docker_mounts == other.docker_mounts
+    }
+}
+
+/// Builders for test fixtures.
+#[cfg(test)]
+impl McpServer {
+    /// An active stdio server running `command`, named after its id.
+    pub fn test_stdio(id: &str, command: &str) -> Self {
+        McpServer {
+            id: id.to_string(),
+            name: id.to_string(),
+            server_type: "stdio".to_string(),
+            command: Some(command.to_string()),
+            is_active: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn named(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn with_args(mut self, args: &[&str]) -> Self {
+        self.args = Some(args.iter().map(|a| a.to_string()).collect());
+        self
+    }
+
+    pub fn with_env(mut self, env: &[(&str, &str)]) -> Self {
+        self.env = Some(
+            env.iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        );
+        self
+    }
+}
+
+/// `alias` trimmed, if it can be given to server `id`: lowercase letters,
+/// digits, `-` and `_`, and not the name or alias of another of `servers`.
+pub fn check_alias(alias: &str, id: &str, servers: &[McpServer]) -> Result<String, String> {
+    let alias = alias.trim();
+    if alias.is_empty() || alias.len() > MAX_ALIAS_LEN {
+        return Err(format!(
+            "An alias is 1 to {} characters long",
+            MAX_ALIAS_LEN
+        ));
+    }
+    if !alias
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    {
+        return Err("An alias may only use lowercase letters, digits, - and _".to_string());
+    }
+    if let Some(other) = servers.iter().find(|s| s.id != id && s.is_called(alias)) {
+        return Err(format!("{} is already used by {}", alias, other.name));
+    }
+    Ok(alias.to_string())
+}
+
+/// `cwd` trimmed, if a stdio server can be started in it: a full path to
+/// a directory that exists.
+pub fn check_cwd(cwd: &str) -> Result<String, String> {
+    let cwd = cwd.trim();
+    let path = std::path::Path::new(cwd);
+    if !path.is_absolute() {
+        return Err(format!("{} is not a full path", cwd));
+    }
+    if !path.is_dir() {
+        return Err(format!("{} is not a directory", cwd));
+    }
+    Ok(cwd.to_string())
+}
+
+/// A server's configuration as it was before an update.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ServerRevision {
+    pub id: i64,
+    pub server_id: String,
+    pub config: McpServer,
+    pub created_at: String,
+}
+
+/// Named arguments saved for one tool of one server, e.g. "prod database".
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ToolPreset {
+    pub server_id: String,
+    pub tool: String,
+    pub name: String,
+    /// Arguments as JSON text.
+    pub args: String,
+}
+
+/// A change the hub makes to a tool's arguments before passing the call on.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RuleAction {
+    /// Set `key` to `value` when the call leaves it out.
+    Default {
+        key: String,
+        value: serde_json::Value,
+    },
+    /// Move `from` to `to`, for clients that name an argument differently.
+    Rename { from: String, to: String },
+    /// Keep the number `key` within `min` and `max`.
+    Clamp {
+        key: String,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    /// Keep the path `key` under `root`: relative paths are resolved
+    /// against it, a missing one becomes the root itself, and paths
+    /// outside it are refused.
+    Root { key: String, root: String },
+}
+
+/// A rule for the calls to one tool of a server, or all of its tools.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ToolRule {
+    pub id: i64,
+    pub server_id: String,
+    /// Tool name, or `*` for every tool.
+    pub tool: String,
+    pub action: RuleAction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct CreateServerArgs {
     pub name: String,
     #[serde(rename = "type")]
@@ -75,7 +598,7 @@ pub struct CreateServerArgs {
     pub description: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct UpdateServerArgs {
     pub name: Option<String>,
     #[serde(rename = "type")]
@@ -86,15 +609,47 @@ pub struct UpdateServerArgs {
     pub env: Option<std::collections::HashMap<String, String>>,
     pub description: Option<String>,
     pub is_active: Option<bool>,
+    /// For the fields below, `Some(None)` clears the setting.
+    pub request_timeout_secs: Option<Option<u32>>,
+    pub alias: Option<Option<String>>,
+    pub cwd: Option<Option<String>>,
+    pub docker_image: Option<Option<String>>,
+    /// Written along with `docker_image`, and only kept with an image.
+    pub docker_mounts: Option<Vec<String>>,
 }
 
 // MCP Protocol Structs
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Tool {
     pub name: String,
     pub description: Option<String>,
     pub inputSchema: serde_json::Value,
+    /// Schema of the structured content the tool returns, if declared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outputSchema: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<ToolAnnotations>,
+}
+
+impl Tool {
+    /// Whether the server declares the tool free of side effects.
+    pub fn is_read_only(&self) -> bool {
+        self.annotations
+            .as_ref()
+            .and_then(|a| a.readOnlyHint)
+            .unwrap_or(false)
+    }
+}
+
+/// Behaviour hints a server may attach to a tool.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ToolAnnotations {
+    pub title: Option<String>,
+    pub readOnlyHint: Option<bool>,
+    pub destructiveHint: Option<bool>,
+    pub idempotentHint: Option<bool>,
+    pub openWorldHint: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -105,20 +660,123 @@ pub struct Resource {
     pub mimeType: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+/// A family of resources addressed by filling in `uriTemplate`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ResourceTemplate {
+    pub uriTemplate: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub mimeType: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Prompt {
     pub name: String,
     pub description: Option<String>,
     pub arguments: Option<Vec<PromptArgument>>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct PromptArgument {
     pub name: String,
     pub description: Option<String>,
     pub required: Option<bool>,
 }
 
+/// Name and version a client or server reports in `initialize`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Implementation {
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+}
+
+/// Features a server declares in its `initialize` reply. Each is an
+/// object of sub-capabilities (e.g. `listChanged`) when supported.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ServerCapabilities {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompts: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logging: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completions: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experimental: Option<serde_json::Value>,
+}
+
+impl ServerCapabilities {
+    /// Whether the server sends updates for resources subscribed to.
+    pub fn resource_subscribe(&self) -> bool {
+        self.resources
+            .as_ref()
+            .and_then(|r| r.get("subscribe"))
+            .and_then(|s| s.as_bool())
+            .unwrap_or(false)
+    }
+}
+
+/// Severity of a server's log messages, least severe first, with the
+/// syslog names MCP uses.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum LoggingLevel {
+    Debug,
+    #[default]
+    Info,
+    Notice,
+    Warning,
+    Error,
+    Critical,
+    Alert,
+    Emergency,
+}
+
+impl LoggingLevel {
+    pub const ALL: [LoggingLevel; 8] = [
+        LoggingLevel::Debug,
+        LoggingLevel::Info,
+        LoggingLevel::Notice,
+        LoggingLevel::Warning,
+        LoggingLevel::Error,
+        LoggingLevel::Critical,
+        LoggingLevel::Alert,
+        LoggingLevel::Emergency,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoggingLevel::Debug => "debug",
+            LoggingLevel::Info => "info",
+            LoggingLevel::Notice => "notice",
+            LoggingLevel::Warning => "warning",
+            LoggingLevel::Error => "error",
+            LoggingLevel::Critical => "critical",
+            LoggingLevel::Alert => "alert",
+            LoggingLevel::Emergency => "emergency",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| level.as_str() == name)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InitializeResult {
+    pub protocolVersion: String,
+    #[serde(default)]
+    pub capabilities: ServerCapabilities,
+    #[serde(default)]
+    pub serverInfo: Option<Implementation>,
+    #[serde(default)]
+    pub instructions: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ListToolsResult {
     pub tools: Vec<Tool>,
@@ -129,24 +787,58 @@ pub struct ListResourcesResult {
     pub resources: Vec<Resource>,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListResourceTemplatesResult {
+    pub resourceTemplates: Vec<ResourceTemplate>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ListPromptsResult {
     pub prompts: Vec<Prompt>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Content {
+    /// `text`, `image`, `audio` or `resource`.
     #[serde(rename = "type")]
     pub content_type: String,
     pub text: Option<String>,
+    /// Media type of `data`, e.g. `image/png` or `audio/wav`.
     pub mimeType: Option<String>,
+    /// Base64 payload of image and audio blocks.
     pub data: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl Content {
+    pub fn is_image(&self) -> bool {
+        self.content_type == "image" && self.data.is_some()
+    }
+
+    pub fn is_audio(&self) -> bool {
+        self.content_type == "audio" && self.data.is_some()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CallToolResult {
     pub content: Vec<Content>,
     pub isError: Option<bool>,
+    /// Id of the request that produced this result, to find what the
+    /// server logged while it ran.
+    #[serde(skip)]
+    pub request_id: Option<u64>,
+    /// Set when the result was cut down to the response size limit.
+    #[serde(skip)]
+    pub truncated: Option<Truncation>,
+}
+
+/// What was cut from a tool result that went over the size limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Truncation {
+    /// Bytes of text and data the server sent.
+    pub original_bytes: usize,
+    /// File holding the whole result, unless writing it failed.
+    pub full_output: Option<std::path::PathBuf>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -162,6 +854,19 @@ pub struct ReadResourceResult {
     pub contents: Vec<ResourceContent>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PromptMessage {
+    /// `user` or `assistant`.
+    pub role: String,
+    pub content: Content,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GetPromptResult {
+    pub description: Option<String>,
+    pub messages: Vec<PromptMessage>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ResearchNote {
     pub id: String,
@@ -198,27 +903,52 @@ pub struct RegistryServer {
     pub category: Option<String>,
 }
 
+/// Registry text, either a plain (English) string or translations keyed
+/// by locale, e.g. `{"en": "Client ID", "de": "Client-ID"}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum LocalizedText {
+    Plain(String),
+    Translated(std::collections::BTreeMap<String, String>),
+}
+
+impl LocalizedText {
+    /// The text for `locale`, falling back to English.
+    pub fn get(&self, locale: &str) -> &str {
+        match self {
+            LocalizedText::Plain(text) => text,
+            LocalizedText::Translated(texts) => crate::locale::pick(texts, locale),
+        }
+    }
+}
+
+impl From<&str> for LocalizedText {
+    fn from(text: &str) -> Self {
+        LocalizedText::Plain(text.to_string())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum WizardAction {
     Link {
         url: String,
-        label: String,
+        label: LocalizedText,
     },
     Input {
         key: String,
-        label: String,
-        placeholder: Option<String>,
+        label: LocalizedText,
+        placeholder: Option<LocalizedText>,
     },
     Message {
-        text: String,
+        text: LocalizedText,
     },
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct WizardStep {
-    pub title: String,
-    pub description: String,
+    pub title: LocalizedText,
+    pub description: LocalizedText,
     pub action: WizardAction,
 }
 
@@ -287,6 +1017,28 @@ mod tests {
     use super::*;
     use std::collections::HashMap;
 
+    #[test]
+    fn test_wizard_step_localized() {
+        let step: WizardStep = serde_json::from_value(serde_json::json!({
+            "title": { "en": "Client ID", "de": "Client-ID" },
+            "description": "Enter your OAuth Client ID.",
+            "action": { "type": "input", "key": "CLIENT_ID", "label": { "en": "Client ID" } }
+        }))
+        .unwrap();
+        assert_eq!(step.title.get("de-AT"), "Client-ID");
+        assert_eq!(step.title.get("fr"), "Client ID");
+        assert_eq!(step.description.get("de"), "Enter your OAuth Client ID.");
+        match step.action {
+            WizardAction::Input {
+                label, placeholder, ..
+            } => {
+                assert_eq!(label.get("de"), "Client ID");
+                assert!(placeholder.is_none());
+            }
+            other => panic!("unexpected action {:?}", other),
+        }
+    }
+
     #[test]
     fn test_prepare_install_args_simple() {
         let item = RegistryItem {
@@ -361,12 +1113,12 @@ mod tests {
             server_type: "stdio".to_string(),
             command: Some("npx".to_string()),
             args: Some(vec!["-y".to_string(), "test".to_string()]),
-            url: None,
             env: Some(HashMap::from([("KEY".to_string(), "VALUE".to_string())])),
             description: Some("Test server".to_string()),
             is_active: true,
             created_at: "2024-01-01".to_string(),
             updated_at: "2024-01-01".to_string(),
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&server).unwrap();
@@ -392,6 +1144,41 @@ mod tests {
         assert_eq!(server.url, Some("https://example.com/sse".to_string()));
     }
 
+    #[test]
+    fn test_check_alias() {
+        let json = r#"{"id":"1","name":"filesystem","type":"stdio","is_active":true,
+            "created_at":"","updated_at":"","alias":"fs"}"#;
+        let files: McpServer = serde_json::from_str(json).unwrap();
+        assert!(files.is_called("filesystem") && files.is_called("fs"));
+        let servers = [files];
+
+        assert_eq!(check_alias(" gh ", "2", &servers).unwrap(), "gh");
+        // A server can keep its own alias
+        assert_eq!(check_alias("fs", "1", &servers).unwrap(), "fs");
+        assert_eq!(
+            check_alias("fs", "2", &servers).unwrap_err(),
+            "fs is already used by filesystem"
+        );
+        assert!(check_alias("filesystem", "2", &servers).is_err());
+        assert!(check_alias("GH", "2", &servers).is_err());
+        assert!(check_alias("g h", "2", &servers).is_err());
+        assert!(check_alias("", "2", &servers).is_err());
+        assert!(check_alias(&"x".repeat(MAX_ALIAS_LEN + 1), "2", &servers).is_err());
+    }
+
+    #[test]
+    fn test_check_cwd() {
+        let dir = std::env::temp_dir();
+        let text = format!(" {} ", dir.display());
+        assert_eq!(check_cwd(&text).unwrap(), dir.display().to_string());
+        assert_eq!(
+            check_cwd("weather").unwrap_err(),
+            "weather is not a full path"
+        );
+        let missing = dir.join(format!("omm-cwd-{}", uuid::Uuid::new_v4()));
+        assert!(check_cwd(&missing.display().to_string()).is_err());
+    }
+
     // === CreateServerArgs Tests ===
 
     #[test]
@@ -437,6 +1224,92 @@ mod tests {
         );
     }
 
+    // === Session Tests ===
+
+    #[test]
+    fn test_session_state_tolerates_missing_fields() {
+        let session: SessionState =
+            serde_json::from_str(r#"{"running_servers": ["a"], "console_tab": "tools"}"#).unwrap();
+        assert_eq!(session.running_servers, vec!["a"]);
+        assert_eq!(session.console_tab.as_deref(), Some("tools"));
+        assert_eq!(session.active_tab, "");
+        assert!(session.console_server.is_none());
+    }
+
+    // === Notification Rule Tests ===
+
+    #[test]
+    fn test_notification_rules_default_delivers_everything() {
+        let rules = NotificationRules::default();
+        let d = rules.delivery(&NotificationLevel::Info, NotificationEvent::General, 12);
+        assert!(d.toast);
+        assert!(!d.native);
+        let d = rules.delivery(
+            &NotificationLevel::Error,
+            NotificationEvent::ServerFailure,
+            12,
+        );
+        assert!(d.toast && d.native);
+    }
+
+    #[test]
+    fn test_notification_rules_muting() {
+        let rules = NotificationRules {
+            muted_levels: vec![NotificationLevel::Info],
+            muted_events: vec![NotificationEvent::PackageUpdate],
+            ..Default::default()
+        };
+        assert!(
+            !rules
+                .delivery(&NotificationLevel::Info, NotificationEvent::General, 12)
+                .toast
+        );
+        assert!(
+            !rules
+                .delivery(
+                    &NotificationLevel::Error,
+                    NotificationEvent::PackageUpdate,
+                    12
+                )
+                .toast
+        );
+        assert!(
+            rules
+                .delivery(&NotificationLevel::Error, NotificationEvent::General, 12)
+                .toast
+        );
+
+        let dnd = NotificationRules {
+            do_not_disturb: true,
+            ..Default::default()
+        };
+        let d = dnd.delivery(
+            &NotificationLevel::Error,
+            NotificationEvent::ServerFailure,
+            12,
+        );
+        assert!(!d.toast && !d.native);
+    }
+
+    #[test]
+    fn test_quiet_hours_wrap_midnight() {
+        let rules = NotificationRules {
+            quiet_hours: Some((22, 7)),
+            ..Default::default()
+        };
+        assert!(rules.in_quiet_hours(23));
+        assert!(rules.in_quiet_hours(3));
+        assert!(!rules.in_quiet_hours(7));
+        assert!(!rules.in_quiet_hours(12));
+
+        let daytime = NotificationRules {
+            quiet_hours: Some((9, 17)),
+            ..Default::default()
+        };
+        assert!(daytime.in_quiet_hours(9));
+        assert!(!daytime.in_quiet_hours(17));
+    }
+
     // === Notification Tests ===
 
     #[test]
@@ -455,6 +1328,10 @@ mod tests {
             message: "Test message".to_string(),
             level: NotificationLevel::Success,
             duration: 5,
+            server_id: None,
+            created_at: String::new(),
+            read: false,
+            event: NotificationEvent::General,
         };
 
         let json = serde_json::to_string(&notification).unwrap();
@@ -475,6 +1352,45 @@ mod tests {
         let tool: Tool = serde_json::from_str(json).unwrap();
         assert_eq!(tool.name, "test_tool");
         assert_eq!(tool.description, Some("A test tool".to_string()));
+        assert!(!tool.is_read_only());
+    }
+
+    #[test]
+    fn test_same_launch_config_ignores_name_and_description() {
+        let launched = McpServer {
+            id: "1".to_string(),
+            name: "fs".to_string(),
+            server_type: "stdio".to_string(),
+            command: Some("npx".to_string()),
+            args: Some(vec!["server-fs".to_string()]),
+            is_active: true,
+            ..Default::default()
+        };
+        let renamed = McpServer {
+            name: "files".to_string(),
+            description: Some("Local files".to_string()),
+            ..launched.clone()
+        };
+        assert!(launched.same_launch_config(&renamed));
+
+        let new_env = McpServer {
+            env: Some(HashMap::from([("ROOT".to_string(), "/tmp".to_string())])),
+            ..launched.clone()
+        };
+        assert!(!launched.same_launch_config(&new_env));
+    }
+
+    #[test]
+    fn test_tool_annotations_read_only() {
+        let json = r#"{
+            "name": "list_directory",
+            "inputSchema": {"type": "object"},
+            "annotations": {"readOnlyHint": true, "openWorldHint": false}
+        }"#;
+
+        let tool: Tool = serde_json::from_str(json).unwrap();
+        assert!(tool.is_read_only());
+        assert_eq!(tool.annotations.unwrap().openWorldHint, Some(false));
     }
 
     // === Resource Tests ===
@@ -493,6 +1409,23 @@ mod tests {
         assert_eq!(resource.mimeType, Some("text/plain".to_string()));
     }
 
+    #[test]
+    fn test_resource_templates_deserialization() {
+        let json = r#"{
+            "resourceTemplates": [
+                {"uriTemplate": "file:///logs/{date}", "name": "Daily log"}
+            ]
+        }"#;
+
+        let result: ListResourceTemplatesResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.resourceTemplates.len(), 1);
+        assert_eq!(
+            result.resourceTemplates[0].uriTemplate,
+            "file:///logs/{date}"
+        );
+        assert_eq!(result.resourceTemplates[0].description, None);
+    }
+
     // === Prompt Tests ===
 
     #[test]
@@ -514,13 +1447,45 @@ mod tests {
         assert_eq!(args[0].required, Some(true));
     }
 
+    #[test]
+    fn test_get_prompt_result_deserialization() {
+        let json = r#"{
+            "description": "Review code",
+            "messages": [
+                {"role": "user", "content": {"type": "text", "text": "Review this"}},
+                {"role": "assistant", "content": {"type": "image", "data": "AAAA", "mimeType": "image/png"}}
+            ]
+        }"#;
+
+        let result: GetPromptResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.description.as_deref(), Some("Review code"));
+        assert_eq!(result.messages.len(), 2);
+        assert_eq!(result.messages[0].role, "user");
+        assert_eq!(
+            result.messages[0].content.text.as_deref(),
+            Some("Review this")
+        );
+        assert!(result.messages[1].content.is_image());
+    }
+
+    #[test]
+    fn test_resource_subscribe_capability() {
+        let caps: ServerCapabilities =
+            serde_json::from_str(r#"{"resources": {"subscribe": true, "listChanged": true}}"#)
+                .unwrap();
+        assert!(caps.resource_subscribe());
+        let caps: ServerCapabilities = serde_json::from_str(r#"{"resources": {}}"#).unwrap();
+        assert!(!caps.resource_subscribe());
+        assert!(!ServerCapabilities::default().resource_subscribe());
+    }
+
     // === WizardAction Tests ===
 
     #[test]
     fn test_wizard_action_link_serialization() {
         let action = WizardAction::Link {
             url: "https://example.com".to_string(),
-            label: "Click here".to_string(),
+            label: "Click here".into(),
         };
 
         let json = serde_json::to_string(&action).unwrap();
@@ -532,8 +1497,8 @@ mod tests {
     fn test_wizard_action_input_serialization() {
         let action = WizardAction::Input {
             key: "API_KEY".to_string(),
-            label: "API Key".to_string(),
-            placeholder: Some("Enter your key".to_string()),
+            label: "API Key".into(),
+            placeholder: Some("Enter your key".into()),
         };
 
         let json = serde_json::to_string(&action).unwrap();
@@ -544,7 +1509,7 @@ mod tests {
     #[test]
     fn test_wizard_action_message_serialization() {
         let action = WizardAction::Message {
-            text: "Hello world".to_string(),
+            text: "Hello world".into(),
         };
 
         let json = serde_json::to_string(&action).unwrap();
@@ -578,6 +1543,21 @@ mod tests {
         assert_eq!(content.content_type, "image");
         assert_eq!(content.mimeType, Some("image/png".to_string()));
         assert_eq!(content.data, Some("base64data".to_string()));
+        assert!(content.is_image());
+    }
+
+    #[test]
+    fn test_content_audio_deserialization() {
+        let json = r#"{
+            "type": "audio",
+            "mimeType": "audio/wav",
+            "data": "UklGRg=="
+        }"#;
+
+        let content: Content = serde_json::from_str(json).unwrap();
+        assert!(content.is_audio());
+        assert!(!content.is_image());
+        assert_eq!(content.mimeType, Some("audio/wav".to_string()));
     }
 
     // === CallToolResult Tests ===
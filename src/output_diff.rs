@@ -0,0 +1,100 @@
+//! Line diff between two tool results, for comparing a pinned run with
+//! the latest one.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+/// Pretty-print JSON so formatting alone doesn't show up as a change.
+/// Other text is compared as is.
+fn normalize(text: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(text.trim())
+        .ok()
+        .and_then(|v| serde_json::to_string_pretty(&v).ok())
+        .unwrap_or_else(|| text.trim_end().to_string())
+}
+
+/// Lines of `old` and `new`, marked as kept, removed or added, in order.
+pub fn diff_outputs(old: &str, new: &str) -> Vec<DiffLine> {
+    let (old, new) = (normalize(old), normalize(new));
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // Longest common subsequence lengths of every suffix pair
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            lines.push(DiffLine::Same(a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(DiffLine::Removed(a[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(b[j].to_string()));
+            j += 1;
+        }
+    }
+    lines.extend(a[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+    lines.extend(b[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+    lines
+}
+
+/// Number of (removed, added) lines.
+pub fn change_counts(lines: &[DiffLine]) -> (usize, usize) {
+    lines.iter().fold((0, 0), |(r, a), line| match line {
+        DiffLine::Removed(_) => (r + 1, a),
+        DiffLine::Added(_) => (r, a + 1),
+        DiffLine::Same(_) => (r, a),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_outputs_marks_changed_lines() {
+        let lines = diff_outputs("a\nb\nc", "a\nx\nc\nd");
+        assert_eq!(
+            lines,
+            vec![
+                DiffLine::Same("a".into()),
+                DiffLine::Removed("b".into()),
+                DiffLine::Added("x".into()),
+                DiffLine::Same("c".into()),
+                DiffLine::Added("d".into()),
+            ]
+        );
+        assert_eq!(change_counts(&lines), (1, 2));
+    }
+
+    #[test]
+    fn test_diff_outputs_ignores_json_formatting() {
+        let lines = diff_outputs(r#"{"a":1,"b":2}"#, "{\n  \"a\": 1,\n  \"b\": 3\n}\n");
+        assert_eq!(change_counts(&lines), (1, 1));
+        assert!(lines.contains(&DiffLine::Added(r#"  "b": 3"#.into())));
+    }
+
+    #[test]
+    fn test_diff_outputs_identical() {
+        let lines = diff_outputs("same\n", "same");
+        assert_eq!(lines, vec![DiffLine::Same("same".into())]);
+        assert_eq!(change_counts(&lines), (0, 0));
+    }
+}
@@ -0,0 +1,388 @@
+//! Renderers for tool output text: picking a format by looking at the
+//! content, and the text transforms behind each one. Also helpers for
+//! binary (image and audio) content blocks.
+
+use base64::Engine;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Raw,
+    Json,
+    Markdown,
+    Csv,
+    /// Plain text with ANSI escape codes removed.
+    Plain,
+}
+
+impl OutputFormat {
+    pub const ALL: [OutputFormat; 5] = [
+        OutputFormat::Raw,
+        OutputFormat::Json,
+        OutputFormat::Markdown,
+        OutputFormat::Csv,
+        OutputFormat::Plain,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OutputFormat::Raw => "Raw",
+            OutputFormat::Json => "JSON",
+            OutputFormat::Markdown => "Markdown",
+            OutputFormat::Csv => "Table",
+            OutputFormat::Plain => "Strip ANSI",
+        }
+    }
+
+    /// Whether this format can show `text` at all.
+    pub fn applies_to(&self, text: &str) -> bool {
+        match self {
+            OutputFormat::Raw | OutputFormat::Markdown => true,
+            OutputFormat::Json => serde_json::from_str::<serde_json::Value>(text.trim()).is_ok(),
+            OutputFormat::Csv => parse_csv(text).is_some(),
+            OutputFormat::Plain => has_ansi(text),
+        }
+    }
+
+    /// Best format for `text`, by content heuristics.
+    pub fn detect(text: &str) -> OutputFormat {
+        let trimmed = text.trim();
+        if (trimmed.starts_with('{') || trimmed.starts_with('['))
+            && OutputFormat::Json.applies_to(trimmed)
+        {
+            OutputFormat::Json
+        } else if has_ansi(text) {
+            OutputFormat::Plain
+        } else if looks_like_markdown(text) {
+            OutputFormat::Markdown
+        } else if parse_csv(text).is_some() {
+            OutputFormat::Csv
+        } else {
+            OutputFormat::Raw
+        }
+    }
+}
+
+pub fn has_ansi(text: &str) -> bool {
+    text.contains('\u{1b}')
+}
+
+/// Remove ANSI escape sequences (colours, cursor movement).
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        match chars.peek() {
+            // CSI: ESC [ params final-byte
+            Some('[') => {
+                chars.next();
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: ESC ] ... BEL or ESC \
+            Some(']') => {
+                chars.next();
+                while let Some(c) = chars.next() {
+                    if c == '\u{7}' || (c == '\u{1b}' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            }
+            Some(_) => {
+                chars.next();
+            }
+            None => {}
+        }
+    }
+    out
+}
+
+fn looks_like_markdown(text: &str) -> bool {
+    text.lines().any(|l| {
+        let l = l.trim_start();
+        l.starts_with("# ")
+            || l.starts_with("## ")
+            || l.starts_with("### ")
+            || l.starts_with("```")
+            || l.starts_with("- ")
+            || l.starts_with("* ")
+    }) || text.contains("**")
+        || text.contains("](")
+}
+
+fn split_csv_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Rows of a CSV or TSV document, if `text` looks like one: at least two
+/// rows with the same number (two or more) of columns.
+pub fn parse_csv(text: &str) -> Option<Vec<Vec<String>>> {
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.len() < 2 {
+        return None;
+    }
+    let delimiter = if lines[0].contains('\t') { '\t' } else { ',' };
+    let rows: Vec<Vec<String>> = lines.iter().map(|l| split_csv_line(l, delimiter)).collect();
+    let columns = rows[0].len();
+    (columns >= 2 && rows.iter().all(|r| r.len() == columns)).then_some(rows)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Inline markdown: `code`, **bold**, *italic* and [links](url).
+fn render_inline(text: &str) -> String {
+    let escaped = escape_html(text);
+    let mut out = String::new();
+    let mut rest = escaped.as_str();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('`') {
+            if let Some(end) = after.find('`') {
+                out.push_str(&format!("<code>{}</code>", &after[..end]));
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix("**") {
+            if let Some(end) = after.find("**") {
+                out.push_str(&format!(
+                    "<strong>{}</strong>",
+                    render_inline_plain(&after[..end])
+                ));
+                rest = &after[end + 2..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix('*') {
+            if let Some(end) = after.find('*').filter(|&e| e > 0) {
+                out.push_str(&format!("<em>{}</em>", &after[..end]));
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix('[') {
+            if let Some((label, tail)) = after.split_once("](") {
+                if let Some((url, tail)) = tail.split_once(')') {
+                    if url.starts_with("http://") || url.starts_with("https://") {
+                        out.push_str(&format!(
+                            "<a href=\"{}\" target=\"_blank\">{}</a>",
+                            url, label
+                        ));
+                        rest = tail;
+                        continue;
+                    }
+                }
+            }
+        }
+        let c = rest.chars().next().unwrap();
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// Already-escaped text inside bold, where only italics are expanded.
+fn render_inline_plain(text: &str) -> String {
+    match text.split_once('*') {
+        Some((before, after)) => match after.split_once('*') {
+            Some((inner, tail)) => format!("{}<em>{}</em>{}", before, inner, tail),
+            None => text.to_string(),
+        },
+        None => text.to_string(),
+    }
+}
+
+/// Convert a markdown subset to HTML. All text is escaped first, so the
+/// result is safe to inject.
+pub fn markdown_to_html(text: &str) -> String {
+    let mut html = String::new();
+    let mut in_code = false;
+    let mut in_list = false;
+    let mut paragraph: Vec<String> = Vec::new();
+
+    let flush = |html: &mut String, paragraph: &mut Vec<String>| {
+        if !paragraph.is_empty() {
+            html.push_str(&format!("<p>{}</p>", paragraph.join(" ")));
+            paragraph.clear();
+        }
+    };
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            flush(&mut html, &mut paragraph);
+            html.push_str(if in_code {
+                "</code></pre>"
+            } else {
+                "<pre><code>"
+            });
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            html.push_str(&escape_html(line));
+            html.push('\n');
+            continue;
+        }
+
+        let item = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "));
+        if item.is_none() && in_list {
+            html.push_str("</ul>");
+            in_list = false;
+        }
+        if let Some(item) = item {
+            flush(&mut html, &mut paragraph);
+            if !in_list {
+                html.push_str("<ul>");
+                in_list = true;
+            }
+            html.push_str(&format!("<li>{}</li>", render_inline(item)));
+        } else if let Some(level) =
+            (1..=3).find(|&n| trimmed.starts_with(&format!("{} ", "#".repeat(n))))
+        {
+            flush(&mut html, &mut paragraph);
+            html.push_str(&format!(
+                "<h{0}>{1}</h{0}>",
+                level,
+                render_inline(&trimmed[level + 1..])
+            ));
+        } else if trimmed.is_empty() {
+            flush(&mut html, &mut paragraph);
+        } else {
+            paragraph.push(render_inline(trimmed));
+        }
+    }
+    flush(&mut html, &mut paragraph);
+    if in_list {
+        html.push_str("</ul>");
+    }
+    if in_code {
+        html.push_str("</code></pre>");
+    }
+    html
+}
+
+/// `data:` URL for a base64 content block, usable directly as an `src`.
+pub fn data_url(mime_type: &str, data: &str) -> String {
+    format!("data:{};base64,{}", mime_type, data.trim())
+}
+
+/// Decode a content block's base64 payload.
+pub fn decode_data(data: &str) -> Result<Vec<u8>, String> {
+    let cleaned: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(cleaned)
+        .map_err(|e| format!("Invalid base64 data: {}", e))
+}
+
+/// File extension to suggest when saving content of `mime_type`.
+pub fn extension_for(mime_type: &str) -> &str {
+    match mime_type {
+        "image/jpeg" => "jpg",
+        "image/svg+xml" => "svg",
+        "image/x-icon" | "image/vnd.microsoft.icon" => "ico",
+        "audio/mpeg" | "audio/mp3" => "mp3",
+        "audio/x-wav" | "audio/wave" => "wav",
+        other => other
+            .split_once('/')
+            .map(|(_, sub)| sub.split(['+', ';']).next().unwrap_or(sub))
+            .filter(|sub| !sub.is_empty())
+            .unwrap_or("bin"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(OutputFormat::detect(r#"{"a": 1}"#), OutputFormat::Json);
+        assert_eq!(
+            OutputFormat::detect("\u{1b}[31mred\u{1b}[0m"),
+            OutputFormat::Plain
+        );
+        assert_eq!(
+            OutputFormat::detect("# Title\nbody"),
+            OutputFormat::Markdown
+        );
+        assert_eq!(OutputFormat::detect("a,b\n1,2\n3,4"), OutputFormat::Csv);
+        assert_eq!(
+            OutputFormat::detect("just some text, really"),
+            OutputFormat::Raw
+        );
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("\u{1b}[1;32mok\u{1b}[0m done"), "ok done");
+        assert_eq!(strip_ansi("\u{1b}]0;title\u{7}text"), "text");
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let rows = parse_csv("name,note\n\"Smith, J\",\"said \"\"hi\"\"\"\n").unwrap();
+        assert_eq!(rows[1], vec!["Smith, J", "said \"hi\""]);
+        assert_eq!(parse_csv("a\tb\n1\t2").unwrap()[1], vec!["1", "2"]);
+        assert!(parse_csv("a,b\n1,2,3").is_none());
+        assert!(parse_csv("single line, here").is_none());
+    }
+
+    #[test]
+    fn test_markdown_to_html_escapes() {
+        let html = markdown_to_html(
+            "# Hi <there>\n\nSome **bold** and `code` [x](https://e.com).\n\n- one\n- two\n\n```\n<b>raw</b>\n```",
+        );
+        assert_eq!(
+            html,
+            "<h1>Hi &lt;there&gt;</h1>\
+             <p>Some <strong>bold</strong> and <code>code</code> <a href=\"https://e.com\" target=\"_blank\">x</a>.</p>\
+             <ul><li>one</li><li>two</li></ul>\
+             <pre><code>&lt;b&gt;raw&lt;/b&gt;\n</code></pre>"
+        );
+        assert!(!markdown_to_html("[x](javascript:alert(1))").contains("<a"));
+    }
+
+    #[test]
+    fn test_binary_content_helpers() {
+        assert_eq!(decode_data("aGVs\nbG8=").unwrap(), b"hello");
+        assert!(decode_data("not base64!").is_err());
+        assert_eq!(
+            data_url("image/png", "AAAA\n"),
+            "data:image/png;base64,AAAA"
+        );
+        assert_eq!(extension_for("image/png"), "png");
+        assert_eq!(extension_for("image/jpeg"), "jpg");
+        assert_eq!(extension_for("image/svg+xml"), "svg");
+        assert_eq!(extension_for("audio/mpeg"), "mp3");
+        assert_eq!(extension_for("garbage"), "bin");
+    }
+}
@@ -0,0 +1,320 @@
+//! Reading a server's stdout/stderr as lines of bytes and decoding them
+//! for the log. JSON-RPC traffic is always UTF-8, but some servers
+//! (often on Windows) print their own messages in a legacy code page.
+
+use encoding_rs::{Encoding, UTF_8};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// Encodings offered per server besides the UTF-8 default, by label.
+pub const COMMON_ENCODINGS: &[(&str, &str)] = &[
+    ("windows-1252", "Western (Windows-1252)"),
+    ("iso-8859-2", "Central European (ISO-8859-2)"),
+    ("windows-1251", "Cyrillic (Windows-1251)"),
+    ("gbk", "Chinese Simplified (GBK)"),
+    ("big5", "Chinese Traditional (Big5)"),
+    ("shift_jis", "Japanese (Shift_JIS)"),
+    ("euc-kr", "Korean (EUC-KR)"),
+];
+
+/// The encoding for a configured label; missing or unknown labels mean
+/// UTF-8.
+pub fn encoding_for(label: Option<&str>) -> &'static Encoding {
+    label
+        .and_then(|l| Encoding::for_label(l.trim().as_bytes()))
+        .unwrap_or(UTF_8)
+}
+
+/// Decode one line, replacing invalid sequences rather than failing.
+pub fn decode_line(encoding: &'static Encoding, bytes: &[u8]) -> String {
+    encoding.decode_without_bom_handling(bytes).0.into_owned()
+}
+
+/// Longest line kept from a server's stdout. JSON-RPC responses can carry
+/// large results (images, file contents), so this is generous.
+pub const MAX_STDOUT_LINE: usize = 32 * 1024 * 1024;
+
+/// Longest line kept from stderr, and shown in the log from stdout.
+pub const MAX_LOG_LINE: usize = 16 * 1024;
+
+/// A line (or framed message) as read, possibly cut short.
+#[derive(Debug, PartialEq)]
+pub struct ReadLine<'a> {
+    pub bytes: &'a [u8],
+    /// Bytes past the limit that were skipped.
+    pub dropped: usize,
+    /// Came as the body of a `Content-Length` frame rather than a line.
+    pub framed: bool,
+}
+
+/// Splits a byte stream into lines without assuming it is valid UTF-8,
+/// keeping at most `max_len` bytes of each. The rest of an oversized line
+/// is skipped chunk by chunk, so a server printing a 100MB line (or
+/// binary without newlines) can't exhaust memory.
+pub struct LineReader<R> {
+    inner: R,
+    buf: Vec<u8>,
+    max_len: usize,
+}
+
+impl<R: AsyncBufRead + Unpin> LineReader<R> {
+    pub fn new(inner: R, max_len: usize) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            max_len,
+        }
+    }
+
+    /// The next line without its `\n` / `\r\n`, or None at end of stream.
+    pub async fn next_line(&mut self) -> std::io::Result<Option<ReadLine<'_>>> {
+        let Some(dropped) = self.fill_line().await? else {
+            return Ok(None);
+        };
+        Ok(Some(ReadLine {
+            bytes: &self.buf,
+            dropped,
+            framed: false,
+        }))
+    }
+
+    /// Like [`Self::next_line`], but a `Content-Length` header block is
+    /// read as one message: the body that follows it.
+    pub async fn next_message(&mut self) -> std::io::Result<Option<ReadLine<'_>>> {
+        let Some(dropped) = self.fill_line().await? else {
+            return Ok(None);
+        };
+        let length = match dropped {
+            0 => crate::framing::content_length(&self.buf),
+            _ => None,
+        };
+        let Some(length) = length else {
+            return Ok(Some(ReadLine {
+                bytes: &self.buf,
+                dropped,
+                framed: false,
+            }));
+        };
+
+        // Skip any other headers up to the blank line
+        loop {
+            match self.fill_line().await? {
+                None => return Ok(None),
+                Some(_) if self.buf.is_empty() => break,
+                Some(_) => {}
+            }
+        }
+        let dropped = self.fill_body(length).await?;
+        Ok(Some(ReadLine {
+            bytes: &self.buf,
+            dropped,
+            framed: true,
+        }))
+    }
+
+    /// Read a line into `buf`, returning how many bytes were skipped, or
+    /// None at end of stream.
+    async fn fill_line(&mut self) -> std::io::Result<Option<usize>> {
+        self.buf.clear();
+        let mut total = 0;
+        let mut read_any = false;
+        loop {
+            let available = self.inner.fill_buf().await?;
+            if available.is_empty() {
+                break;
+            }
+            read_any = true;
+            let newline = available.iter().position(|&b| b == b'\n');
+            let chunk = &available[..newline.unwrap_or(available.len())];
+            let room = self.max_len.saturating_sub(self.buf.len());
+            self.buf.extend_from_slice(&chunk[..chunk.len().min(room)]);
+            total += chunk.len();
+            let used = newline.map_or(available.len(), |i| i + 1);
+            self.inner.consume(used);
+            if newline.is_some() {
+                break;
+            }
+        }
+        if !read_any {
+            return Ok(None);
+        }
+
+        let dropped = total - self.buf.len();
+        if dropped == 0 && self.buf.ends_with(b"\r") {
+            self.buf.pop();
+        }
+        Ok(Some(dropped))
+    }
+
+    /// Read exactly `length` bytes into `buf` (fewer at end of stream),
+    /// returning how many were skipped.
+    async fn fill_body(&mut self, length: usize) -> std::io::Result<usize> {
+        self.buf.clear();
+        let mut remaining = length;
+        while remaining > 0 {
+            let available = self.inner.fill_buf().await?;
+            if available.is_empty() {
+                break;
+            }
+            let chunk = &available[..available.len().min(remaining)];
+            let room = self.max_len.saturating_sub(self.buf.len());
+            self.buf.extend_from_slice(&chunk[..chunk.len().min(room)]);
+            let used = chunk.len();
+            self.inner.consume(used);
+            remaining -= used;
+        }
+        Ok(length - remaining - self.buf.len())
+    }
+}
+
+/// Whether `bytes` look like binary data rather than text: a NUL byte, or
+/// mostly control characters. Tabs, line endings and ANSI escapes count
+/// as text.
+pub fn looks_binary(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(512)];
+    if sample.contains(&0) {
+        return true;
+    }
+    let control = sample
+        .iter()
+        .filter(|&&b| b < 0x20 && !matches!(b, b'\t' | b'\r' | b'\n' | 0x1b))
+        .count();
+    control * 10 > sample.len() * 3
+}
+
+/// How a line appears in the log: decoded and cut to [`MAX_LOG_LINE`]
+/// with a marker, or a placeholder for binary data.
+pub fn log_text(encoding: &'static Encoding, line: &ReadLine) -> String {
+    let total = line.bytes.len() + line.dropped;
+    if looks_binary(line.bytes) {
+        return format!("[binary output hidden, {} bytes]", total);
+    }
+    let shown = &line.bytes[..line.bytes.len().min(MAX_LOG_LINE)];
+    let text = decode_line(encoding, shown);
+    if shown.len() < total {
+        format!(
+            "{} … [truncated, {} of {} bytes shown]",
+            text,
+            shown.len(),
+            total
+        )
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_legacy_code_pages() {
+        // “quoted” in Windows-1252
+        let cp1252 = encoding_for(Some("windows-1252"));
+        assert_eq!(
+            decode_line(cp1252, b"\x93quoted\x94"),
+            "\u{201c}quoted\u{201d}"
+        );
+        // 中文 in GBK
+        assert_eq!(
+            decode_line(encoding_for(Some("GBK")), b"\xd6\xd0\xce\xc4"),
+            "中文"
+        );
+    }
+
+    #[test]
+    fn test_utf8_default_is_lossy() {
+        assert_eq!(encoding_for(None), UTF_8);
+        assert_eq!(encoding_for(Some("not-an-encoding")), UTF_8);
+        assert_eq!(decode_line(UTF_8, b"ok \xff"), "ok \u{fffd}");
+    }
+
+    async fn read_all(input: &[u8], max_len: usize) -> Vec<(Vec<u8>, usize)> {
+        // A tiny buffer makes every line span several chunks
+        let mut reader = LineReader::new(tokio::io::BufReader::with_capacity(4, input), max_len);
+        let mut lines = Vec::new();
+        while let Some(line) = reader.next_line().await.unwrap() {
+            lines.push((line.bytes.to_vec(), line.dropped));
+        }
+        lines
+    }
+
+    #[tokio::test]
+    async fn test_line_reader_splits_bytes() {
+        let lines = read_all(b"one\r\ntw\xffo\nlast", 100).await;
+        assert_eq!(
+            lines,
+            vec![
+                (b"one".to_vec(), 0),
+                (b"tw\xffo".to_vec(), 0),
+                (b"last".to_vec(), 0)
+            ]
+        );
+        assert!(read_all(b"", 100).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_huge_line_is_truncated_and_reading_continues() {
+        let mut input = vec![b'x'; 5 * 1024 * 1024];
+        input.extend_from_slice(b"\r\nnext\n");
+        let lines = read_all(&input, 1024).await;
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].0.len(), 1024);
+        // The \r is part of what was skipped
+        assert_eq!(lines[0].1, 5 * 1024 * 1024 + 1 - 1024);
+        assert_eq!(lines[1], (b"next".to_vec(), 0));
+
+        let line = ReadLine {
+            bytes: &lines[0].0,
+            dropped: lines[0].1,
+            framed: false,
+        };
+        let text = log_text(UTF_8, &line);
+        assert!(text.starts_with("xxxx"));
+        assert!(text.ends_with(&format!(
+            "[truncated, 1024 of {} bytes shown]",
+            5 * 1024 * 1024 + 1
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_next_message_reads_both_framings() {
+        let input = b"{\"id\":1}\nContent-Length: 8\r\nContent-Type: application/json\r\n\r\n{\"id\":2}log line\n";
+        let mut reader = LineReader::new(tokio::io::BufReader::with_capacity(4, &input[..]), 100);
+        let mut messages = Vec::new();
+        while let Some(message) = reader.next_message().await.unwrap() {
+            messages.push((message.bytes.to_vec(), message.framed));
+        }
+        assert_eq!(
+            messages,
+            vec![
+                (br#"{"id":1}"#.to_vec(), false),
+                (br#"{"id":2}"#.to_vec(), true),
+                (b"log line".to_vec(), false),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_binary_output_is_hidden() {
+        let garbage: Vec<u8> = (0..=255u8)
+            .cycle()
+            .take(4096)
+            .filter(|&b| b != b'\n')
+            .collect();
+        let lines = read_all(&garbage, MAX_LOG_LINE).await;
+        assert_eq!(lines.len(), 1);
+        let line = ReadLine {
+            bytes: &lines[0].0,
+            dropped: lines[0].1,
+            framed: false,
+        };
+        assert_eq!(
+            log_text(UTF_8, &line),
+            format!("[binary output hidden, {} bytes]", garbage.len())
+        );
+
+        // Colored text is not binary
+        assert!(!looks_binary(b"\x1b[32mINFO\x1b[0m\tready"));
+    }
+}
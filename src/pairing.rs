@@ -0,0 +1,199 @@
+//! Pairing with remote servers: a code shown by the remote host (as text
+//! or as a QR code) that carries the server's URL and access token, so
+//! the SSE config can be created without typing either.
+//!
+//! A pairing code is the base64url-encoded JSON `{"url", "token", "name"}`,
+//! optionally prefixed with `mcp-pair:` (the form QR codes use). A plain
+//! `http(s)://` URL with a `token` query parameter is accepted too.
+
+use crate::models::CreateServerArgs;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Prefix of pairing codes encoded in QR codes.
+pub const PAIRING_SCHEME: &str = "mcp-pair:";
+
+/// Env entry of an SSE server holding the token sent as
+/// `Authorization: Bearer`.
+pub const TOKEN_ENV_KEY: &str = "MCP_BEARER_TOKEN";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Pairing {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Read a pairing code, `mcp-pair:` QR text or tokenized URL.
+pub fn parse(input: &str) -> Result<Pairing, String> {
+    let input: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if input.is_empty() {
+        return Err("Enter a pairing code".to_string());
+    }
+    let pairing = if is_http_url(&input) {
+        from_url(&input)
+    } else {
+        let code = match input.get(..PAIRING_SCHEME.len()) {
+            Some(prefix) if prefix.eq_ignore_ascii_case(PAIRING_SCHEME) => {
+                &input[PAIRING_SCHEME.len()..]
+            }
+            _ => input.as_str(),
+        };
+        let json = URL_SAFE_NO_PAD
+            .decode(code.trim_end_matches('='))
+            .map_err(|_| "Not a valid pairing code".to_string())?;
+        serde_json::from_slice::<Pairing>(&json)
+            .map_err(|_| "Not a valid pairing code".to_string())?
+    };
+    if !is_http_url(&pairing.url) {
+        return Err(format!("Unsupported server URL: {}", pairing.url));
+    }
+    Ok(pairing)
+}
+
+/// The pairing code for `pairing`, as a host would show it.
+pub fn encode(pairing: &Pairing) -> String {
+    let json = serde_json::to_vec(pairing).unwrap_or_default();
+    format!("{}{}", PAIRING_SCHEME, URL_SAFE_NO_PAD.encode(json))
+}
+
+fn is_http_url(text: &str) -> bool {
+    let lower = text.to_ascii_lowercase();
+    lower.starts_with("https://") || lower.starts_with("http://")
+}
+
+/// Split a `token` query parameter off `url`.
+fn from_url(url: &str) -> Pairing {
+    let (base, fragment) = match url.split_once('#') {
+        Some((base, fragment)) => (base, Some(fragment)),
+        None => (url, None),
+    };
+    let (path, query) = base.split_once('?').unwrap_or((base, ""));
+    let mut token = None;
+    let rest: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| match pair.split_once('=') {
+            Some(("token", value)) => {
+                token = urlencoding::decode(value).ok().map(|v| v.into_owned());
+                false
+            }
+            _ => true,
+        })
+        .collect();
+
+    let mut url = path.to_string();
+    if !rest.is_empty() {
+        url.push('?');
+        url.push_str(&rest.join("&"));
+    }
+    if let Some(fragment) = fragment {
+        url.push('#');
+        url.push_str(fragment);
+    }
+    Pairing {
+        url,
+        token,
+        name: None,
+    }
+}
+
+/// `host[:port]` of an http(s) URL.
+pub fn host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split(['/', '?', '#']).next().unwrap_or(rest)
+}
+
+impl Pairing {
+    /// Whether the token would be sent unencrypted to another machine.
+    pub fn is_insecure(&self) -> bool {
+        let host = host(&self.url);
+        let hostname = host.rsplit_once(':').map_or(host, |(name, _)| name);
+        self.token.is_some()
+            && self.url.to_ascii_lowercase().starts_with("http://")
+            && !matches!(hostname, "localhost" | "127.0.0.1" | "[::1]")
+    }
+
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+            _ => host(&self.url).to_string(),
+        }
+    }
+
+    /// Config for an SSE server using this pairing.
+    pub fn to_server_args(&self) -> CreateServerArgs {
+        let env = self
+            .token
+            .as_ref()
+            .map(|token| HashMap::from([(TOKEN_ENV_KEY.to_string(), token.clone())]));
+        CreateServerArgs {
+            name: self.display_name(),
+            server_type: "sse".to_string(),
+            command: None,
+            args: None,
+            url: Some(self.url.clone()),
+            env,
+            description: Some(format!("Paired with {}", host(&self.url))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_round_trip() {
+        let pairing = Pairing {
+            url: "https://mcp.example.com/sse".to_string(),
+            token: Some("s3cr3t".to_string()),
+            name: Some("Build box".to_string()),
+        };
+        let code = encode(&pairing);
+        assert!(code.starts_with("mcp-pair:"));
+        assert_eq!(parse(&code), Ok(pairing.clone()));
+        // Typed without the prefix, wrapped over lines
+        let bare = &code[PAIRING_SCHEME.len()..];
+        let wrapped = format!("{}\n {}", &bare[..10], &bare[10..]);
+        assert_eq!(parse(&wrapped), Ok(pairing));
+    }
+
+    #[test]
+    fn test_url_with_token() {
+        let pairing = parse("http://10.0.0.5:8080/sse?v=2&token=a%2Bb#x").unwrap();
+        assert_eq!(pairing.url, "http://10.0.0.5:8080/sse?v=2#x");
+        assert_eq!(pairing.token.as_deref(), Some("a+b"));
+        assert_eq!(pairing.display_name(), "10.0.0.5:8080");
+        assert!(pairing.is_insecure());
+        assert!(!parse("http://localhost:3000/sse?token=t")
+            .unwrap()
+            .is_insecure());
+    }
+
+    #[test]
+    fn test_invalid_codes() {
+        assert!(parse("").is_err());
+        assert!(parse("not a code!").is_err());
+        let ftp = encode(&Pairing {
+            url: "ftp://example.com".to_string(),
+            token: None,
+            name: None,
+        });
+        assert!(parse(&ftp).is_err());
+    }
+
+    #[test]
+    fn test_server_args() {
+        let args = parse("https://example.com/sse?token=t")
+            .unwrap()
+            .to_server_args();
+        assert_eq!(args.server_type, "sse");
+        assert_eq!(args.name, "example.com");
+        assert_eq!(args.env.unwrap()[TOKEN_ENV_KEY], "t");
+    }
+}
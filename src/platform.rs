@@ -0,0 +1,92 @@
+//! Cross-platform helpers for showing files and folders in the OS file manager.
+
+use crate::models::{AppError, AppResult, McpServer};
+use std::path::{Path, PathBuf};
+
+/// Directory holding the database and other app data.
+pub fn app_data_dir() -> AppResult<PathBuf> {
+    let mut path = dirs::data_local_dir().ok_or(AppError::Io("Could not find data dir".into()))?;
+    path.push("open-mcp-manager");
+    Ok(path)
+}
+
+/// Directory a stdio server is launched from: its configured one, or
+/// else the manager's own working directory.
+pub fn server_working_dir(server: &McpServer) -> AppResult<PathBuf> {
+    match &server.cwd {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => Ok(std::env::current_dir()?),
+    }
+}
+
+/// Closest existing directory for `path`: the path itself if it is a
+/// directory, otherwise the nearest existing ancestor.
+fn nearest_existing_dir(path: &Path) -> Option<&Path> {
+    let mut current = Some(path);
+    while let Some(p) = current {
+        if p.is_dir() {
+            return Some(p);
+        }
+        current = p.parent();
+    }
+    None
+}
+
+/// Open `path` in Finder / Explorer / the default file manager. Files are
+/// selected where the platform supports it; missing paths fall back to
+/// the nearest existing parent folder.
+pub fn reveal(path: &Path) -> AppResult<()> {
+    if path.is_file() {
+        #[cfg(target_os = "macos")]
+        {
+            let child = std::process::Command::new("open")
+                .arg("-R")
+                .arg(path)
+                .spawn()?;
+            reap(child);
+            return Ok(());
+        }
+        #[cfg(target_os = "windows")]
+        {
+            // Separate args, so a path with spaces or commas stays one argument
+            let child = std::process::Command::new("explorer")
+                .arg("/select,")
+                .arg(path)
+                .spawn()?;
+            reap(child);
+            return Ok(());
+        }
+    }
+
+    let dir = nearest_existing_dir(path)
+        .ok_or_else(|| AppError::Io(format!("{} does not exist", path.display())))?;
+    open::that_detached(dir)?;
+    Ok(())
+}
+
+/// Wait for a short-lived helper process off-thread so it doesn't linger
+/// as a zombie.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn reap(mut child: std::process::Child) {
+    std::thread::spawn(move || child.wait());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_existing_dir() {
+        let tmp = std::env::temp_dir();
+        assert_eq!(nearest_existing_dir(&tmp), Some(tmp.as_path()));
+
+        let missing = tmp.join("omm-missing").join("config.json");
+        assert_eq!(nearest_existing_dir(&missing), Some(tmp.as_path()));
+    }
+
+    #[test]
+    fn test_app_data_dir_is_namespaced() {
+        let dir = app_data_dir().unwrap();
+        assert!(dir.ends_with("open-mcp-manager"));
+    }
+}
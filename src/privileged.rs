@@ -0,0 +1,234 @@
+//! Writing editor configs the manager's user can't write itself, e.g.
+//! files owned by another user or under a protected directory.
+//!
+//! The new config is staged in the app data dir and copied into place by
+//! the OS's own elevation prompt: polkit's `pkexec` on Linux, an
+//! administrator `do shell script` on macOS and a UAC `RunAs` on Windows.
+//! There is no bundled helper binary, so nothing extra needs signing or
+//! notarizing. When the prompt is refused the staged file stays behind
+//! for the user to copy by hand.
+
+use std::path::{Path, PathBuf};
+
+/// A config written to the staging dir, waiting to be copied over `target`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StagedWrite {
+    pub target: PathBuf,
+    pub staged: PathBuf,
+    /// Copy of the file `staged` replaces, if there was one.
+    pub backup: Option<PathBuf>,
+}
+
+impl StagedWrite {
+    /// What to do by hand when elevation is refused.
+    pub fn manual_instructions(&self) -> String {
+        format!(
+            "The new config is saved at {}. Copy it over {} to finish.",
+            self.staged.display(),
+            self.target.display()
+        )
+    }
+}
+
+pub fn staging_dir() -> crate::models::AppResult<PathBuf> {
+    Ok(crate::platform::app_data_dir()?.join("pending_configs"))
+}
+
+/// Whether the manager can write `path`, and a `.bak` next to it, without
+/// elevation. Probes the nearest existing directory with a scratch file.
+pub fn is_writable(path: &Path) -> bool {
+    if path.exists() && std::fs::OpenOptions::new().append(true).open(path).is_err() {
+        return false;
+    }
+    let Some(dir) = path.ancestors().skip(1).find(|p| p.is_dir()) else {
+        return false;
+    };
+    let probe = dir.join(format!(".omm-write-probe-{}", std::process::id()));
+    match std::fs::File::create(&probe) {
+        Ok(_) => {
+            let _ = std::fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Save `contents` for `target` in `dir`, along with a copy of the
+/// current file, which the user can usually still read.
+pub fn stage(dir: &Path, target: &Path, contents: &str) -> std::io::Result<StagedWrite> {
+    std::fs::create_dir_all(dir)?;
+    let name: String = target
+        .iter()
+        .rev()
+        .take(2)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .map(|part| part.to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("-");
+    let staged = dir.join(&name);
+    std::fs::write(&staged, contents)?;
+    let backup = if target.is_file() {
+        let backup = dir.join(format!("{}.bak", name));
+        std::fs::copy(target, &backup)?;
+        Some(backup)
+    } else {
+        None
+    };
+    Ok(StagedWrite {
+        target: target.to_path_buf(),
+        staged,
+        backup,
+    })
+}
+
+/// `s` quoted for a POSIX shell.
+fn sh_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// `s` quoted for PowerShell.
+fn ps_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+/// The program and arguments that copy `write` into place with elevated
+/// rights on `os`, as named by `std::env::consts::OS`.
+pub fn elevation_command(os: &str, write: &StagedWrite) -> Option<(String, Vec<String>)> {
+    let parent = write.target.parent()?.to_string_lossy().into_owned();
+    let staged = write.staged.to_string_lossy().into_owned();
+    let target = write.target.to_string_lossy().into_owned();
+    match os {
+        "linux" => Some((
+            "pkexec".to_string(),
+            vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                // `cp` onto an existing file keeps its owner and mode
+                r#"mkdir -p "$1" && cp "$2" "$3""#.to_string(),
+                "sh".to_string(),
+                parent,
+                staged,
+                target,
+            ],
+        )),
+        "macos" => {
+            let script = format!(
+                "mkdir -p {} && cp {} {}",
+                sh_quote(&parent),
+                sh_quote(&staged),
+                sh_quote(&target)
+            );
+            let script = script.replace('\\', r"\\").replace('"', "\\\"");
+            Some((
+                "osascript".to_string(),
+                vec![
+                    "-e".to_string(),
+                    format!(
+                        "do shell script \"{}\" with administrator privileges",
+                        script
+                    ),
+                ],
+            ))
+        }
+        "windows" => {
+            let inner = format!(
+                "New-Item -ItemType Directory -Force -Path {} | Out-Null; Copy-Item -LiteralPath {} -Destination {} -Force",
+                ps_quote(&parent),
+                ps_quote(&staged),
+                ps_quote(&target)
+            );
+            let outer = format!(
+                "$p = Start-Process -FilePath powershell -Verb RunAs -Wait -PassThru -ArgumentList '-NoProfile','-Command',{}; exit $p.ExitCode",
+                ps_quote(&inner)
+            );
+            Some((
+                "powershell".to_string(),
+                vec!["-NoProfile".to_string(), "-Command".to_string(), outer],
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Copy `write` into place through the OS's elevation prompt.
+pub async fn write_elevated(write: &StagedWrite) -> Result<(), String> {
+    let (program, args) = elevation_command(std::env::consts::OS, write)
+        .ok_or("No way to ask for permission on this system")?;
+    let status = tokio::process::Command::new(&program)
+        .args(&args)
+        .status()
+        .await
+        .map_err(|e| format!("Couldn't run {}: {}", program, e))?;
+    if !status.success() {
+        return Err("Permission was denied".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn staged(target: &str) -> StagedWrite {
+        StagedWrite {
+            target: PathBuf::from(target),
+            staged: PathBuf::from("/data/pending_configs/Claude-config.json"),
+            backup: None,
+        }
+    }
+
+    #[test]
+    fn test_stage_keeps_a_backup() {
+        let root = std::env::temp_dir().join(format!("omm-privileged-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let target = root.join("Claude").join("config.json");
+        std::fs::create_dir_all(target.parent().unwrap()).unwrap();
+        std::fs::write(&target, "{}").unwrap();
+        assert!(is_writable(&target));
+        assert!(is_writable(&root.join("missing").join("config.json")));
+
+        let write = stage(&root.join("staging"), &target, "{\"a\":1}").unwrap();
+        assert!(write.staged.ends_with("Claude-config.json"));
+        assert_eq!(std::fs::read_to_string(&write.staged).unwrap(), "{\"a\":1}");
+        let backup = write.backup.clone().unwrap();
+        assert_eq!(std::fs::read_to_string(backup).unwrap(), "{}");
+        assert!(write
+            .manual_instructions()
+            .contains(&target.display().to_string()));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_elevation_commands() {
+        let write = staged("/etc/Claude/config.json");
+        let (program, args) = elevation_command("linux", &write).unwrap();
+        assert_eq!(program, "pkexec");
+        assert_eq!(
+            args[4..],
+            [
+                "/etc/Claude",
+                &*write.staged.to_string_lossy(),
+                "/etc/Claude/config.json"
+            ]
+        );
+
+        let write = staged("/Library/It's \"here\"/config.json");
+        let (program, args) = elevation_command("macos", &write).unwrap();
+        assert_eq!(program, "osascript");
+        assert!(
+            args[1].starts_with("do shell script \"mkdir -p '/Library/It'\\\\''s \\\"here\\\"'")
+        );
+        assert!(args[1].ends_with("\" with administrator privileges"));
+
+        let write = staged(r"C:\Program Files\It's\config.json");
+        let (program, args) = elevation_command("windows", &write).unwrap();
+        assert_eq!(program, "powershell");
+        // Quoted once for the elevated shell and again for the outer one
+        assert!(args[2].contains(r"-LiteralPath ''/data/pending_configs/Claude-config.json''"));
+        assert!(args[2].contains(r"-Destination ''C:\Program Files\It''''s\config.json''"));
+
+        assert!(elevation_command("plan9", &write).is_none());
+    }
+}
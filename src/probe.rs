@@ -0,0 +1,122 @@
+//! Periodic liveness probes of running servers.
+//!
+//! Every running server is pinged on an interval. Each server's recent
+//! results become a traffic-light level: green while pings come back
+//! quickly, yellow when one is slow or a single ping fails, and red once
+//! several fail in a row.
+
+use std::time::Duration;
+
+/// How often running servers are pinged.
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Replies slower than this turn a server yellow.
+pub const SLOW_PROBE: Duration = Duration::from_secs(2);
+
+/// Failed pings in a row before a server turns red.
+pub const UNHEALTHY_AFTER: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProbeLevel {
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
+impl ProbeLevel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProbeLevel::Healthy => "Healthy",
+            ProbeLevel::Degraded => "Degraded",
+            ProbeLevel::Unhealthy => "Unhealthy",
+        }
+    }
+}
+
+/// What the probes have seen of one server since it started.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProbeHistory {
+    /// Round trip of the last ping that was answered.
+    pub latency: Option<Duration>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub checked_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ProbeHistory {
+    /// Record one ping's outcome. Returns true when this is the ping that
+    /// made the server unhealthy, so it is reported once per outage.
+    pub fn record(
+        &mut self,
+        result: Result<Duration, String>,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        let was_unhealthy = self.level() == ProbeLevel::Unhealthy;
+        self.checked_at = Some(at);
+        match result {
+            Ok(latency) => {
+                self.latency = Some(latency);
+                self.consecutive_failures = 0;
+                self.last_error = None;
+            }
+            Err(e) => {
+                self.consecutive_failures += 1;
+                self.last_error = Some(e);
+            }
+        }
+        !was_unhealthy && self.level() == ProbeLevel::Unhealthy
+    }
+
+    pub fn level(&self) -> ProbeLevel {
+        if self.consecutive_failures >= UNHEALTHY_AFTER {
+            ProbeLevel::Unhealthy
+        } else if self.consecutive_failures > 0 || self.latency.is_some_and(|l| l >= SLOW_PROBE) {
+            ProbeLevel::Degraded
+        } else {
+            ProbeLevel::Healthy
+        }
+    }
+
+    /// One line for a tooltip, e.g. "Healthy · 12ms".
+    pub fn summary(&self) -> String {
+        match (&self.last_error, self.latency) {
+            (Some(e), _) => format!("{} · {}", self.level().label(), e),
+            (None, Some(latency)) => {
+                format!("{} · {}ms", self.level().label(), latency.as_millis())
+            }
+            (None, None) => self.level().label().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels() {
+        let now = chrono::Utc::now();
+        let mut history = ProbeHistory::default();
+        assert_eq!(history.level(), ProbeLevel::Healthy);
+
+        assert!(!history.record(Ok(Duration::from_millis(12)), now));
+        assert_eq!(history.level(), ProbeLevel::Healthy);
+        assert_eq!(history.summary(), "Healthy · 12ms");
+
+        assert!(!history.record(Ok(Duration::from_secs(3)), now));
+        assert_eq!(history.level(), ProbeLevel::Degraded);
+
+        assert!(!history.record(Err("No reply within 10s".to_string()), now));
+        assert_eq!(history.level(), ProbeLevel::Degraded);
+        assert!(history.record(Err("No reply within 10s".to_string()), now));
+        assert_eq!(history.level(), ProbeLevel::Unhealthy);
+        assert_eq!(history.summary(), "Unhealthy · No reply within 10s");
+
+        // Only the first failure past the threshold is reported
+        assert!(!history.record(Err("No reply within 10s".to_string()), now));
+
+        assert!(!history.record(Ok(Duration::from_millis(5)), now));
+        assert_eq!(history.level(), ProbeLevel::Healthy);
+        assert_eq!(history.consecutive_failures, 0);
+    }
+}
@@ -1,14 +1,75 @@
-use futures_util::StreamExt;
+use crate::framing::frame;
+use crate::models::{InitializeResult, LoggingLevel};
+use crate::output_reader::{encoding_for, log_text, LineReader, MAX_LOG_LINE, MAX_STDOUT_LINE};
+use crate::session_recording::{Direction, Replay, SessionRecorder};
+use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
-use std::sync::Arc;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
 use tokio::process::{Child, Command};
-use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio::sync::{broadcast, mpsc, oneshot, watch, Mutex, Notify};
+use tokio_tungstenite::tungstenite::Message;
 
-type PendingRequests = Arc<Mutex<HashMap<u64, oneshot::Sender<Result<Value, String>>>>>;
+/// Requests awaiting a reply, each given the reply message once it comes.
+type PendingRequests = Mutex<HashMap<u64, oneshot::Sender<Result<Value, String>>>>;
+
+/// Shared with the reader and writer tasks, which record while it is set.
+type SharedRecorder = Arc<std::sync::Mutex<Option<SessionRecorder>>>;
+
+/// The server's stdout, a pipe or a terminal.
+type ServerOutput = Box<dyn AsyncRead + Send + Unpin>;
+
+/// The server's stdin, a pipe or a terminal.
+type ServerInput = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// Error a cancelled request's caller gets.
+pub const CANCELLED: &str = "Request cancelled";
+
+/// How long a request waits for its reply when neither the server nor
+/// the request sets a timeout. Generous, as tool calls can be slow.
+pub const DEFAULT_REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Error a request's caller gets when the server doesn't reply in time.
+fn timed_out(method: &str, timeout: std::time::Duration) -> String {
+    format!("No reply to {} within {}s", method, timeout.as_secs_f32())
+}
+
+/// Request ids of tool calls awaiting a reply, oldest first.
+type InFlightCalls = Arc<std::sync::Mutex<Vec<u64>>>;
+
+/// Keeps a tool call in the in-flight list until it completes or is
+/// abandoned, e.g. by a timeout.
+struct InFlightGuard {
+    calls: InFlightCalls,
+    id: u64,
+}
+
+impl InFlightGuard {
+    fn new(calls: &InFlightCalls, id: u64) -> Self {
+        calls.lock().unwrap().push(id);
+        Self {
+            calls: calls.clone(),
+            id,
+        }
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.calls.lock().unwrap().retain(|&id| id != self.id);
+    }
+}
+
+fn record(recorder: &SharedRecorder, dir: Direction, data: &[u8]) {
+    if let Some(recorder) = recorder.lock().unwrap().as_mut() {
+        recorder.record(dir, data);
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug)]
 struct JsonRpcRequest {
@@ -18,40 +79,1031 @@ struct JsonRpcRequest {
     id: u64,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-struct JsonRpcResponse {
-    jsonrpc: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    result: Option<Value>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    error: Option<Value>,
-    id: Option<u64>,
+/// A request that gets no reply.
+#[derive(Serialize, Debug)]
+struct JsonRpcNotification {
+    jsonrpc: String,
+    method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params: Option<Value>,
+}
+
+/// A notification sent by the server, e.g. `notifications/resources/updated`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ServerNotification {
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// A list a server can announce changes to with `notifications/*/list_changed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ListKind {
+    Tools,
+    Resources,
+    Prompts,
+}
+
+impl ListKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ListKind::Tools => "tools",
+            ListKind::Resources => "resources",
+            ListKind::Prompts => "prompts",
+        }
+    }
+}
+
+impl ServerNotification {
+    /// The list this notification says has changed, if any.
+    pub fn list_changed(&self) -> Option<ListKind> {
+        match self.method.as_str() {
+            "notifications/tools/list_changed" => Some(ListKind::Tools),
+            "notifications/resources/list_changed" => Some(ListKind::Resources),
+            "notifications/prompts/list_changed" => Some(ListKind::Prompts),
+            _ => None,
+        }
+    }
+}
+
+/// A request the server sends to the manager, e.g. `sampling/createMessage`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ServerRequest {
+    /// The server's own id for the request, a number or a string.
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// The error half of a JSON-RPC reply.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub const USER_REJECTED: i64 = -1;
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const INVALID_PARAMS: i64 = -32602;
+    pub const INTERNAL_ERROR: i64 = -32603;
+
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Whether `error`, as a request fails with a JSON-RPC error, says the
+/// server doesn't implement the method.
+fn is_method_not_found(error: &str) -> bool {
+    serde_json::from_str::<Value>(error)
+        .ok()
+        .and_then(|e| e["code"].as_i64())
+        == Some(RpcError::METHOD_NOT_FOUND)
+}
+
+/// Notifications kept for subscribers that fall behind.
+const NOTIFICATION_BUFFER: usize = 64;
+
+/// A request sent with [`McpHandler::send_raw_request`] and its reply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawExchange {
+    /// The request message as it was sent.
+    pub request: Value,
+    /// The reply message, with the result or error the server sent; Err
+    /// when none came back, e.g. on a timeout.
+    pub response: Result<Value, String>,
+    pub elapsed: std::time::Duration,
+}
+
+/// The result a reply message carries, or the error the server sent as
+/// its JSON.
+fn reply_result(message: Value) -> Result<Value, String> {
+    match message.get("error") {
+        Some(error) if !error.is_null() => Err(error.to_string()),
+        _ => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+    }
+}
+
+/// `text` as a server notification: a message with a method but no id.
+fn parse_notification(text: &[u8]) -> Option<ServerNotification> {
+    let value: Value = serde_json::from_slice(text).ok()?;
+    if value.get("id").is_some() {
+        return None;
+    }
+    serde_json::from_value(value).ok()
+}
+
+/// `text` as a request from the server: a message with a method and an id.
+fn parse_server_request(text: &[u8]) -> Option<ServerRequest> {
+    let value: Value = serde_json::from_slice(text).ok()?;
+    value.get("method")?;
+    serde_json::from_value(value).ok()
+}
+
+/// The reply to server request `id`.
+fn reply_message(id: &Value, result: Result<Value, RpcError>) -> String {
+    let reply = match result {
+        Ok(result) => serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => serde_json::json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    };
+    reply.to_string()
+}
+
+/// `path` as a `file://` URI, with Windows drive paths as `file:///C:/...`.
+pub fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let encoded = path
+        .split('/')
+        .enumerate()
+        .map(|(i, part)| match i {
+            0 if part.ends_with(':') => part.to_string(),
+            _ => urlencoding::encode(part).into_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    if encoded.starts_with('/') {
+        format!("file://{}", encoded)
+    } else {
+        format!("file:///{}", encoded)
+    }
+}
+
+/// The `roots/list` result offering `dirs` to a server.
+pub fn roots_result(dirs: &[PathBuf]) -> Value {
+    let roots: Vec<Value> = dirs
+        .iter()
+        .map(|dir| {
+            let name = dir
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| dir.display().to_string());
+            serde_json::json!({ "uri": file_uri(dir), "name": name })
+        })
+        .collect();
+    serde_json::json!({ "roots": roots })
+}
+
+/// `line` from where a JSON-RPC message starts, skipping terminal text
+/// printed before it on the same line.
+fn after_prompt(line: &[u8]) -> &[u8] {
+    match line.windows(2).position(|w| w == b"{\"") {
+        Some(start) => &line[start..],
+        None => line,
+    }
+}
+
+/// `n` as a console log line, if it is a `notifications/message`.
+fn log_message(n: &ServerNotification) -> Option<ProcessLog> {
+    if n.method != "notifications/message" {
+        return None;
+    }
+    let level = serde_json::from_value(n.params.get("level")?.clone()).ok()?;
+    let text = match n.params.get("data") {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(data) => data.to_string(),
+    };
+    Some(ProcessLog::Message {
+        level,
+        logger: n.params["logger"].as_str().map(str::to_string),
+        text,
+    })
+}
+
+fn notification(method: &str, params: Option<Value>) -> JsonRpcNotification {
+    JsonRpcNotification {
+        jsonrpc: "2.0".to_string(),
+        method: method.to_string(),
+        params,
+    }
+}
+
+/// Protocol revision the manager asks for in `initialize`.
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Revisions the manager speaks, newest first. A server may answer
+/// `initialize` with any of them.
+pub const SUPPORTED_PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// How long an SSE server gets to announce its message endpoint.
+const ENDPOINT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// `initialize` params. Only transports whose readers answer server
+/// requests (`answers_requests`) offer sampling, roots and elicitation;
+/// a server that trusted them elsewhere would wait forever for a reply.
+fn initialize_params(answers_requests: bool) -> Value {
+    let capabilities = if answers_requests {
+        serde_json::json!({
+            "sampling": {},
+            "roots": { "listChanged": true },
+            "elicitation": {}
+        })
+    } else {
+        serde_json::json!({})
+    };
+    serde_json::json!({
+        "protocolVersion": PROTOCOL_VERSION,
+        "capabilities": capabilities,
+        "clientInfo": {
+            "name": "open-mcp-manager",
+            "version": env!("CARGO_PKG_VERSION")
+        }
+    })
+}
+
+/// Check a server's `initialize` reply, rejecting protocol versions the
+/// manager doesn't speak.
+fn negotiate(result: Value) -> Result<InitializeResult, String> {
+    let result: InitializeResult = serde_json::from_value(result)
+        .map_err(|e| format!("Invalid initialize response: {}", e))?;
+    if !SUPPORTED_PROTOCOL_VERSIONS.contains(&result.protocolVersion.as_str()) {
+        return Err(format!(
+            "Server uses unsupported protocol version {}",
+            result.protocolVersion
+        ));
+    }
+    Ok(result)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct JsonRpcResponse {
+    jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<Value>,
+    id: Option<u64>,
+}
+
+#[derive(Clone, Debug)]
+pub enum ProcessLog {
+    Stdout(String),
+    Stderr(String),
+    /// A `notifications/message` log message from the server.
+    Message {
+        level: LoggingLevel,
+        logger: Option<String>,
+        text: String,
+    },
+    /// The process has ended; says how, e.g. "exited with code 1".
+    Exit(String),
+}
+
+impl ProcessLog {
+    /// Pair the line with the time it was read, so the console shows when
+    /// the server printed it rather than when it was rendered.
+    pub fn stamped(self) -> LogEntry {
+        LogEntry {
+            at: chrono::Utc::now(),
+            log: self,
+            request_id: None,
+        }
+    }
+}
+
+/// A log line and when it was received from the server.
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub at: chrono::DateTime<chrono::Utc>,
+    pub log: ProcessLog,
+    /// Tool call in flight when the line arrived. Best effort: with
+    /// several calls in flight the latest one is assumed.
+    pub request_id: Option<u64>,
+}
+
+impl LogEntry {
+    pub fn during(mut self, request_id: Option<u64>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+}
+
+/// How a stopped server went away.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopOutcome {
+    /// Exited on its own once stdin was closed.
+    Exited,
+    /// Exited after being sent SIGTERM. Unix only.
+    Terminated,
+    /// Still running after the grace period, so it was killed.
+    Killed,
+    /// Remote server; there is no process to wait for.
+    Disconnected,
+}
+
+/// How often a stdio server's process is checked for having exited.
+const EXIT_POLL: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// How long output still being read is waited for once the process has
+/// exited. Bounded, since something the server started may hold it open.
+const OUTPUT_DRAIN: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// How a server's process ended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessExit {
+    /// None when it was ended by a signal.
+    pub code: Option<i32>,
+    /// e.g. "exited with code 1" or "was ended by signal 9"
+    pub description: String,
+    /// Whether the manager stopped it, rather than it exiting by itself.
+    pub requested: bool,
+    /// The last line it printed to stderr, often why it failed.
+    pub last_stderr: Option<String>,
+}
+
+fn describe_exit(status: std::process::ExitStatus) -> String {
+    if let Some(code) = status.code() {
+        return format!("exited with code {}", code);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return format!("was ended by signal {}", signal);
+        }
+    }
+    "exited".to_string()
+}
+
+/// Wait for `child` to exit, then log how once its last output is in and
+/// publish it on `exit_tx`. Polls rather than waiting, so the child stays
+/// free for a stop to wait on; a child reaped there is seen on the next
+/// poll. Ends early if the process handle is dropped.
+async fn watch_exit(
+    child: Arc<Mutex<Child>>,
+    stdout: tokio::task::JoinHandle<()>,
+    stderr: tokio::task::JoinHandle<Option<String>>,
+    stopping: Arc<AtomicBool>,
+    log_tx: mpsc::Sender<LogEntry>,
+    exit_tx: watch::Sender<Option<ProcessExit>>,
+) {
+    let status = loop {
+        tokio::time::sleep(EXIT_POLL).await;
+        if exit_tx.is_closed() {
+            return;
+        }
+        if let Ok(mut child) = child.try_lock() {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!("Couldn't check whether the server exited: {}", e);
+                    return;
+                }
+            }
+        }
+    };
+    let drained =
+        tokio::time::timeout(OUTPUT_DRAIN, futures_util::future::join(stdout, stderr)).await;
+    let exit = ProcessExit {
+        code: status.code(),
+        description: describe_exit(status),
+        requested: stopping.load(Ordering::Relaxed),
+        last_stderr: drained.ok().and_then(|(_, last)| last.ok().flatten()),
+    };
+    let _ = log_tx
+        .send(ProcessLog::Exit(exit.description.clone()).stamped())
+        .await;
+    let _ = exit_tx.send(Some(exit));
+}
+
+pub struct McpProcess {
+    pub child: Arc<Mutex<Child>>,
+    /// The child's process id, which also names its process group on Unix.
+    pid: Option<u32>,
+    /// Set once the manager asks the process to stop.
+    stopping: Arc<AtomicBool>,
+    /// How the process ended, once it has.
+    exit: watch::Receiver<Option<ProcessExit>>,
+    /// The Docker container the server runs in, for servers with an image.
+    container: Option<String>,
+    pub stdin_tx: mpsc::Sender<String>,
+    /// Closes the server's stdin, its cue to shut down.
+    pub stdin_close: Arc<Notify>,
+    /// Messages to the server use `Content-Length` framing instead of
+    /// newlines; set once the server is seen using it.
+    pub content_length_framing: Arc<AtomicBool>,
+    pub recorder: SharedRecorder,
+    rpc: Arc<RpcSession>,
+    notifications: broadcast::Sender<ServerNotification>,
+    /// Sampling requests from the server, for the manager to answer.
+    server_requests: broadcast::Sender<ServerRequest>,
+    /// The terminal the server runs in, in PTY mode.
+    _pty: Option<crate::pty::Pty>,
+    terminal: Option<crate::pty::Terminal>,
+}
+
+pub struct McpSseClient {
+    pub url: String,
+    pub request_url: Arc<Mutex<Option<String>>>,
+    pub client: reqwest::Client,
+    /// Sent as `Authorization: Bearer` on the stream and each request.
+    bearer_token: Option<String>,
+    rpc: Arc<RpcSession>,
+    notifications: broadcast::Sender<ServerNotification>,
+}
+
+/// Client for servers reachable over a WebSocket, one JSON-RPC message
+/// per text frame. A dropped connection fails the requests in flight and
+/// is re-established with backoff, redoing the handshake.
+pub struct McpWsClient {
+    pub url: String,
+    /// Text frames for the connection task to send.
+    outgoing: mpsc::Sender<String>,
+    /// Whether a connection is up; requests fail fast while reconnecting.
+    connected: watch::Receiver<bool>,
+    /// Ends the connection task.
+    close: Arc<Notify>,
+    rpc: Arc<RpcSession>,
+    notifications: broadcast::Sender<ServerNotification>,
+}
+
+/// Answers requests from a recorded session instead of a server.
+pub struct McpReplay {
+    pub path: PathBuf,
+    replay: Mutex<Replay>,
+    server_info: OnceLock<InitializeResult>,
+    /// Never sent on; a recording can't push anything new.
+    notifications: broadcast::Sender<ServerNotification>,
+}
+
+pub enum McpHandler {
+    Stdio(McpProcess),
+    Sse(McpSseClient),
+    Ws(McpWsClient),
+    Replay(Box<McpReplay>),
+}
+
+/// What a JSON-RPC client keeps, whatever carries its messages: the
+/// replies it awaits, its next request id and how long to wait.
+struct RpcSession {
+    pending_requests: PendingRequests,
+    next_request_id: Mutex<u64>,
+    /// How long requests wait for a reply.
+    request_timeout: std::sync::Mutex<std::time::Duration>,
+    in_flight_calls: InFlightCalls,
+    server_info: OnceLock<InitializeResult>,
+}
+
+impl RpcSession {
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            pending_requests: Mutex::default(),
+            next_request_id: Mutex::new(1),
+            request_timeout: std::sync::Mutex::new(DEFAULT_REQUEST_TIMEOUT),
+            in_flight_calls: InFlightCalls::default(),
+            server_info: OnceLock::new(),
+        })
+    }
+
+    async fn allocate_request_id(&self) -> u64 {
+        let mut id_lock = self.next_request_id.lock().await;
+        let id = *id_lock;
+        *id_lock += 1;
+        id
+    }
+
+    /// Hand a response to the request waiting for it. False if `message`
+    /// isn't a response anyone is waiting for.
+    async fn resolve(&self, message: &[u8]) -> bool {
+        let Ok(reply) = serde_json::from_slice::<Value>(message) else {
+            return false;
+        };
+        let Some(id) = JsonRpcResponse::deserialize(&reply).ok().and_then(|r| r.id) else {
+            return false;
+        };
+        let Some(tx) = self.pending_requests.lock().await.remove(&id) else {
+            return false;
+        };
+        let _ = tx.send(Ok(reply));
+        true
+    }
+}
+
+/// A client speaking JSON-RPC to a server. Each transport says how a
+/// message is sent, and hands the replies it reads to
+/// [`RpcSession::resolve`]; ids, timeouts and cancelling are shared.
+trait RpcClient {
+    /// Whether the client answers requests from the server, declared as
+    /// capabilities in `initialize`.
+    const ANSWERS_REQUESTS: bool;
+
+    fn rpc(&self) -> &RpcSession;
+
+    /// Send one message to the server.
+    async fn send_message(&self, message: String) -> Result<(), String>;
+
+    /// Wait until the server can be sent the handshake.
+    async fn ready(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// The MCP handshake: negotiate the protocol version, keep the
+    /// server's capabilities and tell it the session is ready. Must
+    /// complete before any other request.
+    async fn initialize(&self) -> Result<InitializeResult, String> {
+        self.ready().await?;
+        let params = initialize_params(Self::ANSWERS_REQUESTS);
+        let reply = self
+            .send_request_with_timeout("initialize", Some(params), self.request_timeout())
+            .await?;
+        let result = negotiate(reply)?;
+        self.send_notification("notifications/initialized", None)
+            .await?;
+        let _ = self.rpc().server_info.set(result.clone());
+        Ok(result)
+    }
+
+    async fn send_notification(&self, method: &str, params: Option<Value>) -> Result<(), String> {
+        let json =
+            serde_json::to_string(&notification(method, params)).map_err(|e| e.to_string())?;
+        self.send_message(json).await
+    }
+
+    /// How long requests wait for a reply.
+    fn request_timeout(&self) -> std::time::Duration {
+        *self.rpc().request_timeout.lock().unwrap()
+    }
+
+    fn set_request_timeout(&self, timeout: std::time::Duration) {
+        *self.rpc().request_timeout.lock().unwrap() = timeout;
+    }
+
+    /// Send a request that waits `timeout` for its reply instead of the
+    /// client's usual timeout.
+    async fn send_request_with_timeout(
+        &self,
+        method: &str,
+        params: Option<Value>,
+        timeout: std::time::Duration,
+    ) -> Result<Value, String> {
+        let id = self.rpc().allocate_request_id().await;
+        self.send_request_with_id(id, method, params, timeout).await
+    }
+
+    /// Send a request and return the messages exchanged.
+    async fn send_raw_request(&self, method: &str, params: Value) -> RawExchange {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params,
+            id: self.rpc().allocate_request_id().await,
+        };
+        let started = std::time::Instant::now();
+        let response = self.exchange(&request, self.request_timeout()).await;
+        RawExchange {
+            request: serde_json::to_value(&request).unwrap_or_default(),
+            response,
+            elapsed: started.elapsed(),
+        }
+    }
+
+    async fn send_request_with_id(
+        &self,
+        id: u64,
+        method: &str,
+        params: Option<Value>,
+        timeout: std::time::Duration,
+    ) -> Result<Value, String> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            method: method.to_string(),
+            params: params.unwrap_or(serde_json::json!({})),
+            id,
+        };
+        reply_result(self.exchange(&request, timeout).await?)
+    }
+
+    /// Send `request` and wait `timeout` for the message the server
+    /// replies with.
+    async fn exchange(
+        &self,
+        request: &JsonRpcRequest,
+        timeout: std::time::Duration,
+    ) -> Result<Value, String> {
+        let json_str = serde_json::to_string(request).map_err(|e| e.to_string())?;
+
+        let (tx, rx) = oneshot::channel();
+        self.rpc()
+            .pending_requests
+            .lock()
+            .await
+            .insert(request.id, tx);
+
+        if let Err(e) = self.send_message(json_str).await {
+            self.rpc().pending_requests.lock().await.remove(&request.id);
+            return Err(e);
+        }
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(reply)) => reply,
+            Ok(Err(_)) => Err("Request cancelled or connection lost".to_string()),
+            Err(_) => {
+                // Drops the pending entry and tells the server to stop
+                let _ = self.cancel_request(request.id, "Timed out").await;
+                Err(timed_out(&request.method, timeout))
+            }
+        }
+    }
+
+    /// Give up on request `id`: its caller gets an error now, and the
+    /// server is told it can stop working on it.
+    async fn cancel_request(&self, id: u64, reason: &str) -> Result<(), String> {
+        let Some(tx) = self.rpc().pending_requests.lock().await.remove(&id) else {
+            return Err(format!("Request {} has already finished", id));
+        };
+        let _ = tx.send(Err(CANCELLED.to_string()));
+        let params = serde_json::json!({ "requestId": id, "reason": reason });
+        self.send_notification("notifications/cancelled", Some(params))
+            .await
+    }
+
+    /// Ids of tool calls awaiting a reply, oldest first.
+    fn in_flight_calls(&self) -> Vec<u64> {
+        self.rpc().in_flight_calls.lock().unwrap().clone()
+    }
+}
+
+/// The requests a server answers, typed. Implementors say how a request
+/// is sent and a tool called.
+trait McpRequests {
+    async fn send_request(&self, method: &str, params: Option<Value>) -> Result<Value, String>;
+
+    async fn call_tool(
+        &self,
+        name: String,
+        arguments: Value,
+    ) -> Result<crate::models::CallToolResult, String>;
+
+    async fn list_tools(&self) -> Result<Vec<crate::models::Tool>, String> {
+        let val = self.send_request("tools/list", None).await?;
+        let res: crate::models::ListToolsResult =
+            serde_json::from_value(val).map_err(|e| e.to_string())?;
+        Ok(res.tools)
+    }
+
+    async fn list_resources(&self) -> Result<Vec<crate::models::Resource>, String> {
+        let val = self.send_request("resources/list", None).await?;
+        let res: crate::models::ListResourcesResult =
+            serde_json::from_value(val).map_err(|e| e.to_string())?;
+        Ok(res.resources)
+    }
+
+    async fn list_resource_templates(
+        &self,
+    ) -> Result<Vec<crate::models::ResourceTemplate>, String> {
+        let val = self.send_request("resources/templates/list", None).await?;
+        let res: crate::models::ListResourceTemplatesResult =
+            serde_json::from_value(val).map_err(|e| e.to_string())?;
+        Ok(res.resourceTemplates)
+    }
+
+    async fn list_prompts(&self) -> Result<Vec<crate::models::Prompt>, String> {
+        let val = self.send_request("prompts/list", None).await?;
+        let res: crate::models::ListPromptsResult =
+            serde_json::from_value(val).map_err(|e| e.to_string())?;
+        Ok(res.prompts)
+    }
+
+    async fn read_resource(
+        &self,
+        uri: String,
+    ) -> Result<crate::models::ReadResourceResult, String> {
+        let params = serde_json::json!({
+            "uri": uri
+        });
+        let val = self.send_request("resources/read", Some(params)).await?;
+        serde_json::from_value(val).map_err(|e| e.to_string())
+    }
+
+    async fn get_prompt(
+        &self,
+        name: String,
+        arguments: HashMap<String, String>,
+    ) -> Result<crate::models::GetPromptResult, String> {
+        let params = serde_json::json!({
+            "name": name,
+            "arguments": arguments
+        });
+        let val = self.send_request("prompts/get", Some(params)).await?;
+        serde_json::from_value(val).map_err(|e| e.to_string())
+    }
+
+    /// Ask for `notifications/resources/updated` when `uri` changes.
+    async fn subscribe_resource(&self, uri: &str) -> Result<(), String> {
+        let params = serde_json::json!({ "uri": uri });
+        self.send_request("resources/subscribe", Some(params))
+            .await
+            .map(|_| ())
+    }
+
+    async fn unsubscribe_resource(&self, uri: &str) -> Result<(), String> {
+        let params = serde_json::json!({ "uri": uri });
+        self.send_request("resources/unsubscribe", Some(params))
+            .await
+            .map(|_| ())
+    }
+
+    /// Ask the server to send log messages of `level` and above.
+    async fn set_log_level(&self, level: LoggingLevel) -> Result<(), String> {
+        let params = serde_json::json!({ "level": level });
+        self.send_request("logging/setLevel", Some(params))
+            .await
+            .map(|_| ())
+    }
+}
+
+impl<T: RpcClient> McpRequests for T {
+    async fn send_request(&self, method: &str, params: Option<Value>) -> Result<Value, String> {
+        self.send_request_with_timeout(method, params, self.request_timeout())
+            .await
+    }
+
+    async fn call_tool(
+        &self,
+        name: String,
+        arguments: Value,
+    ) -> Result<crate::models::CallToolResult, String> {
+        let params = serde_json::json!({
+            "name": name,
+            "arguments": arguments
+        });
+        let id = self.rpc().allocate_request_id().await;
+        // Stderr printed until the reply is tagged with this call
+        let _in_flight = InFlightGuard::new(&self.rpc().in_flight_calls, id);
+        let val = self
+            .send_request_with_id(id, "tools/call", Some(params), self.request_timeout())
+            .await?;
+        let mut res: crate::models::CallToolResult =
+            serde_json::from_value(val).map_err(|e| e.to_string())?;
+        res.request_id = Some(id);
+        Ok(res)
+    }
+}
+
+/// Ask `child` and the processes it started to exit by sending SIGTERM
+/// to its process group. Windows has no counterpart that reaches a child
+/// without a console, so there servers go straight from a closed stdin
+/// to being killed.
+#[cfg(unix)]
+fn terminate(child: &Child) -> Result<(), String> {
+    let pid = child.id().ok_or("The process has already exited")?;
+    // SAFETY: killpg(2) only sends a signal; the child leads its own group
+    if unsafe { libc::killpg(pid as libc::pid_t, libc::SIGTERM) } == -1 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    Ok(())
+}
+
+/// Kill `child` and every process it started: its process group on Unix,
+/// its process tree on Windows. Descendants that left the group, e.g. by
+/// daemonizing, are out of reach.
+async fn kill_tree(child: &mut Child) -> Result<(), String> {
+    if let Some(pid) = child.id() {
+        kill_group(pid);
+        #[cfg(windows)]
+        {
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            let _ = Command::new("taskkill")
+                .args(["/T", "/F", "/PID", &pid.to_string()])
+                .creation_flags(CREATE_NO_WINDOW)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .await;
+        }
+    }
+    // Also reaps the child
+    child.kill().await.map_err(|e| e.to_string())
+}
+
+/// Kill whatever is left in the process group led by `pid`, e.g. what a
+/// server started before it exited. Windows has no lasting groups; there
+/// this does nothing.
+fn kill_group(pid: u32) {
+    #[cfg(unix)]
+    {
+        // SAFETY: killpg(2) only sends a signal; the child led this group
+        unsafe { libc::killpg(pid as libc::pid_t, libc::SIGKILL) };
+    }
+    #[cfg(not(unix))]
+    let _ = pid;
+}
+
+/// How long the user's login shell gets to report its PATH.
+const LOGIN_SHELL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// A program found by [`resolve_command`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedCommand {
+    pub program: PathBuf,
+    /// PATH to start it with when it was found outside the PATH it would
+    /// have had: that PATH with the program's directory first, so a
+    /// script like `npx` finds the `node` installed beside it.
+    pub path: Option<std::ffi::OsString>,
+}
+
+/// Find a bare command name before spawning it: on `path_var` first,
+/// trying `PATHEXT` extensions on Windows so `npx` finds `npx.cmd`, then
+/// in `fallback_dirs`. Names with a directory part are left to the OS,
+/// as they may be relative to the server's working directory.
+pub fn resolve_command(
+    program: &str,
+    path_var: Option<&str>,
+    fallback_dirs: &[PathBuf],
+) -> Option<ResolvedCommand> {
+    if Path::new(program).components().count() != 1 {
+        return None;
+    }
+    if let Some(found) = crate::launch::resolve_program(program, path_var) {
+        return Some(ResolvedCommand {
+            program: found,
+            path: None,
+        });
+    }
+    fallback_dirs.iter().find_map(|dir| {
+        let found = crate::launch::resolve_program(&dir.join(program).to_string_lossy(), None)?;
+        let rest = path_var.map(std::env::split_paths).into_iter().flatten();
+        let path = std::env::join_paths(std::iter::once(dir.clone()).chain(rest)).ok();
+        Some(ResolvedCommand {
+            program: found,
+            path,
+        })
+    })
+}
+
+static FALLBACK_DIRS: tokio::sync::OnceCell<Vec<PathBuf>> = tokio::sync::OnceCell::const_new();
+
+/// [`fallback_dirs`] if they have been worked out yet, for callers that
+/// can't wait on the login shell.
+pub fn known_fallback_dirs() -> &'static [PathBuf] {
+    FALLBACK_DIRS.get().map(Vec::as_slice).unwrap_or_default()
 }
 
-#[derive(Clone, Debug)]
-pub enum ProcessLog {
-    Stdout(String),
-    Stderr(String),
+/// Where commands missing from PATH are looked for: the PATH of the
+/// user's login shell, which apps started from the macOS Dock or a Linux
+/// desktop don't inherit, then the usual install locations. Worked out
+/// once per run.
+pub async fn fallback_dirs() -> &'static [PathBuf] {
+    FALLBACK_DIRS
+        .get_or_init(|| async {
+            let mut dirs: Vec<PathBuf> = match login_shell_path().await {
+                Some(path) => std::env::split_paths(&path).collect(),
+                None => Vec::new(),
+            };
+            for dir in install_dirs(dirs::home_dir().as_deref(), |key| std::env::var(key).ok()) {
+                if !dirs.contains(&dir) {
+                    dirs.push(dir);
+                }
+            }
+            dirs
+        })
+        .await
 }
 
-pub struct McpProcess {
-    pub child: Arc<Mutex<Child>>,
-    pub stdin_tx: mpsc::Sender<String>,
-    pub pending_requests: PendingRequests,
-    pub next_request_id: Arc<Mutex<u64>>,
+/// The program to spawn for `command`, resolved against the PATH it will
+/// run with: `env`'s if it sets one, else the manager's. Commands that
+/// can't be found are returned as they are, for the OS to report.
+pub async fn resolve_for_spawn(
+    command: &str,
+    env: Option<&HashMap<String, String>>,
+) -> ResolvedCommand {
+    let path_var = env
+        .and_then(|env| env.get("PATH").cloned())
+        .or_else(|| std::env::var("PATH").ok());
+    resolve_command(command, path_var.as_deref(), fallback_dirs().await).unwrap_or_else(|| {
+        ResolvedCommand {
+            program: PathBuf::from(command),
+            path: None,
+        }
+    })
 }
 
-pub struct McpSseClient {
-    pub url: String,
-    pub request_url: Arc<Mutex<Option<String>>>,
-    pub client: reqwest::Client,
-    pub pending_requests: PendingRequests,
-    pub next_request_id: Arc<Mutex<u64>>,
+/// Command for a stdio server, set up the same way wherever it is started
+/// from: program resolved against its PATH, working directory checked and
+/// environment built from `env`, on top of a minimal one with `clean_env`.
+pub async fn spawn_command(
+    command: &str,
+    args: Vec<String>,
+    env: Option<&HashMap<String, String>>,
+    cwd: Option<&Path>,
+    clean_env: bool,
+) -> Result<Command, String> {
+    let resolved = resolve_for_spawn(command, env).await;
+    let mut cmd = Command::new(&resolved.program);
+    cmd.args(args);
+    if let Some(dir) = cwd {
+        // Otherwise it fails like a missing command
+        if !dir.is_dir() {
+            return Err(format!("Working directory {} doesn't exist", dir.display()));
+        }
+        cmd.current_dir(dir);
+    }
+
+    if clean_env {
+        cmd.env_clear();
+        cmd.envs(crate::launch::base_env(true, std::env::vars()));
+    }
+    if let Some(env_vars) = env {
+        cmd.envs(env_vars);
+    }
+    if let Some(path) = resolved.path {
+        cmd.env("PATH", path);
+    }
+    Ok(cmd)
 }
 
-pub enum McpHandler {
-    Stdio(McpProcess),
-    Sse(McpSseClient),
+/// PATH as the user's login shell sets it up.
+#[cfg(unix)]
+async fn login_shell_path() -> Option<String> {
+    let shell = std::env::var("SHELL").ok()?;
+    let output = Command::new(shell)
+        .args(["-l", "-c", "printf '%s' \"$PATH\""])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(LOGIN_SHELL_TIMEOUT, output)
+        .await
+        .ok()?
+        .ok()?;
+    // Profile scripts may print their own lines first
+    let text = String::from_utf8_lossy(&output.stdout);
+    let path = text.lines().last()?.trim();
+    (output.status.success() && !path.is_empty()).then(|| path.to_string())
+}
+
+#[cfg(not(unix))]
+async fn login_shell_path() -> Option<String> {
+    None
+}
+
+/// Where Homebrew, Node version managers (nvm, Volta), npm and Python
+/// tools like uv install commands, for a user whose home is `home`. `var`
+/// reads environment variables. Only directories that exist are listed.
+fn install_dirs(home: Option<&Path>, var: impl Fn(&str) -> Option<String>) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(home) = home {
+        dirs.push(home.join(".volta").join("bin"));
+        dirs.extend(nvm_bin_dirs(
+            &home.join(".nvm").join("versions").join("node"),
+        ));
+        dirs.push(home.join(".local").join("bin"));
+        dirs.push(home.join(".cargo").join("bin"));
+    }
+    if cfg!(windows) {
+        let under = |key: &str, rest: &[&str]| {
+            var(key).map(|base| {
+                rest.iter()
+                    .fold(PathBuf::from(base), |p, part| p.join(part))
+            })
+        };
+        dirs.extend(under("APPDATA", &["npm"]));
+        dirs.extend(under("LOCALAPPDATA", &["Volta", "bin"]));
+        // Where nvm-windows links the active version
+        dirs.extend(under("NVM_SYMLINK", &[]));
+        dirs.extend(under("ProgramFiles", &["nodejs"]));
+    } else {
+        dirs.extend(
+            [
+                "/opt/homebrew/bin",
+                "/usr/local/bin",
+                "/home/linuxbrew/.linuxbrew/bin",
+            ]
+            .map(PathBuf::from),
+        );
+    }
+    dirs.retain(|dir| dir.is_dir());
+    dirs
+}
+
+/// The `bin` directory of each Node version nvm installed in `versions`,
+/// newest first.
+fn nvm_bin_dirs(versions: &Path) -> Vec<PathBuf> {
+    let version = |name: &str| -> Vec<u64> {
+        name.trim_start_matches('v')
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    let mut installed: Vec<(Vec<u64>, PathBuf)> = std::fs::read_dir(versions)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| {
+            (
+                version(&entry.file_name().to_string_lossy()),
+                entry.path().join("bin"),
+            )
+        })
+        .collect();
+    installed.sort_by(|a, b| b.0.cmp(&a.0));
+    installed.into_iter().map(|(_, dir)| dir).collect()
 }
 
 impl McpProcess {
@@ -60,18 +1112,30 @@ impl McpProcess {
         command: String,
         args: Vec<String>,
         env: Option<std::collections::HashMap<String, String>>,
-        log_tx: mpsc::Sender<ProcessLog>, // Channel to send logs back to UI
+        cwd: Option<&Path>,
+        options: &crate::models::LaunchOptions,
+        log_tx: mpsc::Sender<LogEntry>, // Channel to send logs back to UI
     ) -> Result<Self, String> {
-        let mut cmd = Command::new(command);
-        cmd.args(args);
+        let mut cmd = spawn_command(&command, args, env.as_ref(), cwd, options.clean_env).await?;
 
-        if let Some(env_vars) = env {
-            cmd.envs(env_vars);
+        let mut pty = if options.pty {
+            Some(crate::pty::Pty::open()?)
+        } else {
+            None
+        };
+        match pty.as_mut() {
+            // The terminal's session is already a process group of its own
+            Some(pty) => pty.attach(&mut cmd)?,
+            None => {
+                cmd.stdout(Stdio::piped());
+                cmd.stdin(Stdio::piped());
+                // Its own group, so whatever it starts (e.g. node under npx)
+                // is stopped with it
+                #[cfg(unix)]
+                cmd.process_group(0);
+            }
         }
-
-        cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
-        cmd.stdin(Stdio::piped());
 
         #[cfg(windows)]
         {
@@ -80,15 +1144,63 @@ impl McpProcess {
         }
 
         let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+        // Closes our copy of the terminal's server side
+        drop(cmd);
 
-        let stdout = child.stdout.take().unwrap();
         let stderr = child.stderr.take().unwrap();
-        let mut stdin = child.stdin.take().unwrap();
+        let mut keys = None;
+        let mut terminal = None;
+        let (stdout, mut stdin): (ServerOutput, ServerInput) = match &pty {
+            Some(pty) => {
+                let (reader, writer) = pty.io()?;
+                let (term, keys_rx) = crate::pty::Terminal::new();
+                let reader = term.tee(reader);
+                keys = Some(keys_rx);
+                terminal = Some(term);
+                (Box::new(reader), Box::new(writer))
+            }
+            None => (
+                Box::new(child.stdout.take().unwrap()),
+                Box::new(child.stdin.take().unwrap()),
+            ),
+        };
 
         // Stdin writer
         let (stdin_tx, mut stdin_rx) = mpsc::channel::<String>(32);
+        let stdin_close = Arc::new(Notify::new());
+        let close = stdin_close.clone();
+        let content_length_framing = Arc::new(AtomicBool::new(options.content_length_framing));
+        let framed = content_length_framing.clone();
+        let recorder: SharedRecorder = Arc::new(std::sync::Mutex::new(None));
+        let recorder_stdin = recorder.clone();
         tokio::spawn(async move {
-            while let Some(msg) = stdin_rx.recv().await {
+            loop {
+                let msg = tokio::select! {
+                    // Keys go first, so an answer typed at a prompt comes
+                    // before requests queued after it
+                    biased;
+                    // Typed into the Terminal tab, passed on unframed
+                    Some(typed) = async {
+                        match keys.as_mut() {
+                            Some(keys) => keys.recv().await,
+                            None => std::future::pending().await,
+                        }
+                    } => {
+                        if let Err(e) = stdin.write_all(&typed).await {
+                            eprintln!("Failed to write to stdin: {}", e);
+                            break;
+                        }
+                        let _ = stdin.flush().await;
+                        continue;
+                    }
+                    msg = stdin_rx.recv() => msg,
+                    _ = close.notified() => None,
+                };
+                let Some(msg) = msg else {
+                    break;
+                };
+                record(&recorder_stdin, Direction::Sent, msg.as_bytes());
+                let msg = frame(&msg, framed.load(Ordering::Relaxed));
                 if let Err(e) = stdin.write_all(msg.as_bytes()).await {
                     eprintln!("Failed to write to stdin: {}", e);
                     break;
@@ -98,182 +1210,248 @@ impl McpProcess {
                     break;
                 }
             }
+            // Dropping stdin here closes it
         });
 
-        let pending_requests = Arc::new(Mutex::new(HashMap::<
-            u64,
-            oneshot::Sender<Result<Value, String>>,
-        >::new()));
-        let pending_requests_clone = pending_requests.clone();
+        let rpc = RpcSession::new();
+        let rpc_stdout = rpc.clone();
         let log_tx_stdout = log_tx.clone();
+        let encoding = encoding_for(options.encoding.as_deref());
+        let detected_framing = content_length_framing.clone();
+        let recorder_stdout = recorder.clone();
+        let (notifications, _) = broadcast::channel(NOTIFICATION_BUFFER);
+        let notify_tx = notifications.clone();
+        let (server_requests, _) = broadcast::channel(NOTIFICATION_BUFFER);
+        let request_tx = server_requests.clone();
+        let reply_tx = stdin_tx.clone();
+
+        let in_terminal = terminal.is_some();
 
         // Stdout reader
-        tokio::spawn(async move {
-            let reader = BufReader::new(stdout);
-            let mut lines = reader.lines();
+        let stdout_task = tokio::spawn(async move {
+            let mut lines = LineReader::new(BufReader::new(stdout), MAX_STDOUT_LINE);
 
-            while let Ok(Some(line)) = lines.next_line().await {
-                let is_json_rpc =
-                    if let Ok(response) = serde_json::from_str::<JsonRpcResponse>(&line) {
-                        if let Some(req_id) = response.id {
-                            let mut pending = pending_requests_clone.lock().await;
-                            if let Some(tx) = pending.remove(&req_id) {
-                                if let Some(error) = response.error {
-                                    let _ = tx.send(Err(error.to_string()));
-                                } else {
-                                    let _ = tx.send(Ok(response.result.unwrap_or(Value::Null)));
-                                }
-                                true
-                            } else {
-                                false
+            while let Ok(Some(line)) = lines.next_message().await {
+                if line.framed {
+                    detected_framing.store(true, Ordering::Relaxed);
+                }
+                record(&recorder_stdout, Direction::Received, line.bytes);
+                // A prompt answered in the terminal leaves its text in front
+                // of the next message
+                let message = if in_terminal {
+                    after_prompt(line.bytes)
+                } else {
+                    line.bytes
+                };
+                let mut is_log_message = false;
+                let mut is_request = false;
+                if line.dropped == 0 {
+                    if let Some(n) = parse_notification(message) {
+                        if let Some(log) = log_message(&n) {
+                            is_log_message = true;
+                            let _ = log_tx_stdout.send(log.stamped()).await;
+                        }
+                        let _ = notify_tx.send(n);
+                    } else if let Some(request) = parse_server_request(message) {
+                        // Checked before responses, whose ids it could collide with
+                        is_request = true;
+                        let reply = match request.method.as_str() {
+                            "ping" => Some(Ok(serde_json::json!({}))),
+                            "sampling/createMessage" | "roots/list" | "elicitation/create"
+                                if request_tx.receiver_count() > 0 =>
+                            {
+                                let _ = request_tx.send(request.clone());
+                                None
                             }
-                        } else {
-                            false
+                            method => Some(Err(RpcError::new(
+                                RpcError::METHOD_NOT_FOUND,
+                                format!("Method not found: {}", method),
+                            ))),
+                        };
+                        if let Some(reply) = reply {
+                            let _ = reply_tx.send(reply_message(&request.id, reply)).await;
                         }
-                    } else {
-                        false
-                    };
+                    }
+                }
+                // JSON-RPC is always UTF-8, whatever the server's own output uses
+                let is_json_rpc = if is_log_message || is_request {
+                    true
+                } else if line.dropped > 0 {
+                    false
+                } else {
+                    rpc_stdout.resolve(message).await
+                };
 
                 if !is_json_rpc {
-                    let _ = log_tx_stdout.send(ProcessLog::Stdout(line)).await;
+                    let line = log_text(encoding, &line);
+                    let _ = log_tx_stdout.send(ProcessLog::Stdout(line).stamped()).await;
                 }
             }
         });
 
         let log_tx_stderr = log_tx.clone();
-        // Stderr reader
-        tokio::spawn(async move {
-            let reader = BufReader::new(stderr);
-            let mut lines = reader.lines();
+        let recorder_stderr = recorder.clone();
+        let in_flight_stderr = rpc.in_flight_calls.clone();
+        // Stderr reader, ending with the last line printed
+        let stderr_task = tokio::spawn(async move {
+            let mut lines = LineReader::new(BufReader::new(stderr), MAX_LOG_LINE);
+            let mut last = None;
 
             while let Ok(Some(line)) = lines.next_line().await {
-                let _ = log_tx_stderr.send(ProcessLog::Stderr(line)).await;
+                record(&recorder_stderr, Direction::Stderr, line.bytes);
+                let line = log_text(encoding, &line);
+                if !line.trim().is_empty() {
+                    last = Some(line.trim().to_string());
+                }
+                let request_id = in_flight_stderr.lock().unwrap().last().copied();
+                let entry = ProcessLog::Stderr(line).stamped().during(request_id);
+                let _ = log_tx_stderr.send(entry).await;
             }
+            last
         });
 
+        let pid = child.id();
+        let child = Arc::new(Mutex::new(child));
+        let stopping = Arc::new(AtomicBool::new(false));
+        let (exit_tx, exit) = watch::channel(None);
+        tokio::spawn(watch_exit(
+            child.clone(),
+            stdout_task,
+            stderr_task,
+            stopping.clone(),
+            log_tx,
+            exit_tx,
+        ));
+
         Ok(McpProcess {
-            child: Arc::new(Mutex::new(child)),
+            pid,
+            stopping,
+            exit,
+            container: None,
+            child,
             stdin_tx,
-            pending_requests,
-            next_request_id: Arc::new(Mutex::new(1)),
+            stdin_close,
+            content_length_framing,
+            recorder,
+            rpc,
+            notifications,
+            server_requests,
+            _pty: pty,
+            terminal,
         })
     }
 
-    pub async fn send_request(&self, method: &str, params: Option<Value>) -> Result<Value, String> {
-        let id;
-        {
-            let mut id_lock = self.next_request_id.lock().await;
-            id = *id_lock;
-            *id_lock += 1;
-        }
-
-        let request = JsonRpcRequest {
-            jsonrpc: "2.0".to_string(),
-            method: method.to_string(),
-            params: params.unwrap_or(serde_json::json!({})),
-            id,
-        };
-
-        let json_str = serde_json::to_string(&request).map_err(|e| e.to_string())?;
-
-        let (tx, rx) = oneshot::channel();
-        {
-            let mut pending = self.pending_requests.lock().await;
-            pending.insert(id, tx);
-        }
-
-        self.stdin_tx
-            .send(format!("{}\n", json_str))
-            .await
-            .map_err(|e| e.to_string())?;
+    /// Record everything sent, received and printed to stderr to `path`
+    /// from now on, replacing any recording in progress.
+    pub fn start_recording(&self, path: &Path) -> Result<(), String> {
+        let recorder = SessionRecorder::create(path).map_err(|e| e.to_string())?;
+        *self.recorder.lock().unwrap() = Some(recorder);
+        Ok(())
+    }
 
-        match rx.await {
-            Ok(result) => result,
-            Err(_) => Err("Request cancelled or process died".to_string()),
-        }
+    /// Stop recording, returning where the recording was saved.
+    pub fn stop_recording(&self) -> Option<PathBuf> {
+        let recorder = self.recorder.lock().unwrap().take()?;
+        Some(recorder.path().to_path_buf())
     }
 
     pub async fn kill(&self) -> Result<(), String> {
+        self.stopping.store(true, Ordering::Relaxed);
         let mut child = self.child.lock().await;
-        child.kill().await.map_err(|e| e.to_string())?;
-        Ok(())
+        let killed = kill_tree(&mut child).await;
+        // Killing the client doesn't stop its container
+        if let Some(container) = &self.container {
+            crate::docker::remove(container).await;
+        }
+        killed
     }
 
-    pub async fn list_tools(&self) -> Result<Vec<crate::models::Tool>, String> {
-        let val = self.send_request("tools/list", None).await?;
-        let res: crate::models::ListToolsResult =
-            serde_json::from_value(val).map_err(|e| e.to_string())?;
-        Ok(res.tools)
+    /// Stop the way MCP recommends for stdio servers: close stdin, then ask
+    /// the process to terminate, then kill it. Half of `grace` is given to
+    /// exiting once stdin closes and half to exiting once asked; on Windows,
+    /// where it can't be asked, all of it goes to the first.
+    pub async fn shutdown(&self, grace: std::time::Duration) -> Result<StopOutcome, String> {
+        self.stopping.store(true, Ordering::Relaxed);
+        self.stdin_close.notify_one();
+        let mut child = self.child.lock().await;
+        // Gone once the child is reaped, but its group may outlive it
+        let group = child.id();
+        let first = if cfg!(unix) { grace / 2 } else { grace };
+        if let Ok(Ok(_)) = tokio::time::timeout(first, child.wait()).await {
+            if let Some(group) = group {
+                kill_group(group);
+            }
+            return Ok(StopOutcome::Exited);
+        }
+        #[cfg(unix)]
+        match terminate(&child) {
+            Ok(()) => {
+                if let Ok(Ok(_)) = tokio::time::timeout(grace - first, child.wait()).await {
+                    if let Some(group) = group {
+                        kill_group(group);
+                    }
+                    return Ok(StopOutcome::Terminated);
+                }
+            }
+            Err(e) => tracing::warn!("Couldn't ask the server to terminate: {}", e),
+        }
+        kill_tree(&mut child).await?;
+        if let Some(container) = &self.container {
+            crate::docker::remove(container).await;
+        }
+        Ok(StopOutcome::Killed)
     }
 
-    pub async fn list_resources(&self) -> Result<Vec<crate::models::Resource>, String> {
-        let val = self.send_request("resources/list", None).await?;
-        let res: crate::models::ListResourcesResult =
-            serde_json::from_value(val).map_err(|e| e.to_string())?;
-        Ok(res.resources)
+    /// Answer a request the server sent.
+    pub async fn reply(&self, id: &Value, result: Result<Value, RpcError>) -> Result<(), String> {
+        self.send_message(reply_message(id, result)).await
     }
+}
 
-    pub async fn list_prompts(&self) -> Result<Vec<crate::models::Prompt>, String> {
-        let val = self.send_request("prompts/list", None).await?;
-        let res: crate::models::ListPromptsResult =
-            serde_json::from_value(val).map_err(|e| e.to_string())?;
-        Ok(res.prompts)
-    }
+impl RpcClient for McpProcess {
+    const ANSWERS_REQUESTS: bool = true;
 
-    pub async fn call_tool(
-        &self,
-        name: String,
-        arguments: serde_json::Value,
-    ) -> Result<crate::models::CallToolResult, String> {
-        let params = serde_json::json!({
-            "name": name,
-            "arguments": arguments
-        });
-        let val = self.send_request("tools/call", Some(params)).await?;
-        let res: crate::models::CallToolResult =
-            serde_json::from_value(val).map_err(|e| e.to_string())?;
-        Ok(res)
+    fn rpc(&self) -> &RpcSession {
+        &self.rpc
     }
 
-    pub async fn read_resource(
-        &self,
-        uri: String,
-    ) -> Result<crate::models::ReadResourceResult, String> {
-        let params = serde_json::json!({
-            "uri": uri
-        });
-        let val = self.send_request("resources/read", Some(params)).await?;
-        let res: crate::models::ReadResourceResult =
-            serde_json::from_value(val).map_err(|e| e.to_string())?;
-        Ok(res)
+    async fn send_message(&self, message: String) -> Result<(), String> {
+        self.stdin_tx.send(message).await.map_err(|e| e.to_string())
     }
 }
 
 impl McpSseClient {
-    pub async fn start(url: String, log_tx: mpsc::Sender<ProcessLog>) -> Result<Self, String> {
+    pub async fn start(
+        url: String,
+        bearer_token: Option<String>,
+        log_tx: mpsc::Sender<LogEntry>,
+    ) -> Result<Self, String> {
         let client = reqwest::Client::new();
         let request_url = Arc::new(Mutex::new(None));
-        let pending_requests = Arc::new(Mutex::new(HashMap::<
-            u64,
-            oneshot::Sender<Result<Value, String>>,
-        >::new()));
-        let next_request_id = Arc::new(Mutex::new(1));
+        let rpc = RpcSession::new();
 
         let request_url_clone = request_url.clone();
-        let pending_requests_clone = pending_requests.clone();
+        let rpc_clone = rpc.clone();
         let log_tx_clone = log_tx.clone();
         let client_clone = client.clone();
         let url_clone = url.clone();
+        let token_clone = bearer_token.clone();
+        let (notifications, _) = broadcast::channel(NOTIFICATION_BUFFER);
+        let notify_tx = notifications.clone();
 
         tokio::spawn(async move {
-            let res = match client_clone.get(&url_clone).send().await {
+            let mut req = client_clone.get(&url_clone);
+            if let Some(token) = &token_clone {
+                req = req.bearer_auth(token);
+            }
+            let res = match req.send().await {
                 Ok(r) => r,
                 Err(e) => {
                     let _ = log_tx_clone
-                        .send(ProcessLog::Stderr(format!(
-                            "Failed to connect to SSE: {}",
-                            e
-                        )))
+                        .send(
+                            ProcessLog::Stderr(format!("Failed to connect to SSE: {}", e))
+                                .stamped(),
+                        )
                         .await;
                     return;
                 }
@@ -285,7 +1463,7 @@ impl McpSseClient {
                     Ok(b) => b,
                     Err(e) => {
                         let _ = log_tx_clone
-                            .send(ProcessLog::Stderr(format!("SSE stream error: {}", e)))
+                            .send(ProcessLog::Stderr(format!("SSE stream error: {}", e)).stamped())
                             .await;
                         break;
                     }
@@ -300,30 +1478,24 @@ impl McpSseClient {
                             let mut req_url = request_url_clone.lock().await;
                             *req_url = Some(data.to_string());
                             let _ = log_tx_clone
-                                .send(ProcessLog::Stdout(format!(
-                                    "Connected to endpoint: {}",
-                                    data
-                                )))
+                                .send(
+                                    ProcessLog::Stdout(format!("Connected to endpoint: {}", data))
+                                        .stamped(),
+                                )
                                 .await;
-                        } else if let Ok(response) = serde_json::from_str::<JsonRpcResponse>(data) {
-                            if let Some(req_id) = response.id {
-                                let mut pending = pending_requests_clone.lock().await;
-                                if let Some(tx) = pending.remove(&req_id) {
-                                    if let Some(error) = response.error {
-                                        let _ = tx.send(Err(error.to_string()));
-                                    } else {
-                                        let _ = tx.send(Ok(response.result.unwrap_or(Value::Null)));
-                                    }
-                                }
+                        } else if let Some(n) = parse_notification(data.as_bytes()) {
+                            if let Some(log) = log_message(&n) {
+                                let _ = log_tx_clone.send(log.stamped()).await;
                             }
-                        } else {
+                            let _ = notify_tx.send(n);
+                        } else if !rpc_clone.resolve(data.as_bytes()).await {
                             let _ = log_tx_clone
-                                .send(ProcessLog::Stdout(data.to_string()))
+                                .send(ProcessLog::Stdout(data.to_string()).stamped())
                                 .await;
                         }
                     } else if !line.is_empty() {
                         let _ = log_tx_clone
-                            .send(ProcessLog::Stdout(line.to_string()))
+                            .send(ProcessLog::Stdout(line.to_string()).stamped())
                             .await;
                     }
                 }
@@ -334,112 +1506,479 @@ impl McpSseClient {
             url,
             request_url,
             client,
-            pending_requests,
-            next_request_id,
+            bearer_token,
+            rpc,
+            notifications,
         })
     }
+}
 
-    pub async fn send_request(&self, method: &str, params: Option<Value>) -> Result<Value, String> {
-        let req_url = {
-            let lock = self.request_url.lock().await;
-            lock.clone().ok_or("Endpoint not yet received")?
-        };
-
-        let id;
-        {
-            let mut id_lock = self.next_request_id.lock().await;
-            id = *id_lock;
-            *id_lock += 1;
-        }
-
-        let request = JsonRpcRequest {
-            jsonrpc: "2.0".to_string(),
-            method: method.to_string(),
-            params: params.unwrap_or(serde_json::json!({})),
-            id,
-        };
+impl RpcClient for McpSseClient {
+    const ANSWERS_REQUESTS: bool = false;
 
-        let (tx, rx) = oneshot::channel();
-        {
-            let mut pending = self.pending_requests.lock().await;
-            pending.insert(id, tx);
-        }
+    fn rpc(&self) -> &RpcSession {
+        &self.rpc
+    }
 
-        let res = self
+    /// Posts `message` to the endpoint the server announced.
+    async fn send_message(&self, message: String) -> Result<(), String> {
+        let req_url = self
+            .request_url
+            .lock()
+            .await
+            .clone()
+            .ok_or("Endpoint not yet received")?;
+        let mut req = self
             .client
             .post(&req_url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
-
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(message);
+        if let Some(token) = &self.bearer_token {
+            req = req.bearer_auth(token);
+        }
+        let res = req.send().await.map_err(|e| e.to_string())?;
         if !res.status().is_success() {
-            let mut pending = self.pending_requests.lock().await;
-            pending.remove(&id);
             return Err(format!("POST failed with status: {}", res.status()));
         }
+        Ok(())
+    }
 
-        match rx.await {
-            Ok(result) => result,
-            Err(_) => Err("Request cancelled or connection lost".to_string()),
+    /// Waits for the server to announce where requests go.
+    async fn ready(&self) -> Result<(), String> {
+        let deadline = tokio::time::Instant::now() + ENDPOINT_TIMEOUT;
+        while self.request_url.lock().await.is_none() {
+            if tokio::time::Instant::now() >= deadline {
+                return Err("Endpoint not yet received".to_string());
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
         }
+        Ok(())
     }
+}
 
-    pub async fn list_tools(&self) -> Result<Vec<crate::models::Tool>, String> {
-        let val = self.send_request("tools/list", None).await?;
-        let res: crate::models::ListToolsResult =
-            serde_json::from_value(val).map_err(|e| e.to_string())?;
-        Ok(res.tools)
+type WsStream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// First wait before reconnecting a dropped WebSocket; doubles per failed
+/// attempt up to [`WS_MAX_BACKOFF`].
+const WS_MIN_BACKOFF: std::time::Duration = std::time::Duration::from_secs(1);
+const WS_MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How long a reconnected server gets to answer `initialize`.
+const WS_HANDSHAKE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+async fn ws_connect(url: &str, bearer_token: Option<&str>) -> Result<WsStream, String> {
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+    use tokio_tungstenite::tungstenite::http::{header::AUTHORIZATION, HeaderValue};
+
+    let mut request = url.into_client_request().map_err(|e| e.to_string())?;
+    if let Some(token) = bearer_token {
+        let value =
+            HeaderValue::from_str(&format!("Bearer {}", token)).map_err(|e| e.to_string())?;
+        request.headers_mut().insert(AUTHORIZATION, value);
     }
+    let (ws, _) = tokio_tungstenite::connect_async(request)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(ws)
+}
 
-    pub async fn list_resources(&self) -> Result<Vec<crate::models::Resource>, String> {
-        let val = self.send_request("resources/list", None).await?;
-        let res: crate::models::ListResourcesResult =
-            serde_json::from_value(val).map_err(|e| e.to_string())?;
-        Ok(res.resources)
+/// Redo the handshake on a fresh connection, before anything else is
+/// sent on it.
+async fn ws_handshake(ws: &mut WsStream, id: u64) -> Result<(), String> {
+    let request = JsonRpcRequest {
+        jsonrpc: "2.0".to_string(),
+        method: "initialize".to_string(),
+        params: initialize_params(false),
+        id,
+    };
+    let json = serde_json::to_string(&request).map_err(|e| e.to_string())?;
+    ws.send(Message::text(json))
+        .await
+        .map_err(|e| e.to_string())?;
+    let answer = async {
+        while let Some(frame) = ws.next().await {
+            let Message::Text(text) = frame.map_err(|e| e.to_string())? else {
+                continue;
+            };
+            let Ok(response) = serde_json::from_str::<JsonRpcResponse>(&text) else {
+                continue;
+            };
+            if response.id == Some(id) {
+                return match response.error {
+                    Some(error) => Err(error.to_string()),
+                    None => negotiate(response.result.unwrap_or(Value::Null)).map(|_| ()),
+                };
+            }
+        }
+        Err("Connection closed during initialize".to_string())
+    };
+    tokio::time::timeout(WS_HANDSHAKE_TIMEOUT, answer)
+        .await
+        .map_err(|_| "Timed out waiting for initialize".to_string())??;
+    let json = serde_json::to_string(&notification("notifications/initialized", None))
+        .map_err(|e| e.to_string())?;
+    ws.send(Message::text(json))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+impl McpWsClient {
+    pub async fn start(
+        url: String,
+        bearer_token: Option<String>,
+        log_tx: mpsc::Sender<LogEntry>,
+    ) -> Result<Self, String> {
+        let mut ws = ws_connect(&url, bearer_token.as_deref()).await?;
+        let _ = log_tx
+            .send(ProcessLog::Stdout(format!("Connected to {}", url)).stamped())
+            .await;
+
+        let (outgoing, mut outgoing_rx) = mpsc::channel::<String>(32);
+        let (connected_tx, connected) = watch::channel(true);
+        let close = Arc::new(Notify::new());
+        let rpc = RpcSession::new();
+        let (notifications, _) = broadcast::channel(NOTIFICATION_BUFFER);
+        let notify_tx = notifications.clone();
+
+        let url_clone = url.clone();
+        let close_clone = close.clone();
+        let rpc_clone = rpc.clone();
+        tokio::spawn(async move {
+            let log = |entry: ProcessLog| {
+                let log_tx = log_tx.clone();
+                async move {
+                    let _ = log_tx.send(entry.stamped()).await;
+                }
+            };
+            loop {
+                let _ = connected_tx.send(true);
+                let closed = loop {
+                    tokio::select! {
+                        msg = outgoing_rx.recv() => match msg {
+                            Some(text) => {
+                                if let Err(e) = ws.send(Message::text(text)).await {
+                                    log(ProcessLog::Stderr(format!("WebSocket send failed: {}", e))).await;
+                                    break false;
+                                }
+                            }
+                            None => break true,
+                        },
+                        frame = ws.next() => match frame {
+                            Some(Ok(Message::Text(text))) => {
+                                if !rpc_clone.resolve(text.as_bytes()).await {
+                                    let n = parse_notification(text.as_bytes());
+                                    match n.as_ref().and_then(log_message) {
+                                        Some(message) => log(message).await,
+                                        None => log(ProcessLog::Stdout(text.to_string())).await,
+                                    }
+                                    if let Some(n) = n {
+                                        let _ = notify_tx.send(n);
+                                    }
+                                }
+                            }
+                            Some(Ok(Message::Close(_))) | None => break false,
+                            // Pings are answered by the library
+                            Some(Ok(_)) => {}
+                            Some(Err(e)) => {
+                                log(ProcessLog::Stderr(format!("WebSocket error: {}", e))).await;
+                                break false;
+                            }
+                        },
+                        _ = close_clone.notified() => break true,
+                    }
+                };
+
+                let _ = connected_tx.send(false);
+                for (_, tx) in rpc_clone.pending_requests.lock().await.drain() {
+                    let _ = tx.send(Err("Connection lost".to_string()));
+                }
+                while outgoing_rx.try_recv().is_ok() {}
+                if closed {
+                    let _ = ws.close(None).await;
+                    return;
+                }
+
+                let mut backoff = WS_MIN_BACKOFF;
+                ws = loop {
+                    log(ProcessLog::Stderr(format!(
+                        "Disconnected; reconnecting in {}s",
+                        backoff.as_secs()
+                    )))
+                    .await;
+                    tokio::select! {
+                        _ = tokio::time::sleep(backoff) => {}
+                        _ = close_clone.notified() => return,
+                    }
+                    backoff = (backoff * 2).min(WS_MAX_BACKOFF);
+                    let mut ws = match ws_connect(&url_clone, bearer_token.as_deref()).await {
+                        Ok(ws) => ws,
+                        Err(e) => {
+                            log(ProcessLog::Stderr(format!("Reconnect failed: {}", e))).await;
+                            continue;
+                        }
+                    };
+                    if rpc_clone.server_info.get().is_some() {
+                        let id = rpc_clone.allocate_request_id().await;
+                        if let Err(e) = ws_handshake(&mut ws, id).await {
+                            log(ProcessLog::Stderr(format!("Reconnect failed: {}", e))).await;
+                            continue;
+                        }
+                    }
+                    log(ProcessLog::Stdout(format!("Reconnected to {}", url_clone))).await;
+                    break ws;
+                };
+            }
+        });
+
+        Ok(McpWsClient {
+            url,
+            outgoing,
+            connected,
+            close,
+            rpc,
+            notifications,
+        })
     }
 
-    pub async fn list_prompts(&self) -> Result<Vec<crate::models::Prompt>, String> {
-        let val = self.send_request("prompts/list", None).await?;
-        let res: crate::models::ListPromptsResult =
-            serde_json::from_value(val).map_err(|e| e.to_string())?;
-        Ok(res.prompts)
+    /// Close the connection and stop reconnecting.
+    pub fn close(&self) {
+        self.close.notify_one();
+    }
+}
+
+impl RpcClient for McpWsClient {
+    const ANSWERS_REQUESTS: bool = false;
+
+    fn rpc(&self) -> &RpcSession {
+        &self.rpc
+    }
+
+    /// Queues `message` for the connection task. Fails fast while
+    /// reconnecting.
+    async fn send_message(&self, message: String) -> Result<(), String> {
+        if !*self.connected.borrow() {
+            return Err("Not connected; reconnecting".to_string());
+        }
+        self.outgoing.send(message).await.map_err(|e| e.to_string())
+    }
+}
+
+impl Drop for McpWsClient {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl McpReplay {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let messages = crate::session_recording::load(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            replay: Mutex::new(Replay::new(messages)),
+            server_info: OnceLock::new(),
+            notifications: broadcast::channel(1).0,
+        })
+    }
+
+    /// Plays back the recorded handshake. Recordings started after the
+    /// server was up have none, leaving the capabilities unknown.
+    pub async fn initialize(&self) -> Result<(), String> {
+        if let Ok(result) = self
+            .send_request("initialize", Some(initialize_params(true)))
+            .await
+        {
+            let _ = self.server_info.set(negotiate(result)?);
+        }
+        Ok(())
+    }
+
+    /// What the server printed to stderr during the recording.
+    pub async fn stderr_lines(&self) -> Vec<String> {
+        self.replay.lock().await.stderr_lines()
+    }
+
+    /// The recorded reply to a request. Recordings don't number the
+    /// requests replayed, so the one shown has no id.
+    async fn send_raw_request(&self, method: &str, params: Value) -> RawExchange {
+        let started = std::time::Instant::now();
+        let response = self.replay.lock().await.reply(method, &params);
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "method": method,
+            "params": params,
+        });
+        RawExchange {
+            request,
+            response,
+            elapsed: started.elapsed(),
+        }
     }
+}
 
-    pub async fn call_tool(
+impl McpRequests for McpReplay {
+    async fn send_request(&self, method: &str, params: Option<Value>) -> Result<Value, String> {
+        let params = params.unwrap_or(serde_json::json!({}));
+        reply_result(self.replay.lock().await.reply(method, &params)?)
+    }
+
+    async fn call_tool(
         &self,
         name: String,
-        arguments: serde_json::Value,
+        arguments: Value,
     ) -> Result<crate::models::CallToolResult, String> {
         let params = serde_json::json!({
             "name": name,
             "arguments": arguments
         });
         let val = self.send_request("tools/call", Some(params)).await?;
-        let res: crate::models::CallToolResult =
-            serde_json::from_value(val).map_err(|e| e.to_string())?;
-        Ok(res)
+        serde_json::from_value(val).map_err(|e| e.to_string())
     }
+}
 
-    pub async fn read_resource(
-        &self,
-        uri: String,
-    ) -> Result<crate::models::ReadResourceResult, String> {
-        let params = serde_json::json!({
-            "uri": uri
-        });
-        let val = self.send_request("resources/read", Some(params)).await?;
-        let res: crate::models::ReadResourceResult =
-            serde_json::from_value(val).map_err(|e| e.to_string())?;
-        Ok(res)
+/// Launch `server`, or connect to it if it's remote. The MCP handshake is
+/// left to the caller.
+pub async fn start_handler(
+    server: &crate::models::McpServer,
+    options: &crate::models::LaunchOptions,
+    log_tx: mpsc::Sender<LogEntry>,
+) -> Result<McpHandler, String> {
+    let token = server
+        .env
+        .as_ref()
+        .and_then(|env| env.get(crate::pairing::TOKEN_ENV_KEY).cloned());
+    let handler = match server.server_type.as_str() {
+        "sse" => {
+            let url = server.url.clone().ok_or("SSE server must have a URL")?;
+            let sse_client = McpSseClient::start(url, token, log_tx).await?;
+            McpHandler::Sse(sse_client)
+        }
+        "ws" => {
+            let url = server
+                .url
+                .clone()
+                .ok_or("WebSocket server must have a URL")?;
+            let ws_client = McpWsClient::start(url, token, log_tx).await?;
+            McpHandler::Ws(ws_client)
+        }
+        _ => {
+            let (program, args) = crate::docker::server_command(server)?;
+            let container = server
+                .docker_image
+                .is_some()
+                .then(|| crate::docker::container_name(&server.id));
+            // Left behind if the manager quit without stopping it
+            if let Some(container) = &container {
+                crate::docker::remove(container).await;
+            }
+            let mut proc = McpProcess::start(
+                server.id.clone(),
+                program,
+                args,
+                Some(server.env.clone().unwrap_or_default()),
+                server.cwd.as_deref().map(Path::new),
+                options,
+                log_tx,
+            )
+            .await?;
+            proc.container = container;
+            McpHandler::Stdio(proc)
+        }
+    };
+    if let Some(secs) = server.request_timeout_secs {
+        handler.set_request_timeout(std::time::Duration::from_secs(secs.into()));
     }
+    Ok(handler)
 }
 
 impl McpHandler {
+    /// Run the MCP handshake. Nothing else may be sent before it completes.
+    pub async fn initialize(&self) -> Result<(), String> {
+        match self {
+            McpHandler::Stdio(p) => p.initialize().await.map(|_| ()),
+            McpHandler::Sse(p) => p.initialize().await.map(|_| ()),
+            McpHandler::Ws(p) => p.initialize().await.map(|_| ()),
+            McpHandler::Replay(p) => p.initialize().await,
+        }
+    }
+
+    /// What the server reported in `initialize`: its protocol version,
+    /// capabilities and name. `None` until the handshake is done.
+    pub fn server_info(&self) -> Option<&InitializeResult> {
+        match self {
+            McpHandler::Stdio(p) => p.rpc.server_info.get(),
+            McpHandler::Sse(p) => p.rpc.server_info.get(),
+            McpHandler::Ws(p) => p.rpc.server_info.get(),
+            McpHandler::Replay(p) => p.server_info.get(),
+        }
+    }
+
+    pub fn capabilities(&self) -> Option<&crate::models::ServerCapabilities> {
+        self.server_info().map(|info| &info.capabilities)
+    }
+
+    /// How long requests wait for a reply before failing.
+    pub fn set_request_timeout(&self, timeout: std::time::Duration) {
+        match self {
+            McpHandler::Stdio(p) => p.set_request_timeout(timeout),
+            McpHandler::Sse(p) => p.set_request_timeout(timeout),
+            McpHandler::Ws(p) => p.set_request_timeout(timeout),
+            // Replies come straight from the recording
+            McpHandler::Replay(_) => {}
+        }
+    }
+
+    /// Check the server answers within `timeout`, using the spec's `ping`.
+    /// Servers that don't implement it are asked for their tools instead.
+    pub async fn ping(&self, timeout: std::time::Duration) -> Result<(), String> {
+        let ping = async {
+            let reply = match self {
+                McpHandler::Stdio(p) => p.send_request_with_timeout("ping", None, timeout).await,
+                McpHandler::Sse(p) => p.send_request_with_timeout("ping", None, timeout).await,
+                McpHandler::Ws(p) => p.send_request_with_timeout("ping", None, timeout).await,
+                // The recording answers everything; there is no server to reach
+                McpHandler::Replay(_) => return Ok(()),
+            };
+            match reply {
+                Err(e) if is_method_not_found(&e) => self.list_tools().await.map(|_| ()),
+                reply => reply.map(|_| ()),
+            }
+        };
+        tokio::time::timeout(timeout, ping)
+            .await
+            .map_err(|_| format!("No reply within {}s", timeout.as_secs_f32()))?
+    }
+
+    /// Send any request and return the raw result.
+    pub async fn send_request(&self, method: &str, params: Option<Value>) -> Result<Value, String> {
+        match self {
+            McpHandler::Stdio(p) => p.send_request(method, params).await,
+            McpHandler::Sse(p) => p.send_request(method, params).await,
+            McpHandler::Ws(p) => p.send_request(method, params).await,
+            McpHandler::Replay(p) => p.send_request(method, params).await,
+        }
+    }
+
+    /// Send any request and return the messages exchanged, for the
+    /// console's Raw tab.
+    pub async fn send_raw_request(&self, method: &str, params: Option<Value>) -> RawExchange {
+        // Sent as `{}` when there are none, like every other request
+        let params = params.unwrap_or(serde_json::json!({}));
+        match self {
+            McpHandler::Stdio(p) => p.send_raw_request(method, params).await,
+            McpHandler::Sse(p) => p.send_raw_request(method, params).await,
+            McpHandler::Ws(p) => p.send_raw_request(method, params).await,
+            McpHandler::Replay(p) => p.send_raw_request(method, params).await,
+        }
+    }
+
     pub async fn list_tools(&self) -> Result<Vec<crate::models::Tool>, String> {
         match self {
             McpHandler::Stdio(p) => p.list_tools().await,
             McpHandler::Sse(p) => p.list_tools().await,
+            McpHandler::Ws(p) => p.list_tools().await,
+            McpHandler::Replay(p) => p.list_tools().await,
         }
     }
 
@@ -447,6 +1986,19 @@ impl McpHandler {
         match self {
             McpHandler::Stdio(p) => p.list_resources().await,
             McpHandler::Sse(p) => p.list_resources().await,
+            McpHandler::Ws(p) => p.list_resources().await,
+            McpHandler::Replay(p) => p.list_resources().await,
+        }
+    }
+
+    pub async fn list_resource_templates(
+        &self,
+    ) -> Result<Vec<crate::models::ResourceTemplate>, String> {
+        match self {
+            McpHandler::Stdio(p) => p.list_resource_templates().await,
+            McpHandler::Sse(p) => p.list_resource_templates().await,
+            McpHandler::Ws(p) => p.list_resource_templates().await,
+            McpHandler::Replay(p) => p.list_resource_templates().await,
         }
     }
 
@@ -454,6 +2006,8 @@ impl McpHandler {
         match self {
             McpHandler::Stdio(p) => p.list_prompts().await,
             McpHandler::Sse(p) => p.list_prompts().await,
+            McpHandler::Ws(p) => p.list_prompts().await,
+            McpHandler::Replay(p) => p.list_prompts().await,
         }
     }
 
@@ -465,6 +2019,8 @@ impl McpHandler {
         match self {
             McpHandler::Stdio(p) => p.call_tool(name, arguments).await,
             McpHandler::Sse(p) => p.call_tool(name, arguments).await,
+            McpHandler::Ws(p) => p.call_tool(name, arguments).await,
+            McpHandler::Replay(p) => p.call_tool(name, arguments).await,
         }
     }
 
@@ -475,6 +2031,131 @@ impl McpHandler {
         match self {
             McpHandler::Stdio(p) => p.read_resource(uri).await,
             McpHandler::Sse(p) => p.read_resource(uri).await,
+            McpHandler::Ws(p) => p.read_resource(uri).await,
+            McpHandler::Replay(p) => p.read_resource(uri).await,
+        }
+    }
+
+    pub async fn get_prompt(
+        &self,
+        name: String,
+        arguments: HashMap<String, String>,
+    ) -> Result<crate::models::GetPromptResult, String> {
+        match self {
+            McpHandler::Stdio(p) => p.get_prompt(name, arguments).await,
+            McpHandler::Sse(p) => p.get_prompt(name, arguments).await,
+            McpHandler::Ws(p) => p.get_prompt(name, arguments).await,
+            McpHandler::Replay(p) => p.get_prompt(name, arguments).await,
+        }
+    }
+
+    pub async fn subscribe_resource(&self, uri: &str) -> Result<(), String> {
+        match self {
+            McpHandler::Stdio(p) => p.subscribe_resource(uri).await,
+            McpHandler::Sse(p) => p.subscribe_resource(uri).await,
+            McpHandler::Ws(p) => p.subscribe_resource(uri).await,
+            McpHandler::Replay(p) => p.subscribe_resource(uri).await,
+        }
+    }
+
+    pub async fn unsubscribe_resource(&self, uri: &str) -> Result<(), String> {
+        match self {
+            McpHandler::Stdio(p) => p.unsubscribe_resource(uri).await,
+            McpHandler::Sse(p) => p.unsubscribe_resource(uri).await,
+            McpHandler::Ws(p) => p.unsubscribe_resource(uri).await,
+            McpHandler::Replay(p) => p.unsubscribe_resource(uri).await,
+        }
+    }
+
+    pub async fn set_log_level(&self, level: LoggingLevel) -> Result<(), String> {
+        match self {
+            McpHandler::Stdio(p) => p.set_log_level(level).await,
+            McpHandler::Sse(p) => p.set_log_level(level).await,
+            McpHandler::Ws(p) => p.set_log_level(level).await,
+            McpHandler::Replay(p) => p.set_log_level(level).await,
+        }
+    }
+
+    pub async fn cancel_request(&self, id: u64, reason: &str) -> Result<(), String> {
+        match self {
+            McpHandler::Stdio(p) => p.cancel_request(id, reason).await,
+            McpHandler::Sse(p) => p.cancel_request(id, reason).await,
+            McpHandler::Ws(p) => p.cancel_request(id, reason).await,
+            // Replies come straight from the recording; nothing waits
+            McpHandler::Replay(_) => Err(format!("Request {} has already finished", id)),
+        }
+    }
+
+    /// Ids of tool calls awaiting a reply, oldest first.
+    pub fn in_flight_calls(&self) -> Vec<u64> {
+        match self {
+            McpHandler::Stdio(p) => p.in_flight_calls(),
+            McpHandler::Sse(p) => p.in_flight_calls(),
+            McpHandler::Ws(p) => p.in_flight_calls(),
+            McpHandler::Replay(_) => Vec::new(),
+        }
+    }
+
+    /// Notifications the server sends from now on. Ends when the server
+    /// is dropped.
+    pub fn notifications(&self) -> broadcast::Receiver<ServerNotification> {
+        match self {
+            McpHandler::Stdio(p) => p.notifications.subscribe(),
+            McpHandler::Sse(p) => p.notifications.subscribe(),
+            McpHandler::Ws(p) => p.notifications.subscribe(),
+            McpHandler::Replay(p) => p.notifications.subscribe(),
+        }
+    }
+
+    /// Tell the server its roots changed, so it asks for `roots/list` again.
+    pub async fn notify_roots_changed(&self) -> Result<(), String> {
+        let method = "notifications/roots/list_changed";
+        match self {
+            McpHandler::Stdio(p) => p.send_notification(method, None).await,
+            McpHandler::Sse(p) => p.send_notification(method, None).await,
+            McpHandler::Ws(p) => p.send_notification(method, None).await,
+            McpHandler::Replay(_) => Ok(()),
+        }
+    }
+
+    /// Sampling and roots requests the server sends from now on, for
+    /// stdio servers.
+    pub fn server_requests(&self) -> Option<broadcast::Receiver<ServerRequest>> {
+        match self {
+            McpHandler::Stdio(p) => Some(p.server_requests.subscribe()),
+            _ => None,
+        }
+    }
+
+    /// Answer a request from [`McpHandler::server_requests`].
+    pub async fn reply(&self, id: &Value, result: Result<Value, RpcError>) -> Result<(), String> {
+        match self {
+            McpHandler::Stdio(p) => p.reply(id, result).await,
+            _ => Err("Only stdio servers can send requests".to_string()),
+        }
+    }
+
+    /// The local process behind the handler, for stdio servers.
+    pub fn pid(&self) -> Option<u32> {
+        match self {
+            McpHandler::Stdio(p) => p.pid,
+            _ => None,
+        }
+    }
+
+    /// How the process ended, once it has, for stdio servers.
+    pub fn exited(&self) -> Option<watch::Receiver<Option<ProcessExit>>> {
+        match self {
+            McpHandler::Stdio(p) => Some(p.exit.clone()),
+            _ => None,
+        }
+    }
+
+    /// The server's terminal, for servers started in PTY mode.
+    pub fn terminal(&self) -> Option<crate::pty::Terminal> {
+        match self {
+            McpHandler::Stdio(p) => p.terminal.clone(),
+            _ => None,
         }
     }
 
@@ -482,6 +2163,38 @@ impl McpHandler {
         match self {
             McpHandler::Stdio(p) => p.kill().await,
             McpHandler::Sse(_) => Ok(()), // SSE just stops when dropped or connection closes
+            McpHandler::Ws(p) => {
+                p.close();
+                Ok(())
+            }
+            McpHandler::Replay(_) => Ok(()),
+        }
+    }
+
+    pub async fn shutdown(&self, grace: std::time::Duration) -> Result<StopOutcome, String> {
+        match self {
+            McpHandler::Stdio(p) => p.shutdown(grace).await,
+            McpHandler::Ws(p) => {
+                p.close();
+                Ok(StopOutcome::Disconnected)
+            }
+            McpHandler::Sse(_) | McpHandler::Replay(_) => Ok(StopOutcome::Disconnected),
+        }
+    }
+
+    /// Start recording the session to `path`. Only stdio servers can be
+    /// recorded.
+    pub fn start_recording(&self, path: &Path) -> Result<(), String> {
+        match self {
+            McpHandler::Stdio(p) => p.start_recording(path),
+            _ => Err("Only stdio servers can be recorded".to_string()),
+        }
+    }
+
+    pub fn stop_recording(&self) -> Option<PathBuf> {
+        match self {
+            McpHandler::Stdio(p) => p.stop_recording(),
+            _ => None,
         }
     }
 }
@@ -608,7 +2321,7 @@ mod tests {
         let log = ProcessLog::Stdout("Hello from stdout".to_string());
         match log {
             ProcessLog::Stdout(msg) => assert_eq!(msg, "Hello from stdout"),
-            ProcessLog::Stderr(_) => panic!("Expected Stdout"),
+            _ => panic!("Expected Stdout"),
         }
     }
 
@@ -617,7 +2330,7 @@ mod tests {
         let log = ProcessLog::Stderr("Error message".to_string());
         match log {
             ProcessLog::Stderr(msg) => assert_eq!(msg, "Error message"),
-            ProcessLog::Stdout(_) => panic!("Expected Stderr"),
+            _ => panic!("Expected Stderr"),
         }
     }
 
@@ -627,10 +2340,18 @@ mod tests {
         let cloned = log.clone();
         match cloned {
             ProcessLog::Stdout(msg) => assert_eq!(msg, "test"),
-            ProcessLog::Stderr(_) => panic!("Expected Stdout"),
+            _ => panic!("Expected Stdout"),
         }
     }
 
+    #[test]
+    fn test_process_log_stamped_at_receipt() {
+        let before = chrono::Utc::now();
+        let entry = ProcessLog::Stderr("late".to_string()).stamped();
+        assert!(entry.at >= before && entry.at <= chrono::Utc::now());
+        assert!(matches!(entry.log, ProcessLog::Stderr(msg) if msg == "late"));
+    }
+
     // === MCP Protocol Method Tests ===
 
     #[test]
@@ -793,4 +2514,791 @@ mod tests {
             Some("File contents here".to_string())
         );
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_shutdown_closes_stdin_then_terminates_then_kills() {
+        let grace = std::time::Duration::from_secs(1);
+        let options = crate::models::LaunchOptions::default();
+
+        // cat exits as soon as its stdin closes
+        let (log_tx, _log_rx) = mpsc::channel(10);
+        let cat = McpProcess::start(
+            "a".into(),
+            "cat".into(),
+            vec![],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+        assert_eq!(cat.shutdown(grace).await.unwrap(), StopOutcome::Exited);
+
+        // sleep ignores stdin but exits on SIGTERM
+        let (log_tx, _log_rx) = mpsc::channel(10);
+        let sleep = McpProcess::start(
+            "b".into(),
+            "sleep".into(),
+            vec!["30".into()],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            sleep.shutdown(grace).await.unwrap(),
+            StopOutcome::Terminated
+        );
+
+        // Ignores both
+        let (log_tx, _log_rx) = mpsc::channel(10);
+        let stubborn = McpProcess::start(
+            "c".into(),
+            "sh".into(),
+            vec![
+                "-c".into(),
+                "trap '' TERM; while :; do sleep 0.1; done".into(),
+            ],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+        assert_eq!(stubborn.shutdown(grace).await.unwrap(), StopOutcome::Killed);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_exit_is_noticed() {
+        let options = crate::models::LaunchOptions::default();
+        let (log_tx, mut log_rx) = mpsc::channel(10);
+        let crash = McpProcess::start(
+            "a".into(),
+            "sh".into(),
+            vec!["-c".into(), "echo 'out of memory' >&2; exit 3".into()],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+        let mut exit = crash.exit.clone();
+        let exited = exit.wait_for(Option::is_some).await.unwrap().clone();
+        assert_eq!(
+            exited,
+            Some(ProcessExit {
+                code: Some(3),
+                description: "exited with code 3".to_string(),
+                requested: false,
+                last_stderr: Some("out of memory".to_string()),
+            })
+        );
+        // Its last words come before the exit
+        let mut logs = Vec::new();
+        while let Ok(entry) = log_rx.try_recv() {
+            logs.push(entry.log);
+        }
+        assert!(
+            matches!(&logs[..], [ProcessLog::Stderr(line), ProcessLog::Exit(how)]
+            if line == "out of memory" && how == "exited with code 3")
+        );
+
+        // Stopped by the manager
+        let (log_tx, _log_rx) = mpsc::channel(10);
+        let sleep = McpProcess::start(
+            "b".into(),
+            "sleep".into(),
+            vec!["30".into()],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+        sleep.kill().await.unwrap();
+        let mut exit = sleep.exit.clone();
+        let exited = exit
+            .wait_for(Option::is_some)
+            .await
+            .unwrap()
+            .clone()
+            .unwrap();
+        assert!(exited.requested);
+        assert_eq!(exited.description, "was ended by signal 9");
+    }
+
+    #[test]
+    fn test_resolve_command() {
+        let root = std::env::temp_dir().join(format!("omm-resolve-{}", uuid::Uuid::new_v4()));
+        let on_path = root.join("bin");
+        let installed = root.join("installed");
+        std::fs::create_dir_all(&on_path).unwrap();
+        std::fs::create_dir_all(&installed).unwrap();
+        let write_tool = |path: PathBuf| {
+            std::fs::write(&path, "").unwrap();
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let executable = std::fs::Permissions::from_mode(0o755);
+                std::fs::set_permissions(&path, executable).unwrap();
+            }
+        };
+        write_tool(installed.join("tool"));
+        let path_var = on_path.display().to_string();
+        let fallback = [installed.clone()];
+
+        // Found outside PATH, so its directory goes first on the child's
+        let resolved = resolve_command("tool", Some(&path_var), &fallback).unwrap();
+        assert_eq!(resolved.program, installed.join("tool"));
+        let path: Vec<PathBuf> = std::env::split_paths(&resolved.path.unwrap()).collect();
+        assert_eq!(path, vec![installed.clone(), on_path.clone()]);
+
+        // A file on PATH the OS wouldn't run doesn't shadow the real one
+        #[cfg(unix)]
+        {
+            std::fs::write(on_path.join("tool"), "").unwrap();
+            let resolved = resolve_command("tool", Some(&path_var), &fallback).unwrap();
+            assert_eq!(resolved.program, installed.join("tool"));
+        }
+
+        write_tool(on_path.join("tool"));
+        let resolved = resolve_command("tool", Some(&path_var), &fallback).unwrap();
+        assert_eq!(resolved.program, on_path.join("tool"));
+        assert_eq!(resolved.path, None);
+
+        assert_eq!(resolve_command("missing", Some(&path_var), &fallback), None);
+        assert_eq!(resolve_command("./tool", Some(&path_var), &fallback), None);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_install_dirs() {
+        let home = std::env::temp_dir().join(format!("omm-home-{}", uuid::Uuid::new_v4()));
+        let nvm = home.join(".nvm").join("versions").join("node");
+        for dir in [
+            home.join(".volta").join("bin"),
+            nvm.join("v9.11.2").join("bin"),
+            nvm.join("v20.11.1").join("bin"),
+            nvm.join("v18.20.0").join("bin"),
+        ] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+
+        let dirs = install_dirs(Some(&home), |_| None);
+        // Newest Node first
+        assert_eq!(
+            dirs[..4],
+            [
+                home.join(".volta").join("bin"),
+                nvm.join("v20.11.1").join("bin"),
+                nvm.join("v18.20.0").join("bin"),
+                nvm.join("v9.11.2").join("bin"),
+            ]
+        );
+        // Missing directories are left out
+        assert!(!dirs.contains(&home.join(".cargo").join("bin")));
+        std::fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_starts_in_working_directory() {
+        let options = crate::models::LaunchOptions::default();
+        let dir = std::env::temp_dir().canonicalize().unwrap();
+        let (log_tx, mut log_rx) = mpsc::channel(10);
+        let _pwd = McpProcess::start(
+            "a".into(),
+            "pwd".into(),
+            vec![],
+            None,
+            Some(&dir),
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+        let entry = log_rx.recv().await.unwrap();
+        assert!(matches!(entry.log, ProcessLog::Stdout(line) if line == dir.display().to_string()));
+
+        let missing = dir.join(format!("omm-cwd-{}", uuid::Uuid::new_v4()));
+        let (log_tx, _log_rx) = mpsc::channel(10);
+        let err = McpProcess::start(
+            "b".into(),
+            "pwd".into(),
+            vec![],
+            None,
+            Some(&missing),
+            &options,
+            log_tx,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(err.starts_with("Working directory"), "{}", err);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_shutdown_stops_what_the_server_started() {
+        let pid_file = std::env::temp_dir().join(format!("omm-tree-{}", uuid::Uuid::new_v4()));
+        let options = crate::models::LaunchOptions::default();
+        let alive = |pid: &str| {
+            std::fs::read_to_string(format!("/proc/{}/stat", pid))
+                .is_ok_and(|stat| !stat.contains(") Z "))
+        };
+
+        // Like npx: a wrapper whose real work happens in a child
+        let (log_tx, _log_rx) = mpsc::channel(10);
+        let script = format!(
+            "trap '' TERM; sleep 30 & echo $! > {}; while :; do sleep 0.1; done",
+            pid_file.display()
+        );
+        let wrapper = McpProcess::start(
+            "a".into(),
+            "sh".into(),
+            vec!["-c".into(), script],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+        let mut grandchild = String::new();
+        while grandchild.is_empty() {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            grandchild = std::fs::read_to_string(&pid_file)
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+        }
+        assert!(alive(&grandchild));
+
+        let grace = std::time::Duration::from_millis(200);
+        wrapper.shutdown(grace).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert!(!alive(&grandchild));
+        let _ = std::fs::remove_file(&pid_file);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stderr_tagged_with_tool_call() {
+        let options = crate::models::LaunchOptions::default();
+        // Prints a traceback while handling the first request, then answers it
+        let script = r#"read line; echo "Traceback" >&2; sleep 0.2; echo '{"jsonrpc":"2.0","id":1,"result":{"content":[]}}'; sleep 0.2; echo after >&2; sleep 1"#;
+        let (log_tx, mut log_rx) = mpsc::channel(10);
+        let proc = McpProcess::start(
+            "a".into(),
+            "sh".into(),
+            vec!["-c".into(), script.into()],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+
+        let result = proc.call_tool("fail".into(), json!({})).await.unwrap();
+        assert_eq!(result.request_id, Some(1));
+        let during = log_rx.recv().await.unwrap();
+        assert!(matches!(during.log, ProcessLog::Stderr(ref msg) if msg == "Traceback"));
+        assert_eq!(during.request_id, Some(1));
+        let after = log_rx.recv().await.unwrap();
+        assert_eq!(after.request_id, None);
+        let _ = proc.kill().await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cancel_tool_call() {
+        let options = crate::models::LaunchOptions::default();
+        // Never answers the call; echoes the cancellation to stderr
+        let script = r#"read line; read note; echo "$note" >&2; sleep 1"#;
+        let (log_tx, mut log_rx) = mpsc::channel(10);
+        let proc = Arc::new(
+            McpProcess::start(
+                "a".into(),
+                "sh".into(),
+                vec!["-c".into(), script.into()],
+                None,
+                None,
+                &options,
+                log_tx,
+            )
+            .await
+            .unwrap(),
+        );
+
+        let call = tokio::spawn({
+            let proc = proc.clone();
+            async move { proc.call_tool("hang".into(), json!({})).await }
+        });
+        while proc.in_flight_calls().is_empty() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(proc.in_flight_calls(), [1]);
+        proc.cancel_request(1, "Cancelled by the user")
+            .await
+            .unwrap();
+        assert_eq!(call.await.unwrap().unwrap_err(), CANCELLED);
+        assert!(proc.in_flight_calls().is_empty());
+        assert!(proc.rpc.pending_requests.lock().await.is_empty());
+
+        let note = log_rx.recv().await.unwrap();
+        let ProcessLog::Stderr(msg) = note.log else {
+            panic!("expected the notification on stderr");
+        };
+        assert!(msg.contains(r#""method":"notifications/cancelled""#));
+        assert!(msg.contains(r#""requestId":1"#));
+        // Finished requests can't be cancelled
+        assert!(proc.cancel_request(1, "again").await.is_err());
+        let _ = proc.kill().await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_server_requests() {
+        let options = crate::models::LaunchOptions::default();
+        // Waits for the go-ahead, then echoes each reply to stderr
+        let script = r#"read go
+echo '{"jsonrpc":"2.0","id":"p","method":"ping"}'; read r; echo "$r" >&2
+echo '{"jsonrpc":"2.0","id":7,"method":"completion/unknown"}'; read r; echo "$r" >&2
+echo '{"jsonrpc":"2.0","id":8,"method":"roots/list"}'; read r; echo "$r" >&2
+echo '{"jsonrpc":"2.0","id":1,"method":"sampling/createMessage","params":{"maxTokens":5}}'
+read r; echo "$r" >&2; sleep 1"#;
+        let (log_tx, mut log_rx) = mpsc::channel(10);
+        let proc = McpProcess::start(
+            "a".into(),
+            "sh".into(),
+            vec!["-c".into(), script.into()],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+        let mut requests = proc.server_requests.subscribe();
+        proc.stdin_tx.send("go".into()).await.unwrap();
+
+        let mut stderr = Vec::new();
+        for _ in 0..2 {
+            if let ProcessLog::Stderr(line) = log_rx.recv().await.unwrap().log {
+                stderr.push(serde_json::from_str::<Value>(&line).unwrap());
+            }
+        }
+        assert_eq!(
+            stderr[0],
+            json!({ "jsonrpc": "2.0", "id": "p", "result": {} })
+        );
+        assert_eq!(stderr[1]["id"], 7);
+        assert_eq!(stderr[1]["error"]["code"], RpcError::METHOD_NOT_FOUND);
+
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "roots/list");
+        let roots = roots_result(&[PathBuf::from("/home/me/My Project")]);
+        proc.reply(&request.id, Ok(roots)).await.unwrap();
+        let ProcessLog::Stderr(line) = log_rx.recv().await.unwrap().log else {
+            panic!("expected the reply on stderr");
+        };
+        assert_eq!(
+            serde_json::from_str::<Value>(&line).unwrap()["result"],
+            json!({ "roots": [{ "uri": "file:///home/me/My%20Project", "name": "My Project" }] })
+        );
+
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, "sampling/createMessage");
+        assert_eq!(request.params["maxTokens"], 5);
+        // Not mistaken for the reply to the manager's own request 1
+        assert!(proc.rpc.pending_requests.lock().await.is_empty());
+        let rejected = RpcError::new(RpcError::USER_REJECTED, "Rejected");
+        proc.reply(&request.id, Err(rejected)).await.unwrap();
+        let ProcessLog::Stderr(line) = log_rx.recv().await.unwrap().log else {
+            panic!("expected the reply on stderr");
+        };
+        assert_eq!(
+            serde_json::from_str::<Value>(&line).unwrap(),
+            json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -1, "message": "Rejected" } })
+        );
+        let _ = proc.kill().await;
+    }
+
+    #[test]
+    fn test_file_uri() {
+        assert_eq!(file_uri(Path::new("/srv/data")), "file:///srv/data");
+        assert_eq!(
+            file_uri(Path::new(r"C:\Users\me\a#b")),
+            "file:///C:/Users/me/a%23b"
+        );
+    }
+
+    #[test]
+    fn test_negotiate_protocol_version() {
+        let info = negotiate(json!({
+            "protocolVersion": "2025-03-26",
+            "capabilities": { "tools": { "listChanged": true } },
+            "serverInfo": { "name": "files", "version": "0.3.0" }
+        }))
+        .unwrap();
+        assert!(info.capabilities.tools.is_some());
+        assert!(info.capabilities.prompts.is_none());
+        assert_eq!(info.serverInfo.unwrap().name, "files");
+
+        let err = negotiate(json!({ "protocolVersion": "2023-01-01", "capabilities": {} }));
+        assert_eq!(
+            err.unwrap_err(),
+            "Server uses unsupported protocol version 2023-01-01"
+        );
+        assert!(negotiate(json!({ "capabilities": {} })).is_err());
+    }
+
+    #[test]
+    fn test_client_capabilities() {
+        let stdio = initialize_params(true);
+        assert!(stdio["capabilities"]["sampling"].is_object());
+        assert!(stdio["capabilities"]["elicitation"].is_object());
+        assert_eq!(stdio["capabilities"]["roots"]["listChanged"], true);
+        // Remote readers can't answer server requests
+        assert_eq!(initialize_params(false)["capabilities"], json!({}));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_initialize_handshake() {
+        let options = crate::models::LaunchOptions::default();
+        // Answers initialize, then echoes the next message to stderr
+        let script = r#"read line; echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2024-11-05","capabilities":{"tools":{}}}}'; read note; echo "$note" >&2; sleep 1"#;
+        let (log_tx, mut log_rx) = mpsc::channel(10);
+        let proc = McpProcess::start(
+            "a".into(),
+            "sh".into(),
+            vec!["-c".into(), script.into()],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+
+        let info = proc.initialize().await.unwrap();
+        assert_eq!(info.protocolVersion, "2024-11-05");
+        assert!(proc.rpc.server_info.get().is_some());
+        let note = log_rx.recv().await.unwrap();
+        let ProcessLog::Stderr(msg) = note.log else {
+            panic!("expected the notification on stderr");
+        };
+        assert!(msg.contains(r#""method":"notifications/initialized""#));
+        assert!(!msg.contains(r#""id""#));
+        let _ = proc.kill().await;
+    }
+
+    #[test]
+    fn test_log_message() {
+        let n = parse_notification(
+            br#"{"jsonrpc":"2.0","method":"notifications/message","params":{"level":"warning","logger":"db","data":"slow query"}}"#,
+        )
+        .unwrap();
+        let Some(ProcessLog::Message {
+            level,
+            logger,
+            text,
+        }) = log_message(&n)
+        else {
+            panic!("Expected a log message");
+        };
+        assert_eq!(level, LoggingLevel::Warning);
+        assert_eq!(logger.as_deref(), Some("db"));
+        assert_eq!(text, "slow query");
+
+        // Structured data is shown as JSON
+        let n = parse_notification(
+            br#"{"method":"notifications/message","params":{"level":"error","data":{"code":5}}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            log_message(&n),
+            Some(ProcessLog::Message { text, logger: None, .. }) if text == r#"{"code":5}"#
+        ));
+
+        let n = parse_notification(br#"{"method":"notifications/progress","params":{}}"#).unwrap();
+        assert!(log_message(&n).is_none());
+    }
+
+    #[test]
+    fn test_after_prompt() {
+        assert_eq!(
+            after_prompt(br#"Token: {"jsonrpc":"2.0","id":1}"#),
+            br#"{"jsonrpc":"2.0","id":1}"#
+        );
+        assert_eq!(after_prompt(b"no message here"), b"no message here");
+    }
+
+    #[test]
+    fn test_parse_notification() {
+        let n = parse_notification(
+            br#"{"jsonrpc":"2.0","method":"notifications/resources/updated","params":{"uri":"file:///a.txt"}}"#,
+        )
+        .unwrap();
+        assert_eq!(n.method, "notifications/resources/updated");
+        assert_eq!(n.params["uri"], "file:///a.txt");
+        assert_eq!(n.list_changed(), None);
+        let n =
+            parse_notification(br#"{"jsonrpc":"2.0","method":"notifications/tools/list_changed"}"#)
+                .unwrap();
+        assert_eq!(n.list_changed(), Some(ListKind::Tools));
+        // Responses and server-to-client requests aren't notifications
+        assert!(parse_notification(br#"{"jsonrpc":"2.0","id":1,"result":{}}"#).is_none());
+        assert!(parse_notification(br#"{"jsonrpc":"2.0","id":2,"method":"roots/list"}"#).is_none());
+        assert!(parse_notification(b"Listening on stdio").is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_pty_mode_handshake() {
+        let options = crate::models::LaunchOptions {
+            pty: true,
+            ..Default::default()
+        };
+        // Refuses to run on pipes
+        let script = r#"[ -t 0 ] || exit 1; read line; echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-06-18","capabilities":{}}}'; read note; sleep 1"#;
+        let (log_tx, _log_rx) = mpsc::channel(10);
+        let proc = McpProcess::start(
+            "a".into(),
+            "sh".into(),
+            vec!["-c".into(), script.into()],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+
+        let info = proc.initialize().await.unwrap();
+        assert_eq!(info.protocolVersion, "2025-06-18");
+        let _ = proc.kill().await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_pty_mode_answers_a_prompt_from_the_terminal() {
+        let options = crate::models::LaunchOptions {
+            pty: true,
+            ..Default::default()
+        };
+        // Asks for a token before speaking MCP
+        let script = r#"printf 'Token: '; read token; [ "$token" = secret ] || exit 1; read line; echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-06-18","capabilities":{}}}'; read note; sleep 1"#;
+        let (log_tx, _log_rx) = mpsc::channel(10);
+        let proc = McpProcess::start(
+            "a".into(),
+            "sh".into(),
+            vec!["-c".into(), script.into()],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+
+        let handler = McpHandler::Stdio(proc);
+        let terminal = handler.terminal().unwrap();
+        let mut output = terminal.output();
+        output.wait_for(|o| o.ends_with(b"Token: ")).await.unwrap();
+        terminal.send_keys(b"secret\n".to_vec()).await.unwrap();
+
+        handler.initialize().await.unwrap();
+        let _ = handler.kill().await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_ping_falls_back_to_tools_list() {
+        let options = crate::models::LaunchOptions::default();
+        // Rejects ping, answers tools/list, then goes quiet
+        let script = r#"id() { echo "$1" | sed 's/.*"id":\([0-9]*\).*/\1/'; }
+read r; echo "{\"jsonrpc\":\"2.0\",\"id\":$(id "$r"),\"error\":{\"code\":-32601,\"message\":\"Method not found\"}}"
+read r; echo "{\"jsonrpc\":\"2.0\",\"id\":$(id "$r"),\"result\":{\"tools\":[]}}"
+read r; sleep 2"#;
+        let (log_tx, _log_rx) = mpsc::channel(10);
+        let proc = McpProcess::start(
+            "a".into(),
+            "sh".into(),
+            vec!["-c".into(), script.into()],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+        let handler = McpHandler::Stdio(proc);
+
+        let timeout = std::time::Duration::from_secs(5);
+        handler.ping(timeout).await.unwrap();
+        let err = handler
+            .ping(std::time::Duration::from_millis(200))
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("No reply"), "{}", err);
+        assert!(is_method_not_found(r#"{"code":-32601,"message":"x"}"#));
+        assert!(!is_method_not_found("Request cancelled or process died"));
+        let _ = handler.kill().await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_raw_request() {
+        let options = crate::models::LaunchOptions::default();
+        let script = r#"id() { echo "$1" | sed 's/.*"id":\([0-9]*\).*/\1/'; }
+read r; echo "{\"jsonrpc\":\"2.0\",\"id\":$(id "$r"),\"result\":{\"state\":\"ok\"},\"trace\":\"t1\"}"
+read r; echo "{\"jsonrpc\":\"2.0\",\"id\":$(id "$r"),\"error\":{\"code\":-32601,\"message\":\"Method not found\"}}"
+read r"#;
+        let (log_tx, _log_rx) = mpsc::channel(10);
+        let proc = McpProcess::start(
+            "a".into(),
+            "sh".into(),
+            vec!["-c".into(), script.into()],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+        proc.set_request_timeout(std::time::Duration::from_millis(300));
+        let handler = McpHandler::Stdio(proc);
+
+        let exchange = handler
+            .send_raw_request("debug/state", Some(json!({"verbose": true})))
+            .await;
+        assert_eq!(
+            exchange.request,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "debug/state", "params": {"verbose": true}})
+        );
+        // Shown as the server sent it, fields the client ignores included
+        assert_eq!(
+            exchange.response,
+            Ok(json!({"jsonrpc": "2.0", "id": 1, "result": {"state": "ok"}, "trace": "t1"}))
+        );
+
+        let exchange = handler.send_raw_request("debug/other", None).await;
+        assert_eq!(exchange.request["params"], json!({}));
+        assert_eq!(exchange.response.unwrap()["error"]["code"], -32601);
+
+        let exchange = handler.send_raw_request("debug/quiet", None).await;
+        assert_eq!(
+            exchange.response,
+            Err("No reply to debug/quiet within 0.3s".to_string())
+        );
+        let _ = handler.kill().await;
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let options = crate::models::LaunchOptions::default();
+        let (log_tx, _log_rx) = mpsc::channel(10);
+        // Reads everything and never replies
+        let proc = McpProcess::start(
+            "a".into(),
+            "sh".into(),
+            vec!["-c".into(), "cat > /dev/null".into()],
+            None,
+            None,
+            &options,
+            log_tx,
+        )
+        .await
+        .unwrap();
+        proc.set_request_timeout(std::time::Duration::from_millis(100));
+
+        let err = proc.send_request("tools/list", None).await.unwrap_err();
+        assert_eq!(err, "No reply to tools/list within 0.1s");
+        assert!(proc.rpc.pending_requests.lock().await.is_empty());
+
+        // A per-request timeout overrides the server's
+        let started = std::time::Instant::now();
+        let err = proc
+            .send_request_with_timeout("ping", None, std::time::Duration::from_millis(300))
+            .await
+            .unwrap_err();
+        assert_eq!(err, "No reply to ping within 0.3s");
+        assert!(started.elapsed() >= std::time::Duration::from_millis(300));
+        assert!(proc.rpc.pending_requests.lock().await.is_empty());
+        let _ = proc.kill().await;
+    }
+
+    #[tokio::test]
+    async fn test_ws_reconnects_and_reinitializes() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        // Answers requests until the first tools/list, then drops the connection
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                while let Some(Ok(Message::Text(text))) = ws.next().await {
+                    let msg: Value = serde_json::from_str(&text).unwrap();
+                    let result = match msg["method"].as_str() {
+                        Some("initialize") => {
+                            json!({"protocolVersion": PROTOCOL_VERSION, "capabilities": {}})
+                        }
+                        Some("tools/list") => json!({"tools": []}),
+                        _ => continue,
+                    };
+                    let reply = json!({"jsonrpc": "2.0", "id": msg["id"], "result": result});
+                    ws.send(Message::text(reply.to_string())).await.unwrap();
+                    if msg["method"] == "tools/list" {
+                        break;
+                    }
+                }
+            }
+        });
+
+        let (log_tx, _log_rx) = mpsc::channel(100);
+        let client = McpWsClient::start(url, None, log_tx).await.unwrap();
+        client.initialize().await.unwrap();
+        assert!(client.list_tools().await.unwrap().is_empty());
+
+        let mut connected = client.connected.clone();
+        connected.wait_for(|c| !*c).await.unwrap();
+        assert!(client.list_tools().await.is_err());
+        connected.wait_for(|c| *c).await.unwrap();
+        assert!(client.list_tools().await.unwrap().is_empty());
+        client.close();
+    }
+
+    #[tokio::test]
+    async fn test_ws_tool_call_carries_request_id() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let msg: Value = serde_json::from_str(&text).unwrap();
+                let reply = json!({"jsonrpc": "2.0", "id": msg["id"], "result": {"content": []}});
+                ws.send(Message::text(reply.to_string())).await.unwrap();
+            }
+        });
+
+        let (log_tx, _log_rx) = mpsc::channel(100);
+        let client = McpWsClient::start(url, None, log_tx).await.unwrap();
+        let first = client.call_tool("a".into(), json!({})).await.unwrap();
+        let second = client.call_tool("a".into(), json!({})).await.unwrap();
+        assert_eq!(first.request_id, Some(1));
+        assert_eq!(second.request_id, Some(2));
+        client.close();
+    }
 }
@@ -0,0 +1,478 @@
+//! Importing the MCP servers a project folder configures for its editors:
+//! Claude Code's `.mcp.json`, VS Code's `.vscode/mcp.json`, Cursor's
+//! `.cursor/mcp.json` and OpenCode's `opencode.jsonc`.
+//!
+//! Imported servers are tagged `project:<folder name>`. A server the
+//! manager already has with the same config is only tagged; one whose
+//! name is taken by a different config is imported under
+//! `<name> (<folder name>)`.
+//!
+//! An imported folder is kept as a [`ProjectProfile`]: the servers with
+//! its tag make up the profile, and the project's config file can be
+//! regenerated from them whenever they change.
+
+use crate::editor_config::{direct_entry, entry_to_args, MigrationPlan, HUB_ENTRY_NAME};
+use crate::models::{CreateServerArgs, McpServer};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Project config files, by the editor that reads them.
+pub const PROJECT_CONFIG_FILES: [(&str, &str); 4] = [
+    ("Claude Code", ".mcp.json"),
+    ("VS Code", ".vscode/mcp.json"),
+    ("Cursor", ".cursor/mcp.json"),
+    ("OpenCode", "opencode.jsonc"),
+];
+
+/// Project config files a profile can generate; both use `mcpServers`.
+pub const PROFILE_CONFIG_FILES: [&str; 2] = [".mcp.json", ".cursor/mcp.json"];
+
+/// A project folder bound to the servers tagged with its project tag.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProjectProfile {
+    pub path: PathBuf,
+    /// One of [`PROFILE_CONFIG_FILES`], relative to `path`.
+    #[serde(default = "default_config_file")]
+    pub config_file: String,
+}
+
+fn default_config_file() -> String {
+    PROFILE_CONFIG_FILES[0].to_string()
+}
+
+impl ProjectProfile {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            config_file: default_config_file(),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        project_name(&self.path)
+    }
+
+    pub fn tag(&self) -> String {
+        project_tag(&self.path)
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        self.path.join(&self.config_file)
+    }
+
+    /// Servers in the profile: the unarchived ones with its tag.
+    pub fn servers<'a>(
+        &self,
+        servers: &'a [McpServer],
+        tags: &HashMap<String, Vec<String>>,
+    ) -> Vec<&'a McpServer> {
+        let tag = self.tag();
+        servers
+            .iter()
+            .filter(|s| !s.is_archived)
+            .filter(|s| tags.get(&s.id).is_some_and(|t| t.contains(&tag)))
+            .collect()
+    }
+}
+
+/// The edits that make `config` list exactly the profile's servers.
+///
+/// Entries of managed servers outside the profile are dropped; entries
+/// the manager doesn't know are left alone, so hand-added servers
+/// survive until they are imported.
+pub fn profile_plan(
+    config: &Value,
+    profile: &[&McpServer],
+    managed: &[McpServer],
+) -> MigrationPlan {
+    let wanted: Map<String, Value> = profile
+        .iter()
+        .map(|s| (s.name.clone(), direct_entry(s)))
+        .collect();
+    let existing = config.get("mcpServers").and_then(Value::as_object);
+    let current = |name: &str| existing.and_then(|m| m.get(name));
+    MigrationPlan {
+        remove: existing
+            .into_iter()
+            .flat_map(|m| m.keys())
+            .filter(|name| !wanted.contains_key(*name))
+            .filter(|name| {
+                name.as_str() == HUB_ENTRY_NAME || managed.iter().any(|s| &s.name == *name)
+            })
+            .cloned()
+            .collect(),
+        add: wanted
+            .into_iter()
+            .filter(|(name, entry)| current(name) != Some(entry))
+            .collect(),
+    }
+}
+
+/// A server found in one of a project's config files.
+#[derive(Debug, Clone)]
+pub struct ProjectServer {
+    pub editor: &'static str,
+    pub args: CreateServerArgs,
+}
+
+/// What a scan of a project folder found.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectScan {
+    pub servers: Vec<ProjectServer>,
+    /// Config files that were read.
+    pub files: Vec<PathBuf>,
+    /// Config files that couldn't be read, with why.
+    pub errors: Vec<String>,
+}
+
+/// How one project server is brought into the manager.
+#[derive(Debug, Clone)]
+pub enum ImportAction {
+    Create(CreateServerArgs),
+    /// Already managed with the same config; only gets the project tag.
+    Tag {
+        server_id: String,
+    },
+}
+
+/// Tag for servers imported from the project at `dir`.
+pub fn project_tag(dir: &Path) -> String {
+    format!("project:{}", project_name(dir))
+}
+
+fn project_name(dir: &Path) -> String {
+    dir.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| dir.display().to_string())
+}
+
+/// `text` with `//` and `/* */` comments and trailing commas removed, so
+/// JSONC parses as JSON. Strings are left alone.
+pub fn strip_jsonc(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+            }
+            ',' => {
+                // Dropped when only whitespace or comments come before the
+                // closing bracket; comments are already gone by then, so
+                // look ahead through whitespace only
+                let rest: String = chars.clone().take_while(|c| c.is_whitespace()).collect();
+                let next = chars.clone().nth(rest.chars().count());
+                if !matches!(next, Some('}' | ']')) {
+                    out.push(c);
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// An OpenCode `mcp` entry: the command and its arguments in one list,
+/// `environment` for env, and `remote` servers with a `url`.
+fn opencode_entry_to_args(name: &str, entry: &Value) -> CreateServerArgs {
+    let mut converted = serde_json::Map::new();
+    if let Some(url) = entry.get("url") {
+        converted.insert("url".into(), url.clone());
+    }
+    if let Some(mut command) = entry.get("command").and_then(Value::as_array).cloned() {
+        if !command.is_empty() {
+            converted.insert("command".into(), command.remove(0));
+            converted.insert("args".into(), Value::Array(command));
+        }
+    }
+    if let Some(env) = entry.get("environment") {
+        converted.insert("env".into(), env.clone());
+    }
+    entry_to_args(name, &Value::Object(converted))
+}
+
+/// Servers in one parsed config file. VS Code keeps them under
+/// `servers`, OpenCode under `mcp` and the others under `mcpServers`.
+pub fn servers_in(editor: &'static str, config: &Value) -> Vec<ProjectServer> {
+    let key = match editor {
+        "VS Code" => "servers",
+        "OpenCode" => "mcp",
+        _ => "mcpServers",
+    };
+    let Some(entries) = config.get(key).and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter(|(name, _)| name.as_str() != HUB_ENTRY_NAME)
+        .filter(|(_, entry)| entry.get("enabled") != Some(&Value::Bool(false)))
+        .map(|(name, entry)| ProjectServer {
+            editor,
+            args: if editor == "OpenCode" {
+                opencode_entry_to_args(name, entry)
+            } else {
+                entry_to_args(name, entry)
+            },
+        })
+        .filter(|s| s.args.command.is_some() || s.args.url.is_some())
+        .collect()
+}
+
+/// Read every project config file in `dir`. A name found in more than one
+/// file is taken from the first, in [`PROJECT_CONFIG_FILES`] order.
+pub fn scan(dir: &Path) -> ProjectScan {
+    let mut scan = ProjectScan::default();
+    for (editor, file) in PROJECT_CONFIG_FILES {
+        let path = dir.join(file);
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                scan.errors.push(format!("{}: {}", file, e));
+                continue;
+            }
+        };
+        let config: Value = match serde_json::from_str(&strip_jsonc(&text)) {
+            Ok(config) => config,
+            Err(e) => {
+                scan.errors.push(format!("{}: {}", file, e));
+                continue;
+            }
+        };
+        for server in servers_in(editor, &config) {
+            if !scan.servers.iter().any(|s| s.args.name == server.args.name) {
+                scan.servers.push(server);
+            }
+        }
+        scan.files.push(path);
+    }
+    scan
+}
+
+/// Whether `args` would create a server that runs the same as `server`.
+fn same_launch(args: &CreateServerArgs, server: &McpServer) -> bool {
+    args.command == server.command
+        && args.url == server.url
+        && args.args.clone().unwrap_or_default() == server.args.clone().unwrap_or_default()
+}
+
+/// How each of the project's servers is imported next to `existing`.
+pub fn plan(servers: &[ProjectServer], existing: &[McpServer], project: &str) -> Vec<ImportAction> {
+    let mut taken: Vec<String> = existing.iter().map(|s| s.name.clone()).collect();
+    servers
+        .iter()
+        .map(|server| {
+            let mut args = server.args.clone();
+            if let Some(same) = existing.iter().find(|s| same_launch(&args, s)) {
+                return ImportAction::Tag {
+                    server_id: same.id.clone(),
+                };
+            }
+            if taken.contains(&args.name) {
+                args.name = format!("{} ({})", args.name, project);
+            }
+            if args.description.is_none() {
+                args.description = Some(format!("From {} in {}", server.editor, project));
+            }
+            taken.push(args.name.clone());
+            ImportAction::Create(args)
+        })
+        .collect()
+}
+
+/// [`plan`] for the servers scanned from the project at `dir`.
+pub fn plan_for(dir: &Path, scan: &ProjectScan, existing: &[McpServer]) -> Vec<ImportAction> {
+    plan(&scan.servers, existing, &project_name(dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_strip_jsonc() {
+        let text = r#"{
+            // comment
+            "url": "http://example.com/a//b", /* block */
+            "list": [1, 2,],
+            "quote": "say \"hi\" // not a comment",
+        }"#;
+        let value: Value = serde_json::from_str(&strip_jsonc(text)).unwrap();
+        assert_eq!(value["url"], "http://example.com/a//b");
+        assert_eq!(value["list"], json!([1, 2]));
+        assert_eq!(value["quote"], "say \"hi\" // not a comment");
+    }
+
+    #[test]
+    fn test_servers_in_each_format() {
+        let vscode = json!({
+            "inputs": [],
+            "servers": {
+                "github": { "type": "http", "url": "https://api.example.com/mcp" },
+                "fs": { "type": "stdio", "command": "npx", "args": ["-y", "fs"] }
+            }
+        });
+        let found = servers_in("VS Code", &vscode);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].args.name, "fs");
+        assert_eq!(
+            found[1].args.url.as_deref(),
+            Some("https://api.example.com/mcp")
+        );
+
+        let opencode = json!({
+            "mcp": {
+                "db": {
+                    "type": "local",
+                    "command": ["uvx", "db-server", "--ro"],
+                    "environment": { "DB": "dev" }
+                },
+                "off": { "type": "local", "command": ["x"], "enabled": false }
+            }
+        });
+        let found = servers_in("OpenCode", &opencode);
+        assert_eq!(found.len(), 1);
+        let args = &found[0].args;
+        assert_eq!(args.command.as_deref(), Some("uvx"));
+        assert_eq!(args.args, Some(vec!["db-server".into(), "--ro".into()]));
+        assert_eq!(args.env.as_ref().unwrap()["DB"], "dev");
+
+        let claude = json!({ "mcpServers": { HUB_ENTRY_NAME: { "command": "hub" } } });
+        assert!(servers_in("Claude Code", &claude).is_empty());
+    }
+
+    #[test]
+    fn test_scan_folder() {
+        let dir = std::env::temp_dir().join(format!("omm-project-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join(".vscode")).unwrap();
+        std::fs::write(
+            dir.join(".mcp.json"),
+            r#"{ "mcpServers": { "fs": { "command": "npx", "args": ["fs"] } } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join(".vscode/mcp.json"),
+            r#"{ "servers": { "fs": { "command": "other" }, "git": { "command": "git-mcp" }, } }"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(dir.join(".cursor")).unwrap();
+        std::fs::write(dir.join(".cursor/mcp.json"), "{ not json").unwrap();
+
+        let scan = scan(&dir);
+        let names: Vec<&str> = scan.servers.iter().map(|s| s.args.name.as_str()).collect();
+        assert_eq!(names, ["fs", "git"]);
+        assert_eq!(scan.servers[0].args.command.as_deref(), Some("npx"));
+        assert_eq!(scan.files.len(), 2);
+        assert_eq!(scan.errors.len(), 1);
+        assert!(scan.errors[0].starts_with(".cursor/mcp.json"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_profile_servers_and_plan() {
+        let profile = ProjectProfile::new(PathBuf::from("/src/api"));
+        assert_eq!(profile.config_path(), Path::new("/src/api/.mcp.json"));
+        let mut archived = McpServer::test_stdio("3", "old").named("old");
+        archived.is_archived = true;
+        let managed = vec![
+            McpServer::test_stdio("1", "npx").named("fs"),
+            McpServer::test_stdio("2", "git-mcp").named("git"),
+            archived,
+        ];
+        let tags = HashMap::from([
+            ("1".to_string(), vec!["project:api".to_string()]),
+            ("3".to_string(), vec!["project:api".to_string()]),
+        ]);
+        let servers = profile.servers(&managed, &tags);
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].name, "fs");
+
+        let config = serde_json::json!({
+            "mcpServers": {
+                "fs": { "command": "npx-old" },
+                "git": { "command": "git-mcp" },
+                "mine": { "command": "by-hand" }
+            }
+        });
+        let plan = profile_plan(&config, &servers, &managed);
+        assert_eq!(plan.remove, ["git"]);
+        assert_eq!(
+            plan.add,
+            [("fs".to_string(), serde_json::json!({ "command": "npx" }))]
+        );
+
+        let synced = crate::editor_config::apply_migration(config, &plan);
+        assert!(profile_plan(&synced, &servers, &managed).is_empty());
+        assert_eq!(synced["mcpServers"]["mine"]["command"], "by-hand");
+    }
+
+    #[test]
+    fn test_plan() {
+        let existing = McpServer {
+            id: "1".into(),
+            name: "fs".into(),
+            server_type: "stdio".into(),
+            command: Some("npx".into()),
+            args: Some(vec!["fs".into()]),
+            is_active: true,
+            ..Default::default()
+        };
+        let found = |name: &str, command: &str| ProjectServer {
+            editor: "Claude Code",
+            args: entry_to_args(name, &json!({ "command": command, "args": ["fs"] })),
+        };
+        let actions = plan(
+            &[
+                found("files", "npx"),
+                found("fs", "uvx"),
+                found("git", "git"),
+            ],
+            &[existing],
+            "api",
+        );
+        assert!(matches!(&actions[0], ImportAction::Tag { server_id } if server_id == "1"));
+        let ImportAction::Create(renamed) = &actions[1] else {
+            panic!("expected a new server");
+        };
+        assert_eq!(renamed.name, "fs (api)");
+        assert_eq!(
+            renamed.description.as_deref(),
+            Some("From Claude Code in api")
+        );
+        assert!(matches!(&actions[2], ImportAction::Create(a) if a.name == "git"));
+        assert_eq!(project_tag(Path::new("/src/api")), "project:api");
+    }
+}
@@ -0,0 +1,239 @@
+//! Experimental pseudo-terminal launch mode, for servers that check
+//! `isatty` or wait on an interactive prompt and hang silently on pipes.
+//!
+//! The server's stdin and stdout are a terminal in raw mode, so JSON-RPC
+//! goes through unchanged: no echo, no line editing and no newline
+//! translation. Stderr stays a pipe. Only available on macOS and Linux.
+//!
+//! Everything the server prints to the terminal is also kept for the
+//! console's Terminal tab, which can type into it.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::process::Command;
+use tokio::sync::{mpsc, watch};
+
+/// Reads what the server prints and writes what it reads.
+pub type PtyIo = (tokio::fs::File, tokio::fs::File);
+
+/// How much terminal output is kept for display.
+const SCROLLBACK: usize = 256 * 1024;
+
+/// A server's terminal as seen from the Terminal tab.
+#[derive(Clone)]
+pub struct Terminal {
+    /// The latest output, up to `SCROLLBACK` bytes.
+    output: watch::Sender<Vec<u8>>,
+    /// Keystrokes, written to the terminal as they are.
+    input: mpsc::Sender<Vec<u8>>,
+}
+
+impl Terminal {
+    /// A terminal, and the receiver for what is typed into it.
+    pub fn new() -> (Self, mpsc::Receiver<Vec<u8>>) {
+        let (input, keys) = mpsc::channel(64);
+        let output = watch::Sender::new(Vec::new());
+        (Self { output, input }, keys)
+    }
+
+    /// Output so far, updated as the server prints more.
+    pub fn output(&self) -> watch::Receiver<Vec<u8>> {
+        self.output.subscribe()
+    }
+
+    pub async fn send_keys(&self, keys: Vec<u8>) -> Result<(), String> {
+        self.input
+            .send(keys)
+            .await
+            .map_err(|_| "Terminal is closed".to_string())
+    }
+
+    fn append(&self, bytes: &[u8]) {
+        self.output.send_modify(|output| {
+            output.extend_from_slice(bytes);
+            let excess = output.len().saturating_sub(SCROLLBACK);
+            output.drain(..excess);
+        });
+    }
+
+    /// Wrap the terminal's reader so what it reads is also kept here.
+    pub fn tee<R: AsyncRead + Unpin>(&self, reader: R) -> Tee<R> {
+        Tee {
+            reader,
+            terminal: self.clone(),
+        }
+    }
+}
+
+/// A reader that copies what passes through it to a `Terminal`.
+pub struct Tee<R> {
+    reader: R,
+    terminal: Terminal,
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for Tee<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let start = buf.filled().len();
+        let poll = Pin::new(&mut self.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = poll {
+            self.terminal.append(&buf.filled()[start..]);
+        }
+        poll
+    }
+}
+
+#[cfg(unix)]
+pub struct Pty {
+    /// Keeps the terminal open for as long as the server runs.
+    master: std::sync::Mutex<Box<dyn portable_pty::MasterPty + Send>>,
+    /// Our handle on the server's side, given away by `attach`.
+    slave: Option<std::fs::File>,
+}
+
+#[cfg(unix)]
+impl Pty {
+    pub fn open() -> Result<Self, String> {
+        use portable_pty::{native_pty_system, PtySize};
+        use std::os::fd::AsRawFd;
+
+        let pair = native_pty_system()
+            .openpty(PtySize::default())
+            .map_err(|e| format!("Could not open a terminal: {}", e))?;
+        let path = pair
+            .master
+            .tty_name()
+            .ok_or("Could not find the terminal device")?;
+        let slave = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        make_raw(slave.as_raw_fd())?;
+        Ok(Self {
+            master: std::sync::Mutex::new(pair.master),
+            slave: Some(slave),
+        })
+    }
+
+    /// Give `cmd` the terminal as its stdin, stdout and controlling
+    /// terminal.
+    pub fn attach(&mut self, cmd: &mut Command) -> Result<(), String> {
+        let slave = self.slave.take().ok_or("Terminal already in use")?;
+        let stdout = slave.try_clone().map_err(|e| e.to_string())?;
+        cmd.stdin(slave);
+        cmd.stdout(stdout);
+        // SAFETY: only async-signal-safe calls between fork and exec
+        unsafe {
+            cmd.pre_exec(|| {
+                if libc::setsid() == -1 || libc::ioctl(0, libc::TIOCSCTTY as _, 0) == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        Ok(())
+    }
+
+    /// Separate handles for reading and writing, so a read waiting for
+    /// output never holds up a write.
+    pub fn io(&self) -> Result<PtyIo, String> {
+        use std::os::fd::BorrowedFd;
+
+        let master = self.master.lock().unwrap();
+        let fd = master.as_raw_fd().ok_or("Terminal is closed")?;
+        let dup = || -> Result<tokio::fs::File, String> {
+            // SAFETY: the master stays open while we hold its lock
+            let fd = unsafe { BorrowedFd::borrow_raw(fd) }
+                .try_clone_to_owned()
+                .map_err(|e| e.to_string())?;
+            Ok(tokio::fs::File::from_std(std::fs::File::from(fd)))
+        };
+        Ok((dup()?, dup()?))
+    }
+}
+
+/// Turn off echo, line buffering and output processing on the terminal.
+#[cfg(unix)]
+fn make_raw(fd: std::os::fd::RawFd) -> Result<(), String> {
+    // SAFETY: `termios` is plain data filled in by tcgetattr
+    unsafe {
+        let mut termios: libc::termios = std::mem::zeroed();
+        if libc::tcgetattr(fd, &mut termios) != 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+        libc::cfmakeraw(&mut termios);
+        if libc::tcsetattr(fd, libc::TCSANOW, &termios) != 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub struct Pty;
+
+#[cfg(not(unix))]
+impl Pty {
+    pub fn open() -> Result<Self, String> {
+        Err("Terminal mode is only available on macOS and Linux".to_string())
+    }
+
+    pub fn attach(&mut self, _cmd: &mut Command) -> Result<(), String> {
+        unreachable!()
+    }
+
+    pub fn io(&self) -> Result<PtyIo, String> {
+        unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(unix)]
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    #[tokio::test]
+    async fn test_tee_keeps_recent_output() {
+        use tokio::io::AsyncReadExt;
+
+        let (terminal, _keys) = Terminal::new();
+        let mut output = terminal.output();
+        let mut reader = terminal.tee(&b"Enter token: "[..]);
+        let mut read = String::new();
+        reader.read_to_string(&mut read).await.unwrap();
+        assert_eq!(read, "Enter token: ");
+        assert!(output.has_changed().unwrap());
+        assert_eq!(*output.borrow_and_update(), b"Enter token: ");
+
+        terminal.append(&vec![b'x'; SCROLLBACK]);
+        let kept = output.borrow().clone();
+        assert_eq!(kept.len(), SCROLLBACK);
+        assert!(kept.iter().all(|&b| b == b'x'));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_server_sees_a_raw_terminal() {
+        let mut pty = Pty::open().unwrap();
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "[ -t 0 ] && echo tty; read line; echo \"got $line\""]);
+        pty.attach(&mut cmd).unwrap();
+        let mut child = cmd.spawn().unwrap();
+        drop(cmd);
+
+        let (reader, mut writer) = pty.io().unwrap();
+        let mut lines = BufReader::new(reader).lines();
+        assert_eq!(lines.next_line().await.unwrap().unwrap(), "tty");
+        writer.write_all(b"{\"id\":1}\n").await.unwrap();
+        writer.flush().await.unwrap();
+        // No echo of the input, no \r added to the output
+        assert_eq!(lines.next_line().await.unwrap().unwrap(), "got {\"id\":1}");
+        child.wait().await.unwrap();
+    }
+}
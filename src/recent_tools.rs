@@ -0,0 +1,149 @@
+//! Tools called from the console, ranked by how often and how recently
+//! they were used, for quick relaunch with the same arguments.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How many distinct tool calls are remembered.
+pub const MAX_RECENT_TOOLS: usize = 30;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecentTool {
+    pub server_id: String,
+    pub server_name: String,
+    pub tool: String,
+    /// Arguments of the last call, as JSON text.
+    pub args: String,
+    pub uses: u32,
+    /// RFC 3339 time of the last call.
+    pub last_used: String,
+}
+
+/// Note a call of `tool` on a server, bumping an existing entry or adding a
+/// new one, and forgetting the lowest-ranked entries beyond the limit.
+pub fn record(
+    list: &mut Vec<RecentTool>,
+    server_id: &str,
+    server_name: &str,
+    tool: &str,
+    args: &str,
+    now: DateTime<Utc>,
+) {
+    let last_used = now.to_rfc3339();
+    match list
+        .iter_mut()
+        .find(|t| t.server_id == server_id && t.tool == tool)
+    {
+        Some(entry) => {
+            entry.uses += 1;
+            entry.args = args.to_string();
+            entry.server_name = server_name.to_string();
+            entry.last_used = last_used;
+        }
+        None => list.push(RecentTool {
+            server_id: server_id.to_string(),
+            server_name: server_name.to_string(),
+            tool: tool.to_string(),
+            args: args.to_string(),
+            uses: 1,
+            last_used,
+        }),
+    }
+    if list.len() > MAX_RECENT_TOOLS {
+        *list = ranked(list, now);
+        list.truncate(MAX_RECENT_TOOLS);
+    }
+}
+
+/// Uses weighted by recency: a call today counts four times as much as
+/// one last month.
+fn score(entry: &RecentTool, now: DateTime<Utc>) -> f64 {
+    let age_days = DateTime::parse_from_rfc3339(&entry.last_used)
+        .map(|t| (now - t.with_timezone(&Utc)).num_hours() as f64 / 24.0)
+        .unwrap_or(f64::MAX);
+    let weight = match age_days {
+        d if d < 1.0 => 4.0,
+        d if d < 7.0 => 2.0,
+        d if d < 30.0 => 1.0,
+        _ => 0.5,
+    };
+    entry.uses as f64 * weight
+}
+
+/// Entries best first; ties go to the most recently used.
+pub fn ranked(list: &[RecentTool], now: DateTime<Utc>) -> Vec<RecentTool> {
+    let mut list = list.to_vec();
+    list.sort_by(|a, b| {
+        score(b, now)
+            .total_cmp(&score(a, now))
+            .then_with(|| b.last_used.cmp(&a.last_used))
+    });
+    list
+}
+
+/// Whether `query` appears in the tool or server name, ignoring case.
+pub fn matches(entry: &RecentTool, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    query.is_empty()
+        || entry.tool.to_lowercase().contains(&query)
+        || entry.server_name.to_lowercase().contains(&query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_record_bumps_existing_entry() {
+        let now = Utc::now();
+        let mut list = Vec::new();
+        record(&mut list, "s1", "github", "search", "{}", now);
+        record(&mut list, "s1", "github", "search", r#"{"q":"x"}"#, now);
+        record(&mut list, "s2", "fetch", "search", "{}", now);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].uses, 2);
+        assert_eq!(list[0].args, r#"{"q":"x"}"#);
+    }
+
+    #[test]
+    fn test_ranked_prefers_recent_and_frequent() {
+        let now = Utc::now();
+        let mut list = Vec::new();
+        // Used a lot, but a month ago
+        for _ in 0..5 {
+            record(&mut list, "s1", "a", "old", "{}", now - Duration::days(40));
+        }
+        // Used three times today
+        for _ in 0..3 {
+            record(&mut list, "s1", "a", "fresh", "{}", now);
+        }
+        record(&mut list, "s1", "a", "once", "{}", now - Duration::days(3));
+
+        let order: Vec<String> = ranked(&list, now).into_iter().map(|t| t.tool).collect();
+        assert_eq!(order, vec!["fresh", "old", "once"]);
+    }
+
+    #[test]
+    fn test_record_keeps_limit() {
+        let now = Utc::now();
+        let mut list = Vec::new();
+        record(&mut list, "s", "a", "favourite", "{}", now);
+        record(&mut list, "s", "a", "favourite", "{}", now);
+        for i in 0..MAX_RECENT_TOOLS {
+            record(&mut list, "s", "a", &format!("t{}", i), "{}", now);
+        }
+        assert_eq!(list.len(), MAX_RECENT_TOOLS);
+        assert!(list.iter().any(|t| t.tool == "favourite"));
+    }
+
+    #[test]
+    fn test_matches_tool_or_server() {
+        let mut list = Vec::new();
+        record(&mut list, "s1", "GitHub", "search_issues", "{}", Utc::now());
+        assert!(matches(&list[0], ""));
+        assert!(matches(&list[0], "issues"));
+        assert!(matches(&list[0], " github "));
+        assert!(!matches(&list[0], "fetch"));
+    }
+}
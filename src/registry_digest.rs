@@ -0,0 +1,215 @@
+//! Weekly snapshots of the registry cache and the "what's new" digest
+//! built by comparing the two most recent ones.
+
+use crate::models::RegistryItem;
+use chrono::{DateTime, Datelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How often a new snapshot is taken.
+pub const SNAPSHOT_INTERVAL_DAYS: i64 = 7;
+
+/// Snapshots kept in the database.
+pub const SNAPSHOTS_KEPT: usize = 12;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SnapshotEntry {
+    pub name: String,
+    pub version: Option<String>,
+    pub source: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RegistrySnapshot {
+    /// RFC 3339 time the snapshot was taken.
+    pub taken_at: String,
+    pub entries: Vec<SnapshotEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VersionBump {
+    pub name: String,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct RegistryDigest {
+    /// When the older and newer snapshots were taken.
+    pub since: String,
+    pub until: String,
+    pub added: Vec<SnapshotEntry>,
+    pub removed: Vec<SnapshotEntry>,
+    pub bumped: Vec<VersionBump>,
+}
+
+impl RegistryDigest {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.bumped.is_empty()
+    }
+
+    /// One-line summary for notifications.
+    pub fn summary(&self) -> String {
+        format!(
+            "Registry this week: {} new, {} removed, {} major updates",
+            self.added.len(),
+            self.removed.len(),
+            self.bumped.len()
+        )
+    }
+}
+
+pub fn snapshot_from(items: &[RegistryItem], taken_at: DateTime<Utc>) -> RegistrySnapshot {
+    let mut entries: Vec<SnapshotEntry> = items
+        .iter()
+        .map(|item| SnapshotEntry {
+            name: item.server.name.clone(),
+            version: item.server.version.clone(),
+            source: item.source.clone(),
+        })
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries.dedup_by(|a, b| a.name == b.name);
+    RegistrySnapshot {
+        taken_at: taken_at.to_rfc3339(),
+        entries,
+    }
+}
+
+/// A new major version, or a new minor version while still on 0.x.
+pub fn is_big_bump(from: &str, to: &str) -> bool {
+    match (
+        crate::updater::parse_version(from),
+        crate::updater::parse_version(to),
+    ) {
+        (Some((f_major, f_minor, _)), Some((t_major, t_minor, _))) => {
+            t_major > f_major || (t_major == 0 && f_major == 0 && t_minor > f_minor)
+        }
+        _ => false,
+    }
+}
+
+pub fn diff(older: &RegistrySnapshot, newer: &RegistrySnapshot) -> RegistryDigest {
+    let old: BTreeMap<&str, &SnapshotEntry> =
+        older.entries.iter().map(|e| (e.name.as_str(), e)).collect();
+    let new: BTreeMap<&str, &SnapshotEntry> =
+        newer.entries.iter().map(|e| (e.name.as_str(), e)).collect();
+
+    let added = new
+        .iter()
+        .filter(|(name, _)| !old.contains_key(*name))
+        .map(|(_, e)| (*e).clone())
+        .collect();
+    let removed = old
+        .iter()
+        .filter(|(name, _)| !new.contains_key(*name))
+        .map(|(_, e)| (*e).clone())
+        .collect();
+    let bumped = new
+        .iter()
+        .filter_map(|(name, e)| {
+            let from = old.get(name)?.version.as_deref()?;
+            let to = e.version.as_deref()?;
+            is_big_bump(from, to).then(|| VersionBump {
+                name: name.to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+            })
+        })
+        .collect();
+
+    RegistryDigest {
+        since: older.taken_at.clone(),
+        until: newer.taken_at.clone(),
+        added,
+        removed,
+        bumped,
+    }
+}
+
+/// Whether a week has passed since the last snapshot (or there is none).
+pub fn snapshot_due(last_taken_at: Option<&str>, now: DateTime<Utc>) -> bool {
+    last_taken_at
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| now.signed_duration_since(t).num_days() >= SNAPSHOT_INTERVAL_DAYS)
+        .unwrap_or(true)
+}
+
+/// The digest notification goes out on Mondays.
+pub fn is_digest_day(now: DateTime<chrono::Local>) -> bool {
+    now.weekday() == Weekday::Mon
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, version: Option<&str>) -> SnapshotEntry {
+        SnapshotEntry {
+            name: name.to_string(),
+            version: version.map(str::to_string),
+            source: "official".to_string(),
+        }
+    }
+
+    fn snapshot(taken_at: &str, entries: Vec<SnapshotEntry>) -> RegistrySnapshot {
+        RegistrySnapshot {
+            taken_at: taken_at.to_string(),
+            entries,
+        }
+    }
+
+    #[test]
+    fn test_is_big_bump() {
+        assert!(is_big_bump("1.4.2", "2.0.0"));
+        assert!(is_big_bump("0.3.0", "0.4.0"));
+        assert!(!is_big_bump("1.4.2", "1.9.0"));
+        assert!(!is_big_bump("0.3.0", "0.3.9"));
+        assert!(!is_big_bump("latest", "2.0.0"));
+    }
+
+    #[test]
+    fn test_diff() {
+        let older = snapshot(
+            "2026-01-05T00:00:00+00:00",
+            vec![
+                entry("github", Some("1.0.0")),
+                entry("gone", None),
+                entry("slack", Some("0.2.0")),
+            ],
+        );
+        let newer = snapshot(
+            "2026-01-12T00:00:00+00:00",
+            vec![
+                entry("brand-new", Some("0.1.0")),
+                entry("github", Some("2.1.0")),
+                entry("slack", Some("0.2.5")),
+            ],
+        );
+        let digest = diff(&older, &newer);
+        assert_eq!(digest.added, vec![entry("brand-new", Some("0.1.0"))]);
+        assert_eq!(digest.removed, vec![entry("gone", None)]);
+        assert_eq!(
+            digest.bumped,
+            vec![VersionBump {
+                name: "github".to_string(),
+                from: "1.0.0".to_string(),
+                to: "2.1.0".to_string(),
+            }]
+        );
+        assert_eq!(
+            digest.summary(),
+            "Registry this week: 1 new, 1 removed, 1 major updates"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_due() {
+        let now = DateTime::parse_from_rfc3339("2026-01-12T10:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert!(snapshot_due(None, now));
+        assert!(snapshot_due(Some("2026-01-05T09:00:00+00:00"), now));
+        assert!(!snapshot_due(Some("2026-01-08T09:00:00+00:00"), now));
+    }
+}
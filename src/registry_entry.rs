@@ -0,0 +1,351 @@
+//! Turning a locally configured server into an entry for the project's
+//! `registry.json`, for authors who want their server listed.
+//!
+//! Environment values are never copied: each key becomes a placeholder in
+//! `env_template` and an input step in the install wizard, for the author
+//! to reword before submitting.
+
+use crate::models::{
+    CreateServerArgs, RegistryInstallConfig, RegistryItem, RegistryServer, WizardAction, WizardStep,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+
+/// Where registry entries are submitted: GitHub's editor for the file,
+/// which offers to fork and open a pull request.
+pub fn contribution_url() -> String {
+    format!(
+        "{}/edit/main/registry.json",
+        env!("CARGO_PKG_REPOSITORY").trim_end_matches('/')
+    )
+}
+
+/// JSON Schema for one `registry.json` entry.
+pub fn registry_schema() -> Value {
+    let string = json!({"type": "string"});
+    let text = json!({"type": ["string", "object"]});
+    json!({
+        "type": "object",
+        "required": ["server", "install_config"],
+        "additionalProperties": false,
+        "properties": {
+            "server": {
+                "type": "object",
+                "required": ["name", "description"],
+                "additionalProperties": false,
+                "properties": {
+                    "name": string,
+                    "description": string,
+                    "homepage": string,
+                    "bugs": string,
+                    "version": string,
+                    "category": string
+                }
+            },
+            "install_config": {
+                "type": "object",
+                "required": ["command", "args"],
+                "additionalProperties": false,
+                "properties": {
+                    "command": string,
+                    "args": {"type": "array", "items": string},
+                    "env_template": {"type": "object"},
+                    "wizard": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["title", "description", "action"],
+                            "additionalProperties": false,
+                            "properties": {
+                                "title": text,
+                                "description": text,
+                                "action": {
+                                    "type": "object",
+                                    "required": ["type"],
+                                    "properties": {
+                                        "type": {"enum": ["link", "input", "message"]},
+                                        "url": string,
+                                        "key": string,
+                                        "label": text,
+                                        "placeholder": text,
+                                        "text": text
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "topics": {"type": "array", "items": string}
+        }
+    })
+}
+
+/// A registry entry for the server described by `args`. Only stdio
+/// servers can be listed; the registry installs by command.
+pub fn entry(args: &CreateServerArgs) -> Result<RegistryItem, String> {
+    if args.server_type != "stdio" {
+        return Err("Only stdio servers can be added to the registry".to_string());
+    }
+    let command = args
+        .command
+        .clone()
+        .filter(|c| !c.trim().is_empty())
+        .ok_or("No command specified")?;
+
+    let mut keys: Vec<String> = args.env.clone().unwrap_or_default().into_keys().collect();
+    keys.sort();
+    let env_template = keys
+        .iter()
+        .map(|key| (key.clone(), format!("YOUR_{}_HERE", key)))
+        .collect::<HashMap<_, _>>();
+    let wizard: Vec<WizardStep> = keys
+        .iter()
+        .map(|key| WizardStep {
+            title: key.as_str().into(),
+            description: format!("Enter your {}.", key).as_str().into(),
+            action: WizardAction::Input {
+                key: key.clone(),
+                label: key.as_str().into(),
+                placeholder: None,
+            },
+        })
+        .collect();
+
+    Ok(RegistryItem {
+        server: RegistryServer {
+            name: args.name.trim().to_string(),
+            description: args.description.as_deref().map(|d| d.trim().to_string()),
+            homepage: None,
+            bugs: None,
+            version: Some("0.1.0".to_string()),
+            category: None,
+        },
+        install_config: Some(RegistryInstallConfig {
+            command,
+            args: args.args.clone().unwrap_or_default(),
+            env_template: (!keys.is_empty()).then_some(env_template),
+            wizard: (!keys.is_empty()).then_some(wizard),
+        }),
+        source: "community".to_string(),
+        stars: 0,
+        topics: Vec::new(),
+    })
+}
+
+/// `item` laid out like the entries in `registry.json`: fields in their
+/// usual order, empty ones left out, indented by four spaces.
+pub fn to_json(item: &RegistryItem) -> String {
+    #[derive(Serialize)]
+    struct Entry<'a> {
+        server: Server<'a>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        install_config: Option<Install<'a>>,
+        #[serde(skip_serializing_if = "<[String]>::is_empty")]
+        topics: &'a [String],
+    }
+    #[derive(Serialize)]
+    struct Server<'a> {
+        name: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        homepage: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        bugs: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        version: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        category: Option<&'a str>,
+    }
+    #[derive(Serialize)]
+    struct Install<'a> {
+        command: &'a str,
+        args: &'a [String],
+        #[serde(skip_serializing_if = "Option::is_none")]
+        env_template: Option<BTreeMap<&'a String, &'a String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        wizard: Option<&'a [WizardStep]>,
+    }
+
+    let server = &item.server;
+    let entry = Entry {
+        server: Server {
+            name: &server.name,
+            description: server.description.as_deref(),
+            homepage: server.homepage.as_deref(),
+            bugs: server.bugs.as_deref(),
+            version: server.version.as_deref(),
+            category: server.category.as_deref(),
+        },
+        install_config: item.install_config.as_ref().map(|c| Install {
+            command: &c.command,
+            args: &c.args,
+            env_template: c.env_template.as_ref().map(|t| t.iter().collect()),
+            wizard: c.wizard.as_deref(),
+        }),
+        topics: &item.topics,
+    };
+    let mut out = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+    match entry.serialize(&mut serializer) {
+        Ok(()) => String::from_utf8(out).unwrap_or_default(),
+        Err(_) => String::new(),
+    }
+}
+
+/// Problems with an entry's content that would stop it being accepted.
+/// Its shape is checked against [`registry_schema`] by the editor.
+pub fn check(entry: &Value) -> Vec<String> {
+    let item: RegistryItem = match serde_json::from_value(entry.clone()) {
+        Ok(item) => item,
+        Err(e) => return vec![e.to_string()],
+    };
+    let mut problems = Vec::new();
+
+    let name = item.server.name.trim();
+    if name.is_empty() {
+        problems.push("The server needs a name".to_string());
+    }
+    if item
+        .server
+        .description
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .is_empty()
+    {
+        problems.push("Add a description so people know what the server does".to_string());
+    }
+    if crate::db::official_registry()
+        .iter()
+        .any(|existing| existing.server.name.eq_ignore_ascii_case(name))
+    {
+        problems.push(format!("{} is already in the registry", name));
+    }
+
+    if let Some(install) = &item.install_config {
+        let parts = std::iter::once(&install.command).chain(&install.args);
+        for part in parts.filter(|p| is_local_path(p)) {
+            problems.push(format!(
+                "{} is a path on this machine; point at a published package instead",
+                part
+            ));
+        }
+        let template = install.env_template.clone().unwrap_or_default();
+        for step in install.wizard.iter().flatten() {
+            if let WizardAction::Input { key, .. } = &step.action {
+                if !template.contains_key(key) {
+                    problems.push(format!(
+                        "The wizard asks for {}, which isn't in env_template",
+                        key
+                    ));
+                }
+            }
+        }
+    }
+    problems
+}
+
+/// Whether `part` only makes sense on the author's machine.
+fn is_local_path(part: &str) -> bool {
+    let bytes = part.as_bytes();
+    part.starts_with('/')
+        || part.starts_with('~')
+        || part.starts_with("./")
+        || part.starts_with("..")
+        || (bytes.len() > 2
+            && bytes[0].is_ascii_alphabetic()
+            && bytes[1] == b':'
+            && matches!(bytes[2], b'\\' | b'/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn args(command: &str, args: &[&str], env: &[(&str, &str)]) -> CreateServerArgs {
+        CreateServerArgs {
+            name: "Weather".to_string(),
+            server_type: "stdio".to_string(),
+            command: Some(command.to_string()),
+            args: Some(args.iter().map(|a| a.to_string()).collect()),
+            env: Some(
+                env.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect::<HashMap<_, _>>(),
+            ),
+            description: Some("Forecasts by city".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn entry_json(args: &CreateServerArgs) -> Value {
+        serde_json::from_str(&to_json(&entry(args).unwrap())).unwrap()
+    }
+
+    #[test]
+    fn test_entry_hides_env_values() {
+        let item = entry(&args(
+            "npx",
+            &["-y", "weather-mcp"],
+            &[("WEATHER_API_KEY", "sk-secret")],
+        ))
+        .unwrap();
+        let text = to_json(&item);
+        assert!(!text.contains("sk-secret"));
+        // Laid out like registry.json, without empty fields
+        assert!(text.starts_with("{\n    \"server\": {\n        \"name\": \"Weather\""));
+        assert!(!text.contains("homepage") && !text.contains("source"));
+
+        let value: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(
+            value["install_config"]["env_template"]["WEATHER_API_KEY"],
+            "YOUR_WEATHER_API_KEY_HERE"
+        );
+        assert_eq!(
+            value["install_config"]["wizard"][0]["action"]["key"],
+            "WEATHER_API_KEY"
+        );
+        assert_eq!(check(&value), Vec::<String>::new());
+        let parsed: RegistryItem = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.install_config, item.install_config);
+    }
+
+    #[test]
+    fn test_entry_rejects_remote() {
+        let mut remote = args("npx", &[], &[]);
+        remote.server_type = "sse".to_string();
+        assert!(entry(&remote).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let local = entry_json(&args("node", &["/home/me/weather/index.js"], &[]));
+        assert_eq!(
+            check(&local),
+            vec!["/home/me/weather/index.js is a path on this machine; point at a published package instead"]
+        );
+
+        let mut taken = entry_json(&args("npx", &["-y", "x"], &[]));
+        taken["server"]["name"] = json!("memory");
+        assert_eq!(check(&taken), vec!["memory is already in the registry"]);
+
+        let mut stray = entry_json(&args("npx", &["-y", "x"], &[("KEY", "v")]));
+        stray["install_config"]["wizard"][0]["action"]["key"] = json!("OTHER");
+        assert_eq!(
+            check(&stray),
+            vec!["The wizard asks for OTHER, which isn't in env_template"]
+        );
+
+        assert_eq!(check(&json!({"server": {}})), vec!["missing field `name`"]);
+    }
+
+    #[test]
+    fn test_contribution_url() {
+        assert!(contribution_url().ends_with("/edit/main/registry.json"));
+    }
+}
@@ -0,0 +1,198 @@
+//! Checking registry entries before the Explorer shows or caches them.
+//!
+//! The bundled `registry.json` is held to the registry schema, entry by
+//! entry, so a mistake in one entry drops only that entry and is reported
+//! with its location. Items found online (GitHub, npm, PyPI) are built by
+//! the app rather than written by hand, so they only get the checks that
+//! keep a bad item from misleading or breaking an install.
+
+use crate::models::{RegistryItem, WizardAction};
+use serde_json::Value;
+
+/// Entries of a `registry.json` that pass the schema, and a message for
+/// each problem with the rest, e.g. `$[3].install_config.args: expected array`.
+pub fn parse_registry(text: &str) -> (Vec<RegistryItem>, Vec<String>) {
+    let value: Value = match serde_json::from_str(text) {
+        Ok(value) => value,
+        Err(e) => return (Vec::new(), vec![format!("Invalid JSON: {}", e)]),
+    };
+    let Some(entries) = value.as_array() else {
+        return (Vec::new(), vec!["$: expected array".to_string()]);
+    };
+
+    let schema = crate::registry_entry::registry_schema();
+    let mut items: Vec<RegistryItem> = Vec::new();
+    let mut problems = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let at = format!("$[{}]", index);
+        let errors = crate::json_schema::validate_against_schema(entry, &schema);
+        if !errors.is_empty() {
+            problems.extend(errors.iter().map(|e| e.replacen('$', &at, 1)));
+            continue;
+        }
+        let item: RegistryItem = match serde_json::from_value(entry.clone()) {
+            Ok(item) => item,
+            Err(e) => {
+                problems.push(format!("{}: {}", at, e));
+                continue;
+            }
+        };
+        if let Err(e) = validate(&item) {
+            problems.push(format!("{}: {}", at, e));
+        } else if items.iter().any(|i| i.server.name == item.server.name) {
+            problems.push(format!("{}: {} is listed twice", at, item.server.name));
+        } else {
+            items.push(item);
+        }
+    }
+    (items, problems)
+}
+
+/// Whether `item` is safe to show and install: a name, a runnable
+/// command, web links, and environment variable names that are valid.
+pub fn validate(item: &RegistryItem) -> Result<(), String> {
+    let name = &item.server.name;
+    if name.trim().is_empty() {
+        return Err("server.name is empty".to_string());
+    }
+    if name.chars().any(char::is_control) {
+        return Err("server.name contains control characters".to_string());
+    }
+    for (field, url) in [
+        ("homepage", &item.server.homepage),
+        ("bugs", &item.server.bugs),
+    ] {
+        if let Some(url) = url {
+            if !is_web_url(url) {
+                return Err(format!("server.{} is not a web address: {}", field, url));
+            }
+        }
+    }
+
+    let Some(install) = &item.install_config else {
+        return Ok(());
+    };
+    if install.command.trim().is_empty() {
+        return Err("install_config.command is empty".to_string());
+    }
+    let parts = std::iter::once(&install.command).chain(&install.args);
+    if parts.into_iter().any(|p| p.chars().any(char::is_control)) {
+        return Err("install_config has control characters in its command line".to_string());
+    }
+    for key in install.env_template.iter().flat_map(|t| t.keys()) {
+        if !is_env_name(key) {
+            return Err(format!(
+                "install_config.env_template: {} is not a variable name",
+                key
+            ));
+        }
+    }
+    for (index, step) in install.wizard.iter().flatten().enumerate() {
+        match &step.action {
+            WizardAction::Link { url, .. } if !is_web_url(url) => {
+                return Err(format!(
+                    "install_config.wizard[{}]: {} is not a web address",
+                    index, url
+                ));
+            }
+            WizardAction::Input { key, .. } if !is_env_name(key) => {
+                return Err(format!(
+                    "install_config.wizard[{}]: {} is not a variable name",
+                    index, key
+                ));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// The items of `items` that pass [`validate`]. Each one left out is
+/// logged, naming where it came from.
+pub fn keep_valid(items: Vec<RegistryItem>, source: &str) -> Vec<RegistryItem> {
+    items
+        .into_iter()
+        .filter(|item| match validate(item) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!("Skipping {} from {}: {}", item.server.name, source, e);
+                false
+            }
+        })
+        .collect()
+}
+
+fn is_web_url(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+fn is_env_name(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bundled_registry_is_valid() {
+        let (items, problems) = parse_registry(include_str!("../registry.json"));
+        assert_eq!(problems, Vec::<String>::new());
+        assert!(!items.is_empty());
+    }
+
+    #[test]
+    fn test_bad_entries_are_dropped_with_their_location() {
+        let text = json!([
+            {"server": {"name": "Good", "description": "Fine"},
+             "install_config": {"command": "npx", "args": ["-y", "good"]}},
+            {"server": {"name": "Bad args", "description": "Args aren't a list"},
+             "install_config": {"command": "npx", "args": "-y bad"}},
+            {"server": {"name": "Bad key", "description": "Not a variable"},
+             "install_config": {"command": "npx", "args": [], "env_template": {"API KEY": "x"}}},
+            {"server": {"name": "Good", "description": "Again"},
+             "install_config": {"command": "npx", "args": []}}
+        ])
+        .to_string();
+        let (items, problems) = parse_registry(&text);
+        assert_eq!(items.len(), 1);
+        assert_eq!(
+            problems,
+            vec![
+                "$[1].install_config.args: expected array",
+                "$[2]: install_config.env_template: API KEY is not a variable name",
+                "$[3]: Good is listed twice",
+            ]
+        );
+
+        let (items, problems) = parse_registry("[{\"server\": ");
+        assert!(items.is_empty());
+        assert!(problems[0].starts_with("Invalid JSON: EOF while parsing"));
+        assert!(problems[0].contains("line 1"));
+    }
+
+    #[test]
+    fn test_keep_valid() {
+        let mut item: RegistryItem = serde_json::from_value(json!({
+            "server": {"name": "repo", "description": null, "homepage": "https://github.com/a/repo"},
+            "install_config": {"command": "npx", "args": ["-y", "repo"]}
+        }))
+        .unwrap();
+        assert!(validate(&item).is_ok());
+
+        let mut link = item.clone();
+        link.server.homepage = Some("javascript:alert(1)".to_string());
+        item.install_config
+            .as_mut()
+            .unwrap()
+            .args
+            .push("a\nb".to_string());
+        let kept = keep_valid(vec![item, link], "community");
+        assert!(kept.is_empty());
+    }
+}
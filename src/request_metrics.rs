@@ -0,0 +1,153 @@
+//! Rolling per-server request outcomes, and error budget alerts on them.
+//!
+//! Each routed tool call is recorded as a success or failure. A server is
+//! over its error budget when, within the configured window, it has had
+//! enough calls to judge and too large a share of them failed.
+
+use crate::models::ErrorBudgetSettings;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Failed and total calls within a window.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ErrorRate {
+    pub failed: usize,
+    pub total: usize,
+}
+
+impl ErrorRate {
+    pub fn percent(&self) -> u32 {
+        (self.failed * 100).checked_div(self.total).unwrap_or(0) as u32
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RequestMetrics {
+    /// When each recent call finished, and whether it succeeded.
+    outcomes: HashMap<String, VecDeque<(Instant, bool)>>,
+    /// Servers currently over budget, so each spike alerts once.
+    over_budget: HashSet<String>,
+}
+
+impl RequestMetrics {
+    /// Record a finished call, forgetting calls older than `window`.
+    pub fn record(&mut self, server_id: &str, ok: bool, now: Instant, window: Duration) {
+        let outcomes = self.outcomes.entry(server_id.to_string()).or_default();
+        outcomes.push_back((now, ok));
+        while let Some(&(at, _)) = outcomes.front() {
+            if now.duration_since(at) <= window {
+                break;
+            }
+            outcomes.pop_front();
+        }
+    }
+
+    pub fn error_rate(&self, server_id: &str, now: Instant, window: Duration) -> ErrorRate {
+        let Some(outcomes) = self.outcomes.get(server_id) else {
+            return ErrorRate::default();
+        };
+        let recent = outcomes
+            .iter()
+            .filter(|(at, _)| now.duration_since(*at) <= window);
+        ErrorRate {
+            failed: recent.clone().filter(|(_, ok)| !ok).count(),
+            total: recent.count(),
+        }
+    }
+
+    /// Re-evaluate a server against the budget. Returns its error rate if
+    /// it has just gone over, i.e. when to alert.
+    pub fn check_budget(
+        &mut self,
+        server_id: &str,
+        settings: &ErrorBudgetSettings,
+        now: Instant,
+    ) -> Option<ErrorRate> {
+        let rate = self.error_rate(server_id, now, settings.window());
+        let over = settings.enabled
+            && rate.total >= settings.min_requests as usize
+            && rate.percent() >= settings.threshold_percent;
+        if !over {
+            self.over_budget.remove(server_id);
+            return None;
+        }
+        self.over_budget
+            .insert(server_id.to_string())
+            .then_some(rate)
+    }
+
+    pub fn is_over_budget(&self, server_id: &str) -> bool {
+        self.over_budget.contains(server_id)
+    }
+
+    pub fn forget(&mut self, server_id: &str) {
+        self.outcomes.remove(server_id);
+        self.over_budget.remove(server_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> ErrorBudgetSettings {
+        ErrorBudgetSettings {
+            enabled: true,
+            threshold_percent: 50,
+            window_secs: 60,
+            min_requests: 4,
+        }
+    }
+
+    #[test]
+    fn test_alerts_once_per_spike() {
+        let settings = settings();
+        let mut metrics = RequestMetrics::default();
+        let start = Instant::now();
+        for ok in [true, false, false] {
+            metrics.record("a", ok, start, settings.window());
+            // Too few calls to judge yet
+            assert_eq!(metrics.check_budget("a", &settings, start), None);
+        }
+
+        metrics.record("a", false, start, settings.window());
+        assert_eq!(
+            metrics.check_budget("a", &settings, start),
+            Some(ErrorRate {
+                failed: 3,
+                total: 4
+            })
+        );
+        assert!(metrics.is_over_budget("a"));
+        metrics.record("a", false, start, settings.window());
+        assert_eq!(metrics.check_budget("a", &settings, start), None);
+        assert!(metrics.is_over_budget("a"));
+
+        // The failures age out of the window
+        let later = start + Duration::from_secs(61);
+        for _ in 0..4 {
+            metrics.record("a", true, later, settings.window());
+        }
+        assert_eq!(metrics.check_budget("a", &settings, later), None);
+        assert!(!metrics.is_over_budget("a"));
+        assert_eq!(metrics.error_rate("a", later, settings.window()).total, 4);
+    }
+
+    #[test]
+    fn test_disabled_budget_never_alerts() {
+        let settings = ErrorBudgetSettings {
+            enabled: false,
+            ..settings()
+        };
+        let mut metrics = RequestMetrics::default();
+        let now = Instant::now();
+        for _ in 0..10 {
+            metrics.record("a", false, now, settings.window());
+        }
+        assert_eq!(metrics.check_budget("a", &settings, now), None);
+        assert_eq!(
+            metrics.error_rate("a", now, settings.window()).percent(),
+            100
+        );
+    }
+}
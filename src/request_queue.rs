@@ -0,0 +1,146 @@
+//! Per-server limits on concurrent tool calls.
+//!
+//! Single-threaded servers (many Python ones) fall over when an editor
+//! fires a burst of calls at once. With a limit set, calls beyond it wait
+//! for a free slot in arrival order; tokio's semaphore is fair, so the
+//! queue is FIFO.
+
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QueueStats {
+    pub running: usize,
+    pub waiting: usize,
+    /// Most calls ever waiting at once.
+    pub peak_waiting: usize,
+    /// Calls that had to wait for a slot.
+    pub queued_total: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct RequestQueue {
+    limit: Option<usize>,
+    permits: Option<Arc<Semaphore>>,
+    stats: QueueStats,
+}
+
+impl RequestQueue {
+    pub fn new(limit: Option<usize>) -> Self {
+        let mut queue = Self::default();
+        queue.set_limit(limit);
+        queue
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Change the limit, keeping the stats. Calls already holding or
+    /// waiting for a slot finish under the old limit.
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        let limit = limit.filter(|n| *n > 0);
+        if limit != self.limit {
+            self.limit = limit;
+            self.permits = limit.map(|n| Arc::new(Semaphore::new(n)));
+        }
+    }
+
+    pub fn stats(&self) -> QueueStats {
+        self.stats
+    }
+
+    /// Count a new call as waiting. Returns the semaphore to take a slot
+    /// from, or None when the server is unlimited.
+    pub fn enqueue(&mut self) -> Option<Arc<Semaphore>> {
+        self.stats.waiting += 1;
+        self.stats.peak_waiting = self.stats.peak_waiting.max(self.stats.waiting);
+        self.permits.clone()
+    }
+
+    /// A waiting call got its slot.
+    pub fn started(&mut self, waited: bool) {
+        self.stats.waiting = self.stats.waiting.saturating_sub(1);
+        self.stats.running += 1;
+        if waited {
+            self.stats.queued_total += 1;
+        }
+    }
+
+    pub fn finished(&mut self) {
+        self.stats.running = self.stats.running.saturating_sub(1);
+    }
+}
+
+/// Wait for a slot on `permits`. Returns the permit (released on drop)
+/// and whether the call had to wait.
+pub async fn acquire(permits: Option<Arc<Semaphore>>) -> (Option<OwnedSemaphorePermit>, bool) {
+    let Some(permits) = permits else {
+        return (None, false);
+    };
+    if let Ok(permit) = permits.clone().try_acquire_owned() {
+        return (Some(permit), false);
+    }
+    // The semaphore is never closed, so this only fails if it was dropped
+    (permits.acquire_owned().await.ok(), true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_track_waiting_and_running() {
+        let mut queue = RequestQueue::new(Some(1));
+        queue.enqueue();
+        queue.enqueue();
+        assert_eq!(queue.stats().waiting, 2);
+        queue.started(false);
+        queue.started(true);
+        queue.finished();
+        assert_eq!(
+            queue.stats(),
+            QueueStats {
+                running: 1,
+                waiting: 0,
+                peak_waiting: 2,
+                queued_total: 1,
+            }
+        );
+
+        queue.set_limit(Some(0));
+        assert_eq!(queue.limit(), None);
+        assert!(queue.enqueue().is_none());
+        assert_eq!(queue.stats().peak_waiting, 2);
+    }
+
+    #[tokio::test]
+    async fn test_calls_beyond_limit_wait_in_order() {
+        let mut queue = RequestQueue::new(Some(1));
+        let (first, waited) = acquire(queue.enqueue()).await;
+        assert!(first.is_some());
+        assert!(!waited);
+
+        let permits = queue.enqueue().unwrap();
+        assert!(permits.clone().try_acquire_owned().is_err());
+
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut handles = Vec::new();
+        for i in 0..3 {
+            let (permits, order) = (permits.clone(), order.clone());
+            handles.push(tokio::spawn(async move {
+                let (_permit, waited) = acquire(Some(permits)).await;
+                assert!(waited);
+                order.lock().unwrap().push(i);
+            }));
+            // Let each task queue up before the next one
+            tokio::task::yield_now().await;
+        }
+
+        drop(first);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), vec![0, 1, 2]);
+    }
+}
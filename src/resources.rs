@@ -0,0 +1,220 @@
+//! CPU and memory use of running stdio servers.
+//!
+//! A server is measured together with everything in its process group, so
+//! a wrapper like `npx` counts the node process doing the work. Linux is
+//! read from `/proc`; other Unix systems ask `ps`. Windows has no process
+//! groups to follow, so servers there aren't measured.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// How often running servers are measured.
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Totals for one process group at one moment.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Sample {
+    /// CPU time used since the processes started.
+    pub cpu_time: Duration,
+    /// Resident memory.
+    pub memory_bytes: u64,
+}
+
+/// What a server is using, from two samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Usage {
+    /// Share of one core, so it can pass 100% on several.
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+}
+
+impl Usage {
+    pub fn between(before: &Sample, after: &Sample, elapsed: Duration) -> Usage {
+        let used = after.cpu_time.saturating_sub(before.cpu_time);
+        let cpu_percent = if elapsed.is_zero() {
+            0.0
+        } else {
+            (used.as_secs_f64() / elapsed.as_secs_f64() * 100.0) as f32
+        };
+        Usage {
+            cpu_percent,
+            memory_bytes: after.memory_bytes,
+        }
+    }
+
+    /// e.g. "3.2% CPU · 45.1 MB"
+    pub fn summary(&self) -> String {
+        format!(
+            "{:.1}% CPU · {}",
+            self.cpu_percent,
+            crate::storage::format_size(self.memory_bytes)
+        )
+    }
+}
+
+/// Totals for each of `groups` (process group ids) that has any processes
+/// left. Blocking; reads the process table.
+pub fn sample(groups: &[u32]) -> HashMap<u32, Sample> {
+    let mut totals: HashMap<u32, Sample> = HashMap::new();
+    for (group, process) in processes() {
+        if groups.contains(&group) {
+            let total = totals.entry(group).or_default();
+            total.cpu_time += process.cpu_time;
+            total.memory_bytes += process.memory_bytes;
+        }
+    }
+    totals
+}
+
+/// Every process as its group and usage.
+#[cfg(target_os = "linux")]
+fn processes() -> Vec<(u32, Sample)> {
+    // SAFETY: sysconf only reads system configuration
+    let (ticks, page_size) = unsafe {
+        (
+            libc::sysconf(libc::_SC_CLK_TCK),
+            libc::sysconf(libc::_SC_PAGESIZE),
+        )
+    };
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .bytes()
+                .all(|b| b.is_ascii_digit())
+        })
+        // Processes can exit between listing and reading
+        .filter_map(|e| std::fs::read_to_string(e.path().join("stat")).ok())
+        .filter_map(|stat| parse_proc_stat(&stat, ticks.max(1) as u64, page_size.max(1) as u64))
+        .collect()
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn processes() -> Vec<(u32, Sample)> {
+    let Ok(output) = std::process::Command::new("ps")
+        .args(["-A", "-o", "pgid=,rss=,time="])
+        .output()
+    else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_ps_line)
+        .collect()
+}
+
+#[cfg(not(unix))]
+fn processes() -> Vec<(u32, Sample)> {
+    Vec::new()
+}
+
+/// A `/proc/<pid>/stat` line as its process group and usage. The command
+/// name is in parentheses and may itself contain spaces or parentheses.
+#[cfg(any(target_os = "linux", test))]
+fn parse_proc_stat(stat: &str, ticks_per_sec: u64, page_size: u64) -> Option<(u32, Sample)> {
+    let fields: Vec<&str> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+    // Numbered from the state, the third field of the line
+    let group = fields.get(2)?.parse().ok()?;
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    let rss_pages: u64 = fields.get(21)?.parse().ok()?;
+    let ticks = utime + stime;
+    Some((
+        group,
+        Sample {
+            cpu_time: Duration::from_secs_f64(ticks as f64 / ticks_per_sec as f64),
+            memory_bytes: rss_pages * page_size,
+        },
+    ))
+}
+
+/// A `ps -o pgid=,rss=,time=` line, e.g. `  412  20480   1:02.50`.
+#[cfg(any(all(unix, not(target_os = "linux")), test))]
+fn parse_ps_line(line: &str) -> Option<(u32, Sample)> {
+    let mut fields = line.split_whitespace();
+    let group = fields.next()?.parse().ok()?;
+    let rss_kb: u64 = fields.next()?.parse().ok()?;
+    let cpu_time = parse_cpu_time(fields.next()?)?;
+    Some((
+        group,
+        Sample {
+            cpu_time,
+            memory_bytes: rss_kb * 1024,
+        },
+    ))
+}
+
+/// CPU time as `ps` prints it: `[[DD-]HH:]MM:SS[.ss]`.
+#[cfg(any(all(unix, not(target_os = "linux")), test))]
+fn parse_cpu_time(text: &str) -> Option<Duration> {
+    let (days, rest) = match text.split_once('-') {
+        Some((days, rest)) => (days.parse::<u64>().ok()?, rest),
+        None => (0, text),
+    };
+    let mut seconds = 0.0;
+    for part in rest.split(':') {
+        seconds = seconds * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Some(Duration::from_secs(days * 86_400) + Duration::from_secs_f64(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_stat() {
+        let stat = "4242 (node (worker) x) S 1 4200 4200 0 -1 4194560 1200 0 0 0 250 50 0 0 20 0 11 0 12345 1000000 5000 18446744073709551615";
+        let (group, sample) = parse_proc_stat(stat, 100, 4096).unwrap();
+        assert_eq!(group, 4200);
+        assert_eq!(sample.cpu_time, Duration::from_secs(3));
+        assert_eq!(sample.memory_bytes, 5000 * 4096);
+        assert!(parse_proc_stat("4242 (node) S 1", 100, 4096).is_none());
+    }
+
+    #[test]
+    fn test_parse_ps_line() {
+        let (group, sample) = parse_ps_line("  412  20480   1:02.50").unwrap();
+        assert_eq!(group, 412);
+        assert_eq!(sample.memory_bytes, 20480 * 1024);
+        assert_eq!(sample.cpu_time, Duration::from_millis(62_500));
+        assert_eq!(
+            parse_cpu_time("1-02:00:05"),
+            Some(Duration::from_secs(86_400 + 7205))
+        );
+        assert!(parse_ps_line("PGID RSS TIME").is_none());
+    }
+
+    #[test]
+    fn test_usage() {
+        let before = Sample {
+            cpu_time: Duration::from_secs(10),
+            memory_bytes: 1024,
+        };
+        let after = Sample {
+            cpu_time: Duration::from_millis(10_500),
+            memory_bytes: 45 * 1024 * 1024,
+        };
+        let usage = Usage::between(&before, &after, Duration::from_secs(2));
+        assert_eq!(usage.cpu_percent, 25.0);
+        assert_eq!(usage.summary(), "25.0% CPU · 45.0 MB");
+        // A restarted server's counters start again
+        assert_eq!(
+            Usage::between(&after, &before, Duration::from_secs(2)).cpu_percent,
+            0.0
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_sample_own_group() {
+        // SAFETY: getpgrp has no preconditions
+        let group = unsafe { libc::getpgrp() } as u32;
+        let samples = sample(&[group]);
+        assert!(samples[&group].memory_bytes > 0);
+    }
+}
@@ -0,0 +1,205 @@
+//! Keeping huge tool results out of the webview. A result over the size
+//! limit is written whole to a file, and only its first part is kept for
+//! the console and the result cache.
+
+use crate::models::{AppResult, CallToolResult, Content, Truncation};
+use std::path::{Path, PathBuf};
+
+/// Full results kept on disk; older files are removed.
+const KEEP_FILES: usize = 20;
+
+pub fn outputs_dir() -> AppResult<PathBuf> {
+    Ok(crate::platform::app_data_dir()?.join("outputs"))
+}
+
+/// Bytes of text and base64 data in `result`.
+pub fn result_size(result: &CallToolResult) -> usize {
+    result
+        .content
+        .iter()
+        .map(|c| c.text.as_ref().map_or(0, String::len) + c.data.as_ref().map_or(0, String::len))
+        .sum()
+}
+
+/// The first `max` bytes of `text`, not splitting a character.
+fn cut(text: &str, max: usize) -> &str {
+    if text.len() <= max {
+        return text;
+    }
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// `result` cut down to `max_bytes`. Text is cut short; media that
+/// doesn't fit whole is replaced by a note, as part of an image is of no
+/// use.
+pub fn truncate(mut result: CallToolResult, max_bytes: usize) -> CallToolResult {
+    let original_bytes = result_size(&result);
+    if original_bytes <= max_bytes {
+        return result;
+    }
+    let mut left = max_bytes;
+    let mut kept = Vec::new();
+    for mut content in std::mem::take(&mut result.content) {
+        if let Some(data) = &content.data {
+            if data.len() > left {
+                kept.push(text_content(format!(
+                    "[{} omitted: {}]",
+                    content.mimeType.as_deref().unwrap_or(&content.content_type),
+                    crate::storage::format_size(data.len() as u64)
+                )));
+                continue;
+            }
+            left -= data.len();
+        }
+        if let Some(text) = content.text.as_mut() {
+            let end = cut(text, left).len();
+            text.truncate(end);
+            left -= end;
+        }
+        kept.push(content);
+    }
+    result.content = kept;
+    result.truncated = Some(Truncation {
+        original_bytes,
+        full_output: None,
+    });
+    result
+}
+
+fn text_content(text: String) -> Content {
+    Content {
+        content_type: "text".to_string(),
+        text: Some(text),
+        mimeType: None,
+        data: None,
+    }
+}
+
+/// File name for the full result of a call to `tool`.
+fn output_file_name(server_name: &str, tool: &str, timestamp: &str, text_only: bool) -> String {
+    let safe = |s: &str| -> String {
+        s.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect()
+    };
+    let ext = if text_only { "txt" } else { "json" };
+    format!("{}-{}-{}.{}", safe(server_name), safe(tool), timestamp, ext)
+}
+
+/// Write the whole of `result` into `dir`: its text when it is all text,
+/// otherwise the content blocks as JSON.
+pub fn write_full(
+    dir: &Path,
+    server_name: &str,
+    tool: &str,
+    timestamp: &str,
+    result: &CallToolResult,
+) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let text_only = result.content.iter().all(|c| c.data.is_none());
+    let path = dir.join(output_file_name(server_name, tool, timestamp, text_only));
+    if text_only {
+        let text: String = result
+            .content
+            .iter()
+            .filter_map(|c| c.text.as_deref())
+            .collect::<Vec<_>>()
+            .join("\n");
+        std::fs::write(&path, text)?;
+    } else {
+        std::fs::write(&path, serde_json::to_vec_pretty(&result.content)?)?;
+    }
+    prune(dir, KEEP_FILES);
+    Ok(path)
+}
+
+/// Remove all but the `keep` newest files in `dir`.
+fn prune(dir: &Path, keep: usize) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<_> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .collect();
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    for (_, path) in files.into_iter().skip(keep) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(content: Vec<Content>) -> CallToolResult {
+        CallToolResult {
+            content,
+            isError: None,
+            request_id: None,
+            truncated: None,
+        }
+    }
+
+    fn image(data: &str) -> Content {
+        Content {
+            content_type: "image".to_string(),
+            text: None,
+            mimeType: Some("image/png".to_string()),
+            data: Some(data.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_small_results_are_kept_whole() {
+        let small = result(vec![text_content("hello".into())]);
+        assert_eq!(truncate(small.clone(), 5).content, small.content);
+        assert_eq!(truncate(small, 5).truncated, None);
+    }
+
+    #[test]
+    fn test_truncate_text_and_media() {
+        let big = result(vec![
+            text_content("héllo world".into()),
+            image("AAAAAAAA"),
+            text_content("more".into()),
+        ]);
+        let cut = truncate(big, 2);
+        let texts: Vec<_> = cut
+            .content
+            .iter()
+            .map(|c| c.text.as_deref().unwrap_or_default())
+            .collect();
+        // "é" is two bytes, so only "h" fits
+        assert_eq!(texts, ["h", "[image/png omitted: 8 B]", "m"]);
+        assert_eq!(
+            cut.truncated,
+            Some(Truncation {
+                original_bytes: 24,
+                full_output: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_write_full_and_prune() {
+        let dir = std::env::temp_dir().join(format!("omm-outputs-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let text = result(vec![text_content("a".into()), text_content("b".into())]);
+        let path = write_full(&dir, "My Server", "read_file", "20260101-120000", &text).unwrap();
+        assert!(path.ends_with("My-Server-read-file-20260101-120000.txt"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a\nb");
+
+        let media = result(vec![image("AAAA")]);
+        let path = write_full(&dir, "s", "t", "20260101-120001", &media).unwrap();
+        assert!(path.extension().is_some_and(|e| e == "json"));
+
+        prune(&dir, 1);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
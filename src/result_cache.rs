@@ -0,0 +1,273 @@
+//! Short-lived cache of tool results for calls routed through the hub.
+//!
+//! Only tools the server annotates as read-only are cached, so repeated
+//! editor calls such as "list directory" don't hit slow servers again
+//! within the TTL. Error results are never cached.
+
+use crate::models::{CallToolResult, ResultCacheSettings, Tool};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+/// `server_id/tool`, as used for stats and opt-outs.
+pub fn tool_key(server_id: &str, tool: &str) -> String {
+    format!("{}/{}", server_id, tool)
+}
+
+/// Object keys are sorted by serde_json, so equal arguments hash equally
+/// whatever order they were written in.
+fn args_hash(args: &serde_json::Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    args.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Default)]
+pub struct ResultCache {
+    /// Read-only tool names per server, learned from `tools/list`.
+    read_only: HashMap<String, HashSet<String>>,
+    /// Every tool name per server, from the same lists.
+    tools: HashMap<String, HashSet<String>>,
+    entries: HashMap<(String, String, u64), (Instant, CallToolResult)>,
+    /// Per `server_id/tool`.
+    stats: BTreeMap<String, CacheStats>,
+}
+
+impl ResultCache {
+    /// Remember which of a server's tools are read-only.
+    pub fn learn_tools(&mut self, server_id: &str, tools: &[Tool]) {
+        let names = tools
+            .iter()
+            .filter(|t| t.is_read_only())
+            .map(|t| t.name.clone())
+            .collect();
+        self.read_only.insert(server_id.to_string(), names);
+        let all = tools.iter().map(|t| t.name.clone()).collect();
+        self.tools.insert(server_id.to_string(), all);
+    }
+
+    pub fn knows_server(&self, server_id: &str) -> bool {
+        self.read_only.contains_key(server_id)
+    }
+
+    /// Whether the server's learned tool list has `tool`.
+    pub fn lists_tool(&self, server_id: &str, tool: &str) -> bool {
+        self.tools
+            .get(server_id)
+            .is_some_and(|names| names.contains(tool))
+    }
+
+    pub fn is_read_only(&self, server_id: &str, tool: &str) -> bool {
+        self.read_only
+            .get(server_id)
+            .is_some_and(|names| names.contains(tool))
+    }
+
+    /// Every known read-only `(server_id, tool)`, sorted.
+    pub fn read_only_tools(&self) -> Vec<(String, String)> {
+        let mut tools: Vec<(String, String)> = self
+            .read_only
+            .iter()
+            .flat_map(|(id, names)| names.iter().map(move |n| (id.clone(), n.clone())))
+            .collect();
+        tools.sort();
+        tools
+    }
+
+    /// Whether calls of `tool` may be answered from the cache.
+    pub fn cacheable(&self, settings: &ResultCacheSettings, server_id: &str, tool: &str) -> bool {
+        settings.enabled
+            && self.is_read_only(server_id, tool)
+            && !settings.opted_out.contains(&tool_key(server_id, tool))
+    }
+
+    /// A result stored less than `ttl` ago, counting the hit or miss.
+    pub fn get(
+        &mut self,
+        server_id: &str,
+        tool: &str,
+        args: &serde_json::Value,
+        ttl: Duration,
+        now: Instant,
+    ) -> Option<CallToolResult> {
+        let key = (server_id.to_string(), tool.to_string(), args_hash(args));
+        let fresh = match self.entries.get(&key) {
+            Some((stored, result)) if now.duration_since(*stored) < ttl => Some(result.clone()),
+            Some(_) => {
+                self.entries.remove(&key);
+                None
+            }
+            None => None,
+        };
+        let stats = self.stats.entry(tool_key(server_id, tool)).or_default();
+        match fresh {
+            Some(_) => stats.hits += 1,
+            None => stats.misses += 1,
+        }
+        fresh
+    }
+
+    /// Store a successful result, dropping entries older than `ttl`.
+    pub fn insert(
+        &mut self,
+        server_id: &str,
+        tool: &str,
+        args: &serde_json::Value,
+        result: &CallToolResult,
+        ttl: Duration,
+        now: Instant,
+    ) {
+        self.entries
+            .retain(|_, (stored, _)| now.duration_since(*stored) < ttl);
+        if result.isError == Some(true) {
+            return;
+        }
+        self.entries.insert(
+            (server_id.to_string(), tool.to_string(), args_hash(args)),
+            (now, result.clone()),
+        );
+    }
+
+    /// Drop everything known about a server, e.g. when it stops.
+    pub fn forget_server(&mut self, server_id: &str) {
+        self.read_only.remove(server_id);
+        self.tools.remove(server_id);
+        self.entries.retain(|(id, _, _), _| id != server_id);
+    }
+
+    /// Drop cached results and reset the stats.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.stats.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Hits and misses per `server_id/tool`.
+    pub fn stats(&self) -> &BTreeMap<String, CacheStats> {
+        &self.stats
+    }
+
+    pub fn totals(&self) -> CacheStats {
+        self.stats
+            .values()
+            .fold(CacheStats::default(), |acc, s| CacheStats {
+                hits: acc.hits + s.hits,
+                misses: acc.misses + s.misses,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Content;
+    use serde_json::json;
+
+    fn tool(name: &str, read_only: bool) -> Tool {
+        serde_json::from_value(json!({
+            "name": name,
+            "inputSchema": {},
+            "annotations": { "readOnlyHint": read_only }
+        }))
+        .unwrap()
+    }
+
+    fn result(text: &str, is_error: bool) -> CallToolResult {
+        CallToolResult {
+            content: vec![Content {
+                content_type: "text".to_string(),
+                text: Some(text.to_string()),
+                data: None,
+                mimeType: None,
+            }],
+            isError: Some(is_error),
+            request_id: None,
+            truncated: None,
+        }
+    }
+
+    fn enabled() -> ResultCacheSettings {
+        ResultCacheSettings {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_only_read_only_tools_are_cacheable() {
+        let mut cache = ResultCache::default();
+        cache.learn_tools(
+            "fs",
+            &[tool("list_directory", true), tool("write_file", false)],
+        );
+        assert!(cache.cacheable(&enabled(), "fs", "list_directory"));
+        assert!(!cache.cacheable(&enabled(), "fs", "write_file"));
+        assert!(cache.lists_tool("fs", "write_file"));
+        assert!(!cache.lists_tool("fs", "delete_file"));
+        assert!(!cache.lists_tool("git", "list_directory"));
+        assert!(!cache.cacheable(&ResultCacheSettings::default(), "fs", "list_directory"));
+
+        let opted_out = ResultCacheSettings {
+            opted_out: vec![tool_key("fs", "list_directory")],
+            ..enabled()
+        };
+        assert!(!cache.cacheable(&opted_out, "fs", "list_directory"));
+    }
+
+    #[test]
+    fn test_get_respects_ttl_and_args() {
+        let mut cache = ResultCache::default();
+        let ttl = Duration::from_secs(30);
+        let start = Instant::now();
+        let args = json!({"path": "/tmp", "depth": 1});
+
+        assert!(cache.get("fs", "ls", &args, ttl, start).is_none());
+        cache.insert("fs", "ls", &args, &result("a b", false), ttl, start);
+
+        // Same arguments in another key order hit
+        let reordered: serde_json::Value =
+            serde_json::from_str(r#"{"depth": 1, "path": "/tmp"}"#).unwrap();
+        let hit = cache.get("fs", "ls", &reordered, ttl, start + Duration::from_secs(5));
+        assert_eq!(hit.unwrap().content[0].text.as_deref(), Some("a b"));
+        assert!(cache
+            .get("fs", "ls", &json!({"path": "/"}), ttl, start)
+            .is_none());
+        assert!(cache
+            .get("fs", "ls", &args, ttl, start + Duration::from_secs(31))
+            .is_none());
+
+        assert_eq!(cache.totals(), CacheStats { hits: 1, misses: 3 });
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_errors_are_not_cached_and_servers_forgotten() {
+        let mut cache = ResultCache::default();
+        let ttl = Duration::from_secs(30);
+        let now = Instant::now();
+        cache.learn_tools("fs", &[tool("ls", true)]);
+        cache.insert("fs", "ls", &json!({}), &result("boom", true), ttl, now);
+        assert!(cache.is_empty());
+
+        cache.insert("fs", "ls", &json!({}), &result("ok", false), ttl, now);
+        assert_eq!(cache.len(), 1);
+        cache.forget_server("fs");
+        assert!(cache.is_empty());
+        assert!(!cache.knows_server("fs"));
+        assert!(!cache.lists_tool("fs", "ls"));
+    }
+}
@@ -0,0 +1,302 @@
+//! Fulfilling `sampling/createMessage` requests servers send to the
+//! manager. Each request is shown to the user first; approved ones are
+//! sent to the LLM API configured in [`SamplingSettings`].
+
+use crate::models::{SamplingProvider, SamplingSettings};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// How long an LLM API gets to answer.
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Params of a `sampling/createMessage` request.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateMessageRequest {
+    pub messages: Vec<SamplingMessage>,
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    pub max_tokens: u64,
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    #[serde(default)]
+    pub stop_sequences: Vec<String>,
+    #[serde(default)]
+    pub model_preferences: Option<Value>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SamplingMessage {
+    pub role: String,
+    /// A text, image or audio content block.
+    pub content: Value,
+}
+
+/// A sampling request waiting for the user's approval.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingSampling {
+    pub server_id: String,
+    pub server_name: String,
+    /// The server's id for the request, to reply with.
+    pub request_id: Value,
+    pub request: CreateMessageRequest,
+}
+
+impl CreateMessageRequest {
+    /// Models the server would like, most preferred first.
+    pub fn model_hints(&self) -> Vec<String> {
+        self.model_preferences
+            .as_ref()
+            .and_then(|p| p["hints"].as_array())
+            .map(|hints| {
+                hints
+                    .iter()
+                    .filter_map(|h| h["name"].as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// `content` as text for the approval dialog; media shows as its type.
+pub fn content_text(content: &Value) -> String {
+    match content["type"].as_str() {
+        Some("text") => content["text"].as_str().unwrap_or_default().to_string(),
+        Some(kind) => format!("[{}]", content["mimeType"].as_str().unwrap_or(kind)),
+        None => content.to_string(),
+    }
+}
+
+fn openai_content(content: &Value) -> Value {
+    match content["type"].as_str() {
+        Some("image") => json!([{
+            "type": "image_url",
+            "image_url": {
+                "url": format!(
+                    "data:{};base64,{}",
+                    content["mimeType"].as_str().unwrap_or("image/png"),
+                    content["data"].as_str().unwrap_or_default()
+                )
+            }
+        }]),
+        _ => Value::String(content_text(content)),
+    }
+}
+
+fn anthropic_content(content: &Value) -> Value {
+    match content["type"].as_str() {
+        Some("image") => json!([{
+            "type": "image",
+            "source": {
+                "type": "base64",
+                "media_type": content["mimeType"],
+                "data": content["data"]
+            }
+        }]),
+        _ => json!([{ "type": "text", "text": content_text(content) }]),
+    }
+}
+
+/// Request body for `provider`'s API.
+pub fn request_body(
+    provider: SamplingProvider,
+    model: &str,
+    request: &CreateMessageRequest,
+) -> Value {
+    let mut body = json!({
+        "model": model,
+        "max_tokens": request.max_tokens,
+    });
+    match provider {
+        SamplingProvider::OpenAi => {
+            let system = request
+                .system_prompt
+                .iter()
+                .map(|text| json!({ "role": "system", "content": text }));
+            let messages = request
+                .messages
+                .iter()
+                .map(|m| json!({ "role": m.role, "content": openai_content(&m.content) }));
+            body["messages"] = system.chain(messages).collect();
+            if !request.stop_sequences.is_empty() {
+                body["stop"] = json!(request.stop_sequences);
+            }
+        }
+        SamplingProvider::Anthropic => {
+            body["messages"] = request
+                .messages
+                .iter()
+                .map(|m| json!({ "role": m.role, "content": anthropic_content(&m.content) }))
+                .collect();
+            if let Some(system) = &request.system_prompt {
+                body["system"] = json!(system);
+            }
+            if !request.stop_sequences.is_empty() {
+                body["stop_sequences"] = json!(request.stop_sequences);
+            }
+        }
+    }
+    if let Some(temperature) = request.temperature {
+        body["temperature"] = json!(temperature);
+    }
+    body
+}
+
+/// `provider`'s reply as a `sampling/createMessage` result.
+pub fn parse_response(provider: SamplingProvider, response: &Value) -> Result<Value, String> {
+    let (text, stop_reason) = match provider {
+        SamplingProvider::OpenAi => {
+            let choice = &response["choices"][0];
+            let text = choice["message"]["content"]
+                .as_str()
+                .ok_or("Response has no message")?;
+            let stop_reason = match choice["finish_reason"].as_str() {
+                Some("stop") => Some("endTurn"),
+                Some("length") => Some("maxTokens"),
+                _ => None,
+            };
+            (text.to_string(), stop_reason)
+        }
+        SamplingProvider::Anthropic => {
+            let blocks = response["content"]
+                .as_array()
+                .ok_or("Response has no content")?;
+            let text: String = blocks.iter().filter_map(|b| b["text"].as_str()).collect();
+            let stop_reason = match response["stop_reason"].as_str() {
+                Some("end_turn") => Some("endTurn"),
+                Some("max_tokens") => Some("maxTokens"),
+                Some("stop_sequence") => Some("stopSequence"),
+                _ => None,
+            };
+            (text, stop_reason)
+        }
+    };
+    let mut result = json!({
+        "role": "assistant",
+        "content": { "type": "text", "text": text },
+        "model": response["model"],
+    });
+    if let Some(stop_reason) = stop_reason {
+        result["stopReason"] = json!(stop_reason);
+    }
+    Ok(result)
+}
+
+/// Send an approved request to the configured API.
+pub async fn create_message(
+    settings: &SamplingSettings,
+    request: &CreateMessageRequest,
+) -> Result<Value, String> {
+    if settings.model.trim().is_empty() {
+        return Err("No sampling model configured".to_string());
+    }
+    let body = request_body(settings.provider, settings.model.trim(), request);
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut http = client.post(settings.endpoint()).json(&body);
+    http = match settings.provider {
+        SamplingProvider::OpenAi if !settings.api_key.is_empty() => {
+            http.bearer_auth(&settings.api_key)
+        }
+        SamplingProvider::OpenAi => http,
+        SamplingProvider::Anthropic => http
+            .header("x-api-key", &settings.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION),
+    };
+    let response = http.send().await.map_err(|e| e.to_string())?;
+    let status = response.status();
+    let reply: Value = response.json().await.map_err(|e| e.to_string())?;
+    if !status.is_success() {
+        let message = reply["error"]["message"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| reply.to_string());
+        return Err(format!("{}: {}", status, message));
+    }
+    parse_response(settings.provider, &reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> CreateMessageRequest {
+        serde_json::from_value(json!({
+            "messages": [
+                { "role": "user", "content": { "type": "text", "text": "Summarise this" } },
+                { "role": "user", "content": { "type": "image", "mimeType": "image/png", "data": "AAAA" } }
+            ],
+            "systemPrompt": "Be brief",
+            "maxTokens": 100,
+            "stopSequences": ["END"],
+            "modelPreferences": { "hints": [{ "name": "claude-3-haiku" }, { "name": "gpt-4o" }] }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_request_fields() {
+        let request = request();
+        assert_eq!(request.model_hints(), ["claude-3-haiku", "gpt-4o"]);
+        assert_eq!(content_text(&request.messages[0].content), "Summarise this");
+        assert_eq!(content_text(&request.messages[1].content), "[image/png]");
+    }
+
+    #[test]
+    fn test_request_bodies() {
+        let openai = request_body(SamplingProvider::OpenAi, "gpt-4o", &request());
+        assert_eq!(
+            openai["messages"][0],
+            json!({ "role": "system", "content": "Be brief" })
+        );
+        assert_eq!(openai["messages"][1]["content"], "Summarise this");
+        assert_eq!(
+            openai["messages"][2]["content"][0]["image_url"]["url"],
+            "data:image/png;base64,AAAA"
+        );
+        assert_eq!(openai["stop"], json!(["END"]));
+        assert!(openai.get("temperature").is_none());
+
+        let anthropic = request_body(SamplingProvider::Anthropic, "claude", &request());
+        assert_eq!(anthropic["system"], "Be brief");
+        assert_eq!(anthropic["max_tokens"], 100);
+        assert_eq!(anthropic["messages"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            anthropic["messages"][1]["content"][0]["source"]["data"],
+            "AAAA"
+        );
+        assert_eq!(anthropic["stop_sequences"], json!(["END"]));
+    }
+
+    #[test]
+    fn test_parse_responses() {
+        let openai = json!({
+            "model": "gpt-4o",
+            "choices": [{ "message": { "role": "assistant", "content": "Done" }, "finish_reason": "length" }]
+        });
+        assert_eq!(
+            parse_response(SamplingProvider::OpenAi, &openai).unwrap(),
+            json!({
+                "role": "assistant",
+                "content": { "type": "text", "text": "Done" },
+                "model": "gpt-4o",
+                "stopReason": "maxTokens"
+            })
+        );
+
+        let anthropic = json!({
+            "model": "claude",
+            "content": [{ "type": "text", "text": "Hi " }, { "type": "text", "text": "there" }],
+            "stop_reason": "end_turn"
+        });
+        let result = parse_response(SamplingProvider::Anthropic, &anthropic).unwrap();
+        assert_eq!(result["content"]["text"], "Hi there");
+        assert_eq!(result["stopReason"], "endTurn");
+
+        assert!(parse_response(SamplingProvider::OpenAi, &json!({ "error": {} })).is_err());
+    }
+}
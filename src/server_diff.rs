@@ -0,0 +1,99 @@
+//! Field-by-field comparison of two server configurations.
+
+use crate::models::McpServer;
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    /// `command`, `args`, `env.API_KEY`, ...
+    pub field: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+    /// Compared on the real values, so masked env values still show
+    /// whether they differ.
+    pub changed: bool,
+}
+
+/// Hide an env value, keeping just enough to tell values apart by eye.
+pub fn mask_value(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() <= 8 {
+        "••••".to_string()
+    } else {
+        let tail: String = chars[chars.len() - 2..].iter().collect();
+        format!("••••{}", tail)
+    }
+}
+
+fn field(name: &str, left: Option<String>, right: Option<String>) -> FieldDiff {
+    FieldDiff {
+        field: name.to_string(),
+        changed: left != right,
+        left,
+        right,
+    }
+}
+
+/// Compare `a` and `b`. Env values are masked unless `reveal_env` is set.
+pub fn diff_servers(a: &McpServer, b: &McpServer, reveal_env: bool) -> Vec<FieldDiff> {
+    let join_args = |s: &McpServer| s.args.as_ref().map(|args| args.join(" "));
+    let mut fields = vec![
+        field(
+            "type",
+            Some(a.server_type.clone()),
+            Some(b.server_type.clone()),
+        ),
+        field("command", a.command.clone(), b.command.clone()),
+        field("args", join_args(a), join_args(b)),
+        field("url", a.url.clone(), b.url.clone()),
+        field("description", a.description.clone(), b.description.clone()),
+    ];
+
+    let env_a = a.env.clone().unwrap_or_default();
+    let env_b = b.env.clone().unwrap_or_default();
+    let keys: BTreeSet<&String> = env_a.keys().chain(env_b.keys()).collect();
+    let show = |v: Option<&String>| v.map(|v| if reveal_env { v.clone() } else { mask_value(v) });
+    for key in keys {
+        let (left, right) = (env_a.get(key), env_b.get(key));
+        fields.push(FieldDiff {
+            field: format!("env.{}", key),
+            changed: left != right,
+            left: show(left),
+            right: show(right),
+        });
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_servers_masks_env() {
+        let staging = McpServer::test_stdio("a", "npx")
+            .with_args(&["-y", "pkg"])
+            .with_env(&[("API_KEY", "sk-staging-123456"), ("REGION", "eu")]);
+        let prod = McpServer::test_stdio("a", "node")
+            .with_args(&["-y", "pkg"])
+            .with_env(&[("API_KEY", "sk-prod-abcdef56"), ("DEBUG", "1")]);
+        let diff = diff_servers(&staging, &prod, false);
+
+        let get = |name: &str| diff.iter().find(|d| d.field == name).unwrap().clone();
+        assert!(get("command").changed);
+        assert!(!get("args").changed);
+        assert_eq!(get("args").left.as_deref(), Some("-y pkg"));
+
+        let key = get("env.API_KEY");
+        assert!(key.changed);
+        assert_eq!(key.left.as_deref(), Some("••••56"));
+        assert_eq!(key.right.as_deref(), Some("••••56"));
+        assert_eq!(get("env.DEBUG").left, None);
+        assert_eq!(get("env.REGION").left.as_deref(), Some("••••"));
+
+        let revealed = diff_servers(&staging, &prod, true);
+        assert!(revealed
+            .iter()
+            .any(|d| d.right.as_deref() == Some("sk-prod-abcdef56")));
+    }
+}
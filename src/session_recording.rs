@@ -0,0 +1,218 @@
+//! Recording a stdio server's protocol session to a file, and replaying
+//! it without the server.
+//!
+//! A recording is JSON Lines, one [`RecordedMessage`] per message sent,
+//! received or printed to stderr. Replay answers each request with the
+//! response the server gave to the same request when recorded, so
+//! protocol bugs can be reproduced without the server or its API keys.
+
+use crate::models::AppResult;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Manager to server (stdin).
+    Sent,
+    /// Server to manager (stdout).
+    Received,
+    Stderr,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecordedMessage {
+    /// Milliseconds since recording started.
+    pub at_ms: u64,
+    pub dir: Direction,
+    pub data: String,
+}
+
+pub fn recordings_dir() -> AppResult<PathBuf> {
+    Ok(crate::platform::app_data_dir()?.join("recordings"))
+}
+
+/// File name for a new recording of `server_name`.
+pub fn recording_file_name(server_name: &str, timestamp: &str) -> String {
+    let safe: String = server_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    format!("{}-{}.jsonl", safe, timestamp)
+}
+
+/// Appends messages to a recording file as they happen.
+pub struct SessionRecorder {
+    path: PathBuf,
+    started: Instant,
+    file: std::io::BufWriter<std::fs::File>,
+}
+
+impl SessionRecorder {
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            started: Instant::now(),
+            file: std::io::BufWriter::new(std::fs::File::create(path)?),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Best effort: a failed write only loses that message.
+    pub fn record(&mut self, dir: Direction, data: &[u8]) {
+        let message = RecordedMessage {
+            at_ms: self.started.elapsed().as_millis() as u64,
+            dir,
+            data: String::from_utf8_lossy(data).into_owned(),
+        };
+        if let Ok(json) = serde_json::to_string(&message) {
+            let _ = writeln!(self.file, "{}", json);
+            let _ = self.file.flush();
+        }
+    }
+}
+
+pub fn load(path: &Path) -> Result<Vec<RecordedMessage>, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    std::io::BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(i, line)| {
+            let line = line.map_err(|e| e.to_string())?;
+            serde_json::from_str(&line).map_err(|e| format!("Line {}: {}", i + 1, e))
+        })
+        .collect()
+}
+
+/// Answers requests from a recording.
+pub struct Replay {
+    messages: Vec<RecordedMessage>,
+    /// Indexes of sent messages already replayed.
+    used: Vec<bool>,
+}
+
+impl Replay {
+    pub fn new(messages: Vec<RecordedMessage>) -> Self {
+        let used = vec![false; messages.len()];
+        Self { messages, used }
+    }
+
+    /// What the server printed to stderr while recorded.
+    pub fn stderr_lines(&self) -> Vec<String> {
+        self.messages
+            .iter()
+            .filter(|m| m.dir == Direction::Stderr)
+            .map(|m| m.data.clone())
+            .collect()
+    }
+
+    /// The recorded reply to `method` with `params`: the response message
+    /// to the first not yet replayed request with the same method and
+    /// params, or failing that the same method. Each recorded request
+    /// answers once, so repeated calls play back in order.
+    pub fn reply(&mut self, method: &str, params: &Value) -> Result<Value, String> {
+        let requests: Vec<(usize, Value)> = self
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(i, m)| m.dir == Direction::Sent && !self.used[*i])
+            .filter_map(|(i, m)| Some((i, serde_json::from_str::<Value>(&m.data).ok()?)))
+            .filter(|(_, v)| v["method"] == method && !v["id"].is_null())
+            .collect();
+        let (index, request) = requests
+            .iter()
+            .find(|(_, v)| v["params"] == *params)
+            .or_else(|| requests.first())
+            .ok_or_else(|| format!("No recorded request for {}", method))?;
+        self.used[*index] = true;
+
+        self.messages[*index..]
+            .iter()
+            .filter(|m| m.dir == Direction::Received)
+            .filter_map(|m| serde_json::from_str::<Value>(&m.data).ok())
+            .find(|v| v["id"] == request["id"])
+            .ok_or_else(|| format!("The recorded {} request got no response", method))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn message(dir: Direction, data: Value) -> RecordedMessage {
+        RecordedMessage {
+            at_ms: 0,
+            dir,
+            data: data.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_record_and_load_round_trip() {
+        let path = std::env::temp_dir().join(format!("omm-recording-{}.jsonl", std::process::id()));
+        let mut recorder = SessionRecorder::create(&path).unwrap();
+        recorder.record(Direction::Sent, br#"{"id":1,"method":"tools/list"}"#);
+        recorder.record(Direction::Stderr, b"starting \xff");
+
+        let messages = load(&path).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].dir, Direction::Sent);
+        assert_eq!(messages[1].data, "starting \u{fffd}");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_matches_requests_in_order() {
+        let mut replay = Replay::new(vec![
+            message(Direction::Stderr, json!("ready")),
+            message(
+                Direction::Sent,
+                json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call", "params": {"name": "a"}}),
+            ),
+            message(
+                Direction::Sent,
+                json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {"name": "b"}}),
+            ),
+            // Out of order answers
+            message(
+                Direction::Received,
+                json!({"jsonrpc": "2.0", "id": 2, "result": "B"}),
+            ),
+            message(
+                Direction::Received,
+                json!({"jsonrpc": "2.0", "id": 1, "error": {"code": -1}}),
+            ),
+        ]);
+
+        assert_eq!(
+            replay.reply("tools/call", &json!({"name": "b"})),
+            Ok(json!({"jsonrpc": "2.0", "id": 2, "result": "B"}))
+        );
+        assert_eq!(
+            replay.reply("tools/call", &json!({"name": "a"})).unwrap()["error"],
+            json!({"code": -1})
+        );
+        // Every recorded call has been replayed
+        assert!(replay.reply("tools/call", &json!({"name": "a"})).is_err());
+        assert_eq!(replay.stderr_lines(), vec!["\"ready\""]);
+    }
+
+    #[test]
+    fn test_recording_file_name_is_safe() {
+        assert_eq!(
+            recording_file_name("my server/v2", "20260101-120000"),
+            "my-server-v2-20260101-120000.jsonl"
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Ready-to-paste code for repeating a tool call made in the console:
+//! the raw JSON-RPC request, curl against the hub, and MCP SDK clients.
+
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SnippetKind {
+    JsonRpc,
+    Curl,
+    Python,
+    TypeScript,
+}
+
+impl SnippetKind {
+    pub const ALL: [SnippetKind; 4] = [
+        SnippetKind::JsonRpc,
+        SnippetKind::Curl,
+        SnippetKind::Python,
+        SnippetKind::TypeScript,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SnippetKind::JsonRpc => "JSON-RPC",
+            SnippetKind::Curl => "curl",
+            SnippetKind::Python => "Python",
+            SnippetKind::TypeScript => "TypeScript",
+        }
+    }
+}
+
+/// The `tools/call` request for `tool` with `arguments`.
+pub fn tool_call_request(tool: &str, arguments: &Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": tool, "arguments": arguments }
+    })
+}
+
+/// Quote `s` for a POSIX shell.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Render a JSON value as a Python literal (`true` -> `True`, etc.).
+fn python_literal(value: &Value, indent: usize) -> String {
+    let pad = "    ".repeat(indent + 1);
+    let close = "    ".repeat(indent);
+    match value {
+        Value::Null => "None".to_string(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Number(n) => n.to_string(),
+        // JSON string escapes are valid Python string escapes
+        Value::String(s) => Value::String(s.clone()).to_string(),
+        Value::Array(items) if items.is_empty() => "[]".to_string(),
+        Value::Array(items) => {
+            let items: Vec<String> = items
+                .iter()
+                .map(|v| format!("{}{},", pad, python_literal(v, indent + 1)))
+                .collect();
+            format!("[\n{}\n{}]", items.join("\n"), close)
+        }
+        Value::Object(map) if map.is_empty() => "{}".to_string(),
+        Value::Object(map) => {
+            let items: Vec<String> = map
+                .iter()
+                .map(|(k, v)| {
+                    format!(
+                        "{}{}: {},",
+                        pad,
+                        Value::String(k.clone()),
+                        python_literal(v, indent + 1)
+                    )
+                })
+                .collect();
+            format!("{{\n{}\n{}}}", items.join("\n"), close)
+        }
+    }
+}
+
+/// Re-indent every line after the first by `indent` spaces.
+fn indent_tail(text: &str, indent: usize) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                line.to_string()
+            } else {
+                format!("{}{}", " ".repeat(indent), line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Snippet invoking `tool` with `arguments` through the hub at `hub_url`.
+pub fn generate(kind: SnippetKind, tool: &str, arguments: &Value, hub_url: &str) -> String {
+    let request = tool_call_request(tool, arguments);
+    let pretty = |v: &Value| serde_json::to_string_pretty(v).unwrap_or_default();
+    match kind {
+        SnippetKind::JsonRpc => pretty(&request),
+        SnippetKind::Curl => {
+            let base = reqwest::Url::parse(hub_url)
+                .map(|u| u.origin().ascii_serialization())
+                .unwrap_or_else(|_| hub_url.to_string());
+            format!(
+                "# 1. Open the hub's event stream and keep it running. The first\n\
+                 #    `endpoint` event gives the session URL; results arrive here.\n\
+                 curl -N -H 'Accept: text/event-stream' {hub}\n\
+                 \n\
+                 # 2. In another terminal, post the call to that endpoint.\n\
+                 ENDPOINT=\"{base}/messages?sessionId=...\"\n\
+                 curl -X POST \"$ENDPOINT\" \\\n  \
+                 -H 'Content-Type: application/json' \\\n  \
+                 -d {body}\n",
+                hub = shell_quote(hub_url),
+                base = base,
+                body = shell_quote(&request.to_string()),
+            )
+        }
+        SnippetKind::Python => format!(
+            "import asyncio\n\
+             \n\
+             from mcp import ClientSession\n\
+             from mcp.client.sse import sse_client\n\
+             \n\
+             \n\
+             async def main():\n    \
+             async with sse_client({url}) as (read, write):\n        \
+             async with ClientSession(read, write) as session:\n            \
+             await session.initialize()\n            \
+             result = await session.call_tool(\n                \
+             {name},\n                \
+             {args},\n            \
+             )\n            \
+             print(result.content)\n\
+             \n\
+             \n\
+             asyncio.run(main())\n",
+            url = Value::String(hub_url.to_string()),
+            name = Value::String(tool.to_string()),
+            args = indent_tail(&python_literal(arguments, 0), 16),
+        ),
+        SnippetKind::TypeScript => format!(
+            "import {{ Client }} from \"@modelcontextprotocol/sdk/client/index.js\";\n\
+             import {{ SSEClientTransport }} from \"@modelcontextprotocol/sdk/client/sse.js\";\n\
+             \n\
+             const client = new Client({{ name: \"open-mcp-manager-snippet\", version: \"1.0.0\" }});\n\
+             await client.connect(new SSEClientTransport(new URL({url})));\n\
+             \n\
+             const result = await client.callTool({{\n  \
+             name: {name},\n  \
+             arguments: {args},\n\
+             }});\n\
+             console.log(result.content);\n\
+             await client.close();\n",
+            url = Value::String(hub_url.to_string()),
+            name = Value::String(tool.to_string()),
+            args = indent_tail(&pretty(arguments), 2),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_rpc_and_curl() {
+        let args = json!({ "q": "it's" });
+        let rpc: Value = serde_json::from_str(&generate(
+            SnippetKind::JsonRpc,
+            "search",
+            &args,
+            "http://localhost:3000/api/mcp/sse",
+        ))
+        .unwrap();
+        assert_eq!(rpc["method"], "tools/call");
+        assert_eq!(rpc["params"]["arguments"], args);
+
+        let curl = generate(
+            SnippetKind::Curl,
+            "search",
+            &args,
+            "http://localhost:3000/api/mcp/sse",
+        );
+        assert!(curl.contains(
+            "curl -N -H 'Accept: text/event-stream' 'http://localhost:3000/api/mcp/sse'"
+        ));
+        assert!(curl.contains("ENDPOINT=\"http://localhost:3000/messages?sessionId=...\""));
+        assert!(curl.contains(r#"-d '{"id":1,"jsonrpc":"2.0","method":"tools/call","params":{"arguments":{"q":"it'\''s"},"name":"search"}}'"#));
+    }
+
+    #[test]
+    fn test_python_literal() {
+        let value = json!({ "flag": true, "none": null, "list": [1, "a\"b"], "empty": {} });
+        assert_eq!(
+            python_literal(&value, 0),
+            "{\n    \"empty\": {},\n    \"flag\": True,\n    \"list\": [\n        1,\n        \"a\\\"b\",\n    ],\n    \"none\": None,\n}"
+        );
+    }
+
+    #[test]
+    fn test_sdk_snippets_embed_call() {
+        let args = json!({ "path": "/tmp" });
+        let py = generate(SnippetKind::Python, "read", &args, "http://h/sse");
+        assert!(py.contains("sse_client(\"http://h/sse\")"));
+        assert!(py.contains("                \"read\",\n                {\n                    \"path\": \"/tmp\",\n                },"));
+        let ts = generate(SnippetKind::TypeScript, "read", &args, "http://h/sse");
+        assert!(ts.contains("  name: \"read\",\n  arguments: {\n    \"path\": \"/tmp\"\n  },"));
+    }
+}
@@ -0,0 +1,110 @@
+//! `open-mcp-manager start NAME`: run one of the active workspace's stdio
+//! servers in the foreground, found by name or alias, with the environment
+//! and hooks the manager would start it with. Its stdin and stdout are the
+//! terminal's, so a client can be piped to it or it can be tried by hand.
+
+use crate::models::{LaunchOptions, McpServer};
+
+/// Subcommand name used to run a server from the command line.
+pub const START_SUBCOMMAND: &str = "start";
+
+/// Parse the start arguments (everything after `start`): a single `NAME`.
+pub fn parse_args(args: &[String]) -> Result<String, String> {
+    match args {
+        [name] if !name.starts_with("--") => Ok(name.clone()),
+        _ => Err(format!("Usage: open-mcp-manager {} NAME", START_SUBCOMMAND)),
+    }
+}
+
+/// The server called `name` among `servers`, if it can be started here.
+pub fn find(
+    servers: Vec<(McpServer, LaunchOptions)>,
+    name: &str,
+) -> Result<(McpServer, LaunchOptions), String> {
+    let (server, options) = crate::verify::select(servers, &[name.to_string()])?
+        .pop()
+        .ok_or(format!("No server named {}", name))?;
+    if server.is_remote() {
+        return Err(format!(
+            "{} is a remote server; there is nothing to start",
+            server.name
+        ));
+    }
+    Ok((server, options))
+}
+
+/// Run the server called `name` until it exits, returning its exit code.
+pub async fn run(name: &str) -> Result<i32, String> {
+    let (server, options) = find(crate::verify::servers_from_database()?, name)?;
+    let env = server.env.clone().unwrap_or_default();
+    let (command, args) = crate::docker::server_command(&server)?;
+
+    if let Some(hook) = &options.pre_start {
+        let run = crate::hooks::run(hook, &env, crate::hooks::HOOK_TIMEOUT).await;
+        if let Some(e) = run.error {
+            return Err(format!("Pre-start hook {}", e));
+        }
+    }
+
+    // Left behind if the manager quit without stopping it
+    if server.docker_image.is_some() {
+        crate::docker::remove(&crate::docker::container_name(&server.id)).await;
+    }
+    let cwd = server.cwd.as_deref().map(std::path::Path::new);
+    let status =
+        match crate::process::spawn_command(&command, args, Some(&env), cwd, options.clean_env)
+            .await
+        {
+            Ok(mut cmd) => cmd
+                .status()
+                .await
+                .map_err(|e| format!("Failed to start {}: {}", command, e)),
+            Err(e) => Err(e),
+        };
+
+    if let Some(hook) = &options.post_stop {
+        crate::hooks::run(hook, &env, crate::hooks::HOOK_TIMEOUT).await;
+    }
+    // Killed by a signal when there is no code
+    Ok(status?.code().unwrap_or(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(&["fs".to_string()]).unwrap(), "fs");
+        assert!(parse_args(&[]).is_err());
+        assert!(parse_args(&["fs".to_string(), "gh".to_string()]).is_err());
+        assert!(parse_args(&["--help".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_find() {
+        let config = json!({"mcpServers": {
+            "filesystem": {"command": "npx", "args": ["-y", "files"]},
+            "remote": {"url": "https://example.com/sse"}
+        }});
+        let servers: Vec<(McpServer, LaunchOptions)> = crate::verify::servers_from_config(&config)
+            .unwrap()
+            .into_iter()
+            .map(|mut s| {
+                if s.name == "filesystem" {
+                    s.alias = Some("fs".to_string());
+                }
+                (s, LaunchOptions::default())
+            })
+            .collect();
+
+        let (server, _) = find(servers.clone(), "fs").unwrap();
+        assert_eq!(server.name, "filesystem");
+        assert_eq!(
+            find(servers.clone(), "remote").unwrap_err(),
+            "remote is a remote server; there is nothing to start"
+        );
+        assert_eq!(find(servers, "gh").unwrap_err(), "No server named gh");
+    }
+}
@@ -1,22 +1,274 @@
+use crate::appearance::AppearanceSettings;
+use crate::dashboard::DashboardSettings;
 use crate::db::Database;
+use crate::dedupe::MergePlan;
+use crate::editor_config::{ConfigDrift, DriftEntry, DriftKind, MigrationPlan};
 use crate::models::{
-    CreateServerArgs, McpServer, Notification, NotificationLevel, RegistryItem, ResearchNote,
-    UpdateServerArgs,
+    AuditEntry, Content, CreateServerArgs, ErrorBudgetSettings, FailoverPair,
+    HealthEndpointSettings, LaunchOptions, LoggingLevel, McpServer, Notification,
+    NotificationEvent, NotificationLevel, NotificationRules, RegistryItem, ResearchNote,
+    ResponseLimitSettings, ResultCacheSettings, RuleAction, SamplingSettings, ServerRevision,
+    SessionState, ToolPreset, ToolRule, UpdateServerArgs,
 };
-use crate::process::{McpProcess, ProcessLog};
+use crate::process::{ProcessExit, ProcessLog, RawExchange, RpcError, ServerRequest, StopOutcome};
+use crate::project_import::{ImportAction, ProjectProfile};
+use crate::sampling::{CreateMessageRequest, PendingSampling};
 use dioxus::prelude::*;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::process::Command;
 use tokio::sync::mpsc; // Added for running updates
 
+/// Settings key holding the JSON-encoded [`NotificationRules`].
+const NOTIFICATION_RULES_KEY: &str = "notification_rules";
+
+/// `text` as an AppleScript string literal.
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Show an OS-level notification. Best effort: failures are only logged.
+fn send_native_notification(message: &str) {
+    let title = "Open MCP Manager";
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {} with title {}",
+            applescript_string(message),
+            applescript_string(title)
+        );
+        let mut command = Command::new("osascript");
+        command.args(["-e", &script]);
+        command
+    } else if cfg!(target_os = "linux") {
+        let mut command = Command::new("notify-send");
+        command.args([title, message]);
+        command
+    } else {
+        tracing::debug!("Native notifications not supported on this platform");
+        return;
+    };
+    // Waited on, so the finished process is reaped
+    spawn(async move {
+        if let Err(e) = command.status().await {
+            tracing::debug!("Failed to send native notification: {}", e);
+        }
+    });
+}
+
+/// Replace the server with the same id in place, or insert it after the
+/// pinned servers, where the database puts new servers.
+fn upsert_by_id(list: &mut Vec<McpServer>, server: McpServer) {
+    match list.iter_mut().find(|s| s.id == server.id) {
+        Some(existing) => *existing = server,
+        None => {
+            let at = list.iter().take_while(|s| s.is_pinned).count();
+            list.insert(at, server)
+        }
+    }
+}
+
+/// Take `moved` out of `ids` and put it back just before `target`.
+fn move_before(ids: &mut Vec<String>, moved: &str, target: &str) {
+    if moved == target || !ids.iter().any(|id| id == target) {
+        return;
+    }
+    let Some(from) = ids.iter().position(|id| id == moved) else {
+        return;
+    };
+    let id = ids.remove(from);
+    let to = ids.iter().position(|id| id == target).unwrap_or(ids.len());
+    ids.insert(to, id);
+}
+
+/// Settings key for how long a stopping server gets to exit on its own.
+const STOP_GRACE_KEY: &str = "stop_grace_secs";
+const DEFAULT_STOP_GRACE_SECS: u64 = 5;
+
+/// Settings key for the automatic update check opt-out.
+const UPDATE_CHECKS_KEY: &str = "update_checks_enabled";
+
+/// Settings keys for session restore.
+const SESSION_KEY: &str = "session";
+const RESTORE_SESSION_KEY: &str = "restore_session";
+
+/// Settings keys for the weekly registry digest.
+const DIGEST_NOTIFY_KEY: &str = "registry_digest_notify";
+const DIGEST_NOTIFIED_ON_KEY: &str = "registry_digest_notified_on";
+
+/// Settings keys for the weekly summary report.
+const WEEKLY_REPORT_OPEN_KEY: &str = "weekly_report_open";
+const WEEKLY_REPORT_ON_KEY: &str = "weekly_report_generated_on";
+
+/// How many days of tool calls and server runs are kept for reports.
+const USAGE_HISTORY_DAYS: i64 = 28;
+
+/// How often the digest is recomputed and the Monday notification checked.
+const DIGEST_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// How often the session is saved (only when it changed).
+const SESSION_SAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// How often temporary servers are checked for expiry.
+const EXPIRY_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How long a server gets to answer the MCP `initialize` handshake.
+const INITIALIZE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How long a local server may stay silent, printing nothing and not
+/// answering `initialize`, before it's suspected of waiting for input.
+const QUIET_START_WARNING: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// How long a failed server's last output gets to reach its log before
+/// the failure is diagnosed.
+const FAILURE_LOG_SETTLE: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// How often editor config files are checked for external edits.
+const CONFIG_WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Settings key holding the JSON-encoded recently used tools.
+const RECENT_TOOLS_KEY: &str = "recent_tools";
+
+/// Settings key holding the JSON-encoded [`ResultCacheSettings`].
+const RESULT_CACHE_KEY: &str = "result_cache";
+
+/// Settings key holding the JSON-encoded [`ResponseLimitSettings`].
+const RESPONSE_LIMITS_KEY: &str = "response_limits";
+const SAMPLING_KEY: &str = "sampling";
+const PROJECT_PROFILES_KEY: &str = "project_profiles";
+
+/// Settings key holding the JSON-encoded [`ErrorBudgetSettings`].
+const ERROR_BUDGET_KEY: &str = "error_budget";
+/// Settings key holding the JSON-encoded health endpoint settings.
+const HEALTH_ENDPOINT_KEY: &str = "health_endpoint";
+
+/// Settings key holding the JSON-encoded [`DashboardSettings`].
+const DASHBOARD_KEY: &str = "dashboard_widgets";
+
+/// Settings key holding the JSON-encoded [`AppearanceSettings`].
+const APPEARANCE_KEY: &str = "appearance";
+
+/// Settings key holding the JSON-encoded app language; null follows the
+/// system.
+const LANGUAGE_KEY: &str = "language";
+
+/// Settings key holding the JSON-encoded [`FailoverPair`]s.
+const FAILOVER_KEY: &str = "failover_pairs";
+
+/// Settings key holding the JSON-encoded [`LaunchOptions`] per server id.
+pub(crate) const LAUNCH_OPTIONS_KEY: &str = "launch_options";
+
+/// Settings key holding the JSON-encoded per-server concurrency limits.
+const CONCURRENCY_LIMITS_KEY: &str = "concurrency_limits";
+/// Settings key holding the JSON-encoded directories offered to each server.
+const SERVER_ROOTS_KEY: &str = "server_roots";
+
+/// How long Test Connection waits for a server to answer.
+const PING_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// How many recent audit entries are kept in memory for display.
+const AUDIT_LOG_LIMIT: usize = 50;
+
+/// How many notifications the notification center keeps.
+pub const NOTIFICATION_HISTORY_LIMIT: usize = 50;
+
+/// Append to the history, dropping the oldest entries beyond `limit`.
+fn record_history(history: &mut Vec<Notification>, notification: Notification, limit: usize) {
+    history.push(notification);
+    if history.len() > limit {
+        let overflow = history.len() - limit;
+        history.drain(..overflow);
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct AppState {
     pub servers: Signal<Vec<McpServer>>,
     pub processes: Signal<HashMap<String, Signal<String>>>,
     pub running_handlers: Signal<HashMap<String, Arc<crate::process::McpHandler>>>,
+    /// Per-server launch options, by server id. Servers without an entry
+    /// use the defaults.
+    pub launch_options: Signal<HashMap<String, LaunchOptions>>,
+    /// Config and launch options each running server was started with.
+    pub launched_configs: Signal<HashMap<String, (McpServer, LaunchOptions)>>,
+    /// Where each server being recorded is saving its session.
+    pub recordings: Signal<HashMap<String, std::path::PathBuf>>,
+    /// Resources watched in consoles, by server id then URI, with how
+    /// many updates the server has reported for each.
+    pub watched_resources: Signal<HashMap<String, HashMap<String, u64>>>,
+    /// How often each server has said one of its lists changed, so open
+    /// consoles know to reload it.
+    pub list_changes: Signal<HashMap<(String, crate::process::ListKind), u64>>,
     pub db: Signal<Option<Database>>,
     pub notifications: Signal<Vec<Notification>>, // New signal
+    pub notification_history: Signal<Vec<Notification>>,
+    pub notification_rules: Signal<NotificationRules>,
+    /// External edits found in editor config files, per file.
+    pub config_drift: Signal<Vec<ConfigDrift>>,
+    /// Newer release of the manager, once found.
+    pub available_update: Signal<Option<crate::updater::Release>>,
+    pub update_checks_enabled: Signal<bool>,
+    /// Seconds a stopping server gets to exit before it is killed.
+    pub stop_grace_secs: Signal<u64>,
+    /// Live session, saved periodically for restore on next launch.
+    pub session: Signal<SessionState>,
+    /// Session loaded at startup, waiting for the UI to apply it.
+    pub pending_restore: Signal<Option<SessionState>>,
+    pub restore_session_enabled: Signal<bool>,
+    /// Name of the open workspace, and all known workspaces.
+    pub workspace: Signal<String>,
+    pub workspaces: Signal<Vec<String>>,
+    /// Changes between the two latest weekly registry snapshots.
+    pub registry_digest: Signal<Option<crate::registry_digest::RegistryDigest>>,
+    pub digest_notifications_enabled: Signal<bool>,
+    /// Open the weekly report when it is generated on Mondays.
+    pub weekly_report_open: Signal<bool>,
+    /// Tools called from consoles, for the quick-launch palette.
+    pub recent_tools: Signal<Vec<crate::recent_tools::RecentTool>>,
+    /// Read-only tool results cached by the hub, and how caching is set up.
+    pub result_cache: Signal<crate::result_cache::ResultCache>,
+    pub result_cache_settings: Signal<ResultCacheSettings>,
+    /// Size past which tool results are cut off and saved to a file.
+    pub response_limits: Signal<ResponseLimitSettings>,
+    pub sampling: Signal<SamplingSettings>,
+    /// Sampling requests from servers awaiting approval, oldest first.
+    pub sampling_requests: Signal<Vec<PendingSampling>>,
+    /// Forms servers asked the user to fill in, oldest first.
+    pub elicitation_requests: Signal<Vec<crate::elicitation::PendingElicitation>>,
+    /// A failed start with a known cause, offered a guided fix.
+    pub pending_fix: Signal<Option<crate::fixes::PendingFix>>,
+    /// Directories each server may access, answered to `roots/list`.
+    pub server_roots: Signal<HashMap<String, Vec<std::path::PathBuf>>>,
+    /// Per-server circuit breakers guarding routed tool calls.
+    pub circuit_breakers: Signal<crate::circuit_breaker::CircuitBreakers>,
+    /// Recent routed call outcomes per server, and when to alert on them.
+    pub request_metrics: Signal<crate::request_metrics::RequestMetrics>,
+    /// Recent liveness pings of each running server.
+    pub probes: Signal<HashMap<String, crate::probe::ProbeHistory>>,
+    /// CPU and memory of each running stdio server, where measurable.
+    pub resource_usage: Signal<HashMap<String, crate::resources::Usage>>,
+    pub error_budget: Signal<ErrorBudgetSettings>,
+    pub health_endpoint: Signal<HealthEndpointSettings>,
+    /// The task serving `/healthz` and `/readyz`, while enabled.
+    health_listener: Signal<Option<Task>>,
+    /// Which stat widgets the dashboard shows.
+    pub dashboard: Signal<DashboardSettings>,
+    /// High-contrast and reduced-motion preferences.
+    pub appearance: Signal<AppearanceSettings>,
+    /// Chosen locale for registry translations; None follows the system.
+    pub language: Signal<Option<String>>,
+    /// Max concurrent tool calls per server id, and the queues enforcing them.
+    pub concurrency_limits: Signal<HashMap<String, usize>>,
+    pub request_queues: Signal<HashMap<String, crate::request_queue::RequestQueue>>,
+    /// Backups that take over tool calls while a primary is unhealthy.
+    pub failover_pairs: Signal<Vec<FailoverPair>>,
+    /// Argument rewrites for hub tool calls, in the order they apply.
+    pub tool_rules: Signal<Vec<ToolRule>>,
+    /// Tags by server id, e.g. `project:<name>` for project imports.
+    pub server_tags: Signal<HashMap<String, Vec<String>>>,
+    /// Project folders whose config files are generated from their tagged servers.
+    pub project_profiles: Signal<Vec<ProjectProfile>>,
+    /// Most recent audit log entries, newest first.
+    pub audit_log: Signal<Vec<AuditEntry>>,
     pub community_servers: Signal<Vec<RegistryItem>>,
     pub research_notes: Signal<Vec<ResearchNote>>,
 }
@@ -26,35 +278,233 @@ pub static APP_STATE: GlobalSignal<AppState> = Signal::global(|| AppState {
     servers: Signal::new(Vec::new()),
     processes: Signal::new(HashMap::new()),
     running_handlers: Signal::new(HashMap::new()),
+    launch_options: Signal::new(HashMap::new()),
+    launched_configs: Signal::new(HashMap::new()),
+    recordings: Signal::new(HashMap::new()),
+    watched_resources: Signal::new(HashMap::new()),
+    list_changes: Signal::new(HashMap::new()),
     db: Signal::new(None),
     notifications: Signal::new(Vec::new()),
+    notification_history: Signal::new(Vec::new()),
+    notification_rules: Signal::new(NotificationRules::default()),
+    config_drift: Signal::new(Vec::new()),
+    available_update: Signal::new(None),
+    update_checks_enabled: Signal::new(true),
+    stop_grace_secs: Signal::new(DEFAULT_STOP_GRACE_SECS),
+    session: Signal::new(SessionState::default()),
+    pending_restore: Signal::new(None),
+    restore_session_enabled: Signal::new(false),
+    workspace: Signal::new(String::new()),
+    workspaces: Signal::new(Vec::new()),
+    registry_digest: Signal::new(None),
+    digest_notifications_enabled: Signal::new(false),
+    weekly_report_open: Signal::new(false),
+    recent_tools: Signal::new(Vec::new()),
+    result_cache: Signal::new(Default::default()),
+    result_cache_settings: Signal::new(ResultCacheSettings::default()),
+    response_limits: Signal::new(ResponseLimitSettings::default()),
+    sampling: Signal::new(SamplingSettings::default()),
+    sampling_requests: Signal::new(Vec::new()),
+    elicitation_requests: Signal::new(Vec::new()),
+    pending_fix: Signal::new(None),
+    server_roots: Signal::new(HashMap::new()),
+    circuit_breakers: Signal::new(Default::default()),
+    request_metrics: Signal::new(Default::default()),
+    probes: Signal::new(HashMap::new()),
+    resource_usage: Signal::new(HashMap::new()),
+    error_budget: Signal::new(ErrorBudgetSettings::default()),
+    health_endpoint: Signal::new(HealthEndpointSettings::default()),
+    health_listener: Signal::new(None),
+    dashboard: Signal::new(DashboardSettings::default()),
+    appearance: Signal::new(AppearanceSettings::default()),
+    language: Signal::new(None),
+    concurrency_limits: Signal::new(HashMap::new()),
+    request_queues: Signal::new(HashMap::new()),
+    failover_pairs: Signal::new(Vec::new()),
+    tool_rules: Signal::new(Vec::new()),
+    server_tags: Signal::new(HashMap::new()),
+    project_profiles: Signal::new(Vec::new()),
+    audit_log: Signal::new(Vec::new()),
     community_servers: Signal::new(Vec::new()),
     research_notes: Signal::new(Vec::new()),
 });
 
 pub fn use_app_state() {
     use_hook(|| {
+        // Asks the login shell for its PATH now rather than on first start
+        spawn(async move {
+            crate::process::fallback_dirs().await;
+        });
         spawn(async move {
-            let db_res = Database::new();
-            match db_res {
+            let workspace = crate::workspace::active();
+            match Database::open_workspace(&workspace) {
                 Ok(db) => {
-                    APP_STATE.write().db.set(Some(db.clone()));
-                    if let Ok(servers) = db.get_servers() {
-                        APP_STATE.write().servers.set(servers);
-                    }
-                    if let Ok(notes) = db.get_research_notes() {
-                        APP_STATE.write().research_notes.set(notes);
-                    }
+                    AppState::load_workspace(workspace, db).await;
+                    spawn(AppState::run_session_saver());
+                    spawn(AppState::run_registry_digest());
+                    spawn(AppState::run_weekly_report());
+                    spawn(AppState::run_server_expiry());
+                    spawn(AppState::run_health_probes());
+                    spawn(AppState::run_resource_monitor());
                 }
                 Err(e) => {
                     tracing::error!("Failed to init DB: {}", e);
                 }
             }
+            let registry_problems = crate::db::official_registry_problems();
+            if let Some(first) = registry_problems.first() {
+                AppState::push_notification(
+                    format!(
+                        "The built-in registry has {} problem(s); the entries affected are hidden from the Explorer. First: {}",
+                        registry_problems.len(),
+                        first
+                    ),
+                    NotificationLevel::Warning,
+                );
+            }
+            spawn(AppState::run_update_checks());
+            AppState::watch_editor_configs().await;
         });
     });
 }
 
 impl AppState {
+    /// Point the app at a workspace database and load everything from it,
+    /// restoring its saved session if that workspace asks for it.
+    async fn load_workspace(workspace: String, db: Database) {
+        APP_STATE.write().db.set(Some(db.clone()));
+        APP_STATE.write().workspace.set(workspace);
+        APP_STATE.write().workspaces.set(crate::workspace::list());
+
+        APP_STATE
+            .write()
+            .servers
+            .set(db.get_servers().unwrap_or_default());
+        APP_STATE
+            .write()
+            .research_notes
+            .set(db.get_research_notes().unwrap_or_default());
+        let rules = Self::load_json_setting(&db, NOTIFICATION_RULES_KEY);
+        APP_STATE.write().notification_rules.set(rules);
+        let recent_tools = Self::load_json_setting(&db, RECENT_TOOLS_KEY);
+        APP_STATE.write().recent_tools.set(recent_tools);
+        let cache_settings = Self::load_json_setting(&db, RESULT_CACHE_KEY);
+        APP_STATE.write().result_cache_settings.set(cache_settings);
+        APP_STATE.write().result_cache.with_mut(|c| c.clear());
+        let response_limits = Self::load_json_setting(&db, RESPONSE_LIMITS_KEY);
+        APP_STATE.write().response_limits.set(response_limits);
+        let sampling = Self::load_json_setting(&db, SAMPLING_KEY);
+        APP_STATE.write().sampling.set(sampling);
+        let error_budget = Self::load_json_setting(&db, ERROR_BUDGET_KEY);
+        APP_STATE.write().error_budget.set(error_budget);
+        let health_endpoint = Self::load_json_setting(&db, HEALTH_ENDPOINT_KEY);
+        APP_STATE.write().health_endpoint.set(health_endpoint);
+        Self::restart_health_endpoint();
+        let dashboard = Self::load_json_setting(&db, DASHBOARD_KEY);
+        APP_STATE.write().dashboard.set(dashboard);
+        let appearance = Self::load_json_setting(&db, APPEARANCE_KEY);
+        APP_STATE.write().appearance.set(appearance);
+        let language = Self::load_json_setting(&db, LANGUAGE_KEY);
+        APP_STATE.write().language.set(language);
+        let failover_pairs = Self::load_json_setting(&db, FAILOVER_KEY);
+        APP_STATE.write().failover_pairs.set(failover_pairs);
+        APP_STATE
+            .write()
+            .tool_rules
+            .set(db.get_tool_rules().unwrap_or_default());
+        APP_STATE
+            .write()
+            .server_tags
+            .set(db.get_server_tags().unwrap_or_default());
+        let project_profiles = Self::load_json_setting(&db, PROJECT_PROFILES_KEY);
+        APP_STATE.write().project_profiles.set(project_profiles);
+        let limits = Self::load_json_setting(&db, CONCURRENCY_LIMITS_KEY);
+        APP_STATE.write().concurrency_limits.set(limits);
+        let server_roots = Self::load_json_setting(&db, SERVER_ROOTS_KEY);
+        APP_STATE.write().server_roots.set(server_roots);
+        let launch_options = Self::load_json_setting(&db, LAUNCH_OPTIONS_KEY);
+        APP_STATE.write().launch_options.set(launch_options);
+        APP_STATE
+            .write()
+            .audit_log
+            .set(db.get_audit_log(AUDIT_LOG_LIMIT).unwrap_or_default());
+        let update_checks =
+            !matches!(db.get_setting(UPDATE_CHECKS_KEY), Ok(Some(v)) if v == "false");
+        APP_STATE.write().update_checks_enabled.set(update_checks);
+        let stop_grace = db
+            .get_setting(STOP_GRACE_KEY)
+            .ok()
+            .flatten()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_STOP_GRACE_SECS);
+        APP_STATE.write().stop_grace_secs.set(stop_grace);
+
+        let digest_notify = matches!(db.get_setting(DIGEST_NOTIFY_KEY), Ok(Some(v)) if v == "true");
+        APP_STATE
+            .write()
+            .digest_notifications_enabled
+            .set(digest_notify);
+        APP_STATE
+            .write()
+            .registry_digest
+            .set(Self::digest_from_db(&db));
+        let report_open =
+            matches!(db.get_setting(WEEKLY_REPORT_OPEN_KEY), Ok(Some(v)) if v == "true");
+        APP_STATE.write().weekly_report_open.set(report_open);
+        // Nothing is running yet, so any open runs were cut short
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(USAGE_HISTORY_DAYS);
+        if let Err(e) = db
+            .close_open_server_runs()
+            .and_then(|_| db.prune_usage_history(&crate::weekly_report::db_time(cutoff)))
+        {
+            tracing::error!("Failed to tidy usage history: {}", e);
+        }
+
+        let restore = matches!(db.get_setting(RESTORE_SESSION_KEY), Ok(Some(v)) if v == "true");
+        APP_STATE.write().restore_session_enabled.set(restore);
+        let saved: Option<SessionState> = Self::load_json_setting(&db, SESSION_KEY);
+        APP_STATE.write().session.set(SessionState::default());
+        if let (true, Some(session)) = (restore, saved) {
+            APP_STATE.write().session.set(session.clone());
+            APP_STATE.write().pending_restore.set(Some(session.clone()));
+            Self::start_servers(&session.running_servers).await;
+        }
+    }
+
+    /// Stop this workspace's servers and open another (created if new).
+    /// Servers, secrets, notes and settings never cross workspaces.
+    pub async fn switch_workspace(name: String) -> Result<(), String> {
+        let workspace = crate::workspace::slugify(&name)?;
+        if *APP_STATE.read().workspace.read() == workspace {
+            return Ok(());
+        }
+        let db = Database::open_workspace(&workspace).map_err(|e| e.to_string())?;
+
+        let running: Vec<String> = APP_STATE
+            .read()
+            .running_handlers
+            .read()
+            .keys()
+            .cloned()
+            .collect();
+        for id in running {
+            Self::stop_server_process(&id).await;
+        }
+        APP_STATE.write().notifications.set(Vec::new());
+        APP_STATE.write().notification_history.set(Vec::new());
+        APP_STATE.write().config_drift.set(Vec::new());
+
+        if let Err(e) = crate::workspace::set_active(&workspace) {
+            tracing::error!("Failed to remember active workspace: {}", e);
+        }
+        Self::load_workspace(workspace.clone(), db).await;
+        Self::push_notification(
+            format!("Switched to workspace \"{}\"", workspace),
+            NotificationLevel::Info,
+        );
+        Ok(())
+    }
+
     pub async fn refresh_servers() {
         let db_opt = APP_STATE.read().db.cloned();
         if let Some(db) = db_opt {
@@ -64,138 +514,2013 @@ impl AppState {
         }
     }
 
+    /// Replace a single server in the list by id, or add a new one right
+    /// after the pinned servers.
+    /// Avoids re-rendering every card that `refresh_servers` would cause.
+    pub fn upsert_server(server: McpServer) {
+        let mut servers = APP_STATE.write().servers;
+        servers.with_mut(|list| upsert_by_id(list, server));
+    }
+
+    pub fn remove_server(id: &str) {
+        let mut servers = APP_STATE.write().servers;
+        servers.with_mut(|list| list.retain(|s| s.id != id));
+    }
+
     pub async fn add_server(args: CreateServerArgs) -> Result<(), String> {
         let db_opt = APP_STATE.read().db.cloned();
         if let Some(db) = db_opt {
-            db.create_server(args).map_err(|e| e.to_string())?;
-            Self::refresh_servers().await;
+            let server = db.create_server(args).map_err(|e| e.to_string())?;
+            Self::upsert_server(server);
             Ok(())
         } else {
             Err("DB not initialized".into())
         }
     }
 
-    pub async fn update_server(id: String, args: UpdateServerArgs) -> Result<(), String> {
-        let db_opt = APP_STATE.read().db.cloned();
-        if let Some(db) = db_opt {
-            db.update_server(id, args).map_err(|e| e.to_string())?;
-            Self::refresh_servers().await;
-            Ok(())
-        } else {
-            Err("DB not initialized".into())
+    /// Add a server that is archived automatically after `hours`.
+    pub async fn add_temporary_server(args: CreateServerArgs, hours: i64) -> Result<(), String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        let server = db.create_server(args).map_err(|e| e.to_string())?;
+        let expires_at = crate::expiry::expires_at(chrono::Utc::now(), hours);
+        let server = db
+            .set_server_expiry(&server.id, Some(&expires_at))
+            .map_err(|e| e.to_string())?;
+        Self::upsert_server(server);
+        Ok(())
+    }
+
+    /// Extract an `.mcpb` bundle into the extensions dir and add its server.
+    pub async fn install_bundle(
+        bundle: crate::mcpb::Bundle,
+        values: std::collections::BTreeMap<String, serde_json::Value>,
+    ) -> Result<(), String> {
+        let errors = crate::mcpb::validate_user_config(&bundle.manifest, &values);
+        if !errors.is_empty() {
+            return Err(errors.join("; "));
         }
+        let dir = crate::mcpb::install_dir(&bundle.manifest).map_err(|e| e.to_string())?;
+        crate::mcpb::extract(&bundle, &dir)?;
+        let args = crate::mcpb::server_args(&bundle.manifest, &dir, &values);
+        let name = args.name.clone();
+        Self::add_server(args).await?;
+        Self::push_notification(format!("Installed {}", name), NotificationLevel::Success);
+        Ok(())
     }
 
-    pub async fn delete_server(id: String) -> Result<(), String> {
-        let db_opt = APP_STATE.read().db.cloned();
-        if let Some(db) = db_opt {
-            db.delete_server(id).map_err(|e| e.to_string())?;
-            Self::refresh_servers().await;
-            Ok(())
-        } else {
-            Err("DB not initialized".into())
+    /// Package a local server as `<name>.mcpb` in the downloads folder and
+    /// show it in the file manager.
+    pub fn export_bundle(server: &McpServer) {
+        let result = crate::mcpb::build_bundle(server).and_then(|(manifest, bytes)| {
+            let dir = dirs::download_dir()
+                .or_else(dirs::home_dir)
+                .ok_or("Could not find downloads folder")?;
+            let path = dir.join(format!("{}.mcpb", manifest.name));
+            std::fs::write(&path, bytes).map_err(|e| e.to_string())?;
+            Ok(path)
+        });
+        match result {
+            Ok(path) => {
+                Self::push_notification(
+                    format!("Saved {}", path.display()),
+                    NotificationLevel::Success,
+                );
+                Self::reveal_in_file_manager(&path);
+            }
+            Err(e) => Self::push_notification(
+                format!("Could not export bundle: {}", e),
+                NotificationLevel::Error,
+            ),
         }
     }
 
-    pub async fn refresh_research_notes() {
-        let db_opt = APP_STATE.read().db.cloned();
-        if let Some(db) = db_opt {
-            if let Ok(notes) = db.get_research_notes() {
-                APP_STATE.write().research_notes.set(notes);
+    /// Ask where to save a binary tool result block and write it there.
+    pub async fn save_content_as(content: Content, file_stem: &str) {
+        let mime = content.mimeType.clone().unwrap_or_default();
+        let bytes = match crate::output_format::decode_data(content.data.as_deref().unwrap_or("")) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                Self::push_notification(
+                    format!("Could not save {}: {}", content.content_type, e),
+                    NotificationLevel::Error,
+                );
+                return;
             }
+        };
+        let file_name = format!(
+            "{}.{}",
+            file_stem,
+            crate::output_format::extension_for(&mime)
+        );
+        let Some(handle) = rfd::AsyncFileDialog::new()
+            .set_file_name(&file_name)
+            .save_file()
+            .await
+        else {
+            return;
+        };
+        match handle.write(&bytes).await {
+            Ok(()) => Self::push_notification(
+                format!("Saved {}", handle.path().display()),
+                NotificationLevel::Success,
+            ),
+            Err(e) => Self::push_notification(
+                format!("Could not save {}: {}", file_name, e),
+                NotificationLevel::Error,
+            ),
         }
     }
 
-    pub async fn save_research_note(note: ResearchNote) -> Result<(), String> {
-        let db_opt = APP_STATE.read().db.cloned();
-        if let Some(db) = db_opt {
-            db.save_research_note(note).map_err(|e| e.to_string())?;
-            Self::refresh_research_notes().await;
-            Ok(())
-        } else {
-            Err("DB not initialized".into())
+    /// Ask where to save the tool catalog of all running servers in
+    /// `format` and write it there. Stopped servers are left out since
+    /// their tools can't be listed.
+    pub async fn export_tool_catalog(format: crate::catalog::CatalogFormat) {
+        let mut running: Vec<(McpServer, Arc<crate::process::McpHandler>)> = {
+            let state = APP_STATE.read();
+            let handlers = state.running_handlers.read();
+            state
+                .servers
+                .read()
+                .iter()
+                .filter_map(|s| handlers.get(&s.id).map(|h| (s.clone(), h.clone())))
+                .collect()
+        };
+        if running.is_empty() {
+            Self::push_notification(
+                "Start a server to export its tools".to_string(),
+                NotificationLevel::Warning,
+            );
+            return;
+        }
+        running.sort_by_key(|(s, _)| s.name.to_lowercase());
+
+        let mut servers = Vec::new();
+        for (server, handler) in running {
+            match handler.list_tools().await {
+                Ok(tools) => servers.push(crate::catalog::CatalogServer {
+                    name: server.name,
+                    description: server.description,
+                    tools,
+                }),
+                Err(e) => tracing::warn!("Could not list tools of {}: {}", server.name, e),
+            }
+        }
+        let text = crate::catalog::render(format, &servers, crate::updater::CURRENT_VERSION);
+
+        let file_name = format!("mcp-tools.{}", format.extension());
+        let Some(handle) = rfd::AsyncFileDialog::new()
+            .set_file_name(&file_name)
+            .save_file()
+            .await
+        else {
+            return;
+        };
+        match handle.write(text.as_bytes()).await {
+            Ok(()) => Self::push_notification(
+                format!(
+                    "Exported tools of {} servers to {}",
+                    servers.len(),
+                    handle.path().display()
+                ),
+                NotificationLevel::Success,
+            ),
+            Err(e) => Self::push_notification(
+                format!("Could not save {}: {}", file_name, e),
+                NotificationLevel::Error,
+            ),
         }
     }
 
-    pub async fn start_server_process(server: McpServer) -> Result<(), String> {
-        // Don't start if already running
-        if APP_STATE
+    /// Save `table` within `range` as CSV where the user picks.
+    pub async fn export_usage(
+        table: crate::usage_export::UsageTable,
+        range: crate::usage_export::DateRange,
+    ) -> Result<(), String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        let csv = crate::usage_export::export(&db, table, range).map_err(|e| e.to_string())?;
+        let Some(handle) = rfd::AsyncFileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_file_name(format!("{}.csv", range.file_stem(table)))
+            .save_file()
+            .await
+        else {
+            return Ok(());
+        };
+        handle
+            .write(csv.as_bytes())
+            .await
+            .map_err(|e| e.to_string())?;
+        Self::push_notification(
+            format!(
+                "Exported {} to {}",
+                table.label().to_lowercase(),
+                handle.path().display()
+            ),
+            NotificationLevel::Success,
+        );
+        Ok(())
+    }
+
+    /// Save changes to a server in one write, checking its alias, working
+    /// directory and Docker settings first. A new request timeout applies
+    /// to a running server straight away; the rest take effect on its
+    /// next start.
+    pub async fn update_server(id: String, mut args: UpdateServerArgs) -> Result<(), String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        if let Some(Some(alias)) = &args.alias {
+            let servers = APP_STATE.read().servers.cloned();
+            args.alias = Some(Some(crate::models::check_alias(alias, &id, &servers)?));
+        }
+        if let Some(Some(cwd)) = &args.cwd {
+            args.cwd = Some(Some(crate::models::check_cwd(cwd)?));
+        }
+        // Mounts are only kept, so only checked, with an image
+        if let Some(Some(image)) = &args.docker_image {
+            args.docker_image = Some(Some(crate::docker::check_image(image)?));
+            if let Some(mounts) = &args.docker_mounts {
+                let mounts = mounts
+                    .iter()
+                    .filter(|m| !m.trim().is_empty())
+                    .map(|m| crate::docker::check_mount(m))
+                    .collect::<Result<Vec<_>, _>>()?;
+                args.docker_mounts = Some(mounts);
+            }
+        }
+        let timeout = args.request_timeout_secs;
+        let server = db
+            .update_server(id.clone(), args)
+            .map_err(|e| e.to_string())?;
+        Self::upsert_server(server);
+
+        let handler = APP_STATE.read().running_handlers.read().get(&id).cloned();
+        if let (Some(secs), Some(handler)) = (timeout, handler) {
+            let timeout = secs
+                .map(|s| std::time::Duration::from_secs(s.into()))
+                .unwrap_or(crate::process::DEFAULT_REQUEST_TIMEOUT);
+            handler.set_request_timeout(timeout);
+        }
+        Ok(())
+    }
+
+    /// Write a new value for env `key` to the given servers in one
+    /// transaction. Returns the servers that changed.
+    pub async fn set_env_value(
+        ids: Vec<String>,
+        key: String,
+        value: String,
+    ) -> Result<Vec<McpServer>, String> {
+        let key = crate::env_rotation::validate_key(&key)?;
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        let updated = db
+            .set_env_value(&ids, key, &value)
+            .map_err(|e| e.to_string())?;
+        for server in &updated {
+            Self::upsert_server(server.clone());
+        }
+        Ok(updated)
+    }
+
+    pub fn get_server_revisions(server_id: &str) -> Result<Vec<ServerRevision>, String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        db.get_server_revisions(server_id)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Restore a server's config from a revision; returns the restored server.
+    pub async fn restore_server_revision(revision_id: i64) -> Result<McpServer, String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        let server = db
+            .restore_server_revision(revision_id)
+            .map_err(|e| e.to_string())?;
+        Self::upsert_server(server.clone());
+        Ok(server)
+    }
+
+    /// Archive a server, stopping it first, or restore it from the archive.
+    /// Archived servers keep their config, presets and history.
+    pub async fn set_server_archived(id: String, archived: bool) -> Result<(), String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        if archived && APP_STATE.read().processes.read().contains_key(&id) {
+            Self::stop_server_process(&id).await;
+        }
+        let mut server = db
+            .set_server_archived(&id, archived)
+            .map_err(|e| e.to_string())?;
+        // A restored temporary server that already expired is kept for good
+        if !archived && crate::expiry::is_expired(&server, chrono::Utc::now()) {
+            server = db.set_server_expiry(&id, None).map_err(|e| e.to_string())?;
+        }
+        Self::upsert_server(server);
+        Ok(())
+    }
+
+    /// Pin a server to the top of the dashboard, or unpin it.
+    pub fn set_server_pinned(id: &str, pinned: bool) -> Result<(), String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        db.set_server_pinned(id, pinned)
+            .map_err(|e| e.to_string())?;
+        let servers = db.get_servers().map_err(|e| e.to_string())?;
+        APP_STATE.write().servers.set(servers);
+        Ok(())
+    }
+
+    /// Move a server to just before `target` on the dashboard, saving the
+    /// new order.
+    pub fn move_server(id: &str, target: &str) -> Result<(), String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        let mut ids: Vec<String> = APP_STATE
             .read()
-            .running_handlers
+            .servers
             .read()
-            .contains_key(&server.id)
-        {
-            return Ok(());
+            .iter()
+            .map(|s| s.id.clone())
+            .collect();
+        move_before(&mut ids, id, target);
+        db.reorder_servers(&ids).map_err(|e| e.to_string())?;
+        let servers = db.get_servers().map_err(|e| e.to_string())?;
+        APP_STATE.write().servers.set(servers);
+        Ok(())
+    }
+
+    /// Make a server temporary for `hours`, or permanent with `None`.
+    pub fn set_server_expiry(id: &str, hours: Option<i64>) -> Result<(), String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        let expires_at = hours.map(|h| crate::expiry::expires_at(chrono::Utc::now(), h));
+        let server = db
+            .set_server_expiry(id, expires_at.as_deref())
+            .map_err(|e| e.to_string())?;
+        Self::upsert_server(server);
+        Ok(())
+    }
+
+    /// Stop and archive temporary servers whose time is up. Runs for the
+    /// lifetime of the app.
+    async fn run_server_expiry() {
+        loop {
+            let now = chrono::Utc::now();
+            let expired: Vec<McpServer> = APP_STATE
+                .read()
+                .servers
+                .read()
+                .iter()
+                .filter(|s| !s.is_archived && crate::expiry::is_expired(s, now))
+                .cloned()
+                .collect();
+            for server in expired {
+                match Self::set_server_archived(server.id.clone(), true).await {
+                    Ok(()) => {
+                        Self::record_audit("expired", Some(&server.id), &server.name);
+                        Self::push_notification(
+                            format!(
+                                "Temporary server {} expired and was archived. Restore it from the Archived section to keep it.",
+                                server.name
+                            ),
+                            NotificationLevel::Info,
+                        );
+                    }
+                    Err(e) => tracing::error!("Failed to archive expired {}: {}", server.name, e),
+                }
+            }
+            tokio::time::sleep(EXPIRY_CHECK_INTERVAL).await;
         }
+    }
 
-        let (log_tx, mut log_rx) = mpsc::channel(100);
-        let log_signal = Signal::new(String::new());
+    /// Ping every running server on an interval, recording how each
+    /// answers and notifying when one stops. Runs for the lifetime of the app.
+    async fn run_health_probes() {
+        loop {
+            tokio::time::sleep(crate::probe::PROBE_INTERVAL).await;
+            let handlers: Vec<(String, Arc<crate::process::McpHandler>)> = APP_STATE
+                .read()
+                .running_handlers
+                .read()
+                .iter()
+                .map(|(id, handler)| (id.clone(), handler.clone()))
+                .collect();
+            let pings = handlers.into_iter().map(|(id, handler)| async move {
+                let start = std::time::Instant::now();
+                let result = handler.ping(PING_TIMEOUT).await.map(|_| start.elapsed());
+                (id, result)
+            });
+            for (id, result) in futures_util::future::join_all(pings).await {
+                // Stopped while its ping was in flight
+                if !APP_STATE.read().running_handlers.read().contains_key(&id) {
+                    continue;
+                }
+                let became_unhealthy = APP_STATE
+                    .write()
+                    .probes
+                    .write()
+                    .entry(id.clone())
+                    .or_default()
+                    .record(result.clone(), chrono::Utc::now());
+                if let (true, Err(e)) = (became_unhealthy, result) {
+                    Self::push_server_notification(
+                        &id,
+                        NotificationEvent::ServerFailure,
+                        format!(
+                            "{} stopped answering health checks: {}",
+                            Self::server_name(&id),
+                            e
+                        ),
+                        NotificationLevel::Warning,
+                    );
+                }
+            }
+        }
+    }
 
-        // Spawn listener for logs
-        let s_id = server.id.clone();
-        let mut s_log_sig = log_signal; // copy signal
-        spawn(async move {
-            while let Some(log) = log_rx.recv().await {
-                let line = match log {
-                    ProcessLog::Stdout(s) => format!("[stdout] {}\n", s),
-                    ProcessLog::Stderr(s) => format!("[stderr] {}\n", s),
+    /// Measure the CPU and memory of every running stdio server on an
+    /// interval. Runs for the lifetime of the app.
+    async fn run_resource_monitor() {
+        use crate::resources::{Sample, Usage};
+        // Last sample per server, with the process it was taken from
+        let mut previous: HashMap<String, (u32, Sample, std::time::Instant)> = HashMap::new();
+        loop {
+            tokio::time::sleep(crate::resources::SAMPLE_INTERVAL).await;
+            let groups: Vec<(String, u32)> = APP_STATE
+                .read()
+                .running_handlers
+                .read()
+                .iter()
+                .filter_map(|(id, handler)| handler.pid().map(|pid| (id.clone(), pid)))
+                .collect();
+            let pids: Vec<u32> = groups.iter().map(|(_, pid)| *pid).collect();
+            let samples = if pids.is_empty() {
+                HashMap::new()
+            } else {
+                tokio::task::spawn_blocking(move || crate::resources::sample(&pids))
+                    .await
+                    .unwrap_or_default()
+            };
+
+            let now = std::time::Instant::now();
+            let mut usage = HashMap::new();
+            let mut latest = HashMap::new();
+            for (id, pid) in groups {
+                let Some(sample) = samples.get(&pid) else {
+                    continue;
                 };
-                // Update the global signal for this process
-                s_log_sig.with_mut(|s| s.push_str(&line));
-                // Also log to tracing
-                tracing::debug!("[{}] {}", s_id, line.trim());
+                if let Some((before_pid, before, at)) = previous.get(&id) {
+                    if *before_pid == pid {
+                        usage.insert(id.clone(), Usage::between(before, sample, now - *at));
+                    }
+                }
+                latest.insert(id, (pid, *sample, now));
             }
-        });
+            previous = latest;
+            if usage != *APP_STATE.read().resource_usage.peek() {
+                APP_STATE.write().resource_usage.set(usage);
+            }
+        }
+    }
 
-        // Store log signal in map
+    pub async fn delete_server(id: String) -> Result<(), String> {
+        let db_opt = APP_STATE.read().db.cloned();
+        if let Some(db) = db_opt {
+            db.delete_server(id.clone()).map_err(|e| e.to_string())?;
+            Self::forget_server(&id);
+            Self::suggest_orphan_cleanup();
+            Ok(())
+        } else {
+            Err("DB not initialized".into())
+        }
+    }
+
+    /// Drop a deleted server from the list and its per-server settings.
+    fn forget_server(id: &str) {
+        Self::remove_server(id);
+        if APP_STATE.read().concurrency_limits.read().contains_key(id) {
+            Self::set_concurrency_limit(id, None);
+        }
+        if APP_STATE.read().launch_options.read().contains_key(id) {
+            Self::set_launch_options(id, LaunchOptions::default());
+        }
+        if APP_STATE.read().server_roots.read().contains_key(id) {
+            let mut roots = APP_STATE.read().server_roots.cloned();
+            roots.remove(id);
+            Self::save_server_roots(roots);
+        }
         APP_STATE
             .write()
-            .processes
+            .tool_rules
+            .with_mut(|rules| rules.retain(|r| r.server_id != id));
+        APP_STATE.write().server_tags.with_mut(|tags| {
+            tags.remove(id);
+        });
+        let pairs = APP_STATE.read().failover_pairs.cloned();
+        if pairs.iter().any(|p| p.primary == id || p.backup == id) {
+            Self::save_failover_pairs(
+                pairs
+                    .into_iter()
+                    .filter(|p| p.primary != id && p.backup != id)
+                    .collect(),
+            );
+        }
+    }
+
+    /// Merge a set of duplicate servers into one, stopping the duplicates
+    /// being removed first. Returns the merged server.
+    pub async fn merge_duplicates(plan: MergePlan) -> Result<McpServer, String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        for duplicate in &plan.remove {
+            if APP_STATE
+                .read()
+                .processes
+                .read()
+                .contains_key(&duplicate.id)
+            {
+                Self::stop_server_process(&duplicate.id).await;
+            }
+        }
+        let server = db.merge_servers(&plan).map_err(|e| e.to_string())?;
+        for duplicate in &plan.remove {
+            Self::forget_server(&duplicate.id);
+        }
+        Self::upsert_server(server.clone());
+        Self::suggest_orphan_cleanup();
+        APP_STATE
             .write()
-            .insert(server.id.clone(), log_signal);
+            .server_tags
+            .set(db.get_server_tags().unwrap_or_default());
+        let entries = db.get_audit_log(AUDIT_LOG_LIMIT).unwrap_or_default();
+        APP_STATE.write().audit_log.set(entries);
+        Ok(server)
+    }
+
+    /// Managed install directories no server in any workspace uses, since
+    /// workspaces share one storage root.
+    pub fn orphaned_dirs() -> Result<Vec<std::path::PathBuf>, String> {
+        let current = APP_STATE.read().workspace.cloned();
+        let mut servers = APP_STATE.read().servers.cloned();
+        for workspace in crate::workspace::list() {
+            if workspace != current {
+                let db = Database::open_workspace(&workspace).map_err(|e| e.to_string())?;
+                servers.extend(db.get_servers().map_err(|e| e.to_string())?);
+            }
+        }
+        crate::storage::orphans(&servers).map_err(|e| e.to_string())
+    }
+
+    /// Point at Settings → Storage when removing servers left directories
+    /// no server uses, so they can be reviewed before anything is deleted.
+    fn suggest_orphan_cleanup() {
+        match Self::orphaned_dirs() {
+            Ok(dirs) if !dirs.is_empty() => Self::push_notification(
+                format!(
+                    "{} install {} no longer used; review and clean up in Settings → Storage",
+                    dirs.len(),
+                    if dirs.len() == 1 {
+                        "directory is"
+                    } else {
+                        "directories are"
+                    }
+                ),
+                NotificationLevel::Info,
+            ),
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to check for orphaned directories: {}", e),
+        }
+    }
+
+    /// Delete managed install directories no server uses any more.
+    /// Returns how many bytes were freed.
+    pub fn remove_orphaned_dirs() -> u64 {
+        let result = Self::orphaned_dirs()
+            .and_then(|dirs| crate::storage::remove_dirs(&dirs).map_err(|e| e.to_string()));
+        match result {
+            Ok(freed) => freed,
+            Err(e) => {
+                tracing::error!("Failed to remove orphaned directories: {}", e);
+                0
+            }
+        }
+    }
 
-        let handler = if server.server_type == "sse" {
-            let url = server.url.clone().ok_or("SSE server must have a URL")?;
-            let sse_client = crate::process::McpSseClient::start(url, log_tx).await?;
-            Arc::new(crate::process::McpHandler::Sse(sse_client))
+    /// Ask for a new storage root for installs; `reset` goes back to the
+    /// data dir. Returns the root in use afterwards.
+    pub async fn choose_storage_root(reset: bool) -> Result<std::path::PathBuf, String> {
+        let root = if reset {
+            None
         } else {
-            let env_map = server.env.unwrap_or_default();
-            let cmd = server.command.ok_or("No command specified")?;
-            let args = server.args.unwrap_or_default();
+            match rfd::AsyncFileDialog::new().pick_folder().await {
+                Some(handle) => Some(handle.path().to_path_buf()),
+                None => return crate::storage::root().map_err(|e| e.to_string()),
+            }
+        };
+        crate::storage::set_root(root.as_deref()).map_err(|e| e.to_string())?;
+        crate::storage::root().map_err(|e| e.to_string())
+    }
+
+    /// Poll known editor config files and re-detect drift whenever one
+    /// changes on disk. Runs for the lifetime of the app.
+    async fn watch_editor_configs() {
+        let mut seen: HashMap<std::path::PathBuf, Option<std::time::SystemTime>> = HashMap::new();
+        loop {
+            for (editor, path) in crate::editor_config::known_config_paths() {
+                let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+                let previous = seen.insert(path.clone(), modified);
+                if matches!(previous, Some(prev) if prev != modified) {
+                    let drift = Self::detect_config_drift(editor, path);
+                    if !drift.entries.is_empty() {
+                        Self::notify(
+                            None,
+                            NotificationEvent::ConfigChange,
+                            format!(
+                                "{} config was edited outside the manager ({} server(s) differ)",
+                                drift.editor,
+                                drift.entries.len()
+                            ),
+                            NotificationLevel::Info,
+                        );
+                    }
+                }
+            }
+            tokio::time::sleep(CONFIG_WATCH_INTERVAL).await;
+        }
+    }
 
-            let proc =
-                McpProcess::start(server.id.clone(), cmd, args, Some(env_map), log_tx).await?;
-            Arc::new(crate::process::McpHandler::Stdio(proc))
+    /// Re-read one editor config and store its drift against the managed servers.
+    fn detect_config_drift(editor: &'static str, path: std::path::PathBuf) -> ConfigDrift {
+        let servers = APP_STATE.read().servers.cloned();
+        let entries = match crate::editor_config::read_config(&path) {
+            Ok(config) => crate::editor_config::detect_drift(&config, &servers),
+            Err(e) => {
+                tracing::warn!("Could not read {}: {}", path.display(), e);
+                Vec::new()
+            }
+        };
+        let drift = ConfigDrift {
+            editor,
+            path,
+            entries,
         };
 
-        let mut handlers = APP_STATE.write().running_handlers;
-        handlers.write().insert(server.id, handler);
-        tracing::info!("Started server {}", server.name);
-        Ok(())
+        let mut all = APP_STATE.write().config_drift;
+        all.with_mut(|list| {
+            list.retain(|d| d.path != drift.path);
+            if !drift.entries.is_empty() {
+                list.push(drift.clone());
+            }
+        });
+        drift
+    }
+
+    /// Start a server, reporting failures in the notification center and
+    /// offering a guided fix for the ones with a known cause.
+    pub async fn start_server_with_notification(server: McpServer) {
+        let id = server.id.clone();
+        let name = server.name.clone();
+        if let Err(e) = Self::start_server_process(server.clone()).await {
+            Self::push_server_notification(
+                &id,
+                NotificationEvent::ServerFailure,
+                format!("Failed to start {}: {}", name, e),
+                NotificationLevel::Error,
+            );
+            tokio::time::sleep(FAILURE_LOG_SETTLE).await;
+            let log = APP_STATE
+                .read()
+                .processes
+                .read()
+                .get(&id)
+                .map(|log| log.cloned())
+                .unwrap_or_default();
+            if let Some(failure) = crate::fixes::diagnose(&server, &e, &log) {
+                let fix = crate::fixes::PendingFix { server, failure };
+                APP_STATE.write().pending_fix.set(Some(fix));
+            }
+        }
+    }
+
+    /// Run a guided fix's command, returning what it printed.
+    pub async fn run_fix_command(command: &str) -> Result<Vec<String>, String> {
+        let run = crate::hooks::run(command, &HashMap::new(), crate::fixes::FIX_TIMEOUT).await;
+        match run.error {
+            None => Ok(run.output),
+            Some(e) => {
+                let last = run
+                    .output
+                    .last()
+                    .map(|line| format!(": {}", line))
+                    .unwrap_or_default();
+                Err(format!("The fix {}{}", e, last))
+            }
+        }
+    }
+
+    /// Close the guided fix and start its server again, with any settings
+    /// changed since.
+    pub async fn retry_pending_fix() {
+        let Some(fix) = APP_STATE.write().pending_fix.take() else {
+            return;
+        };
+        let server = APP_STATE
+            .read()
+            .servers
+            .read()
+            .iter()
+            .find(|s| s.id == fix.server.id)
+            .cloned()
+            .unwrap_or(fix.server);
+        Self::start_server_with_notification(server).await;
+    }
+
+    /// Start several servers by id, e.g. at launch. Unknown ids are skipped.
+    pub async fn start_servers(ids: &[String]) {
+        let servers = APP_STATE.read().servers.cloned();
+        for id in ids {
+            if let Some(server) = servers.iter().find(|s| &s.id == id) {
+                Self::start_server_with_notification(server.clone()).await;
+            }
+        }
+    }
+
+    /// Update the live session. Persisted by `run_session_saver`.
+    pub fn update_session(change: impl FnOnce(&mut SessionState)) {
+        let mut session = APP_STATE.write().session;
+        session.with_mut(change);
+    }
+
+    /// Periodically record running servers and save the session if it changed.
+    async fn run_session_saver() {
+        let mut last_saved = String::new();
+        loop {
+            tokio::time::sleep(SESSION_SAVE_INTERVAL).await;
+            let mut running: Vec<String> = APP_STATE
+                .read()
+                .running_handlers
+                .read()
+                .keys()
+                .cloned()
+                .collect();
+            running.sort();
+            Self::update_session(|s| s.running_servers = running);
+
+            let json = serde_json::to_string(&*APP_STATE.read().session.read()).unwrap_or_default();
+            if json != last_saved {
+                let db_opt = APP_STATE.read().db.cloned();
+                if let Some(db) = db_opt {
+                    match db.set_setting(SESSION_KEY, &json) {
+                        Ok(()) => last_saved = json,
+                        Err(e) => tracing::error!("Failed to save session: {}", e),
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn set_stop_grace_secs(secs: u64) {
+        Self::save_setting(STOP_GRACE_KEY, &secs.to_string());
+        APP_STATE.write().stop_grace_secs.set(secs);
+    }
+
+    pub fn set_restore_session_enabled(enabled: bool) {
+        Self::save_setting(RESTORE_SESSION_KEY, &enabled.to_string());
+        APP_STATE.write().restore_session_enabled.set(enabled);
+    }
+
+    fn save_setting(key: &str, value: &str) {
+        let db_opt = APP_STATE.read().db.cloned();
+        if let Some(db) = db_opt {
+            if let Err(e) = db.set_setting(key, value) {
+                tracing::error!("Failed to save setting {}: {}", key, e);
+            }
+        }
+    }
+
+    /// The JSON setting `key`, or the default if it's missing or unreadable.
+    pub(crate) fn load_json_setting<T: serde::de::DeserializeOwned + Default>(
+        db: &Database,
+        key: &str,
+    ) -> T {
+        db.get_setting(key)
+            .ok()
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_json_setting<T: serde::Serialize + ?Sized>(key: &str, value: &T) {
+        match serde_json::to_string(value) {
+            Ok(json) => Self::save_setting(key, &json),
+            Err(e) => tracing::error!("Failed to serialize setting {}: {}", key, e),
+        }
+    }
+
+    /// Digest of the two newest registry snapshots in `db`.
+    fn digest_from_db(db: &Database) -> Option<crate::registry_digest::RegistryDigest> {
+        match db.get_registry_snapshots(2).ok()?.as_slice() {
+            [newer, older] => Some(crate::registry_digest::diff(older, newer)),
+            _ => None,
+        }
+    }
+
+    /// Snapshot freshly fetched registry items if the last snapshot is a
+    /// week old, then refresh the digest.
+    pub fn record_registry_snapshot(items: &[RegistryItem]) {
+        use crate::registry_digest::{snapshot_due, snapshot_from, SNAPSHOTS_KEPT};
+        let Some(db) = APP_STATE.read().db.cloned() else {
+            return;
+        };
+        let last = db.get_registry_snapshots(1).unwrap_or_default();
+        let now = chrono::Utc::now();
+        if items.is_empty() || !snapshot_due(last.first().map(|s| s.taken_at.as_str()), now) {
+            return;
+        }
+        if let Err(e) = db.save_registry_snapshot(&snapshot_from(items, now), SNAPSHOTS_KEPT) {
+            tracing::error!("Failed to save registry snapshot: {}", e);
+            return;
+        }
+        APP_STATE
+            .write()
+            .registry_digest
+            .set(Self::digest_from_db(&db));
+    }
+
+    /// On Mondays, post the digest as a native notification once, if enabled.
+    async fn run_registry_digest() {
+        loop {
+            let now = chrono::Local::now();
+            let enabled = *APP_STATE.read().digest_notifications_enabled.read();
+            let db_opt = APP_STATE.read().db.cloned();
+            if let (true, true, Some(db)) =
+                (enabled, crate::registry_digest::is_digest_day(now), db_opt)
+            {
+                let today = now.format("%Y-%m-%d").to_string();
+                let already = db.get_setting(DIGEST_NOTIFIED_ON_KEY).ok().flatten();
+                if already.as_deref() != Some(today.as_str()) {
+                    if let Some(digest) = Self::digest_from_db(&db).filter(|d| !d.is_empty()) {
+                        send_native_notification(&digest.summary());
+                    }
+                    Self::save_setting(DIGEST_NOTIFIED_ON_KEY, &today);
+                }
+            }
+            tokio::time::sleep(DIGEST_CHECK_INTERVAL).await;
+        }
+    }
+
+    pub fn set_digest_notifications_enabled(enabled: bool) {
+        Self::save_setting(DIGEST_NOTIFY_KEY, &enabled.to_string());
+        APP_STATE.write().digest_notifications_enabled.set(enabled);
+    }
+
+    /// On Mondays, save the weekly report once, opening it if enabled.
+    async fn run_weekly_report() {
+        loop {
+            let now = chrono::Local::now();
+            let db_opt = APP_STATE.read().db.cloned();
+            if let (true, Some(db)) = (crate::registry_digest::is_digest_day(now), db_opt) {
+                let today = now.format("%Y-%m-%d").to_string();
+                let already = db.get_setting(WEEKLY_REPORT_ON_KEY).ok().flatten();
+                if already.as_deref() != Some(today.as_str()) {
+                    match Self::generate_weekly_report() {
+                        Ok(path) if *APP_STATE.read().weekly_report_open.read() => {
+                            Self::open_url(&path.to_string_lossy())
+                        }
+                        Ok(_) => {}
+                        Err(e) => tracing::error!("Failed to write weekly report: {}", e),
+                    }
+                    Self::save_setting(WEEKLY_REPORT_ON_KEY, &today);
+                }
+            }
+            tokio::time::sleep(DIGEST_CHECK_INTERVAL).await;
+        }
+    }
+
+    /// Write the report for the past week to the reports directory as
+    /// Markdown and HTML, returning the HTML file.
+    pub fn generate_weekly_report() -> Result<std::path::PathBuf, String> {
+        use crate::weekly_report::{db_time, reports_dir, WeeklyReport, REPORT_PERIOD_DAYS};
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        let now = chrono::Utc::now();
+        let since = db_time(now - chrono::Duration::days(REPORT_PERIOD_DAYS));
+        let servers = APP_STATE.read().servers.cloned();
+        let runs = db
+            .get_server_runs_since(&since)
+            .map_err(|e| e.to_string())?;
+        let calls = db.get_tool_calls_since(&since).map_err(|e| e.to_string())?;
+
+        let mut report = WeeklyReport::new(now, &servers, &runs, &calls);
+        report.audit = db
+            .get_audit_log(AUDIT_LOG_LIMIT)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|entry| entry.created_at >= since)
+            .rev()
+            .collect();
+        report.registry = APP_STATE.read().registry_digest.cloned();
+        report.update = APP_STATE
+            .read()
+            .available_update
+            .read()
+            .as_ref()
+            .map(|release| release.tag_name.clone());
+
+        let dir = reports_dir().map_err(|e| e.to_string())?;
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let stem = report.file_stem();
+        std::fs::write(dir.join(format!("{}.md", stem)), report.to_markdown())
+            .map_err(|e| e.to_string())?;
+        let html = dir.join(format!("{}.html", stem));
+        std::fs::write(&html, report.to_html()).map_err(|e| e.to_string())?;
+        Ok(html)
+    }
+
+    pub fn set_weekly_report_open(enabled: bool) {
+        Self::save_setting(WEEKLY_REPORT_OPEN_KEY, &enabled.to_string());
+        APP_STATE.write().weekly_report_open.set(enabled);
+    }
+
+    /// Check for a newer release now and then every day, unless opted out.
+    async fn run_update_checks() {
+        loop {
+            if *APP_STATE.read().update_checks_enabled.read() {
+                Self::check_for_updates(false).await;
+            }
+            tokio::time::sleep(crate::updater::CHECK_INTERVAL).await;
+        }
+    }
+
+    /// Look for a newer release. Manual checks also report "up to date"
+    /// and errors; background checks stay quiet unless an update exists.
+    pub async fn check_for_updates(manual: bool) {
+        match crate::updater::check_latest().await {
+            Ok(Some(release)) => {
+                let already_known = APP_STATE
+                    .read()
+                    .available_update
+                    .read()
+                    .as_ref()
+                    .map(|r| r.tag_name == release.tag_name)
+                    .unwrap_or(false);
+                if manual || !already_known {
+                    Self::push_notification(
+                        format!("Open MCP Manager {} is available", release.version()),
+                        NotificationLevel::Info,
+                    );
+                }
+                APP_STATE.write().available_update.set(Some(release));
+            }
+            Ok(None) => {
+                if manual {
+                    Self::push_notification(
+                        format!(
+                            "You're on the latest version ({})",
+                            crate::updater::CURRENT_VERSION
+                        ),
+                        NotificationLevel::Success,
+                    );
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Update check failed: {}", e);
+                if manual {
+                    Self::push_notification(
+                        format!("Update check failed: {}", e),
+                        NotificationLevel::Error,
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn set_update_checks_enabled(enabled: bool) {
+        Self::save_setting(UPDATE_CHECKS_KEY, &enabled.to_string());
+        APP_STATE.write().update_checks_enabled.set(enabled);
+    }
+
+    /// Download the installer for this platform and launch it.
+    pub async fn install_update(release: crate::updater::Release) -> Result<(), String> {
+        let asset = crate::updater::pick_asset(
+            &release.assets,
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+        )
+        .ok_or("No installer for this platform in the release")?
+        .clone();
+
+        Self::push_notification(
+            format!("Downloading {}...", asset.name),
+            NotificationLevel::Info,
+        );
+        let checksums = crate::updater::checksum_asset(&release.assets, &asset);
+        let path = crate::updater::download_asset(&asset, checksums).await?;
+        crate::updater::launch_installer(&path)?;
+        Self::push_notification(
+            "Installer started. Close the manager to finish updating.".to_string(),
+            NotificationLevel::Success,
+        );
+        Ok(())
+    }
+
+    /// Show a file or folder in the OS file manager, reporting failures as a toast.
+    pub fn reveal_in_file_manager(path: &std::path::Path) {
+        if let Err(e) = crate::platform::reveal(path) {
+            Self::push_notification(
+                format!("Could not open {}: {}", path.display(), e),
+                NotificationLevel::Error,
+            );
+        }
+    }
+
+    /// Copy `text` to the system clipboard via the webview.
+    pub fn copy_to_clipboard(text: String) {
+        spawn(async move {
+            let eval = document::eval("navigator.clipboard.writeText(await dioxus.recv());");
+            let _ = eval.send(text);
+        });
+    }
+
+    /// Open a web page in the default browser, reporting failures as a toast.
+    pub fn open_url(url: &str) {
+        if let Err(e) = open::that_detached(url) {
+            Self::push_notification(
+                format!("Could not open {}: {}", url, e),
+                NotificationLevel::Error,
+            );
+        }
+    }
+
+    pub fn dismiss_config_drift(path: &std::path::Path) {
+        let mut all = APP_STATE.write().config_drift;
+        all.with_mut(|list| list.retain(|d| d.path != path));
+    }
+
+    /// Pick a project folder and import the servers its editor configs
+    /// define, tagged with the project. Returns a summary of what changed.
+    pub async fn import_project() -> Result<String, String> {
+        let Some(handle) = rfd::AsyncFileDialog::new().pick_folder().await else {
+            return Ok(String::new());
+        };
+        Self::import_project_dir(handle.path())
+    }
+
+    /// Import the servers the project at `dir` defines and keep the
+    /// folder as a project profile. Importing again picks up entries added
+    /// to its config files since.
+    pub fn import_project_dir(dir: &std::path::Path) -> Result<String, String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        let scan = crate::project_import::scan(dir);
+        Self::bind_project_profile(dir);
+        let tag = crate::project_import::project_tag(dir);
+        if scan.files.is_empty() && scan.errors.is_empty() {
+            return Ok(format!("Added profile {}; it has no MCP config yet", tag));
+        }
+
+        let existing = APP_STATE.read().servers.cloned();
+        let (mut added, mut tagged) = (0, 0);
+        for action in crate::project_import::plan_for(dir, &scan, &existing) {
+            let server_id = match action {
+                ImportAction::Create(args) => {
+                    let server = db.create_server(args).map_err(|e| e.to_string())?;
+                    let id = server.id.clone();
+                    Self::upsert_server(server);
+                    added += 1;
+                    id
+                }
+                ImportAction::Tag { server_id } => {
+                    tagged += 1;
+                    server_id
+                }
+            };
+            db.add_server_tag(&server_id, &tag)
+                .map_err(|e| e.to_string())?;
+        }
+        APP_STATE
+            .write()
+            .server_tags
+            .set(db.get_server_tags().map_err(|e| e.to_string())?);
+
+        let mut summary = format!(
+            "{}: added {} server(s), tagged {} already managed",
+            tag, added, tagged
+        );
+        if !scan.errors.is_empty() {
+            summary.push_str(&format!("; skipped {}", scan.errors.join(", ")));
+        }
+        Ok(summary)
+    }
+
+    fn bind_project_profile(dir: &std::path::Path) {
+        let mut profiles = APP_STATE.read().project_profiles.cloned();
+        if profiles.iter().all(|p| p.path != dir) {
+            profiles.push(ProjectProfile::new(dir.to_path_buf()));
+            Self::save_project_profiles(profiles);
+        }
+    }
+
+    /// Forget a project profile. Its servers and their tags stay.
+    pub fn remove_project_profile(path: &std::path::Path) {
+        let mut profiles = APP_STATE.read().project_profiles.cloned();
+        profiles.retain(|p| p.path != path);
+        Self::save_project_profiles(profiles);
+    }
+
+    pub fn set_profile_config_file(path: &std::path::Path, config_file: &str) {
+        let mut profiles = APP_STATE.read().project_profiles.cloned();
+        for profile in profiles.iter_mut().filter(|p| p.path == path) {
+            profile.config_file = config_file.to_string();
+        }
+        Self::save_project_profiles(profiles);
+    }
+
+    pub fn save_project_profiles(profiles: Vec<ProjectProfile>) {
+        Self::save_json_setting(PROJECT_PROFILES_KEY, &profiles);
+        APP_STATE.write().project_profiles.set(profiles);
+    }
+
+    /// Add a server to a profile by tagging it, or take it out.
+    pub fn set_server_tagged(server_id: &str, tag: &str, tagged: bool) -> Result<(), String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        if tagged {
+            db.add_server_tag(server_id, tag)
+        } else {
+            db.remove_server_tag(server_id, tag)
+        }
+        .map_err(|e| e.to_string())?;
+        APP_STATE
+            .write()
+            .server_tags
+            .set(db.get_server_tags().map_err(|e| e.to_string())?);
+        Ok(())
+    }
+
+    /// What generating `profile`'s config file would change in it.
+    pub fn project_profile_plan(profile: &ProjectProfile) -> Result<MigrationPlan, String> {
+        let config =
+            crate::editor_config::read_config(&profile.config_path()).map_err(|e| e.to_string())?;
+        let servers = APP_STATE.read().servers.cloned();
+        let tags = APP_STATE.read().server_tags.cloned();
+        let profile_servers = profile.servers(&servers, &tags);
+        Ok(crate::project_import::profile_plan(
+            &config,
+            &profile_servers,
+            &servers,
+        ))
+    }
+
+    /// Write `profile`'s servers into the project's config file, keeping
+    /// the previous file as a backup. Returns the file written.
+    pub fn generate_project_config(profile: &ProjectProfile) -> Result<std::path::PathBuf, String> {
+        let plan = Self::project_profile_plan(profile)?;
+        let path = profile.config_path();
+        crate::editor_config::write_migration(&path, &plan).map_err(|e| e.to_string())?;
+        Ok(path)
+    }
+
+    /// Bring one externally edited entry into the manager.
+    pub async fn import_drift_entry(drift: ConfigDrift, entry: DriftEntry) -> Result<(), String> {
+        let args = crate::editor_config::entry_to_args(&entry.name, &entry.entry);
+        match entry.kind {
+            DriftKind::NotInManager => Self::add_server(args).await?,
+            DriftKind::Changed => {
+                let existing = APP_STATE
+                    .read()
+                    .servers
+                    .read()
+                    .iter()
+                    .find(|s| s.name == entry.name)
+                    .map(|s| s.id.clone())
+                    .ok_or("Server no longer exists")?;
+                let update = UpdateServerArgs {
+                    name: None,
+                    server_type: Some(args.server_type),
+                    command: args.command,
+                    args: args.args,
+                    url: args.url,
+                    env: args.env,
+                    description: None,
+                    is_active: None,
+                    ..Default::default()
+                };
+                Self::update_server(existing, update).await?;
+            }
+        }
+        Self::detect_config_drift(drift.editor, drift.path);
+        Ok(())
+    }
+
+    /// Remember a tool call for quick relaunch with the same arguments.
+    pub fn record_recent_tool(server: &McpServer, tool: &str, args: &str) {
+        let mut recent = APP_STATE.write().recent_tools;
+        recent.with_mut(|list| {
+            crate::recent_tools::record(
+                list,
+                &server.id,
+                &server.name,
+                tool,
+                args,
+                chrono::Utc::now(),
+            )
+        });
+        Self::save_json_setting(RECENT_TOOLS_KEY, &*recent.read());
+    }
+
+    pub fn get_tool_presets(server_id: &str, tool: &str) -> Result<Vec<ToolPreset>, String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        db.get_tool_presets(server_id, tool)
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn save_tool_preset(preset: ToolPreset) -> Result<(), String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        db.save_tool_preset(&preset).map_err(|e| e.to_string())
+    }
+
+    pub fn delete_tool_preset(server_id: &str, tool: &str, name: &str) -> Result<(), String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        db.delete_tool_preset(server_id, tool, name)
+            .map_err(|e| e.to_string())
+    }
+
+    pub async fn refresh_research_notes() {
+        let db_opt = APP_STATE.read().db.cloned();
+        if let Some(db) = db_opt {
+            if let Ok(notes) = db.get_research_notes() {
+                APP_STATE.write().research_notes.set(notes);
+            }
+        }
+    }
+
+    pub async fn save_research_note(note: ResearchNote) -> Result<(), String> {
+        let db_opt = APP_STATE.read().db.cloned();
+        if let Some(db) = db_opt {
+            db.save_research_note(note).map_err(|e| e.to_string())?;
+            Self::refresh_research_notes().await;
+            Ok(())
+        } else {
+            Err("DB not initialized".into())
+        }
+    }
+
+    pub async fn start_server_process(server: McpServer) -> Result<(), String> {
+        // Don't start if already running
+        if APP_STATE
+            .read()
+            .running_handlers
+            .read()
+            .contains_key(&server.id)
+        {
+            return Ok(());
+        }
+        let archived = server.is_archived
+            || APP_STATE
+                .read()
+                .servers
+                .read()
+                .iter()
+                .any(|s| s.id == server.id && s.is_archived);
+        if archived {
+            return Err(format!("{} is archived; restore it first", server.name));
+        }
+
+        let (log_tx, mut log_rx) = mpsc::channel(100);
+        let log_signal = Signal::new(String::new());
+        // Whether the server has printed anything yet
+        let heard = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        // Spawn listener for logs
+        let s_id = server.id.clone();
+        let mut s_log_sig = log_signal; // copy signal
+        let heard_from = heard.clone();
+        spawn(async move {
+            while let Some(entry) = log_rx.recv().await {
+                heard_from.store(true, std::sync::atomic::Ordering::Relaxed);
+                let line = match (entry.log, entry.request_id) {
+                    (ProcessLog::Stdout(s), _) => format!("[stdout] {}", s),
+                    (ProcessLog::Stderr(s), Some(id)) => {
+                        format!("{} {}", crate::log_time::request_tag(id), s)
+                    }
+                    (ProcessLog::Stderr(s), None) => format!("[stderr] {}", s),
+                    (ProcessLog::Exit(how), _) => format!("[exit] Process {}", how),
+                    (
+                        ProcessLog::Message {
+                            level,
+                            logger,
+                            text,
+                        },
+                        _,
+                    ) => crate::log_time::message_line(level, logger.as_deref(), &text),
+                };
+                let line = format!("{}\n", crate::log_time::stamp(entry.at, &line));
+                // Update the global signal for this process
+                s_log_sig.with_mut(|s| s.push_str(&line));
+                // Also log to tracing
+                tracing::debug!("[{}] {}", s_id, line.trim());
+            }
+        });
+
+        // Store log signal in map
+        APP_STATE
+            .write()
+            .processes
+            .write()
+            .insert(server.id.clone(), log_signal);
+
+        let options = Self::launch_options(&server.id);
+        let launched = (server.clone(), options.clone());
+        if let Some(hook) = &options.pre_start {
+            let env = server.env.clone().unwrap_or_default();
+            let run = crate::hooks::run(hook, &env, crate::hooks::HOOK_TIMEOUT).await;
+            Self::append_log_lines(&server.id, log_signal, "pre-start", &run.output);
+            if let Some(e) = run.error {
+                APP_STATE.write().processes.write().remove(&server.id);
+                let last = run
+                    .output
+                    .last()
+                    .map(|line| format!(": {}", line))
+                    .unwrap_or_default();
+                return Err(format!("Pre-start hook for {} {}{}", server.name, e, last));
+            }
+        }
+
+        let handler = Arc::new(crate::process::start_handler(&server, &options, log_tx).await?);
+
+        let initialize = tokio::time::timeout(INITIALIZE_TIMEOUT, handler.initialize());
+        tokio::pin!(initialize);
+        let initialized = tokio::select! {
+            result = &mut initialize => result,
+            _ = tokio::time::sleep(QUIET_START_WARNING) => {
+                if !server.is_remote() && !heard.load(std::sync::atomic::Ordering::Relaxed) {
+                    let hint = if options.pty {
+                        ""
+                    } else {
+                        " Try terminal mode in its settings."
+                    };
+                    Self::push_server_notification(
+                        &server.id,
+                        NotificationEvent::General,
+                        format!(
+                            "{} has printed nothing for {}s; this server may require interactive input.{}",
+                            server.name,
+                            QUIET_START_WARNING.as_secs(),
+                            hint
+                        ),
+                        NotificationLevel::Warning,
+                    );
+                }
+                initialize.await
+            }
+        }
+        .unwrap_or_else(|_| Err("Timed out waiting for initialize".to_string()));
+        if let Err(e) = initialized {
+            let _ = handler.kill().await;
+            return Err(format!("{} failed the MCP handshake: {}", server.name, e));
+        }
+
+        let mut notifications = handler.notifications();
+        let server_requests = handler.server_requests();
+        let exited = handler.exited();
+        let mut handlers = APP_STATE.write().running_handlers;
+        handlers.write().insert(server.id.clone(), handler.clone());
+        if let Some(mut exited) = exited {
+            let s_id = server.id.clone();
+            spawn(async move {
+                let exit = match exited.wait_for(Option::is_some).await {
+                    Ok(exit) => exit.clone(),
+                    Err(_) => return,
+                };
+                if let Some(exit) = exit.filter(|e| !e.requested) {
+                    Self::handle_unexpected_exit(&s_id, &handler, exit);
+                }
+            });
+        }
+        if let Some(mut requests) = server_requests {
+            let s_id = server.id.clone();
+            let s_name = server.name.clone();
+            spawn(async move {
+                loop {
+                    match requests.recv().await {
+                        Ok(request) => Self::handle_server_request(&s_id, &s_name, request),
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                        Err(_) => break,
+                    }
+                }
+                // The server is gone; nothing is left to answer
+                APP_STATE
+                    .write()
+                    .sampling_requests
+                    .with_mut(|pending| pending.retain(|p| p.server_id != s_id));
+                APP_STATE
+                    .write()
+                    .elicitation_requests
+                    .with_mut(|pending| pending.retain(|p| p.server_id != s_id));
+            });
+        }
+        let s_id = server.id.clone();
+        spawn(async move {
+            loop {
+                match notifications.recv().await {
+                    Ok(n) => Self::handle_server_notification(&s_id, n),
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(_) => break,
+                }
+                if !APP_STATE.read().running_handlers.read().contains_key(&s_id) {
+                    break;
+                }
+            }
+        });
+        if let Some(db) = APP_STATE.read().db.cloned() {
+            if let Err(e) = db.start_server_run(&server.id) {
+                tracing::error!("Failed to log server start: {}", e);
+            }
+        }
+        APP_STATE
+            .write()
+            .launched_configs
+            .write()
+            .insert(server.id, launched);
+        tracing::info!("Started server {}", server.name);
+        Ok(())
+    }
+
+    /// A stdio server's process ended without being asked to: forget it
+    /// was running and say why, with the last thing it printed.
+    fn handle_unexpected_exit(
+        id: &str,
+        handler: &Arc<crate::process::McpHandler>,
+        exit: ProcessExit,
+    ) {
+        let current = APP_STATE.read().running_handlers.read().get(id).cloned();
+        // Already stopped or replaced by a newer run
+        if !current.is_some_and(|h| Arc::ptr_eq(&h, handler)) {
+            return;
+        }
+        let last_error = exit
+            .last_stderr
+            .as_deref()
+            .map(|line| format!(": {}", line))
+            .unwrap_or_default();
+        tracing::warn!("Process {} {}", id, exit.description);
+        Self::push_server_notification(
+            id,
+            NotificationEvent::ServerFailure,
+            format!(
+                "{} stopped unexpectedly ({}){}",
+                Self::server_name(id),
+                exit.description,
+                last_error
+            ),
+            NotificationLevel::Error,
+        );
+        Self::forget_running(id);
+    }
+
+    /// Queue a server's sampling request for the user to approve, or
+    /// refuse it straight away when sampling is off.
+    fn handle_server_request(id: &str, name: &str, request: ServerRequest) {
+        if request.method == "roots/list" {
+            let roots = APP_STATE
+                .read()
+                .server_roots
+                .read()
+                .get(id)
+                .cloned()
+                .unwrap_or_default();
+            let id = id.to_string();
+            spawn(async move {
+                let result = Ok(crate::process::roots_result(&roots));
+                if let Err(e) = Self::reply_to_server(&id, &request.id, result).await {
+                    tracing::warn!("Failed to answer roots/list: {}", e);
+                }
+            });
+            return;
+        }
+        if request.method == "elicitation/create" {
+            Self::handle_elicitation(id, name, request);
+            return;
+        }
+        let refusal = if !APP_STATE.read().sampling.read().enabled {
+            RpcError::new(
+                RpcError::USER_REJECTED,
+                "Sampling is turned off in Open MCP Manager",
+            )
+        } else {
+            match serde_json::from_value::<CreateMessageRequest>(request.params) {
+                Ok(params) => {
+                    APP_STATE.write().sampling_requests.with_mut(|pending| {
+                        pending.push(PendingSampling {
+                            server_id: id.to_string(),
+                            server_name: name.to_string(),
+                            request_id: request.id,
+                            request: params,
+                        })
+                    });
+                    Self::push_notification(
+                        format!("{} asks to sample the LLM", name),
+                        NotificationLevel::Info,
+                    );
+                    return;
+                }
+                Err(e) => RpcError::new(RpcError::INVALID_PARAMS, e.to_string()),
+            }
+        };
+        let id = id.to_string();
+        spawn(async move {
+            if let Err(e) = Self::reply_to_server(&id, &request.id, Err(refusal)).await {
+                tracing::warn!("Failed to refuse sampling request: {}", e);
+            }
+        });
+    }
+
+    async fn reply_to_server(
+        id: &str,
+        request_id: &serde_json::Value,
+        result: Result<serde_json::Value, RpcError>,
+    ) -> Result<(), String> {
+        let handler = APP_STATE
+            .read()
+            .running_handlers
+            .read()
+            .get(id)
+            .cloned()
+            .ok_or("Process not running")?;
+        handler.reply(request_id, result).await
+    }
+
+    /// Take a pending sampling request off the queue.
+    fn take_sampling_request(
+        server_id: &str,
+        request_id: &serde_json::Value,
+    ) -> Option<PendingSampling> {
+        let mut pending = APP_STATE.write().sampling_requests;
+        pending.with_mut(|list| {
+            let index = list
+                .iter()
+                .position(|p| p.server_id == server_id && &p.request_id == request_id)?;
+            Some(list.remove(index))
+        })
+    }
+
+    /// Send an approved sampling request to the configured LLM and hand
+    /// the reply to the server. An LLM failure is passed on to the server
+    /// as well as returned.
+    pub async fn approve_sampling(
+        server_id: String,
+        request_id: serde_json::Value,
+    ) -> Result<(), String> {
+        let pending = Self::take_sampling_request(&server_id, &request_id)
+            .ok_or("Sampling request already answered")?;
+        let settings = APP_STATE.read().sampling.cloned();
+        let result = crate::sampling::create_message(&settings, &pending.request).await;
+        let reply = result
+            .clone()
+            .map_err(|e| RpcError::new(RpcError::INTERNAL_ERROR, e));
+        Self::reply_to_server(&server_id, &request_id, reply).await?;
+        result
+            .map(|_| ())
+            .map_err(|e| format!("Sampling for {} failed: {}", pending.server_name, e))
+    }
+
+    pub async fn reject_sampling(
+        server_id: String,
+        request_id: serde_json::Value,
+    ) -> Result<(), String> {
+        Self::take_sampling_request(&server_id, &request_id)
+            .ok_or("Sampling request already answered")?;
+        let refusal = RpcError::new(RpcError::USER_REJECTED, "User rejected sampling request");
+        Self::reply_to_server(&server_id, &request_id, Err(refusal)).await
+    }
+
+    /// Queue a server's request for user input as a form, or refuse it if
+    /// its schema can't be shown as one.
+    fn handle_elicitation(id: &str, name: &str, request: ServerRequest) {
+        use crate::elicitation::{fields, ElicitRequest, PendingElicitation};
+
+        let parsed = serde_json::from_value::<ElicitRequest>(request.params)
+            .map_err(|e| e.to_string())
+            .and_then(|params| Ok((fields(&params.requested_schema)?, params.message)));
+        match parsed {
+            Ok((fields, message)) => {
+                APP_STATE.write().elicitation_requests.with_mut(|pending| {
+                    pending.push(PendingElicitation {
+                        server_id: id.to_string(),
+                        server_name: name.to_string(),
+                        request_id: request.id,
+                        message,
+                        fields,
+                    })
+                });
+                Self::push_notification(
+                    format!("{} is asking for input", name),
+                    NotificationLevel::Info,
+                );
+            }
+            Err(e) => {
+                let id = id.to_string();
+                let error = RpcError::new(RpcError::INVALID_PARAMS, e);
+                spawn(async move {
+                    if let Err(e) = Self::reply_to_server(&id, &request.id, Err(error)).await {
+                        tracing::warn!("Failed to refuse elicitation request: {}", e);
+                    }
+                });
+            }
+        }
+    }
+
+    /// Send the user's answers to a pending elicitation, or decline it when
+    /// `answers` is None. Invalid answers leave the form open.
+    pub async fn answer_elicitation(
+        server_id: String,
+        request_id: serde_json::Value,
+        answers: Option<HashMap<String, String>>,
+    ) -> Result<(), String> {
+        let pending = APP_STATE
+            .read()
+            .elicitation_requests
+            .read()
+            .iter()
+            .find(|p| p.server_id == server_id && p.request_id == request_id)
+            .cloned()
+            .ok_or("Request already answered")?;
+        let content = match answers {
+            Some(answers) => Some(crate::elicitation::content(&pending.fields, &answers)?),
+            None => None,
+        };
+        APP_STATE.write().elicitation_requests.with_mut(|list| {
+            list.retain(|p| !(p.server_id == server_id && p.request_id == request_id))
+        });
+        let result = crate::elicitation::result(content);
+        Self::reply_to_server(&server_id, &request_id, Ok(result)).await
+    }
+
+    fn handle_server_notification(id: &str, notification: crate::process::ServerNotification) {
+        if let Some(kind) = notification.list_changed() {
+            Self::handle_list_changed(id, kind);
+            return;
+        }
+        if notification.method != "notifications/resources/updated" {
+            return;
+        }
+        let Some(uri) = notification.params.get("uri").and_then(|u| u.as_str()) else {
+            return;
+        };
+        APP_STATE.write().watched_resources.with_mut(|watched| {
+            if let Some(count) = watched.get_mut(id).and_then(|w| w.get_mut(uri)) {
+                *count += 1;
+            }
+        });
+    }
+
+    /// Drop what's cached from a server's changed list, let open consoles
+    /// reload it and tell the user.
+    fn handle_list_changed(id: &str, kind: crate::process::ListKind) {
+        APP_STATE
+            .write()
+            .list_changes
+            .with_mut(|changes| *changes.entry((id.to_string(), kind)).or_default() += 1);
+        if kind == crate::process::ListKind::Tools {
+            // Read-only flags and cached results may no longer hold
+            APP_STATE
+                .write()
+                .result_cache
+                .with_mut(|c| c.forget_server(id));
+            let id = id.to_string();
+            spawn(async move {
+                if let Err(e) = Self::get_tools(id).await {
+                    tracing::warn!("Failed to reload changed tools: {}", e);
+                }
+            });
+        }
+        Self::push_notification(
+            format!("{} updated its {}", Self::server_name(id), kind.label()),
+            NotificationLevel::Info,
+        );
+    }
+
+    /// Whether a running server sends log messages over MCP.
+    pub fn supports_logging(id: &str) -> bool {
+        APP_STATE
+            .read()
+            .running_handlers
+            .read()
+            .get(id)
+            .and_then(|h| h.capabilities().map(|c| c.logging.is_some()))
+            .unwrap_or(false)
+    }
+
+    /// Ask a server to send log messages of `level` and above.
+    pub async fn set_log_level(id: String, level: LoggingLevel) -> Result<(), String> {
+        let handler = APP_STATE
+            .read()
+            .running_handlers
+            .read()
+            .get(&id)
+            .cloned()
+            .ok_or("Process not running")?;
+        if !Self::supports_logging(&id) {
+            return Err(format!(
+                "{} doesn't send log messages",
+                Self::server_name(&id)
+            ));
+        }
+        handler.set_log_level(level).await
+    }
+
+    /// Send any JSON-RPC request, for trying out nonstandard methods. The
+    /// exchange goes into the server's log, and into the session recording
+    /// while one is running.
+    pub async fn send_raw_request(
+        id: String,
+        method: String,
+        params: Option<serde_json::Value>,
+    ) -> Result<RawExchange, String> {
+        let handler = APP_STATE
+            .read()
+            .running_handlers
+            .read()
+            .get(&id)
+            .cloned()
+            .ok_or("Process not running")?;
+        let log = APP_STATE.read().processes.read().get(&id).copied();
+        let exchange = handler.send_raw_request(&method, params).await;
+        if let Some(log) = log {
+            Self::append_log_lines(&id, log, "sent", &[exchange.request.to_string()]);
+            let (tag, line) = match &exchange.response {
+                Ok(message) => ("received", message.to_string()),
+                Err(e) => ("error", e.clone()),
+            };
+            Self::append_log_lines(&id, log, tag, &[line]);
+        }
+        Ok(exchange)
+    }
+
+    /// Subscribe to `uri` so the console can refresh it when it changes.
+    pub async fn watch_resource(id: String, uri: String) -> Result<(), String> {
+        let handler = APP_STATE
+            .read()
+            .running_handlers
+            .read()
+            .get(&id)
+            .cloned()
+            .ok_or("Process not running")?;
+        if !handler
+            .capabilities()
+            .is_some_and(|c| c.resource_subscribe())
+        {
+            return Err(format!(
+                "{} doesn't send resource updates",
+                Self::server_name(&id)
+            ));
+        }
+        handler.subscribe_resource(&uri).await?;
+        APP_STATE.write().watched_resources.with_mut(|watched| {
+            watched.entry(id).or_default().insert(uri, 0);
+        });
+        Ok(())
+    }
+
+    pub async fn unwatch_resource(id: String, uri: String) {
+        APP_STATE.write().watched_resources.with_mut(|watched| {
+            if let Some(uris) = watched.get_mut(&id) {
+                uris.remove(&uri);
+            }
+        });
+        let handler = APP_STATE.read().running_handlers.read().get(&id).cloned();
+        if let Some(handler) = handler {
+            if let Err(e) = handler.unsubscribe_resource(&uri).await {
+                tracing::warn!("Failed to unsubscribe from {}: {}", uri, e);
+            }
+        }
+    }
+
+    /// Add what a start/stop hook printed to the server's log.
+    fn append_log_lines(id: &str, mut log: Signal<String>, tag: &str, output: &[String]) {
+        let now = chrono::Utc::now();
+        log.with_mut(|text| {
+            for line in output {
+                let line = format!("[{}] {}", tag, line);
+                tracing::debug!("[{}] {}", id, line);
+                text.push_str(&crate::log_time::stamp(now, &line));
+                text.push('\n');
+            }
+        });
+    }
+
+    pub async fn stop_server_process(id: &str) {
+        // Retrieve process handle
+        let proc_opt = {
+            let state = APP_STATE.read();
+            let handlers = state.running_handlers.read();
+            handlers.get(id).cloned()
+        };
+
+        if let Some(proc) = proc_opt {
+            let grace = std::time::Duration::from_secs(*APP_STATE.read().stop_grace_secs.read());
+            match proc.shutdown(grace).await {
+                Ok(StopOutcome::Exited) => tracing::info!("Process {} exited cleanly", id),
+                Ok(StopOutcome::Terminated) => {
+                    tracing::info!("Process {} exited once asked to terminate", id)
+                }
+                Ok(StopOutcome::Killed) => {
+                    tracing::info!("Process {} killed after {}s grace", id, grace.as_secs());
+                    Self::push_server_notification(
+                        id,
+                        NotificationEvent::General,
+                        format!(
+                            "{} did not exit within {}s of being asked to stop and was killed",
+                            Self::server_name(id),
+                            grace.as_secs()
+                        ),
+                        NotificationLevel::Warning,
+                    );
+                }
+                Ok(StopOutcome::Disconnected) => tracing::info!("Disconnected from {}", id),
+                Err(e) => tracing::error!("Failed to kill process {}: {}", id, e),
+            }
+        }
+
+        if let Some(hook) = Self::launch_options(id).post_stop {
+            let env = APP_STATE
+                .read()
+                .servers
+                .read()
+                .iter()
+                .find(|s| s.id == id)
+                .and_then(|s| s.env.clone())
+                .unwrap_or_default();
+            let run = crate::hooks::run(&hook, &env, crate::hooks::HOOK_TIMEOUT).await;
+            let log = APP_STATE.read().processes.read().get(id).copied();
+            if let Some(log) = log {
+                Self::append_log_lines(id, log, "post-stop", &run.output);
+            }
+            if let Some(e) = run.error {
+                Self::push_server_notification(
+                    id,
+                    NotificationEvent::General,
+                    format!("Post-stop hook for {} {}", Self::server_name(id), e),
+                    NotificationLevel::Warning,
+                );
+            }
+        }
+
+        Self::forget_running(id);
+    }
+
+    /// Record the end of a server's run and drop what was kept about it
+    /// while it ran.
+    fn forget_running(id: &str) {
+        if let Some(db) = APP_STATE.read().db.cloned() {
+            if let Err(e) = db.end_server_run(id) {
+                tracing::error!("Failed to log server stop: {}", e);
+            }
+        }
+
+        // Cleanup maps
+        APP_STATE.write().running_handlers.write().remove(id);
+        APP_STATE.write().processes.write().remove(id);
+        APP_STATE.write().launched_configs.write().remove(id);
+        APP_STATE.write().recordings.write().remove(id);
+        APP_STATE.write().watched_resources.write().remove(id);
+        APP_STATE.write().probes.write().remove(id);
+        APP_STATE.write().resource_usage.write().remove(id);
+        APP_STATE
+            .write()
+            .result_cache
+            .with_mut(|c| c.forget_server(id));
+        Self::reset_circuit_breaker(id);
+        APP_STATE.write().request_queues.with_mut(|q| q.remove(id));
+        APP_STATE.write().request_metrics.with_mut(|m| m.forget(id));
+    }
+
+    /// The terminal of a server running in PTY mode.
+    pub fn terminal(id: &str) -> Option<crate::pty::Terminal> {
+        APP_STATE
+            .read()
+            .running_handlers
+            .read()
+            .get(id)
+            .and_then(|h| h.terminal())
+    }
+
+    /// Start recording a running server's stdio session to a new file in
+    /// the recordings directory.
+    pub fn start_recording(id: &str) -> Result<(), String> {
+        let handler = APP_STATE
+            .read()
+            .running_handlers
+            .read()
+            .get(id)
+            .cloned()
+            .ok_or("Process not running")?;
+        let file_name = crate::session_recording::recording_file_name(
+            &Self::server_name(id),
+            &chrono::Local::now().format("%Y%m%d-%H%M%S").to_string(),
+        );
+        let path = crate::session_recording::recordings_dir()
+            .map_err(|e| e.to_string())?
+            .join(file_name);
+        handler.start_recording(&path)?;
+        APP_STATE
+            .write()
+            .recordings
+            .write()
+            .insert(id.to_string(), path);
+        Ok(())
+    }
+
+    pub fn stop_recording(id: &str) {
+        let handler = APP_STATE.read().running_handlers.read().get(id).cloned();
+        APP_STATE.write().recordings.write().remove(id);
+        if let Some(path) = handler.and_then(|h| h.stop_recording()) {
+            Self::push_notification(
+                format!("Session saved to {}", path.display()),
+                NotificationLevel::Success,
+            );
+        }
+    }
+
+    /// Stand in for `server` with a recording of one of its sessions:
+    /// requests get the recorded responses, and the recorded stderr is
+    /// shown in its log. Pick the recording with a file dialog.
+    pub async fn start_replay(server: McpServer) -> Result<(), String> {
+        if APP_STATE
+            .read()
+            .running_handlers
+            .read()
+            .contains_key(&server.id)
+        {
+            return Err("Stop the server before replaying a recording".into());
+        }
+        let mut dialog = rfd::AsyncFileDialog::new().add_filter("Session recording", &["jsonl"]);
+        if let Ok(dir) = crate::session_recording::recordings_dir() {
+            dialog = dialog.set_directory(dir);
+        }
+        let Some(handle) = dialog.pick_file().await else {
+            return Ok(());
+        };
+
+        let replay = crate::process::McpReplay::open(handle.path())?;
+        replay.initialize().await?;
+        let mut log = format!("[replay] {}\n", handle.path().display());
+        for line in replay.stderr_lines().await {
+            log.push_str(&format!("[stderr] {}\n", line));
+        }
+        APP_STATE
+            .write()
+            .processes
+            .write()
+            .insert(server.id.clone(), Signal::new(log));
+        APP_STATE.write().running_handlers.write().insert(
+            server.id.clone(),
+            Arc::new(crate::process::McpHandler::Replay(Box::new(replay))),
+        );
+        tracing::info!("Replaying a recorded session of {}", server.name);
+        Ok(())
+    }
+
+    /// Whether `server` is running with a config or launch options that
+    /// have since changed.
+    pub fn needs_restart(server: &McpServer) -> bool {
+        let options = Self::launch_options(&server.id);
+        APP_STATE
+            .read()
+            .launched_configs
+            .read()
+            .get(&server.id)
+            .is_some_and(|(launched, launched_options)| {
+                !launched.same_launch_config(server) || *launched_options != options
+            })
+    }
+
+    pub fn reset_circuit_breaker(id: &str) {
+        let mut breakers = APP_STATE.read().circuit_breakers;
+        breakers.with_mut(|b| b.reset(id));
+    }
+
+    /// Display name of a server, or its id if it is unknown.
+    fn server_name(id: &str) -> String {
+        APP_STATE
+            .read()
+            .servers
+            .read()
+            .iter()
+            .find(|s| s.id == id)
+            .map(|s| s.name.clone())
+            .unwrap_or_else(|| id.to_string())
     }
 
-    pub async fn stop_server_process(id: &str) {
-        // Retrieve process handle
+    pub async fn get_tools(id: String) -> Result<Vec<crate::models::Tool>, String> {
         let proc_opt = {
             let state = APP_STATE.read();
             let handlers = state.running_handlers.read();
-            handlers.get(id).cloned()
+            handlers.get(&id).cloned()
         };
 
         if let Some(proc) = proc_opt {
-            if let Err(e) = proc.kill().await {
-                tracing::error!("Failed to kill process {}: {}", id, e);
-            } else {
-                tracing::info!("Process {} killed", id);
-            }
+            let tools = proc.list_tools().await?;
+            APP_STATE
+                .write()
+                .result_cache
+                .with_mut(|c| c.learn_tools(&id, &tools));
+            Ok(tools)
+        } else {
+            Err("Process not running".into())
         }
-
-        // Cleanup maps
-        APP_STATE.write().running_handlers.write().remove(id);
-        APP_STATE.write().processes.write().remove(id);
     }
 
-    pub async fn get_tools(id: String) -> Result<Vec<crate::models::Tool>, String> {
+    pub async fn get_resources(id: String) -> Result<Vec<crate::models::Resource>, String> {
         let proc_opt = {
             let state = APP_STATE.read();
             let handlers = state.running_handlers.read();
@@ -203,14 +2528,16 @@ impl AppState {
         };
 
         if let Some(proc) = proc_opt {
-            let tools = proc.list_tools().await?;
-            Ok(tools)
+            let resources = proc.list_resources().await?;
+            Ok(resources)
         } else {
             Err("Process not running".into())
         }
     }
 
-    pub async fn get_resources(id: String) -> Result<Vec<crate::models::Resource>, String> {
+    pub async fn get_resource_templates(
+        id: String,
+    ) -> Result<Vec<crate::models::ResourceTemplate>, String> {
         let proc_opt = {
             let state = APP_STATE.read();
             let handlers = state.running_handlers.read();
@@ -218,8 +2545,7 @@ impl AppState {
         };
 
         if let Some(proc) = proc_opt {
-            let resources = proc.list_resources().await?;
-            Ok(resources)
+            proc.list_resource_templates().await
         } else {
             Err("Process not running".into())
         }
@@ -240,6 +2566,25 @@ impl AppState {
         }
     }
 
+    /// Fetch a prompt's messages with `arguments` filled in.
+    pub async fn get_prompt(
+        id: String,
+        name: String,
+        arguments: HashMap<String, String>,
+    ) -> Result<crate::models::GetPromptResult, String> {
+        let proc_opt = {
+            let state = APP_STATE.read();
+            let handlers = state.running_handlers.read();
+            handlers.get(&id).cloned()
+        };
+
+        if let Some(proc) = proc_opt {
+            proc.get_prompt(name, arguments).await
+        } else {
+            Err("Process not running".into())
+        }
+    }
+
     pub async fn execute_tool(
         id: String,
         name: String,
@@ -251,13 +2596,547 @@ impl AppState {
             handlers.get(&id).cloned()
         };
 
-        if let Some(proc) = proc_opt {
-            proc.call_tool(name, args).await
+        let Some(proc) = proc_opt else {
+            return Err("Process not running".into());
+        };
+
+        // Unhealthy servers fail fast instead of queueing more timeouts,
+        // or hand the call to a failover backup that has the tool
+        let primary_name = Self::server_name(&id);
+        let health = APP_STATE.read().circuit_breakers.read().check(
+            &id,
+            &primary_name,
+            std::time::Instant::now(),
+        );
+        let (id, proc) = match health {
+            Ok(()) => (id, proc),
+            Err(e) => {
+                let Some((backup_id, backup)) = Self::failover_backup(&id, &name).await else {
+                    return Err(e);
+                };
+                Self::record_audit(
+                    "failover",
+                    Some(&id),
+                    &format!(
+                        "{} → {} for {}: {}",
+                        primary_name,
+                        Self::server_name(&backup_id),
+                        name,
+                        e
+                    ),
+                );
+                (backup_id, backup)
+            }
+        };
+        // Rules and the cache are those of the server that answers
+        let server_name = Self::server_name(&id);
+
+        let rules: Vec<ToolRule> = APP_STATE
+            .read()
+            .tool_rules
+            .read()
+            .iter()
+            .filter(|r| r.server_id == id)
+            .cloned()
+            .collect();
+        let args = crate::tool_rules::apply(&rules, &name, args)
+            .map_err(|e| format!("Refused by a hub rule: {}", e))?;
+
+        // Read-only tools may be answered from the hub's result cache
+        let settings = APP_STATE.read().result_cache_settings.cloned();
+        let ttl = std::time::Duration::from_secs(settings.ttl_secs);
+        let mut cache = APP_STATE.read().result_cache;
+        if settings.enabled && !cache.read().knows_server(&id) {
+            if let Ok(tools) = proc.list_tools().await {
+                cache.with_mut(|c| c.learn_tools(&id, &tools));
+            }
+        }
+        let cacheable = cache.read().cacheable(&settings, &id, &name);
+        if cacheable {
+            let now = std::time::Instant::now();
+            if let Some(mut hit) = cache.with_mut(|c| c.get(&id, &name, &args, ttl, now)) {
+                // Answered without a request, so no logs belong to it
+                hit.request_id = None;
+                return Ok(hit);
+            }
+        }
+
+        let mut result = Self::call_through_breaker(&id, &server_name, &proc, &name, &args).await?;
+        // Huge results are saved to a file and cut down before they reach
+        // the cache or the console
+        let limits = APP_STATE.read().response_limits.cloned();
+        if limits.enabled && crate::response_limit::result_size(&result) > limits.max_bytes() {
+            result = Self::limit_response(&server_name, &name, result, limits.max_bytes());
+        }
+        if cacheable {
+            let now = std::time::Instant::now();
+            cache.with_mut(|c| c.insert(&id, &name, &args, &result, ttl, now));
+        }
+        Ok(result)
+    }
+
+    /// The first `max_bytes` of a large result, with the whole of it
+    /// written to the outputs directory.
+    fn limit_response(
+        server_name: &str,
+        tool: &str,
+        result: crate::models::CallToolResult,
+        max_bytes: usize,
+    ) -> crate::models::CallToolResult {
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+        let written = crate::response_limit::outputs_dir()
+            .map_err(|e| e.to_string())
+            .and_then(|dir| {
+                crate::response_limit::write_full(&dir, server_name, tool, &timestamp, &result)
+                    .map_err(|e| e.to_string())
+            });
+        let full_output = match written {
+            Ok(path) => Some(path),
+            Err(e) => {
+                tracing::error!("Failed to save the full result of {}: {}", tool, e);
+                None
+            }
+        };
+        let mut result = crate::response_limit::truncate(result, max_bytes);
+        if let Some(truncated) = result.truncated.as_mut() {
+            tracing::warn!(
+                "{} returned {} from {}, over the response size limit",
+                server_name,
+                crate::storage::format_size(truncated.original_bytes as u64),
+                tool
+            );
+            truncated.full_output = full_output;
+        }
+        result
+    }
+
+    /// Cancel the tool calls a server is working on, failing them with
+    /// [`crate::process::CANCELLED`] and telling the server to stop.
+    pub async fn cancel_request(id: String) -> Result<(), String> {
+        let handler = APP_STATE
+            .read()
+            .running_handlers
+            .read()
+            .get(&id)
+            .cloned()
+            .ok_or("Process not running")?;
+        let calls = handler.in_flight_calls();
+        if calls.is_empty() {
+            return Err("No tool call to cancel".into());
+        }
+        for request_id in calls {
+            handler
+                .cancel_request(request_id, "Cancelled by the user")
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Call a tool, feeding the outcome to the server's circuit breaker.
+    async fn call_through_breaker(
+        id: &str,
+        server_name: &str,
+        proc: &crate::process::McpHandler,
+        name: &str,
+        args: &serde_json::Value,
+    ) -> Result<crate::models::CallToolResult, String> {
+        // A recovering server gets one trial call at a time
+        let mut breakers = APP_STATE.read().circuit_breakers;
+        breakers.with_mut(|b| b.begin_call(id, server_name, std::time::Instant::now()))?;
+
+        // Wait for a free slot if the server limits concurrent calls
+        let mut queues = APP_STATE.read().request_queues;
+        let limit = APP_STATE.read().concurrency_limits.read().get(id).copied();
+        let permits = queues.with_mut(|q| {
+            let queue = q.entry(id.to_string()).or_default();
+            queue.set_limit(limit);
+            queue.enqueue()
+        });
+        let (permit, waited) = crate::request_queue::acquire(permits).await;
+        queues.with_mut(|q| q.entry(id.to_string()).or_default().started(waited));
+
+        // The handler's own request timeout cancels a call that runs long
+        let result = proc.call_tool(name.to_string(), args.clone()).await;
+        drop(permit);
+        queues.with_mut(|q| q.entry(id.to_string()).or_default().finished());
+
+        // A call the user cancelled says nothing about the server's health
+        if matches!(&result, Err(e) if e == crate::process::CANCELLED) {
+            breakers.with_mut(|b| b.end_trial(id));
+            return result;
+        }
+        Self::record_call_outcome(id, server_name, name, args, result.is_ok());
+        match &result {
+            Ok(_) => breakers.with_mut(|b| b.record_success(id)),
+            Err(e) => {
+                let now = std::time::Instant::now();
+                if breakers.with_mut(|b| b.record_failure(id, now)) {
+                    Self::push_server_notification(
+                        id,
+                        NotificationEvent::ServerFailure,
+                        format!(
+                            "{} marked unhealthy, pausing calls for {}s: {}",
+                            server_name,
+                            crate::circuit_breaker::COOL_DOWN.as_secs(),
+                            e
+                        ),
+                        NotificationLevel::Error,
+                    );
+                    // Re-render breaker badges once the cool-down is over
+                    spawn(async move {
+                        tokio::time::sleep(crate::circuit_breaker::COOL_DOWN).await;
+                        breakers.with_mut(|_| {});
+                    });
+                }
+            }
+        }
+        result
+    }
+
+    /// Log a routed call for reports and count it towards the server's
+    /// error rate, warning when the rate goes over the error budget.
+    fn record_call_outcome(
+        id: &str,
+        server_name: &str,
+        tool: &str,
+        args: &serde_json::Value,
+        ok: bool,
+    ) {
+        if let Some(db) = APP_STATE.read().db.cloned() {
+            if let Err(e) = db.append_tool_call(id, tool, &args.to_string(), ok) {
+                tracing::error!("Failed to log tool call: {}", e);
+            }
+        }
+        let budget = APP_STATE.read().error_budget.cloned();
+        let now = std::time::Instant::now();
+        let mut metrics = APP_STATE.read().request_metrics;
+        let spike = metrics.with_mut(|m| {
+            m.record(id, ok, now, budget.window());
+            m.check_budget(id, &budget, now)
+        });
+        if let Some(rate) = spike {
+            Self::push_server_notification(
+                id,
+                NotificationEvent::ServerFailure,
+                format!(
+                    "{} failed {} of its last {} calls ({}%) in {} min",
+                    server_name,
+                    rate.failed,
+                    rate.total,
+                    rate.percent(),
+                    (budget.window_secs / 60).max(1)
+                ),
+                NotificationLevel::Warning,
+            );
+        }
+    }
+
+    pub fn save_error_budget_settings(settings: ErrorBudgetSettings) {
+        Self::save_json_setting(ERROR_BUDGET_KEY, &settings);
+        APP_STATE.write().error_budget.set(settings);
+    }
+
+    pub fn save_health_endpoint(settings: HealthEndpointSettings) {
+        Self::save_json_setting(HEALTH_ENDPOINT_KEY, &settings);
+        APP_STATE.write().health_endpoint.set(settings);
+        Self::restart_health_endpoint();
+    }
+
+    /// Stop serving health checks and, if enabled, serve them again on
+    /// the configured port.
+    fn restart_health_endpoint() {
+        if let Some(task) = APP_STATE.read().health_listener.cloned() {
+            task.cancel();
+        }
+        let settings = APP_STATE.read().health_endpoint.cloned();
+        let task = settings.enabled.then(|| {
+            spawn(async move {
+                match tokio::net::TcpListener::bind(("127.0.0.1", settings.port)).await {
+                    Ok(listener) => crate::health::serve(listener, Self::health_report).await,
+                    Err(e) => Self::push_notification(
+                        format!(
+                            "Couldn't serve health checks on port {}: {}",
+                            settings.port, e
+                        ),
+                        NotificationLevel::Warning,
+                    ),
+                }
+            })
+        });
+        APP_STATE.write().health_listener.set(task);
+    }
+
+    /// The manager's and each server's health, as the health endpoint
+    /// reports it.
+    pub fn health_report() -> crate::health::HealthReport {
+        use crate::circuit_breaker::BreakerState;
+        use crate::health::{HealthReport, ServerHealth, ServerStatus};
+
+        let state = APP_STATE.read();
+        let running = state.running_handlers.read();
+        let breakers = state.circuit_breakers.read();
+        let metrics = state.request_metrics.read();
+        let window = state.error_budget.read().window();
+        let now = std::time::Instant::now();
+        let servers = state
+            .servers
+            .read()
+            .iter()
+            .filter(|s| !s.is_archived)
+            .map(|s| {
+                let status = if !running.contains_key(&s.id) {
+                    ServerStatus::Stopped
+                } else if matches!(breakers.state(&s.id, now), BreakerState::Open { .. }) {
+                    ServerStatus::Unhealthy
+                } else {
+                    ServerStatus::Running
+                };
+                let rate = metrics.error_rate(&s.id, now, window);
+                ServerHealth {
+                    id: s.id.clone(),
+                    name: s.name.clone(),
+                    status,
+                    error_rate_percent: rate.percent(),
+                    calls: rate.total,
+                }
+            })
+            .collect();
+        HealthReport {
+            ready: state.db.read().is_some(),
+            servers,
+        }
+    }
+
+    pub fn save_dashboard_settings(settings: DashboardSettings) {
+        Self::save_json_setting(DASHBOARD_KEY, &settings);
+        APP_STATE.write().dashboard.set(settings);
+    }
+
+    pub fn save_appearance_settings(settings: AppearanceSettings) {
+        Self::save_json_setting(APPEARANCE_KEY, &settings);
+        APP_STATE.write().appearance.set(settings);
+    }
+
+    /// The locale registry text is shown in: the chosen language, or the
+    /// system's.
+    pub fn locale() -> String {
+        APP_STATE
+            .read()
+            .language
+            .cloned()
+            .unwrap_or_else(crate::locale::system_locale)
+    }
+
+    /// Pass None to follow the system language.
+    pub fn set_language(language: Option<String>) {
+        Self::save_json_setting(LANGUAGE_KEY, &language);
+        APP_STATE.write().language.set(language);
+    }
+
+    /// The first running, healthy backup of `primary_id` that lists
+    /// `tool`, learning the tool lists of backups not yet asked.
+    async fn failover_backup(
+        primary_id: &str,
+        tool: &str,
+    ) -> Option<(String, Arc<crate::process::McpHandler>)> {
+        let backups: Vec<(String, Arc<crate::process::McpHandler>)> = {
+            let state = APP_STATE.read();
+            let now = std::time::Instant::now();
+            let breakers = state.circuit_breakers.read();
+            let handlers = state.running_handlers.read();
+            state
+                .failover_pairs
+                .read()
+                .iter()
+                .filter(|pair| pair.primary == primary_id)
+                .filter(|pair| {
+                    !matches!(
+                        breakers.state(&pair.backup, now),
+                        crate::circuit_breaker::BreakerState::Open { .. }
+                    )
+                })
+                .filter_map(|pair| {
+                    handlers
+                        .get(&pair.backup)
+                        .map(|h| (pair.backup.clone(), h.clone()))
+                })
+                .collect()
+        };
+        let mut cache = APP_STATE.read().result_cache;
+        for (backup_id, backup) in backups {
+            if !cache.read().knows_server(&backup_id) {
+                if let Ok(tools) = backup.list_tools().await {
+                    cache.with_mut(|c| c.learn_tools(&backup_id, &tools));
+                }
+            }
+            if cache.read().lists_tool(&backup_id, tool) {
+                return Some((backup_id, backup));
+            }
+        }
+        None
+    }
+
+    pub fn launch_options(server_id: &str) -> LaunchOptions {
+        APP_STATE
+            .read()
+            .launch_options
+            .read()
+            .get(server_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Takes effect the next time the server starts.
+    pub fn set_launch_options(server_id: &str, options: LaunchOptions) {
+        let mut all = APP_STATE.read().launch_options.cloned();
+        if options == LaunchOptions::default() {
+            all.remove(server_id);
         } else {
-            Err("Process not running".into())
+            all.insert(server_id.to_string(), options);
+        }
+        Self::save_json_setting(LAUNCH_OPTIONS_KEY, &all);
+        APP_STATE.write().launch_options.set(all);
+    }
+
+    /// Cap concurrent tool calls to a server; None removes the cap.
+    pub fn set_concurrency_limit(server_id: &str, limit: Option<usize>) {
+        let mut limits = APP_STATE.read().concurrency_limits.cloned();
+        match limit.filter(|n| *n > 0) {
+            Some(n) => limits.insert(server_id.to_string(), n),
+            None => limits.remove(server_id),
+        };
+        Self::save_json_setting(CONCURRENCY_LIMITS_KEY, &limits);
+        APP_STATE.write().concurrency_limits.set(limits);
+    }
+
+    pub fn save_failover_pairs(pairs: Vec<FailoverPair>) {
+        Self::save_json_setting(FAILOVER_KEY, &pairs);
+        APP_STATE.write().failover_pairs.set(pairs);
+    }
+
+    pub fn add_tool_rule(server_id: &str, tool: &str, action: RuleAction) -> Result<(), String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        let id = db
+            .add_tool_rule(server_id, tool, &action)
+            .map_err(|e| e.to_string())?;
+        APP_STATE.write().tool_rules.with_mut(|rules| {
+            rules.push(ToolRule {
+                id,
+                server_id: server_id.to_string(),
+                tool: tool.to_string(),
+                action,
+            })
+        });
+        Ok(())
+    }
+
+    pub fn delete_tool_rule(id: i64) -> Result<(), String> {
+        let db = APP_STATE.read().db.cloned().ok_or("DB not initialized")?;
+        db.delete_tool_rule(id).map_err(|e| e.to_string())?;
+        APP_STATE
+            .write()
+            .tool_rules
+            .with_mut(|rules| rules.retain(|r| r.id != id));
+        Ok(())
+    }
+
+    /// Append to the audit log, keeping the recent entries in memory.
+    pub fn record_audit(event: &str, server_id: Option<&str>, detail: &str) {
+        let Some(db) = APP_STATE.read().db.cloned() else {
+            return;
+        };
+        if let Err(e) = db.append_audit(event, server_id, detail) {
+            tracing::error!("Failed to write audit entry: {}", e);
+            return;
+        }
+        let entries = db.get_audit_log(AUDIT_LOG_LIMIT).unwrap_or_default();
+        APP_STATE.write().audit_log.set(entries);
+    }
+
+    pub fn save_result_cache_settings(settings: ResultCacheSettings) {
+        Self::save_json_setting(RESULT_CACHE_KEY, &settings);
+        if !settings.enabled {
+            APP_STATE.write().result_cache.with_mut(|c| c.clear());
+        }
+        APP_STATE.write().result_cache_settings.set(settings);
+    }
+
+    pub fn save_response_limits(settings: ResponseLimitSettings) {
+        Self::save_json_setting(RESPONSE_LIMITS_KEY, &settings);
+        APP_STATE.write().response_limits.set(settings);
+    }
+
+    pub fn save_sampling(settings: SamplingSettings) {
+        Self::save_json_setting(SAMPLING_KEY, &settings);
+        APP_STATE.write().sampling.set(settings);
+    }
+
+    fn save_server_roots(roots: HashMap<String, Vec<std::path::PathBuf>>) {
+        Self::save_json_setting(SERVER_ROOTS_KEY, &roots);
+        APP_STATE.write().server_roots.set(roots);
+    }
+
+    /// Replace the directories offered to a server and, if it's running,
+    /// tell it to ask for them again.
+    pub fn set_server_roots(server_id: &str, dirs: Vec<std::path::PathBuf>) {
+        let mut roots = APP_STATE.read().server_roots.cloned();
+        if dirs.is_empty() {
+            roots.remove(server_id);
+        } else {
+            roots.insert(server_id.to_string(), dirs);
+        }
+        Self::save_server_roots(roots);
+
+        let handler = APP_STATE
+            .read()
+            .running_handlers
+            .read()
+            .get(server_id)
+            .cloned();
+        if let Some(handler) = handler {
+            spawn(async move {
+                if let Err(e) = handler.notify_roots_changed().await {
+                    tracing::warn!("Failed to send roots/list_changed: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Pick a folder and add it to the directories offered to a server.
+    pub async fn add_server_root(server_id: String) {
+        let Some(handle) = rfd::AsyncFileDialog::new().pick_folder().await else {
+            return;
+        };
+        let mut dirs = APP_STATE
+            .read()
+            .server_roots
+            .read()
+            .get(&server_id)
+            .cloned()
+            .unwrap_or_default();
+        let dir = handle.path().to_path_buf();
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+            Self::set_server_roots(&server_id, dirs);
         }
     }
 
+    pub fn remove_server_root(server_id: &str, dir: &std::path::Path) {
+        let mut dirs = APP_STATE
+            .read()
+            .server_roots
+            .read()
+            .get(server_id)
+            .cloned()
+            .unwrap_or_default();
+        dirs.retain(|d| d != dir);
+        Self::set_server_roots(server_id, dirs);
+    }
+
+    pub fn clear_result_cache() {
+        APP_STATE.write().result_cache.with_mut(|c| c.clear());
+    }
+
     pub async fn read_resource(
         id: String,
         uri: String,
@@ -284,29 +3163,225 @@ impl AppState {
 
         if let Some(proc) = proc_opt {
             let start = std::time::Instant::now();
-            // We use list_tools as a ping mechanism. It's a standard MCP method.
-            let _ = proc.list_tools().await.map_err(|e| e.to_string())?;
-            let duration = start.elapsed().as_millis();
-            Ok(duration)
+            proc.ping(PING_TIMEOUT).await?;
+            Ok(start.elapsed().as_millis())
         } else {
             Err("Process not running".into())
         }
     }
 
+    /// Point Claude Desktop at the hub through the stdio bridge and report
+    /// the outcome as a toast.
+    pub async fn connect_claude_desktop() {
+        use crate::editor_config::{
+            bridge_command, claude_desktop_config_path, hub_bridge_entry, merge_server_entry,
+            read_config, write_server_entry, HUB_ENTRY_NAME,
+        };
+
+        let path = match claude_desktop_config_path() {
+            Ok(p) => p,
+            Err(e) => {
+                Self::push_notification(e.to_string(), NotificationLevel::Error);
+                return;
+            }
+        };
+
+        // The bridge only forwards to a running hub, so don't point Claude at nothing
+        if !crate::bridge::hub_reachable(crate::bridge::DEFAULT_HUB_URL).await {
+            Self::push_notification(
+                format!(
+                    "The hub is not reachable at {}; start it before connecting Claude Desktop",
+                    crate::bridge::DEFAULT_HUB_URL
+                ),
+                NotificationLevel::Warning,
+            );
+            return;
+        }
+
+        let entry = hub_bridge_entry(&bridge_command());
+        let written = if crate::privileged::is_writable(&path) {
+            write_server_entry(&path, HUB_ENTRY_NAME, entry).map_err(|e| e.to_string())
+        } else {
+            match read_config(&path) {
+                Ok(config) => {
+                    let config = merge_server_entry(config, HUB_ENTRY_NAME, entry);
+                    Self::write_protected_config(&path, &config)
+                        .await
+                        .map(|_| ())
+                }
+                Err(e) => Err(e.to_string()),
+            }
+        };
+        if let Err(e) = written {
+            Self::push_notification(
+                format!("Failed to update {}: {}", path.display(), e),
+                NotificationLevel::Error,
+            );
+            return;
+        }
+
+        Self::push_notification(
+            "Claude Desktop connected. Restart Claude to load the hub.".to_string(),
+            NotificationLevel::Success,
+        );
+    }
+
+    /// Write `config` to an editor config the manager can't write itself,
+    /// through the OS's permission prompt. Returns where the previous file
+    /// was copied; if permission is refused, the error says how to finish
+    /// by hand.
+    async fn write_protected_config(
+        path: &std::path::Path,
+        config: &serde_json::Value,
+    ) -> Result<Option<std::path::PathBuf>, String> {
+        let contents = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+        let dir = crate::privileged::staging_dir().map_err(|e| e.to_string())?;
+        let write = crate::privileged::stage(&dir, path, &contents)
+            .map_err(|e| format!("Couldn't stage the new config: {}", e))?;
+        Self::push_notification(
+            format!(
+                "{} is protected; asking for permission to update it",
+                path.display()
+            ),
+            NotificationLevel::Info,
+        );
+        match crate::privileged::write_elevated(&write).await {
+            Ok(()) => {
+                let _ = std::fs::remove_file(&write.staged);
+                Ok(write.backup)
+            }
+            Err(e) => Err(format!("{}. {}", e, write.manual_instructions())),
+        }
+    }
+
+    /// Move an editor's config to Hub Mode (`to_hub`) or Direct Mode,
+    /// keeping a backup, then re-read the file to check the edits landed.
+    pub async fn migrate_editor_config(
+        editor: &'static str,
+        path: std::path::PathBuf,
+        to_hub: bool,
+        hub_entry: serde_json::Value,
+        direct: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), String> {
+        use crate::editor_config::{apply_migration, plan_migration, read_config, write_migration};
+
+        let mode = if to_hub { "Hub" } else { "Direct" };
+        let config = read_config(&path).map_err(|e| e.to_string())?;
+        let plan = plan_migration(&config, to_hub, &hub_entry, &direct);
+        if plan.is_empty() {
+            return Ok(());
+        }
+        // Without a hub to talk to, Hub Mode would leave the editor with no servers
+        if to_hub && !crate::bridge::hub_reachable(crate::bridge::DEFAULT_HUB_URL).await {
+            return Err(format!(
+                "The hub is not reachable at {}; start it before switching {} to Hub Mode",
+                crate::bridge::DEFAULT_HUB_URL,
+                editor
+            ));
+        }
+        let backup = if crate::privileged::is_writable(&path) {
+            write_migration(&path, &plan)
+                .map(Some)
+                .map_err(|e| e.to_string())
+        } else {
+            Self::write_protected_config(&path, &apply_migration(config, &plan)).await
+        }
+        .map_err(|e| format!("Failed to update {}: {}", path.display(), e))?;
+        Self::record_audit(
+            "config_migration",
+            None,
+            &format!(
+                "{} to {} Mode: removed {}, added {}",
+                editor,
+                mode,
+                plan.remove.len(),
+                plan.add.len()
+            ),
+        );
+
+        let written = read_config(&path).map_err(|e| e.to_string())?;
+        if !plan_migration(&written, to_hub, &hub_entry, &direct).is_empty() {
+            let backup = backup
+                .map(|b| format!("; the previous file is at {}", b.display()))
+                .unwrap_or_default();
+            return Err(format!(
+                "{} config still differs from {} Mode after writing{}",
+                editor, mode, backup
+            ));
+        }
+        Self::detect_config_drift(editor, path);
+
+        Self::push_notification(
+            format!(
+                "{} switched to {} Mode. Restart {} to reconnect.",
+                editor, mode, editor
+            ),
+            NotificationLevel::Success,
+        );
+        Ok(())
+    }
+
     pub fn push_notification(message: String, level: NotificationLevel) {
-        let mut notifications = APP_STATE.write().notifications;
+        Self::notify(None, NotificationEvent::General, message, level);
+    }
+
+    /// Like `push_notification`, but linked to a server so the notification
+    /// center can open its console.
+    pub fn push_server_notification(
+        server_id: &str,
+        event: NotificationEvent,
+        message: String,
+        level: NotificationLevel,
+    ) {
+        Self::notify(Some(server_id.to_string()), event, message, level);
+    }
+
+    /// Record a notification and deliver it according to the user's rules.
+    fn notify(
+        server_id: Option<String>,
+        event: NotificationEvent,
+        message: String,
+        level: NotificationLevel,
+    ) {
         // Simple ID generation using time
         let id = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .subsec_nanos();
 
-        notifications.push(Notification {
+        let notification = Notification {
             id,
             message,
             level,
             duration: 5,
-        });
+            server_id,
+            created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            read: false,
+            event,
+        };
+
+        let mut history = APP_STATE.write().notification_history;
+        history.with_mut(|h| record_history(h, notification.clone(), NOTIFICATION_HISTORY_LIMIT));
+
+        let hour = chrono::Timelike::hour(&chrono::Local::now()) as u8;
+        let delivery =
+            APP_STATE
+                .read()
+                .notification_rules
+                .read()
+                .delivery(&notification.level, event, hour);
+        if delivery.native {
+            send_native_notification(&notification.message);
+        }
+        if delivery.toast {
+            let mut notifications = APP_STATE.write().notifications;
+            notifications.push(notification);
+        }
+    }
+
+    pub fn save_notification_rules(rules: NotificationRules) {
+        Self::save_json_setting(NOTIFICATION_RULES_KEY, &rules);
+        APP_STATE.write().notification_rules.set(rules);
     }
 
     pub fn remove_notification(id: u32) {
@@ -314,6 +3389,24 @@ impl AppState {
         notifications.retain(|n| n.id != id);
     }
 
+    pub fn mark_notification_read(id: u32) {
+        let mut history = APP_STATE.write().notification_history;
+        history.with_mut(|h| {
+            if let Some(n) = h.iter_mut().find(|n| n.id == id) {
+                n.read = true;
+            }
+        });
+    }
+
+    pub fn mark_all_notifications_read() {
+        let mut history = APP_STATE.write().notification_history;
+        history.write().iter_mut().for_each(|n| n.read = true);
+    }
+
+    pub fn clear_notification_history() {
+        APP_STATE.write().notification_history.set(Vec::new());
+    }
+
     pub async fn update_server_package(id: String) {
         let server_opt: Option<McpServer> = {
             let state = APP_STATE.read();
@@ -335,7 +3428,9 @@ impl AppState {
                         // Borrow args
                         let pkg_opt = args.iter().find(|a: &&String| !a.starts_with("-"));
                         if let Some(pkg) = pkg_opt {
-                            Self::push_notification(
+                            Self::push_server_notification(
+                                &server.id,
+                                NotificationEvent::PackageUpdate,
                                 format!("Updating {}...", pkg),
                                 NotificationLevel::Info,
                             );
@@ -348,20 +3443,26 @@ impl AppState {
                             match output {
                                 Ok(o) => {
                                     if o.status.success() {
-                                        Self::push_notification(
+                                        Self::push_server_notification(
+                                            &server.id,
+                                            NotificationEvent::PackageUpdate,
                                             format!("Updated {} successfully", pkg),
                                             NotificationLevel::Success,
                                         );
                                     } else {
                                         let err = String::from_utf8_lossy(&o.stderr);
-                                        Self::push_notification(
+                                        Self::push_server_notification(
+                                            &server.id,
+                                            NotificationEvent::PackageUpdate,
                                             format!("Update failed: {}", err),
                                             NotificationLevel::Error,
                                         );
                                     }
                                 }
                                 Err(e) => {
-                                    Self::push_notification(
+                                    Self::push_server_notification(
+                                        &server.id,
+                                        NotificationEvent::PackageUpdate,
                                         format!("Failed to run update: {}", e),
                                         NotificationLevel::Error,
                                     );
@@ -380,7 +3481,9 @@ impl AppState {
                             !a.starts_with("-") && a.as_str() != "tool" && a.as_str() != "run"
                         });
                         if let Some(pkg) = pkg_opt {
-                            Self::push_notification(
+                            Self::push_server_notification(
+                                &server.id,
+                                NotificationEvent::PackageUpdate,
                                 format!("Updating {}...", pkg),
                                 NotificationLevel::Info,
                             );
@@ -391,19 +3494,25 @@ impl AppState {
                             match output {
                                 Ok(o) => {
                                     if o.status.success() {
-                                        Self::push_notification(
+                                        Self::push_server_notification(
+                                            &server.id,
+                                            NotificationEvent::PackageUpdate,
                                             format!("Updated {} successfully", pkg),
                                             NotificationLevel::Success,
                                         );
                                     } else {
                                         let err = String::from_utf8_lossy(&o.stderr);
-                                        Self::push_notification(
+                                        Self::push_server_notification(
+                                            &server.id,
+                                            NotificationEvent::PackageUpdate,
                                             format!("Update info: {}", err),
                                             NotificationLevel::Info,
                                         );
                                     }
                                 }
-                                Err(e) => Self::push_notification(
+                                Err(e) => Self::push_server_notification(
+                                    &server.id,
+                                    NotificationEvent::PackageUpdate,
                                     format!("Update error: {}", e),
                                     NotificationLevel::Error,
                                 ),
@@ -413,7 +3522,9 @@ impl AppState {
                     }
                 }
 
-                Self::push_notification(
+                Self::push_server_notification(
+                    &server.id,
+                    NotificationEvent::PackageUpdate,
                     "Automatic update not supported for this configuration.".to_string(),
                     NotificationLevel::Warning,
                 );
@@ -480,4 +3591,77 @@ mod tests {
             assert_eq!(s_list_after.len(), 0);
         });
     }
+
+    fn note(id: u32) -> Notification {
+        Notification {
+            id,
+            message: format!("n{}", id),
+            level: NotificationLevel::Info,
+            duration: 5,
+            server_id: None,
+            created_at: String::new(),
+            read: false,
+            event: NotificationEvent::General,
+        }
+    }
+
+    #[test]
+    fn test_record_history_keeps_newest() {
+        let mut history = Vec::new();
+        for id in 0..5 {
+            record_history(&mut history, note(id), 3);
+        }
+        let ids: Vec<u32> = history.iter().map(|n| n.id).collect();
+        assert_eq!(ids, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_upsert_by_id() {
+        let mut list = vec![
+            McpServer::test_stdio("a", "echo").named("first"),
+            McpServer::test_stdio("b", "echo").named("second"),
+        ];
+        upsert_by_id(
+            &mut list,
+            McpServer::test_stdio("b", "echo").named("renamed"),
+        );
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[1].name, "renamed");
+
+        upsert_by_id(&mut list, McpServer::test_stdio("c", "echo").named("new"));
+        let ids: Vec<&str> = list.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "a", "b"]);
+
+        // New servers go after favorites
+        list[1].is_pinned = true;
+        list.swap(0, 1);
+        upsert_by_id(&mut list, McpServer::test_stdio("d", "echo").named("newer"));
+        let ids: Vec<&str> = list.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "d", "c", "b"]);
+    }
+
+    #[test]
+    fn test_move_before() {
+        let mut ids: Vec<String> = ["a", "b", "c", "d"].map(String::from).to_vec();
+        move_before(&mut ids, "d", "b");
+        assert_eq!(ids, ["a", "d", "b", "c"]);
+        move_before(&mut ids, "a", "c");
+        assert_eq!(ids, ["d", "b", "a", "c"]);
+        // Unknown ids and dropping on itself change nothing
+        move_before(&mut ids, "x", "a");
+        move_before(&mut ids, "a", "x");
+        move_before(&mut ids, "a", "a");
+        assert_eq!(ids, ["d", "b", "a", "c"]);
+    }
+
+    #[test]
+    fn test_applescript_string() {
+        assert_eq!(applescript_string("plain"), r#""plain""#);
+        assert_eq!(
+            applescript_string(r#"say "hi" \ bye"#),
+            r#""say \"hi\" \\ bye""#
+        );
+        // Only quotes and backslashes are special
+        assert_eq!(applescript_string("tab\tü"), "\"tab\tü\"");
+    }
 }
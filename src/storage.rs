@@ -0,0 +1,582 @@
+//! Managed directory layout for files the manager installs on behalf of
+//! servers: source installs and clones, extracted bundles and caches.
+//!
+//! ```text
+//! <root>/servers/<server id>/   source installs and git clones
+//! <root>/extensions/<name>/     extracted .mcpb / .dxt bundles
+//! <root>/cache/                 downloads and other re-creatable files
+//! ```
+//!
+//! The root defaults to the data dir and can be moved, e.g. to a larger
+//! disk. Directories no server refers to any more are orphans and can be
+//! cleaned up. Servers also fill caches outside the root, such as npx's;
+//! `server_usage` reports those alongside the managed directories.
+
+use crate::models::{AppError, AppResult, McpServer};
+use std::path::{Path, PathBuf};
+
+/// File in the data dir holding a custom storage root.
+const ROOT_FILE: &str = "storage_root";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageKind {
+    Servers,
+    Extensions,
+    Cache,
+}
+
+impl StorageKind {
+    pub const ALL: [StorageKind; 3] = [
+        StorageKind::Servers,
+        StorageKind::Extensions,
+        StorageKind::Cache,
+    ];
+
+    pub fn dir_name(&self) -> &'static str {
+        match self {
+            StorageKind::Servers => "servers",
+            StorageKind::Extensions => "extensions",
+            StorageKind::Cache => "cache",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StorageKind::Servers => "Server installs",
+            StorageKind::Extensions => "Extensions",
+            StorageKind::Cache => "Cache",
+        }
+    }
+}
+
+fn root_in(data_dir: &Path) -> PathBuf {
+    std::fs::read_to_string(data_dir.join(ROOT_FILE))
+        .ok()
+        .map(|s| PathBuf::from(s.trim()))
+        .filter(|p| p.is_absolute())
+        .unwrap_or_else(|| data_dir.to_path_buf())
+}
+
+fn set_root_in(data_dir: &Path, root: Option<&Path>) -> AppResult<()> {
+    let file = data_dir.join(ROOT_FILE);
+    match root {
+        Some(root) if root != data_dir => {
+            if !root.is_absolute() {
+                return Err(AppError::Io(format!(
+                    "Storage location must be an absolute path: {}",
+                    root.display()
+                )));
+            }
+            std::fs::create_dir_all(root)?;
+            std::fs::create_dir_all(data_dir)?;
+            std::fs::write(file, root.to_string_lossy().as_bytes())?;
+        }
+        _ => {
+            if file.exists() {
+                std::fs::remove_file(file)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Root of the managed layout.
+pub fn root() -> AppResult<PathBuf> {
+    Ok(root_in(&crate::platform::app_data_dir()?))
+}
+
+/// Move the root for new installs; `None` resets it to the data dir.
+/// Existing installs stay where they are.
+pub fn set_root(root: Option<&Path>) -> AppResult<()> {
+    set_root_in(&crate::platform::app_data_dir()?, root)
+}
+
+pub fn dir(kind: StorageKind) -> AppResult<PathBuf> {
+    Ok(root()?.join(kind.dir_name()))
+}
+
+/// File-safe directory name for `name`.
+pub fn dir_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    name.trim_matches('.').to_string()
+}
+
+/// Where a server's source install or clone lives.
+pub fn server_dir(server_id: &str) -> AppResult<PathBuf> {
+    Ok(dir(StorageKind::Servers)?.join(dir_name(server_id)))
+}
+
+/// Total size of the files under `path`, not following symlinks.
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| dir_size(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Size of each kind's directory under `root`.
+pub fn usage_in(root: &Path) -> Vec<(StorageKind, u64)> {
+    StorageKind::ALL
+        .into_iter()
+        .map(|kind| (kind, dir_size(&root.join(kind.dir_name()))))
+        .collect()
+}
+
+/// Whether `server` launches from, runs in or mounts somewhere inside
+/// `dir`, or points an env var at it.
+fn refers_to(server: &McpServer, dir: &Path) -> bool {
+    let dir = dir.to_string_lossy();
+    server
+        .command
+        .iter()
+        .chain(server.args.iter().flatten())
+        .chain(server.cwd.iter())
+        .chain(server.env.iter().flat_map(|env| env.values()))
+        .chain(server.docker_mounts.iter().flatten())
+        .any(|part| part.contains(dir.as_ref()))
+}
+
+/// Install and extension directories under `root` that no server in
+/// `servers` uses any more.
+pub fn orphans_in(root: &Path, servers: &[McpServer]) -> Vec<PathBuf> {
+    let subdirs = |kind: StorageKind| -> Vec<PathBuf> {
+        std::fs::read_dir(root.join(kind.dir_name()))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.is_dir())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut orphans: Vec<PathBuf> = subdirs(StorageKind::Servers)
+        .into_iter()
+        .filter(|dir| {
+            let name = dir.file_name().map(|n| n.to_string_lossy().to_string());
+            !servers
+                .iter()
+                .any(|s| Some(dir_name(&s.id)) == name || refers_to(s, dir))
+        })
+        .chain(
+            subdirs(StorageKind::Extensions)
+                .into_iter()
+                .filter(|dir| !servers.iter().any(|s| refers_to(s, dir))),
+        )
+        .collect();
+    orphans.sort();
+    orphans
+}
+
+/// Orphaned directories under the current root.
+pub fn orphans(servers: &[McpServer]) -> AppResult<Vec<PathBuf>> {
+    Ok(orphans_in(&root()?, servers))
+}
+
+/// Delete `dirs`, returning how many bytes were freed.
+pub fn remove_dirs(dirs: &[PathBuf]) -> AppResult<u64> {
+    let mut freed = 0;
+    for dir in dirs {
+        let size = dir_size(dir);
+        std::fs::remove_dir_all(dir)?;
+        freed += size;
+    }
+    Ok(freed)
+}
+
+/// What part of a server's footprint a directory or file is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageKind {
+    /// Source install, clone or extracted bundle.
+    Install,
+    /// Packages npx downloaded to run the server.
+    NpxCache,
+    /// Environment `uv tool` installed the server into.
+    UvTool,
+    /// Recorded stdio sessions.
+    Logs,
+}
+
+impl UsageKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UsageKind::Install => "Install",
+            UsageKind::NpxCache => "npx cache",
+            UsageKind::UvTool => "uv tool",
+            UsageKind::Logs => "Logs",
+        }
+    }
+
+    /// Whether deleting it only costs a re-download on next launch.
+    pub fn is_cache(&self) -> bool {
+        matches!(self, UsageKind::NpxCache)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageEntry {
+    pub kind: UsageKind,
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// Places outside the storage root that servers fill up. Each is `None`
+/// when it can't be located on this machine.
+#[derive(Debug, Clone, Default)]
+pub struct Locations {
+    pub root: PathBuf,
+    /// npm's `_npx` directory, one subdirectory per package set.
+    pub npx_cache: Option<PathBuf>,
+    pub uv_tools: Option<PathBuf>,
+    pub recordings: Option<PathBuf>,
+}
+
+impl Locations {
+    /// Locations on this machine, honouring `npm_config_cache` and
+    /// `UV_TOOL_DIR` when they are set.
+    pub fn current() -> AppResult<Self> {
+        let home = dirs::home_dir();
+        let npm_cache = std::env::var_os("npm_config_cache")
+            .map(PathBuf::from)
+            .or_else(|| {
+                if cfg!(windows) {
+                    dirs::data_local_dir().map(|d| d.join("npm-cache"))
+                } else {
+                    home.as_ref().map(|h| h.join(".npm"))
+                }
+            });
+        let uv_tools = std::env::var_os("UV_TOOL_DIR")
+            .map(PathBuf::from)
+            .or_else(|| {
+                if cfg!(windows) {
+                    dirs::data_dir().map(|d| d.join("uv").join("data").join("tools"))
+                } else {
+                    std::env::var_os("XDG_DATA_HOME")
+                        .map(PathBuf::from)
+                        .or_else(|| home.as_ref().map(|h| h.join(".local").join("share")))
+                        .map(|d| d.join("uv").join("tools"))
+                }
+            });
+        Ok(Self {
+            root: root()?,
+            npx_cache: npm_cache.map(|d| d.join("_npx")),
+            uv_tools,
+            recordings: crate::session_recording::recordings_dir().ok(),
+        })
+    }
+}
+
+/// Package `server` runs through npx or uvx, without its version, e.g.
+/// `npx -y @scope/pkg@1.2` -> `("npx", "@scope/pkg")`.
+fn launched_package(server: &McpServer) -> Option<(&'static str, String)> {
+    let command = Path::new(server.command.as_deref()?.trim()).file_stem()?;
+    let launcher = match command.to_str()? {
+        "npx" => "npx",
+        "uvx" => "uvx",
+        _ => return None,
+    };
+    let args: Vec<&str> = server.args.iter().flatten().map(|a| a.trim()).collect();
+    let spec = match args
+        .iter()
+        .position(|a| *a == "--from" || *a == "--package")
+    {
+        Some(i) => args.get(i + 1)?,
+        None => args.iter().find(|a| !a.is_empty() && !a.starts_with('-'))?,
+    };
+    let name = if launcher == "npx" {
+        // Keep the leading @ of a scope, drop a trailing @version
+        match spec.char_indices().skip(1).find(|(_, c)| *c == '@') {
+            Some((i, _)) => &spec[..i],
+            None => spec,
+        }
+    } else {
+        spec.split(['=', '@', '<', '>', '[', '~', '!'])
+            .next()
+            .unwrap_or(spec)
+    };
+    (!name.is_empty()).then(|| (launcher, name.to_string()))
+}
+
+/// npx cache directories whose package set includes `package`.
+fn npx_dirs(npx_cache: &Path, package: &str) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(npx_cache) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|dir| {
+            std::fs::read_to_string(dir.join("package.json"))
+                .ok()
+                .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+                .is_some_and(|manifest| manifest["dependencies"].get(package).is_some())
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Whether `file_name` is a recording of `server_name`, as named by
+/// `session_recording::recording_file_name`.
+fn is_recording_of(file_name: &str, server_name: &str) -> bool {
+    let prefix = crate::session_recording::recording_file_name(server_name, "");
+    let prefix = prefix.trim_end_matches(".jsonl");
+    let Some(timestamp) = file_name
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.strip_suffix(".jsonl"))
+    else {
+        return false;
+    };
+    // %Y%m%d-%H%M%S
+    timestamp.len() == 15
+        && timestamp
+            .char_indices()
+            .all(|(i, c)| if i == 8 { c == '-' } else { c.is_ascii_digit() })
+}
+
+/// Everything on disk that belongs to `server`, largest first.
+pub fn server_usage(server: &McpServer, locations: &Locations) -> Vec<UsageEntry> {
+    let mut paths: Vec<(UsageKind, PathBuf)> = Vec::new();
+    let install = locations
+        .root
+        .join(StorageKind::Servers.dir_name())
+        .join(dir_name(&server.id));
+    if install.exists() {
+        paths.push((UsageKind::Install, install));
+    }
+    if let Ok(entries) = std::fs::read_dir(locations.root.join(StorageKind::Extensions.dir_name()))
+    {
+        for dir in entries.flatten().map(|e| e.path()) {
+            if refers_to(server, &dir) {
+                paths.push((UsageKind::Install, dir));
+            }
+        }
+    }
+    match launched_package(server) {
+        Some(("npx", package)) => {
+            if let Some(npx_cache) = &locations.npx_cache {
+                for dir in npx_dirs(npx_cache, &package) {
+                    paths.push((UsageKind::NpxCache, dir));
+                }
+            }
+        }
+        Some((_, package)) => {
+            let tool = locations.uv_tools.as_ref().map(|d| d.join(&package));
+            if let Some(tool) = tool.filter(|t| t.is_dir()) {
+                paths.push((UsageKind::UvTool, tool));
+            }
+        }
+        None => {}
+    }
+    if let Ok(entries) = locations
+        .recordings
+        .as_deref()
+        .map(std::fs::read_dir)
+        .transpose()
+    {
+        for entry in entries.into_iter().flatten().flatten() {
+            if is_recording_of(&entry.file_name().to_string_lossy(), &server.name) {
+                paths.push((UsageKind::Logs, entry.path()));
+            }
+        }
+    }
+
+    let mut usage: Vec<UsageEntry> = paths
+        .into_iter()
+        .map(|(kind, path)| UsageEntry {
+            kind,
+            bytes: dir_size(&path),
+            path,
+        })
+        .collect();
+    usage.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.path.cmp(&b.path)));
+    usage
+}
+
+/// Delete the cache entries in `usage`, returning how many bytes were
+/// freed. Installs and logs are left alone.
+pub fn clean_caches(usage: &[UsageEntry]) -> AppResult<u64> {
+    let caches: Vec<PathBuf> = usage
+        .iter()
+        .filter(|e| e.kind.is_cache() && e.path.exists())
+        .map(|e| e.path.clone())
+        .collect();
+    remove_dirs(&caches)
+}
+
+/// `bytes` for display, e.g. "1.5 MB".
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("omm-storage-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_root_setting() {
+        let data = temp_dir();
+        assert_eq!(root_in(&data), data);
+
+        let custom = temp_dir();
+        set_root_in(&data, Some(&custom)).unwrap();
+        assert_eq!(root_in(&data), custom);
+        assert!(set_root_in(&data, Some(Path::new("relative"))).is_err());
+
+        set_root_in(&data, None).unwrap();
+        assert_eq!(root_in(&data), data);
+        let _ = std::fs::remove_dir_all(&data);
+        let _ = std::fs::remove_dir_all(&custom);
+    }
+
+    #[test]
+    fn test_usage_and_orphans() {
+        let root = temp_dir();
+        let kept = root.join("servers").join("a");
+        let orphan = root.join("servers").join("gone");
+        let extension = root.join("extensions").join("weather");
+        let unused_extension = root.join("extensions").join("old");
+        for dir in [&kept, &orphan, &extension, &unused_extension] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::write(kept.join("index.js"), [0u8; 100]).unwrap();
+        std::fs::write(orphan.join("index.js"), [0u8; 50]).unwrap();
+
+        let entry = extension
+            .join("server/index.js")
+            .to_string_lossy()
+            .to_string();
+        let servers = [
+            McpServer::test_stdio("a", "node"),
+            McpServer::test_stdio("b", "node").with_args(&[&entry]),
+        ];
+        let orphans = orphans_in(&root, &servers);
+        assert_eq!(orphans, vec![unused_extension, orphan.clone()]);
+
+        let usage = usage_in(&root);
+        assert_eq!(usage[0], (StorageKind::Servers, 150));
+        assert_eq!(usage[2], (StorageKind::Cache, 0));
+        assert_eq!(remove_dirs(&orphans[1..]).unwrap(), 50);
+        assert!(!orphan.exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_cwd_and_env_keep_dirs() {
+        let root = temp_dir();
+        let by_cwd = root.join("extensions").join("by-cwd");
+        let by_env = root.join("servers").join("by-env");
+        for dir in [&by_cwd, &by_env] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+
+        let mut in_cwd = McpServer::test_stdio("a", "node");
+        in_cwd.cwd = Some(by_cwd.to_string_lossy().to_string());
+        let data_dir = by_env.join("data").to_string_lossy().to_string();
+        let with_env = McpServer::test_stdio("b", "node").with_env(&[("DATA_DIR", &data_dir)]);
+        assert!(orphans_in(&root, &[in_cwd, with_env]).is_empty());
+        assert_eq!(orphans_in(&root, &[]), vec![by_cwd, by_env]);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_server_usage() {
+        let base = temp_dir();
+        let locations = Locations {
+            root: base.join("root"),
+            npx_cache: Some(base.join("_npx")),
+            uv_tools: Some(base.join("tools")),
+            recordings: Some(base.join("recordings")),
+        };
+        let npx_dir = base.join("_npx").join("0a1b");
+        let other_npx_dir = base.join("_npx").join("2c3d");
+        for dir in [
+            &npx_dir,
+            &other_npx_dir,
+            &locations.root.join("servers").join("a"),
+        ] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::create_dir_all(base.join("recordings")).unwrap();
+        std::fs::write(
+            npx_dir.join("package.json"),
+            r#"{"dependencies":{"@scope/memory":"^1.0.0"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            other_npx_dir.join("package.json"),
+            r#"{"dependencies":{"other":"^1.0.0"}}"#,
+        )
+        .unwrap();
+        std::fs::write(npx_dir.join("index.js"), [0u8; 300]).unwrap();
+        std::fs::write(base.join("recordings/a-20260101-120000.jsonl"), [0u8; 20]).unwrap();
+        std::fs::write(base.join("recordings/a-b-20260101-120000.jsonl"), [0u8; 20]).unwrap();
+
+        let a = McpServer::test_stdio("a", "npx").with_args(&["-y", "@scope/memory@1.2.0"]);
+        let usage = server_usage(&a, &locations);
+        let kinds: Vec<UsageKind> = usage.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            [UsageKind::NpxCache, UsageKind::Logs, UsageKind::Install]
+        );
+        assert_eq!(usage[0].path, npx_dir);
+
+        assert_eq!(clean_caches(&usage).unwrap(), usage[0].bytes);
+        assert!(!npx_dir.exists());
+        assert!(other_npx_dir.exists());
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_launched_package() {
+        let mut s = McpServer::test_stdio("a", "npx").with_args(&["-y", "@scope/pkg@1.2"]);
+        assert_eq!(
+            launched_package(&s),
+            Some(("npx", "@scope/pkg".to_string()))
+        );
+        s.command = Some("uvx".to_string());
+        s.args = Some(vec![
+            "--from".into(),
+            "mcp-git==1.0".into(),
+            "mcp-server-git".into(),
+        ]);
+        assert_eq!(launched_package(&s), Some(("uvx", "mcp-git".to_string())));
+        s.command = Some("node".to_string());
+        assert_eq!(launched_package(&s), None);
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MB");
+    }
+}
@@ -0,0 +1,322 @@
+//! Generated reference docs for a tool: its input schema flattened into a
+//! parameter table and its annotations spelled out. Examples come from
+//! the arguments of past successful calls.
+//!
+//! The same schema is also broken down node by node for the schema tree
+//! in the Tools tab.
+
+use crate::models::ToolAnnotations;
+use serde_json::Value;
+
+/// How deep nested object properties are expanded into dotted names.
+const MAX_DEPTH: usize = 3;
+
+/// One row of the parameter table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    /// Dotted path for nested properties, e.g. `filter.status`.
+    pub name: String,
+    pub type_label: String,
+    pub required: bool,
+    pub description: Option<String>,
+    pub default: Option<String>,
+    /// Allowed values, for enums.
+    pub choices: Vec<String>,
+}
+
+/// Schema keywords the schema tree shows in its own way; others are
+/// listed as plain JSON.
+const TREE_KEYS: [&str; 9] = [
+    "type",
+    "description",
+    "properties",
+    "required",
+    "enum",
+    "items",
+    "anyOf",
+    "oneOf",
+    "allOf",
+];
+
+/// One schema of the schema tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaNode {
+    pub type_label: String,
+    pub description: Option<String>,
+    /// Allowed values, for enums.
+    pub choices: Vec<String>,
+    /// Property schemas with whether each is required, then the items of
+    /// an array and the variants of `anyOf` and the like.
+    pub children: Vec<(String, Value, bool)>,
+    /// Other keywords, e.g. `default` or `minimum`.
+    pub extra: serde_json::Map<String, Value>,
+}
+
+/// `schema` broken down for the schema tree.
+pub fn schema_node(schema: &Value) -> SchemaNode {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let mut children: Vec<(String, Value, bool)> = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(name, property)| {
+            let required = required.contains(&name.as_str());
+            (name.clone(), property.clone(), required)
+        })
+        .collect();
+    if let Some(items) = schema.get("items") {
+        children.push(("items".to_string(), items.clone(), false));
+    }
+    for keyword in ["anyOf", "oneOf", "allOf"] {
+        let variants = schema.get(keyword).and_then(Value::as_array);
+        for (i, variant) in variants.into_iter().flatten().enumerate() {
+            children.push((format!("{}[{}]", keyword, i), variant.clone(), false));
+        }
+    }
+    SchemaNode {
+        type_label: type_label(schema),
+        description: schema
+            .get("description")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        choices: schema
+            .get("enum")
+            .and_then(Value::as_array)
+            .map(|values| values.iter().map(value_text).collect())
+            .unwrap_or_default(),
+        children,
+        extra: schema
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter(|(k, _)| !TREE_KEYS.contains(&k.as_str()))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+    }
+}
+
+/// Readable type of a schema, e.g. `string`, `array of number`,
+/// `string | null`.
+fn type_label(schema: &Value) -> String {
+    match &schema["type"] {
+        Value::String(t) if t == "array" => match schema.get("items") {
+            Some(items) => format!("array of {}", type_label(items)),
+            None => "array".to_string(),
+        },
+        Value::String(t) => t.clone(),
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" | "),
+        _ => {
+            let variants = schema
+                .get("anyOf")
+                .or_else(|| schema.get("oneOf"))
+                .and_then(Value::as_array);
+            match variants {
+                Some(variants) => variants
+                    .iter()
+                    .map(type_label)
+                    .collect::<Vec<_>>()
+                    .join(" | "),
+                None if schema.get("enum").is_some() => "enum".to_string(),
+                None => "any".to_string(),
+            }
+        }
+    }
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn collect(schema: &Value, prefix: &str, depth: usize, params: &mut Vec<Param>) {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return;
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    for (name, property) in properties {
+        let path = format!("{}{}", prefix, name);
+        params.push(Param {
+            name: path.clone(),
+            type_label: type_label(property),
+            required: required.contains(&name.as_str()),
+            description: property
+                .get("description")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            default: property.get("default").map(value_text),
+            choices: property
+                .get("enum")
+                .and_then(Value::as_array)
+                .map(|values| values.iter().map(value_text).collect())
+                .unwrap_or_default(),
+        });
+        if depth + 1 < MAX_DEPTH {
+            collect(property, &format!("{}.", path), depth + 1, params);
+        }
+    }
+}
+
+/// Parameters of a tool's `inputSchema`, required ones first, nested
+/// object properties after their parent.
+pub fn parameters(input_schema: &Value) -> Vec<Param> {
+    let mut params = Vec::new();
+    collect(input_schema, "", 0, &mut params);
+    let required: Vec<String> = params
+        .iter()
+        .filter(|p| p.required && !p.name.contains('.'))
+        .map(|p| p.name.clone())
+        .collect();
+    // Stable, so nested rows stay after their parent
+    params.sort_by_key(|p| {
+        let top = p.name.split('.').next().unwrap_or_default().to_string();
+        (!required.contains(&top), top)
+    });
+    params
+}
+
+/// The hints a tool's annotations set, as (label, explanation) pairs.
+pub fn hints(annotations: &ToolAnnotations) -> Vec<(&'static str, &'static str)> {
+    let mut hints = Vec::new();
+    match annotations.readOnlyHint {
+        Some(true) => hints.push(("Read-only", "Does not modify its environment")),
+        Some(false) => hints.push(("Writes", "May modify its environment")),
+        None => {}
+    }
+    if annotations.destructiveHint == Some(true) {
+        hints.push(("Destructive", "May delete or overwrite data"));
+    }
+    if annotations.idempotentHint == Some(true) {
+        hints.push((
+            "Idempotent",
+            "Repeating a call with the same arguments has no further effect",
+        ));
+    }
+    match annotations.openWorldHint {
+        Some(true) => hints.push(("Open world", "Talks to external systems")),
+        Some(false) => hints.push(("Closed world", "Only touches local state")),
+        None => {}
+    }
+    hints
+}
+
+/// Past arguments pretty-printed for display; unparsable ones as stored.
+pub fn format_example(args: &str) -> String {
+    serde_json::from_str::<Value>(args)
+        .and_then(|v| serde_json::to_string_pretty(&v))
+        .unwrap_or_else(|_| args.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parameters() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "limit": { "type": "integer", "default": 10 },
+                "filter": {
+                    "type": "object",
+                    "description": "Narrow the results",
+                    "properties": {
+                        "status": { "enum": ["open", "closed"] }
+                    }
+                },
+                "query": { "type": "string", "description": "Search text" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "cursor": { "type": ["string", "null"] }
+            },
+            "required": ["query"]
+        });
+        let params = parameters(&schema);
+        let names: Vec<&str> = params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "query",
+                "cursor",
+                "filter",
+                "filter.status",
+                "limit",
+                "tags"
+            ]
+        );
+        assert!(params[0].required);
+        assert_eq!(params[0].description.as_deref(), Some("Search text"));
+        assert_eq!(params[1].type_label, "string | null");
+        assert_eq!(params[3].type_label, "enum");
+        assert_eq!(params[3].choices, ["open", "closed"]);
+        assert_eq!(params[4].default.as_deref(), Some("10"));
+        assert_eq!(params[5].type_label, "array of string");
+        assert!(parameters(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_schema_node() {
+        let schema = json!({
+            "type": "object",
+            "description": "Search options",
+            "properties": {
+                "query": { "type": "string" },
+                "mode": { "enum": ["fast", "exact"], "default": "fast" },
+                "ids": { "type": "array", "items": { "type": "integer" } }
+            },
+            "required": ["query"],
+            "additionalProperties": false
+        });
+        let node = schema_node(&schema);
+        assert_eq!(node.type_label, "object");
+        assert_eq!(node.description.as_deref(), Some("Search options"));
+        let children: Vec<(&str, bool)> = node
+            .children
+            .iter()
+            .map(|(name, _, required)| (name.as_str(), *required))
+            .collect();
+        assert_eq!(children, [("ids", false), ("mode", false), ("query", true)]);
+        assert_eq!(
+            node.extra.keys().collect::<Vec<_>>(),
+            ["additionalProperties"]
+        );
+
+        let mode = schema_node(&node.children[1].1);
+        assert_eq!(mode.choices, ["fast", "exact"]);
+        assert_eq!(mode.extra["default"], json!("fast"));
+        let ids = schema_node(&node.children[0].1);
+        assert_eq!(ids.type_label, "array of integer");
+        assert_eq!(ids.children[0].0, "items");
+
+        let nullable = schema_node(&json!({"anyOf": [{"type": "string"}, {"type": "null"}]}));
+        assert_eq!(nullable.type_label, "string | null");
+        assert_eq!(nullable.children.len(), 2);
+        assert_eq!(nullable.children[1].0, "anyOf[1]");
+    }
+
+    #[test]
+    fn test_hints() {
+        let annotations = ToolAnnotations {
+            readOnlyHint: Some(false),
+            destructiveHint: Some(true),
+            ..Default::default()
+        };
+        let labels: Vec<&str> = hints(&annotations).iter().map(|(l, _)| *l).collect();
+        assert_eq!(labels, ["Writes", "Destructive"]);
+        assert_eq!(format_example(r#"{"q":1}"#), "{\n  \"q\": 1\n}");
+    }
+}
@@ -0,0 +1,196 @@
+//! Rewriting tool call arguments in the hub, per the rules saved for a
+//! server: defaults, renamed keys, clamped numbers and paths kept under
+//! a root. Rules apply in the order they were added.
+
+use crate::models::{RuleAction, ToolRule};
+use serde_json::{Map, Value};
+use std::path::{Component, Path, PathBuf};
+
+/// Short description of a rule for lists, e.g. "rename file → path".
+pub fn describe(action: &RuleAction) -> String {
+    match action {
+        RuleAction::Default { key, value } => format!("default {} = {}", key, value),
+        RuleAction::Rename { from, to } => format!("rename {} → {}", from, to),
+        RuleAction::Clamp { key, min, max } => match (min, max) {
+            (Some(min), Some(max)) => format!("clamp {} to {}–{}", key, min, max),
+            (Some(min), None) => format!("clamp {} to at least {}", key, min),
+            (None, Some(max)) => format!("clamp {} to at most {}", key, max),
+            (None, None) => format!("clamp {}", key),
+        },
+        RuleAction::Root { key, root } => format!("keep {} under {}", key, root),
+    }
+}
+
+/// `path` with `.` and `..` worked out, without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+fn apply_one(action: &RuleAction, args: &mut Map<String, Value>) -> Result<(), String> {
+    match action {
+        RuleAction::Default { key, value } => {
+            args.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+        RuleAction::Rename { from, to } => {
+            if let Some(value) = args.remove(from) {
+                args.insert(to.clone(), value);
+            }
+        }
+        RuleAction::Clamp { key, min, max } => {
+            if let Some(n) = args.get(key).and_then(Value::as_f64) {
+                let clamped = min.map_or(n, |min| n.max(min));
+                let clamped = max.map_or(clamped, |max| clamped.min(max));
+                if clamped != n {
+                    // Keep integers integers
+                    let value = if args[key].is_i64() || args[key].is_u64() {
+                        Value::from(clamped as i64)
+                    } else {
+                        Value::from(clamped)
+                    };
+                    args.insert(key.clone(), value);
+                }
+            }
+        }
+        RuleAction::Root { key, root } => {
+            let root = normalize(Path::new(root));
+            let path = match args.get(key) {
+                None | Some(Value::Null) => root.clone(),
+                Some(Value::String(path)) => normalize(&root.join(path)),
+                Some(_) => return Err(format!("{} must be a path", key)),
+            };
+            if !path.starts_with(&root) {
+                return Err(format!("{} must be under {}", key, root.display()));
+            }
+            args.insert(
+                key.clone(),
+                Value::from(path.to_string_lossy().into_owned()),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `args` for a call to `tool` after the server's `rules`. Fails when a
+/// rule refuses the call, e.g. a path outside its root.
+pub fn apply(rules: &[ToolRule], tool: &str, args: Value) -> Result<Value, String> {
+    let mut rules = rules
+        .iter()
+        .filter(|r| r.tool == "*" || r.tool == tool)
+        .peekable();
+    if rules.peek().is_none() {
+        return Ok(args);
+    }
+    let mut map = match args {
+        Value::Object(map) => map,
+        Value::Null => Map::new(),
+        other => return Ok(other),
+    };
+    for rule in rules {
+        apply_one(&rule.action, &mut map)?;
+    }
+    Ok(Value::Object(map))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(tool: &str, action: RuleAction) -> ToolRule {
+        ToolRule {
+            id: 0,
+            server_id: "fs".into(),
+            tool: tool.into(),
+            action,
+        }
+    }
+
+    #[test]
+    fn test_default_and_rename() {
+        let rules = vec![
+            rule(
+                "*",
+                RuleAction::Rename {
+                    from: "file".into(),
+                    to: "path".into(),
+                },
+            ),
+            rule(
+                "read",
+                RuleAction::Default {
+                    key: "encoding".into(),
+                    value: json!("utf-8"),
+                },
+            ),
+        ];
+        let args = apply(&rules, "read", json!({"file": "a.txt"})).unwrap();
+        assert_eq!(args, json!({"path": "a.txt", "encoding": "utf-8"}));
+        // Defaults don't override what the call sets
+        let args = apply(&rules, "read", json!({"encoding": "latin1"})).unwrap();
+        assert_eq!(args, json!({"encoding": "latin1"}));
+        // Rules for other tools are left out
+        let args = apply(&rules, "write", json!({"file": "a.txt"})).unwrap();
+        assert_eq!(args, json!({"path": "a.txt"}));
+    }
+
+    #[test]
+    fn test_clamp() {
+        let rules = vec![rule(
+            "search",
+            RuleAction::Clamp {
+                key: "limit".into(),
+                min: Some(1.0),
+                max: Some(50.0),
+            },
+        )];
+        let clamp = |args| apply(&rules, "search", args).unwrap()["limit"].clone();
+        assert_eq!(clamp(json!({"limit": 500})), json!(50));
+        assert_eq!(clamp(json!({"limit": 0.5})), json!(1.0));
+        assert_eq!(clamp(json!({"limit": 10})), json!(10));
+        assert_eq!(clamp(json!({"limit": "ten"})), json!("ten"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_root() {
+        let rules = vec![rule(
+            "*",
+            RuleAction::Root {
+                key: "path".into(),
+                root: "/srv/data".into(),
+            },
+        )];
+        let path = |args| apply(&rules, "list", args).map(|a| a["path"].clone());
+        assert_eq!(path(json!({})), Ok(json!("/srv/data")));
+        assert_eq!(
+            path(json!({"path": "logs/./a"})),
+            Ok(json!("/srv/data/logs/a"))
+        );
+        assert_eq!(
+            path(json!({"path": "/srv/data/x"})),
+            Ok(json!("/srv/data/x"))
+        );
+        assert!(path(json!({"path": "../etc/passwd"})).is_err());
+        assert!(path(json!({"path": "/etc/passwd"})).is_err());
+    }
+
+    #[test]
+    fn test_describe() {
+        let action = RuleAction::Clamp {
+            key: "limit".into(),
+            min: None,
+            max: Some(50.0),
+        };
+        assert_eq!(describe(&action), "clamp limit to at most 50");
+    }
+}
@@ -0,0 +1,384 @@
+//! Checking GitHub Releases for newer versions of the manager itself.
+
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::PathBuf;
+
+const RELEASES_API: &str =
+    "https://api.github.com/repos/millsydotdev/Open-MCP-Manager/releases/latest";
+
+/// Version of the running build.
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// How often the background check runs.
+pub const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Release {
+    pub tag_name: String,
+    pub name: Option<String>,
+    /// Markdown changelog.
+    pub body: Option<String>,
+    pub html_url: String,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+impl Release {
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+}
+
+/// `major.minor.patch` from a tag like `v1.2.3` or `1.2.3-beta`.
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version
+        .trim()
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    Some((major, minor, patch))
+}
+
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(l), Some(c)) => l > c,
+        _ => false,
+    }
+}
+
+/// CPU names release assets are tagged with.
+const ARCH_TOKENS: [&str; 8] = [
+    "x86_64", "x64", "amd64", "aarch64", "arm64", "i686", "i386", "armv7",
+];
+
+/// Installer asset for this platform built for this CPU, or failing that
+/// one whose name names no CPU at all, such as a universal build.
+pub fn pick_asset<'a>(
+    assets: &'a [ReleaseAsset],
+    os: &str,
+    arch: &str,
+) -> Option<&'a ReleaseAsset> {
+    let extensions: &[&str] = match os {
+        "windows" => &[".msi", ".exe"],
+        "macos" => &[".dmg"],
+        "linux" => &[".appimage", ".deb"],
+        _ => return None,
+    };
+    let arch_aliases: &[&str] = match arch {
+        "x86_64" => &["x86_64", "x64", "amd64"],
+        "aarch64" => &["aarch64", "arm64"],
+        _ => &[],
+    };
+
+    for ext in extensions {
+        let candidates: Vec<&ReleaseAsset> = assets
+            .iter()
+            .filter(|a| a.name.to_lowercase().ends_with(ext))
+            .collect();
+        let for_arch = candidates.iter().find(|a| {
+            let name = a.name.to_lowercase();
+            arch_aliases.iter().any(|alias| name.contains(alias))
+        });
+        let any_arch = candidates.iter().find(|a| {
+            let name = a.name.to_lowercase();
+            !ARCH_TOKENS.iter().any(|token| name.contains(token))
+        });
+        if let Some(asset) = for_arch.or(any_arch) {
+            return Some(asset);
+        }
+    }
+    None
+}
+
+/// The latest release, if it is newer than this build.
+pub async fn check_latest() -> Result<Option<Release>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let release: Release = client
+        .get(RELEASES_API)
+        .header("User-Agent", "Open-MCP-Manager")
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if is_newer(&release.tag_name, CURRENT_VERSION) {
+        Ok(Some(release))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Published checksum for `asset`: a `<name>.sha256` file next to it, or
+/// else a combined sums file such as `SHA256SUMS` or `checksums.txt`.
+pub fn checksum_asset<'a>(
+    assets: &'a [ReleaseAsset],
+    asset: &ReleaseAsset,
+) -> Option<&'a ReleaseAsset> {
+    let own = format!("{}.sha256", asset.name).to_lowercase();
+    assets
+        .iter()
+        .find(|a| a.name.to_lowercase() == own)
+        .or_else(|| {
+            assets.iter().find(|a| {
+                let name = a.name.to_lowercase();
+                name.contains("sha256sum") || name.starts_with("checksums")
+            })
+        })
+}
+
+/// Hex SHA-256 for `name` in a checksum file: either a bare digest or
+/// `sha256sum`-style lines of `<digest>  <file name>`.
+pub fn expected_checksum(text: &str, name: &str) -> Option<String> {
+    let is_digest = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit());
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    if let [only] = lines.as_slice() {
+        if is_digest(only) {
+            return Some(only.to_lowercase());
+        }
+    }
+    lines.iter().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let digest = parts.next()?;
+        // `sha256sum -b` marks binary files with a leading `*`
+        let file = parts.next()?.trim_start_matches('*');
+        (is_digest(digest) && file == name).then(|| digest.to_lowercase())
+    })
+}
+
+fn client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .user_agent("Open-MCP-Manager")
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+/// Download an installer into a temp folder and return its path. The file
+/// is streamed to disk, checked against the size the release lists and,
+/// when `checksums` is given, against its published SHA-256.
+pub async fn download_asset(
+    asset: &ReleaseAsset,
+    checksums: Option<&ReleaseAsset>,
+) -> Result<PathBuf, String> {
+    let file_name = std::path::Path::new(&asset.name)
+        .file_name()
+        .ok_or_else(|| format!("Invalid asset name: {}", asset.name))?;
+    let dir = std::env::temp_dir().join("open-mcp-manager-update");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(file_name);
+
+    let client = client()?;
+    let expected = match checksums {
+        Some(sums) => {
+            let text = client
+                .get(&sums.browser_download_url)
+                .send()
+                .await
+                .map_err(|e| e.to_string())?
+                .error_for_status()
+                .map_err(|e| e.to_string())?
+                .text()
+                .await
+                .map_err(|e| e.to_string())?;
+            let digest = expected_checksum(&text, &asset.name)
+                .ok_or_else(|| format!("{} has no checksum for {}", sums.name, asset.name))?;
+            Some(digest)
+        }
+        None => None,
+    };
+
+    let mut stream = client
+        .get(&asset.browser_download_url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?
+        .bytes_stream();
+    let mut file = std::fs::File::create(&path).map_err(|e| e.to_string())?;
+    let mut hasher = Sha256::new();
+    let mut written = 0u64;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| e.to_string())?;
+        file.write_all(&chunk).map_err(|e| e.to_string())?;
+        hasher.update(&chunk);
+        written += chunk.len() as u64;
+    }
+    file.flush().map_err(|e| e.to_string())?;
+    drop(file);
+
+    let verified = if asset.size > 0 && written != asset.size {
+        Err(format!(
+            "Download of {} is incomplete: got {} of {} bytes",
+            asset.name, written, asset.size
+        ))
+    } else {
+        let actual: String = hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        match expected {
+            Some(expected) if expected != actual => Err(format!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                asset.name, expected, actual
+            )),
+            _ => Ok(()),
+        }
+    };
+    if let Err(e) = verified {
+        let _ = std::fs::remove_file(&path);
+        return Err(e);
+    }
+
+    #[cfg(unix)]
+    if asset.name.to_lowercase().ends_with(".appimage") {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(path)
+}
+
+/// Start a downloaded installer. AppImages are run directly; everything
+/// else is handed to the OS default handler.
+pub fn launch_installer(path: &std::path::Path) -> Result<(), String> {
+    let is_appimage = path
+        .extension()
+        .map(|e| e.eq_ignore_ascii_case("appimage"))
+        .unwrap_or(false);
+    if is_appimage {
+        let mut child = std::process::Command::new(path)
+            .spawn()
+            .map_err(|e| e.to_string())?;
+        // Reap the new version when it exits, if the manager is still running
+        std::thread::spawn(move || child.wait());
+        Ok(())
+    } else {
+        open::that_detached(path).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(name: &str) -> ReleaseAsset {
+        ReleaseAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{}", name),
+            size: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("v1.2.3"), Some((1, 2, 3)));
+        assert_eq!(parse_version("0.10.0-beta.1"), Some((0, 10, 0)));
+        assert_eq!(parse_version("2.1"), Some((2, 1, 0)));
+        assert_eq!(parse_version("nightly"), None);
+    }
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("v0.10.0", "0.9.1"));
+        assert!(!is_newer("v0.9.1", "0.9.1"));
+        assert!(!is_newer("v0.9.0", "0.9.1"));
+        assert!(!is_newer("latest", "0.9.1"));
+    }
+
+    #[test]
+    fn test_checksum_asset() {
+        let installer = asset("open-mcp-manager_1.0.0_amd64.AppImage");
+        let assets = vec![
+            installer.clone(),
+            asset("SHA256SUMS"),
+            asset("open-mcp-manager_1.0.0_amd64.AppImage.sha256"),
+        ];
+        assert_eq!(
+            checksum_asset(&assets, &installer).unwrap().name,
+            "open-mcp-manager_1.0.0_amd64.AppImage.sha256"
+        );
+        assert_eq!(
+            checksum_asset(&assets[..2], &installer).unwrap().name,
+            "SHA256SUMS"
+        );
+        assert!(checksum_asset(&assets[..1], &installer).is_none());
+    }
+
+    #[test]
+    fn test_expected_checksum() {
+        let a = "a".repeat(64);
+        let b = "B".repeat(64);
+        assert_eq!(
+            expected_checksum(&format!("{}\n", a), "x.msi"),
+            Some(a.clone())
+        );
+        let sums = format!("{}  app.dmg\n{} *app.msi\n", a, b);
+        assert_eq!(expected_checksum(&sums, "app.dmg"), Some(a));
+        assert_eq!(expected_checksum(&sums, "app.msi"), Some(b.to_lowercase()));
+        assert_eq!(expected_checksum(&sums, "app.deb"), None);
+        assert_eq!(expected_checksum("not a digest", "app.deb"), None);
+    }
+
+    #[test]
+    fn test_pick_asset() {
+        let assets = vec![
+            asset("open-mcp-manager_1.0.0_amd64.deb"),
+            asset("open-mcp-manager_1.0.0_aarch64.AppImage"),
+            asset("open-mcp-manager_1.0.0_amd64.AppImage"),
+            asset("Open.MCP.Manager_1.0.0_x64_en-US.msi"),
+            asset("Open.MCP.Manager_1.0.0_aarch64.dmg"),
+        ];
+        assert_eq!(
+            pick_asset(&assets, "linux", "x86_64").unwrap().name,
+            "open-mcp-manager_1.0.0_amd64.AppImage"
+        );
+        assert_eq!(
+            pick_asset(&assets, "windows", "x86_64").unwrap().name,
+            "Open.MCP.Manager_1.0.0_x64_en-US.msi"
+        );
+        // Never an installer built for another CPU
+        assert!(pick_asset(&assets, "macos", "x86_64").is_none());
+        assert!(pick_asset(&assets, "freebsd", "x86_64").is_none());
+
+        let universal = vec![
+            asset("Open.MCP.Manager_1.0.0_aarch64.dmg"),
+            asset("Open.MCP.Manager_1.0.0_universal.dmg"),
+        ];
+        assert_eq!(
+            pick_asset(&universal, "macos", "x86_64").unwrap().name,
+            "Open.MCP.Manager_1.0.0_universal.dmg"
+        );
+        assert_eq!(
+            pick_asset(&universal, "macos", "aarch64").unwrap().name,
+            "Open.MCP.Manager_1.0.0_aarch64.dmg"
+        );
+    }
+}
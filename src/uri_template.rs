@@ -0,0 +1,174 @@
+//! Filling in the URI templates servers list under
+//! `resources/templates/list` (RFC 6570), e.g. `file:///logs/{date}` or
+//! `github://repos/{owner}/{repo}{?ref}`.
+//!
+//! Values are strings typed into a form, so explode (`*`) has no effect
+//! and an empty value counts as not given.
+
+use std::collections::HashMap;
+
+/// How an expression's operator joins and escapes its values.
+struct Operator {
+    first: &'static str,
+    separator: &'static str,
+    /// Whether values are written as `name=value`.
+    named: bool,
+    /// Whether reserved characters such as `/` pass through unescaped.
+    reserved: bool,
+}
+
+fn operator(expression: &str) -> (Operator, &str) {
+    let op = |first, separator, named, reserved| Operator {
+        first,
+        separator,
+        named,
+        reserved,
+    };
+    match expression.chars().next() {
+        Some('+') => (op("", ",", false, true), &expression[1..]),
+        Some('#') => (op("#", ",", false, true), &expression[1..]),
+        Some('.') => (op(".", ".", false, false), &expression[1..]),
+        Some('/') => (op("/", "/", false, false), &expression[1..]),
+        Some(';') => (op(";", ";", true, false), &expression[1..]),
+        Some('?') => (op("?", "&", true, false), &expression[1..]),
+        Some('&') => (op("&", "&", true, false), &expression[1..]),
+        _ => (op("", ",", false, false), expression),
+    }
+}
+
+/// A variable name with its modifiers stripped, and the prefix length
+/// if it has one (`{name:3}`).
+fn variable(spec: &str) -> (&str, Option<usize>) {
+    let spec = spec.trim().trim_end_matches('*');
+    match spec.split_once(':') {
+        Some((name, len)) => (name, len.parse().ok()),
+        None => (spec, None),
+    }
+}
+
+fn encode(value: &str, reserved: bool) -> String {
+    let mut out = String::new();
+    for c in value.chars() {
+        let keep = c.is_ascii_alphanumeric()
+            || "-._~".contains(c)
+            || (reserved && ":/?#[]@!$&'()*+,;=%".contains(c));
+        if keep {
+            out.push(c);
+        } else {
+            let mut buf = [0; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                out.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
+    out
+}
+
+/// The expressions in `template`, e.g. `["owner", "repo"]`, each once and
+/// in order of appearance.
+pub fn variables(template: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for expression in expressions(template) {
+        for spec in operator(expression).1.split(',') {
+            let name = variable(spec).0.to_string();
+            if !name.is_empty() && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+fn expressions(template: &str) -> impl Iterator<Item = &str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(expression, _)| expression))
+}
+
+/// `template` with its expressions replaced by `values`.
+pub fn expand(template: &str, values: &HashMap<String, String>) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let (op, specs) = operator(&rest[start + 1..start + len]);
+        let mut first = true;
+        for spec in specs.split(',') {
+            let (name, prefix) = variable(spec);
+            let Some(value) = values.get(name).filter(|v| !v.is_empty()) else {
+                continue;
+            };
+            let value: String = match prefix {
+                Some(n) => value.chars().take(n).collect(),
+                None => value.clone(),
+            };
+            out.push_str(if first { op.first } else { op.separator });
+            first = false;
+            if op.named {
+                out.push_str(name);
+                out.push('=');
+            }
+            out.push_str(&encode(&value, op.reserved));
+        }
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_variables_in_order() {
+        assert_eq!(
+            variables("github://repos/{owner}/{repo}{?ref,owner}"),
+            vec!["owner", "repo", "ref"]
+        );
+        assert_eq!(variables("file:///{+path}{#frag:3}"), vec!["path", "frag"]);
+        assert!(variables("file:///readme.md").is_empty());
+    }
+
+    #[test]
+    fn test_expand_operators() {
+        let v = values(&[
+            ("owner", "anthropic"),
+            ("repo", "my repo"),
+            ("path", "src/lib.rs"),
+            ("ref", "main"),
+            ("empty", ""),
+        ]);
+        assert_eq!(
+            expand("github://repos/{owner}/{repo}", &v),
+            "github://repos/anthropic/my%20repo"
+        );
+        assert_eq!(expand("file:///{path}", &v), "file:///src%2Flib.rs");
+        assert_eq!(expand("file:///{+path}", &v), "file:///src/lib.rs");
+        assert_eq!(expand("x{/owner,repo}", &v), "x/anthropic/my%20repo");
+        assert_eq!(expand("x{?ref,empty,missing}", &v), "x?ref=main");
+        assert_eq!(expand("x{?owner}{&ref}", &v), "x?owner=anthropic&ref=main");
+        assert_eq!(expand("x{#path}", &v), "x#src/lib.rs");
+        assert_eq!(expand("x{.ref}{;owner:4}", &v), "x.main;owner=anth");
+    }
+
+    #[test]
+    fn test_expand_leaves_missing_values_out() {
+        assert_eq!(
+            expand("logs://{date}/app{?level}", &values(&[])),
+            "logs:///app"
+        );
+        assert_eq!(expand("bad{template", &values(&[])), "bad{template");
+    }
+}
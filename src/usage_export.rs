@@ -0,0 +1,274 @@
+//! Usage history as CSV, for digging into MCP usage in a spreadsheet or
+//! notebook: the routed tool call log and server run times, filtered to a
+//! range of days. Exported from the settings page or headless with
+//! `open-mcp-manager export-usage`.
+//!
+//! Days are UTC, like the stored timestamps, and only the history the app
+//! keeps (the last four weeks) can be exported.
+
+use crate::db::Database;
+use crate::models::{AppResult, ServerRun, ToolCallRecord};
+use chrono::{Duration, NaiveDate};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Subcommand name used to export from the command line.
+pub const EXPORT_SUBCOMMAND: &str = "export-usage";
+
+/// Days exported when no `--from` is given.
+const DEFAULT_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UsageTable {
+    ToolCalls,
+    ServerRuns,
+}
+
+impl UsageTable {
+    pub const ALL: [UsageTable; 2] = [UsageTable::ToolCalls, UsageTable::ServerRuns];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            UsageTable::ToolCalls => "Tool calls",
+            UsageTable::ServerRuns => "Server runs",
+        }
+    }
+
+    /// Name on the command line and in file names.
+    pub fn name(&self) -> &'static str {
+        match self {
+            UsageTable::ToolCalls => "tool_calls",
+            UsageTable::ServerRuns => "server_runs",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+}
+
+/// Days from `from` to `to`, both included.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl DateRange {
+    /// The `days` days up to and including `today`.
+    pub fn last_days(today: NaiveDate, days: i64) -> Self {
+        Self {
+            from: today - Duration::days(days - 1),
+            to: today,
+        }
+    }
+
+    /// Start and (exclusive) end in the database's timestamp format.
+    pub fn bounds(&self) -> (String, String) {
+        let day = |d: NaiveDate| d.format("%Y-%m-%d 00:00:00").to_string();
+        (day(self.from), day(self.to + Duration::days(1)))
+    }
+
+    pub fn file_stem(&self, table: UsageTable) -> String {
+        format!("mcp-{}-{}-to-{}", table.name(), self.from, self.to)
+    }
+}
+
+/// Quote a CSV field if it holds a separator, quote or line break.
+fn field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn row(fields: &[&str]) -> String {
+    let fields: Vec<String> = fields.iter().map(|f| field(f)).collect();
+    format!("{}\n", fields.join(","))
+}
+
+/// Tool calls as CSV, with server names from `names` where known.
+pub fn tool_calls_csv(calls: &[ToolCallRecord], names: &HashMap<String, String>) -> String {
+    let mut out = row(&[
+        "created_at",
+        "server_id",
+        "server_name",
+        "tool",
+        "ok",
+        "args",
+    ]);
+    for call in calls {
+        out.push_str(&row(&[
+            &call.created_at,
+            &call.server_id,
+            names.get(&call.server_id).map_or("", String::as_str),
+            &call.tool,
+            if call.ok { "true" } else { "false" },
+            call.args.as_deref().unwrap_or(""),
+        ]));
+    }
+    out
+}
+
+/// Server runs as CSV. Runs still going have no `stopped_at`.
+pub fn server_runs_csv(runs: &[ServerRun], names: &HashMap<String, String>) -> String {
+    let mut out = row(&["server_id", "server_name", "started_at", "stopped_at"]);
+    for run in runs {
+        out.push_str(&row(&[
+            &run.server_id,
+            names.get(&run.server_id).map_or("", String::as_str),
+            &run.started_at,
+            run.stopped_at.as_deref().unwrap_or(""),
+        ]));
+    }
+    out
+}
+
+/// `table` within `range` as CSV.
+pub fn export(db: &Database, table: UsageTable, range: DateRange) -> AppResult<String> {
+    let names: HashMap<String, String> = db
+        .get_servers()?
+        .into_iter()
+        .map(|s| (s.id, s.name))
+        .collect();
+    let (from, to) = range.bounds();
+    Ok(match table {
+        UsageTable::ToolCalls => tool_calls_csv(&db.get_tool_calls_between(&from, &to)?, &names),
+        UsageTable::ServerRuns => server_runs_csv(&db.get_server_runs_between(&from, &to)?, &names),
+    })
+}
+
+/// What `export-usage` was asked for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportArgs {
+    pub table: UsageTable,
+    pub range: DateRange,
+    /// Where to write the CSV; stdout if None.
+    pub output: Option<PathBuf>,
+}
+
+fn parse_date(flag: &str, value: Option<&String>) -> Result<NaiveDate, String> {
+    let value = value.ok_or(format!("{} requires a date", flag))?;
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("{} expects YYYY-MM-DD, got {}", flag, value))
+}
+
+/// Parse the export arguments (everything after `export-usage`):
+/// `[--table tool_calls|server_runs] [--from DATE] [--to DATE] [--output FILE]`.
+/// Without dates the last week up to `today` is exported.
+pub fn parse_args(args: &[String], today: NaiveDate) -> Result<ExportArgs, String> {
+    let mut table = UsageTable::ToolCalls;
+    let mut from = None;
+    let mut to = None;
+    let mut output = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--table" => {
+                let name = iter.next().ok_or("--table requires a value")?;
+                table = UsageTable::from_name(name).ok_or(format!(
+                    "Unknown table: {} (tool_calls or server_runs)",
+                    name
+                ))?;
+            }
+            "--from" => from = Some(parse_date("--from", iter.next())?),
+            "--to" => to = Some(parse_date("--to", iter.next())?),
+            "--output" => {
+                output = Some(PathBuf::from(
+                    iter.next().ok_or("--output requires a file")?,
+                ));
+            }
+            other => return Err(format!("Unknown export-usage argument: {}", other)),
+        }
+    }
+    let to = to.unwrap_or(today);
+    let from = from.unwrap_or(DateRange::last_days(to, DEFAULT_DAYS).from);
+    if from > to {
+        return Err("--from must not be after --to".to_string());
+    }
+    Ok(ExportArgs {
+        table,
+        range: DateRange { from, to },
+        output,
+    })
+}
+
+/// Run `export-usage` against the active workspace's database.
+pub fn run(args: ExportArgs) -> Result<(), String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let csv = export(&db, args.table, args.range).map_err(|e| e.to_string())?;
+    match args.output {
+        Some(path) => std::fs::write(path, csv).map_err(|e| e.to_string()),
+        None => {
+            print!("{}", csv);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        let names = HashMap::from([("s1".to_string(), "Files, local".to_string())]);
+        let calls = vec![ToolCallRecord {
+            server_id: "s1".to_string(),
+            tool: "read".to_string(),
+            ok: true,
+            created_at: "2026-03-01 10:00:00".to_string(),
+            args: Some(r#"{"path":"a.txt"}"#.to_string()),
+        }];
+        assert_eq!(
+            tool_calls_csv(&calls, &names),
+            "created_at,server_id,server_name,tool,ok,args\n\
+             2026-03-01 10:00:00,s1,\"Files, local\",read,true,\"{\"\"path\"\":\"\"a.txt\"\"}\"\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let today = day("2026-03-10");
+        let args = parse_args(&[], today).unwrap();
+        assert_eq!(args.table, UsageTable::ToolCalls);
+        assert_eq!(args.range.from, day("2026-03-04"));
+        assert_eq!(
+            args.range.bounds(),
+            (
+                "2026-03-04 00:00:00".to_string(),
+                "2026-03-11 00:00:00".to_string()
+            )
+        );
+
+        let argv: Vec<String> = [
+            "--table",
+            "server_runs",
+            "--from",
+            "2026-03-01",
+            "--output",
+            "runs.csv",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let args = parse_args(&argv, today).unwrap();
+        assert_eq!(args.table, UsageTable::ServerRuns);
+        assert_eq!(
+            args.range,
+            DateRange {
+                from: day("2026-03-01"),
+                to: today
+            }
+        );
+        assert_eq!(args.output, Some(PathBuf::from("runs.csv")));
+
+        assert!(parse_args(&["--from".to_string(), "March".to_string()], today).is_err());
+        assert!(parse_args(&["--table".to_string(), "metrics".to_string()], today).is_err());
+    }
+}
@@ -0,0 +1,420 @@
+//! `open-mcp-manager verify`: start servers headlessly, run the MCP
+//! handshake and `tools/list` against each, and report which failed. Meant
+//! for CI over a shared setup, so it exits non-zero if any server fails.
+//!
+//! Servers come from the active workspace's database, an editor config
+//! with `mcpServers` (`--config`), or a `.mcpb` bundle (`--bundle`).
+
+use crate::db::Database;
+use crate::models::{CreateServerArgs, LaunchOptions, McpServer};
+use crate::process::{LogEntry, ProcessLog};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Subcommand name used to verify servers from the command line.
+pub const VERIFY_SUBCOMMAND: &str = "verify";
+
+/// How long a server may take to start, finish the handshake and list its
+/// tools when no `--timeout` is given.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Server output lines kept to explain a failure.
+const LOG_TAIL: usize = 5;
+
+/// Where the servers to verify are defined.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Database,
+    /// An editor config file with an `mcpServers` object.
+    Config(PathBuf),
+    Bundle(PathBuf),
+}
+
+/// What `verify` was asked for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyArgs {
+    /// Servers to check by name; all of them if empty.
+    pub names: Vec<String>,
+    pub source: Source,
+    pub timeout: Duration,
+}
+
+/// Parse the verify arguments (everything after `verify`):
+/// `[NAME...] [--config FILE | --bundle FILE] [--timeout SECONDS]`.
+pub fn parse_args(args: &[String]) -> Result<VerifyArgs, String> {
+    let mut names = Vec::new();
+    let mut source = Source::Database;
+    let mut timeout = DEFAULT_TIMEOUT;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--config" | "--bundle" => {
+                if source != Source::Database {
+                    return Err("Use only one of --config and --bundle".to_string());
+                }
+                let path = PathBuf::from(iter.next().ok_or(format!("{} requires a file", arg))?);
+                source = if arg == "--config" {
+                    Source::Config(path)
+                } else {
+                    Source::Bundle(path)
+                };
+            }
+            "--timeout" => {
+                let value = iter.next().ok_or("--timeout requires a value")?;
+                let secs: u64 = value
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or(format!("--timeout expects seconds, got {}", value))?;
+                timeout = Duration::from_secs(secs);
+            }
+            flag if flag.starts_with("--") => {
+                return Err(format!("Unknown verify argument: {}", flag))
+            }
+            name => names.push(name.to_string()),
+        }
+    }
+    Ok(VerifyArgs {
+        names,
+        source,
+        timeout,
+    })
+}
+
+fn server_from_args(args: CreateServerArgs) -> McpServer {
+    McpServer {
+        id: args.name.clone(),
+        name: args.name,
+        server_type: args.server_type,
+        command: args.command,
+        args: args.args,
+        url: args.url,
+        env: args.env,
+        description: args.description,
+        is_active: true,
+        ..Default::default()
+    }
+}
+
+/// Servers in an editor config, in file order.
+pub fn servers_from_config(config: &serde_json::Value) -> Result<Vec<McpServer>, String> {
+    let entries = config
+        .get("mcpServers")
+        .and_then(|m| m.as_object())
+        .ok_or("Config has no mcpServers object")?;
+    Ok(entries
+        .iter()
+        .map(|(name, entry)| server_from_args(crate::editor_config::entry_to_args(name, entry)))
+        .collect())
+}
+
+/// The non-archived servers of the active workspace, with their launch
+/// options.
+pub fn servers_from_database() -> Result<Vec<(McpServer, LaunchOptions)>, String> {
+    let db = Database::new().map_err(|e| e.to_string())?;
+    let mut options: HashMap<String, LaunchOptions> =
+        crate::state::AppState::load_json_setting(&db, crate::state::LAUNCH_OPTIONS_KEY);
+    Ok(db
+        .get_servers()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|s| !s.is_archived)
+        .map(|s| {
+            let opts = options.remove(&s.id).unwrap_or_default();
+            (s, opts)
+        })
+        .collect())
+}
+
+/// Unpack the bundle at `path` into `dir` and describe its server, using
+/// the defaults for its user config.
+fn server_from_bundle(path: &Path, dir: &Path) -> Result<McpServer, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let bundle = crate::mcpb::read_bundle(&bytes)?;
+    let values = Default::default();
+    let missing = crate::mcpb::validate_user_config(&bundle.manifest, &values);
+    if !missing.is_empty() {
+        return Err(format!(
+            "{} needs settings that have no default: {}",
+            bundle.manifest.name,
+            missing.join(", ")
+        ));
+    }
+    crate::mcpb::extract(&bundle, dir)?;
+    Ok(server_from_args(crate::mcpb::server_args(
+        &bundle.manifest,
+        dir,
+        &values,
+    )))
+}
+
+/// Only the servers named in `names`, by name or alias, in that order;
+/// all of them if `names` is empty.
+pub fn select<T>(
+    servers: Vec<(McpServer, T)>,
+    names: &[String],
+) -> Result<Vec<(McpServer, T)>, String> {
+    if names.is_empty() {
+        return Ok(servers);
+    }
+    let mut servers: Vec<Option<(McpServer, T)>> = servers.into_iter().map(Some).collect();
+    names
+        .iter()
+        .map(|name| {
+            servers
+                .iter_mut()
+                .find(|entry| entry.as_ref().is_some_and(|(s, _)| s.is_called(name)))
+                .and_then(Option::take)
+                .ok_or(format!("No server named {}", name))
+        })
+        .collect()
+}
+
+/// What a server reported when it passed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verified {
+    pub protocol_version: String,
+    pub tools: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub name: String,
+    pub outcome: Result<Verified, String>,
+}
+
+/// Start `server`, run the handshake and list its tools, then stop it.
+/// Failures carry the last lines the server printed.
+pub async fn verify_server(
+    server: &McpServer,
+    options: &LaunchOptions,
+    timeout: Duration,
+) -> Result<Verified, String> {
+    let (log_tx, mut log_rx) = mpsc::channel::<LogEntry>(100);
+    let tail = Arc::new(Mutex::new(VecDeque::new()));
+    let collector = {
+        let tail = tail.clone();
+        tokio::spawn(async move {
+            while let Some(entry) = log_rx.recv().await {
+                let line = match entry.log {
+                    ProcessLog::Stdout(line) | ProcessLog::Stderr(line) => line,
+                    ProcessLog::Exit(how) => format!("Process {}", how),
+                    ProcessLog::Message { level, text, .. } => {
+                        format!("{}: {}", level.as_str(), text)
+                    }
+                };
+                let mut tail = tail.lock().unwrap();
+                tail.push_back(line);
+                if tail.len() > LOG_TAIL {
+                    tail.pop_front();
+                }
+            }
+        })
+    };
+
+    if let Some(hook) = &options.pre_start {
+        let env = server.env.clone().unwrap_or_default();
+        let run = crate::hooks::run(hook, &env, crate::hooks::HOOK_TIMEOUT).await;
+        if let Some(e) = run.error {
+            return Err(format!("Pre-start hook {}", e));
+        }
+    }
+
+    let checked = tokio::time::timeout(timeout, async {
+        let handler = crate::process::start_handler(server, options, log_tx).await?;
+        let result = async {
+            handler.initialize().await?;
+            let tools = handler.list_tools().await?;
+            let protocol_version = handler
+                .server_info()
+                .map(|info| info.protocolVersion.clone())
+                .unwrap_or_default();
+            Ok(Verified {
+                protocol_version,
+                tools: tools.len(),
+            })
+        }
+        .await;
+        let _ = handler.shutdown(Duration::from_secs(2)).await;
+        result
+    })
+    .await
+    .unwrap_or_else(|_| Err(format!("Timed out after {}s", timeout.as_secs())));
+
+    if let Some(hook) = &options.post_stop {
+        let env = server.env.clone().unwrap_or_default();
+        crate::hooks::run(hook, &env, crate::hooks::HOOK_TIMEOUT).await;
+    }
+
+    // The log channel closes once the server is gone
+    let _ = tokio::time::timeout(Duration::from_secs(1), collector).await;
+    checked.map_err(|e| {
+        let tail = tail.lock().unwrap();
+        if tail.is_empty() {
+            e
+        } else {
+            let lines: Vec<&str> = tail.iter().map(String::as_str).collect();
+            format!("{}\n    {}", e, lines.join("\n    "))
+        }
+    })
+}
+
+/// One line per check, then a summary.
+pub fn report(checks: &[Check]) -> String {
+    let width = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for check in checks {
+        let (status, detail) = match &check.outcome {
+            Ok(v) => (
+                "PASS",
+                format!("{} tools, protocol {}", v.tools, v.protocol_version),
+            ),
+            Err(e) => ("FAIL", e.clone()),
+        };
+        out.push_str(&format!(
+            "{}  {:width$}  {}\n",
+            status,
+            check.name,
+            detail,
+            width = width
+        ));
+    }
+    let passed = checks.iter().filter(|c| c.outcome.is_ok()).count();
+    out.push_str(&format!("{} of {} servers passed\n", passed, checks.len()));
+    out
+}
+
+/// Run `verify`, printing the report. `Ok(false)` if any server failed.
+pub async fn run(args: VerifyArgs) -> Result<bool, String> {
+    let bundle_dir =
+        std::env::temp_dir().join(format!("open-mcp-manager-verify-{}", uuid::Uuid::new_v4()));
+    let servers = match &args.source {
+        Source::Database => servers_from_database()?,
+        Source::Config(path) => {
+            let config = crate::editor_config::read_config(path).map_err(|e| e.to_string())?;
+            servers_from_config(&config)?
+                .into_iter()
+                .map(|s| (s, LaunchOptions::default()))
+                .collect()
+        }
+        Source::Bundle(path) => vec![(
+            server_from_bundle(path, &bundle_dir)?,
+            LaunchOptions::default(),
+        )],
+    };
+    let servers = select(servers, &args.names)?;
+    if servers.is_empty() {
+        return Err("No servers to verify".to_string());
+    }
+
+    let mut checks = Vec::new();
+    for (server, options) in &servers {
+        checks.push(Check {
+            name: server.name.clone(),
+            outcome: verify_server(server, options, args.timeout).await,
+        });
+    }
+    let _ = std::fs::remove_dir_all(&bundle_dir);
+
+    print!("{}", report(&checks));
+    Ok(checks.iter().all(|c| c.outcome.is_ok()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = parse_args(&[]).unwrap();
+        assert_eq!(args.source, Source::Database);
+        assert!(args.names.is_empty());
+        assert_eq!(args.timeout, DEFAULT_TIMEOUT);
+
+        let args = parse_args(&strings(&[
+            "files",
+            "--config",
+            "mcp.json",
+            "--timeout",
+            "5",
+            "github",
+        ]))
+        .unwrap();
+        assert_eq!(args.names, vec!["files", "github"]);
+        assert_eq!(args.source, Source::Config(PathBuf::from("mcp.json")));
+        assert_eq!(args.timeout, Duration::from_secs(5));
+
+        assert!(parse_args(&strings(&["--timeout", "0"])).is_err());
+        assert!(parse_args(&strings(&["--config", "a.json", "--bundle", "b.mcpb"])).is_err());
+        assert!(parse_args(&strings(&["--verbose"])).is_err());
+    }
+
+    #[test]
+    fn test_select_from_config() {
+        let config = json!({"mcpServers": {
+            "files": {"command": "npx", "args": ["-y", "files"]},
+            "remote": {"url": "wss://example.com/mcp"}
+        }});
+        let mut servers: Vec<(McpServer, ())> = servers_from_config(&config)
+            .unwrap()
+            .into_iter()
+            .map(|s| (s, ()))
+            .collect();
+        assert_eq!(servers.len(), 2);
+        servers[0].0.alias = Some("fs".to_string());
+
+        let picked = select(servers.clone(), &strings(&["remote"])).unwrap();
+        assert_eq!(picked.len(), 1);
+        assert_eq!(picked[0].0.server_type, "ws");
+        let picked = select(servers.clone(), &strings(&["fs", "remote"])).unwrap();
+        assert_eq!(picked[0].0.name, "files");
+        assert_eq!(
+            select(servers, &strings(&["missing"])).unwrap_err(),
+            "No server named missing"
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let checks = vec![
+            Check {
+                name: "files".to_string(),
+                outcome: Ok(Verified {
+                    protocol_version: "2025-06-18".to_string(),
+                    tools: 3,
+                }),
+            },
+            Check {
+                name: "gh".to_string(),
+                outcome: Err("Timed out after 30s".to_string()),
+            },
+        ];
+        assert_eq!(
+            report(&checks),
+            "PASS  files  3 tools, protocol 2025-06-18\n\
+             FAIL  gh     Timed out after 30s\n\
+             1 of 2 servers passed\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_verify_server_reports_output_on_failure() {
+        let config = json!({"mcpServers": {
+            "broken": {"command": "sh", "args": ["-c", "echo 'missing API key' >&2; exit 1"]}
+        }});
+        let server = servers_from_config(&config).unwrap().remove(0);
+        let err = verify_server(&server, &LaunchOptions::default(), Duration::from_secs(2))
+            .await
+            .unwrap_err();
+        assert!(err.contains("missing API key"), "{}", err);
+    }
+}
@@ -0,0 +1,439 @@
+//! The weekly summary report: uptime, most used tools and errors per
+//! server, plus registry news and update status, saved as Markdown and
+//! HTML in the data directory.
+
+use crate::models::{AppResult, AuditEntry, McpServer, ServerRun, ToolCallRecord};
+use crate::registry_digest::RegistryDigest;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// How far back a report looks.
+pub const REPORT_PERIOD_DAYS: i64 = 7;
+
+/// Tools listed under "Most used tools".
+const TOP_TOOLS: usize = 10;
+
+/// Format of SQLite's `CURRENT_TIMESTAMP` (UTC).
+const DB_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+pub fn reports_dir() -> AppResult<PathBuf> {
+    Ok(crate::platform::app_data_dir()?.join("reports"))
+}
+
+/// `time` in the format the database stores timestamps in.
+pub fn db_time(time: DateTime<Utc>) -> String {
+    time.format(DB_TIME_FORMAT).to_string()
+}
+
+fn parse_db_time(text: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(text, DB_TIME_FORMAT)
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerUptime {
+    pub name: String,
+    pub running: Duration,
+    pub percent: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolUsage {
+    pub server: String,
+    pub tool: String,
+    pub calls: usize,
+    pub failed: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerErrors {
+    pub name: String,
+    pub failed: usize,
+    pub calls: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeeklyReport {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    /// Every configured server, longest running first.
+    pub uptime: Vec<ServerUptime>,
+    pub top_tools: Vec<ToolUsage>,
+    /// Servers with failed calls, most failures first.
+    pub errors: Vec<ServerErrors>,
+    /// Actions the manager took on its own during the period.
+    pub audit: Vec<AuditEntry>,
+    pub registry: Option<RegistryDigest>,
+    /// Newer release of the manager, if one is available.
+    pub update: Option<String>,
+}
+
+/// How long `runs` overlap the period from `since` to `until`. Runs still
+/// going count up to `until`.
+pub fn uptime(runs: &[&ServerRun], since: DateTime<Utc>, until: DateTime<Utc>) -> Duration {
+    runs.iter()
+        .filter_map(|run| {
+            let start = parse_db_time(&run.started_at)?.max(since);
+            let end = match &run.stopped_at {
+                Some(stopped) => parse_db_time(stopped)?,
+                None => until,
+            }
+            .min(until);
+            Some((end - start).max(Duration::zero()))
+        })
+        .fold(Duration::zero(), |total, d| total + d)
+}
+
+impl WeeklyReport {
+    /// Summarize the week up to `until` from the server runs and routed
+    /// tool calls recorded since its start. Audit entries, registry news
+    /// and update status are left for the caller to fill in.
+    pub fn new(
+        until: DateTime<Utc>,
+        servers: &[McpServer],
+        runs: &[ServerRun],
+        calls: &[ToolCallRecord],
+    ) -> Self {
+        let since = until - Duration::days(REPORT_PERIOD_DAYS);
+        let names: HashMap<&str, &str> = servers
+            .iter()
+            .map(|s| (s.id.as_str(), s.name.as_str()))
+            .collect();
+        let name = |id: &str| names.get(id).copied().unwrap_or(id).to_string();
+
+        let period = (until - since).num_seconds().max(1);
+        let mut uptime: Vec<ServerUptime> = servers
+            .iter()
+            .map(|server| {
+                let server_runs: Vec<&ServerRun> =
+                    runs.iter().filter(|r| r.server_id == server.id).collect();
+                let running = self::uptime(&server_runs, since, until);
+                ServerUptime {
+                    name: server.name.clone(),
+                    running,
+                    percent: (running.num_seconds() * 100 / period) as u32,
+                }
+            })
+            .collect();
+        uptime.sort_by(|a, b| b.running.cmp(&a.running).then(a.name.cmp(&b.name)));
+
+        let mut by_tool: HashMap<(&str, &str), (usize, usize)> = HashMap::new();
+        let mut by_server: HashMap<&str, (usize, usize)> = HashMap::new();
+        for call in calls {
+            let failed = usize::from(!call.ok);
+            let tool = by_tool.entry((&call.server_id, &call.tool)).or_default();
+            tool.0 += 1;
+            tool.1 += failed;
+            let server = by_server.entry(&call.server_id).or_default();
+            server.0 += 1;
+            server.1 += failed;
+        }
+        let mut top_tools: Vec<ToolUsage> = by_tool
+            .into_iter()
+            .map(|((server_id, tool), (calls, failed))| ToolUsage {
+                server: name(server_id),
+                tool: tool.to_string(),
+                calls,
+                failed,
+            })
+            .collect();
+        top_tools.sort_by(|a, b| {
+            b.calls
+                .cmp(&a.calls)
+                .then(a.server.cmp(&b.server))
+                .then(a.tool.cmp(&b.tool))
+        });
+        top_tools.truncate(TOP_TOOLS);
+        let mut errors: Vec<ServerErrors> = by_server
+            .into_iter()
+            .filter(|(_, (_, failed))| *failed > 0)
+            .map(|(server_id, (calls, failed))| ServerErrors {
+                name: name(server_id),
+                failed,
+                calls,
+            })
+            .collect();
+        errors.sort_by(|a, b| b.failed.cmp(&a.failed).then(a.name.cmp(&b.name)));
+
+        Self {
+            since,
+            until,
+            uptime,
+            top_tools,
+            errors,
+            audit: Vec::new(),
+            registry: None,
+            update: None,
+        }
+    }
+
+    /// File name (without extension) the report is saved under.
+    pub fn file_stem(&self) -> String {
+        format!("weekly-report-{}", self.until.format("%Y-%m-%d"))
+    }
+
+    fn period(&self) -> String {
+        format!(
+            "{} to {}",
+            self.since.format("%Y-%m-%d"),
+            self.until.format("%Y-%m-%d")
+        )
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Weekly report\n\n{}\n", self.period());
+
+        out.push_str("\n## Uptime\n\n");
+        if self.uptime.is_empty() {
+            out.push_str("No servers configured.\n");
+        } else {
+            out.push_str("| Server | Running | Uptime |\n|---|---|---|\n");
+            for s in &self.uptime {
+                out.push_str(&format!(
+                    "| {} | {} | {}% |\n",
+                    s.name,
+                    hours(s.running),
+                    s.percent
+                ));
+            }
+        }
+
+        out.push_str("\n## Most used tools\n\n");
+        if self.top_tools.is_empty() {
+            out.push_str("No tool calls through the hub this week.\n");
+        } else {
+            out.push_str("| Server | Tool | Calls | Failed |\n|---|---|---|---|\n");
+            for t in &self.top_tools {
+                out.push_str(&format!(
+                    "| {} | `{}` | {} | {} |\n",
+                    t.server, t.tool, t.calls, t.failed
+                ));
+            }
+        }
+
+        out.push_str("\n## Errors\n\n");
+        if self.errors.is_empty() {
+            out.push_str("No failed tool calls.\n");
+        }
+        for e in &self.errors {
+            out.push_str(&format!(
+                "- {}: {} of {} calls failed\n",
+                e.name, e.failed, e.calls
+            ));
+        }
+        if !self.audit.is_empty() {
+            out.push_str("\nAutomatic actions:\n\n");
+            for entry in &self.audit {
+                out.push_str(&format!(
+                    "- {} {}: {}\n",
+                    entry.created_at, entry.event, entry.detail
+                ));
+            }
+        }
+
+        out.push_str("\n## Registry\n\n");
+        match &self.registry {
+            Some(digest) if !digest.is_empty() => {
+                out.push_str(&format!("{}\n", digest.summary()));
+                for e in &digest.added {
+                    out.push_str(&format!("- New: {}\n", e.name));
+                }
+                for b in &digest.bumped {
+                    out.push_str(&format!("- Updated: {} {} → {}\n", b.name, b.from, b.to));
+                }
+                for e in &digest.removed {
+                    out.push_str(&format!("- Removed: {}\n", e.name));
+                }
+            }
+            Some(_) => out.push_str("No registry changes.\n"),
+            None => out.push_str("Not enough registry snapshots to compare yet.\n"),
+        }
+
+        out.push_str("\n## Updates\n\n");
+        match &self.update {
+            Some(version) => out.push_str(&format!(
+                "Open MCP Manager {} is available (running {}).\n",
+                version,
+                crate::updater::CURRENT_VERSION
+            )),
+            None => out.push_str(&format!(
+                "Open MCP Manager {} is up to date.\n",
+                crate::updater::CURRENT_VERSION
+            )),
+        }
+        out
+    }
+
+    /// The same report as a standalone HTML page.
+    pub fn to_html(&self) -> String {
+        let mut body = String::new();
+        // "table" or "ul" while inside one
+        let mut open: Option<&str> = None;
+        for line in self.to_markdown().lines() {
+            let block = if line.starts_with('|') {
+                Some("table")
+            } else if line.starts_with("- ") {
+                Some("ul")
+            } else {
+                None
+            };
+            if block != open {
+                if let Some(tag) = open {
+                    body.push_str(&format!("</{}>\n", tag));
+                }
+                if let Some(tag) = block {
+                    body.push_str(&format!("<{}>\n", tag));
+                }
+            }
+            let header_row = block == Some("table") && open != block;
+            open = block;
+
+            if let Some(title) = line.strip_prefix("# ") {
+                body.push_str(&format!("<h1>{}</h1>\n", inline_html(title)));
+            } else if let Some(title) = line.strip_prefix("## ") {
+                body.push_str(&format!("<h2>{}</h2>\n", inline_html(title)));
+            } else if line.starts_with("|---") {
+                continue;
+            } else if let Some(row) = line.strip_prefix('|') {
+                let cell = if header_row { "th" } else { "td" };
+                let cells: String = row
+                    .trim_end_matches('|')
+                    .split('|')
+                    .map(|text| format!("<{0}>{1}</{0}>", cell, inline_html(text.trim())))
+                    .collect();
+                body.push_str(&format!("<tr>{}</tr>\n", cells));
+            } else if let Some(item) = line.strip_prefix("- ") {
+                body.push_str(&format!("<li>{}</li>\n", inline_html(item)));
+            } else if !line.is_empty() {
+                body.push_str(&format!("<p>{}</p>\n", inline_html(line)));
+            }
+        }
+        if let Some(tag) = open {
+            body.push_str(&format!("</{}>\n", tag));
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Weekly report {}</title>\n\
+             <style>body{{font-family:system-ui,sans-serif;max-width:50rem;margin:2rem auto;padding:0 1rem;color:#222}}\
+             table{{border-collapse:collapse}}td,th{{border:1px solid #ccc;padding:.25rem .5rem;text-align:left}}</style>\n\
+             </head><body>\n{}</body></html>\n",
+            escape(&self.period()),
+            body
+        )
+    }
+}
+
+fn hours(d: Duration) -> String {
+    format!("{}h {:02}m", d.num_hours(), d.num_minutes() % 60)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Escaped text with `code` spans.
+fn inline_html(text: &str) -> String {
+    escape(text)
+        .split('`')
+        .enumerate()
+        .map(|(i, part)| match i % 2 {
+            1 => format!("<code>{}</code>", part),
+            _ => part.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(id: &str, started_at: &str, stopped_at: Option<&str>) -> ServerRun {
+        ServerRun {
+            server_id: id.to_string(),
+            started_at: started_at.to_string(),
+            stopped_at: stopped_at.map(str::to_string),
+        }
+    }
+
+    fn call(id: &str, tool: &str, ok: bool) -> ToolCallRecord {
+        ToolCallRecord {
+            server_id: id.to_string(),
+            tool: tool.to_string(),
+            ok,
+            created_at: "2026-01-10 12:00:00".to_string(),
+            args: None,
+        }
+    }
+
+    fn until() -> DateTime<Utc> {
+        parse_db_time("2026-01-12 00:00:00").unwrap()
+    }
+
+    #[test]
+    fn test_uptime_is_clipped_to_the_period() {
+        let runs = [
+            // Started before the period
+            run("a", "2026-01-04 00:00:00", Some("2026-01-05 06:00:00")),
+            run("a", "2026-01-10 00:00:00", Some("2026-01-10 01:30:00")),
+            // Still running
+            run("a", "2026-01-11 22:00:00", None),
+        ];
+        let refs: Vec<&ServerRun> = runs.iter().collect();
+        let since = until() - Duration::days(REPORT_PERIOD_DAYS);
+        assert_eq!(
+            uptime(&refs, since, until()),
+            Duration::minutes(6 * 60 + 90 + 120)
+        );
+    }
+
+    #[test]
+    fn test_report_ranks_tools_and_errors() {
+        let servers = [
+            McpServer::test_stdio("a", "npx").named("Search"),
+            McpServer::test_stdio("b", "npx").named("Files"),
+        ];
+        let runs = [run("a", "2026-01-05 00:00:00", None)];
+        let calls = [
+            call("a", "query", true),
+            call("a", "query", false),
+            call("b", "read", true),
+            call("a", "query", true),
+            call("gone", "old", false),
+        ];
+        let report = WeeklyReport::new(until(), &servers, &runs, &calls);
+
+        assert_eq!(report.uptime[0].name, "Search");
+        assert_eq!(report.uptime[0].percent, 100);
+        assert_eq!(report.uptime[1].percent, 0);
+        assert_eq!(
+            report.top_tools[0],
+            ToolUsage {
+                server: "Search".to_string(),
+                tool: "query".to_string(),
+                calls: 3,
+                failed: 1,
+            }
+        );
+        assert_eq!(report.errors.len(), 2);
+        // Calls to removed servers are shown by id
+        assert!(report.errors.iter().any(|e| e.name == "gone"));
+        assert_eq!(report.file_stem(), "weekly-report-2026-01-12");
+    }
+
+    #[test]
+    fn test_html_is_escaped() {
+        let servers = [McpServer::test_stdio("a", "npx").named("<script>")];
+        let report = WeeklyReport::new(until(), &servers, &[], &[call("a", "x&y", false)]);
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("| <script> | `x&y` | 1 | 1 |"));
+        let html = report.to_html();
+        assert!(html.contains("<tr><td>&lt;script&gt;</td><td><code>x&amp;y</code></td>"));
+        assert!(html.contains("<tr><th>Server</th>"));
+        assert!(!html.contains("<script>"));
+    }
+}
@@ -0,0 +1,138 @@
+//! Named workspaces, each backed by its own SQLite file so server configs
+//! and their secrets stay isolated from one another.
+
+use crate::models::AppResult;
+use std::path::{Path, PathBuf};
+
+/// Workspace that uses the original `servers.db`.
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+/// File in the data dir naming the workspace to open on launch.
+const ACTIVE_FILE: &str = "active_workspace";
+const WORKSPACES_DIR: &str = "workspaces";
+
+/// Normalise a user-entered name to a file-safe slug, e.g. "Client A" -> "client-a".
+pub fn slugify(name: &str) -> Result<String, String> {
+    let mut slug = String::new();
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if matches!(c, ' ' | '-' | '_') && !slug.ends_with('-') {
+            slug.push('-');
+        } else if !matches!(c, ' ' | '-' | '_') {
+            return Err(format!(
+                "Workspace names may only contain letters, numbers, spaces, '-' and '_' (got '{}')",
+                c
+            ));
+        }
+    }
+    let slug = slug.trim_matches('-').to_string();
+    if slug.is_empty() {
+        return Err("Workspace name is empty".to_string());
+    }
+    Ok(slug)
+}
+
+/// Database file for a workspace under `data_dir`.
+fn db_path_in(data_dir: &Path, workspace: &str) -> PathBuf {
+    if workspace == DEFAULT_WORKSPACE {
+        data_dir.join("servers.db")
+    } else {
+        data_dir
+            .join(WORKSPACES_DIR)
+            .join(format!("{}.db", workspace))
+    }
+}
+
+/// All workspaces under `data_dir`, default first, the rest sorted.
+fn list_in(data_dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(data_dir.join(WORKSPACES_DIR))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| {
+                    let path = e.path();
+                    if path.extension()? != "db" {
+                        return None;
+                    }
+                    Some(path.file_stem()?.to_string_lossy().to_string())
+                })
+                .filter(|name| name != DEFAULT_WORKSPACE)
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names.insert(0, DEFAULT_WORKSPACE.to_string());
+    names
+}
+
+fn active_in(data_dir: &Path) -> String {
+    std::fs::read_to_string(data_dir.join(ACTIVE_FILE))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty() && slugify(s).as_deref() == Ok(s.as_str()))
+        .unwrap_or_else(|| DEFAULT_WORKSPACE.to_string())
+}
+
+fn set_active_in(data_dir: &Path, workspace: &str) -> AppResult<()> {
+    std::fs::create_dir_all(data_dir)?;
+    std::fs::write(data_dir.join(ACTIVE_FILE), workspace)?;
+    Ok(())
+}
+
+pub fn db_path(workspace: &str) -> AppResult<PathBuf> {
+    let path = db_path_in(&crate::platform::app_data_dir()?, workspace);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(path)
+}
+
+pub fn list() -> Vec<String> {
+    crate::platform::app_data_dir()
+        .map(|dir| list_in(&dir))
+        .unwrap_or_else(|_| vec![DEFAULT_WORKSPACE.to_string()])
+}
+
+/// Workspace opened on launch.
+pub fn active() -> String {
+    crate::platform::app_data_dir()
+        .map(|dir| active_in(&dir))
+        .unwrap_or_else(|_| DEFAULT_WORKSPACE.to_string())
+}
+
+pub fn set_active(workspace: &str) -> AppResult<()> {
+    set_active_in(&crate::platform::app_data_dir()?, workspace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Client A").unwrap(), "client-a");
+        assert_eq!(slugify("  acme__corp - 2 ").unwrap(), "acme-corp-2");
+        assert!(slugify("   ").is_err());
+        assert!(slugify("../etc").is_err());
+    }
+
+    #[test]
+    fn test_workspace_files() {
+        let dir = std::env::temp_dir().join(format!("omm-ws-{}", uuid::Uuid::new_v4()));
+        assert_eq!(list_in(&dir), vec!["default"]);
+        assert_eq!(active_in(&dir), "default");
+        assert_eq!(db_path_in(&dir, "default"), dir.join("servers.db"));
+
+        let path = db_path_in(&dir, "client-b");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "").unwrap();
+        std::fs::write(db_path_in(&dir, "client-a"), "").unwrap();
+        assert_eq!(list_in(&dir), vec!["default", "client-a", "client-b"]);
+
+        set_active_in(&dir, "client-b").unwrap();
+        assert_eq!(active_in(&dir), "client-b");
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
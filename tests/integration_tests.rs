@@ -12,11 +12,12 @@ use tokio::time::timeout;
 
 // We need to import from the main crate
 // The McpProcess and ProcessLog are re-exported for testing
-use open_mcp_manager::process::{McpProcess, ProcessLog};
+use open_mcp_manager::models::LaunchOptions;
+use open_mcp_manager::process::{LogEntry, McpProcess, ProcessLog};
 
 /// Helper to create a log channel for tests
-fn create_log_channel() -> (mpsc::Sender<ProcessLog>, mpsc::Receiver<ProcessLog>) {
-    mpsc::channel::<ProcessLog>(100)
+fn create_log_channel() -> (mpsc::Sender<LogEntry>, mpsc::Receiver<LogEntry>) {
+    mpsc::channel::<LogEntry>(100)
 }
 
 fn get_npx_command() -> String {
@@ -42,6 +43,7 @@ async fn test_memory_server_lifecycle() {
             "@modelcontextprotocol/server-memory".to_string(),
         ],
         None,
+        &LaunchOptions::default(),
         log_tx,
     )
     .await;
@@ -53,8 +55,13 @@ async fn test_memory_server_lifecycle() {
     );
     let process = process.unwrap();
 
-    // Give the server time to initialize
-    tokio::time::sleep(Duration::from_secs(2)).await;
+    // Handshake before any other request
+    let init_result = timeout(Duration::from_secs(15), process.initialize()).await;
+    assert!(
+        matches!(init_result, Ok(Ok(_))),
+        "Initialize failed: {:?}",
+        init_result
+    );
 
     // Test: List tools should return available tools
     let tools_result = timeout(Duration::from_secs(15), process.list_tools()).await;
@@ -91,6 +98,7 @@ async fn test_everything_server_tools_list() {
             "@modelcontextprotocol/server-everything".to_string(),
         ],
         None,
+        &LaunchOptions::default(),
         log_tx,
     )
     .await;
@@ -101,7 +109,7 @@ async fn test_everything_server_tools_list() {
     }
 
     let process = process.unwrap();
-    tokio::time::sleep(Duration::from_secs(3)).await;
+    let _ = timeout(Duration::from_secs(15), process.initialize()).await;
 
     // List tools
     let tools_result = timeout(Duration::from_secs(10), process.list_tools()).await;
@@ -135,6 +143,7 @@ async fn test_invalid_command_fails() {
         "nonexistent-command-that-does-not-exist-12345".to_string(),
         vec![],
         None,
+        &LaunchOptions::default(),
         log_tx,
     )
     .await;
@@ -159,6 +168,7 @@ async fn test_env_vars_passed_to_process() {
         "node".to_string(),
         vec!["-e".to_string(), script.to_string()],
         Some(env),
+        &LaunchOptions::default(),
         log_tx,
     )
     .await;
@@ -176,7 +186,7 @@ async fn test_env_vars_passed_to_process() {
     // Check if we received the env var in stdout
     let mut found = false;
     while let Ok(log) = log_rx.try_recv() {
-        if let ProcessLog::Stdout(msg) = log {
+        if let ProcessLog::Stdout(msg) = log.log {
             if msg.contains("ENV_TEST=hello_from_test") {
                 found = true;
                 break;
@@ -204,6 +214,7 @@ async fn test_multiple_concurrent_servers() {
             "@modelcontextprotocol/server-memory".to_string(),
         ],
         None,
+        &LaunchOptions::default(),
         log_tx1,
     )
     .await;
@@ -216,6 +227,7 @@ async fn test_multiple_concurrent_servers() {
             "@modelcontextprotocol/server-memory".to_string(),
         ],
         None,
+        &LaunchOptions::default(),
         log_tx2,
     )
     .await;
@@ -229,8 +241,9 @@ async fn test_multiple_concurrent_servers() {
     let process1 = result1.unwrap();
     let process2 = result2.unwrap();
 
-    // Give servers time to start
-    tokio::time::sleep(Duration::from_secs(3)).await;
+    // Handshake with both
+    let _ = timeout(Duration::from_secs(15), process1.initialize()).await;
+    let _ = timeout(Duration::from_secs(15), process2.initialize()).await;
 
     // Both should respond to list_tools
     let tools1 = timeout(Duration::from_secs(15), process1.list_tools()).await;
@@ -258,6 +271,7 @@ async fn test_process_kill() {
         "node".to_string(),
         vec!["-e".to_string(), script.to_string()],
         None,
+        &LaunchOptions::default(),
         log_tx,
     )
     .await;
@@ -291,6 +305,7 @@ async fn test_request_id_increments() {
             "@modelcontextprotocol/server-memory".to_string(),
         ],
         None,
+        &LaunchOptions::default(),
         log_tx,
     )
     .await;
@@ -301,7 +316,7 @@ async fn test_request_id_increments() {
     }
 
     let process = result.unwrap();
-    tokio::time::sleep(Duration::from_secs(2)).await;
+    let _ = timeout(Duration::from_secs(15), process.initialize()).await;
 
     // Make multiple requests
     let _ = timeout(Duration::from_secs(5), process.list_tools()).await;
@@ -329,6 +344,7 @@ async fn test_stderr_logging() {
         "node".to_string(),
         vec!["-e".to_string(), script.to_string()],
         None,
+        &LaunchOptions::default(),
         log_tx,
     )
     .await;
@@ -344,7 +360,7 @@ async fn test_stderr_logging() {
     // Check for stderr output
     let mut found_stderr = false;
     while let Ok(log) = log_rx.try_recv() {
-        if let ProcessLog::Stderr(msg) = log {
+        if let ProcessLog::Stderr(msg) = log.log {
             if msg.contains("error message") {
                 found_stderr = true;
                 break;